@@ -4,9 +4,9 @@ use num_complex::Complex;
 use num_traits::One;
 
 use au::{
-    plots::bode::Bode,
+    plots::{bode::Bode, export::Export},
     poly,
-    units::{RadiansPerSecond, ToDecibel},
+    units::{NormalizedFrequency, RadiansPerSecond, ToDecibel},
     Poly, Tf, Tfz,
 };
 
@@ -27,25 +27,17 @@ fn main() {
 
     println!("\nBode Plot:");
     let b = Bode::new(tf, RadiansPerSecond(0.1), RadiansPerSecond(10.0), 0.1);
-    for g in b.into_iter().into_db_deg() {
-        println!(
-            "f: {:.3} rad/s, m: {:.3} dB, f: {:.1} °",
-            g.angular_frequency(),
-            g.magnitude(),
-            g.phase()
-        );
-    }
+    b.into_iter()
+        .into_db_deg()
+        .to_csv(std::io::stdout())
+        .unwrap();
 
     let k = 0.5;
     let tfz = Tfz::new(poly!(1. - k), poly!(-k, 1.));
     println!("\nDiscrete function T:\n{}\n", tfz);
-    let pz = Bode::new_discrete(tfz, RadiansPerSecond(0.01), 0.1);
-    for g in pz.into_iter().into_db_deg() {
-        println!(
-            "f: {:.3} rad/s, m: {:.3} dB, f: {:.1} °",
-            g.angular_frequency(),
-            g.magnitude(),
-            g.phase()
-        );
-    }
+    let pz = Bode::new_discrete(tfz, NormalizedFrequency(0.01), 0.1);
+    pz.into_iter()
+        .into_db_deg()
+        .to_csv(std::io::stdout())
+        .unwrap();
 }
@@ -0,0 +1,330 @@
+//! # Autotuning of PID controllers
+//!
+//! Tuning of a PID controller from the recorded samples of an open loop step
+//! test. The samples are fitted with a first order plus dead time (FOPDT)
+//! model
+//! ```text
+//!        k * exp(-theta*s)
+//! G(s) = -----------------
+//!           1 + tau*s
+//! ```
+//! with the two point method: the time constant and the dead time are
+//! calculated from the times when the output reaches 28.3% and 63.2% of its
+//! change. The selected [tuning rule](../tuning/index.html) is applied to the
+//! fitted model.
+
+use num_traits::Float;
+
+use crate::{
+    controller::{pid::Pid, tuning::PidStructure},
+    error::{Error, ErrorKind},
+    polynomial::Poly,
+    transfer_function::continuous::Tf,
+};
+
+/// First order plus dead time model `k * exp(-theta*s) / (1 + tau*s)`
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde-serialize",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub struct Fopdt<T: Float> {
+    /// Static gain
+    k: T,
+    /// Time constant
+    tau: T,
+    /// Dead time
+    theta: T,
+}
+
+/// Tuning rule applied to the fitted model
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde-serialize",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub enum TuningRule<T: Float> {
+    /// Ziegler-Nichols open loop step response method
+    ZieglerNichols(PidStructure),
+    /// Cohen-Coon method
+    CohenCoon(PidStructure),
+    /// Skogestad SIMC method for PI controllers, with the desired closed loop
+    /// time constant
+    Simc(T),
+}
+
+impl<T: Float> Fopdt<T> {
+    /// Create a first order plus dead time model.
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - Static gain
+    /// * `tau` - Time constant
+    /// * `theta` - Dead time
+    ///
+    /// # Panics
+    ///
+    /// Panics if the gain is null, the time constant is not positive or the
+    /// dead time is negative.
+    #[must_use]
+    pub fn new(k: T, tau: T, theta: T) -> Self {
+        assert!(k != T::zero(), "Gain must not be null");
+        assert!(tau > T::zero(), "Time constant must be positive");
+        assert!(theta >= T::zero(), "Dead time must not be negative");
+        Self { k, tau, theta }
+    }
+
+    /// Fit the model on the samples of an open loop step response.
+    ///
+    /// The step is applied at the time of the first sample, whose output is
+    /// the initial value; the final value is the mean of the last 10% of
+    /// the samples.
+    ///
+    /// # Arguments
+    ///
+    /// * `samples` - Time (seconds) and output samples of the step response
+    /// * `step` - Amplitude of the input step
+    ///
+    /// # Errors
+    ///
+    /// It returns an error if there are less than three samples, the time is
+    /// not strictly increasing, the output does not change or does not
+    /// reach 63.2% of its change, or if the amplitude of the step is null or
+    /// not finite.
+    ///
+    /// # Example
+    /// ```
+    /// use au::controller::autotune::Fopdt;
+    /// let samples: Vec<(f64, f64)> = (0..1000)
+    ///     .map(|i| {
+    ///         let t = f64::from(i) * 0.05;
+    ///         (t, if t > 2. { 3. * (1. - (-(t - 2.) / 4.).exp()) } else { 0. })
+    ///     })
+    ///     .collect();
+    /// let model = Fopdt::fit(&samples, 1.).unwrap();
+    /// assert!((model.time_constant() - 4.).abs() < 1e-2);
+    /// assert!((model.dead_time() - 2.).abs() < 1e-2);
+    /// ```
+    pub fn fit(samples: &[(T, T)], step: T) -> Result<Self, Error> {
+        let insufficient = || Error::new_internal(ErrorKind::InsufficientData);
+        if samples.len() < 3 || samples.windows(2).any(|w| w[1].0 <= w[0].0) {
+            return Err(insufficient());
+        }
+        let (t0, y0) = samples[0];
+        let tail = (samples.len() / 10).max(1);
+        let yf = samples[samples.len() - tail..]
+            .iter()
+            .fold(T::zero(), |acc, s| acc + s.1)
+            / T::from(tail).unwrap();
+        let delta = yf - y0;
+        if delta == T::zero() || !delta.is_finite() {
+            return Err(insufficient());
+        }
+        // Normalized output, it goes from 0 to 1.
+        let norm = |v: T| (v - y0) / delta;
+        let crossing = |level: T| {
+            let k = samples.iter().position(|s| norm(s.1) >= level)?;
+            if k == 0 {
+                return Some(t0);
+            }
+            // Linear interpolation between the samples.
+            let ((ta, ya), (tb, yb)) = (samples[k - 1], samples[k]);
+            let (na, nb) = (norm(ya), norm(yb));
+            Some(ta + (tb - ta) * (level - na) / (nb - na))
+        };
+        // Output of the model at theta + tau/3 and theta + tau.
+        let t28 = crossing(T::one() - (-T::one() / T::from(3.).unwrap()).exp())
+            .ok_or_else(insufficient)?;
+        let t63 = crossing(T::one() - (-T::one()).exp()).ok_or_else(insufficient)?;
+        let tau = T::from(1.5).unwrap() * (t63 - t28);
+        if tau <= T::zero() {
+            return Err(insufficient());
+        }
+        let theta = (t63 - t0 - tau).max(T::zero());
+        let k = delta / step;
+        if k == T::zero() || !k.is_finite() {
+            return Err(Error::new_internal(ErrorKind::InvalidData));
+        }
+        Ok(Self::new(k, tau, theta))
+    }
+
+    /// Get the static gain
+    #[must_use]
+    pub fn gain(&self) -> T {
+        self.k
+    }
+
+    /// Get the time constant
+    #[must_use]
+    pub fn time_constant(&self) -> T {
+        self.tau
+    }
+
+    /// Get the dead time
+    #[must_use]
+    pub fn dead_time(&self) -> T {
+        self.theta
+    }
+
+    /// Transfer function of the model without the dead time
+    #[must_use]
+    pub fn tf(&self) -> Tf<T> {
+        Tf::new(
+            Poly::new_from_coeffs(&[self.k]),
+            Poly::new_from_coeffs(&[T::one(), self.tau]),
+        )
+    }
+
+    /// Tune a PID controller on the model.
+    ///
+    /// # Arguments
+    ///
+    /// * `rule` - Tuning rule
+    ///
+    /// # Errors
+    ///
+    /// It returns an error if the rule requires a positive dead time
+    /// (Ziegler-Nichols and Cohen-Coon) and the dead time of the model is
+    /// null, or if the closed loop time constant of the SIMC rule is not
+    /// positive.
+    pub fn tune(&self, rule: TuningRule<T>) -> Result<Pid<T>, Error> {
+        let (k, tau, theta) = (self.k, self.tau, self.theta);
+        match rule {
+            TuningRule::ZieglerNichols(_) | TuningRule::CohenCoon(_) if theta == T::zero() => {
+                Err(Error::new_internal(ErrorKind::UnfeasibleSpecification))
+            }
+            TuningRule::Simc(tau_c) if tau_c <= T::zero() || tau_c.is_nan() => {
+                Err(Error::new_internal(ErrorKind::UnfeasibleSpecification))
+            }
+            TuningRule::ZieglerNichols(s) => Ok(Pid::tune_ziegler_nichols_step(k, tau, theta, s)),
+            TuningRule::CohenCoon(s) => Ok(Pid::tune_cohen_coon(k, tau, theta, s)),
+            TuningRule::Simc(tau_c) => Ok(Pid::tune_simc(k, tau, theta, tau_c)),
+        }
+    }
+}
+
+/// Tune a PID controller from the samples of an open loop step response.
+///
+/// The samples are fitted with a first order plus dead time model, then the
+/// tuning rule is applied to the model.
+///
+/// # Arguments
+///
+/// * `samples` - Time (seconds) and output samples of the step response
+/// * `step` - Amplitude of the input step
+/// * `rule` - Tuning rule
+///
+/// # Errors
+///
+/// It returns an error if the samples are not sufficient to fit the model or
+/// the rule cannot be applied to it, see `Fopdt::fit` and `Fopdt::tune`.
+///
+/// # Example
+/// ```
+/// use au::controller::{autotune::{autotune, TuningRule}, tuning::PidStructure};
+/// // Step response of 1/(1+s)^2
+/// let samples: Vec<(f64, f64)> = (0..500)
+///     .map(|i| {
+///         let t = f64::from(i) * 0.02;
+///         (t, 1. - (1. + t) * (-t).exp())
+///     })
+///     .collect();
+/// let (model, pid) = autotune(&samples, 1., TuningRule::ZieglerNichols(PidStructure::Pi)).unwrap();
+/// assert!(model.dead_time() > 0.);
+/// assert!(pid.kp() > 0.);
+/// ```
+pub fn autotune<T: Float>(
+    samples: &[(T, T)],
+    step: T,
+    rule: TuningRule<T>,
+) -> Result<(Fopdt<T>, Pid<T>), Error> {
+    let model = Fopdt::fit(samples, step)?;
+    let pid = model.tune(rule)?;
+    Ok((model, pid))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Samples of the step response of a FOPDT plant.
+    fn fopdt_samples(k: f64, tau: f64, theta: f64, step: f64) -> Vec<(f64, f64)> {
+        (0..=4000)
+            .map(|i| {
+                let t = f64::from(i) * 0.01;
+                let y = if t > theta {
+                    k * step * (1. - (-(t - theta) / tau).exp())
+                } else {
+                    0.
+                };
+                (t, y + 1.)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn fit_fopdt() {
+        let samples = fopdt_samples(2., 3., 1.5, 0.5);
+        let model = Fopdt::fit(&samples, 0.5).unwrap();
+        assert_relative_eq!(2., model.gain(), max_relative = 1e-3);
+        assert_relative_eq!(3., model.time_constant(), max_relative = 1e-3);
+        assert_relative_eq!(1.5, model.dead_time(), max_relative = 1e-3);
+        assert_relative_eq!(2., model.tf().static_gain(), max_relative = 1e-3);
+    }
+
+    #[test]
+    fn fit_negative_step() {
+        let samples = fopdt_samples(-1., 2., 0.5, -3.);
+        let model = Fopdt::fit(&samples, -3.).unwrap();
+        assert_relative_eq!(-1., model.gain(), max_relative = 1e-3);
+        assert_relative_eq!(2., model.time_constant(), max_relative = 1e-3);
+        assert_relative_eq!(0.5, model.dead_time(), max_relative = 1e-2);
+    }
+
+    #[test]
+    fn fit_insufficient_data() {
+        let flat: Vec<_> = (0..10).map(|i| (f64::from(i), 1.)).collect();
+        let res = Fopdt::fit(&flat, 1.);
+        assert_eq!(ErrorKind::InsufficientData, res.unwrap_err().kind());
+
+        let res = Fopdt::fit(&[(0., 0.), (1., 1.)], 1.);
+        assert_eq!(ErrorKind::InsufficientData, res.unwrap_err().kind());
+
+        let res = Fopdt::fit(&[(0., 0.), (1., 1.), (1., 1.), (2., 1.)], 1.);
+        assert_eq!(ErrorKind::InsufficientData, res.unwrap_err().kind());
+
+        let samples = fopdt_samples(2., 3., 1.5, 1.);
+        let res = Fopdt::fit(&samples, 0.);
+        assert_eq!(ErrorKind::InvalidData, res.unwrap_err().kind());
+    }
+
+    #[test]
+    fn autotune_rules() {
+        let samples = fopdt_samples(2., 3., 1.5, 1.);
+        for &rule in &[
+            TuningRule::ZieglerNichols(PidStructure::Pid),
+            TuningRule::CohenCoon(PidStructure::Pi),
+            TuningRule::Simc(1.5),
+        ] {
+            let (model, pid) = autotune(&samples, 1., rule).unwrap();
+            assert_eq!(model.tune(rule).unwrap(), pid);
+        }
+        let (_, pid) = autotune(&samples, 1., TuningRule::Simc(1.5)).unwrap();
+        let expected = Pid::tune_simc(2., 3., 1.5, 1.5);
+        assert_relative_eq!(expected.kp(), pid.kp(), max_relative = 1e-3);
+        assert_relative_eq!(expected.ti(), pid.ti(), max_relative = 1e-3);
+    }
+
+    #[test]
+    fn autotune_no_dead_time() {
+        let samples = fopdt_samples(1., 2., 0., 1.);
+        let (model, pi) = autotune(&samples, 1., TuningRule::Simc(1.)).unwrap();
+        assert!(model.dead_time() < 1e-2);
+        assert!(pi.kp() > 0.);
+        let model = Fopdt::new(1., 2., 0.);
+        let res = model.tune(TuningRule::CohenCoon(PidStructure::Pid));
+        assert_eq!(ErrorKind::UnfeasibleSpecification, res.unwrap_err().kind());
+        let res = autotune(&samples, 1., TuningRule::Simc(0.));
+        assert_eq!(ErrorKind::UnfeasibleSpecification, res.unwrap_err().kind());
+    }
+}
@@ -0,0 +1,408 @@
+//! # Nonlinear blocks
+//!
+//! Static and dynamic nonlinearities executed in a sampled loop, usually
+//! inserted between the controller and the plant to model the actuator.
+//! * saturation
+//! * rate limiter
+//! * dead zone
+//!
+//! All blocks share the [`Block`](trait.Block.html) interface and can be
+//! connected in series.
+
+use num_traits::Float;
+
+use crate::units::Seconds;
+
+/// Sampled block that transforms an input value into an output value
+pub trait Block<T> {
+    /// Calculate the output of the block for the current input sample.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - Input sample
+    fn step(&mut self, input: T) -> T;
+
+    /// Reset the internal state of the block, if any.
+    fn reset(&mut self) {}
+
+    /// Connect the output of this block to the input of another block.
+    ///
+    /// # Arguments
+    ///
+    /// * `next` - Block that follows this one
+    ///
+    /// # Example
+    /// ```
+    /// use au::{controller::blocks::{Block, DeadZone, Saturation}};
+    /// let mut actuator = DeadZone::new(-0.5, 0.5).chain(Saturation::new(-1., 1.));
+    /// assert_eq!(0., actuator.step(0.3));
+    /// assert_eq!(1., actuator.step(3.));
+    /// ```
+    fn chain<B: Block<T>>(self, next: B) -> Series<Self, B>
+    where
+        Self: Sized,
+    {
+        Series {
+            first: self,
+            second: next,
+        }
+    }
+}
+
+impl<T, B: Block<T> + ?Sized> Block<T> for &mut B {
+    fn step(&mut self, input: T) -> T {
+        (**self).step(input)
+    }
+
+    fn reset(&mut self) {
+        (**self).reset();
+    }
+}
+
+impl<T, B: Block<T> + ?Sized> Block<T> for Box<B> {
+    fn step(&mut self, input: T) -> T {
+        (**self).step(input)
+    }
+
+    fn reset(&mut self) {
+        (**self).reset();
+    }
+}
+
+/// Series connection of two blocks
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde-serialize",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub struct Series<A, B> {
+    /// Block that receives the input
+    first: A,
+    /// Block that produces the output
+    second: B,
+}
+
+impl<T, A: Block<T>, B: Block<T>> Block<T> for Series<A, B> {
+    fn step(&mut self, input: T) -> T {
+        let intermediate = self.first.step(input);
+        self.second.step(intermediate)
+    }
+
+    fn reset(&mut self) {
+        self.first.reset();
+        self.second.reset();
+    }
+}
+
+/// Saturation of the input between a lower and an upper limit
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde-serialize",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub struct Saturation<T: Float> {
+    /// Lower limit
+    #[cfg_attr(feature = "serde-serialize", serde(with = "crate::serde_float"))]
+    min: T,
+    /// Upper limit
+    #[cfg_attr(feature = "serde-serialize", serde(with = "crate::serde_float"))]
+    max: T,
+}
+
+impl<T: Float> Saturation<T> {
+    /// Create a saturation block.
+    ///
+    /// # Arguments
+    ///
+    /// * `min` - Lower limit
+    /// * `max` - Upper limit
+    ///
+    /// # Panics
+    ///
+    /// Panics if the lower limit is greater than the upper limit.
+    ///
+    /// # Example
+    /// ```
+    /// use au::controller::blocks::{Block, Saturation};
+    /// let mut sat = Saturation::new(-1., 2.);
+    /// assert_eq!(2., sat.step(5.));
+    /// ```
+    #[must_use]
+    pub fn new(min: T, max: T) -> Self {
+        assert!(
+            min <= max,
+            "Lower limit must not be greater than upper limit"
+        );
+        Self { min, max }
+    }
+
+    /// Get the limits of the saturation as `(min, max)`
+    #[must_use]
+    pub fn limits(&self) -> (T, T) {
+        (self.min, self.max)
+    }
+}
+
+impl<T: Float> Block<T> for Saturation<T> {
+    fn step(&mut self, input: T) -> T {
+        input.max(self.min).min(self.max)
+    }
+}
+
+/// Limitation of the rate of change of the input
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde-serialize",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub struct RateLimiter<T: Float> {
+    /// Maximum increment for each sample
+    #[cfg_attr(feature = "serde-serialize", serde(with = "crate::serde_float"))]
+    rising: T,
+    /// Maximum decrement for each sample
+    #[cfg_attr(feature = "serde-serialize", serde(with = "crate::serde_float"))]
+    falling: T,
+    /// Initial output
+    initial: Option<T>,
+    /// Output of the previous sample
+    previous: Option<T>,
+}
+
+impl<T: Float> RateLimiter<T> {
+    /// Create a rate limiter block.
+    ///
+    /// The first sample passes unchanged, unless an initial output is set with
+    /// `with_initial`.
+    ///
+    /// # Arguments
+    ///
+    /// * `rising` - Maximum rising rate (units per second)
+    /// * `falling` - Maximum falling rate (units per second), positive value
+    /// * `st` - Sample time
+    ///
+    /// # Panics
+    ///
+    /// Panics if the rates are negative or the sample time is not positive.
+    ///
+    /// # Example
+    /// ```
+    /// use au::{controller::blocks::{Block, RateLimiter}, Seconds};
+    /// let mut rl = RateLimiter::new(1., 2., Seconds(0.5)).with_initial(0.);
+    /// assert_eq!(0.5, rl.step(10.));
+    /// assert_eq!(1., rl.step(10.));
+    /// assert_eq!(0., rl.step(-10.));
+    /// ```
+    #[must_use]
+    pub fn new(rising: T, falling: T, st: Seconds<T>) -> Self {
+        assert!(st.0 > T::zero(), "Sample time must be positive");
+        assert!(
+            rising >= T::zero() && falling >= T::zero(),
+            "Rates must not be negative"
+        );
+        Self {
+            rising: rising * st.0,
+            falling: falling * st.0,
+            initial: None,
+            previous: None,
+        }
+    }
+
+    /// Set the output of the block before the first sample.
+    ///
+    /// # Arguments
+    ///
+    /// * `initial` - Initial output
+    #[must_use]
+    pub fn with_initial(mut self, initial: T) -> Self {
+        self.initial = Some(initial);
+        self.previous = Some(initial);
+        self
+    }
+}
+
+impl<T: Float> Block<T> for RateLimiter<T> {
+    fn step(&mut self, input: T) -> T {
+        let output = match self.previous {
+            Some(prev) => input.max(prev - self.falling).min(prev + self.rising),
+            None => input,
+        };
+        self.previous = Some(output);
+        output
+    }
+
+    fn reset(&mut self) {
+        self.previous = self.initial;
+    }
+}
+
+/// Dead zone of the input: the output is null inside the zone, outside of it
+/// the output is the input shifted by the closest limit of the zone.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde-serialize",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub struct DeadZone<T: Float> {
+    /// Lower limit of the dead zone
+    lower: T,
+    /// Upper limit of the dead zone
+    upper: T,
+}
+
+impl<T: Float> DeadZone<T> {
+    /// Create a dead zone block.
+    ///
+    /// # Arguments
+    ///
+    /// * `lower` - Lower limit of the dead zone
+    /// * `upper` - Upper limit of the dead zone
+    ///
+    /// # Panics
+    ///
+    /// Panics if the lower limit is greater than the upper limit.
+    ///
+    /// # Example
+    /// ```
+    /// use au::controller::blocks::{Block, DeadZone};
+    /// let mut dz = DeadZone::new(-1., 1.);
+    /// assert_eq!(0., dz.step(0.5));
+    /// assert_eq!(2., dz.step(3.));
+    /// assert_eq!(-1., dz.step(-2.));
+    /// ```
+    #[must_use]
+    pub fn new(lower: T, upper: T) -> Self {
+        assert!(
+            lower <= upper,
+            "Lower limit must not be greater than upper limit"
+        );
+        Self { lower, upper }
+    }
+
+    /// Get the limits of the dead zone as `(lower, upper)`
+    #[must_use]
+    pub fn limits(&self) -> (T, T) {
+        (self.lower, self.upper)
+    }
+}
+
+impl<T: Float> Block<T> for DeadZone<T> {
+    fn step(&mut self, input: T) -> T {
+        if input > self.upper {
+            input - self.upper
+        } else if input < self.lower {
+            input - self.lower
+        } else {
+            T::zero()
+        }
+    }
+}
+
+/// Implementation of the `Controller` interface for the blocks, the input
+/// time is ignored.
+macro_rules! impl_controller {
+    ($t:ty $(, $g:ident)*) => {
+        impl<T: Float, $($g: Block<T>),*> crate::controller::Controller<T> for $t {
+            type Input = T;
+            type Output = T;
+
+            fn step(&mut self, _time: Seconds<T>, input: T) -> T {
+                Block::step(self, input)
+            }
+
+            fn reset(&mut self) {
+                Block::reset(self);
+            }
+        }
+    };
+}
+
+impl_controller!(Series<A, B>, A, B);
+impl_controller!(Saturation<T>);
+impl_controller!(RateLimiter<T>);
+impl_controller!(DeadZone<T>);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn saturation() {
+        let mut sat = Saturation::new(-1., 1.);
+        assert_eq!((-1., 1.), sat.limits());
+        let out: Vec<_> = [-3., -0.5, 0.5, 3.].iter().map(|&u| sat.step(u)).collect();
+        assert_eq!(vec![-1., -0.5, 0.5, 1.], out);
+    }
+
+    #[test]
+    #[should_panic]
+    fn saturation_wrong_limits() {
+        let _ = Saturation::new(1., -1.);
+    }
+
+    #[test]
+    fn rate_limiter() {
+        let mut rl = RateLimiter::new(2., 1., Seconds(0.1));
+        // First sample is not limited.
+        assert_relative_eq!(5., rl.step(5.));
+        assert_relative_eq!(5.2, rl.step(10.));
+        assert_relative_eq!(5.1, rl.step(0.));
+        assert_relative_eq!(5.12, rl.step(5.12));
+        rl.reset();
+        assert_relative_eq!(-3., rl.step(-3.));
+    }
+
+    #[test]
+    fn rate_limiter_ramp() {
+        let mut rl = RateLimiter::new(1., 1., Seconds(0.1)).with_initial(0.);
+        let out: Vec<_> = (0..20).map(|_| rl.step(1.)).collect();
+        assert_relative_eq!(0.5, out[4], max_relative = 1e-12);
+        assert_relative_eq!(1., out[19]);
+        rl.reset();
+        assert_relative_eq!(0.1, rl.step(1.));
+    }
+
+    #[test]
+    fn dead_zone() {
+        let mut dz = DeadZone::new(-0.5, 1.);
+        assert_eq!((-0.5, 1.), dz.limits());
+        let out: Vec<_> = [-2., -0.5, 0., 1., 3.]
+            .iter()
+            .map(|&u| dz.step(u))
+            .collect();
+        assert_eq!(vec![-1.5, 0., 0., 0., 2.], out);
+    }
+
+    #[test]
+    fn series() {
+        let mut actuator =
+            Saturation::new(-1., 1.).chain(RateLimiter::new(1., 1., Seconds(0.5)).with_initial(0.));
+        assert_relative_eq!(0.5, actuator.step(3.));
+        assert_relative_eq!(1., actuator.step(3.));
+        assert_relative_eq!(1., actuator.step(3.));
+        actuator.reset();
+        assert_relative_eq!(-0.5, actuator.step(-3.));
+
+        let mut boxed: Vec<Box<dyn Block<f64>>> = vec![
+            Box::new(DeadZone::new(-0.1, 0.1)),
+            Box::new(Saturation::new(0., 2.)),
+        ];
+        let out = boxed.iter_mut().fold(5., |u, b| b.step(u));
+        assert_relative_eq!(2., out);
+    }
+
+    #[cfg(feature = "serde-serialize")]
+    #[test]
+    fn serde_round_trip() {
+        let sat = Saturation::new(f64::NEG_INFINITY, 1.);
+        let json = serde_json::to_string(&sat).unwrap();
+        assert_eq!(sat, serde_json::from_str(&json).unwrap());
+
+        let rl = RateLimiter::new(f32::INFINITY, 1., Seconds(0.1)).with_initial(0.);
+        let json = serde_json::to_string(&rl).unwrap();
+        assert_eq!(rl, serde_json::from_str(&json).unwrap());
+
+        let actuator = DeadZone::new(-0.5, 0.5).chain(Saturation::new(-1., 1.));
+        let json = serde_json::to_string(&actuator).unwrap();
+        assert_eq!(actuator, serde_json::from_str(&json).unwrap());
+    }
+}
@@ -0,0 +1,213 @@
+//! # Deadbeat controller
+//!
+//! Controller for stable discrete plants `G(z) = B(z)/A(z)` that brings the
+//! error of the step response of the closed loop to zero in a finite
+//! number of samples.
+//!
+//! The standard design gives the closed loop `z^-d`, where `d` is the
+//! relative degree of the plant; it cancels the zeros of the plant, which
+//! shall be inside the unit circle, and the control action can oscillate
+//! between the samples (ripple).
+//!
+//! The ripple free design gives the closed loop `B(z)/(B(1)*z^n)`, where `n`
+//! is the degree of `A(z)`; the zeros of the plant are not cancelled and the
+//! control action is constant after `n` samples, at the cost of a longer
+//! response.
+
+use nalgebra::RealField;
+use num_traits::{Float, Zero};
+
+use crate::{
+    error::{Error, ErrorKind},
+    polynomial::Poly,
+    transfer_function::discrete::Tfz,
+};
+
+/// Deadbeat controller
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde-serialize",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub struct Deadbeat<T: Float> {
+    /// Controller transfer function
+    controller: Tfz<T>,
+    /// Number of samples of the step response of the closed loop
+    length: usize,
+}
+
+impl<T: Float> Deadbeat<T> {
+    /// Get the controller transfer function
+    #[must_use]
+    pub fn controller(&self) -> &Tfz<T> {
+        &self.controller
+    }
+
+    /// Get the number of samples after which the output of the closed loop
+    /// is equal to the step reference
+    #[must_use]
+    pub fn length(&self) -> usize {
+        self.length
+    }
+}
+
+/// Design the deadbeat controller of the discrete plant.
+///
+/// # Arguments
+///
+/// * `plant` - Discrete plant
+/// * `ripple_free` - Do not cancel the plant zeros, so that the control
+///   action is constant at the end of the response
+///
+/// # Errors
+///
+/// It returns an error if the plant is not proper, it has poles on or
+/// outside the unit circle, it has a null static gain, or, for the
+/// standard design, it has zeros on or outside the unit circle or a null
+/// relative degree (no delay between input and output).
+///
+/// The ripple free design returns an error if the denominator of the
+/// controller is null.
+///
+/// # Example
+/// ```
+/// use au::{controller::deadbeat::deadbeat, poly, Tfz};
+/// let plant = Tfz::new(poly!(0.5), poly!(-0.5, 1.));
+/// let db = deadbeat(&plant, false).unwrap();
+/// assert_eq!(1, db.length());
+/// let closed_loop = (&plant * db.controller()).feedback_n();
+/// let step: Vec<_> = closed_loop.arma_iter(std::iter::repeat(1.)).take(4).collect();
+/// assert_eq!(vec![0., 1., 1., 1.], step);
+/// ```
+pub fn deadbeat<T: Float + RealField>(
+    plant: &Tfz<T>,
+    ripple_free: bool,
+) -> Result<Deadbeat<T>, Error> {
+    let plant = plant.normalize();
+    let (b, a) = (plant.num(), plant.den());
+    let n = a.degree().unwrap_or(0);
+    let m = b
+        .degree()
+        .ok_or_else(|| Error::new_internal(ErrorKind::UnfeasibleSpecification))?;
+    if m > n {
+        return Err(Error::new_internal(ErrorKind::ImproperTransferFunction));
+    }
+    if plant.complex_poles().iter().any(|p| p.norm() >= T::one()) {
+        return Err(Error::new_internal(ErrorKind::UnstableSystem));
+    }
+    let b1 = b.eval_by_val(T::one());
+    if b1 == T::zero() {
+        return Err(Error::new_internal(ErrorKind::UnfeasibleSpecification));
+    }
+
+    let z_pow = |k: usize| {
+        let mut coeffs = vec![T::zero(); k + 1];
+        coeffs[k] = T::one();
+        Poly::new_from_coeffs(&coeffs)
+    };
+    let (den, length) = if ripple_free {
+        // C = A / (B(1)*z^n - B)
+        (&(z_pow(n) * b1) - b, n)
+    } else {
+        if plant.complex_zeros().iter().any(|z| z.norm() >= T::one()) {
+            return Err(Error::new_internal(ErrorKind::UnfeasibleSpecification));
+        }
+        // C = A / (B*(z^d - 1))
+        let d = n - m;
+        if d == 0 {
+            return Err(Error::new_internal(ErrorKind::UnfeasibleSpecification));
+        }
+        (b * &(z_pow(d) - T::one()), d)
+    };
+    if den.is_zero() {
+        return Err(Error::new_internal(ErrorKind::UnfeasibleSpecification));
+    }
+    Ok(Deadbeat {
+        controller: Tfz::new(a.clone(), den),
+        length,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::poly;
+
+    /// Step response of the closed loop and control action.
+    fn responses(plant: &Tfz<f64>, db: &Deadbeat<f64>, len: usize) -> (Vec<f64>, Vec<f64>) {
+        let l = plant * db.controller();
+        let y = l
+            .feedback_n()
+            .arma_iter(std::iter::repeat(1.))
+            .take(len)
+            .collect();
+        let q = Tfz::new(
+            db.controller().num() * plant.den(),
+            db.controller().num() * plant.num() + db.controller().den() * plant.den(),
+        );
+        let u = q.arma_iter(std::iter::repeat(1.)).take(len).collect();
+        (y, u)
+    }
+
+    fn plant() -> Tfz<f64> {
+        // Zero in -0.8, poles in 0.2 and 0.6
+        Tfz::new(poly!(0.4, 0.5), poly!(0.12, -0.8, 1.))
+    }
+
+    #[test]
+    fn standard_design() {
+        let db = deadbeat(&plant(), false).unwrap();
+        assert_eq!(1, db.length());
+        let (y, u) = responses(&plant(), &db, 20);
+        assert_relative_eq!(0., y[0]);
+        for &v in &y[1..] {
+            assert_relative_eq!(1., v, max_relative = 1e-10);
+        }
+        // The cancelled zero in -0.8 gives a ripple of the control action.
+        let last = u[19];
+        assert!((u[10] - last).abs() > 1e-3);
+    }
+
+    #[test]
+    fn ripple_free_design() {
+        let db = deadbeat(&plant(), true).unwrap();
+        assert_eq!(2, db.length());
+        let (y, u) = responses(&plant(), &db, 20);
+        for &v in &y[2..] {
+            assert_relative_eq!(1., v, max_relative = 1e-10);
+        }
+        // Static gain of the plant 0.9/0.32
+        for &v in &u[2..] {
+            assert_relative_eq!(0.32 / 0.9, v, max_relative = 1e-10);
+        }
+    }
+
+    #[test]
+    fn non_minimum_phase_plant() {
+        let plant = Tfz::new(poly!(-1.5, 1.), poly!(0., -0.5, 1.));
+        let res = deadbeat(&plant, false);
+        assert_eq!(ErrorKind::UnfeasibleSpecification, res.unwrap_err().kind());
+        let db = deadbeat(&plant, true).unwrap();
+        let (y, _) = responses(&plant, &db, 10);
+        for &v in &y[2..] {
+            assert_relative_eq!(1., v, max_relative = 1e-10);
+        }
+    }
+
+    #[test]
+    fn null_relative_degree() {
+        let plant = Tfz::new(poly!(0., 0.5), poly!(-0.5, 1.));
+        let res = deadbeat(&plant, false);
+        assert_eq!(ErrorKind::UnfeasibleSpecification, res.unwrap_err().kind());
+        // B(1)*z - B = 0.5*z - 0.5*z
+        let res = deadbeat(&plant, true);
+        assert_eq!(ErrorKind::UnfeasibleSpecification, res.unwrap_err().kind());
+    }
+
+    #[test]
+    fn unstable_plant() {
+        let plant = Tfz::new(poly!(1.), poly!(-1.5, 1.));
+        let res = deadbeat(&plant, true);
+        assert_eq!(ErrorKind::UnstableSystem, res.unwrap_err().kind());
+    }
+}
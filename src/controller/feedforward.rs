@@ -0,0 +1,304 @@
+//! # Feedforward
+//!
+//! Feedforward path `F(s)`, whose output is added to the output of the
+//! feedback controller `R(s)`. The input of the path is the reference or a
+//! measured disturbance.
+//!
+//! ```text
+//!          v   +------+
+//!     +------->| F(s) |--------+
+//!     |        +------+        |
+//!     |           +------+   + v +   +------+
+//! r --+---->o---->| R(s) |------>o-->| G(s) |---+--> y
+//!         + ^ -   +------+           +------+   |
+//!           |                                   |
+//!           +-----------------------------------+
+//! ```
+//!
+//! The feedforward path can be analyzed with the plant and the feedback
+//! controller in continuous time and executed in discrete time together with
+//! a [discrete PID](../pid/struct.DiscretePid.html).
+
+use num_traits::Float;
+
+use std::collections::VecDeque;
+
+use crate::{
+    enums::Discretization,
+    polynomial::Poly,
+    transfer_function::{continuous::Tf, discrete::Tfz},
+    units::Seconds,
+};
+
+/// Feedforward path
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde-serialize",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub struct Feedforward<T: Float> {
+    /// Transfer function of the path
+    tf: Tf<T>,
+}
+
+impl<T: Float> Feedforward<T> {
+    /// Create a feedforward path from its transfer function
+    ///
+    /// # Arguments
+    ///
+    /// * `tf` - Transfer function of the path
+    #[must_use]
+    pub fn new(tf: Tf<T>) -> Self {
+        Self { tf }
+    }
+
+    /// Create a static feedforward path
+    ///
+    /// # Arguments
+    ///
+    /// * `gain` - Gain of the path
+    ///
+    /// # Example
+    /// ```
+    /// use au::{controller::feedforward::Feedforward, poly, Tf};
+    /// let ff = Feedforward::new_gain(2.);
+    /// assert_eq!(&Tf::new(poly!(2.), poly!(1.)), ff.tf());
+    /// ```
+    #[must_use]
+    pub fn new_gain(gain: T) -> Self {
+        Self {
+            tf: Tf::new(
+                Poly::new_from_coeffs(&[gain]),
+                Poly::new_from_coeffs(&[T::one()]),
+            ),
+        }
+    }
+
+    /// Get the transfer function of the path
+    #[must_use]
+    pub fn tf(&self) -> &Tf<T> {
+        &self.tf
+    }
+
+    /// Transfer function from the reference to the plant output, when the
+    /// input of the path is the reference.
+    /// ```text
+    ///        G(s)*(R(s) + F(s))
+    /// Y/r = -------------------
+    ///          1 + G(s)*R(s)
+    /// ```
+    ///
+    /// # Arguments
+    ///
+    /// * `plant` - Plant `G(s)`
+    /// * `controller` - Feedback controller `R(s)`
+    ///
+    /// # Example
+    /// ```
+    /// use au::{controller::{feedforward::Feedforward, pid::Pid}, poly, Tf};
+    /// let g = Tf::new(poly!(1.), poly!(1., 1.));
+    /// let r = Pid::new_ideal(2., 1., 0.).tf();
+    /// let ff = Feedforward::new_gain(1.);
+    /// let t = ff.reference_response(&g, &r);
+    /// assert!((t.static_gain() - 1_f64).abs() < 1e-12);
+    /// ```
+    #[must_use]
+    pub fn reference_response(&self, plant: &Tf<T>, controller: &Tf<T>) -> Tf<T> {
+        let gang = plant.gang_of_four(controller);
+        gang.t() + &(gang.gs() * &self.tf)
+    }
+
+    /// Transfer function from the measured disturbance to the plant output,
+    /// when the input of the path is the disturbance.
+    /// ```text
+    ///        D(s) + G(s)*F(s)
+    /// Y/v = -----------------
+    ///         1 + G(s)*R(s)
+    /// ```
+    /// The disturbance is compensated when `F(s) = -D(s)/G(s)`.
+    ///
+    /// # Arguments
+    ///
+    /// * `plant` - Plant `G(s)`
+    /// * `disturbance` - Transfer function `D(s)` from the disturbance to the plant output
+    /// * `controller` - Feedback controller `R(s)`
+    #[must_use]
+    pub fn disturbance_response(
+        &self,
+        plant: &Tf<T>,
+        disturbance: &Tf<T>,
+        controller: &Tf<T>,
+    ) -> Tf<T> {
+        let gang = plant.gang_of_four(controller);
+        &(gang.s() * disturbance) + &(gang.gs() * &self.tf)
+    }
+
+    /// Discretize the feedforward path to be executed in a sampled loop.
+    ///
+    /// # Arguments
+    ///
+    /// * `ts` - Sampling period
+    /// * `method` - Discretization method
+    ///
+    /// # Panics
+    ///
+    /// Panics if the discretized transfer function is not causal.
+    #[must_use]
+    pub fn discretize(&self, ts: Seconds<T>, method: Discretization) -> DiscreteFeedforward<T> {
+        DiscreteFeedforward::new(&self.tf.discretize(ts, method))
+    }
+}
+
+/// Discrete feedforward path with internal state
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde-serialize",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub struct DiscreteFeedforward<T: Float> {
+    /// Numerator coefficients of the normalized transfer function
+    num: Vec<T>,
+    /// Denominator coefficients of the normalized transfer function,
+    /// excluding the leading one
+    den: Vec<T>,
+    /// Inputs `[u(k-n), ..., u(k)]`
+    inputs: VecDeque<T>,
+    /// Outputs `[y(k-n), ..., y(k-1)]`
+    outputs: VecDeque<T>,
+}
+
+impl<T: Float> DiscreteFeedforward<T> {
+    /// Create a discrete feedforward path with a null initial state
+    ///
+    /// # Arguments
+    ///
+    /// * `tfz` - Discrete transfer function of the path
+    ///
+    /// # Panics
+    ///
+    /// Panics if the transfer function is not causal.
+    ///
+    /// # Example
+    /// ```
+    /// use au::{controller::feedforward::DiscreteFeedforward, poly, Tfz};
+    /// let mut ff = DiscreteFeedforward::new(&Tfz::new(poly!(1.), poly!(-0.5, 1.)));
+    /// assert_eq!(0., ff.step(1.));
+    /// assert_eq!(1., ff.step(1.));
+    /// assert_eq!(1.5, ff.step(1.));
+    /// ```
+    #[must_use]
+    pub fn new(tfz: &Tfz<T>) -> Self {
+        let g = tfz.normalize();
+        let n = g.den().degree().unwrap_or(0);
+        assert!(
+            g.num().degree().unwrap_or(0) <= n,
+            "Feedforward transfer function must be causal"
+        );
+        let mut den = g.den().coeffs();
+        den.truncate(n);
+        Self {
+            num: g.num().coeffs(),
+            den,
+            inputs: VecDeque::from(vec![T::zero(); n + 1]),
+            outputs: VecDeque::from(vec![T::zero(); n]),
+        }
+    }
+
+    /// Calculate the output of the path for the current sample and update
+    /// its state.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - Input of the path, reference or measured disturbance
+    pub fn step(&mut self, input: T) -> T {
+        self.inputs.pop_front();
+        self.inputs.push_back(input);
+        let mov = self
+            .num
+            .iter()
+            .zip(&self.inputs)
+            .fold(T::zero(), |acc, (&b, &u)| acc + b * u);
+        let auto = self
+            .den
+            .iter()
+            .zip(&self.outputs)
+            .fold(T::zero(), |acc, (&a, &y)| acc + a * y);
+        let y = mov - auto;
+        if !self.outputs.is_empty() {
+            self.outputs.pop_front();
+            self.outputs.push_back(y);
+        }
+        y
+    }
+
+    /// Reset the state of the path to the initial null state.
+    pub fn reset(&mut self) {
+        self.inputs.iter_mut().for_each(|u| *u = T::zero());
+        self.outputs.iter_mut().for_each(|y| *y = T::zero());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{controller::pid::Pid, poly};
+    use num_complex::Complex64;
+
+    #[test]
+    fn reference_response() {
+        let g = Tf::new(poly!(1.), poly!(1., 1.));
+        let r = Pid::new_ideal(2., 1., 0.).tf();
+        let ff = Feedforward::new(Tf::new(poly!(1., 1.), poly!(1., 0.1)));
+        let t = ff.reference_response(&g, &r);
+        for &w in &[0.1, 1., 10.] {
+            let s = Complex64::new(0., w);
+            let (gs, rs, fs) = (g.eval(&s), r.eval(&s), ff.tf().eval(&s));
+            let expected = gs * (rs + fs) / (1. + gs * rs);
+            let actual = t.eval(&s);
+            assert_relative_eq!(expected.re, actual.re, max_relative = 1e-10);
+            assert_relative_eq!(expected.im, actual.im, max_relative = 1e-10);
+        }
+    }
+
+    #[test]
+    fn disturbance_compensation() {
+        let g = Tf::new(poly!(2.), poly!(1., 1.));
+        let d = Tf::new(poly!(1.), poly!(1., 1.));
+        let r = Pid::new_ideal(1., 1., 0.).tf();
+        let ff = Feedforward::new_gain(-0.5);
+        let y = ff.disturbance_response(&g, &d, &r);
+        for &w in &[0., 0.5, 5.] {
+            assert_relative_eq!(0., y.eval(&Complex64::new(0., w)).norm(), epsilon = 1e-12);
+        }
+        let y = Feedforward::new_gain(0.).disturbance_response(&g, &d, &r);
+        assert_eq!(g.gang_of_four(&r).s() * &d, y);
+    }
+
+    #[test]
+    fn discrete_execution() {
+        let ff = Feedforward::new(Tf::new(poly!(1.), poly!(1., 1.)));
+        let mut dff = ff.discretize(Seconds(0.1), Discretization::Tustin);
+        let tfz = ff.tf().discretize(Seconds(0.1), Discretization::Tustin);
+        let expected: Vec<_> = tfz.arma_iter(std::iter::repeat(1.)).take(100).collect();
+        let actual: Vec<_> = (0..100).map(|_| dff.step(1.)).collect();
+        for (e, a) in expected.iter().zip(&actual) {
+            assert_relative_eq!(e, a, max_relative = 1e-12);
+        }
+        dff.reset();
+        assert_relative_eq!(actual[0], dff.step(1.));
+    }
+
+    #[test]
+    fn static_discrete_path() {
+        let mut dff =
+            Feedforward::new_gain(3.).discretize(Seconds(1.), Discretization::BackwardEuler);
+        assert_relative_eq!(6., dff.step(2.));
+        assert_relative_eq!(-3., dff.step(-1.));
+    }
+
+    #[test]
+    #[should_panic]
+    fn non_causal_path() {
+        let _ = DiscreteFeedforward::new(&Tfz::new(poly!(0., 0., 1.), poly!(0.5, 1.)));
+    }
+}
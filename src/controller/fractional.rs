@@ -0,0 +1,337 @@
+//! # Fractional order PID controller
+//!
+//! PIλDμ controller with fractional integral and derivative actions
+//! ```text
+//! C(s) = kp + ki / s^lambda + kd * s^mu
+//! ```
+//! The fractional powers of `s` are realized with the Oustaloup rational
+//! approximation, accurate within a frequency band `[wb, wh]`:
+//! ```text
+//!                N   s + w'k
+//! s^a ≈ wh^a * prod  -------
+//!               k=-N  s + wk
+//!
+//! w'k = wb * (wh/wb)^((k + N + (1 - a)/2) / (2N + 1))
+//! wk  = wb * (wh/wb)^((k + N + (1 + a)/2) / (2N + 1))
+//! ```
+//! The integer part of the exponent is kept exact, only the fractional part
+//! is approximated.
+
+use num_traits::Float;
+
+use crate::{
+    controller::resonant::DiscreteController,
+    enums::Discretization,
+    polynomial::Poly,
+    transfer_function::continuous::Tf,
+    units::{RadiansPerSecond, Seconds},
+};
+
+/// Fractional order PID controller
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde-serialize",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub struct FractionalPid<T: Float> {
+    /// Proportional gain
+    kp: T,
+    /// Integral gain
+    ki: T,
+    /// Order of the integral action
+    lambda: T,
+    /// Derivative gain
+    kd: T,
+    /// Order of the derivative action
+    mu: T,
+}
+
+impl<T: Float> FractionalPid<T> {
+    /// Create a fractional order PID controller.
+    ///
+    /// # Arguments
+    ///
+    /// * `kp` - Proportional gain
+    /// * `ki` - Integral gain
+    /// * `lambda` - Order of the integral action
+    /// * `kd` - Derivative gain
+    /// * `mu` - Order of the derivative action
+    ///
+    /// # Panics
+    ///
+    /// Panics if the orders are negative.
+    ///
+    /// # Example
+    /// ```
+    /// use au::controller::fractional::FractionalPid;
+    /// let pid = FractionalPid::new(1., 2., 0.5, 0.1, 0.7);
+    /// assert_eq!(0.5, pid.lambda());
+    /// ```
+    #[must_use]
+    pub fn new(kp: T, ki: T, lambda: T, kd: T, mu: T) -> Self {
+        assert!(
+            lambda >= T::zero() && mu >= T::zero(),
+            "Orders must not be negative"
+        );
+        Self {
+            kp,
+            ki,
+            lambda,
+            kd,
+            mu,
+        }
+    }
+
+    /// Get the proportional gain
+    #[must_use]
+    pub fn kp(&self) -> T {
+        self.kp
+    }
+
+    /// Get the integral gain
+    #[must_use]
+    pub fn ki(&self) -> T {
+        self.ki
+    }
+
+    /// Get the order of the integral action
+    #[must_use]
+    pub fn lambda(&self) -> T {
+        self.lambda
+    }
+
+    /// Get the derivative gain
+    #[must_use]
+    pub fn kd(&self) -> T {
+        self.kd
+    }
+
+    /// Get the order of the derivative action
+    #[must_use]
+    pub fn mu(&self) -> T {
+        self.mu
+    }
+
+    /// Rational approximation of the transfer function of the controller,
+    /// the fractional powers of `s` are replaced by their Oustaloup
+    /// approximation.
+    ///
+    /// # Arguments
+    ///
+    /// * `band` - Lower and upper frequencies of the approximation
+    /// * `order` - Order of the approximation, `2*order + 1` zeros and poles
+    ///   for each fractional power
+    ///
+    /// # Panics
+    ///
+    /// Panics if the lower frequency is not positive or it is not lower than
+    /// the upper frequency.
+    ///
+    /// # Example
+    /// ```
+    /// use au::{controller::fractional::FractionalPid, num_complex::Complex, RadiansPerSecond};
+    /// let pid = FractionalPid::new(0., 1., 0.5_f64, 0., 0.);
+    /// let tf = pid.tf((RadiansPerSecond(0.01), RadiansPerSecond(100.)), 4);
+    /// // Phase of -45° at the center of the band.
+    /// let phase = tf.eval(&Complex::new(0., 1.)).arg().to_degrees();
+    /// assert!((phase + 45.).abs() < 1.);
+    /// ```
+    #[must_use]
+    pub fn tf(&self, band: (RadiansPerSecond<T>, RadiansPerSecond<T>), order: usize) -> Tf<T> {
+        let mut tf = Tf::new(
+            Poly::new_from_coeffs(&[self.kp]),
+            Poly::new_from_coeffs(&[T::one()]),
+        );
+        if self.ki != T::zero() {
+            tf = &tf + &(oustaloup(-self.lambda, band, order) * self.ki);
+        }
+        if self.kd != T::zero() {
+            tf = &tf + &(oustaloup(self.mu, band, order) * self.kd);
+        }
+        tf
+    }
+
+    /// Discrete controller with internal state, the rational approximation
+    /// of the controller (see `tf`) is discretized with the Tustin method.
+    ///
+    /// # Arguments
+    ///
+    /// * `band` - Lower and upper frequencies of the approximation
+    /// * `order` - Order of the approximation
+    /// * `ts` - Sampling period
+    ///
+    /// # Panics
+    ///
+    /// Panics if the frequency band is not valid or the sampling period is
+    /// not positive.
+    ///
+    /// # Example
+    /// ```
+    /// use au::{controller::fractional::FractionalPid, RadiansPerSecond, Seconds};
+    /// let pid = FractionalPid::new(1., 1., 0.8, 0., 0.);
+    /// let mut ctrl = pid.discretize((RadiansPerSecond(0.01), RadiansPerSecond(10.)), 3, Seconds(0.05));
+    /// let u: Vec<f64> = (0..100).map(|_| ctrl.step(1.)).collect();
+    /// // The integral action makes the control action grow.
+    /// assert!(u[99] > u[0]);
+    /// ```
+    #[must_use]
+    pub fn discretize(
+        &self,
+        band: (RadiansPerSecond<T>, RadiansPerSecond<T>),
+        order: usize,
+        ts: Seconds<T>,
+    ) -> DiscreteController<T> {
+        assert!(ts.0 > T::zero(), "Sampling period must be positive");
+        let tfz = self.tf(band, order).discretize(ts, Discretization::Tustin);
+        DiscreteController::new(&tfz)
+    }
+}
+
+/// Oustaloup rational approximation of `s^alpha`.
+///
+/// The integer part of `alpha` is exact, its fractional part is approximated
+/// with `2*order + 1` zeros and poles distributed in the frequency band.
+///
+/// # Arguments
+///
+/// * `alpha` - Exponent of `s`, negative for integrators
+/// * `band` - Lower and upper frequencies of the approximation
+/// * `order` - Order of the approximation
+///
+/// # Panics
+///
+/// Panics if the lower frequency is not positive or it is not lower than the
+/// upper frequency.
+///
+/// # Example
+/// ```
+/// use au::{controller::fractional::oustaloup, num_complex::Complex, RadiansPerSecond};
+/// let tf = oustaloup(0.5, (RadiansPerSecond(0.1), RadiansPerSecond(1000.)), 5);
+/// // |s^0.5| = 10 at 100 rad/s.
+/// let g: f64 = tf.eval(&Complex::new(0., 100.)).norm();
+/// assert!((g - 10.).abs() < 0.2);
+/// ```
+#[must_use]
+pub fn oustaloup<T: Float>(
+    alpha: T,
+    band: (RadiansPerSecond<T>, RadiansPerSecond<T>),
+    order: usize,
+) -> Tf<T> {
+    let (wb, wh) = (band.0 .0, band.1 .0);
+    assert!(
+        wb > T::zero() && wb < wh,
+        "Frequency band must be positive and increasing"
+    );
+    let integer = alpha.floor();
+    let fraction = alpha - integer;
+    let power = integer.abs().to_usize().unwrap();
+    // s^n, exact integer part of the exponent.
+    let mut s_n = vec![T::zero(); power];
+    s_n.push(T::one());
+    let s_n = Poly::new_from_coeffs(&s_n);
+
+    let (num, den) = if fraction == T::zero() {
+        (
+            Poly::new_from_coeffs(&[T::one()]),
+            Poly::new_from_coeffs(&[T::one()]),
+        )
+    } else {
+        let two = T::one() + T::one();
+        let n = T::from(order).unwrap();
+        let ratio = wh / wb;
+        let pairs = two * n + T::one();
+        let freq = |k: usize, shift: T| {
+            let k = T::from(k).unwrap();
+            wb * ratio.powf((k + shift / two) / pairs)
+        };
+        let count = 2 * order + 1;
+        let zeros: Vec<_> = (0..count).map(|k| -freq(k, T::one() - fraction)).collect();
+        let poles: Vec<_> = (0..count).map(|k| -freq(k, T::one() + fraction)).collect();
+        (
+            Poly::new_from_roots(&zeros) * wh.powf(fraction),
+            Poly::new_from_roots(&poles),
+        )
+    };
+    if integer < T::zero() {
+        Tf::new(num, den * s_n)
+    } else {
+        Tf::new(num * s_n, den)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::poly;
+    use num_complex::Complex64;
+
+    const BAND: (RadiansPerSecond<f64>, RadiansPerSecond<f64>) =
+        (RadiansPerSecond(1e-2), RadiansPerSecond(1e2));
+
+    #[test]
+    fn oustaloup_in_band() {
+        for &alpha in &[0.3, 0.5, -0.5, 1.4, -1.7] {
+            let tf = oustaloup(alpha, BAND, 5);
+            for &w in &[0.1, 1., 10.] {
+                let g = tf.eval(&Complex64::new(0., w));
+                let expected: f64 = w.powf(alpha);
+                assert_relative_eq!(expected, g.norm(), max_relative = 2e-2);
+                // The phase ripple grows towards the edges of the band.
+                assert_relative_eq!(alpha * 90., g.arg().to_degrees(), epsilon = 3.);
+            }
+        }
+    }
+
+    #[test]
+    fn oustaloup_integer_order() {
+        let tf = oustaloup(-1., BAND, 3);
+        assert_eq!(Tf::new(poly!(1.), poly!(0., 1.)), tf);
+        let tf = oustaloup(2., BAND, 3);
+        assert_eq!(Tf::new(poly!(0., 0., 1.), poly!(1.)), tf);
+    }
+
+    #[test]
+    #[should_panic]
+    fn oustaloup_wrong_band() {
+        let _ = oustaloup(0.5, (RadiansPerSecond(1.), RadiansPerSecond(0.1)), 3);
+    }
+
+    #[test]
+    fn fractional_pid() {
+        let pid = FractionalPid::new(2., 1., 0.5, 0.5, 0.5);
+        assert_eq!(2., pid.kp());
+        assert_eq!(1., pid.ki());
+        assert_eq!(0.5, pid.lambda());
+        assert_eq!(0.5, pid.kd());
+        assert_eq!(0.5, pid.mu());
+        let tf = pid.tf((RadiansPerSecond(1e-3), RadiansPerSecond(1e3)), 4);
+        let s = Complex64::new(0., 1.);
+        let expected = 2. + s.powf(-0.5) + 0.5 * s.powf(0.5);
+        let g = tf.eval(&s);
+        assert_relative_eq!(expected.re, g.re, max_relative = 2e-2);
+        assert_relative_eq!(expected.im, g.im, max_relative = 2e-2);
+    }
+
+    #[test]
+    fn integer_orders_give_pid() {
+        let pid = FractionalPid::new(2., 3., 1., 0.5, 1.);
+        let tf = pid.tf(BAND, 4);
+        let s = Complex64::new(0., 0.7);
+        let expected = 2. + 3. / s + 0.5 * s;
+        assert_relative_eq!(expected.re, tf.eval(&s).re, max_relative = 1e-12);
+        assert_relative_eq!(expected.im, tf.eval(&s).im, max_relative = 1e-12);
+    }
+
+    #[test]
+    fn discrete_fractional_pid() {
+        let pid = FractionalPid::new(1., 0., 0., 0., 0.);
+        let mut ctrl = pid.discretize(BAND, 3, Seconds(0.1));
+        assert_relative_eq!(0.5, ctrl.step(0.5));
+    }
+
+    #[test]
+    #[should_panic]
+    fn negative_order() {
+        let _ = FractionalPid::new(1., 1., -0.5, 0., 0.);
+    }
+}
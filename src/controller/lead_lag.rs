@@ -0,0 +1,140 @@
+//! # Lead-lag compensator
+//!
+//! Design of a first order lead or lag compensator that gives the desired
+//! phase margin at the desired gain crossover frequency of the open loop.
+//!
+//! ```text
+//!          1 + T*s
+//! C(s) = K ---------
+//!          1 + a*T*s
+//! ```
+//!
+//! The compensator is a lead network (`a < 1`) when the phase of the open
+//! loop shall be increased at the crossover frequency, a lag network
+//! (`a > 1`) otherwise. The maximum phase shift of the network is placed at
+//! the crossover frequency and the gain `K` sets the crossover.
+
+use num_complex::Complex;
+use num_traits::Float;
+
+use crate::{
+    error::{Error, ErrorKind},
+    polynomial::Poly,
+    transfer_function::continuous::Tf,
+    units::RadiansPerSecond,
+};
+
+/// Design a lead or lag compensator that gives the desired phase margin at
+/// the desired gain crossover frequency of the loop with the plant.
+///
+/// # Arguments
+///
+/// * `plant` - Plant transfer function
+/// * `desired_pm` - Desired phase margin (degrees)
+/// * `desired_crossover` - Desired gain crossover angular frequency
+///
+/// # Errors
+///
+/// It returns an error if the phase shift of the compensator is 90° or more
+/// or if the plant has a null or infinite gain at the crossover frequency.
+///
+/// # Panics
+///
+/// Panics if the crossover frequency is not positive.
+///
+/// # Example
+/// ```
+/// use au::{controller::lead_lag::design_lead_lag, num_complex::Complex, poly, RadiansPerSecond, Tf};
+/// let plant = Tf::new(poly!(1_f64), poly!(0., 1., 1.));
+/// let c = design_lead_lag(&plant, 50., RadiansPerSecond(2.)).unwrap();
+/// let l = (&plant * &c).eval(&Complex::new(0., 2.));
+/// assert!((l.norm() - 1.).abs() < 1e-10);
+/// assert!((l.arg().to_degrees() - (50. - 180.)).abs() < 1e-8);
+/// ```
+pub fn design_lead_lag<T: Float>(
+    plant: &Tf<T>,
+    desired_pm: T,
+    desired_crossover: RadiansPerSecond<T>,
+) -> Result<Tf<T>, Error> {
+    let wc = desired_crossover.0;
+    assert!(wc > T::zero(), "Crossover frequency must be positive");
+    let g = plant.eval(&Complex::new(T::zero(), wc));
+    let magnitude = g.norm();
+    if !magnitude.is_normal() {
+        return Err(Error::new_internal(ErrorKind::UnfeasibleSpecification));
+    }
+    // Phase shift of the compensator, wrapped in (-180°, 180°].
+    let full = T::from(360.).unwrap();
+    let half = T::from(180.).unwrap();
+    let mut shift = (desired_pm - half - g.arg().to_degrees()) % full;
+    if shift > half {
+        shift = shift - full;
+    } else if shift <= -half {
+        shift = shift + full;
+    }
+    if shift.abs() >= T::from(90.).unwrap() {
+        return Err(Error::new_internal(ErrorKind::UnfeasibleSpecification));
+    }
+
+    let sin = shift.to_radians().sin();
+    let alpha = (T::one() - sin) / (T::one() + sin);
+    let tau = (wc * alpha.sqrt()).recip();
+    let k = alpha.sqrt() / magnitude;
+    Ok(Tf::new(
+        Poly::new_from_coeffs(&[k, k * tau]),
+        Poly::new_from_coeffs(&[T::one(), alpha * tau]),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::poly;
+
+    fn check(plant: &Tf<f64>, pm: f64, wc: f64) -> Tf<f64> {
+        let c = design_lead_lag(plant, pm, RadiansPerSecond(wc)).unwrap();
+        let l = (plant * &c).eval(&Complex::new(0., wc));
+        assert_relative_eq!(1., l.norm(), max_relative = 1e-10);
+        let phase = (l.arg().to_degrees() + 180. - pm) / 360.;
+        assert_relative_eq!(0., phase - phase.round(), epsilon = 1e-10);
+        c
+    }
+
+    #[test]
+    fn lead_network() {
+        let plant = Tf::new(poly!(10.), poly!(0., 1., 1.));
+        let c = check(&plant, 45., 5.);
+        // Zero at lower frequency than the pole.
+        let z = c.real_zeros().unwrap()[0];
+        let p = c.real_poles().unwrap()[0];
+        assert!(p < z);
+    }
+
+    #[test]
+    fn lag_network() {
+        let plant = Tf::new(poly!(10.), poly!(1., 1.));
+        let c = check(&plant, 100., 2.);
+        let z = c.real_zeros().unwrap()[0];
+        let p = c.real_poles().unwrap()[0];
+        assert!(z < p);
+    }
+
+    #[test]
+    fn wrapped_phase() {
+        // Phase -270° at every frequency.
+        let plant = Tf::new(poly!(1.), poly!(0., 0., 0., 1.));
+        assert!(design_lead_lag(&plant, 45., RadiansPerSecond(1.)).is_err());
+        let plant = Tf::new(poly!(1.), poly!(0., 0., 1.));
+        check(&plant, 30., 1.);
+    }
+
+    #[test]
+    fn unfeasible() {
+        let plant = Tf::new(poly!(1.), poly!(1., 1.));
+        let res = design_lead_lag(&plant, 10., RadiansPerSecond(1.));
+        assert_eq!(ErrorKind::UnfeasibleSpecification, res.unwrap_err().kind());
+        // Null gain at the crossover frequency.
+        let plant = Tf::new(poly!(1., 0., 1.), poly!(1., 1., 1.));
+        assert!(design_lead_lag(&plant, 60., RadiansPerSecond(1.)).is_err());
+    }
+}
@@ -3,5 +3,231 @@
 //! Available controllers are PID (Proportional-integral-derivative).
 //!
 //! Both ideal and real PID are available.
+//!
+//! PID controllers can be [tuned](tuning/index.html) with empirical rules
+//! and their parameters can be [scheduled](scheduling/index.html) on the
+//! operating point. They can be [autotuned](autotune/index.html) from the
+//! recorded samples of a step test.
+//!
+//! A [feedforward](feedforward/index.html) path can be added to the
+//! controllers.
+//!
+//! [Lead-lag](lead_lag/index.html) compensators are designed from phase
+//! margin and crossover frequency specifications.
+//!
+//! [Fractional order PID](fractional/index.html) controllers are realized
+//! with rational approximations.
+//!
+//! [State feedback](state_feedback/index.html) controllers with integral
+//! action are designed by LQR or pole placement.
+//!
+//! [Deadbeat](deadbeat/index.html) and [RST](rst/index.html) controllers
+//! are available for discrete plants.
+//!
+//! [Notch](notch/index.html) compensators attenuate lightly damped modes.
+//!
+//! [Resonant and repetitive](resonant/index.html) controllers track periodic
+//! references.
+//!
+//! Nonlinear [blocks](blocks/index.html) model the actuator between the
+//! controller and the plant.
+//!
+//! The discrete controllers and the nonlinear blocks share the
+//! [`Controller`](trait.Controller.html) interface, so that closed loop
+//! simulations can be generic over the control law.
 
+pub mod autotune;
+pub mod blocks;
+pub mod deadbeat;
+pub mod feedforward;
+pub mod fractional;
+pub mod lead_lag;
+pub mod notch;
 pub mod pid;
+pub mod resonant;
+pub mod rst;
+pub mod scheduling;
+pub mod state_feedback;
+pub mod tuning;
+
+use nalgebra::Scalar;
+use num_traits::Float;
+
+use std::ops::{AddAssign, MulAssign};
+
+use crate::{
+    controller::pid::LoopResponse,
+    error::{Error, ErrorKind},
+    linear_system::discrete::Ssd,
+    units::Seconds,
+};
+
+/// Control law executed in a sampled loop
+pub trait Controller<T: Float> {
+    /// Input of the control law, as the error or the measurements
+    type Input;
+    /// Output of the control law
+    type Output;
+
+    /// Calculate the output of the current sample and update the internal
+    /// state, if any.
+    ///
+    /// # Arguments
+    ///
+    /// * `time` - Time of the current sample
+    /// * `input` - Input of the control law
+    fn step(&mut self, time: Seconds<T>, input: Self::Input) -> Self::Output;
+
+    /// Reset the internal state to the initial one.
+    fn reset(&mut self) {}
+}
+
+impl<T: Float, C: Controller<T> + ?Sized> Controller<T> for &mut C {
+    type Input = C::Input;
+    type Output = C::Output;
+
+    fn step(&mut self, time: Seconds<T>, input: Self::Input) -> Self::Output {
+        (**self).step(time, input)
+    }
+
+    fn reset(&mut self) {
+        (**self).reset();
+    }
+}
+
+impl<T: Float, C: Controller<T> + ?Sized> Controller<T> for Box<C> {
+    type Input = C::Input;
+    type Output = C::Output;
+
+    fn step(&mut self, time: Seconds<T>, input: Self::Input) -> Self::Output {
+        (**self).step(time, input)
+    }
+
+    fn reset(&mut self) {
+        (**self).reset();
+    }
+}
+
+/// Simulate the closed loop of a control law with a discrete plant,
+/// following a sampled reference signal.
+///
+/// The input of the control law is the error between the reference and the
+/// measured output, its output is the input of the plant. The plant starts
+/// from the null state; the control law is not reset before the simulation.
+///
+/// # Arguments
+///
+/// * `plant` - Single input single output discrete plant
+/// * `st` - Sample time of the plant and the control law
+/// * `controller` - Control law
+/// * `reference` - Reference signal samples
+///
+/// # Errors
+///
+/// It returns an error if the plant is not single input single output.
+///
+/// # Panics
+///
+/// Panics if the sample time is not positive.
+///
+/// # Example
+/// ```
+/// use au::{controller::{closed_loop_discrete, pid::Pid}, Seconds, Ssd};
+/// let plant = Ssd::new_from_slice(1, 1, 1, &[0.9], &[0.1], &[1.], &[0.]);
+/// let pid = Pid::new_ideal(2., 0.5, 0.).discretize(Seconds(0.1));
+/// let resp = closed_loop_discrete(&plant, Seconds(0.1), pid, &[1.; 200]).unwrap();
+/// assert!((resp.output().last().unwrap() - 1_f64).abs() < 1e-6);
+/// ```
+pub fn closed_loop_discrete<T, C>(
+    plant: &Ssd<T>,
+    st: Seconds<T>,
+    mut controller: C,
+    reference: &[T],
+) -> Result<LoopResponse<T>, Error>
+where
+    T: AddAssign + Float + MulAssign + Scalar,
+    C: Controller<T, Input = T, Output = T>,
+{
+    assert!(st.0 > T::zero(), "Sample time must be positive");
+    let dim = plant.dim();
+    if dim.inputs() != 1 || dim.outputs() != 1 {
+        return Err(Error::new_internal(ErrorKind::NoSisoSystem));
+    }
+    let time: Vec<_> = (0..reference.len())
+        .map(|k| Seconds(T::from(k).unwrap() * st.0))
+        .collect();
+    if reference.is_empty() {
+        return Ok(LoopResponse {
+            time,
+            reference: Vec::new(),
+            error: Vec::new(),
+            control: Vec::new(),
+            output: Vec::new(),
+        });
+    }
+
+    let mut errors = Vec::with_capacity(reference.len());
+    let mut controls = Vec::with_capacity(reference.len());
+    let x0 = vec![T::zero(); dim.states()];
+    let outputs: Vec<_> = plant
+        .evolution_feedback(
+            reference.len() - 1,
+            |k, y| {
+                let e = reference[k] - y[0];
+                let u = controller.step(time[k], e);
+                errors.push(e);
+                controls.push(u);
+                vec![u]
+            },
+            &x0,
+        )
+        .map(|s| s.output()[0])
+        .collect();
+
+    Ok(LoopResponse {
+        time,
+        reference: reference.to_vec(),
+        error: errors,
+        control: controls,
+        output: outputs,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::controller::{
+        blocks::{DeadZone, Saturation},
+        pid::Pid,
+    };
+
+    #[test]
+    fn generic_controllers() {
+        let plant = Ssd::new_from_slice(1, 1, 1, &[0.9], &[0.1], &[1.], &[0.]);
+        let st = Seconds(0.1);
+        let mut laws: Vec<Box<dyn Controller<f64, Input = f64, Output = f64>>> = vec![
+            Box::new(Pid::new_ideal(2., 0.5, 0.).discretize(st)),
+            Box::new(Saturation::new(-1., 1.)),
+            Box::new(DeadZone::new(-0.1, 0.1)),
+        ];
+        let finals: Vec<_> = laws
+            .iter_mut()
+            .map(|c| {
+                let resp = closed_loop_discrete(&plant, st, c, &[1.; 300]).unwrap();
+                *resp.output().last().unwrap()
+            })
+            .collect();
+        // Integral action removes the steady state error.
+        assert_relative_eq!(1., finals[0], max_relative = 1e-6);
+        // Proportional laws with unitary gain: y = u/(1 + 1).
+        assert_relative_eq!(0.5, finals[1], max_relative = 1e-6);
+        assert!(finals[2] < 0.5);
+    }
+
+    #[test]
+    fn closed_loop_no_siso() {
+        let plant = Ssd::new_from_slice(1, 2, 1, &[0.9], &[0.1, 0.1], &[1.], &[0., 0.]);
+        let res = closed_loop_discrete(&plant, Seconds(0.1), Saturation::new(-1., 1.), &[1.]);
+        assert_eq!(ErrorKind::NoSisoSystem, res.unwrap_err().kind());
+    }
+}
@@ -0,0 +1,275 @@
+//! # Notch compensator
+//!
+//! Attenuation of a lightly damped mode of the plant with a notch filter
+//! ```text
+//!        s^2 + 2*depth*width*wn*s + wn^2
+//! N(s) = -------------------------------
+//!           s^2 + 2*width*wn*s + wn^2
+//! ```
+//! whose gain is `depth` at the center frequency `wn`; `width` is the damping
+//! ratio of the poles of the filter, the bandwidth of the notch is about
+//! `2*width*wn`.
+//!
+//! The mode is usually identified with [`Tf::damp`](../../transfer_function/continuous/type.Tf.html)
+//! and the designer verifies the stability and the margins of the
+//! compensated loop.
+
+use num_traits::{Float, FloatConst};
+
+use crate::{
+    error::{Error, ErrorKind},
+    polynomial::Poly,
+    transfer_function::continuous::{Margins, Tf},
+    units::{DampingRatio, Degrees, NaturalFrequency, RadiansPerSecond},
+};
+
+/// Notch filter
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde-serialize",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub struct Notch<T: Float> {
+    /// Center angular frequency
+    frequency: RadiansPerSecond<T>,
+    /// Gain at the center frequency
+    depth: T,
+    /// Damping ratio of the poles
+    width: T,
+}
+
+impl<T: Float> Notch<T> {
+    /// Create a notch filter.
+    ///
+    /// # Arguments
+    ///
+    /// * `frequency` - Center angular frequency
+    /// * `depth` - Gain at the center frequency, between 0 and 1
+    /// * `width` - Damping ratio of the poles of the filter
+    ///
+    /// # Panics
+    ///
+    /// Panics if the frequency or the width are not positive or the depth is
+    /// not in the interval [0, 1).
+    ///
+    /// # Example
+    /// ```
+    /// use au::{controller::notch::Notch, num_complex::Complex, RadiansPerSecond};
+    /// let notch = Notch::new(RadiansPerSecond(10.), 0.1, 0.5);
+    /// let g = notch.tf().eval(&Complex::new(0., 10.));
+    /// assert!((g.norm() - 0.1_f64).abs() < 1e-12);
+    /// ```
+    #[must_use]
+    pub fn new(frequency: RadiansPerSecond<T>, depth: T, width: T) -> Self {
+        assert!(frequency.0 > T::zero(), "Frequency must be positive");
+        assert!(
+            depth >= T::zero() && depth < T::one(),
+            "Depth must be in the interval [0, 1)"
+        );
+        assert!(width > T::zero(), "Width must be positive");
+        Self {
+            frequency,
+            depth,
+            width,
+        }
+    }
+
+    /// Get the center angular frequency
+    #[must_use]
+    pub fn frequency(&self) -> RadiansPerSecond<T> {
+        self.frequency
+    }
+
+    /// Get the gain at the center frequency
+    #[must_use]
+    pub fn depth(&self) -> T {
+        self.depth
+    }
+
+    /// Get the damping ratio of the poles
+    #[must_use]
+    pub fn width(&self) -> T {
+        self.width
+    }
+
+    /// Transfer function of the filter
+    #[must_use]
+    pub fn tf(&self) -> Tf<T> {
+        let wn = self.frequency.0;
+        let two = T::one() + T::one();
+        let p = two * self.width * wn;
+        Tf::new(
+            Poly::new_from_coeffs(&[wn * wn, self.depth * p, T::one()]),
+            Poly::new_from_coeffs(&[wn * wn, p, T::one()]),
+        )
+    }
+}
+
+/// Notch compensator with the margins of the compensated loop
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde-serialize",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub struct NotchDesign<T: Float> {
+    /// Notch filter
+    notch: Notch<T>,
+    /// Compensated loop transfer function
+    loop_tf: Tf<T>,
+    /// Margins of the compensated loop
+    margins: Margins<T>,
+}
+
+impl<T: Float> NotchDesign<T> {
+    /// Get the notch filter
+    #[must_use]
+    pub fn notch(&self) -> &Notch<T> {
+        &self.notch
+    }
+
+    /// Get the compensated loop transfer function
+    #[must_use]
+    pub fn loop_tf(&self) -> &Tf<T> {
+        &self.loop_tf
+    }
+
+    /// Get the gain and phase margins of the compensated loop
+    #[must_use]
+    pub fn margins(&self) -> &Margins<T> {
+        &self.margins
+    }
+}
+
+/// Design a notch compensator centered on a lightly damped mode of the loop.
+///
+/// The notch is inserted in series with the loop transfer function, the
+/// design is accepted if the compensated loop is stable in negative feedback
+/// and its margins are not lower than the required ones. A missing
+/// crossover is an infinite margin.
+///
+/// # Arguments
+///
+/// * `loop_tf` - Loop transfer function (controller and plant)
+/// * `mode` - Natural frequency and damping ratio of the mode, see `Tf::damp`
+/// * `depth` - Gain of the notch at the natural frequency, between 0 and 1
+/// * `width` - Damping ratio of the poles of the notch
+/// * `min_margins` - Minimum gain margin (absolute value) and phase margin
+///   (degrees)
+///
+/// # Errors
+///
+/// It returns an error if the mode is not lightly damped (its damping ratio
+/// is not lower than `1/sqrt(2)`, so it has no resonance), if the compensated
+/// loop is unstable or its margins are lower than required.
+///
+/// # Panics
+///
+/// Panics if the frequency of the mode or the width are not positive or the
+/// depth is not in the interval [0, 1).
+///
+/// # Example
+/// ```
+/// use au::{controller::notch::design_notch, poly, Degrees, Tf};
+/// // Integrator with a resonance at 10 rad/s.
+/// let l = Tf::new(poly!(200.), poly!(0., 1.) * poly!(100., 0.2, 1.));
+/// let mode = l.damp().into_iter().find(|m| m.0 .0 > 0. && m.1 .0 < 0.1).unwrap();
+/// let design = design_notch(&l, mode, 0.02, 0.7, (2., Degrees(45.))).unwrap();
+/// assert!(design.margins().phase_margin().unwrap().0 > 45.);
+/// ```
+pub fn design_notch<T: Float + FloatConst>(
+    loop_tf: &Tf<T>,
+    mode: (NaturalFrequency<T>, DampingRatio<T>),
+    depth: T,
+    width: T,
+    min_margins: (T, Degrees<T>),
+) -> Result<NotchDesign<T>, Error> {
+    let notch = Notch::new(mode.0.into(), depth, width);
+    if mode.1 .0 >= T::one() / Float::sqrt(T::one() + T::one()) {
+        return Err(Error::new_internal(ErrorKind::UnfeasibleSpecification));
+    }
+    let compensated = loop_tf * &notch.tf();
+    let closed_loop = compensated.feedback_n();
+    if closed_loop
+        .den()
+        .iterative_roots()
+        .iter()
+        .any(|p| p.re >= T::zero())
+    {
+        return Err(Error::new_internal(ErrorKind::UnstableSystem));
+    }
+    let margins = compensated.margins();
+    let gain_ok = margins
+        .gain_margin()
+        .into_iter()
+        .all(|gm| gm >= min_margins.0);
+    let phase_ok = margins
+        .phase_margin()
+        .into_iter()
+        .all(|pm| pm >= min_margins.1);
+    if !(gain_ok && phase_ok) {
+        return Err(Error::new_internal(ErrorKind::UnfeasibleSpecification));
+    }
+    Ok(NotchDesign {
+        notch,
+        loop_tf: compensated,
+        margins,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::poly;
+    use num_complex::Complex64;
+
+    fn resonant_loop(k: f64) -> Tf<f64> {
+        Tf::new(poly!(k), poly!(0., 1.) * poly!(100., 0.2, 1.))
+    }
+
+    #[test]
+    fn notch_filter() {
+        let notch = Notch::new(RadiansPerSecond(5.), 0., 0.3);
+        assert_eq!(RadiansPerSecond(5.), notch.frequency());
+        assert_eq!(0., notch.depth());
+        assert_eq!(0.3, notch.width());
+        let tf = notch.tf();
+        assert_relative_eq!(0., tf.eval(&Complex64::new(0., 5.)).norm(), epsilon = 1e-12);
+        assert_relative_eq!(1., tf.static_gain());
+        assert_relative_eq!(1., tf.eval(&Complex64::new(0., 1e4)).norm(), epsilon = 1e-3);
+    }
+
+    #[test]
+    fn notch_design() {
+        let l = resonant_loop(200.);
+        // Without the notch the resonance crosses 0 dB with a poor margin.
+        assert!(!l.feedback_n().is_stable() || l.margins().gain_margin().unwrap() < 1.);
+        let mode = l.damp()[1];
+        assert_relative_eq!(10., mode.0 .0, max_relative = 1e-10);
+        assert_relative_eq!(0.01, mode.1 .0, max_relative = 1e-10);
+        let design = design_notch(&l, mode, 0.02, 0.7, (2., Degrees(45.))).unwrap();
+        assert!(design.margins().gain_margin().unwrap() >= 2.);
+        assert!(design.margins().phase_margin().unwrap().0 >= 45.);
+        assert_eq!(&(&l * &design.notch().tf()), design.loop_tf());
+    }
+
+    #[test]
+    fn notch_design_failures() {
+        let l = resonant_loop(200.);
+        let mode = l.damp()[1];
+        // Shallow notch, the resonance is still too high.
+        let res = design_notch(&l, mode, 0.9, 0.7, (2., Degrees(45.)));
+        assert!(res.is_err());
+        // Required margins too high.
+        let res = design_notch(&l, mode, 0.02, 0.7, (100., Degrees(89.)));
+        assert_eq!(ErrorKind::UnfeasibleSpecification, res.unwrap_err().kind());
+        // Well damped mode.
+        let res = design_notch(
+            &l,
+            (NaturalFrequency(10.), DampingRatio(0.8)),
+            0.02,
+            0.7,
+            (2., Degrees(45.)),
+        );
+        assert_eq!(ErrorKind::UnfeasibleSpecification, res.unwrap_err().kind());
+    }
+}
@@ -4,19 +4,44 @@
 //! * real PID
 //! * ideal PID
 //! * automatic calculation of the corrisponding transfer function
+//! * discrete controller with internal state, to be executed in a sampled loop
+//! * velocity (incremental) form of the discrete controller
+//! * closed loop simulation with a plant, actuator limits and nonlinear
+//!   actuator blocks
 
-use crate::{polynomial::Poly, transfer_function::continuous::Tf};
+use crate::{
+    controller::{
+        blocks::{Block, Saturation},
+        Controller,
+    },
+    enums::Discretization,
+    error::Error,
+    linear_system::{continuous::Ss, discrete::Ssd},
+    polynomial::Poly,
+    transfer_function::{continuous::Tf, discrete::Tfz},
+    units::Seconds,
+};
 
+use nalgebra::{ComplexField, RealField, Scalar};
 use num_traits::Float;
 
+use std::ops::{AddAssign, MulAssign};
+
 /// Proportional-Integral-Derivative controller
-#[derive(Debug)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde-serialize",
+    derive(serde::Serialize, serde::Deserialize)
+)]
 pub struct Pid<T: Float> {
     /// Proportional action coefficient
+    #[cfg_attr(feature = "serde-serialize", serde(with = "crate::serde_float"))]
     kp: T,
     /// Integral time
+    #[cfg_attr(feature = "serde-serialize", serde(with = "crate::serde_float"))]
     ti: T,
     /// Derivative time
+    #[cfg_attr(feature = "serde-serialize", serde(with = "crate::serde_float"))]
     td: T,
     /// Constant for additional pole
     n: Option<T>,
@@ -69,8 +94,34 @@ impl<T: Float> Pid<T> {
         }
     }
 
+    /// Get the proportional action coefficient
+    #[must_use]
+    pub fn kp(&self) -> T {
+        self.kp
+    }
+
+    /// Get the integral time, infinite if there is no integral action
+    #[must_use]
+    pub fn ti(&self) -> T {
+        self.ti
+    }
+
+    /// Get the derivative time
+    #[must_use]
+    pub fn td(&self) -> T {
+        self.td
+    }
+
+    /// Get the constant for the additional pole, `None` for the ideal PID
+    #[must_use]
+    pub fn n(&self) -> Option<T> {
+        self.n
+    }
+
     /// Calculate the transfer function of the PID controller
     ///
+    /// An infinite integral time removes the integral action.
+    ///
     /// # Real PID
     /// ```text
     ///          1         Td
@@ -102,12 +153,40 @@ impl<T: Float> Pid<T> {
             .map_or_else(|| self.tf_from_ideal_pid(), |n| self.tf_from_real_pid(n))
     }
 
+    /// Calculate the discrete time transfer function of the PID controller,
+    /// discretizing its continuous time transfer function.
+    ///
+    /// # Arguments
+    ///
+    /// * `ts` - Sampling period
+    /// * `method` - Discretization method
+    ///
+    /// # Example
+    /// ```
+    /// use au::{controller::pid::Pid, Discretization, Seconds};
+    /// let pid = Pid::new(2., 2., 0.5, 5.);
+    /// let tfz = pid.to_tfz(Seconds(0.1), Discretization::Tustin);
+    /// // The integral action gives a pole in z = 1.
+    /// let poles = tfz.real_poles().unwrap();
+    /// assert!(poles.iter().any(|p| (p - 1_f64).abs() < 1e-12));
+    /// ```
+    pub fn to_tfz(&self, ts: Seconds<T>, method: Discretization) -> Tfz<T> {
+        self.tf().discretize(ts, method)
+    }
+
     /// Calculate the transfer function of a real PID controller
     ///
     /// # Arguments
     ///
     /// * `n` - Constant for additional pole
     fn tf_from_real_pid(&self, n: T) -> Tf<T> {
+        if self.ti.is_infinite() {
+            // Kp (N + Td(1 + N)s) / (N + Td*s)
+            return Tf::new(
+                Poly::new_from_coeffs(&[self.kp * n, self.kp * self.td * (T::one() + n)]),
+                Poly::new_from_coeffs(&[n, self.td]),
+            );
+        }
         let a0 = self.kp * n;
         let a1 = self.kp * (self.ti * n + self.td);
         let a2 = self.kp * self.ti * self.td * (T::one() + n);
@@ -122,17 +201,707 @@ impl<T: Float> Pid<T> {
 
     /// Calculate the transfer function of an ideal PID controller
     fn tf_from_ideal_pid(&self) -> Tf<T> {
+        if self.ti.is_infinite() {
+            return Tf::new(
+                Poly::new_from_coeffs(&[self.kp, self.kp * self.td]),
+                Poly::new_from_coeffs(&[T::one()]),
+            );
+        }
         Tf::new(
             Poly::new_from_coeffs(&[T::one(), self.ti, self.ti * self.td]),
             Poly::new_from_coeffs(&[T::zero(), self.ti / self.kp]),
         )
     }
+
+    /// Discretize the controller with the backward Euler method, the
+    /// resulting controller has a null initial state and no output limits.
+    ///
+    /// # Arguments
+    ///
+    /// * `st` - Sample time of the controller
+    ///
+    /// # Panics
+    ///
+    /// Panics if the sample time is not positive.
+    ///
+    /// # Example
+    /// ```
+    /// use au::{controller::pid::Pid, Seconds};
+    /// let mut pid = Pid::new_ideal(2., 0.5, 0.).discretize(Seconds(0.1));
+    /// // Proportional and integral actions on the first sample.
+    /// assert_eq!(2. + 0.4, pid.step(1., 0.));
+    /// ```
+    pub fn discretize(&self, st: Seconds<T>) -> DiscretePid<T> {
+        assert!(st.0 > T::zero(), "Sample time must be positive");
+        let ki = self.kp * st.0 / self.ti;
+        let (kd_prev, kd_err) = match self.n {
+            Some(n) => {
+                let den = self.td + n * st.0;
+                (self.td / den, self.kp * self.td * n / den)
+            }
+            None => (T::zero(), self.kp * self.td / st.0),
+        };
+        DiscretePid {
+            st,
+            kp: self.kp,
+            ki,
+            kd_prev,
+            kd_err,
+            limits: (T::neg_infinity(), T::infinity()),
+            integral: T::zero(),
+            derivative: T::zero(),
+            prev_error: None,
+        }
+    }
+
+    /// Discretize the controller in velocity (incremental) form with the
+    /// backward Euler method, the resulting controller has a null initial
+    /// state and output and no output limits.
+    ///
+    /// # Arguments
+    ///
+    /// * `st` - Sample time of the controller
+    ///
+    /// # Panics
+    ///
+    /// Panics if the sample time is not positive.
+    ///
+    /// # Example
+    /// ```
+    /// use au::{controller::pid::Pid, Seconds};
+    /// let mut pid = Pid::new_ideal(2., 0.5, 0.).discretize_velocity(Seconds(0.1));
+    /// assert!((pid.step(1., 0.) - 2.4_f64).abs() < 1e-12);
+    /// // Only the integral action changes with a constant error.
+    /// assert!((pid.step(1., 0.) - 0.4_f64).abs() < 1e-12);
+    /// ```
+    pub fn discretize_velocity(&self, st: Seconds<T>) -> VelocityPid<T> {
+        let pid = self.discretize(st);
+        VelocityPid {
+            st,
+            kp: pid.kp,
+            ki: pid.ki,
+            kd_prev: pid.kd_prev,
+            kd_err: pid.kd_err,
+            limits: pid.limits,
+            initial: T::zero(),
+            output: T::zero(),
+            derivative: T::zero(),
+            prev_error: None,
+        }
+    }
+}
+
+/// Discrete PID controller with internal state.
+///
+/// The actions are discretized with the backward Euler method and the
+/// control action is limited between the output limits. When the control
+/// action is saturated the integral action is frozen (anti-windup).
+/// The derivative action starts from the second sample.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde-serialize",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub struct DiscretePid<T: Float> {
+    /// Sample time
+    st: Seconds<T>,
+    /// Proportional action coefficient
+    kp: T,
+    /// Integral action increment coefficient
+    ki: T,
+    /// Coefficient of the previous derivative action
+    kd_prev: T,
+    /// Coefficient of the error increment in the derivative action
+    kd_err: T,
+    /// Minimum and maximum control action
+    #[cfg_attr(feature = "serde-serialize", serde(with = "crate::serde_float::pair"))]
+    limits: (T, T),
+    /// Integral action
+    integral: T,
+    /// Derivative action
+    derivative: T,
+    /// Error of the previous sample
+    prev_error: Option<T>,
+}
+
+impl<T: Float> DiscretePid<T> {
+    /// Set the minimum and maximum control action.
+    ///
+    /// # Arguments
+    ///
+    /// * `limits` - Minimum and maximum control action
+    ///
+    /// # Panics
+    ///
+    /// Panics if the minimum limit is greater than the maximum one.
+    ///
+    /// # Example
+    /// ```
+    /// use au::{controller::pid::Pid, Seconds};
+    /// let mut pid = Pid::new_ideal(10., 1., 0.)
+    ///     .discretize(Seconds(0.1))
+    ///     .with_limits((-1., 1.));
+    /// assert_eq!(1., pid.step(1., 0.));
+    /// ```
+    #[must_use]
+    pub fn with_limits(mut self, limits: (T, T)) -> Self {
+        assert!(limits.0 <= limits.1, "Minimum limit greater than maximum");
+        self.limits = limits;
+        self
+    }
+
+    /// Get the sample time
+    #[must_use]
+    pub fn sample_time(&self) -> Seconds<T> {
+        self.st
+    }
+
+    /// Get the minimum and maximum control action
+    #[must_use]
+    pub fn limits(&self) -> (T, T) {
+        self.limits
+    }
+
+    /// Calculate the control action of the current sample and update the
+    /// state of the controller.
+    ///
+    /// # Arguments
+    ///
+    /// * `setpoint` - Reference value
+    /// * `measurement` - Measured output of the plant
+    pub fn step(&mut self, setpoint: T, measurement: T) -> T {
+        self.step_with_feedforward(setpoint, measurement, T::zero())
+    }
+
+    /// Calculate the control action of the current sample, adding the
+    /// feedforward action before the output limits, and update the state of
+    /// the controller.
+    ///
+    /// The integral action is frozen also when the saturation is caused by
+    /// the feedforward action.
+    ///
+    /// # Arguments
+    ///
+    /// * `setpoint` - Reference value
+    /// * `measurement` - Measured output of the plant
+    /// * `feedforward` - Feedforward action
+    ///
+    /// # Example
+    /// ```
+    /// use au::{controller::pid::Pid, Seconds};
+    /// let mut pid = Pid::new_ideal(1., 1., 0.)
+    ///     .discretize(Seconds(0.1))
+    ///     .with_limits((0., 2.));
+    /// assert_eq!(2., pid.step_with_feedforward(1., 0., 1.5));
+    /// ```
+    pub fn step_with_feedforward(&mut self, setpoint: T, measurement: T, feedforward: T) -> T {
+        let e = setpoint - measurement;
+        let delta = self.prev_error.map_or_else(T::zero, |p| e - p);
+        self.derivative = self.kd_prev * self.derivative + self.kd_err * delta;
+        let proportional = self.kp * e + feedforward;
+        let mut u = proportional + self.integral + self.ki * e + self.derivative;
+        let saturated = u > self.limits.1 && e > T::zero() || u < self.limits.0 && e < T::zero();
+        if saturated {
+            u = proportional + self.integral + self.derivative;
+        } else {
+            self.integral = self.integral + self.ki * e;
+        }
+        self.prev_error = Some(e);
+        u.max(self.limits.0).min(self.limits.1)
+    }
+
+    /// Reset the state of the controller to the initial null state.
+    pub fn reset(&mut self) {
+        self.integral = T::zero();
+        self.derivative = T::zero();
+        self.prev_error = None;
+    }
+
+    /// Replace the parameters of the controller during the execution,
+    /// keeping the sample time, the output limits and the state.
+    ///
+    /// The update is bumpless: the integral action is adjusted so that the
+    /// new parameters give the same control action on the previous error.
+    ///
+    /// # Arguments
+    ///
+    /// * `pid` - Controller with the new parameters
+    ///
+    /// # Example
+    /// ```
+    /// use au::{controller::pid::Pid, Seconds};
+    /// let mut pid = Pid::new_ideal(1., 1., 0.).discretize(Seconds(0.1));
+    /// let u: f64 = pid.step(1., 0.);
+    /// pid.set_parameters(&Pid::new_ideal(3., 1., 0.));
+    /// // With the same error only the new integral increment is added.
+    /// assert!((pid.step(1., 0.) - u - 0.3).abs() < 1e-12);
+    /// ```
+    pub fn set_parameters(&mut self, pid: &Pid<T>) {
+        let new = pid.discretize(self.st);
+        if let Some(e) = self.prev_error {
+            self.integral = self.integral + (self.kp - new.kp) * e;
+        }
+        self.kp = new.kp;
+        self.ki = new.ki;
+        self.kd_prev = new.kd_prev;
+        self.kd_err = new.kd_err;
+    }
+}
+
+/// The input of the control law is the error between setpoint and
+/// measurement.
+impl<T: Float> Controller<T> for DiscretePid<T> {
+    type Input = T;
+    type Output = T;
+
+    fn step(&mut self, _time: Seconds<T>, error: T) -> T {
+        DiscretePid::step(self, error, T::zero())
+    }
+
+    fn reset(&mut self) {
+        DiscretePid::reset(self);
+    }
+}
+
+/// Discrete PID controller in velocity (incremental) form.
+///
+/// The controller calculates the increment of the control action for each
+/// sample, suited for integrating actuators. The control action is the sum
+/// of the increments and is limited between the output limits; the
+/// increments that exceed the limits are discarded, so the integral action
+/// does not wind up. Without output limits the control action is the same as
+/// the one of the positional form.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde-serialize",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub struct VelocityPid<T: Float> {
+    /// Sample time
+    st: Seconds<T>,
+    /// Proportional action coefficient
+    kp: T,
+    /// Integral action increment coefficient
+    ki: T,
+    /// Coefficient of the previous derivative action
+    kd_prev: T,
+    /// Coefficient of the error increment in the derivative action
+    kd_err: T,
+    /// Minimum and maximum control action
+    #[cfg_attr(feature = "serde-serialize", serde(with = "crate::serde_float::pair"))]
+    limits: (T, T),
+    /// Initial control action
+    initial: T,
+    /// Control action, sum of the increments
+    output: T,
+    /// Derivative action
+    derivative: T,
+    /// Error of the previous sample
+    prev_error: Option<T>,
+}
+
+impl<T: Float> VelocityPid<T> {
+    /// Set the minimum and maximum control action.
+    ///
+    /// # Arguments
+    ///
+    /// * `limits` - Minimum and maximum control action
+    ///
+    /// # Panics
+    ///
+    /// Panics if the minimum limit is greater than the maximum one.
+    #[must_use]
+    pub fn with_limits(mut self, limits: (T, T)) -> Self {
+        assert!(limits.0 <= limits.1, "Minimum limit greater than maximum");
+        self.limits = limits;
+        self
+    }
+
+    /// Set the control action before the first sample, as the current
+    /// position of the actuator.
+    ///
+    /// # Arguments
+    ///
+    /// * `initial` - Initial control action
+    #[must_use]
+    pub fn with_initial_output(mut self, initial: T) -> Self {
+        self.initial = initial;
+        self.output = initial;
+        self
+    }
+
+    /// Get the sample time
+    #[must_use]
+    pub fn sample_time(&self) -> Seconds<T> {
+        self.st
+    }
+
+    /// Get the minimum and maximum control action
+    #[must_use]
+    pub fn limits(&self) -> (T, T) {
+        self.limits
+    }
+
+    /// Get the control action, the sum of the increments
+    #[must_use]
+    pub fn output(&self) -> T {
+        self.output
+    }
+
+    /// Calculate the increment of the control action of the current sample
+    /// and update the state of the controller.
+    ///
+    /// The returned increment is the one applied after the output limits.
+    ///
+    /// # Arguments
+    ///
+    /// * `setpoint` - Reference value
+    /// * `measurement` - Measured output of the plant
+    ///
+    /// # Example
+    /// ```
+    /// use au::{controller::pid::Pid, Seconds};
+    /// let mut pid = Pid::new_ideal(2., 0.5, 0.)
+    ///     .discretize_velocity(Seconds(0.1))
+    ///     .with_limits((-1., 1.));
+    /// assert_eq!(1., pid.step(1., 0.));
+    /// assert_eq!(0., pid.step(1., 0.));
+    /// // The increments beyond the limit are discarded.
+    /// assert!((pid.step(1., 0.5) + 0.8_f64).abs() < 1e-12);
+    /// ```
+    pub fn step(&mut self, setpoint: T, measurement: T) -> T {
+        let e = setpoint - measurement;
+        let (prev, delta) = self
+            .prev_error
+            .map_or((T::zero(), T::zero()), |p| (p, e - p));
+        let derivative = self.kd_prev * self.derivative + self.kd_err * delta;
+        let increment = self.kp * (e - prev) + self.ki * e + derivative - self.derivative;
+        self.derivative = derivative;
+        self.prev_error = Some(e);
+        let output = (self.output + increment)
+            .max(self.limits.0)
+            .min(self.limits.1);
+        let applied = output - self.output;
+        self.output = output;
+        applied
+    }
+
+    /// Reset the state of the controller to the initial one.
+    pub fn reset(&mut self) {
+        self.output = self.initial;
+        self.derivative = T::zero();
+        self.prev_error = None;
+    }
+
+    /// Replace the parameters of the controller during the execution,
+    /// keeping the sample time, the output limits and the state.
+    ///
+    /// The update is bumpless, since the state of the controller is the
+    /// control action.
+    ///
+    /// # Arguments
+    ///
+    /// * `pid` - Controller with the new parameters
+    pub fn set_parameters(&mut self, pid: &Pid<T>) {
+        let new = pid.discretize(self.st);
+        self.kp = new.kp;
+        self.ki = new.ki;
+        self.kd_prev = new.kd_prev;
+        self.kd_err = new.kd_err;
+    }
+}
+
+/// Increment of the control action of a controller in velocity form.
+///
+/// The increment is not the control action, the actuator shall sum the
+/// increments or the control action can be read with `VelocityPid::output`.
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub struct Increment<T>(pub T);
+
+/// The input of the control law is the error between setpoint and
+/// measurement, the output is the increment of the control action.
+impl<T: Float> Controller<T> for VelocityPid<T> {
+    type Input = T;
+    type Output = Increment<T>;
+
+    fn step(&mut self, _time: Seconds<T>, error: T) -> Increment<T> {
+        Increment(VelocityPid::step(self, error, T::zero()))
+    }
+
+    fn reset(&mut self) {
+        VelocityPid::reset(self);
+    }
+}
+
+/// Implementation of the closed loop simulation
+impl<T: AddAssign + Float + MulAssign + Scalar> Pid<T> {
+    /// Simulate the closed loop of the controller with a discrete plant,
+    /// following a sampled reference signal.
+    ///
+    /// The controller is discretized with the backward Euler method and the
+    /// control action is limited between the given values. When the control
+    /// action is saturated the integral action is frozen (anti-windup).
+    ///
+    /// The plant starts from a null state. The output used to calculate the
+    /// error is measured with the control action of the previous sample,
+    /// see `Ssd::evolution_feedback`.
+    ///
+    /// # Arguments
+    ///
+    /// * `plant` - Single input single output discrete plant
+    /// * `st` - Sample time of the plant and the controller
+    /// * `limits` - Minimum and maximum control action
+    /// * `reference` - Reference signal samples
+    ///
+    /// # Errors
+    ///
+    /// It returns an error if the plant is not single input single output.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the sample time is not positive or the minimum limit is
+    /// greater than the maximum one.
+    ///
+    /// # Example
+    /// ```
+    /// use au::{controller::pid::Pid, Seconds, Ssd};
+    /// let plant = Ssd::new_from_slice(1, 1, 1, &[0.9], &[0.1], &[1.], &[0.]);
+    /// let pid = Pid::new_ideal(2., 0.5, 0.);
+    /// let resp = pid
+    ///     .closed_loop_discrete(&plant, Seconds(0.1), (-5., 5.), &[1.; 200])
+    ///     .unwrap();
+    /// assert!((resp.output().last().unwrap() - 1_f64).abs() < 1e-6);
+    /// ```
+    pub fn closed_loop_discrete(
+        &self,
+        plant: &Ssd<T>,
+        st: Seconds<T>,
+        limits: (T, T),
+        reference: &[T],
+    ) -> Result<LoopResponse<T>, Error> {
+        let mut saturation = Saturation::new(limits.0, limits.1);
+        self.closed_loop_discrete_with_actuator(plant, st, limits, &mut saturation, reference)
+    }
+
+    /// Simulate the closed loop of the controller with a discrete plant,
+    /// following a sampled reference signal, with an actuator block between
+    /// the controller and the plant.
+    ///
+    /// The simulation follows `closed_loop_discrete`, the control action of
+    /// the response is the output of the actuator, that is the input of the
+    /// plant. The actuator is not reset before the simulation.
+    ///
+    /// # Arguments
+    ///
+    /// * `plant` - Single input single output discrete plant
+    /// * `st` - Sample time of the plant and the controller
+    /// * `limits` - Minimum and maximum output of the controller
+    /// * `actuator` - Block between the controller and the plant
+    /// * `reference` - Reference signal samples
+    ///
+    /// # Errors
+    ///
+    /// It returns an error if the plant is not single input single output.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the sample time is not positive or the minimum limit is
+    /// greater than the maximum one.
+    ///
+    /// # Example
+    /// ```
+    /// use au::{controller::{blocks::RateLimiter, pid::Pid}, Seconds, Ssd};
+    /// let plant = Ssd::new_from_slice(1, 1, 1, &[0.9], &[0.1], &[1.], &[0.]);
+    /// let pid = Pid::new_ideal(2., 0.5, 0.);
+    /// let mut actuator = RateLimiter::new(1., 1., Seconds(0.1)).with_initial(0.);
+    /// let resp = pid
+    ///     .closed_loop_discrete_with_actuator(&plant, Seconds(0.1), (-5., 5.), &mut actuator, &[1_f64; 300])
+    ///     .unwrap();
+    /// assert!(resp.control().windows(2).all(|u| (u[1] - u[0]).abs() <= 0.1 + 1e-12));
+    /// ```
+    pub fn closed_loop_discrete_with_actuator<B: Block<T>>(
+        &self,
+        plant: &Ssd<T>,
+        st: Seconds<T>,
+        limits: (T, T),
+        actuator: B,
+        reference: &[T],
+    ) -> Result<LoopResponse<T>, Error> {
+        let controller = self.discretize(st).with_limits(limits);
+        super::closed_loop_discrete(
+            plant,
+            st,
+            Actuated {
+                controller,
+                actuator,
+            },
+            reference,
+        )
+    }
+}
+
+/// Control law followed by an actuator block
+struct Actuated<C, B> {
+    /// Control law
+    controller: C,
+    /// Block between the control law and the plant
+    actuator: B,
+}
+
+impl<T: Float, C: Controller<T, Input = T, Output = T>, B: Block<T>> Controller<T>
+    for Actuated<C, B>
+{
+    type Input = T;
+    type Output = T;
+
+    fn step(&mut self, time: Seconds<T>, input: T) -> T {
+        let u = self.controller.step(time, input);
+        self.actuator.step(u)
+    }
+
+    fn reset(&mut self) {
+        self.controller.reset();
+        self.actuator.reset();
+    }
+}
+
+impl<T: AddAssign + ComplexField + Float + MulAssign + RealField> Pid<T> {
+    /// Simulate the closed loop of the controller with a continuous plant,
+    /// following a sampled reference signal.
+    ///
+    /// The plant is discretized with the zero order hold method, the
+    /// simulation follows `closed_loop_discrete`.
+    ///
+    /// # Arguments
+    ///
+    /// * `plant` - Single input single output continuous plant
+    /// * `st` - Sample time of the controller
+    /// * `limits` - Minimum and maximum control action
+    /// * `reference` - Reference signal samples
+    ///
+    /// # Errors
+    ///
+    /// It returns an error if the plant is not single input single output.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the sample time is not positive or the minimum limit is
+    /// greater than the maximum one.
+    ///
+    /// # Example
+    /// ```
+    /// use au::{controller::pid::Pid, Seconds, Ss};
+    /// let plant = Ss::new_from_slice(1, 1, 1, &[-1.], &[1.], &[1.], &[0.]);
+    /// let pid = Pid::new(2., 1., 0.1, 10.);
+    /// let resp = pid
+    ///     .closed_loop(&plant, Seconds(0.01), (0., 3.), &[1.; 1000])
+    ///     .unwrap();
+    /// assert!(resp.control().iter().all(|&u| u >= 0. && u <= 3.));
+    /// ```
+    pub fn closed_loop(
+        &self,
+        plant: &Ss<T>,
+        st: Seconds<T>,
+        limits: (T, T),
+        reference: &[T],
+    ) -> Result<LoopResponse<T>, Error> {
+        assert!(st.0 > T::zero(), "Sample time must be positive");
+        self.closed_loop_discrete(&plant.discretize_zoh(st), st, limits, reference)
+    }
+
+    /// Simulate the closed loop of the controller with a continuous plant,
+    /// following a sampled reference signal, with an actuator block between
+    /// the controller and the plant.
+    ///
+    /// The plant is discretized with the zero order hold method, the
+    /// simulation follows `closed_loop_discrete_with_actuator`.
+    ///
+    /// # Arguments
+    ///
+    /// * `plant` - Single input single output continuous plant
+    /// * `st` - Sample time of the controller
+    /// * `limits` - Minimum and maximum output of the controller
+    /// * `actuator` - Block between the controller and the plant
+    /// * `reference` - Reference signal samples
+    ///
+    /// # Errors
+    ///
+    /// It returns an error if the plant is not single input single output.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the sample time is not positive or the minimum limit is
+    /// greater than the maximum one.
+    pub fn closed_loop_with_actuator<B: Block<T>>(
+        &self,
+        plant: &Ss<T>,
+        st: Seconds<T>,
+        limits: (T, T),
+        actuator: B,
+        reference: &[T],
+    ) -> Result<LoopResponse<T>, Error> {
+        assert!(st.0 > T::zero(), "Sample time must be positive");
+        self.closed_loop_discrete_with_actuator(
+            &plant.discretize_zoh(st),
+            st,
+            limits,
+            actuator,
+            reference,
+        )
+    }
+}
+
+/// Trajectories of the closed loop simulation of a controller and a plant
+#[derive(Clone, Debug, PartialEq)]
+pub struct LoopResponse<T: Float> {
+    /// Sample times
+    pub(super) time: Vec<Seconds<T>>,
+    /// Reference signal
+    pub(super) reference: Vec<T>,
+    /// Error between reference and output
+    pub(super) error: Vec<T>,
+    /// Control action
+    pub(super) control: Vec<T>,
+    /// Plant output
+    pub(super) output: Vec<T>,
+}
+
+impl<T: Float> LoopResponse<T> {
+    /// Get the sample times
+    #[must_use]
+    pub fn time(&self) -> &[Seconds<T>] {
+        &self.time
+    }
+
+    /// Get the reference signal
+    #[must_use]
+    pub fn reference(&self) -> &[T] {
+        &self.reference
+    }
+
+    /// Get the error between reference and measured output
+    #[must_use]
+    pub fn error(&self) -> &[T] {
+        &self.error
+    }
+
+    /// Get the control action
+    #[must_use]
+    pub fn control(&self) -> &[T] {
+        &self.control
+    }
+
+    /// Get the plant output
+    #[must_use]
+    pub fn output(&self) -> &[T] {
+        &self.output
+    }
 }
 
 #[cfg(test)]
 mod pid_tests {
     use super::*;
-    use crate::units::ToDecibel;
+    use crate::{error::ErrorKind, poly, units::ToDecibel};
     use num_complex::Complex64;
 
     #[test]
@@ -158,4 +927,239 @@ mod pid_tests {
         let c = l.eval(&Complex64::new(0., critical_freq));
         assert_abs_diff_eq!(0., c.norm().to_db(), epsilon = 0.1);
     }
+
+    #[test]
+    fn discrete_transfer_function() {
+        let ts = Seconds(0.1);
+        let z = Complex64::from_polar(1., 0.3);
+        let methods = [
+            (Discretization::ForwardEuler, (z - 1.) / ts.0),
+            (Discretization::BackwardEuler, (z - 1.) / (z * ts.0)),
+            (Discretization::Tustin, (z - 1.) / (z + 1.) * 2. / ts.0),
+        ];
+        for pid in &[Pid::new_ideal(2., 0.5, 0.1), Pid::new(2., 0.5, 0.1, 5.)] {
+            for &(method, s) in &methods {
+                let expected = pid.tf().eval(&s);
+                let actual = pid.to_tfz(ts, method).eval(&z);
+                assert_relative_eq!(expected.re, actual.re, max_relative = 1e-10);
+                assert_relative_eq!(expected.im, actual.im, max_relative = 1e-10);
+            }
+        }
+    }
+
+    #[test]
+    fn no_integral_action() {
+        let pd = Pid::new_ideal(2., f64::INFINITY, 0.5);
+        assert_eq!(Tf::new(poly![2., 1.], poly![1.]), pd.tf());
+        let pd = Pid::new(2., f64::INFINITY, 0.5, 10.);
+        let c = pd.tf().eval(&Complex64::new(0., 1.));
+        let expected = 2. * (1. + Complex64::new(0., 0.5) / Complex64::new(1., 0.05));
+        assert_relative_eq!(expected.re, c.re, max_relative = 1e-12);
+        assert_relative_eq!(expected.im, c.im, max_relative = 1e-12);
+    }
+
+    #[test]
+    fn closed_loop_first_samples() {
+        let plant = Ssd::new_from_slice(1, 1, 1, &[0.5], &[1.], &[1.], &[0.]);
+        let pid = Pid::new_ideal(2., 0.5, 0.1);
+        let st = Seconds(0.1);
+        let inf = f64::INFINITY;
+        let resp = pid
+            .closed_loop_discrete(&plant, st, (-inf, inf), &[1., 1., 1.])
+            .unwrap();
+        assert_eq!(3, resp.time().len());
+        assert_eq!(Seconds(0.2), resp.time()[2]);
+        assert_eq!(&[1., 1., 1.], resp.reference());
+        // u0 = kp*e0 + kp*st/ti*e0, no derivative on the first sample.
+        assert_relative_eq!(1., resp.error()[0]);
+        assert_relative_eq!(2. + 0.4, resp.control()[0]);
+        assert_relative_eq!(0., resp.output()[0]);
+        // y1 = 0.5*0 + 2.4, e1 = -1.4
+        assert_relative_eq!(2.4, resp.output()[1]);
+        assert_relative_eq!(-1.4, resp.error()[1]);
+        let expected = 2. * -1.4 + (0.4 - 0.4 * 1.4) + 2. * 0.1 * (-2.4) / 0.1;
+        assert_relative_eq!(expected, resp.control()[1]);
+    }
+
+    #[test]
+    fn discrete_pid_matches_closed_loop() {
+        let plant = Ssd::new_from_slice(1, 1, 1, &[0.9], &[0.1], &[1.], &[0.]);
+        let pid = Pid::new(2., 0.5, 0.2, 5.);
+        let st = Seconds(0.1);
+        let limits = (-3., 3.);
+        let resp = pid
+            .closed_loop_discrete(&plant, st, limits, &[1.; 50])
+            .unwrap();
+        let mut discrete = pid.discretize(st).with_limits(limits);
+        assert_eq!(st, discrete.sample_time());
+        assert_eq!(limits, discrete.limits());
+        let mut x = 0.;
+        for &u in resp.control() {
+            let y = x;
+            assert_relative_eq!(u, discrete.step(1., y));
+            x = 0.9 * x + 0.1 * u;
+        }
+    }
+
+    #[test]
+    fn discrete_pid_reset() {
+        let mut pid = Pid::new(1., 2., 0.5, 10.).discretize(Seconds(0.01));
+        let first = pid.step(1., 0.);
+        let second = pid.step(1., 0.2);
+        assert!(first != second);
+        pid.reset();
+        assert_relative_eq!(first, pid.step(1., 0.));
+    }
+
+    #[test]
+    fn discrete_pid_bumpless_update() {
+        let mut pid = Pid::new_ideal(2., 1., 0.).discretize(Seconds(0.1));
+        pid.step(1., 0.);
+        let u = pid.step(1., 0.3);
+        let mut updated = pid.clone();
+        updated.set_parameters(&Pid::new_ideal(5., 2., 0.));
+        // Same control action on the same error, apart from the integral
+        // increment.
+        let mut same = pid.clone();
+        assert_relative_eq!(u + 0.2 * 0.7, same.step(1., 0.3), max_relative = 1e-12);
+        assert_relative_eq!(u + 0.25 * 0.7, updated.step(1., 0.3), max_relative = 1e-12);
+        assert_eq!(pid.sample_time(), updated.sample_time());
+    }
+
+    #[test]
+    #[should_panic]
+    fn discrete_pid_wrong_limits() {
+        let _ = Pid::new_ideal(1., 1., 0.)
+            .discretize(Seconds(0.1))
+            .with_limits((1., -1.));
+    }
+
+    #[test]
+    fn velocity_pid_matches_positional() {
+        let pid = Pid::new(2., 0.5, 0.2, 5.);
+        let st = Seconds(0.1);
+        let mut positional = pid.discretize(st);
+        let mut velocity = pid.discretize_velocity(st);
+        assert_eq!(st, velocity.sample_time());
+        let mut sum = 0.;
+        for k in 0..30 {
+            let y = (f64::from(k) * 0.3).sin();
+            sum += velocity.step(1., y);
+            assert_relative_eq!(positional.step(1., y), sum, max_relative = 1e-10);
+        }
+        assert_relative_eq!(sum, velocity.output(), max_relative = 1e-12);
+    }
+
+    #[test]
+    fn velocity_pid_no_windup() {
+        let mut pid = Pid::new_ideal(1., 0.5, 0.)
+            .discretize_velocity(Seconds(0.1))
+            .with_limits((-1., 1.))
+            .with_initial_output(0.5);
+        assert_eq!((-1., 1.), pid.limits());
+        // Long saturation with a positive error.
+        for _ in 0..100 {
+            pid.step(2., 0.);
+        }
+        assert_relative_eq!(1., pid.output());
+        // The control action leaves the limit as soon as the error changes sign.
+        let du = pid.step(0., 0.5);
+        assert!(du < 0.);
+        assert!(pid.output() < 1.);
+        pid.reset();
+        assert_relative_eq!(0.5, pid.output());
+    }
+
+    #[test]
+    fn velocity_pid_update() {
+        let mut pid = Pid::new_ideal(2., 1., 0.).discretize_velocity(Seconds(0.1));
+        pid.step(1., 0.);
+        let u = pid.output();
+        pid.set_parameters(&Pid::new_ideal(5., 2., 0.));
+        // No bump with the same error, only the new integral increment.
+        assert_relative_eq!(0.25, pid.step(1., 0.), max_relative = 1e-12);
+        assert_relative_eq!(u + 0.25, pid.output(), max_relative = 1e-12);
+        let du = Controller::step(&mut pid, Seconds(0.2), 1.);
+        assert_relative_eq!(0.25, du.0, max_relative = 1e-12);
+    }
+
+    #[test]
+    fn closed_loop_continuous_plant() {
+        let plant = Ss::new_from_slice(1, 1, 1, &[-1.], &[1.], &[1.], &[0.]);
+        let pid = Pid::new(1.5, 0.8, 0.2, 8.);
+        let inf = f64::INFINITY;
+        let resp = pid
+            .closed_loop(&plant, Seconds(0.01), (-inf, inf), &[2.; 2000])
+            .unwrap();
+        assert_relative_eq!(2., *resp.output().last().unwrap(), max_relative = 1e-6);
+        assert_relative_eq!(0., *resp.error().last().unwrap(), epsilon = 1e-6);
+    }
+
+    #[test]
+    fn closed_loop_saturation() {
+        let plant = Ss::new_from_slice(1, 1, 1, &[-1.], &[1.], &[1.], &[0.]);
+        let pid = Pid::new_ideal(10., 0.5, 0.);
+        let limits = (-1., 1.5);
+        let resp = pid
+            .closed_loop(&plant, Seconds(0.01), limits, &[1.; 3000])
+            .unwrap();
+        assert!(resp
+            .control()
+            .iter()
+            .all(|&u| u >= limits.0 && u <= limits.1));
+        assert_relative_eq!(1.5, resp.control()[0]);
+        // Anti-windup avoids a large overshoot.
+        assert!(resp.output().iter().all(|&y| y < 1.05));
+        assert_relative_eq!(1., *resp.output().last().unwrap(), max_relative = 1e-6);
+    }
+
+    #[test]
+    fn closed_loop_actuator() {
+        use crate::controller::blocks::{DeadZone, Saturation};
+        let plant = Ss::new_from_slice(1, 1, 1, &[-1.], &[1.], &[1.], &[0.]);
+        let pid = Pid::new_ideal(2., 0.5, 0.);
+        let inf = f64::INFINITY;
+        // The integral action compensates the dead zone of the actuator.
+        let actuator = DeadZone::new(-0.5, 0.5).chain(Saturation::new(-2., 2.));
+        let resp = pid
+            .closed_loop_with_actuator(&plant, Seconds(0.01), (-inf, inf), actuator, &[1.; 3000])
+            .unwrap();
+        assert!(resp.control().iter().all(|u| (-2. ..=2.).contains(u)));
+        assert_relative_eq!(1., *resp.control().last().unwrap(), max_relative = 1e-6);
+        assert_relative_eq!(1., *resp.output().last().unwrap(), max_relative = 1e-6);
+    }
+
+    #[test]
+    fn closed_loop_no_siso() {
+        let plant = Ssd::new_from_slice(1, 2, 1, &[0.5], &[1., 1.], &[1.], &[0., 0.]);
+        let pid = Pid::new_ideal(1., 1., 0.);
+        let res = pid.closed_loop_discrete(&plant, Seconds(0.1), (-1., 1.), &[1.]);
+        assert_eq!(ErrorKind::NoSisoSystem, res.unwrap_err().kind());
+
+        let plant = Ssd::new_from_slice(1, 1, 1, &[0.5], &[1.], &[1.], &[0.]);
+        let res = pid
+            .closed_loop_discrete(&plant, Seconds(0.1), (-1., 1.), &[])
+            .unwrap();
+        assert!(res.output().is_empty());
+    }
+
+    #[cfg(feature = "serde-serialize")]
+    #[test]
+    fn serde_round_trip() {
+        // Proportional controller, without integral action.
+        let p =
+            Pid::tune_ziegler_nichols_ultimate(8., 2., crate::controller::tuning::PidStructure::P);
+        assert!(p.ti().is_infinite());
+        let json = serde_json::to_string(&p).unwrap();
+        assert_eq!(p, serde_json::from_str(&json).unwrap());
+
+        // Discrete controller without limits.
+        let pid = Pid::new(2., 1., 0.5, 10.).discretize(Seconds(0.1_f32));
+        let json = serde_json::to_string(&pid).unwrap();
+        assert_eq!(pid, serde_json::from_str(&json).unwrap());
+
+        let pid = Pid::new_ideal(2., 1., 0.).discretize_velocity(Seconds(0.1));
+        let json = serde_json::to_string(&pid).unwrap();
+        assert_eq!(pid, serde_json::from_str(&json).unwrap());
+    }
 }
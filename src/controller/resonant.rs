@@ -0,0 +1,563 @@
+//! # Resonant and repetitive controllers
+//!
+//! Controllers for the tracking of periodic references and the rejection of
+//! periodic disturbances, based on the internal model principle.
+//! * proportional-resonant controller, with infinite (or high) gain at a
+//!   fundamental frequency and at a selected set of its harmonics
+//! * repetitive controller, a discrete delay line that contains the model of
+//!   every harmonic of a periodic signal
+//!
+//! Both controllers are executed in a sampled loop by a
+//! [`DiscreteController`](struct.DiscreteController.html).
+
+use num_traits::Float;
+
+use crate::{
+    controller::{feedforward::DiscreteFeedforward, Controller},
+    polynomial::Poly,
+    transfer_function::{continuous::Tf, discrete::Tfz},
+    units::{RadiansPerSecond, Seconds},
+};
+
+/// Proportional-resonant controller
+/// ```text
+///                     2*wc*s
+/// kp + sum kr --------------------------
+///             s^2 + 2*wc*s + (h*w0)^2
+/// ```
+/// where the sum is extended to the harmonics `h`. When the damping `wc` is
+/// null the controller is ideal and its gain is infinite at the resonance,
+/// the resonant terms become `kr*s / (s^2 + (h*w0)^2)`.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde-serialize",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub struct ProportionalResonant<T: Float> {
+    /// Proportional gain
+    kp: T,
+    /// Resonant gain
+    kr: T,
+    /// Fundamental angular frequency
+    w0: RadiansPerSecond<T>,
+    /// Harmonics of the fundamental frequency
+    harmonics: Vec<u32>,
+    /// Damping angular frequency of the resonant terms
+    wc: T,
+}
+
+impl<T: Float> ProportionalResonant<T> {
+    /// Create an ideal proportional-resonant controller tuned on the
+    /// fundamental frequency.
+    ///
+    /// # Arguments
+    ///
+    /// * `kp` - Proportional gain
+    /// * `kr` - Resonant gain
+    /// * `w0` - Fundamental angular frequency
+    ///
+    /// # Panics
+    ///
+    /// Panics if the fundamental frequency is not positive.
+    ///
+    /// # Example
+    /// ```
+    /// use au::{controller::resonant::ProportionalResonant, RadiansPerSecond};
+    /// let pr = ProportionalResonant::new(1., 10., RadiansPerSecond(50.));
+    /// assert_eq!(&[1], pr.harmonics());
+    /// ```
+    #[must_use]
+    pub fn new(kp: T, kr: T, w0: RadiansPerSecond<T>) -> Self {
+        assert!(w0.0 > T::zero(), "Fundamental frequency must be positive");
+        Self {
+            kp,
+            kr,
+            w0,
+            harmonics: vec![1],
+            wc: T::zero(),
+        }
+    }
+
+    /// Set the harmonics of the fundamental frequency that have a resonant
+    /// term. Duplicated harmonics are removed.
+    ///
+    /// # Arguments
+    ///
+    /// * `harmonics` - Harmonic numbers, 1 is the fundamental frequency
+    ///
+    /// # Panics
+    ///
+    /// Panics if the list is empty or contains zero.
+    ///
+    /// # Example
+    /// ```
+    /// use au::{controller::resonant::ProportionalResonant, RadiansPerSecond};
+    /// let pr = ProportionalResonant::new(1., 10., RadiansPerSecond(50.)).with_harmonics(&[5, 1, 3]);
+    /// assert_eq!(&[1, 3, 5], pr.harmonics());
+    /// ```
+    #[must_use]
+    pub fn with_harmonics(mut self, harmonics: &[u32]) -> Self {
+        assert!(
+            !harmonics.is_empty() && !harmonics.contains(&0),
+            "Harmonics must be a non empty list of positive numbers"
+        );
+        self.harmonics = harmonics.to_vec();
+        self.harmonics.sort_unstable();
+        self.harmonics.dedup();
+        self
+    }
+
+    /// Set the damping of the resonant terms (non ideal controller), the
+    /// gain of each term at its resonance is `kr`.
+    ///
+    /// # Arguments
+    ///
+    /// * `wc` - Damping angular frequency
+    ///
+    /// # Panics
+    ///
+    /// Panics if the damping is negative.
+    #[must_use]
+    pub fn with_damping(mut self, wc: RadiansPerSecond<T>) -> Self {
+        assert!(wc.0 >= T::zero(), "Damping must not be negative");
+        self.wc = wc.0;
+        self
+    }
+
+    /// Get the proportional gain
+    #[must_use]
+    pub fn kp(&self) -> T {
+        self.kp
+    }
+
+    /// Get the resonant gain
+    #[must_use]
+    pub fn kr(&self) -> T {
+        self.kr
+    }
+
+    /// Get the fundamental angular frequency
+    #[must_use]
+    pub fn fundamental(&self) -> RadiansPerSecond<T> {
+        self.w0
+    }
+
+    /// Get the harmonics with a resonant term
+    #[must_use]
+    pub fn harmonics(&self) -> &[u32] {
+        &self.harmonics
+    }
+
+    /// Resonant term of the given harmonic.
+    fn resonant_term(&self, harmonic: u32) -> Tf<T> {
+        let w = T::from(harmonic).unwrap() * self.w0.0;
+        let two = T::one() + T::one();
+        let num = if self.wc == T::zero() {
+            self.kr
+        } else {
+            two * self.wc * self.kr
+        };
+        Tf::new(
+            Poly::new_from_coeffs(&[T::zero(), num]),
+            Poly::new_from_coeffs(&[w * w, two * self.wc, T::one()]),
+        )
+    }
+
+    /// Transfer function of the controller
+    ///
+    /// # Example
+    /// ```
+    /// use au::{controller::resonant::ProportionalResonant, RadiansPerSecond};
+    /// let pr = ProportionalResonant::new(2_f64, 10., RadiansPerSecond(3.));
+    /// let poles = pr.tf().complex_poles();
+    /// assert!(poles.iter().all(|p| p.re.abs() < 1e-12 && (p.im.abs() - 3_f64).abs() < 1e-12));
+    /// ```
+    #[must_use]
+    pub fn tf(&self) -> Tf<T> {
+        let kp = Tf::new(
+            Poly::new_from_coeffs(&[self.kp]),
+            Poly::new_from_coeffs(&[T::one()]),
+        );
+        self.harmonics
+            .iter()
+            .fold(kp, |acc, &h| &acc + &self.resonant_term(h))
+    }
+
+    /// Discrete transfer function of the controller.
+    ///
+    /// Each resonant term is discretized with the Tustin method pre-warped at
+    /// its resonance frequency, so that the resonance of the discrete
+    /// controller is at the same frequency of the continuous one.
+    ///
+    /// # Arguments
+    ///
+    /// * `ts` - Sampling period
+    ///
+    /// # Panics
+    ///
+    /// Panics if the sampling period is not positive or a resonance frequency
+    /// is not lower than the Nyquist frequency.
+    ///
+    /// # Example
+    /// ```
+    /// use au::{controller::resonant::ProportionalResonant, RadiansPerSecond, Seconds};
+    /// let pr = ProportionalResonant::new(1., 10., RadiansPerSecond(2.));
+    /// let tfz = pr.to_tfz(Seconds(0.1));
+    /// let poles = tfz.complex_poles();
+    /// assert!(poles.iter().all(|p| (p.norm() - 1_f64).abs() < 1e-10));
+    /// assert!(poles.iter().all(|p| (p.arg().abs() - 0.2_f64).abs() < 1e-10));
+    /// ```
+    #[must_use]
+    pub fn to_tfz(&self, ts: Seconds<T>) -> Tfz<T> {
+        assert!(ts.0 > T::zero(), "Sampling period must be positive");
+        let kp = Tfz::new(
+            Poly::new_from_coeffs(&[self.kp]),
+            Poly::new_from_coeffs(&[T::one()]),
+        );
+        self.harmonics.iter().fold(kp, |acc, &h| {
+            let w = T::from(h).unwrap() * self.w0.0;
+            assert!(
+                w * ts.0 < T::from(std::f64::consts::PI).unwrap(),
+                "Resonance frequency must be lower than the Nyquist frequency"
+            );
+            let term = self
+                .resonant_term(h)
+                .discretize_with_warp(ts, RadiansPerSecond(w));
+            &acc + &term
+        })
+    }
+
+    /// Discrete controller with internal state, see `to_tfz` for the
+    /// discretization.
+    ///
+    /// # Arguments
+    ///
+    /// * `ts` - Sampling period
+    ///
+    /// # Panics
+    ///
+    /// Panics if the sampling period is not positive or a resonance frequency
+    /// is not lower than the Nyquist frequency.
+    #[must_use]
+    pub fn discretize(&self, ts: Seconds<T>) -> DiscreteController<T> {
+        DiscreteController::new(&self.to_tfz(ts))
+    }
+}
+
+/// Discrete repetitive controller
+/// ```text
+///           kr * q * z^m
+/// kp + ------------------
+///            z^N - q
+/// ```
+/// where `N` is the number of samples in a period of the reference, `q` is
+/// the attenuation of the delay line and `m` is the phase lead in samples
+/// that compensates the delay of the plant.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde-serialize",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub struct Repetitive<T: Float> {
+    /// Proportional gain
+    kp: T,
+    /// Repetitive gain
+    kr: T,
+    /// Number of samples of the period
+    samples: usize,
+    /// Attenuation of the delay line
+    q: T,
+    /// Phase lead in samples
+    lead: usize,
+}
+
+impl<T: Float> Repetitive<T> {
+    /// Create a repetitive controller for signals of the given period.
+    /// The delay line has no attenuation and no phase lead.
+    ///
+    /// # Arguments
+    ///
+    /// * `kp` - Proportional gain
+    /// * `kr` - Repetitive gain
+    /// * `period` - Period of the reference signal
+    /// * `ts` - Sampling period
+    ///
+    /// # Panics
+    ///
+    /// Panics if the sampling period is not positive or the period is
+    /// shorter than the sampling period.
+    ///
+    /// # Example
+    /// ```
+    /// use au::{controller::resonant::Repetitive, Seconds};
+    /// let rc = Repetitive::new(1., 0.5, Seconds(0.02), Seconds(0.001));
+    /// assert_eq!(20, rc.samples());
+    /// ```
+    #[must_use]
+    pub fn new(kp: T, kr: T, period: Seconds<T>, ts: Seconds<T>) -> Self {
+        assert!(ts.0 > T::zero(), "Sampling period must be positive");
+        let samples = (period.0 / ts.0).round().to_usize().unwrap_or(0);
+        assert!(
+            samples > 0,
+            "Period must not be shorter than sampling period"
+        );
+        Self {
+            kp,
+            kr,
+            samples,
+            q: T::one(),
+            lead: 0,
+        }
+    }
+
+    /// Set the attenuation of the delay line, that improves the robustness
+    /// of the loop at the price of a non null tracking error.
+    ///
+    /// # Arguments
+    ///
+    /// * `q` - Attenuation, between zero (excluded) and one
+    ///
+    /// # Panics
+    ///
+    /// Panics if the attenuation is not in the interval (0, 1].
+    #[must_use]
+    pub fn with_attenuation(mut self, q: T) -> Self {
+        assert!(
+            q > T::zero() && q <= T::one(),
+            "Attenuation must be in the interval (0, 1]"
+        );
+        self.q = q;
+        self
+    }
+
+    /// Set the phase lead of the repetitive term.
+    ///
+    /// # Arguments
+    ///
+    /// * `lead` - Phase lead in samples
+    ///
+    /// # Panics
+    ///
+    /// Panics if the lead is not shorter than the period.
+    #[must_use]
+    pub fn with_lead(mut self, lead: usize) -> Self {
+        assert!(lead < self.samples, "Lead must be shorter than the period");
+        self.lead = lead;
+        self
+    }
+
+    /// Get the number of samples of the period
+    #[must_use]
+    pub fn samples(&self) -> usize {
+        self.samples
+    }
+
+    /// Discrete transfer function of the controller
+    ///
+    /// # Example
+    /// ```
+    /// use au::{controller::resonant::Repetitive, Seconds};
+    /// let rc = Repetitive::new(1., 0.5, Seconds(4.), Seconds(1.));
+    /// let tfz = rc.tfz();
+    /// assert_eq!(Some(4), tfz.den().degree());
+    /// ```
+    #[must_use]
+    pub fn tfz(&self) -> Tfz<T> {
+        let mut den = vec![T::zero(); self.samples + 1];
+        den[0] = -self.q;
+        den[self.samples] = T::one();
+        let mut num: Vec<_> = den.iter().map(|&d| self.kp * d).collect();
+        num[self.lead] = num[self.lead] + self.kr * self.q;
+        Tfz::new(Poly::new_from_coeffs(&num), Poly::new_from_coeffs(&den))
+    }
+
+    /// Discrete controller with internal state
+    #[must_use]
+    pub fn discretize(&self) -> DiscreteController<T> {
+        DiscreteController::new(&self.tfz())
+    }
+}
+
+/// Linear discrete controller with internal state, executed in a sampled
+/// loop.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde-serialize",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub struct DiscreteController<T: Float> {
+    /// Difference equation of the transfer function
+    filter: DiscreteFeedforward<T>,
+}
+
+impl<T: Float> DiscreteController<T> {
+    /// Create a discrete controller with a null initial state
+    ///
+    /// # Arguments
+    ///
+    /// * `tfz` - Discrete transfer function of the controller
+    ///
+    /// # Panics
+    ///
+    /// Panics if the transfer function is not causal.
+    #[must_use]
+    pub fn new(tfz: &Tfz<T>) -> Self {
+        Self {
+            filter: DiscreteFeedforward::new(tfz),
+        }
+    }
+
+    /// Calculate the control action for the current sample and update
+    /// the state of the controller.
+    ///
+    /// # Arguments
+    ///
+    /// * `error` - Error between reference and measured output
+    pub fn step(&mut self, error: T) -> T {
+        self.filter.step(error)
+    }
+
+    /// Reset the state of the controller to the initial null state.
+    pub fn reset(&mut self) {
+        self.filter.reset();
+    }
+}
+
+/// The input of the control law is the error between reference and measured
+/// output.
+impl<T: Float> Controller<T> for DiscreteController<T> {
+    type Input = T;
+    type Output = T;
+
+    fn step(&mut self, _time: Seconds<T>, error: T) -> T {
+        DiscreteController::step(self, error)
+    }
+
+    fn reset(&mut self) {
+        DiscreteController::reset(self);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_complex::Complex64;
+
+    /// Closed loop with the plant `y(k+1) = 0.9*y(k) + 0.1*u(k)`, returns
+    /// the errors.
+    fn track(controller: &mut DiscreteController<f64>, reference: &[f64]) -> Vec<f64> {
+        let mut y = 0.;
+        reference
+            .iter()
+            .map(|&r| {
+                let e = r - y;
+                let u = controller.step(e);
+                y = 0.9 * y + 0.1 * u;
+                e
+            })
+            .collect()
+    }
+
+    #[test]
+    fn resonant_gain() {
+        let pr = ProportionalResonant::new(1., 5., RadiansPerSecond(2.))
+            .with_harmonics(&[1, 3])
+            .with_damping(RadiansPerSecond(0.1));
+        let tf = pr.tf();
+        for &h in &[1., 3.] {
+            let c = tf.eval(&Complex64::new(0., 2. * h));
+            // Proportional term, resonant term and the other resonant term.
+            assert!(c.re > 5.9);
+        }
+        let ideal = ProportionalResonant::new(1., 5., RadiansPerSecond(2.)).with_harmonics(&[2]);
+        let mut poles = ideal.tf().complex_poles();
+        poles.sort_by(|a, b| a.im.partial_cmp(&b.im).unwrap());
+        assert_relative_eq!(-4., poles[0].im, max_relative = 1e-10);
+        assert_relative_eq!(4., poles[1].im, max_relative = 1e-10);
+    }
+
+    #[test]
+    fn resonant_tracking() {
+        let w0 = 2. * std::f64::consts::PI * 5.;
+        let ts = 0.001;
+        let pr = ProportionalResonant::new(2., 50., RadiansPerSecond(w0)).with_harmonics(&[1, 3]);
+        let mut controller = pr.discretize(Seconds(ts));
+        let reference: Vec<_> = (0..20_000)
+            .map(|k| {
+                let t = k as f64 * ts;
+                (w0 * t).sin() + 0.3 * (3. * w0 * t).cos()
+            })
+            .collect();
+        let errors = track(&mut controller, &reference);
+        let last = errors
+            .iter()
+            .rev()
+            .take(1000)
+            .fold(0., |m: f64, e| m.max(e.abs()));
+        assert!(last < 1e-3);
+        controller.reset();
+        let mut fresh = pr.discretize(Seconds(ts));
+        assert_relative_eq!(fresh.step(1.), controller.step(1.));
+    }
+
+    #[test]
+    fn repetitive_tracking() {
+        let rc = Repetitive::new(1., 0.5, Seconds(0.02), Seconds(0.001)).with_lead(1);
+        let tfz = rc.tfz();
+        assert_eq!(Some(20), tfz.den().degree());
+        let mut controller = rc.discretize();
+        // Triangular wave with a period of 20 samples.
+        let reference: Vec<_> = (0..4000)
+            .map(|k| {
+                let phase = (k % 20) as f64;
+                if phase < 10. {
+                    phase / 10.
+                } else {
+                    2. - phase / 10.
+                }
+            })
+            .collect();
+        let errors = track(&mut controller, &reference);
+        let last = errors
+            .iter()
+            .rev()
+            .take(20)
+            .fold(0., |m: f64, e| m.max(e.abs()));
+        assert!(last < 1e-3);
+        // Error in the first period.
+        assert!(errors.iter().take(20).any(|e| e.abs() > 0.5));
+    }
+
+    #[test]
+    fn repetitive_attenuation() {
+        let rc = Repetitive::new(0., 1., Seconds(3.), Seconds(1.)).with_attenuation(0.5);
+        let mut controller = rc.discretize();
+        let out: Vec<_> = (0..7)
+            .map(|k| controller.step(if k == 0 { 1. } else { 0. }))
+            .collect();
+        assert_eq!(vec![0., 0., 0., 0.5, 0., 0., 0.25], out);
+    }
+
+    #[test]
+    #[should_panic]
+    fn resonance_over_nyquist() {
+        let pr = ProportionalResonant::new(1., 1., RadiansPerSecond(40.));
+        let _ = pr.to_tfz(Seconds(0.1));
+    }
+
+    #[cfg(feature = "serde-serialize")]
+    #[test]
+    fn serde_round_trip() {
+        let pr = ProportionalResonant::new(1., 10., RadiansPerSecond(50.)).with_harmonics(&[1, 3]);
+        let json = serde_json::to_string(&pr).unwrap();
+        assert_eq!(pr, serde_json::from_str(&json).unwrap());
+
+        let rc = Repetitive::new(1., 0.5, Seconds(0.02), Seconds(0.001)).with_lead(1);
+        let json = serde_json::to_string(&rc).unwrap();
+        assert_eq!(rc, serde_json::from_str(&json).unwrap());
+
+        let ctrl = pr.discretize(Seconds(0.001));
+        let json = serde_json::to_string(&ctrl).unwrap();
+        assert_eq!(ctrl, serde_json::from_str(&json).unwrap());
+    }
+}
@@ -0,0 +1,220 @@
+//! # RST controller
+//!
+//! Two degrees of freedom polynomial controller for discrete plants
+//! `G(z) = B(z)/A(z)`:
+//! ```text
+//! R(z)*u = T(z)*r - S(z)*y
+//! ```
+//! The closed loop transfer function from the reference is
+//! `B(z)*T(z) / (A(z)*R(z) + B(z)*S(z))`.
+//!
+//! The pole placement design solves the Diophantine equation
+//! `A(z)*R(z) + B(z)*S(z) = Acl(z)` for the desired closed loop
+//! characteristic polynomial `Acl(z)`.
+
+use nalgebra::{ComplexField, RealField};
+use num_traits::Float;
+
+use crate::{
+    error::{Error, ErrorKind},
+    polynomial::Poly,
+    transfer_function::discrete::Tfz,
+};
+
+/// RST controller
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde-serialize",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub struct Rst<T: Float> {
+    /// Polynomial that multiplies the control action
+    r: Poly<T>,
+    /// Polynomial that multiplies the plant output
+    s: Poly<T>,
+    /// Polynomial that multiplies the reference
+    t: Poly<T>,
+}
+
+impl<T: Float> Rst<T> {
+    /// Create a RST controller from its polynomials
+    ///
+    /// # Arguments
+    ///
+    /// * `r` - Polynomial that multiplies the control action
+    /// * `s` - Polynomial that multiplies the plant output
+    /// * `t` - Polynomial that multiplies the reference
+    #[must_use]
+    pub fn new(r: Poly<T>, s: Poly<T>, t: Poly<T>) -> Self {
+        Self { r, s, t }
+    }
+
+    /// Get the polynomial that multiplies the control action
+    #[must_use]
+    pub fn r(&self) -> &Poly<T> {
+        &self.r
+    }
+
+    /// Get the polynomial that multiplies the plant output
+    #[must_use]
+    pub fn s(&self) -> &Poly<T> {
+        &self.s
+    }
+
+    /// Get the polynomial that multiplies the reference
+    #[must_use]
+    pub fn t(&self) -> &Poly<T> {
+        &self.t
+    }
+
+    /// Feedback part of the controller `S(z)/R(z)`
+    #[must_use]
+    pub fn feedback(&self) -> Tfz<T> {
+        Tfz::new(self.s.clone(), self.r.clone())
+    }
+
+    /// Feedforward part of the controller `T(z)/R(z)`
+    #[must_use]
+    pub fn feedforward(&self) -> Tfz<T> {
+        Tfz::new(self.t.clone(), self.r.clone())
+    }
+
+    /// Closed loop transfer function from the reference to the plant output
+    ///
+    /// # Arguments
+    ///
+    /// * `plant` - Discrete plant
+    #[must_use]
+    pub fn closed_loop(&self, plant: &Tfz<T>) -> Tfz<T> {
+        Tfz::new(
+            plant.num() * &self.t,
+            plant.den() * &self.r + plant.num() * &self.s,
+        )
+    }
+}
+
+impl<T: ComplexField + Float + RealField> Rst<T> {
+    /// Design the RST controller that places the poles of the closed loop in
+    /// the roots of the given characteristic polynomial. The reference
+    /// polynomial `T(z)` is the constant that gives a unitary static gain of
+    /// the closed loop.
+    ///
+    /// With the integral action `R(z)` has the factor `(z - 1)`, so that
+    /// constant disturbances are rejected.
+    ///
+    /// The controller is causal if the degree of the characteristic polynomial
+    /// is at least `2n - 1`, or `2n` with integral action, where `n` is the
+    /// degree of `A(z)`.
+    ///
+    /// # Arguments
+    ///
+    /// * `plant` - Discrete plant
+    /// * `closed_loop` - Desired characteristic polynomial of the closed loop
+    /// * `integral` - Add the integral action
+    ///
+    /// # Errors
+    ///
+    /// It returns an error if the plant is not proper, the numerator and the
+    /// denominator of the plant have a common factor, the plant has a null
+    /// static gain or the degree of the characteristic polynomial is too low.
+    ///
+    /// # Example
+    /// ```
+    /// use au::{controller::rst::Rst, poly, Poly, Tfz};
+    /// let plant = Tfz::new(poly!(0.5), poly!(-0.9, 1.));
+    /// let rst = Rst::pole_placement(&plant, &Poly::new_from_roots(&[0.2, 0.3]), true).unwrap();
+    /// let step: Vec<_> = rst.closed_loop(&plant).arma_iter(std::iter::repeat(1.)).take(30).collect();
+    /// assert!((step[29] - 1_f64).abs() < 1e-10);
+    /// ```
+    pub fn pole_placement(
+        plant: &Tfz<T>,
+        closed_loop: &Poly<T>,
+        integral: bool,
+    ) -> Result<Self, Error> {
+        let (b, a) = (plant.num(), plant.den());
+        let n = a.degree().unwrap_or(0);
+        let m = b.degree().unwrap_or(0);
+        if m > n {
+            return Err(Error::new_internal(ErrorKind::ImproperTransferFunction));
+        }
+        let unfeasible = || Error::new_internal(ErrorKind::UnfeasibleSpecification);
+        let fixed = if integral {
+            Poly::new_from_coeffs(&[-T::one(), T::one()])
+        } else {
+            Poly::new_from_coeffs(&[T::one()])
+        };
+        let a_fixed = a * &fixed;
+        if n == 0 || closed_loop.degree().unwrap_or(0) + usize::from(!integral) < 2 * n {
+            return Err(unfeasible());
+        }
+        let (r, s) = Poly::diophantine(&a_fixed, b, closed_loop).ok_or_else(unfeasible)?;
+        let b1 = b.eval_by_val(T::one());
+        if b1 == T::zero() {
+            return Err(unfeasible());
+        }
+        let t = Poly::new_from_coeffs(&[closed_loop.eval_by_val(T::one()) / b1]);
+        Ok(Self { r: r * fixed, s, t })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::poly;
+
+    fn plant() -> Tfz<f64> {
+        Tfz::new(poly!(0.05, 0.1), poly!(0.7, -1.5, 1.))
+    }
+
+    fn check_poles(rst: &Rst<f64>, poles: &[f64]) {
+        let cl = rst.closed_loop(&plant());
+        let mut actual: Vec<_> = cl.complex_poles().iter().map(|p| p.re).collect();
+        actual.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        for (e, a) in poles.iter().zip(&actual) {
+            assert_relative_eq!(e, a, epsilon = 1e-6);
+        }
+    }
+
+    #[test]
+    fn pole_placement() {
+        let poles = [0.3, 0.4, 0.5];
+        let acl = Poly::new_from_roots(&poles);
+        let rst = Rst::pole_placement(&plant(), &acl, false).unwrap();
+        assert_eq!(Some(1), rst.r().degree());
+        assert_eq!(Some(1), rst.s().degree());
+        check_poles(&rst, &poles);
+        assert_relative_eq!(
+            1.,
+            rst.closed_loop(&plant()).static_gain(),
+            max_relative = 1e-10
+        );
+        assert_eq!(rst.s(), rst.feedback().num());
+        assert_eq!(rst.t(), rst.feedforward().num());
+    }
+
+    #[test]
+    fn integral_action() {
+        let poles = [0.2, 0.3, 0.4, 0.5];
+        let acl = Poly::new_from_roots(&poles);
+        let rst = Rst::pole_placement(&plant(), &acl, true).unwrap();
+        assert_relative_eq!(0., rst.r().eval_by_val(1.), epsilon = 1e-12);
+        check_poles(&rst, &poles);
+        // Constant input disturbance rejection: S(1) != 0 and R(1) = 0, the
+        // transfer function from the disturbance to the output B*R/Acl has
+        // null static gain.
+        let dist = Tfz::new(plant().num() * rst.r(), acl);
+        assert_relative_eq!(0., dist.static_gain(), epsilon = 1e-12);
+    }
+
+    #[test]
+    fn unfeasible_designs() {
+        let acl = Poly::new_from_roots(&[0.3, 0.4]);
+        let res = Rst::pole_placement(&plant(), &acl, false);
+        assert_eq!(ErrorKind::UnfeasibleSpecification, res.unwrap_err().kind());
+        // Common factor between numerator and denominator.
+        let plant = Tfz::new(poly!(-0.5, 1.), Poly::new_from_roots(&[0.5, 0.6]));
+        let acl = Poly::new_from_roots(&[0.1, 0.2, 0.3]);
+        let res = Rst::pole_placement(&plant, &acl, false);
+        assert_eq!(ErrorKind::UnfeasibleSpecification, res.unwrap_err().kind());
+    }
+}
@@ -0,0 +1,298 @@
+//! # Gain scheduling
+//!
+//! PID controller whose parameters depend on a scheduling variable, as the
+//! operating point of the plant. The parameters are given for a set of
+//! operating points and are linearly interpolated between them; the
+//! proportional, integral (`Kp/Ti`) and derivative (`Kp*Td`) gains are
+//! interpolated, so that the actions change smoothly also when the integral
+//! action is missing in some operating points.
+//!
+//! During the execution the parameters are updated without bumps in the
+//! control action.
+
+use num_traits::Float;
+
+use crate::{
+    controller::{
+        pid::{DiscretePid, Pid},
+        Controller,
+    },
+    units::Seconds,
+};
+
+/// Gain scheduled discrete PID controller
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde-serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "GainScheduledData<T>")
+)]
+pub struct GainScheduled<T: Float> {
+    /// Operating points, in increasing order of the scheduling variable
+    schedule: Vec<(T, Pid<T>)>,
+    /// Controller in execution
+    controller: DiscretePid<T>,
+}
+
+/// Serialized gain scheduled controller, the schedule is checked before the
+/// creation of the controller.
+#[cfg(feature = "serde-serialize")]
+#[derive(serde::Deserialize)]
+struct GainScheduledData<T: Float> {
+    /// Operating points
+    schedule: Vec<(T, Pid<T>)>,
+    /// Controller in execution
+    controller: DiscretePid<T>,
+}
+
+#[cfg(feature = "serde-serialize")]
+impl<T: Float> std::convert::TryFrom<GainScheduledData<T>> for GainScheduled<T> {
+    type Error = crate::error::Error;
+
+    /// Create the controller from the serialized data.
+    ///
+    /// # Errors
+    ///
+    /// It returns an error if the schedule is empty or the values of the
+    /// scheduling variable are not strictly increasing.
+    fn try_from(data: GainScheduledData<T>) -> Result<Self, Self::Error> {
+        if data.schedule.is_empty() || data.schedule.windows(2).any(|w| w[0].0 >= w[1].0) {
+            return Err(crate::error::Error::new_internal(
+                crate::error::ErrorKind::InvalidData,
+            ));
+        }
+        Ok(Self {
+            schedule: data.schedule,
+            controller: data.controller,
+        })
+    }
+}
+
+impl<T: Float> GainScheduled<T> {
+    /// Create a gain scheduled controller with a null initial state and no
+    /// output limits.
+    ///
+    /// # Arguments
+    ///
+    /// * `schedule` - Value of the scheduling variable and controller of each operating point
+    /// * `st` - Sample time of the controller
+    ///
+    /// # Panics
+    ///
+    /// Panics if the schedule is empty, the values of the scheduling variable
+    /// are not strictly increasing or the sample time is not positive.
+    ///
+    /// # Example
+    /// ```
+    /// use au::{controller::{pid::Pid, scheduling::GainScheduled}, Seconds};
+    /// let gs = GainScheduled::new(
+    ///     vec![(0., Pid::new_ideal(1., 2., 0.)), (10., Pid::new_ideal(3., 2., 0.))],
+    ///     Seconds(0.1),
+    /// );
+    /// assert_eq!(2., gs.pid_at(5.).kp());
+    /// ```
+    #[must_use]
+    pub fn new(schedule: Vec<(T, Pid<T>)>, st: Seconds<T>) -> Self {
+        assert!(!schedule.is_empty(), "Schedule must not be empty");
+        assert!(
+            schedule.windows(2).all(|w| w[0].0 < w[1].0),
+            "Scheduling variable must be strictly increasing"
+        );
+        let controller = schedule[0].1.discretize(st);
+        Self {
+            schedule,
+            controller,
+        }
+    }
+
+    /// Set the minimum and maximum control action.
+    ///
+    /// # Arguments
+    ///
+    /// * `limits` - Minimum and maximum control action
+    ///
+    /// # Panics
+    ///
+    /// Panics if the minimum limit is greater than the maximum one.
+    #[must_use]
+    pub fn with_limits(mut self, limits: (T, T)) -> Self {
+        self.controller = self.controller.with_limits(limits);
+        self
+    }
+
+    /// Get the operating points
+    #[must_use]
+    pub fn schedule(&self) -> &[(T, Pid<T>)] {
+        &self.schedule
+    }
+
+    /// Controller at the given value of the scheduling variable. Outside the
+    /// range of the operating points the controller of the nearest one is
+    /// returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `scheduling` - Value of the scheduling variable
+    #[must_use]
+    pub fn pid_at(&self, scheduling: T) -> Pid<T> {
+        let upper = self.schedule.iter().position(|(x, _)| *x > scheduling);
+        let (x0, a, x1, b) = match upper {
+            Some(0) => return self.schedule[0].1.clone(),
+            None => return self.schedule[self.schedule.len() - 1].1.clone(),
+            Some(i) => {
+                let (x0, a) = &self.schedule[i - 1];
+                let (x1, b) = &self.schedule[i];
+                (*x0, a, *x1, b)
+            }
+        };
+        let t = (scheduling - x0) / (x1 - x0);
+        if t <= T::zero() {
+            return a.clone();
+        }
+        let lerp = |a: T, b: T| a + (b - a) * t;
+        let kp = lerp(a.kp(), b.kp());
+        let ki = lerp(a.kp() / a.ti(), b.kp() / b.ti());
+        let kd = lerp(a.kp() * a.td(), b.kp() * b.td());
+        let (ti, td) = (kp / ki, kd / kp);
+        match (a.n(), b.n()) {
+            (Some(na), Some(nb)) => Pid::new(kp, ti, td, lerp(na, nb)),
+            (Some(n), None) | (None, Some(n)) => Pid::new(kp, ti, td, n),
+            (None, None) => Pid::new_ideal(kp, ti, td),
+        }
+    }
+
+    /// Calculate the control action of the current sample with the
+    /// controller of the current value of the scheduling variable and update
+    /// the state of the controller.
+    ///
+    /// # Arguments
+    ///
+    /// * `scheduling` - Value of the scheduling variable
+    /// * `setpoint` - Reference value
+    /// * `measurement` - Measured output of the plant
+    ///
+    /// # Example
+    /// ```
+    /// use au::{controller::{pid::Pid, scheduling::GainScheduled}, Seconds};
+    /// let mut gs = GainScheduled::new(
+    ///     vec![(0., Pid::new_ideal(1., 1., 0.)), (1., Pid::new_ideal(2., 1., 0.))],
+    ///     Seconds(0.1),
+    /// )
+    /// .with_limits((-10., 10.));
+    /// let u: f64 = gs.step(1., 1., 0.);
+    /// assert!((u - 2.2).abs() < 1e-12);
+    /// ```
+    pub fn step(&mut self, scheduling: T, setpoint: T, measurement: T) -> T {
+        let pid = self.pid_at(scheduling);
+        self.controller.set_parameters(&pid);
+        self.controller.step(setpoint, measurement)
+    }
+
+    /// Reset the state of the controller to the initial null state.
+    pub fn reset(&mut self) {
+        self.controller.reset();
+    }
+}
+
+/// The input of the control law is the value of the scheduling variable and
+/// the error between setpoint and measurement.
+impl<T: Float> Controller<T> for GainScheduled<T> {
+    type Input = (T, T);
+    type Output = T;
+
+    fn step(&mut self, _time: Seconds<T>, (scheduling, error): (T, T)) -> T {
+        GainScheduled::step(self, scheduling, error, T::zero())
+    }
+
+    fn reset(&mut self) {
+        GainScheduled::reset(self);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schedule() -> GainScheduled<f64> {
+        GainScheduled::new(
+            vec![
+                (0., Pid::new_ideal(1., f64::INFINITY, 0.)),
+                (1., Pid::new_ideal(2., 1., 0.5)),
+                (3., Pid::new(4., 4., 0.25, 10.)),
+            ],
+            Seconds(0.1),
+        )
+    }
+
+    #[cfg(feature = "serde-serialize")]
+    #[test]
+    fn serde_round_trip() {
+        let gs = schedule().with_limits((-1., 1.));
+        let json = serde_json::to_string(&gs).unwrap();
+        assert_eq!(gs, serde_json::from_str(&json).unwrap());
+
+        let empty = json.replace(&serde_json::to_string(gs.schedule()).unwrap(), "[]");
+        assert_ne!(json, empty);
+        assert!(serde_json::from_str::<GainScheduled<f64>>(&empty).is_err());
+    }
+
+    #[test]
+    fn interpolation() {
+        let gs = schedule();
+        assert_eq!(3, gs.schedule().len());
+        assert_eq!(&Pid::new_ideal(1., f64::INFINITY, 0.), &gs.pid_at(-1.));
+        assert_eq!(&Pid::new(4., 4., 0.25, 10.), &gs.pid_at(5.));
+        assert_eq!(&gs.schedule()[1].1, &gs.pid_at(1.));
+
+        // Integral gain from 0 to 2, derivative gain from 0 to 1.
+        let pid = gs.pid_at(0.5);
+        assert_relative_eq!(1.5, pid.kp());
+        assert_relative_eq!(1.5 / 1., pid.ti());
+        assert_relative_eq!(0.5 / 1.5, pid.td());
+        assert_eq!(None, pid.n());
+
+        let pid = gs.pid_at(2.);
+        assert_relative_eq!(3., pid.kp());
+        assert_relative_eq!(3. / 1.5, pid.ti());
+        assert_relative_eq!(1. / 3., pid.td());
+        assert_eq!(Some(10.), pid.n());
+    }
+
+    #[test]
+    fn bumpless_execution() {
+        let mut gs = schedule().with_limits((-100., 100.));
+        let mut fixed = Pid::new_ideal(1., f64::INFINITY, 0.).discretize(Seconds(0.1));
+        let u = gs.step(0., 1., 0.5);
+        assert_relative_eq!(fixed.step(1., 0.5), u);
+        // Change of operating point at constant error.
+        let v = gs.step(1., 1., 0.5);
+        assert_relative_eq!(u + 0.1 * 2. * 0.5, v);
+        gs.reset();
+        assert_relative_eq!(u, gs.step(0., 1., 0.5));
+    }
+
+    #[test]
+    fn controller_interface() {
+        let mut gs = schedule().with_limits((-100., 100.));
+        let mut law = schedule().with_limits((-100., 100.));
+        for &(s, e) in &[(0., 0.5), (1., 0.5), (2., -0.2)] {
+            let u = Controller::step(&mut law, Seconds(0.), (s, e));
+            assert_relative_eq!(gs.step(s, e, 0.), u);
+        }
+        Controller::reset(&mut law);
+        gs.reset();
+        assert_eq!(gs, law);
+    }
+
+    #[test]
+    #[should_panic]
+    fn unsorted_schedule() {
+        let _ = GainScheduled::new(
+            vec![
+                (1., Pid::new_ideal(1., 1., 0.)),
+                (0., Pid::new_ideal(1., 1., 0.)),
+            ],
+            Seconds(0.1),
+        );
+    }
+}
@@ -0,0 +1,451 @@
+//! # State feedback with integral action
+//!
+//! State feedback controller with the integral of the tracking error, for
+//! the continuous plant `xdot = A*x + B*u`, `y = C*x + D*u`:
+//! ```text
+//! u = -K*x - Ki*xi
+//! xi = ∫ (y - r) dt
+//! ```
+//! The integral action removes the steady state error for constant
+//! references and disturbances.
+//!
+//! The gains are calculated on the plant augmented with the integrators of
+//! the error:
+//! ```text
+//! | xdot  |   | A 0 | | x  |   | B |     |  0 |
+//! |       | = |     | |    | + |   | u + |    | r
+//! | xidot |   | C 0 | | xi |   | D |     | -I |
+//! ```
+
+use nalgebra::{ComplexField, DMatrix, DVector, RealField, Scalar};
+use num_complex::Complex;
+use num_traits::Float;
+
+use crate::{controller::Controller, error::Error, linear_system::continuous::Ss, units::Seconds};
+
+/// State feedback controller with integral action
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde-serialize",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub struct StateFeedback<T: Scalar> {
+    /// State feedback gain (mxn)
+    k: DMatrix<T>,
+    /// Integral action gain (mxp)
+    ki: DMatrix<T>,
+}
+
+impl<T: Scalar> StateFeedback<T> {
+    /// Create a state feedback controller with integral action from its
+    /// gains
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - State feedback gain (mxn)
+    /// * `ki` - Integral action gain (mxp)
+    ///
+    /// # Panics
+    ///
+    /// Panics if the gains do not have the same number of rows.
+    #[must_use]
+    pub fn new(k: DMatrix<T>, ki: DMatrix<T>) -> Self {
+        assert_eq!(k.nrows(), ki.nrows(), "Gains must have the same rows");
+        Self { k, ki }
+    }
+
+    /// Get the state feedback gain
+    #[must_use]
+    pub fn k(&self) -> &DMatrix<T> {
+        &self.k
+    }
+
+    /// Get the integral action gain
+    #[must_use]
+    pub fn ki(&self) -> &DMatrix<T> {
+        &self.ki
+    }
+}
+
+impl<T: ComplexField + Float + RealField> StateFeedback<T> {
+    /// Calculate the gains with the linear quadratic regulator of the
+    /// augmented plant.
+    ///
+    /// # Arguments
+    ///
+    /// * `plant` - Continuous plant
+    /// * `q` - Weight matrix of the augmented state `[x; xi]` ((n+p)x(n+p))
+    /// * `r` - Input weight matrix (mxm)
+    ///
+    /// # Errors
+    ///
+    /// It returns an error if the Riccati equation of the augmented plant has
+    /// no stabilizing solution.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the weight matrices dimensions do not match the augmented
+    /// plant ones.
+    ///
+    /// # Example
+    /// ```
+    /// use au::{controller::state_feedback::StateFeedback, nalgebra::DMatrix, Ss};
+    /// let plant = Ss::new_from_slice(1, 1, 1, &[-1.], &[1.], &[1.], &[0.]);
+    /// let sf = StateFeedback::from_lqr(&plant, &DMatrix::identity(2, 2), &DMatrix::identity(1, 1))
+    ///     .unwrap();
+    /// assert!(sf.closed_loop(&plant).is_stable());
+    /// ```
+    pub fn from_lqr(plant: &Ss<T>, q: &DMatrix<T>, r: &DMatrix<T>) -> Result<Self, Error> {
+        let (k_aug, _) = augmented(plant).lqr(q, r)?;
+        Ok(Self::split(plant, &k_aug))
+    }
+
+    /// Calculate the gains that place the poles of the closed loop, that has
+    /// the states of the plant and of the integrators of the error.
+    ///
+    /// # Arguments
+    ///
+    /// * `plant` - Continuous plant
+    /// * `poles` - Desired poles of the closed loop (n+p)
+    ///
+    /// # Errors
+    ///
+    /// It returns an error if the augmented plant is not controllable or the
+    /// poles cannot be placed, see `SsGen::place`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of poles is not equal to the number of states of
+    /// the augmented plant.
+    ///
+    /// # Example
+    /// ```
+    /// use au::{controller::state_feedback::StateFeedback, num_complex::Complex, Ss};
+    /// let plant = Ss::new_from_slice(1, 1, 1, &[-1.], &[1.], &[1.], &[0.]);
+    /// let poles = [Complex::new(-2., 0.), Complex::new(-3., 0.)];
+    /// let sf = StateFeedback::from_place(&plant, &poles).unwrap();
+    /// assert!((sf.k()[(0, 0)] - 4_f64).abs() < 1e-10);
+    /// assert!((sf.ki()[(0, 0)] - 6_f64).abs() < 1e-10);
+    /// ```
+    pub fn from_place(plant: &Ss<T>, poles: &[Complex<T>]) -> Result<Self, Error> {
+        let k_aug = augmented(plant).place(poles)?;
+        Ok(Self::split(plant, &k_aug))
+    }
+
+    /// Split the gain of the augmented plant.
+    fn split(plant: &Ss<T>, k_aug: &DMatrix<T>) -> Self {
+        let n = plant.dim().states();
+        let p = plant.dim().outputs();
+        Self {
+            k: k_aug.columns(0, n).into_owned(),
+            ki: k_aug.columns(n, p).into_owned(),
+        }
+    }
+
+    /// Closed loop system, whose inputs are the references `r` and the
+    /// outputs are the plant outputs `y`. The states are the plant states
+    /// followed by the integrals of the error.
+    ///
+    /// # Arguments
+    ///
+    /// * `plant` - Continuous plant
+    ///
+    /// # Panics
+    ///
+    /// Panics if the gains dimensions do not match the plant ones.
+    #[must_use]
+    pub fn closed_loop(&self, plant: &Ss<T>) -> Ss<T> {
+        self.check_dimensions(plant);
+        let n = plant.dim().states();
+        let p = plant.dim().outputs();
+        let c_cl = plant.c() - plant.d() * &self.k;
+        let d_ki = -(plant.d() * &self.ki);
+        let mut a = DMatrix::zeros(n + p, n + p);
+        a.slice_mut((0, 0), (n, n))
+            .copy_from(&(plant.a() - plant.b() * &self.k));
+        a.slice_mut((0, n), (n, p))
+            .copy_from(&-(plant.b() * &self.ki));
+        a.slice_mut((n, 0), (p, n)).copy_from(&c_cl);
+        a.slice_mut((n, n), (p, p)).copy_from(&d_ki);
+        let mut b = DMatrix::zeros(n + p, p);
+        b.slice_mut((n, 0), (p, p))
+            .copy_from(&-DMatrix::<T>::identity(p, p));
+        let mut c = DMatrix::zeros(p, n + p);
+        c.slice_mut((0, 0), (p, n)).copy_from(&c_cl);
+        c.slice_mut((0, n), (p, p)).copy_from(&d_ki);
+        Ss::new_from_matrices(a, b, c, DMatrix::zeros(p, p))
+    }
+
+    /// Loop transfer function broken at the plant input, `L(s)` such that
+    /// `u = -L(s)*v` when the loop is opened at the input `v` of the plant and
+    /// the reference is null. It is the equivalent compensator for the
+    /// frequency domain checks of stability margins.
+    ///
+    /// # Arguments
+    ///
+    /// * `plant` - Continuous plant
+    ///
+    /// # Panics
+    ///
+    /// Panics if the gains dimensions do not match the plant ones.
+    ///
+    /// # Example
+    /// ```
+    /// use au::{controller::state_feedback::StateFeedback, nalgebra::DMatrix, Ss, Tf};
+    /// let plant = Ss::new_from_slice(1, 1, 1, &[-1.], &[1.], &[1.], &[0.]);
+    /// let sf = StateFeedback::new(DMatrix::from_element(1, 1, 4.), DMatrix::from_element(1, 1, 6.));
+    /// let l = Tf::<f64>::new_from_siso(&sf.loop_transfer(&plant)).unwrap();
+    /// // (4s + 6) / (s(s + 1))
+    /// assert_eq!(Tf::new(au::poly!(6., 4.), au::poly!(0., 1., 1.)), l.normalize());
+    /// ```
+    #[must_use]
+    pub fn loop_transfer(&self, plant: &Ss<T>) -> Ss<T> {
+        self.check_dimensions(plant);
+        let aug = augmented(plant);
+        let mut c = DMatrix::zeros(self.k.nrows(), aug.dim().states());
+        let n = plant.dim().states();
+        c.columns_mut(0, n).copy_from(&self.k);
+        c.columns_mut(n, self.ki.ncols()).copy_from(&self.ki);
+        let m = plant.dim().inputs();
+        Ss::new_from_matrices(aug.a().clone(), aug.b().clone(), c, DMatrix::zeros(m, m))
+    }
+
+    /// Check that the dimensions of the gains match the plant ones.
+    fn check_dimensions(&self, plant: &Ss<T>) {
+        let dim = plant.dim();
+        assert_eq!(
+            (dim.inputs(), dim.states()),
+            self.k.shape(),
+            "Wrong state feedback gain dimensions"
+        );
+        assert_eq!(
+            (dim.inputs(), dim.outputs()),
+            self.ki.shape(),
+            "Wrong integral gain dimensions"
+        );
+    }
+}
+
+impl<T: ComplexField + Float + RealField> StateFeedback<T> {
+    /// Discretize the controller, the integral of the error is calculated
+    /// with the backward Euler method. The resulting controller has a null
+    /// initial state.
+    ///
+    /// # Arguments
+    ///
+    /// * `st` - Sample time of the controller
+    ///
+    /// # Panics
+    ///
+    /// Panics if the sample time is not positive.
+    #[must_use]
+    pub fn discretize(&self, st: Seconds<T>) -> DiscreteStateFeedback<T> {
+        assert!(st.0 > T::zero(), "Sample time must be positive");
+        DiscreteStateFeedback {
+            st,
+            k: self.k.clone(),
+            ki: self.ki.clone(),
+            integral: DVector::from_element(self.ki.ncols(), T::zero()),
+        }
+    }
+}
+
+/// Discrete state feedback controller with integral action and internal
+/// state
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde-serialize",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub struct DiscreteStateFeedback<T: Float + Scalar> {
+    /// Sample time
+    st: Seconds<T>,
+    /// State feedback gain (mxn)
+    k: DMatrix<T>,
+    /// Integral action gain (mxp)
+    ki: DMatrix<T>,
+    /// Integral of the error
+    integral: DVector<T>,
+}
+
+impl<T: ComplexField + Float + RealField> DiscreteStateFeedback<T> {
+    /// Get the sample time
+    #[must_use]
+    pub fn sample_time(&self) -> Seconds<T> {
+        self.st
+    }
+
+    /// Calculate the control action of the current sample and update the
+    /// integral of the error.
+    ///
+    /// # Arguments
+    ///
+    /// * `reference` - Reference values (p)
+    /// * `output` - Measured plant outputs (p)
+    /// * `state` - Measured or estimated plant state (n)
+    ///
+    /// # Panics
+    ///
+    /// Panics if the dimensions of the arguments do not match the gains ones.
+    ///
+    /// # Example
+    /// ```
+    /// use au::{controller::state_feedback::StateFeedback, nalgebra::DMatrix, Seconds};
+    /// let sf = StateFeedback::new(DMatrix::from_element(1, 1, 4.), DMatrix::from_element(1, 1, 6.));
+    /// let mut ctrl = sf.discretize(Seconds(0.1));
+    /// // u = -4*0.5 - 6*(0.1*(0.5 - 1))
+    /// let u = ctrl.step(&[1.], &[0.5], &[0.5]);
+    /// assert!((u[0] + 1.7_f64).abs() < 1e-12);
+    /// ```
+    pub fn step(&mut self, reference: &[T], output: &[T], state: &[T]) -> Vec<T> {
+        let p = self.integral.len();
+        assert_eq!(p, reference.len(), "Wrong number of references");
+        assert_eq!(p, output.len(), "Wrong number of outputs");
+        assert_eq!(self.k.ncols(), state.len(), "Wrong number of states");
+        for ((i, &r), &y) in self.integral.iter_mut().zip(reference).zip(output) {
+            *i += self.st.0 * (y - r);
+        }
+        let x = DVector::from_column_slice(state);
+        let u = -(&self.k * x) - &self.ki * &self.integral;
+        u.as_slice().to_vec()
+    }
+
+    /// Reset the integral of the error to zero.
+    pub fn reset(&mut self) {
+        self.integral.fill(T::zero());
+    }
+}
+
+/// The input of the control law is the triple of references, measured plant
+/// outputs and plant state, the output is the control action.
+impl<T: ComplexField + Float + RealField> Controller<T> for DiscreteStateFeedback<T> {
+    type Input = (Vec<T>, Vec<T>, Vec<T>);
+    type Output = Vec<T>;
+
+    fn step(&mut self, _time: Seconds<T>, input: Self::Input) -> Vec<T> {
+        DiscreteStateFeedback::step(self, &input.0, &input.1, &input.2)
+    }
+
+    fn reset(&mut self) {
+        DiscreteStateFeedback::reset(self);
+    }
+}
+
+/// Plant augmented with the integrators of the error, without the
+/// reference input.
+fn augmented<T: ComplexField + Float + RealField>(plant: &Ss<T>) -> Ss<T> {
+    let dim = plant.dim();
+    let (n, m, p) = (dim.states(), dim.inputs(), dim.outputs());
+    let mut a = DMatrix::zeros(n + p, n + p);
+    a.slice_mut((0, 0), (n, n)).copy_from(plant.a());
+    a.slice_mut((n, 0), (p, n)).copy_from(plant.c());
+    let mut b = DMatrix::zeros(n + p, m);
+    b.slice_mut((0, 0), (n, m)).copy_from(plant.b());
+    b.slice_mut((n, 0), (p, m)).copy_from(plant.d());
+    Ss::new_from_matrices(a, b, DMatrix::zeros(0, n + p), DMatrix::zeros(0, m))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Tf;
+
+    /// Double integrator
+    fn plant() -> Ss<f64> {
+        Ss::new_from_slice(2, 1, 1, &[0., 1., 0., 0.], &[0., 1.], &[1., 0.], &[0.])
+    }
+
+    #[test]
+    fn pole_placement() {
+        let poles = [-1., -2., -3.];
+        let cpoles: Vec<_> = poles.iter().map(|&p| Complex::new(p, 0.)).collect();
+        let sf = StateFeedback::from_place(&plant(), &cpoles).unwrap();
+        let mut cl_poles: Vec<_> = sf
+            .closed_loop(&plant())
+            .poles()
+            .iter()
+            .map(|p| p.re)
+            .collect();
+        cl_poles.sort_by(|a, b| b.partial_cmp(a).unwrap());
+        for (e, a) in poles.iter().zip(&cl_poles) {
+            assert_relative_eq!(e, a, max_relative = 1e-8);
+        }
+        // Unitary static gain from the reference to the output.
+        let tf = Tf::<f64>::new_from_siso(&sf.closed_loop(&plant())).unwrap();
+        assert_relative_eq!(1., tf.static_gain(), max_relative = 1e-10);
+    }
+
+    #[test]
+    fn lqr_margins() {
+        let q = DMatrix::from_diagonal(&DVector::from_vec(vec![1., 1., 10.]));
+        let sf = StateFeedback::from_lqr(&plant(), &q, &DMatrix::identity(1, 1)).unwrap();
+        assert_eq!((1, 2), sf.k().shape());
+        assert_eq!((1, 1), sf.ki().shape());
+        let l = Tf::<f64>::new_from_siso(&sf.loop_transfer(&plant())).unwrap();
+        // Return difference of LQR: |1 + L(jw)| >= 1
+        for &w in &[0.01, 0.1, 1., 10., 100.] {
+            let rd = (l.eval(&Complex::new(0., w)) + 1.).norm();
+            assert!(rd >= 1. - 1e-9);
+        }
+    }
+
+    #[test]
+    fn discrete_execution() {
+        let poles: Vec<_> = [-1., -1.5, -2.]
+            .iter()
+            .map(|&p| Complex::new(p, 0.))
+            .collect();
+        let sf = StateFeedback::from_place(&plant(), &poles).unwrap();
+        let st = 0.01;
+        let mut ctrl = sf.discretize(Seconds(st));
+        assert_eq!(Seconds(st), ctrl.sample_time());
+        let sys = plant().discretize_zoh(Seconds(st));
+        let mut x = DVector::from_vec(vec![0., 0.]);
+        for _ in 0..2000 {
+            let y = sys.c() * &x;
+            let u = ctrl.step(&[2.], y.as_slice(), x.as_slice());
+            x = sys.a() * &x + sys.b() * DVector::from_vec(u);
+        }
+        assert_relative_eq!(2., x[0], max_relative = 1e-3);
+        ctrl.reset();
+        let u = ctrl.step(&[0.], &[0.], &[0., 0.]);
+        assert_relative_eq!(0., u[0]);
+    }
+
+    #[test]
+    fn controller_interface() {
+        let sf = StateFeedback::new(
+            DMatrix::from_element(1, 1, 4.),
+            DMatrix::from_element(1, 1, 6.),
+        );
+        let mut ctrl = sf.discretize(Seconds(0.1));
+        let mut law = sf.discretize(Seconds(0.1));
+        let u = Controller::step(&mut law, Seconds(0.), (vec![1.], vec![0.5], vec![0.5]));
+        assert_eq!(ctrl.step(&[1.], &[0.5], &[0.5]), u);
+        Controller::reset(&mut law);
+        assert_ne!(ctrl, law);
+        ctrl.reset();
+        assert_eq!(ctrl, law);
+    }
+
+    #[test]
+    #[should_panic]
+    fn wrong_gains() {
+        let _ = StateFeedback::new(DMatrix::<f64>::zeros(1, 2), DMatrix::zeros(2, 1));
+    }
+
+    #[cfg(feature = "serde-serialize")]
+    #[test]
+    fn serde_round_trip() {
+        let sf = StateFeedback::new(
+            DMatrix::from_element(1, 1, 4.),
+            DMatrix::from_element(1, 1, 6.),
+        );
+        let json = serde_json::to_string(&sf).unwrap();
+        assert_eq!(sf, serde_json::from_str(&json).unwrap());
+
+        let dsf = sf.discretize(Seconds(0.1));
+        let json = serde_json::to_string(&dsf).unwrap();
+        assert_eq!(dsf, serde_json::from_str(&json).unwrap());
+    }
+}
@@ -0,0 +1,511 @@
+//! # Tuning rules of PID controllers
+//!
+//! Empirical rules that calculate the parameters of ideal PID controllers
+//! from a simple model of the plant.
+//! * Ziegler-Nichols open loop step response method, for first order plus
+//!   dead time (FOPDT) models
+//! * Ziegler-Nichols closed loop ultimate gain method
+//! * Cohen-Coon method, for FOPDT models with significant dead time
+//! * Skogestad SIMC (lambda) method, for FOPDT and second order plus dead
+//!   time (SOPDT) models
+//!
+//! The analytic pole placement design assigns the dominant closed loop poles
+//! of first and second order plants without zeros.
+//!
+//! The structure of the controller (P, PI or PID) is chosen with
+//! [PidStructure](enum.PidStructure.html). Controllers without integral
+//! action have an infinite integral time.
+
+use num_traits::Float;
+
+use crate::{
+    controller::pid::Pid,
+    error::{Error, ErrorKind},
+    transfer_function::continuous::Tf,
+    units::{DampingRatio, NaturalFrequency},
+};
+
+/// Structure of the tuned controller
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde-serialize",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub enum PidStructure {
+    /// Proportional controller
+    P,
+    /// Proportional-integral controller
+    Pi,
+    /// Proportional-integral-derivative controller
+    Pid,
+}
+
+impl<T: Float> Pid<T> {
+    /// Tune the controller with the Ziegler-Nichols open loop step response
+    /// method, from a first order plus dead time model of the plant
+    /// `k * exp(-theta*s) / (1 + tau*s)`.
+    ///
+    /// | Structure | Kp               | Ti         | Td        |
+    /// |-----------|------------------|------------|-----------|
+    /// | P         | tau/(k*theta)    | ∞          | 0         |
+    /// | PI        | 0.9*tau/(k*theta)| theta/0.3  | 0         |
+    /// | PID       | 1.2*tau/(k*theta)| 2*theta    | 0.5*theta |
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - Static gain of the plant
+    /// * `tau` - Time constant of the plant
+    /// * `theta` - Dead time of the plant
+    /// * `structure` - Structure of the controller
+    ///
+    /// # Panics
+    ///
+    /// Panics if the gain is null or the time constant or the dead time are
+    /// not positive.
+    ///
+    /// # Example
+    /// ```
+    /// use au::controller::{pid::Pid, tuning::PidStructure};
+    /// let pid = Pid::tune_ziegler_nichols_step(2., 10., 1., PidStructure::Pid);
+    /// assert_eq!(6., pid.kp());
+    /// assert_eq!(2., pid.ti());
+    /// assert_eq!(0.5, pid.td());
+    /// ```
+    #[must_use]
+    pub fn tune_ziegler_nichols_step(k: T, tau: T, theta: T, structure: PidStructure) -> Self {
+        assert!(k != T::zero(), "Plant gain must not be null");
+        assert!(tau > T::zero(), "Time constant must be positive");
+        assert!(theta > T::zero(), "Dead time must be positive");
+        let c = |x: f64| T::from(x).unwrap();
+        let kp = tau / (k * theta);
+        match structure {
+            PidStructure::P => Self::new_ideal(kp, T::infinity(), T::zero()),
+            PidStructure::Pi => Self::new_ideal(c(0.9) * kp, theta / c(0.3), T::zero()),
+            PidStructure::Pid => Self::new_ideal(c(1.2) * kp, c(2.) * theta, c(0.5) * theta),
+        }
+    }
+
+    /// Tune the controller with the Ziegler-Nichols closed loop method,
+    /// from the ultimate gain of the proportional controller that brings the
+    /// loop to the stability limit and the period of the oscillation.
+    ///
+    /// | Structure | Kp      | Ti     | Td   |
+    /// |-----------|---------|--------|------|
+    /// | P         | 0.5*Ku  | ∞      | 0    |
+    /// | PI        | 0.45*Ku | Tu/1.2 | 0    |
+    /// | PID       | 0.6*Ku  | Tu/2   | Tu/8 |
+    ///
+    /// # Arguments
+    ///
+    /// * `ku` - Ultimate gain
+    /// * `tu` - Ultimate period
+    /// * `structure` - Structure of the controller
+    ///
+    /// # Panics
+    ///
+    /// Panics if the ultimate gain is null or the ultimate period is not
+    /// positive.
+    ///
+    /// # Example
+    /// ```
+    /// use au::controller::{pid::Pid, tuning::PidStructure};
+    /// let pi = Pid::tune_ziegler_nichols_ultimate(10., 2.4, PidStructure::Pi);
+    /// assert_eq!(4.5, pi.kp());
+    /// assert_eq!(2., pi.ti());
+    /// ```
+    #[must_use]
+    pub fn tune_ziegler_nichols_ultimate(ku: T, tu: T, structure: PidStructure) -> Self {
+        assert!(ku != T::zero(), "Ultimate gain must not be null");
+        assert!(tu > T::zero(), "Ultimate period must be positive");
+        let c = |x: f64| T::from(x).unwrap();
+        match structure {
+            PidStructure::P => Self::new_ideal(c(0.5) * ku, T::infinity(), T::zero()),
+            PidStructure::Pi => Self::new_ideal(c(0.45) * ku, tu / c(1.2), T::zero()),
+            PidStructure::Pid => Self::new_ideal(c(0.6) * ku, tu / c(2.), tu / c(8.)),
+        }
+    }
+
+    /// Tune the controller with the Cohen-Coon method, from a first order
+    /// plus dead time model of the plant `k * exp(-theta*s) / (1 + tau*s)`.
+    ///
+    /// The rules are designed for a quarter amplitude decay of the load
+    /// disturbance response and are suited for plants where the dead time is
+    /// significant compared to the time constant. With `r = theta/tau`:
+    ///
+    /// | Structure | Kp                          | Ti                          | Td                 |
+    /// |-----------|-----------------------------|-----------------------------|--------------------|
+    /// | P         | tau/(k*theta)*(1 + r/3)     | ∞                           | 0                  |
+    /// | PI        | tau/(k*theta)*(0.9 + r/12)  | theta*(30 + 3r)/(9 + 20r)   | 0                  |
+    /// | PID       | tau/(k*theta)*(4/3 + r/4)   | theta*(32 + 6r)/(13 + 8r)   | theta*4/(11 + 2r)  |
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - Static gain of the plant
+    /// * `tau` - Time constant of the plant
+    /// * `theta` - Dead time of the plant
+    /// * `structure` - Structure of the controller
+    ///
+    /// # Panics
+    ///
+    /// Panics if the gain is null or the time constant or the dead time are
+    /// not positive.
+    ///
+    /// # Example
+    /// ```
+    /// use au::controller::{pid::Pid, tuning::PidStructure};
+    /// let cc = Pid::tune_cohen_coon(1., 10., 2., PidStructure::Pid);
+    /// let zn = Pid::tune_ziegler_nichols_step(1., 10., 2., PidStructure::Pid);
+    /// assert!(cc.kp() > zn.kp());
+    /// ```
+    #[must_use]
+    pub fn tune_cohen_coon(k: T, tau: T, theta: T, structure: PidStructure) -> Self {
+        assert!(k != T::zero(), "Plant gain must not be null");
+        assert!(tau > T::zero(), "Time constant must be positive");
+        assert!(theta > T::zero(), "Dead time must be positive");
+        let c = |x: f64| T::from(x).unwrap();
+        let r = theta / tau;
+        let kp = tau / (k * theta);
+        match structure {
+            PidStructure::P => {
+                Self::new_ideal(kp * (T::one() + r / c(3.)), T::infinity(), T::zero())
+            }
+            PidStructure::Pi => Self::new_ideal(
+                kp * (c(0.9) + r / c(12.)),
+                theta * (c(30.) + c(3.) * r) / (c(9.) + c(20.) * r),
+                T::zero(),
+            ),
+            PidStructure::Pid => Self::new_ideal(
+                kp * (c(4.) / c(3.) + r / c(4.)),
+                theta * (c(32.) + c(6.) * r) / (c(13.) + c(8.) * r),
+                theta * c(4.) / (c(11.) + c(2.) * r),
+            ),
+        }
+    }
+
+    /// Tune a PI controller with the Skogestad SIMC method, from a first
+    /// order plus dead time model of the plant `k * exp(-theta*s) / (1 + tau*s)`.
+    ///
+    /// ```text
+    /// Kp = tau / (k * (tau_c + theta))
+    /// Ti = min(tau, 4 * (tau_c + theta))
+    /// ```
+    ///
+    /// The desired closed loop time constant `tau_c` trades the speed of the
+    /// response for robustness, `tau_c = theta` is the recommended default
+    /// for a tight control with good robustness.
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - Static gain of the plant
+    /// * `tau` - Time constant of the plant
+    /// * `theta` - Dead time of the plant
+    /// * `tau_c` - Desired closed loop time constant
+    ///
+    /// # Panics
+    ///
+    /// Panics if the gain is null, the time constant or the closed loop time
+    /// constant are not positive or the dead time is negative.
+    ///
+    /// # Example
+    /// ```
+    /// use au::controller::pid::Pid;
+    /// let pi = Pid::tune_simc(1., 10., 1., 1.);
+    /// assert_eq!(5., pi.kp());
+    /// assert_eq!(8., pi.ti());
+    /// ```
+    #[must_use]
+    pub fn tune_simc(k: T, tau: T, theta: T, tau_c: T) -> Self {
+        let (kc, ti) = simc_pi(k, tau, theta, tau_c);
+        Self::new_ideal(kc, ti, T::zero())
+    }
+
+    /// Tune a PID controller with the Skogestad SIMC method, from a second
+    /// order plus dead time model of the plant
+    /// `k * exp(-theta*s) / ((1 + tau1*s)(1 + tau2*s))`.
+    ///
+    /// The rule gives the series controller
+    /// `Kc * (1 + Ti'*s)/(Ti'*s) * (1 + tau2*s)`, with `Kc` and `Ti'` of the
+    /// PI rule on the dominant time constant `tau1`; the derivative action
+    /// cancels the second time constant. The returned ideal controller has
+    /// the same transfer function.
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - Static gain of the plant
+    /// * `tau1` - Dominant time constant of the plant
+    /// * `tau2` - Second time constant of the plant
+    /// * `theta` - Dead time of the plant
+    /// * `tau_c` - Desired closed loop time constant
+    ///
+    /// # Panics
+    ///
+    /// Panics if the gain is null, the time constants are not positive or
+    /// the dead time is negative.
+    ///
+    /// # Example
+    /// ```
+    /// use au::controller::pid::Pid;
+    /// let pid = Pid::tune_simc_sopdt(1., 10., 2., 1., 1.);
+    /// assert_eq!(6.25, pid.kp());
+    /// assert_eq!(10., pid.ti());
+    /// assert_eq!(1.6, pid.td());
+    /// ```
+    #[must_use]
+    pub fn tune_simc_sopdt(k: T, tau1: T, tau2: T, theta: T, tau_c: T) -> Self {
+        assert!(tau2 > T::zero(), "Time constant must be positive");
+        let (tau1, tau2) = (tau1.max(tau2), tau1.min(tau2));
+        let (kc, ti) = simc_pi(k, tau1, theta, tau_c);
+        // Conversion from the series form to the ideal form.
+        let sum = ti + tau2;
+        Self::new_ideal(kc * sum / ti, sum, ti * tau2 / sum)
+    }
+
+    /// Design the controller that places the dominant closed loop poles in
+    /// the roots of `s^2 + 2*zeta*wn*s + wn^2`.
+    ///
+    /// The plant must have no zeros and be of the first order, giving a PI
+    /// controller, or of the second order, giving an ideal PID controller.
+    /// For second order plants the third closed loop pole is real and placed
+    /// in `-5*zeta*wn`, five times faster than the dominant poles.
+    ///
+    /// # Arguments
+    ///
+    /// * `plant` - Plant of the first or second order without zeros
+    /// * `zeta` - Damping ratio of the dominant closed loop poles
+    /// * `wn` - Natural frequency of the dominant closed loop poles
+    ///
+    /// # Errors
+    ///
+    /// It returns an error if the damping or the natural frequency are not
+    /// positive, if the plant is not of the first or second order, has zeros
+    /// or if the required gains are not positive, that is the specification
+    /// is slower than the plant.
+    ///
+    /// # Example
+    /// ```
+    /// use au::{controller::pid::Pid, poly, DampingRatio, NaturalFrequency, Tf};
+    /// let plant = Tf::new(poly!(2.), poly!(1., 1.));
+    /// let pi = Pid::from_pole_placement(&plant, DampingRatio(0.7), NaturalFrequency(2.)).unwrap();
+    /// assert!((pi.kp() - 0.9_f64).abs() < 1e-12);
+    /// assert!((pi.ti() - 0.45_f64).abs() < 1e-12);
+    /// ```
+    pub fn from_pole_placement(
+        plant: &Tf<T>,
+        zeta: DampingRatio<T>,
+        wn: NaturalFrequency<T>,
+    ) -> Result<Self, Error> {
+        let (zeta, wn) = (zeta.0, wn.0);
+        let unfeasible = || Error::new_internal(ErrorKind::UnfeasibleSpecification);
+        if zeta <= T::zero() || wn <= T::zero() {
+            return Err(unfeasible());
+        }
+        if plant.num().degree() != Some(0) {
+            return Err(unfeasible());
+        }
+        // Monic denominator s^n + a1*s^(n-1) + ... and numerator b0.
+        let lead = plant.den().leading_coeff();
+        let b0 = plant.num()[0] / lead;
+        let a = |i: usize| plant.den()[i] / lead;
+        let two = T::one() + T::one();
+        let (kp, ki, kd) = match plant.den().degree() {
+            // s^2 + (a0 + b0*Kp)*s + b0*Ki
+            Some(1) => ((two * zeta * wn - a(0)) / b0, wn * wn / b0, T::zero()),
+            // s^3 + (a1 + b0*Kd)*s^2 + (a0 + b0*Kp)*s + b0*Ki
+            Some(2) => {
+                let p = T::from(5.).unwrap() * zeta * wn;
+                (
+                    (wn * wn + two * zeta * wn * p - a(0)) / b0,
+                    wn * wn * p / b0,
+                    (two * zeta * wn + p - a(1)) / b0,
+                )
+            }
+            _ => return Err(unfeasible()),
+        };
+        if kp <= T::zero() || ki <= T::zero() || kd < T::zero() {
+            return Err(unfeasible());
+        }
+        Ok(Self::new_ideal(kp, kp / ki, kd / kp))
+    }
+}
+
+/// Gain and integral time of the SIMC PI rule.
+fn simc_pi<T: Float>(k: T, tau: T, theta: T, tau_c: T) -> (T, T) {
+    assert!(k != T::zero(), "Plant gain must not be null");
+    assert!(tau > T::zero(), "Time constant must be positive");
+    assert!(theta >= T::zero(), "Dead time must not be negative");
+    assert!(
+        tau_c > T::zero(),
+        "Closed loop time constant must be positive"
+    );
+    let four = T::from(4.).unwrap();
+    (tau / (k * (tau_c + theta)), tau.min(four * (tau_c + theta)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{polynomial::Poly, Tf};
+    use num_complex::Complex64;
+
+    #[test]
+    fn ziegler_nichols_step() {
+        let p = Pid::tune_ziegler_nichols_step(0.5, 4., 2., PidStructure::P);
+        assert_relative_eq!(4., p.kp());
+        assert!(p.ti().is_infinite());
+        assert_relative_eq!(0., p.td());
+        let pi = Pid::tune_ziegler_nichols_step(0.5, 4., 2., PidStructure::Pi);
+        assert_relative_eq!(3.6, pi.kp());
+        assert_relative_eq!(2. / 0.3, pi.ti());
+        assert_eq!(None, pi.n());
+    }
+
+    #[test]
+    fn ziegler_nichols_ultimate() {
+        // Plant 1/(s+1)^3: ultimate gain 8 at the angular frequency sqrt(3).
+        let plant = Tf::new(
+            Poly::new_from_coeffs(&[1.]),
+            Poly::new_from_roots(&[-1., -1., -1.]),
+        );
+        let wu = 3_f64.sqrt();
+        let tu = 2. * std::f64::consts::PI / wu;
+        let l = plant.eval(&Complex64::new(0., wu)) * 8.;
+        assert_relative_eq!(-1., l.re, max_relative = 1e-12);
+
+        let pid = Pid::tune_ziegler_nichols_ultimate(8., tu, PidStructure::Pid);
+        assert_relative_eq!(4.8, pid.kp());
+        assert_relative_eq!(tu / 2., pid.ti());
+        assert_relative_eq!(tu / 8., pid.td());
+        // The tuned loop is stable.
+        let closed = (&plant * &pid.tf()).feedback_n();
+        assert!(closed.complex_poles().iter().all(|p| p.re < 0.));
+
+        let p = Pid::tune_ziegler_nichols_ultimate(8., tu, PidStructure::P);
+        assert_relative_eq!(4., p.kp());
+        assert_eq!(
+            Tf::new(Poly::new_from_coeffs(&[4.]), Poly::new_from_coeffs(&[1.])),
+            p.tf()
+        );
+    }
+
+    #[test]
+    fn cohen_coon() {
+        let p = Pid::tune_cohen_coon(2., 10., 2., PidStructure::P);
+        assert_relative_eq!(2.5 * (1. + 0.2 / 3.), p.kp());
+        assert!(p.ti().is_infinite());
+        let pi = Pid::tune_cohen_coon(2., 10., 2., PidStructure::Pi);
+        assert_relative_eq!(2.5 * (0.9 + 0.2 / 12.), pi.kp());
+        assert_relative_eq!(2. * 30.6 / 13., pi.ti());
+        assert_relative_eq!(0., pi.td());
+        let pid = Pid::tune_cohen_coon(2., 10., 2., PidStructure::Pid);
+        assert_relative_eq!(2.5 * (4. / 3. + 0.05), pid.kp());
+        assert_relative_eq!(2. * 33.2 / 14.6, pid.ti());
+        assert_relative_eq!(8. / 11.4, pid.td());
+    }
+
+    #[test]
+    #[should_panic]
+    fn cohen_coon_no_time_constant() {
+        let _ = Pid::tune_cohen_coon(1., 0., 1., PidStructure::Pi);
+    }
+
+    #[test]
+    fn simc_first_order() {
+        // Slow plant, the integral time is limited by the closed loop.
+        let pi = Pid::tune_simc(2., 20., 1., 2.);
+        assert_relative_eq!(20. / 6., pi.kp());
+        assert_relative_eq!(12., pi.ti());
+        // Fast plant, the integral time is the time constant.
+        let pi = Pid::tune_simc(2., 5., 1., 2.);
+        assert_relative_eq!(5. / 6., pi.kp());
+        assert_relative_eq!(5., pi.ti());
+        assert_relative_eq!(0., pi.td());
+    }
+
+    #[test]
+    fn simc_sopdt() {
+        let pid = Pid::tune_simc_sopdt(1., 2., 10., 1., 1.);
+        // Series form controller
+        let series = Tf::new(
+            Poly::new_from_coeffs(&[1., 8.]) * Poly::new_from_coeffs(&[1., 2.]) * 5.,
+            Poly::new_from_coeffs(&[0., 8.]),
+        );
+        for &w in &[0.01, 0.1, 1., 10.] {
+            let s = Complex64::new(0., w);
+            let expected = series.eval(&s);
+            let actual = pid.tf().eval(&s);
+            assert_relative_eq!(expected.re, actual.re, max_relative = 1e-12);
+            assert_relative_eq!(expected.im, actual.im, max_relative = 1e-12);
+        }
+    }
+
+    fn check_dominant_poles(plant: &Tf<f64>, pid: &Pid<f64>, zeta: f64, wn: f64) {
+        let cl = (plant * &pid.tf()).feedback_n();
+        let dominant = Complex64::new(-zeta * wn, wn * (1. - zeta * zeta).sqrt());
+        let poles = cl.complex_poles();
+        assert!(poles.iter().any(|p| (p - dominant).norm() < 1e-8));
+        assert!(poles.iter().any(|p| (p - dominant.conj()).norm() < 1e-8));
+    }
+
+    #[test]
+    fn pole_placement_first_order() {
+        let plant = Tf::new(
+            Poly::new_from_coeffs(&[3.]),
+            Poly::new_from_coeffs(&[1., 2.]),
+        );
+        let pi = Pid::from_pole_placement(&plant, DampingRatio(0.5), NaturalFrequency(2.)).unwrap();
+        assert_relative_eq!(0., pi.td());
+        check_dominant_poles(&plant, &pi, 0.5, 2.);
+    }
+
+    #[test]
+    fn pole_placement_second_order() {
+        let plant = Tf::new(
+            Poly::new_from_coeffs(&[2.]),
+            Poly::new_from_roots(&[-1., -2.]),
+        );
+        let pid =
+            Pid::from_pole_placement(&plant, DampingRatio(0.8), NaturalFrequency(4.)).unwrap();
+        check_dominant_poles(&plant, &pid, 0.8, 4.);
+        let cl = (&plant * &pid.tf()).feedback_n();
+        assert!(cl
+            .complex_poles()
+            .iter()
+            .any(|p| (p.re + 16.).abs() < 1e-8 && p.im.abs() < 1e-8));
+    }
+
+    #[test]
+    fn pole_placement_unfeasible() {
+        let with_zero = Tf::new(
+            Poly::new_from_coeffs(&[1., 1.]),
+            Poly::new_from_roots(&[-1., -2.]),
+        );
+        let res = Pid::from_pole_placement(&with_zero, DampingRatio(0.7), NaturalFrequency(1.));
+        assert_eq!(ErrorKind::UnfeasibleSpecification, res.unwrap_err().kind());
+        let third_order = Tf::new(
+            Poly::new_from_coeffs(&[1.]),
+            Poly::new_from_roots(&[-1., -2., -3.]),
+        );
+        let res = Pid::from_pole_placement(&third_order, DampingRatio(0.7), NaturalFrequency(1.));
+        assert_eq!(ErrorKind::UnfeasibleSpecification, res.unwrap_err().kind());
+        // The plant pole is faster than the specification.
+        let fast = Tf::new(Poly::new_from_coeffs(&[1.]), Poly::new_from_roots(&[-10.]));
+        let res = Pid::from_pole_placement(&fast, DampingRatio(0.7), NaturalFrequency(1.));
+        assert_eq!(ErrorKind::UnfeasibleSpecification, res.unwrap_err().kind());
+        let res = Pid::from_pole_placement(&fast, DampingRatio(0.), NaturalFrequency(20.));
+        assert_eq!(ErrorKind::UnfeasibleSpecification, res.unwrap_err().kind());
+        let res = Pid::from_pole_placement(&fast, DampingRatio(0.7), NaturalFrequency(-20.));
+        assert_eq!(ErrorKind::UnfeasibleSpecification, res.unwrap_err().kind());
+    }
+
+    #[test]
+    #[should_panic]
+    fn simc_no_closed_loop_time_constant() {
+        let _ = Pid::tune_simc(1., 1., 1., 0.);
+    }
+
+    #[test]
+    #[should_panic]
+    fn ziegler_nichols_no_dead_time() {
+        let _ = Pid::tune_ziegler_nichols_step(1., 1., 0., PidStructure::Pid);
+    }
+}
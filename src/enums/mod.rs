@@ -25,3 +25,34 @@ pub enum Discretization {
     /// Tustin (trapezoidal rule)
     Tustin,
 }
+
+/// Behavior of a sampled input between two consecutive samples.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Interpolation {
+    /// Zero order hold, the input is constant between samples
+    ZeroOrderHold,
+    /// First order hold, the input changes linearly between samples
+    FirstOrderHold,
+}
+
+/// Law of the frequency of a swept sine.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Sweep {
+    /// The frequency changes linearly with time
+    Linear,
+    /// The frequency changes exponentially with time, equal time for each
+    /// decade
+    Logarithmic,
+}
+
+/// Result of a stability test.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Stability {
+    /// All poles are strictly inside the stability region.
+    Stable,
+    /// At least one pole lies on the boundary of the stability region,
+    /// within the given tolerance.
+    MarginallyStable,
+    /// At least one pole lies outside the stability region.
+    Unstable,
+}
@@ -14,6 +14,9 @@ pub struct Error {
 enum Repr {
     /// Errors that are created by this library.
     Internal(ErrorKind),
+    /// Errors of the rendering of figures.
+    #[cfg(feature = "plotters")]
+    Rendering(Box<dyn error::Error + Send + Sync>),
     // Add if necessary additional variants that wrap errors given by used libraries.
 }
 
@@ -27,6 +30,47 @@ pub enum ErrorKind {
     ZeroPolynomialDenominator,
     /// The given transfer function has no poles.
     NoPolesDenominator,
+    /// The given transfer function is not proper.
+    ImproperTransferFunction,
+    /// The given system is not stable.
+    UnstableSystem,
+    /// The given system is not minimal (it is either not controllable or not
+    /// observable).
+    NoMinimalSystem,
+    /// The given system is not controllable.
+    NoControllableSystem,
+    /// The given system is not observable.
+    NoObservableSystem,
+    /// The states matrix of the given system is not diagonalizable.
+    NoDiagonalizableSystem,
+    /// The input matrix of the given system is not full column rank.
+    NoFullRankInput,
+    /// The Riccati equation has no stabilizing solution.
+    NoRiccatiSolution,
+    /// The descriptor matrix of the given system is singular.
+    SingularDescriptorMatrix,
+    /// The given descriptor system is not of index one.
+    NoIndexOneSystem,
+    /// The given system has no equilibrium point.
+    NoEquilibrium,
+    /// The given system has infinitely many equilibrium points.
+    UnderdeterminedEquilibrium,
+    /// The multiplicity of a pole is greater than the number of inputs.
+    ExcessivePoleMultiplicity,
+    /// The given input or output channel does not exist.
+    ChannelOutOfRange,
+    /// The format of the given file is not supported.
+    UnsupportedFileFormat,
+    /// The rendering of the figure failed.
+    RenderingFailure,
+    /// The design specifications cannot be met.
+    UnfeasibleSpecification,
+    /// The given data are not sufficient for the identification of a model.
+    InsufficientData,
+    /// The given time cannot be represented as a duration.
+    InvalidDuration,
+    /// The given data are not consistent with each other.
+    InvalidData,
 }
 
 impl Error {
@@ -41,29 +85,55 @@ impl Error {
         }
     }
 
+    /// Create a new error of the rendering of figures.
+    ///
+    /// # Arguments
+    ///
+    /// `error` - error given by the rendering library
+    #[cfg(feature = "plotters")]
+    pub(crate) fn new_rendering<E: error::Error + Send + Sync + 'static>(error: E) -> Self {
+        Error {
+            repr: Repr::Rendering(Box::new(error)),
+        }
+    }
+
     /// Return the kind of describing the `Error`
     #[must_use]
     pub fn kind(&self) -> ErrorKind {
         match self.repr {
             Repr::Internal(kind) => kind,
+            #[cfg(feature = "plotters")]
+            Repr::Rendering(_) => ErrorKind::RenderingFailure,
         }
     }
 }
 
-impl error::Error for Error {}
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match &self.repr {
+            Repr::Internal(_) => None,
+            #[cfg(feature = "plotters")]
+            Repr::Rendering(e) => Some(e.as_ref()),
+        }
+    }
+}
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self.repr {
+        match &self.repr {
             Repr::Internal(kind) => write!(f, "{}", kind.as_str()),
+            #[cfg(feature = "plotters")]
+            Repr::Rendering(e) => write!(f, "{}: {}", ErrorKind::RenderingFailure.as_str(), e),
         }
     }
 }
 
 impl fmt::Debug for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self.repr {
+        match &self.repr {
             Repr::Internal(kind) => write!(f, "Error: {:?}", kind),
+            #[cfg(feature = "plotters")]
+            Repr::Rendering(e) => write!(f, "Error: {:?}({:?})", ErrorKind::RenderingFailure, e),
         }
     }
 }
@@ -77,6 +147,34 @@ impl ErrorKind {
                 "Transfer functions cannot have zero polynomial denominator"
             }
             ErrorKind::NoPolesDenominator => "Denominator has no poles",
+            ErrorKind::ImproperTransferFunction => {
+                "Numerator degree is greater than denominator degree"
+            }
+            ErrorKind::UnstableSystem => "Linear system is not stable",
+            ErrorKind::NoMinimalSystem => "Linear system is not minimal",
+            ErrorKind::NoControllableSystem => "Linear system is not controllable",
+            ErrorKind::NoObservableSystem => "Linear system is not observable",
+            ErrorKind::NoDiagonalizableSystem => "States matrix is not diagonalizable",
+            ErrorKind::NoFullRankInput => "Input matrix is not full column rank",
+            ErrorKind::NoRiccatiSolution => "Riccati equation has no stabilizing solution",
+            ErrorKind::SingularDescriptorMatrix => "Descriptor matrix is singular",
+            ErrorKind::NoIndexOneSystem => "Descriptor system is not of index one",
+            ErrorKind::NoEquilibrium => "Linear system has no equilibrium point",
+            ErrorKind::UnderdeterminedEquilibrium => {
+                "Linear system has infinitely many equilibrium points"
+            }
+            ErrorKind::ExcessivePoleMultiplicity => {
+                "Pole multiplicity is greater than the number of inputs"
+            }
+            ErrorKind::ChannelOutOfRange => "Input or output channel is out of range",
+            ErrorKind::UnsupportedFileFormat => "File format is not supported",
+            ErrorKind::RenderingFailure => "Rendering of the figure failed",
+            ErrorKind::UnfeasibleSpecification => "Design specifications cannot be met",
+            ErrorKind::InsufficientData => "Data are not sufficient for the model identification",
+            ErrorKind::InvalidDuration => {
+                "Time is negative, not finite or too large for a duration"
+            }
+            ErrorKind::InvalidData => "Data are not consistent with each other",
         }
     }
 }
@@ -104,6 +202,82 @@ mod tests {
         assert!(!err.to_string().is_empty());
         assert!(!format!("{:?}", err).is_empty());
         assert_eq!(ErrorKind::NoPolesDenominator.as_str(), err.to_string());
+
+        let err = Error::new_internal(ErrorKind::ImproperTransferFunction);
+        assert!(!err.to_string().is_empty());
+        assert!(!format!("{:?}", err).is_empty());
+        assert_eq!(
+            ErrorKind::ImproperTransferFunction.as_str(),
+            err.to_string()
+        );
+
+        let err = Error::new_internal(ErrorKind::UnstableSystem);
+        assert!(!err.to_string().is_empty());
+        assert!(!format!("{:?}", err).is_empty());
+        assert_eq!(ErrorKind::UnstableSystem.as_str(), err.to_string());
+
+        let err = Error::new_internal(ErrorKind::NoMinimalSystem);
+        assert!(!err.to_string().is_empty());
+        assert!(!format!("{:?}", err).is_empty());
+        assert_eq!(ErrorKind::NoMinimalSystem.as_str(), err.to_string());
+
+        let err = Error::new_internal(ErrorKind::NoControllableSystem);
+        assert!(!err.to_string().is_empty());
+        assert!(!format!("{:?}", err).is_empty());
+        assert_eq!(ErrorKind::NoControllableSystem.as_str(), err.to_string());
+
+        let err = Error::new_internal(ErrorKind::NoObservableSystem);
+        assert!(!err.to_string().is_empty());
+        assert!(!format!("{:?}", err).is_empty());
+        assert_eq!(ErrorKind::NoObservableSystem.as_str(), err.to_string());
+
+        let err = Error::new_internal(ErrorKind::NoDiagonalizableSystem);
+        assert!(!err.to_string().is_empty());
+        assert!(!format!("{:?}", err).is_empty());
+        assert_eq!(ErrorKind::NoDiagonalizableSystem.as_str(), err.to_string());
+
+        let err = Error::new_internal(ErrorKind::NoFullRankInput);
+        assert!(!err.to_string().is_empty());
+        assert!(!format!("{:?}", err).is_empty());
+        assert_eq!(ErrorKind::NoFullRankInput.as_str(), err.to_string());
+
+        let err = Error::new_internal(ErrorKind::NoRiccatiSolution);
+        assert!(!err.to_string().is_empty());
+        assert!(!format!("{:?}", err).is_empty());
+        assert_eq!(ErrorKind::NoRiccatiSolution.as_str(), err.to_string());
+
+        let err = Error::new_internal(ErrorKind::SingularDescriptorMatrix);
+        assert!(!err.to_string().is_empty());
+        assert!(!format!("{:?}", err).is_empty());
+        assert_eq!(
+            ErrorKind::SingularDescriptorMatrix.as_str(),
+            err.to_string()
+        );
+
+        let err = Error::new_internal(ErrorKind::NoIndexOneSystem);
+        assert!(!err.to_string().is_empty());
+        assert!(!format!("{:?}", err).is_empty());
+        assert_eq!(ErrorKind::NoIndexOneSystem.as_str(), err.to_string());
+
+        let err = Error::new_internal(ErrorKind::NoEquilibrium);
+        assert!(!err.to_string().is_empty());
+        assert!(!format!("{:?}", err).is_empty());
+        assert_eq!(ErrorKind::NoEquilibrium.as_str(), err.to_string());
+
+        let err = Error::new_internal(ErrorKind::ChannelOutOfRange);
+        assert!(!err.to_string().is_empty());
+        assert!(!format!("{:?}", err).is_empty());
+        assert_eq!(ErrorKind::ChannelOutOfRange.as_str(), err.to_string());
+
+        let err = Error::new_internal(ErrorKind::UnsupportedFileFormat);
+        assert!(!err.to_string().is_empty());
+        assert!(!format!("{:?}", err).is_empty());
+        assert_eq!(ErrorKind::UnsupportedFileFormat.as_str(), err.to_string());
+
+        let err = Error::new_internal(ErrorKind::RenderingFailure);
+        assert!(!err.to_string().is_empty());
+        assert!(!format!("{:?}", err).is_empty());
+        assert_eq!(ErrorKind::RenderingFailure.as_str(), err.to_string());
     }
 
     #[test]
@@ -116,5 +290,62 @@ mod tests {
 
         let err = Error::new_internal(ErrorKind::NoPolesDenominator);
         assert_eq!(ErrorKind::NoPolesDenominator, err.kind());
+
+        let err = Error::new_internal(ErrorKind::ImproperTransferFunction);
+        assert_eq!(ErrorKind::ImproperTransferFunction, err.kind());
+
+        let err = Error::new_internal(ErrorKind::UnstableSystem);
+        assert_eq!(ErrorKind::UnstableSystem, err.kind());
+
+        let err = Error::new_internal(ErrorKind::NoMinimalSystem);
+        assert_eq!(ErrorKind::NoMinimalSystem, err.kind());
+
+        let err = Error::new_internal(ErrorKind::NoControllableSystem);
+        assert_eq!(ErrorKind::NoControllableSystem, err.kind());
+
+        let err = Error::new_internal(ErrorKind::NoObservableSystem);
+        assert_eq!(ErrorKind::NoObservableSystem, err.kind());
+
+        let err = Error::new_internal(ErrorKind::NoDiagonalizableSystem);
+        assert_eq!(ErrorKind::NoDiagonalizableSystem, err.kind());
+
+        let err = Error::new_internal(ErrorKind::NoFullRankInput);
+        assert_eq!(ErrorKind::NoFullRankInput, err.kind());
+
+        let err = Error::new_internal(ErrorKind::NoRiccatiSolution);
+        assert_eq!(ErrorKind::NoRiccatiSolution, err.kind());
+
+        let err = Error::new_internal(ErrorKind::SingularDescriptorMatrix);
+        assert_eq!(ErrorKind::SingularDescriptorMatrix, err.kind());
+
+        let err = Error::new_internal(ErrorKind::NoIndexOneSystem);
+        assert_eq!(ErrorKind::NoIndexOneSystem, err.kind());
+
+        let err = Error::new_internal(ErrorKind::NoEquilibrium);
+        assert_eq!(ErrorKind::NoEquilibrium, err.kind());
+
+        let err = Error::new_internal(ErrorKind::UnderdeterminedEquilibrium);
+        assert_eq!(ErrorKind::UnderdeterminedEquilibrium, err.kind());
+
+        let err = Error::new_internal(ErrorKind::ExcessivePoleMultiplicity);
+        assert_eq!(ErrorKind::ExcessivePoleMultiplicity, err.kind());
+
+        let err = Error::new_internal(ErrorKind::ChannelOutOfRange);
+        assert_eq!(ErrorKind::ChannelOutOfRange, err.kind());
+
+        let err = Error::new_internal(ErrorKind::UnsupportedFileFormat);
+        assert_eq!(ErrorKind::UnsupportedFileFormat, err.kind());
+
+        let err = Error::new_internal(ErrorKind::RenderingFailure);
+        assert_eq!(ErrorKind::RenderingFailure, err.kind());
+
+        let err = Error::new_internal(ErrorKind::InsufficientData);
+        assert_eq!(ErrorKind::InsufficientData, err.kind());
+
+        let err = Error::new_internal(ErrorKind::InvalidDuration);
+        assert_eq!(ErrorKind::InvalidDuration, err.kind());
+
+        let err = Error::new_internal(ErrorKind::InvalidData);
+        assert_eq!(ErrorKind::InvalidData, err.kind());
     }
 }
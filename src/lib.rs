@@ -10,6 +10,12 @@
 //!
 //! [Solvers](linear_system/solver/index.html)
 //!
+//! [Linear algebra](linear_system/linalg/index.html)
+//!
+//! ## Nonlinear systems
+//!
+//! [Nonlinear systems](nonlinear/index.html)
+//!
 //! ## Transfer function representation
 //!
 //! [Generic transfer function](transfer_function/index.html)
@@ -42,11 +48,31 @@
 //!
 //! [Units](units/index.html)
 //!
+//! ## Utilities
+//!
+//! [Grids of times and frequencies](utils/index.html)
+//!
 //! ## Signals
 //!
 //! [Continuous](signals/continuous/index.html)
 //!
 //! [Discrete](signals/discrete/index.html)
+//!
+//! ## Optional features
+//!
+//! `rand`: generation of [random stable systems](linear_system/random/index.html)
+//!
+//! `rayon`: [parallel batch simulation](linear_system/batch/index.html)
+//!
+//! `plotters`: [rendering of plots](plots/render/index.html) to PNG and SVG
+//! images
+//!
+//! `serde-serialize`: serialization of the [state-space](linear_system/index.html)
+//! representations, of the transfer functions, of the
+//! [controllers](controller/index.html) and of the
+//! [units of measurement](units/index.html), serialized as their inner value.
+//! Infinite limits and gains are serialized as the strings `"inf"` and
+//! `"-inf"`, the deserialized data are checked for consistency.
 
 #![warn(
     missing_crate_level_docs,
@@ -71,22 +97,29 @@ pub mod enums;
 pub mod error;
 mod iterator;
 pub mod linear_system;
+pub mod nonlinear;
 pub mod plots;
 pub mod polynomial;
 pub mod polynomial_matrix;
 pub mod rational_function;
+#[cfg(feature = "serde-serialize")]
+mod serde_float;
 pub mod signals;
 pub mod transfer_function;
 pub mod units;
+pub mod utils;
 
 // Export from crate root.
 pub use crate::complex::{damp, pulse};
 pub use crate::enums::{Continuous, Discrete, Discretization, Time};
 pub use crate::error::Error;
-pub use crate::linear_system::{continuous::Ss, discrete::Ssd};
+pub use crate::linear_system::{continuous::Ss, descriptor::Dss, discrete::Ssd};
 pub use crate::polynomial::Poly;
 pub use crate::rational_function::Rf;
 pub use crate::transfer_function::{
     continuous::Tf, discrete::Tfz, discretization::TfDiscretization, matrix::TfMatrix,
 };
-pub use crate::units::{Decibel, Hertz, RadiansPerSecond, Seconds};
+pub use crate::units::{
+    DampingRatio, Decibel, Degrees, Hertz, NaturalFrequency, NormalizedFrequency, Radians,
+    RadiansPerSecond, SampleTime, Seconds,
+};
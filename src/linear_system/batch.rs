@@ -0,0 +1,265 @@
+//! # Batch simulation
+//!
+//! Parallel simulation of a continuous system over a batch of initial states
+//! or of parameter perturbations (Monte Carlo analysis), useful for
+//! robustness studies.
+//!
+//! The simulations use the Runge-Kutta fourth order method and are run in
+//! parallel with [rayon](https://docs.rs/rayon).
+//!
+//! This module is available with the `rayon` feature.
+
+use nalgebra::Scalar;
+use num_traits::Float;
+use rayon::prelude::*;
+
+use std::ops::{AddAssign, MulAssign};
+
+use crate::{
+    linear_system::{
+        continuous::Ss,
+        solver::{RkConst, TimeResponse},
+    },
+    units::Seconds,
+};
+
+/// Simulate the system from each of the given initial states.
+///
+/// # Arguments
+///
+/// * `sys` - continuous system
+/// * `u` - input function of time returning a vector (column vector)
+/// * `initial_states` - initial states of the simulations
+/// * `h` - integration time interval
+/// * `n` - integration steps
+///
+/// # Example
+/// ```
+/// use au::{linear_system::batch, Seconds, Ss};
+/// let sys = Ss::new_from_slice(1, 1, 1, &[-1.], &[1.], &[1.], &[0.]);
+/// let x0 = vec![vec![0.], vec![1.], vec![2.]];
+/// let batch = batch::simulate(&sys, |_| vec![0.], &x0, Seconds(0.1), 10);
+/// assert_eq!(3, batch.len());
+/// assert_eq!(1., batch.mean_output(0)[0]);
+/// ```
+#[must_use]
+pub fn simulate<T, F>(
+    sys: &Ss<T>,
+    u: F,
+    initial_states: &[Vec<T>],
+    h: Seconds<T>,
+    n: usize,
+) -> BatchResponse<T>
+where
+    F: Fn(Seconds<T>) -> Vec<T> + Sync,
+    T: AddAssign + Float + MulAssign + RkConst + Scalar + Send + Sync,
+{
+    let responses = initial_states
+        .par_iter()
+        .map(|x0| sys.rk4(&u, x0, h, n).collect())
+        .collect();
+    BatchResponse { responses }
+}
+
+/// Simulate the systems built from each of the given parameters, starting
+/// from the same initial state.
+///
+/// # Arguments
+///
+/// * `params` - parameters of the systems
+/// * `system` - function that builds the system from a parameter
+/// * `u` - input function of time returning a vector (column vector)
+/// * `x0` - initial state
+/// * `h` - integration time interval
+/// * `n` - integration steps
+///
+/// # Example
+/// ```
+/// use au::{linear_system::batch, Seconds, Ss};
+/// let poles = [-1., -2., -4.];
+/// let batch = batch::simulate_perturbed(
+///     &poles,
+///     |&p| Ss::new_from_slice(1, 1, 1, &[p], &[-p], &[1.], &[0.]),
+///     |_| vec![1.],
+///     &[0.],
+///     Seconds(0.01),
+///     1000,
+/// );
+/// let (min, max) = batch.output_envelope(0);
+/// assert!(min[100] < max[100]);
+/// ```
+#[must_use]
+pub fn simulate_perturbed<T, P, S, F>(
+    params: &[P],
+    system: S,
+    u: F,
+    x0: &[T],
+    h: Seconds<T>,
+    n: usize,
+) -> BatchResponse<T>
+where
+    P: Sync,
+    S: Fn(&P) -> Ss<T> + Sync,
+    F: Fn(Seconds<T>) -> Vec<T> + Sync,
+    T: AddAssign + Float + MulAssign + RkConst + Scalar + Send + Sync,
+{
+    let responses = params
+        .par_iter()
+        .map(|p| system(p).rk4(&u, x0, h, n).collect())
+        .collect();
+    BatchResponse { responses }
+}
+
+/// Time responses of a batch simulation.
+///
+/// The statistics are calculated sample by sample over the responses of the
+/// batch, that shall have the same time samples.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BatchResponse<T: Float + Scalar> {
+    /// Time responses of the simulations
+    responses: Vec<TimeResponse<T>>,
+}
+
+impl<T: Float + Scalar> BatchResponse<T> {
+    /// Get the time responses of the simulations
+    #[must_use]
+    pub fn responses(&self) -> &[TimeResponse<T>] {
+        &self.responses
+    }
+
+    /// Number of simulations
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.responses.len()
+    }
+
+    /// Check if the batch has no simulations
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.responses.is_empty()
+    }
+
+    /// Get the time samples, empty if the batch has no simulations
+    #[must_use]
+    pub fn time(&self) -> &[Seconds<T>] {
+        self.responses.first().map_or(&[], TimeResponse::time)
+    }
+
+    /// Mean of the `i`-th output for each time sample
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is greater than the number of outputs.
+    #[must_use]
+    pub fn mean_output(&self, i: usize) -> Vec<T> {
+        let n = T::from(self.len()).unwrap();
+        self.fold_output(i, T::zero(), |acc, y| acc + y)
+            .into_iter()
+            .map(|s| s / n)
+            .collect()
+    }
+
+    /// Sample standard deviation of the `i`-th output for each time sample.
+    /// It is zero for batches with less than two simulations.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is greater than the number of outputs.
+    #[must_use]
+    pub fn std_output(&self, i: usize) -> Vec<T> {
+        if self.len() < 2 {
+            return vec![T::zero(); self.time().len()];
+        }
+        let mean = self.mean_output(i);
+        let n1 = T::from(self.len() - 1).unwrap();
+        let mut var = vec![T::zero(); mean.len()];
+        for r in &self.responses {
+            for ((v, &y), &m) in var.iter_mut().zip(r.output(i)).zip(&mean) {
+                *v = *v + (y - m) * (y - m);
+            }
+        }
+        var.into_iter().map(|v| (v / n1).sqrt()).collect()
+    }
+
+    /// Minimum and maximum of the `i`-th output for each time sample
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is greater than the number of outputs.
+    #[must_use]
+    pub fn output_envelope(&self, i: usize) -> (Vec<T>, Vec<T>) {
+        (
+            self.fold_output(i, T::infinity(), Float::min),
+            self.fold_output(i, T::neg_infinity(), Float::max),
+        )
+    }
+
+    /// Fold the `i`-th output of the responses sample by sample.
+    fn fold_output<G: Fn(T, T) -> T>(&self, i: usize, init: T, f: G) -> Vec<T> {
+        let mut acc = vec![init; self.time().len()];
+        for r in &self.responses {
+            for (a, &y) in acc.iter_mut().zip(r.output(i)) {
+                *a = f(*a, y);
+            }
+        }
+        acc
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn batch_initial_states() {
+        let sys = Ss::new_from_slice(1, 1, 1, &[-1.], &[1.], &[1.], &[0.]);
+        let x0 = vec![vec![1.], vec![3.]];
+        let batch = simulate(&sys, |_| vec![0.], &x0, Seconds(0.1), 20);
+        assert_eq!(2, batch.len());
+        assert!(!batch.is_empty());
+        assert_eq!(21, batch.time().len());
+        assert_eq!(x0.len(), batch.responses().len());
+
+        let mean = batch.mean_output(0);
+        let std = batch.std_output(0);
+        let (min, max) = batch.output_envelope(0);
+        assert_relative_eq!(2., mean[0]);
+        assert_relative_eq!(2_f64.sqrt(), std[0]);
+        assert_relative_eq!(1., min[0]);
+        assert_relative_eq!(3., max[0]);
+        for k in 0..21 {
+            let y1 = batch.responses()[0].output(0)[k];
+            let y2 = batch.responses()[1].output(0)[k];
+            assert_relative_eq!((y1 + y2) / 2., mean[k]);
+            assert_relative_eq!(y1, min[k]);
+            assert_relative_eq!(y2, max[k]);
+        }
+    }
+
+    #[test]
+    fn batch_perturbed_parameters() {
+        let gains = [1., 2., 3., 4.];
+        let batch = simulate_perturbed(
+            &gains,
+            |&k| Ss::new_from_slice(1, 1, 1, &[-1.], &[k], &[1.], &[0.]),
+            |_| vec![1.],
+            &[0.],
+            Seconds(0.01),
+            1000,
+        );
+        let mean = batch.mean_output(0);
+        assert_relative_eq!(2.5, *mean.last().unwrap(), max_relative = 1e-3);
+        let (min, max) = batch.output_envelope(0);
+        assert_relative_eq!(1., *min.last().unwrap(), max_relative = 1e-3);
+        assert_relative_eq!(4., *max.last().unwrap(), max_relative = 1e-3);
+    }
+
+    #[test]
+    fn empty_batch() {
+        let sys = Ss::new_from_slice(1, 1, 1, &[-1.], &[1.], &[1.], &[0.]);
+        let batch = simulate(&sys, |_| vec![0.], &[], Seconds(0.1), 20);
+        assert!(batch.is_empty());
+        assert!(batch.time().is_empty());
+        assert!(batch.std_output(0).is_empty());
+    }
+}
@@ -0,0 +1,439 @@
+//! Canonical forms of state-space systems
+//!
+//! Similarity transformations that bring a system into the controllable,
+//! observable or real modal canonical form.
+
+use nalgebra::{ComplexField, DMatrix, RealField};
+use num_complex::Complex;
+use num_traits::Float;
+
+use crate::{
+    enums::Time,
+    error::{Error, ErrorKind},
+    linear_system::{controllability_impl, observability_impl, range_basis, SsGen},
+};
+
+impl<T: ComplexField + Float + RealField, U: Time> SsGen<T, U> {
+    /// Transform the system into the controllable canonical form.
+    /// The new state is `z = T*x`, the returned system has matrices
+    /// `T*A*T^-1`, `T*B`, `C*T^-1` and `D`.
+    ///
+    /// ```text
+    ///     ┌                          ┐        ┌   ┐
+    ///     │  0    1    0   .  0      │        │ 0 │
+    ///     │  0    0    1   .  0      │        │ 0 │
+    /// A = │  .    .    .   .  .      │,   B = │ . │
+    ///     │  0    0    0   .  1      │        │ 0 │
+    ///     │ -a_0 -a_1 -a_2 . -a_(n-1)│        │ 1 │
+    ///     └                          ┘        └   ┘
+    /// ```
+    ///
+    /// The return value is `(transformed system, T)`.
+    ///
+    /// # Errors
+    ///
+    /// It returns an error if the system has more than one input or it is
+    /// not controllable.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use au::Ss;
+    /// let sys = Ss::<f64>::new_from_slice(2, 1, 1, &[-1., 0., 1., -2.], &[1., 0.], &[0., 1.], &[0.]);
+    /// let (ctrb, _t) = sys.to_controllable_form().unwrap();
+    /// assert!((ctrb.a()[(1, 0)] + 2.).abs() < 1e-12);
+    /// assert!((ctrb.a()[(1, 1)] + 3.).abs() < 1e-12);
+    /// ```
+    pub fn to_controllable_form(&self) -> Result<(Self, DMatrix<T>), Error> {
+        if self.dim.inputs != 1 {
+            return Err(Error::new_internal(ErrorKind::NoSisoSystem));
+        }
+        let n = self.dim.states;
+        let mr = controllability_impl(n, 1, &self.a, &self.b);
+        let q = last_row_of_inverse(&mr)
+            .ok_or_else(|| Error::new_internal(ErrorKind::NoControllableSystem))?;
+
+        // T = [q; q*A; q*A^2; ...; q*A^(n-1)]
+        let mut t = DMatrix::zeros(n, n);
+        if n > 0 {
+            t.set_row(0, &q);
+        }
+        for i in 1..n {
+            let row = t.row(i - 1) * &self.a;
+            t.set_row(i, &row);
+        }
+        let t_inv = t
+            .clone()
+            .try_inverse()
+            .ok_or_else(|| Error::new_internal(ErrorKind::NoControllableSystem))?;
+        Ok((self.transform(&t, &t_inv), t))
+    }
+
+    /// Transform the system into the observable canonical form.
+    /// The new state is `z = T*x`, the returned system has matrices
+    /// `T*A*T^-1`, `T*B`, `C*T^-1` and `D`.
+    ///
+    /// ```text
+    ///     ┌                   ┐
+    ///     │ 0 0 0 . 0 -a_0    │
+    ///     │ 1 0 0 . 0 -a_1    │        ┌           ┐
+    /// A = │ 0 1 0 . 0 -a_2    │,   C = │0 0 0 . 0 1│
+    ///     │ . . . . . .       │        └           ┘
+    ///     │ 0 0 0 . 1 -a_(n-1)│
+    ///     └                   ┘
+    /// ```
+    ///
+    /// The return value is `(transformed system, T)`.
+    ///
+    /// # Errors
+    ///
+    /// It returns an error if the system has more than one output or it is
+    /// not observable.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use au::Ss;
+    /// let sys = Ss::<f64>::new_from_slice(2, 1, 1, &[-1., 0., 1., -2.], &[1., 0.], &[0., 1.], &[0.]);
+    /// let (obsv, _t) = sys.to_observable_form().unwrap();
+    /// assert!((obsv.a()[(0, 1)] + 2.).abs() < 1e-12);
+    /// assert!((obsv.a()[(1, 1)] + 3.).abs() < 1e-12);
+    /// ```
+    pub fn to_observable_form(&self) -> Result<(Self, DMatrix<T>), Error> {
+        if self.dim.outputs != 1 {
+            return Err(Error::new_internal(ErrorKind::NoSisoSystem));
+        }
+        let n = self.dim.states;
+        let mo = observability_impl(n, 1, &self.a, &self.c);
+        let q = last_row_of_inverse(&mo)
+            .ok_or_else(|| Error::new_internal(ErrorKind::NoObservableSystem))?;
+
+        // Dual of the controllable form: T^-1 = [q' A*q' A^2*q' ... A^(n-1)*q']
+        let mut t_inv = DMatrix::zeros(n, n);
+        if n > 0 {
+            t_inv.set_column(0, &q.transpose());
+        }
+        for i in 1..n {
+            let col = &self.a * t_inv.column(i - 1);
+            t_inv.set_column(i, &col);
+        }
+        let t = t_inv
+            .clone()
+            .try_inverse()
+            .ok_or_else(|| Error::new_internal(ErrorKind::NoObservableSystem))?;
+        Ok((self.transform(&t, &t_inv), t))
+    }
+
+    /// Transform the system into the real modal canonical form.
+    /// The new state is `z = T*x`, the returned system has matrices
+    /// `T*A*T^-1`, `T*B`, `C*T^-1` and `D`.
+    ///
+    /// The states matrix is block diagonal, real eigenvalues are on the
+    /// diagonal and each pair of complex conjugate eigenvalues `σ ± jω`
+    /// corresponds to the block:
+    /// ```text
+    /// ┌      ┐
+    /// │  σ ω │
+    /// │ -ω σ │
+    /// └      ┘
+    /// ```
+    ///
+    /// The return value is `(transformed system, T)`.
+    ///
+    /// # Errors
+    ///
+    /// It returns an error if the states matrix is not diagonalizable.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use au::Ss;
+    /// let sys = Ss::<f64>::new_from_slice(2, 1, 1, &[0., 1., -2., -2.], &[0., 1.], &[1., 0.], &[0.]);
+    /// let (modal, _t) = sys.to_modal_form().unwrap();
+    /// // Eigenvalues -1 ± j
+    /// assert!((modal.a()[(0, 0)] + 1.).abs() < 1e-12);
+    /// assert!((modal.a()[(0, 1)] - 1.).abs() < 1e-12);
+    /// assert!((modal.a()[(1, 0)] + 1.).abs() < 1e-12);
+    /// ```
+    pub fn to_modal_form(&self) -> Result<(Self, DMatrix<T>), Error> {
+        let n = self.dim.states;
+        let no_diagonalizable = || Error::new_internal(ErrorKind::NoDiagonalizableSystem);
+        let scale = Float::max(T::one(), self.a.norm());
+        let tol = Float::sqrt(T::epsilon()) * scale;
+
+        let poles = self.poles();
+        let mut processed: Vec<Complex<T>> = Vec::with_capacity(n);
+        let mut t_inv = DMatrix::zeros(n, n);
+        let mut col = 0;
+        for &p in &poles {
+            if p.im < -tol || processed.iter().any(|&q| (q - p).norm() <= tol) {
+                continue;
+            }
+            processed.push(p);
+            let multiplicity = poles.iter().filter(|&&q| (q - p).norm() <= tol).count();
+            if Float::abs(p.im) <= tol {
+                let a_p = &self.a - DMatrix::from_diagonal_element(n, n, p.re);
+                let v = right_null_vectors(a_p, multiplicity, tol).ok_or_else(no_diagonalizable)?;
+                t_inv.columns_mut(col, multiplicity).copy_from(&v);
+                col += multiplicity;
+            } else {
+                let a_p = self.a.map(|x| Complex::new(x, T::zero()))
+                    - DMatrix::from_diagonal_element(n, n, p);
+                let v = right_null_vectors(a_p, multiplicity, tol).ok_or_else(no_diagonalizable)?;
+                // A*(vr + j*vi) = (σ + jω)*(vr + j*vi) => A*[vr vi] = [vr vi]*[σ ω; -ω σ]
+                for k in 0..multiplicity {
+                    t_inv.set_column(col, &v.column(k).map(|c| c.re));
+                    t_inv.set_column(col + 1, &v.column(k).map(|c| c.im));
+                    col += 2;
+                }
+            }
+        }
+        if col != n {
+            return Err(no_diagonalizable());
+        }
+
+        // Reject an ill conditioned eigenvector basis (defective matrix).
+        let sv = t_inv.singular_values();
+        let sv_max = sv.iter().fold(T::zero(), |acc, &s| Float::max(acc, s));
+        let sv_min = sv.iter().fold(T::infinity(), |acc, &s| Float::min(acc, s));
+        if n > 0 && sv_min <= tol * sv_max {
+            return Err(no_diagonalizable());
+        }
+        let t = t_inv.clone().try_inverse().ok_or_else(no_diagonalizable)?;
+        Ok((self.transform(&t, &t_inv), t))
+    }
+
+    /// Apply the similarity transformation `z = T*x` to the system.
+    fn transform(&self, t: &DMatrix<T>, t_inv: &DMatrix<T>) -> Self {
+        Self::new_from_matrices(
+            t * &self.a * t_inv,
+            t * &self.b,
+            &self.c * t_inv,
+            self.d.clone(),
+        )
+        .with_names(self.names.io())
+        .with_sampling(self.sample_time)
+    }
+}
+
+/// Last row of the inverse of a square matrix, if the matrix is full rank.
+fn last_row_of_inverse<T: ComplexField + Float + RealField>(
+    m: &DMatrix<T>,
+) -> Option<nalgebra::RowDVector<T>> {
+    let n = m.nrows();
+    let eps = T::epsilon();
+    let tol = eps * T::from(n).unwrap_or_else(T::one);
+    if range_basis(m, tol).ncols() < n {
+        return None;
+    }
+    m.clone()
+        .try_inverse()
+        .map(|inv| inv.row(n.saturating_sub(1)).into_owned())
+}
+
+/// Orthonormal vectors spanning the null space of the square matrix `m`,
+/// whose dimension is assumed to be `dim`. It returns `None` if the singular
+/// values associated to the vectors are greater than `tol`.
+fn right_null_vectors<N>(m: DMatrix<N>, dim: usize, tol: N::RealField) -> Option<DMatrix<N>>
+where
+    N: ComplexField,
+    N::RealField: Float,
+{
+    let n = m.ncols();
+    let svd = m.svd(false, true);
+    let sv = svd.singular_values;
+    let v_t = svd.v_t.expect("Right singular vectors have been requested");
+    let mut idx: Vec<usize> = (0..n).collect();
+    idx.sort_by(|&i, &j| {
+        sv[i]
+            .partial_cmp(&sv[j])
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    if idx.iter().take(dim).any(|&i| sv[i] > tol) {
+        return None;
+    }
+    Some(DMatrix::from_fn(n, dim, |r, c| {
+        v_t[(idx[c], r)].conjugate()
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Continuous, Discrete};
+
+    fn assert_matrix_eq(expected: &DMatrix<f64>, actual: &DMatrix<f64>) {
+        assert_eq!(expected.shape(), actual.shape());
+        assert_relative_eq!(expected, actual, max_relative = 1e-9, epsilon = 1e-9);
+    }
+
+    fn sys_3() -> SsGen<f64, Continuous> {
+        SsGen::new_from_slice(
+            3,
+            1,
+            1,
+            &[-1., 2., 0., 0., -3., 1., 1., 0., -4.],
+            &[1., 0., 2.],
+            &[1., 1., 0.],
+            &[0.5],
+        )
+    }
+
+    #[test]
+    fn controllable_form() {
+        let sys = sys_3();
+        let (ctrb, t) = sys.to_controllable_form().unwrap();
+        let expected_b = DMatrix::from_column_slice(3, 1, &[0., 0., 1.]);
+        assert_matrix_eq(&expected_b, &ctrb.b);
+        // Characteristic polynomial s^3 + 8s^2 + 19s + 10
+        let expected_a = DMatrix::from_row_slice(3, 3, &[0., 1., 0., 0., 0., 1., -10., -19., -8.]);
+        assert_matrix_eq(&expected_a, &ctrb.a);
+        assert_matrix_eq(&sys.b, &(t.clone().try_inverse().unwrap() * &ctrb.b));
+        assert_matrix_eq(&(&sys.c * t.try_inverse().unwrap()), &ctrb.c);
+        assert_eq!(sys.d, ctrb.d);
+    }
+
+    #[test]
+    fn observable_form() {
+        let sys = sys_3();
+        let (obsv, t) = sys.to_observable_form().unwrap();
+        let expected_c = DMatrix::from_row_slice(1, 3, &[0., 0., 1.]);
+        assert_matrix_eq(&expected_c, &obsv.c);
+        let expected_a = DMatrix::from_row_slice(3, 3, &[0., 0., -10., 1., 0., -19., 0., 1., -8.]);
+        assert_matrix_eq(&expected_a, &obsv.a);
+        assert_matrix_eq(&(&t * &sys.b), &obsv.b);
+    }
+
+    #[test]
+    fn canonical_forms_same_transfer_function() {
+        let sys = sys_3();
+        let (ctrb, _) = sys.to_controllable_form().unwrap();
+        let (obsv, _) = sys.to_observable_form().unwrap();
+        // The observable form is the transpose of the controllable form.
+        assert_matrix_eq(&ctrb.a.transpose(), &obsv.a);
+        assert_matrix_eq(&ctrb.c.transpose(), &obsv.b);
+    }
+
+    #[test]
+    fn canonical_forms_fail() {
+        let sys = SsGen::<_, Discrete>::new_from_slice(
+            2,
+            1,
+            1,
+            &[1., 0., 0., 2.],
+            &[1., 0.],
+            &[0., 1.],
+            &[0.],
+        );
+        assert_eq!(
+            ErrorKind::NoControllableSystem,
+            sys.to_controllable_form().unwrap_err().kind()
+        );
+        assert_eq!(
+            ErrorKind::NoObservableSystem,
+            sys.to_observable_form().unwrap_err().kind()
+        );
+
+        let mimo = SsGen::<_, Discrete>::new_from_slice(
+            1,
+            2,
+            2,
+            &[1.],
+            &[1., 1.],
+            &[1., 1.],
+            &[0., 0., 0., 0.],
+        );
+        assert_eq!(
+            ErrorKind::NoSisoSystem,
+            mimo.to_controllable_form().unwrap_err().kind()
+        );
+        assert_eq!(
+            ErrorKind::NoSisoSystem,
+            mimo.to_observable_form().unwrap_err().kind()
+        );
+    }
+
+    #[test]
+    fn modal_form_real() {
+        let sys = SsGen::<_, Continuous>::new_from_slice(
+            3,
+            1,
+            1,
+            &[-1., 2., 0., 0., -3., 1., 0., 0., -4.],
+            &[1., 0., 2.],
+            &[1., 1., 0.],
+            &[0.],
+        );
+        let (modal, t) = sys.to_modal_form().unwrap();
+        let t_inv = t.clone().try_inverse().unwrap();
+        assert_matrix_eq(&sys.a, &(&t_inv * &modal.a * &t));
+        let mut diag: Vec<_> = modal.a.diagonal().iter().copied().collect();
+        diag.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let mut poles: Vec<_> = sys.poles().iter().map(|p| p.re).collect();
+        poles.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        for (p, d) in poles.iter().zip(&diag) {
+            assert_relative_eq!(*p, *d, max_relative = 1e-9);
+        }
+        let off_diagonal = &modal.a - DMatrix::from_diagonal(&modal.a.diagonal());
+        assert!(off_diagonal.norm() < 1e-9);
+    }
+
+    #[test]
+    fn modal_form_complex() {
+        let sys = SsGen::<_, Continuous>::new_from_slice(
+            3,
+            1,
+            1,
+            &[0., 1., 0., -5., -2., 0., 0., 0., -3.],
+            &[0., 1., 1.],
+            &[1., 0., 1.],
+            &[0.],
+        );
+        let (modal, t) = sys.to_modal_form().unwrap();
+        let t_inv = t.clone().try_inverse().unwrap();
+        assert_matrix_eq(&sys.a, &(&t_inv * &modal.a * &t));
+        // Eigenvalues -1 ± 2j and -3
+        let (block, real) = if modal.a[(2, 2)] < -2. {
+            (modal.a.slice((0, 0), (2, 2)).into_owned(), modal.a[(2, 2)])
+        } else {
+            (modal.a.slice((1, 1), (2, 2)).into_owned(), modal.a[(0, 0)])
+        };
+        assert_relative_eq!(-3., real, max_relative = 1e-9);
+        assert_relative_eq!(-1., block[(0, 0)], max_relative = 1e-9);
+        assert_relative_eq!(-1., block[(1, 1)], max_relative = 1e-9);
+        assert_relative_eq!(2., block[(0, 1)].abs(), max_relative = 1e-9);
+        assert_relative_eq!(-block[(0, 1)], block[(1, 0)], max_relative = 1e-9);
+    }
+
+    #[test]
+    fn modal_form_repeated_eigenvalues() {
+        let sys = SsGen::<_, Continuous>::new_from_slice(
+            2,
+            1,
+            1,
+            &[-2., 0., 0., -2.],
+            &[1., 1.],
+            &[1., 0.],
+            &[0.],
+        );
+        let (modal, _) = sys.to_modal_form().unwrap();
+        assert_matrix_eq(&DMatrix::from_diagonal_element(2, 2, -2.), &modal.a);
+    }
+
+    #[test]
+    fn modal_form_fail() {
+        let sys = SsGen::<_, Continuous>::new_from_slice(
+            2,
+            1,
+            1,
+            &[-2., 1., 0., -2.],
+            &[1., 1.],
+            &[1., 0.],
+            &[0.],
+        );
+        assert_eq!(
+            ErrorKind::NoDiagonalizableSystem,
+            sys.to_modal_form().unwrap_err().kind()
+        );
+    }
+}
@@ -3,18 +3,30 @@
 //! The time evolution of the system is performed through ODE (ordinary
 //! differential equation) [solvers](../solver/index.html).
 
-use nalgebra::{ComplexField, DVector, RealField};
-use num_traits::Float;
+use approx::{AbsDiffEq, RelativeEq};
+use nalgebra::{Cholesky, ComplexField, DMatrix, DVector, RealField, Scalar, SimdPartialOrd};
+use num_complex::Complex;
+use num_traits::{Float, Signed, Zero};
+#[cfg(feature = "rand")]
+use rand::Rng;
+
+use std::ops::{AddAssign, MulAssign, SubAssign};
 
 use crate::{
-    enums::Continuous,
+    enums::{Continuous, Interpolation},
+    error::{Error, ErrorKind},
     linear_system::{
-        solver::{Order, Radau, Rk, Rkf45},
+        eigenvalues, linalg,
+        solver::{Order, Radau, RadauConst, Rk, RkConst, Rkf45, Rkf45Const, Step},
         Equilibrium, SsGen,
     },
-    units::Seconds,
+    plots::sigma::{Sigma, SigmaPlot},
+    units::{DampingRatio, NaturalFrequency, RadiansPerSecond, Seconds},
 };
 
+#[cfg(feature = "rand")]
+use crate::linear_system::random::{self, RandomPoles};
+
 /// State-space representation of continuous time linear system
 pub type Ss<T> = SsGen<T, Continuous>;
 
@@ -27,10 +39,28 @@ impl<T: ComplexField> Ss<T> {
     /// y = - (C * A^-1 * B + D) * u
     /// ```
     ///
+    /// If the A matrix is singular the equilibrium condition `0 = A*x + B*u`
+    /// has either no solution, when `B*u` is not in the range of A, or
+    /// infinitely many solutions. In both cases `least_squares_equilibrium`
+    /// gives the minimum norm solution `x`, and the equilibrium points are
+    /// the family `x + N*z`, where the columns of `N` are a basis of the null
+    /// space of A, available as `Equilibrium::null_space`.
+    ///
     /// # Arguments
     ///
     /// * `u` - Input vector
     ///
+    /// # Errors
+    ///
+    /// It returns an error if the A matrix is singular: the kind is
+    /// `NoEquilibrium` if the equilibrium condition cannot be satisfied and
+    /// `UnderdeterminedEquilibrium` if it has infinitely many solutions.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of the input vector does not match the number of
+    /// inputs of the system.
+    ///
     /// # Example
     ///
     /// ```
@@ -45,50 +75,1197 @@ impl<T: ComplexField> Ss<T> {
     /// let eq = sys.equilibrium(&[u]).unwrap();
     /// assert_eq!((0., 0.), (eq.x()[0], eq.y()[0]));
     /// ```
-    pub fn equilibrium(&self, u: &[T]) -> Option<Equilibrium<T>> {
+    pub fn equilibrium(&self, u: &[T]) -> Result<Equilibrium<T>, Error> {
+        let (eq, consistent) = self.equilibrium_impl(u);
+        if !consistent {
+            Err(Error::new_internal(ErrorKind::NoEquilibrium))
+        } else if !eq.is_unique() {
+            Err(Error::new_internal(ErrorKind::UnderdeterminedEquilibrium))
+        } else {
+            Ok(eq)
+        }
+    }
+
+    /// Calculate the minimum norm least squares solution of the equilibrium
+    /// condition `0 = A*x + B*u` for continuous time systems, given the input.
+    ///
+    /// It coincides with the equilibrium point when it exists, otherwise it
+    /// is the state that minimizes the norm of the state derivative.
+    ///
+    /// # Arguments
+    ///
+    /// * `u` - Input vector
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of the input vector does not match the number of
+    /// inputs of the system.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use au::Ss;
+    /// let sys = Ss::new_from_slice(2, 1, 1, &[0., 0., 0., -1.], &[1., 1.], &[1., 1.], &[0.]);
+    /// assert!(sys.equilibrium(&[1.]).is_err());
+    /// let eq = sys.least_squares_equilibrium(&[1.]);
+    /// assert_eq!(&[0., 1.], eq.x());
+    /// ```
+    #[must_use]
+    pub fn least_squares_equilibrium(&self, u: &[T]) -> Equilibrium<T> {
+        self.equilibrium_impl(u).0
+    }
+
+    /// Minimum norm least squares solution of `A*x = -B*u` through the
+    /// singular value decomposition of A, and whether the solution satisfies
+    /// the equilibrium condition.
+    ///
+    /// # Arguments
+    ///
+    /// * `u` - Input vector
+    fn equilibrium_impl(&self, u: &[T]) -> (Equilibrium<T>, bool) {
         assert_eq!(u.len(), self.b.ncols(), "Wrong number of inputs.");
+        let n = self.dim.states;
         let u = DVector::from_row_slice(u);
         // 0 = A*x + B*u
         let bu = -&self.b * &u;
-        let lu = &self.a.clone().lu();
-        // A*x = -B*u
-        let x = lu.solve(&bu)?;
+        let svd = self.a.clone().svd(true, true);
+        let sigma_max = svd
+            .singular_values
+            .iter()
+            .fold(T::RealField::zero(), |m, s| m.max(*s));
+        let eps = <T::RealField as AbsDiffEq>::default_epsilon();
+        let tol = nalgebra::convert::<f64, T::RealField>(n as f64) * eps * sigma_max;
+        let null: Vec<_> = (0..n).filter(|&i| svd.singular_values[i] <= tol).collect();
+        if null.is_empty() {
+            // A*x = -B*u
+            if let Some(x) = self.a.clone().lu().solve(&bu) {
+                // y = C*x + D*u
+                let y = &self.c * &x + &self.d * u;
+                return (Equilibrium::new(x, y), true);
+            }
+        }
+        // Minimum norm least squares solution of A*x = -B*u
+        let x = svd.solve(&bu, tol).unwrap_or_else(|_| DVector::zeros(n));
+        let null_space = match &svd.v_t {
+            Some(v_t) => DMatrix::from_fn(n, null.len(), |r, c| v_t[(null[c], r)].conjugate()),
+            None => DMatrix::zeros(n, 0),
+        };
+        let residual = (&self.a * &x - &bu).norm();
+        let scale = sigma_max * x.norm() + bu.norm();
+        let consistent = residual <= eps.sqrt() * scale;
         // y = C*x + D*u
         let y = &self.c * &x + &self.d * u;
-        Some(Equilibrium::new(x, y))
+        (Equilibrium::new_family(x, y, null_space), consistent)
+    }
+}
+
+/// Generation of random systems
+#[cfg(feature = "rand")]
+impl<T: ComplexField + Float + RealField> Ss<T> {
+    /// Generate a random stable system, the natural frequency of the poles
+    /// is in the interval [0.1, 10].
+    ///
+    /// # Arguments
+    ///
+    /// * `states` - number of states
+    /// * `inputs` - number of inputs
+    /// * `outputs` - number of outputs
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use au::Ss;
+    /// let sys = Ss::<f64>::rand_stable(4, 2, 3);
+    /// assert!(sys.is_stable());
+    /// ```
+    #[must_use]
+    pub fn rand_stable(states: usize, inputs: usize, outputs: usize) -> Self {
+        Self::rand_stable_with(
+            states,
+            inputs,
+            outputs,
+            &RandomPoles::continuous(),
+            &mut rand::thread_rng(),
+        )
+    }
+
+    /// Generate a random stable system with the given distribution of the
+    /// poles and random number generator.
+    ///
+    /// The magnitude of the distribution is the natural frequency of the poles.
+    ///
+    /// # Arguments
+    ///
+    /// * `states` - number of states
+    /// * `inputs` - number of inputs
+    /// * `outputs` - number of outputs
+    /// * `poles` - distribution of the poles
+    /// * `rng` - random number generator
+    ///
+    /// # Panics
+    ///
+    /// Panics if the minimum magnitude of the poles is zero.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use au::{linear_system::random::RandomPoles, Ss};
+    /// use rand::thread_rng;
+    /// let poles = RandomPoles::new(1., 2.).with_complex_probability(0.);
+    /// let sys = Ss::<f64>::rand_stable_with(3, 1, 1, &poles, &mut thread_rng());
+    /// assert!(sys.poles().iter().all(|p| p.im == 0. && p.re <= -1. && p.re >= -2.));
+    /// ```
+    pub fn rand_stable_with<R: Rng + ?Sized>(
+        states: usize,
+        inputs: usize,
+        outputs: usize,
+        poles: &RandomPoles,
+        rng: &mut R,
+    ) -> Self {
+        assert!(
+            poles.magnitude().0 > 0.,
+            "Minimum magnitude of the poles must be positive"
+        );
+        let [a, b, c, d] = random::random_matrices((states, inputs, outputs), poles, false, rng);
+        Self::new_from_matrices(a, b, c, d)
+    }
+}
+
+/// Implementation of the methods for the state-space
+impl<T: ComplexField + Float + RealField> Ss<T> {
+    /// System stability. Checks if all A matrix eigenvalues (poles) are negative.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use au::Ss;
+    /// let sys = Ss::new_from_slice(2, 1, 1, &[-2., 0., 3., -7.], &[1., 3.], &[-1., 0.5], &[0.1]);
+    /// assert!(sys.is_stable());
+    /// ```
+    #[must_use]
+    pub fn is_stable(&self) -> bool {
+        self.poles().iter().all(|p| p.re.is_negative())
+    }
+
+    /// Stability margin of the system, the maximum real part of the A matrix
+    /// eigenvalues (poles). The system is stable if it is negative.
+    ///
+    /// It is negative infinity for systems without states.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use au::Ss;
+    /// let sys = Ss::new_from_slice(2, 1, 1, &[-2., 0., 3., -7.], &[1., 3.], &[-1., 0.5], &[0.1]);
+    /// assert_eq!(-2., sys.stability_margin());
+    /// ```
+    #[must_use]
+    pub fn stability_margin(&self) -> T {
+        self.poles()
+            .iter()
+            .fold(T::neg_infinity(), |m, p| Float::max(m, p.re))
+    }
+
+    /// Modal analysis of the system. For each eigenvalue of the A matrix
+    /// it returns the natural frequency, the damping ratio, the time constant
+    /// and the mode shape (eigenvector).
+    ///
+    /// The modes are in the same order of the poles of the system.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use au::Ss;
+    /// let sys = Ss::new_from_slice(2, 1, 1, &[0., 1., -4., -2.], &[0., 1.], &[1., 0.], &[0.]);
+    /// let modes = sys.modes();
+    /// assert_eq!(2, modes.len());
+    /// assert!((modes[0].natural_frequency().0 - 2_f64).abs() < 1e-12);
+    /// assert!((modes[0].damping().0 - 0.5_f64).abs() < 1e-12);
+    /// assert!((modes[0].time_constant() - 1_f64).abs() < 1e-12);
+    /// ```
+    #[must_use]
+    pub fn modes(&self) -> Vec<Mode<T>> {
+        let n = self.dim.states;
+        let a = self.a.map(|x| Complex::new(x, T::zero()));
+        self.poles()
+            .into_iter()
+            .map(|p| {
+                let shifted = &a - DMatrix::from_diagonal_element(n, n, p);
+                Mode {
+                    eigenvalue: p,
+                    natural_frequency: crate::complex::pulse(p),
+                    damping: crate::complex::damp(p),
+                    time_constant: -T::one() / p.re,
+                    shape: null_vector(shifted),
+                }
+            })
+            .collect()
+    }
+
+    /// Balanced realization of the system. In the balanced realization the
+    /// controllability and observability gramians are equal and diagonal,
+    /// their diagonal contains the Hankel singular values of the system.
+    ///
+    /// The balancing transformation is calculated with the square root
+    /// algorithm, the Hankel singular values are in decreasing order.
+    ///
+    /// The return value is: `(balanced system, Hankel singular values)`
+    ///
+    /// # Errors
+    ///
+    /// It returns an error if the system is not stable or it is not minimal.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use au::Ss;
+    /// let sys = Ss::new_from_slice(2, 1, 1, &[-2., 0., 3., -7.], &[1., 3.], &[-1., 0.5], &[0.1]);
+    /// let (bal, hsv) = sys.balanced().unwrap();
+    /// assert_eq!(2, hsv.len());
+    /// assert!(hsv[0] >= hsv[1]);
+    /// ```
+    pub fn balanced(&self) -> Result<(Self, Vec<T>), Error> {
+        if !self.is_stable() {
+            return Err(Error::new_internal(ErrorKind::UnstableSystem));
+        }
+        let no_minimal = || Error::new_internal(ErrorKind::NoMinimalSystem);
+        // Controllability gramian: A*Wc + Wc*A' + B*B' = 0
+        let wc = linalg::lyap(&self.a, &(&self.b * self.b.transpose())).ok_or_else(no_minimal)?;
+        // Observability gramian: A'*Wo + Wo*A + C'*C = 0
+        let wo = linalg::lyap(&self.a.transpose(), &(self.c.transpose() * &self.c))
+            .ok_or_else(no_minimal)?;
+        // Wc = Lc*Lc', Wo = Lo*Lo'
+        let lc = Cholesky::new(wc).ok_or_else(no_minimal)?.l();
+        let lo = Cholesky::new(wo).ok_or_else(no_minimal)?.l();
+
+        // Lo'*Lc = U*S*V'
+        let svd = (lo.tr_mul(&lc)).svd(true, true);
+        let u = svd.u.ok_or_else(no_minimal)?;
+        let v_t = svd.v_t.ok_or_else(no_minimal)?;
+        let sv = svd.singular_values;
+        // Order the singular values in decreasing order.
+        let mut idx: Vec<usize> = (0..sv.len()).collect();
+        idx.sort_by(|&i, &j| {
+            sv[j]
+                .partial_cmp(&sv[i])
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        if idx.iter().any(|&i| sv[i] <= T::zero()) {
+            return Err(no_minimal());
+        }
+        let hsv: Vec<T> = idx.iter().map(|&i| sv[i]).collect();
+
+        let n = self.dim.states;
+        // T = Lc*V*S^(-1/2), T^(-1) = S^(-1/2)*U'*Lo'
+        let v = DMatrix::from_fn(n, n, |r, c| v_t[(idx[c], r)] / Float::sqrt(hsv[c]));
+        let u_t = DMatrix::from_fn(n, n, |r, c| u[(c, idx[r])] / Float::sqrt(hsv[r]));
+        let t = lc * v;
+        let t_inv = u_t * lo.transpose();
+
+        let a = &t_inv * &self.a * &t;
+        let b = &t_inv * &self.b;
+        let c = &self.c * &t;
+        Ok((
+            Self::new_from_matrices(a, b, c, self.d.clone()).with_names(self.names.io()),
+            hsv,
+        ))
+    }
+
+    /// Balanced truncation of the system. The states of the balanced
+    /// realization associated to the smallest Hankel singular values are
+    /// removed.
+    ///
+    /// The reduced system is stable, the H-infinity norm of the error is
+    /// bounded by twice the sum of the discarded Hankel singular values.
+    ///
+    /// The return value is: `(reduced system, H-infinity error bound)`
+    ///
+    /// # Arguments
+    ///
+    /// * `order` - Number of states of the reduced system
+    ///
+    /// # Errors
+    ///
+    /// It returns an error if the system is not stable or it is not minimal.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `order` is greater than the number of states.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use au::Ss;
+    /// let sys = Ss::new_from_slice(2, 1, 1, &[-2., 0., 3., -7.], &[1., 3.], &[-1., 0.5], &[0.1]);
+    /// let (red, bound) = sys.balanced_truncation(1).unwrap();
+    /// assert_eq!(1, red.dim().states());
+    /// assert!(bound > 0.);
+    /// ```
+    pub fn balanced_truncation(&self, order: usize) -> Result<(Self, T), Error> {
+        assert!(
+            order <= self.dim.states,
+            "Order cannot be greater than the number of states"
+        );
+        let (bal, hsv) = self.balanced()?;
+        let two = T::one() + T::one();
+        let bound = hsv[order..].iter().fold(T::zero(), |acc, &s| acc + s) * two;
+        let a = bal.a.slice((0, 0), (order, order)).into_owned();
+        let b = bal.b.rows(0, order).into_owned();
+        let c = bal.c.columns(0, order).into_owned();
+        Ok((
+            Self::new_from_matrices(a, b, c, bal.d).with_names(bal.names),
+            bound,
+        ))
+    }
+
+    /// Continuous time linear quadratic regulator.
+    ///
+    /// Calculate the state feedback gain `K` of the control law `u = -K*x`
+    /// that minimizes the cost function
+    /// ```text
+    /// J = ∫ x(t)'*Q*x(t) + u(t)'*R*u(t) dt
+    /// ```
+    /// The gain is `K = R^-1*B'*P`, where `P` is the stabilizing solution of
+    /// the continuous algebraic Riccati equation.
+    ///
+    /// The return value is: `(K, P)`
+    ///
+    /// # Arguments
+    ///
+    /// * `q` - State weight matrix (nxn), symmetric positive semi-definite
+    /// * `r` - Input weight matrix (mxm), symmetric positive definite
+    ///
+    /// # Errors
+    ///
+    /// It returns an error if the Riccati equation has no stabilizing
+    /// solution or `R` is not positive definite.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the weight matrices dimensions do not match the system ones.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use au::{nalgebra::DMatrix, Ss};
+    /// let sys = Ss::new_from_slice(2, 1, 1, &[0., 1., 0., 0.], &[0., 1.], &[1., 0.], &[0.]);
+    /// let (k, p) = sys.lqr(&DMatrix::identity(2, 2), &DMatrix::identity(1, 1)).unwrap();
+    /// assert!((k[(0, 0)] - 1_f64).abs() < 1e-10);
+    /// assert!((k[(0, 1)] - 3_f64.sqrt()).abs() < 1e-10);
+    /// ```
+    pub fn lqr(&self, q: &DMatrix<T>, r: &DMatrix<T>) -> Result<(DMatrix<T>, DMatrix<T>), Error> {
+        let no_solution = || Error::new_internal(ErrorKind::NoRiccatiSolution);
+        let p = linalg::care(&self.a, &self.b, q, r).ok_or_else(no_solution)?;
+        let k = Cholesky::new(r.clone())
+            .ok_or_else(no_solution)?
+            .solve(&self.b.tr_mul(&p));
+        let closed_loop = Self::new_from_matrices(
+            &self.a - &self.b * &k,
+            self.b.clone(),
+            self.c.clone(),
+            self.d.clone(),
+        );
+        if closed_loop.is_stable() {
+            Ok((k, p))
+        } else {
+            Err(no_solution())
+        }
+    }
+
+    /// Kalman filter (linear quadratic estimator) for the system
+    /// ```text
+    /// xdot(t) = A * x(t) + B * u(t) + w(t)
+    /// y(t)    = C * x(t) + D * u(t) + v(t)
+    /// ```
+    /// where `w` and `v` are uncorrelated white noises with covariance `Qn`
+    /// and `Rn`.
+    ///
+    /// The gain `L` of the estimator
+    /// `xhatdot = A*xhat + B*u + L*(y - C*xhat - D*u)` is `L = P*C'*Rn^-1`,
+    /// where `P` is the stabilizing solution of the dual Riccati equation.
+    ///
+    /// The return value is: `(L, P)`
+    ///
+    /// # Arguments
+    ///
+    /// * `qn` - Process noise covariance (nxn), symmetric positive semi-definite
+    /// * `rn` - Measurement noise covariance (pxp), symmetric positive definite
+    ///
+    /// # Errors
+    ///
+    /// It returns an error if the Riccati equation has no stabilizing
+    /// solution or `Rn` is not positive definite.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the covariance matrices dimensions do not match the system
+    /// ones.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use au::{nalgebra::DMatrix, Ss};
+    /// let sys = Ss::new_from_slice(2, 1, 1, &[0., 1., 0., 0.], &[0., 1.], &[1., 0.], &[0.]);
+    /// let (l, p) = sys.lqe(&DMatrix::identity(2, 2), &DMatrix::identity(1, 1)).unwrap();
+    /// assert_eq!((2, 1), l.shape());
+    /// ```
+    pub fn lqe(&self, qn: &DMatrix<T>, rn: &DMatrix<T>) -> Result<(DMatrix<T>, DMatrix<T>), Error> {
+        // Duality with the linear quadratic regulator.
+        let dual = Self::new_from_matrices(
+            self.a.transpose(),
+            self.c.transpose(),
+            self.b.transpose(),
+            self.d.transpose(),
+        );
+        let (k, p) = dual.lqr(qn, rn)?;
+        Ok((k.transpose(), p))
+    }
+
+    /// Linear quadratic Gaussian compensator. The compensator combines the
+    /// linear quadratic regulator and the Kalman filter, its input is the
+    /// measured output `y` of the system and its output is the control input
+    /// `u`:
+    /// ```text
+    /// xhatdot = (A - B*K - L*C + L*D*K) * xhat + L * y
+    /// u       = -K * xhat
+    /// ```
+    ///
+    /// The compensator is built with the
+    /// [`observer_compensator`](../struct.SsGen.html#method.observer_compensator)
+    /// method.
+    ///
+    /// The closed loop system can be obtained with the
+    /// [`closed_loop`](../struct.SsGen.html#method.closed_loop) method.
+    ///
+    /// # Arguments
+    ///
+    /// * `q` - State weight matrix (nxn)
+    /// * `r` - Input weight matrix (mxm)
+    /// * `qn` - Process noise covariance (nxn)
+    /// * `rn` - Measurement noise covariance (pxp)
+    ///
+    /// # Errors
+    ///
+    /// It returns an error if one of the Riccati equations has no stabilizing
+    /// solution.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the matrices dimensions do not match the system ones.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use au::{nalgebra::DMatrix, Ss};
+    /// let sys = Ss::new_from_slice(2, 1, 1, &[0., 1., 0., 0.], &[0., 1.], &[1., 0.], &[0.]);
+    /// let i2 = DMatrix::identity(2, 2);
+    /// let i1 = DMatrix::identity(1, 1);
+    /// let comp = sys.lqg(&i2, &i1, &i2, &i1).unwrap();
+    /// assert!(sys.closed_loop(&comp).unwrap().is_stable());
+    /// ```
+    pub fn lqg(
+        &self,
+        q: &DMatrix<T>,
+        r: &DMatrix<T>,
+        qn: &DMatrix<T>,
+        rn: &DMatrix<T>,
+    ) -> Result<Self, Error> {
+        let (k, _) = self.lqr(q, r)?;
+        let (l, _) = self.lqe(qn, rn)?;
+        Ok(self.observer_compensator(&k, &l))
+    }
+
+    /// H-infinity loop shaping synthesis (Glover-McFarlane).
+    ///
+    /// The system is the shaped plant `Gs = W1*G*W2`, where the weights
+    /// `W1` and `W2` give the desired open loop shape. The controller
+    /// robustly stabilizes the normalized coprime factor perturbations of
+    /// the shaped plant, the final controller for the plant `G` is
+    /// `W2*K*W1`.
+    ///
+    /// With `R = I + D*D'` and `S = I + D'*D`, `X` and `Z` are the
+    /// stabilizing solutions of the control and filter Riccati equations
+    /// ```text
+    /// Ar'*X + X*Ar - X*B*S^-1*B'*X + C'*R^-1*C = 0
+    /// Ar*Z + Z*Ar' - Z*C'*R^-1*C*Z + B*S^-1*B' = 0
+    /// ```
+    /// where `Ar = A - B*S^-1*D'*C`. The maximum stability margin is
+    /// `eps_max = 1/sqrt(1 + rho(X*Z))`, the controller is designed for the
+    /// margin `eps = eps_max / factor`.
+    ///
+    /// The controller is connected in positive feedback, as required by the
+    /// [`closed_loop`](../struct.SsGen.html#method.closed_loop) method.
+    ///
+    /// The return value is: `(K, eps)`
+    ///
+    /// # Arguments
+    ///
+    /// * `factor` - Relaxation of the optimal performance, greater than one
+    ///   (typically 1.1)
+    ///
+    /// # Errors
+    ///
+    /// It returns an error if one of the Riccati equations has no stabilizing
+    /// solution, i.e. the system is not stabilizable or not detectable.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the relaxation factor is not greater than one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use au::Ss;
+    /// // Shaped plant 1/(s+1)
+    /// let gs = Ss::new_from_slice(1, 1, 1, &[-1.], &[1.], &[1.], &[0.]);
+    /// let (k, eps) = gs.loop_shaping(1.1).unwrap();
+    /// assert!(gs.closed_loop(&k).unwrap().is_stable());
+    /// assert!((eps - 0.924 / 1.1_f64).abs() < 1e-3);
+    /// ```
+    pub fn loop_shaping(&self, factor: T) -> Result<(Self, T), Error> {
+        assert!(
+            factor > T::one(),
+            "Relaxation factor must be greater than one"
+        );
+        let no_solution = || Error::new_internal(ErrorKind::NoRiccatiSolution);
+        let (m, p) = (self.dim.inputs, self.dim.outputs);
+        let s = DMatrix::identity(m, m) + self.d.tr_mul(&self.d);
+        let r = DMatrix::identity(p, p) + &self.d * self.d.transpose();
+        let s_inv = s.clone().try_inverse().ok_or_else(no_solution)?;
+        let r_inv = r.clone().try_inverse().ok_or_else(no_solution)?;
+        let ar = &self.a - &self.b * &s_inv * self.d.tr_mul(&self.c);
+
+        let x = linalg::care(&ar, &self.b, &(self.c.tr_mul(&r_inv) * &self.c), &s)
+            .ok_or_else(no_solution)?;
+        let z = linalg::care(
+            &ar.transpose(),
+            &self.c.transpose(),
+            &(&self.b * &s_inv * self.b.transpose()),
+            &r,
+        )
+        .ok_or_else(no_solution)?;
+
+        // rho(X*Z) = rho(Z^1/2*X*Z^1/2), both matrices are symmetric.
+        let eig = z.clone().symmetric_eigen();
+        let sqrt_values = eig
+            .eigenvalues
+            .map(|l| Float::sqrt(Float::max(l, T::zero())));
+        let z_half =
+            &eig.eigenvectors * DMatrix::from_diagonal(&sqrt_values) * eig.eigenvectors.transpose();
+        let rho = (&z_half * &x * &z_half)
+            .symmetric_eigen()
+            .eigenvalues
+            .iter()
+            .fold(T::zero(), |acc, &l| Float::max(acc, l));
+        let gamma = factor * Float::sqrt(T::one() + rho);
+
+        let n = self.dim.states;
+        let gamma2 = gamma * gamma;
+        let l = DMatrix::identity(n, n) * (T::one() - gamma2) + &x * &z;
+        let lt_inv = l.transpose().try_inverse().ok_or_else(no_solution)?;
+        let f = -(&s_inv * (self.d.tr_mul(&self.c) + self.b.tr_mul(&x)));
+        let bc = lt_inv * &z * self.c.transpose() * gamma2;
+        let ac = &self.a + &self.b * &f + &bc * (&self.c + &self.d * &f);
+        let cc = self.b.tr_mul(&x);
+        let dc = -self.d.transpose();
+        Ok((Self::new_from_matrices(ac, bc, cc, dc), T::one() / gamma))
+    }
+
+    /// Simulate the time response of the system to a sampled input signal.
+    ///
+    /// The response is calculated exactly at the sample times, given the
+    /// behavior of the input between two consecutive samples. Samples do not
+    /// need to be equally spaced.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - Input samples as `(time, input vector)`, in increasing time order
+    /// * `x0` - Initial state at the time of the first sample (column vector)
+    /// * `hold` - Behavior of the input between samples
+    ///
+    /// # Panics
+    ///
+    /// Panics if the sample times are not increasing or the input and
+    /// initial state lengths do not match the system dimensions.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use au::{enums::Interpolation, Seconds, Ss};
+    /// let sys = Ss::new_from_slice(1, 1, 1, &[-1.], &[1.], &[1.], &[0.]);
+    /// let input: Vec<_> = (0..=50).map(|i| (Seconds(0.1 * f64::from(i)), vec![1.])).collect();
+    /// let response = sys.lsim(&input, &[0.], Interpolation::ZeroOrderHold);
+    /// let last = response.last().unwrap();
+    /// assert!((last.output()[0] - (1. - (-5_f64).exp())).abs() < 1e-12);
+    /// ```
+    #[must_use]
+    pub fn lsim(
+        &self,
+        input: &[(Seconds<T>, Vec<T>)],
+        x0: &[T],
+        hold: Interpolation,
+    ) -> Vec<Step<T>> {
+        let n = self.dim.states;
+        let m = self.dim.inputs;
+        assert_eq!(n, x0.len(), "Wrong initial state length");
+        assert!(
+            input.iter().all(|(_, u)| u.len() == m),
+            "Wrong number of inputs"
+        );
+        assert!(
+            input.windows(2).all(|w| w[0].0 < w[1].0),
+            "Sample times must be increasing"
+        );
+
+        let mut state = DVector::from_column_slice(x0);
+        let mut response = Vec::with_capacity(input.len());
+        // Transition matrices are recalculated only when the sampling
+        // interval changes, sampling intervals are strictly positive.
+        let mut h_prev = T::zero();
+        let mut transition = (
+            DMatrix::zeros(n, n),
+            DMatrix::zeros(n, m),
+            DMatrix::zeros(n, m),
+        );
+        for (k, (time, u)) in input.iter().enumerate() {
+            let u = DVector::from_column_slice(u);
+            if k > 0 {
+                let (prev_time, prev_u) = &input[k - 1];
+                let prev_u = DVector::from_column_slice(prev_u);
+                let h = time.0 - prev_time.0;
+                if h != h_prev {
+                    transition = self.sampled_transition(h);
+                    h_prev = h;
+                }
+                let (phi, gamma1, gamma2) = &transition;
+                let mut next = phi * &state + gamma1 * &prev_u;
+                if hold == Interpolation::FirstOrderHold {
+                    next += gamma2 * (&u - &prev_u);
+                }
+                state = next;
+            }
+            let output = &self.c * &state + &self.d * &u;
+            response.push(Step::new(
+                *time,
+                state.as_slice().to_vec(),
+                output.as_slice().to_vec(),
+            ));
+        }
+        response
+    }
+
+    /// Free response of the system to the initial state, with zero input.
+    ///
+    /// The response is calculated exactly at multiples of the time interval
+    /// through the matrix exponential `x(k*h) = e^(A*h*k) * x0`.
+    ///
+    /// # Arguments
+    ///
+    /// * `x0` - Initial state (column vector)
+    /// * `h` - Time interval
+    /// * `n` - Number of intervals
+    ///
+    /// # Panics
+    ///
+    /// Panics if the initial state length does not match the number of
+    /// states of the system, or if the number of intervals cannot be
+    /// converted into the floating point type of the system.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use au::{Seconds, Ss};
+    /// let sys = Ss::new_from_slice(1, 1, 1, &[-2.], &[1.], &[3.], &[0.]);
+    /// let response = sys.initial_response(&[1.], Seconds(0.5), 4);
+    /// assert_eq!(5, response.len());
+    /// let last = response.last().unwrap();
+    /// assert!((last.output()[0] - 3. * (-4_f64).exp()).abs() < 1e-12);
+    /// ```
+    #[must_use]
+    pub fn initial_response(&self, x0: &[T], h: Seconds<T>, n: usize) -> Vec<Step<T>> {
+        assert_eq!(self.dim.states, x0.len(), "Wrong initial state length");
+        let phi = linalg::expm(&(&self.a * h.0));
+        let mut state = DVector::from_column_slice(x0);
+        let mut response = Vec::with_capacity(n + 1);
+        for k in 0..=n {
+            if k > 0 {
+                state = &phi * state;
+            }
+            let output = &self.c * &state;
+            let time = T::from(k).expect("Number of intervals is representable") * h.0;
+            response.push(Step::new(
+                Seconds(time),
+                state.as_slice().to_vec(),
+                output.as_slice().to_vec(),
+            ));
+        }
+        response
+    }
+
+    /// Impulse response of the system for each input channel.
+    ///
+    /// The Dirac impulse on the `j`-th input moves the state to the `j`-th
+    /// column of the B matrix, the response is then the free response from
+    /// this state. The first element of each response is the value at time
+    /// `0+`, the impulse transmitted through the D matrix at time zero is not
+    /// represented.
+    ///
+    /// # Arguments
+    ///
+    /// * `h` - Time interval
+    /// * `n` - Number of intervals
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use au::{Seconds, Ss};
+    /// let sys = Ss::<f64>::new_from_slice(1, 2, 1, &[-2.], &[1., 4.], &[3.], &[0., 0.]);
+    /// let response = sys.impulse(Seconds(0.5), 4);
+    /// assert_eq!(2, response.len());
+    /// assert!((response[1][0].output()[0] - 12.).abs() < 1e-12);
+    /// assert!((response[0][4].output()[0] - 3. * (-4_f64).exp()).abs() < 1e-12);
+    /// ```
+    #[must_use]
+    pub fn impulse(&self, h: Seconds<T>, n: usize) -> Vec<Vec<Step<T>>> {
+        self.b
+            .column_iter()
+            .map(|x0| self.initial_response(x0.as_slice(), h, n))
+            .collect()
+    }
+
+    /// Augment the system with a Padé approximation of a time delay on each
+    /// input channel.
+    ///
+    /// ```text
+    ///               sum_k c_k*(-s*tau)^k             (2n-k)! n!
+    /// e^(-s*tau) ≈ ----------------------,   c_k = ---------------
+    ///               sum_k c_k*(s*tau)^k             (2n)! k! (n-k)!
+    /// ```
+    ///
+    /// The states of the delay approximations are appended after the states
+    /// of the system, `order` states for each input.
+    ///
+    /// # Arguments
+    ///
+    /// * `tau` - Time delay
+    /// * `order` - Order of the Padé approximation
+    ///
+    /// # Panics
+    ///
+    /// Panics if the time delay is negative or NaN.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use au::{Seconds, Ss};
+    /// let sys = Ss::new_from_slice(1, 1, 1, &[-1.], &[1.], &[1.], &[0.]);
+    /// let delayed = sys.with_input_delay_pade(Seconds(0.5), 3);
+    /// assert_eq!(4, delayed.dim().states());
+    /// ```
+    #[must_use]
+    pub fn with_input_delay_pade(&self, tau: Seconds<T>, order: usize) -> Self {
+        assert!(tau.0 >= T::zero(), "Time delay must not be negative");
+        if order == 0 || tau.0 == T::zero() {
+            return self.clone();
+        }
+
+        let (n, m) = (self.dim.states, self.dim.inputs);
+        let nd = order * m;
+        // Delay block of a single channel.
+        let (ad, bd, cd, dd) = pade_delay(tau.0, order);
+
+        let mut a = DMatrix::zeros(n + nd, n + nd);
+        let mut b = DMatrix::zeros(n + nd, m);
+        let mut c = DMatrix::zeros(self.dim.outputs, n + nd);
+        a.slice_mut((0, 0), (n, n)).copy_from(&self.a);
+        c.slice_mut((0, 0), (self.dim.outputs, n))
+            .copy_from(&self.c);
+        for i in 0..m {
+            let offset = n + i * order;
+            a.slice_mut((offset, offset), (order, order)).copy_from(&ad);
+            b.slice_mut((offset, i), (order, 1)).copy_from(&bd);
+            // The delayed input u_d = Cd*xd + Dd*u enters the system.
+            let b_col = self.b.column(i);
+            a.slice_mut((0, offset), (n, order))
+                .copy_from(&(b_col * &cd));
+            b.slice_mut((0, i), (n, 1)).copy_from(&(b_col * dd));
+            let d_col = self.d.column(i);
+            c.slice_mut((0, offset), (self.dim.outputs, order))
+                .copy_from(&(d_col * &cd));
+        }
+        let d = &self.d * dd;
+        Self::new_from_matrices(a, b, c, d).with_names(self.names.io())
+    }
+
+    /// Frequency response of the system at the given angular frequencies
+    /// ```text
+    /// G(jω) = C * (jω*I - A)^-1 * B + D
+    /// ```
+    ///
+    /// The A matrix is reduced to upper Hessenberg form once, so that the
+    /// linear system at each frequency is solved with a number of operations
+    /// proportional to the square of the number of states.
+    ///
+    /// The result contains a (outputs x inputs) matrix for each frequency,
+    /// its elements are not finite if the frequency is a pole of the system.
+    ///
+    /// # Arguments
+    ///
+    /// * `freqs` - Angular frequencies
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use au::{RadiansPerSecond, Ss};
+    /// let sys = Ss::new_from_slice(1, 1, 1, &[-1.], &[1.], &[1.], &[0.]);
+    /// let g = sys.freq_response(&[RadiansPerSecond(0.), RadiansPerSecond(1.)]);
+    /// assert_eq!(1., g[0][(0, 0)].re);
+    /// assert!((g[1][(0, 0)].norm() - 0.5_f64.sqrt()).abs() < 1e-15);
+    /// ```
+    #[must_use]
+    pub fn freq_response(&self, freqs: &[RadiansPerSecond<T>]) -> Vec<DMatrix<Complex<T>>> {
+        // A = Q*H*Q'  =>  C*(jω*I - A)^-1*B = C*Q*(jω*I - H)^-1*Q'*B
+        let (q, h) = self.a.clone().hessenberg().unpack();
+        let to_complex = |m: DMatrix<T>| m.map(|x| Complex::new(x, T::zero()));
+        let qt_b = to_complex(q.tr_mul(&self.b));
+        let c_q = to_complex(&self.c * q);
+        let d = to_complex(self.d.clone());
+        let h = to_complex(h);
+        freqs
+            .iter()
+            .map(|w| {
+                let jw = Complex::new(T::zero(), w.0);
+                let mut m = -&h;
+                for i in 0..m.nrows() {
+                    m[(i, i)] += jw;
+                }
+                &c_q * hessenberg_solve(m, qt_b.clone()) + &d
+            })
+            .collect()
+    }
+
+    /// Singular values of the frequency response over a logarithmic grid of
+    /// angular frequencies, the multivariable generalization of the Bode
+    /// magnitude plot.
+    ///
+    /// The singular values are sorted in decreasing order for each frequency.
+    ///
+    /// # Arguments
+    ///
+    /// * `min_freq` - Minimum angular frequency
+    /// * `max_freq` - Maximum angular frequency
+    /// * `step` - Step between frequencies
+    ///
+    /// `step` shall be in logarithmic scale. Use 0.1 to have 10 point per decade
+    ///
+    /// # Panics
+    ///
+    /// Panics if the step is not strictly positive and the minimum frequency
+    /// is not lower than the maximum frequency
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use au::{RadiansPerSecond, Ss};
+    /// let sys = Ss::new_from_slice(1, 2, 2, &[-1.], &[1., 2.], &[1., 0.], &[0.; 4]);
+    /// let s = sys.sigma(RadiansPerSecond(0.1), RadiansPerSecond(10.), 1.);
+    /// assert_eq!(3, s.len());
+    /// assert_eq!(RadiansPerSecond(10.), s[2].0);
+    /// assert!(s[0].1[0] > s[2].1[0]);
+    /// ```
+    #[must_use]
+    pub fn sigma(
+        &self,
+        min_freq: RadiansPerSecond<T>,
+        max_freq: RadiansPerSecond<T>,
+        step: T,
+    ) -> Vec<(RadiansPerSecond<T>, Vec<T>)> {
+        Sigma::new(self, min_freq, max_freq, step)
+            .into_iter()
+            .map(|d| (d.angular_frequency(), d.singular_values().to_vec()))
+            .collect()
+    }
+
+    /// H2 norm of the system, calculated from the controllability gramian
+    /// ```text
+    /// ||G||_2 = sqrt(trace(C*Wc*C'))
+    /// A*Wc + Wc*A' + B*B' = 0
+    /// ```
+    ///
+    /// The norm is infinite if the system is not strictly proper.
+    ///
+    /// # Errors
+    ///
+    /// It returns an error if the system is not stable.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use au::Ss;
+    /// let sys = Ss::new_from_slice(1, 1, 1, &[-1.], &[1.], &[1.], &[0.]);
+    /// let h2 = sys.norm_h2().unwrap();
+    /// assert!((h2 - 0.5_f64.sqrt()).abs() < 1e-12);
+    /// ```
+    pub fn norm_h2(&self) -> Result<T, Error> {
+        if !self.is_stable() {
+            return Err(Error::new_internal(ErrorKind::UnstableSystem));
+        }
+        if self.d.iter().any(|x| !x.is_zero()) {
+            return Ok(T::infinity());
+        }
+        let wc = linalg::lyap(&self.a, &(&self.b * self.b.transpose()))
+            .ok_or_else(|| Error::new_internal(ErrorKind::UnstableSystem))?;
+        let trace = (&self.c * wc * self.c.transpose()).trace();
+        Ok(Float::sqrt(Float::max(trace, T::zero())))
     }
-}
 
-/// Implementation of the methods for the state-space
-impl<T: ComplexField + Float + RealField> Ss<T> {
-    /// System stability. Checks if all A matrix eigenvalues (poles) are negative.
+    /// H-infinity norm of the system, the peak of the maximum singular value
+    /// of the frequency response.
+    ///
+    /// The norm is calculated through bisection: `gamma` is greater than the
+    /// norm if and only if the Hamiltonian matrix
+    /// ```text
+    ///     ┌                                                   ┐
+    /// H = │ A + B*R^-1*D'*C        B*R^-1*B'                  │
+    ///     │ -C'*(I + D*R^-1*D')*C  -(A + B*R^-1*D'*C)'        │
+    ///     └                                                   ┘
+    /// R = gamma^2*I - D'*D
+    /// ```
+    /// has no eigenvalues on the imaginary axis.
+    ///
+    /// # Arguments
+    ///
+    /// * `tol` - Relative tolerance of the result
+    ///
+    /// # Errors
+    ///
+    /// It returns an error if the system is not stable.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the tolerance is not strictly positive.
     ///
     /// # Example
     ///
     /// ```
     /// use au::Ss;
-    /// let sys = Ss::new_from_slice(2, 1, 1, &[-2., 0., 3., -7.], &[1., 3.], &[-1., 0.5], &[0.1]);
-    /// assert!(sys.is_stable());
+    /// let sys = Ss::new_from_slice(1, 1, 1, &[-1.], &[1.], &[1.], &[0.]);
+    /// let hinf = sys.norm_hinf(1e-9).unwrap();
+    /// assert!((hinf - 1_f64).abs() < 1e-8);
+    /// ```
+    pub fn norm_hinf(&self, tol: T) -> Result<T, Error> {
+        assert!(tol > T::zero(), "Tolerance must be positive");
+        if !self.is_stable() {
+            return Err(Error::new_internal(ErrorKind::UnstableSystem));
+        }
+        let max_sv = |g: &DMatrix<Complex<T>>| {
+            g.singular_values()
+                .iter()
+                .fold(T::zero(), |m, &s| Float::max(m, s))
+        };
+        let d_norm = max_sv(&self.d.map(|x| Complex::new(x, T::zero())));
+        if self.dim.states == 0 {
+            return Ok(d_norm);
+        }
+
+        // Lower bound from the direct transmission and the response at zero
+        // frequency and at the natural frequencies of the poles.
+        let freqs: Vec<_> = std::iter::once(T::zero())
+            .chain(self.poles().iter().map(|p| p.norm()))
+            .map(RadiansPerSecond)
+            .collect();
+        let mut lower = self
+            .freq_response(&freqs)
+            .iter()
+            .map(max_sv)
+            .fold(d_norm, Float::max);
+        if lower.is_zero() {
+            return Ok(T::zero());
+        }
+
+        let two = T::one() + T::one();
+        let mut upper = lower * two;
+        while self.hamiltonian_has_imaginary_eigenvalues(upper) {
+            lower = upper;
+            upper *= two;
+        }
+        while upper - lower > two * tol * lower {
+            let gamma = (lower + upper) / two;
+            if self.hamiltonian_has_imaginary_eigenvalues(gamma) {
+                lower = gamma;
+            } else {
+                upper = gamma;
+            }
+        }
+        Ok((lower + upper) / two)
+    }
+
+    /// Check if the Hamiltonian matrix associated to the level `gamma` of
+    /// the H-infinity norm has eigenvalues on the imaginary axis.
+    ///
+    /// # Arguments
+    ///
+    /// * `gamma` - Level of the norm, greater than the norm of D
+    fn hamiltonian_has_imaginary_eigenvalues(&self, gamma: T) -> bool {
+        let n = self.dim.states;
+        let m = self.dim.inputs;
+        let p = self.dim.outputs;
+        let r = DMatrix::identity(m, m) * (gamma * gamma) - self.d.tr_mul(&self.d);
+        let r_inv = match r.try_inverse() {
+            Some(r_inv) => r_inv,
+            None => return true,
+        };
+        let a = &self.a + &self.b * &r_inv * self.d.transpose() * &self.c;
+        let mut h = DMatrix::zeros(2 * n, 2 * n);
+        h.slice_mut((0, 0), (n, n)).copy_from(&a);
+        h.slice_mut((0, n), (n, n))
+            .copy_from(&(&self.b * &r_inv * self.b.transpose()));
+        let s = DMatrix::identity(p, p) + &self.d * &r_inv * self.d.transpose();
+        h.slice_mut((n, 0), (n, n))
+            .copy_from(&-(self.c.transpose() * s * &self.c));
+        h.slice_mut((n, n), (n, n)).copy_from(&-a.transpose());
+
+        let tol = Float::sqrt(T::epsilon());
+        eigenvalues(&h)
+            .iter()
+            .any(|e| Float::abs(e.re) < tol * Float::max(T::one(), e.norm()))
+    }
+
+    /// Transition matrices of the system sampled with interval `h`.
+    /// ```text
+    ///     ┌               ┐   ┌                 ┐
+    ///     │ A*h  B*h  0   │   │ Phi Gamma1 Gamma2 │
+    /// exp │ 0    0    I   │ = │ 0   I      I      │
+    ///     │ 0    0    0   │   │ 0   0      I      │
+    ///     └               ┘   └                 ┘
     /// ```
+    /// The state evolves as `x(k+1) = Phi*x(k) + Gamma1*u(k) + Gamma2*(u(k+1) - u(k))`
+    /// when the input changes linearly between samples.
+    fn sampled_transition(&self, h: T) -> (DMatrix<T>, DMatrix<T>, DMatrix<T>) {
+        let n = self.dim.states;
+        let m = self.dim.inputs;
+        let mut aug = DMatrix::zeros(n + 2 * m, n + 2 * m);
+        aug.slice_mut((0, 0), (n, n)).copy_from(&(&self.a * h));
+        aug.slice_mut((0, n), (n, m)).copy_from(&(&self.b * h));
+        aug.slice_mut((n, n + m), (m, m)).fill_with_identity();
+        let exp = linalg::expm(&aug);
+        (
+            exp.slice((0, 0), (n, n)).into_owned(),
+            exp.slice((0, n), (n, m)).into_owned(),
+            exp.slice((0, n + m), (n, m)).into_owned(),
+        )
+    }
+}
+
+/// Unit vector of the null space of a singular complex matrix, obtained from
+/// the right singular vector related to the smallest singular value.
+/// The vector is rotated so that its largest component is real and positive.
+fn null_vector<T: Float + RealField>(m: DMatrix<Complex<T>>) -> DVector<Complex<T>> {
+    let svd = m.svd(false, true);
+    let v_t = svd.v_t.expect("Right singular vectors have been requested");
+    let (idx, _) =
+        svd.singular_values
+            .iter()
+            .enumerate()
+            .fold(
+                (0, T::infinity()),
+                |(i, min), (j, &s)| {
+                    if s < min {
+                        (j, s)
+                    } else {
+                        (i, min)
+                    }
+                },
+            );
+    let v = v_t.row(idx).transpose().map(|c| c.conj());
+    let largest = v.iter().fold(
+        Complex::zero(),
+        |m: Complex<T>, &c| if c.norm() > m.norm() { c } else { m },
+    );
+    if largest.is_zero() {
+        v
+    } else {
+        let rotation = largest.conj() / largest.norm();
+        v.map(|c| c * rotation)
+    }
+}
+
+/// Mode of a continuous time linear system
+#[derive(Clone, Debug)]
+pub struct Mode<T: Scalar> {
+    /// Eigenvalue of the A matrix
+    eigenvalue: Complex<T>,
+    /// Natural frequency
+    natural_frequency: T,
+    /// Damping ratio
+    damping: T,
+    /// Time constant
+    time_constant: T,
+    /// Mode shape (eigenvector)
+    shape: DVector<Complex<T>>,
+}
+
+impl<T: Float + Scalar> Mode<T> {
+    /// Get the eigenvalue of the A matrix related to the mode
     #[must_use]
-    pub fn is_stable(&self) -> bool {
-        self.poles().iter().all(|p| p.re.is_negative())
+    pub fn eigenvalue(&self) -> Complex<T> {
+        self.eigenvalue
+    }
+
+    /// Get the natural frequency of the mode, the modulus of the eigenvalue
+    #[must_use]
+    pub fn natural_frequency(&self) -> NaturalFrequency<T> {
+        NaturalFrequency(self.natural_frequency)
+    }
+
+    /// Get the damping ratio of the mode, see [`damp`](../../complex/fn.damp.html)
+    #[must_use]
+    pub fn damping(&self) -> DampingRatio<T> {
+        DampingRatio(self.damping)
+    }
+
+    /// Get the time constant of the mode, the opposite of the inverse of the
+    /// real part of the eigenvalue. It is negative for unstable modes and
+    /// infinite for modes on the imaginary axis.
+    #[must_use]
+    pub fn time_constant(&self) -> T {
+        self.time_constant
+    }
+
+    /// Get the mode shape, the unit norm eigenvector related to the eigenvalue.
+    /// Its largest component is real and positive.
+    #[must_use]
+    pub fn shape(&self) -> &DVector<Complex<T>> {
+        &self.shape
+    }
+}
+
+impl<T: ComplexField + Float + RealField> SigmaPlot<T> for Ss<T> {
+    /// Evaluate the frequency response of the system at the given angular
+    /// frequencies, reducing the A matrix to Hessenberg form only once.
+    ///
+    /// # Arguments
+    ///
+    /// * `freqs` - angular frequencies at which the response is evaluated
+    fn eval_matrices(&self, freqs: &[RadiansPerSecond<T>]) -> Vec<DMatrix<Complex<T>>> {
+        self.freq_response(freqs)
     }
 }
 
 /// Implementation of the methods for the state-space
-impl Ss<f64> {
+impl<T: Float + Scalar> Ss<T> {
     /// Time evolution for the given input, using Runge-Kutta second order method
     ///
     /// # Arguments
     ///
-    /// * `u` - input function returning a vector (column mayor)
+    /// * `u` - input function of time returning a vector (column vector)
     /// * `x0` - initial state (column mayor)
     /// * `h` - integration time interval
     /// * `n` - integration steps
-    pub fn rk2<F>(&self, u: F, x0: &[f64], h: Seconds<f64>, n: usize) -> Rk<F, f64>
+    pub fn rk2<F>(&self, u: F, x0: &[T], h: Seconds<T>, n: usize) -> Rk<F, T>
     where
-        F: Fn(Seconds<f64>) -> Vec<f64>,
+        F: Fn(Seconds<T>) -> Vec<T>,
+        T: AddAssign + MulAssign + RkConst,
     {
         Rk::new(self, u, x0, h, n, Order::Rk2)
     }
@@ -97,13 +1274,14 @@ impl Ss<f64> {
     ///
     /// # Arguments
     ///
-    /// * `u` - input function returning a vector (column mayor)
+    /// * `u` - input function of time returning a vector (column vector)
     /// * `x0` - initial state (column mayor)
     /// * `h` - integration time interval
     /// * `n` - integration steps
-    pub fn rk4<F>(&self, u: F, x0: &[f64], h: Seconds<f64>, n: usize) -> Rk<F, f64>
+    pub fn rk4<F>(&self, u: F, x0: &[T], h: Seconds<T>, n: usize) -> Rk<F, T>
     where
-        F: Fn(Seconds<f64>) -> Vec<f64>,
+        F: Fn(Seconds<T>) -> Vec<T>,
+        T: AddAssign + MulAssign + RkConst,
     {
         Rk::new(self, u, x0, h, n, Order::Rk4)
     }
@@ -112,21 +1290,15 @@ impl Ss<f64> {
     ///
     /// # Arguments
     ///
-    /// * `u` - input function returning a vector (column vector)
+    /// * `u` - input function of time returning a vector (column vector)
     /// * `x0` - initial state (column vector)
     /// * `h` - integration time interval
     /// * `limit` - time evaluation limit
     /// * `tol` - error tolerance
-    pub fn rkf45<F>(
-        &self,
-        u: F,
-        x0: &[f64],
-        h: Seconds<f64>,
-        limit: Seconds<f64>,
-        tol: f64,
-    ) -> Rkf45<F, f64>
+    pub fn rkf45<F>(&self, u: F, x0: &[T], h: Seconds<T>, limit: Seconds<T>, tol: T) -> Rkf45<F, T>
     where
-        F: Fn(Seconds<f64>) -> Vec<f64>,
+        F: Fn(Seconds<T>) -> Vec<T>,
+        T: AddAssign + MulAssign + Rkf45Const + Signed + SimdPartialOrd + SubAssign,
     {
         Rkf45::new(self, u, x0, h, limit, tol)
     }
@@ -135,23 +1307,148 @@ impl Ss<f64> {
     ///
     /// # Arguments
     ///
-    /// * `u` - input function returning a vector (column vector)
+    /// * `u` - input function of time returning a vector (column vector)
     /// * `x0` - initial state (column vector)
     /// * `h` - integration time interval
     /// * `n` - integration steps
     /// * `tol` - error tolerance
-    pub fn radau<F>(&self, u: F, x0: &[f64], h: Seconds<f64>, n: usize, tol: f64) -> Radau<F, f64>
+    pub fn radau<F>(&self, u: F, x0: &[T], h: Seconds<T>, n: usize, tol: T) -> Radau<F, T>
     where
-        F: Fn(Seconds<f64>) -> Vec<f64>,
+        F: Fn(Seconds<T>) -> Vec<T>,
+        T: AbsDiffEq<Epsilon = T> + ComplexField + RadauConst + RelativeEq,
     {
         Radau::new(self, u, x0, h, n, tol)
     }
 }
 
+/// Controllable canonical realization (A, B, C, D) of the Padé approximation
+/// of a time delay.
+///
+/// # Arguments
+///
+/// * `tau` - Time delay
+/// * `order` - Order of the approximation, strictly positive
+fn pade_delay<T: ComplexField + Float + RealField>(
+    tau: T,
+    order: usize,
+) -> (DMatrix<T>, DMatrix<T>, DMatrix<T>, T) {
+    // c_(k+1) = c_k * (n - k) / ((2n - k) * (k + 1))
+    let n = T::from(order).unwrap();
+    let mut den = Vec::with_capacity(order + 1);
+    let mut c_k = T::one();
+    let mut tau_k = T::one();
+    for k in 0..=order {
+        den.push(c_k * tau_k);
+        let k = T::from(k).unwrap();
+        c_k *= (n - k) / ((n + n - k) * (k + T::one()));
+        tau_k *= tau;
+    }
+    let num: Vec<_> = den
+        .iter()
+        .enumerate()
+        .map(|(k, &d)| if k % 2 == 0 { d } else { -d })
+        .collect();
+    let lead = den[order];
+    let dd = num[order] / lead;
+
+    let mut a = DMatrix::zeros(order, order);
+    for i in 0..order - 1 {
+        a[(i, i + 1)] = T::one();
+    }
+    let mut c = DMatrix::zeros(1, order);
+    for k in 0..order {
+        let a_k = den[k] / lead;
+        a[(order - 1, k)] = -a_k;
+        c[(0, k)] = num[k] / lead - a_k * dd;
+    }
+    let mut b = DMatrix::zeros(order, 1);
+    b[(order - 1, 0)] = T::one();
+    (a, b, c, dd)
+}
+
+/// Solve the linear system `M*X = R` where `M` is an upper Hessenberg matrix,
+/// through Gaussian elimination with partial pivoting between adjacent rows.
+fn hessenberg_solve<T: Float + RealField>(
+    mut m: DMatrix<Complex<T>>,
+    mut r: DMatrix<Complex<T>>,
+) -> DMatrix<Complex<T>> {
+    let n = m.nrows();
+    // Reduce to upper triangular form.
+    for k in 0..n.saturating_sub(1) {
+        if m[(k + 1, k)].norm() > m[(k, k)].norm() {
+            m.swap_rows(k, k + 1);
+            r.swap_rows(k, k + 1);
+        }
+        let l = m[(k + 1, k)] / m[(k, k)];
+        if l.is_zero() {
+            continue;
+        }
+        for j in k..n {
+            let mkj = m[(k, j)];
+            m[(k + 1, j)] -= l * mkj;
+        }
+        for j in 0..r.ncols() {
+            let rkj = r[(k, j)];
+            r[(k + 1, j)] -= l * rkj;
+        }
+    }
+    // Back substitution.
+    for j in 0..r.ncols() {
+        for i in (0..n).rev() {
+            let mut acc = r[(i, j)];
+            for k in (i + 1)..n {
+                acc -= m[(i, k)] * r[(k, j)];
+            }
+            r[(i, j)] = acc / m[(i, i)];
+        }
+    }
+    r
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn modes() {
+        let sys = Ss::new_from_slice(
+            3,
+            1,
+            1,
+            &[-1., 2., 0., -2., -1., 0., 0., 0., 3.],
+            &[1., 0., 0.],
+            &[1., 0., 0.],
+            &[0.],
+        );
+        let modes = sys.modes();
+        assert_eq!(3, modes.len());
+        let a = sys.a().map(|x| Complex::new(x, 0.));
+        for m in &modes {
+            let v = m.shape();
+            assert_relative_eq!(1., v.norm(), max_relative = 1e-10);
+            let r = &a * v - v * m.eigenvalue();
+            assert_abs_diff_eq!(0., r.norm(), epsilon = 1e-10);
+        }
+        let unstable = modes.iter().find(|m| m.eigenvalue().re > 0.).unwrap();
+        assert_relative_eq!(3., unstable.natural_frequency().0);
+        assert_relative_eq!(-1., unstable.damping().0);
+        assert_relative_eq!(-1. / 3., unstable.time_constant());
+        assert_relative_eq!(1., unstable.shape()[2].re, max_relative = 1e-10);
+        let oscillating = modes.iter().find(|m| m.eigenvalue().im > 0.).unwrap();
+        assert_relative_eq!(5_f64.sqrt(), oscillating.natural_frequency().0);
+        assert_relative_eq!(1. / 5_f64.sqrt(), oscillating.damping().0);
+        assert_relative_eq!(1., oscillating.time_constant());
+    }
+
+    #[test]
+    fn modes_integrator() {
+        let sys = Ss::new_from_slice(1, 1, 1, &[0.], &[1.], &[1.], &[0.]);
+        let modes = sys.modes();
+        assert_eq!(NaturalFrequency(0.), modes[0].natural_frequency());
+        assert!(modes[0].time_constant().is_infinite());
+        assert_eq!(Complex::new(1., 0.), modes[0].shape()[0]);
+    }
+
     #[allow(clippy::many_single_char_names)]
     #[test]
     fn equilibrium() {
@@ -164,9 +1461,37 @@ mod tests {
         let u = 0.0;
         let eq = sys.equilibrium(&[u]).unwrap();
         assert_eq!((0., 0.), (eq.x()[0], eq.y()[0]));
+        assert!(eq.is_unique());
         assert!(!format!("{}", eq).is_empty());
     }
 
+    #[test]
+    fn equilibrium_under_determined() {
+        // Integrator on the first state, not driven by the input.
+        let sys = Ss::new_from_slice(2, 1, 1, &[0., 0., 0., -2.], &[0., 1.], &[1., 1.], &[0.]);
+        let err = sys.equilibrium(&[4.]).unwrap_err();
+        assert_eq!(ErrorKind::UnderdeterminedEquilibrium, err.kind());
+        let eq = sys.least_squares_equilibrium(&[4.]);
+        assert!(!eq.is_unique());
+        assert_relative_eq!(0., eq.x()[0]);
+        assert_relative_eq!(2., eq.x()[1]);
+        assert_relative_eq!(2., eq.y()[0]);
+        assert_eq!((2, 1), eq.null_space().shape());
+        assert_relative_eq!(1., Float::abs(eq.null_space()[(0, 0)]));
+        assert_relative_eq!(0., eq.null_space()[(1, 0)]);
+    }
+
+    #[test]
+    fn equilibrium_inconsistent() {
+        let sys = Ss::new_from_slice(2, 1, 1, &[0., 0., 0., -2.], &[1., 1.], &[1., 1.], &[0.]);
+        let err = sys.equilibrium(&[4.]).unwrap_err();
+        assert_eq!(ErrorKind::NoEquilibrium, err.kind());
+        let eq = sys.least_squares_equilibrium(&[4.]);
+        assert_relative_eq!(0., eq.x()[0]);
+        assert_relative_eq!(2., eq.x()[1]);
+        assert_eq!(1, eq.null_space().ncols());
+    }
+
     #[test]
     fn stability() {
         let eig1 = -2.;
@@ -183,6 +1508,229 @@ mod tests {
         assert!(sys.is_stable())
     }
 
+    #[test]
+    fn stability_margin() {
+        let sys = Ss::new_from_slice(
+            2,
+            1,
+            1,
+            &[-1., 2., -2., -1.],
+            &[1., 3.],
+            &[-1., 0.5],
+            &[0.1],
+        );
+        assert_relative_eq!(-1., sys.stability_margin(), max_relative = 1e-12);
+
+        let sys = Ss::new_from_slice(2, 1, 1, &[0.5, 0., 0., -3.], &[1., 3.], &[-1., 0.5], &[0.1]);
+        assert_relative_eq!(0.5, sys.stability_margin());
+        assert!(!sys.is_stable());
+
+        let sys = Ss::new_from_slice(0, 1, 1, &[], &[], &[], &[1.]);
+        assert_eq!(f64::NEG_INFINITY, sys.stability_margin());
+    }
+
+    #[test]
+    fn balanced_realization() {
+        let sys = Ss::new_from_slice(
+            3,
+            1,
+            1,
+            &[-1., 0.5, 0., 0., -2., 1., 0., 0., -10.],
+            &[1., 0.5, 2.],
+            &[1., 1., 0.1],
+            &[0.],
+        );
+        let (bal, hsv) = sys.balanced().unwrap();
+        assert!(hsv.windows(2).all(|w| w[0] >= w[1]));
+        let wc = linalg::lyap(&bal.a, &(&bal.b * bal.b.transpose())).unwrap();
+        let wo = linalg::lyap(&bal.a.transpose(), &(bal.c.transpose() * &bal.c)).unwrap();
+        let expected = DMatrix::from_diagonal(&DVector::from_vec(hsv));
+        assert_relative_eq!(expected, wc, epsilon = 1e-10);
+        assert_relative_eq!(expected, wo, epsilon = 1e-10);
+
+        // Balancing does not change the static gain.
+        let eq = sys.equilibrium(&[1.]).unwrap();
+        let eq_bal = bal.equilibrium(&[1.]).unwrap();
+        assert_relative_eq!(eq.y()[0], eq_bal.y()[0], max_relative = 1e-10);
+    }
+
+    #[test]
+    fn balanced_truncation() {
+        let sys = Ss::new_from_slice(
+            3,
+            1,
+            1,
+            &[-1., 0.5, 0., 0., -2., 1., 0., 0., -10.],
+            &[1., 0.5, 2.],
+            &[1., 1., 0.1],
+            &[0.],
+        );
+        let (_, hsv) = sys.balanced().unwrap();
+        let (red, bound) = sys.balanced_truncation(2).unwrap();
+        assert_eq!(2, red.dim().states());
+        assert_relative_eq!(2. * hsv[2], bound);
+        assert!(red.is_stable());
+
+        let eq = sys.equilibrium(&[1.]).unwrap();
+        let eq_red = red.equilibrium(&[1.]).unwrap();
+        assert!(f64::abs(eq.y()[0] - eq_red.y()[0]) <= bound * (1. + 1e-9));
+
+        let (full, bound) = sys.balanced_truncation(3).unwrap();
+        assert_eq!(3, full.dim().states());
+        assert_relative_eq!(0., bound);
+    }
+
+    #[test]
+    fn balanced_errors() {
+        let unstable = Ss::new_from_slice(2, 1, 1, &[1., 0., 0., -2.], &[1., 1.], &[1., 1.], &[0.]);
+        assert_eq!(
+            ErrorKind::UnstableSystem,
+            unstable.balanced().unwrap_err().kind()
+        );
+        let no_minimal =
+            Ss::new_from_slice(2, 1, 1, &[-1., 0., 0., -2.], &[1., 0.], &[1., 1.], &[0.]);
+        assert_eq!(
+            ErrorKind::NoMinimalSystem,
+            no_minimal.balanced().unwrap_err().kind()
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn balanced_truncation_fail() {
+        let sys = Ss::new_from_slice(2, 1, 1, &[-2., 0., 3., -7.], &[1., 3.], &[-1., 0.5], &[0.1]);
+        let _ = sys.balanced_truncation(3);
+    }
+
+    #[test]
+    fn linear_quadratic_regulator() {
+        let sys = Ss::new_from_slice(
+            3,
+            2,
+            1,
+            &[-1., 2., 0., 0., 1., 1., 1., 0., -3.],
+            &[1., 0., 0., 1., 1., 1.],
+            &[1., 0., 0.],
+            &[0., 0.],
+        );
+        let q = DMatrix::from_row_slice(3, 3, &[2., 0., 0., 0., 1., 0., 0., 0., 1.]);
+        let r = DMatrix::from_row_slice(2, 2, &[1., 0., 0., 3.]);
+        let (k, p) = sys.lqr(&q, &r).unwrap();
+        assert_eq!((2, 3), k.shape());
+        let cl = Ss::new_from_matrices(
+            &sys.a - &sys.b * &k,
+            sys.b.clone(),
+            sys.c.clone(),
+            sys.d.clone(),
+        );
+        assert!(cl.is_stable());
+        // Riccati equation: A'*P + P*(A - B*K) + Q = 0
+        let res = sys.a.tr_mul(&p) + &p * &cl.a + &q;
+        assert_relative_eq!(0., res.norm(), epsilon = 1e-9);
+    }
+
+    #[test]
+    fn linear_quadratic_regulator_no_solution() {
+        let sys = Ss::new_from_slice(2, 1, 1, &[1., 0., 0., -1.], &[0., 1.], &[1., 1.], &[0.]);
+        let i2 = DMatrix::identity(2, 2);
+        let i1 = DMatrix::identity(1, 1);
+        assert_eq!(
+            ErrorKind::NoRiccatiSolution,
+            sys.lqr(&i2, &i1).unwrap_err().kind()
+        );
+    }
+
+    #[test]
+    fn kalman_filter() {
+        let sys = Ss::new_from_slice(2, 1, 1, &[0., 1., -2., -1.], &[0., 1.], &[1., 0.], &[0.]);
+        let qn = DMatrix::from_row_slice(2, 2, &[0.1, 0., 0., 1.]);
+        let rn = DMatrix::from_element(1, 1, 0.01);
+        let (l, p) = sys.lqe(&qn, &rn).unwrap();
+        assert_eq!((2, 1), l.shape());
+        let a_obs = &sys.a - &l * &sys.c;
+        let obs = Ss::new_from_matrices(a_obs.clone(), sys.b.clone(), sys.c.clone(), sys.d.clone());
+        assert!(obs.is_stable());
+        // Filter Riccati equation: (A - L*C)*P + P*A' + Qn = 0
+        let res = &a_obs * &p + &p * sys.a.transpose() + &qn;
+        assert_relative_eq!(0., res.norm(), epsilon = 1e-9);
+    }
+
+    #[test]
+    fn loop_shaping_margin() {
+        // X = Z = sqrt(2) - 1 for the plant 1/(s+1).
+        let sys = Ss::new_from_slice(1, 1, 1, &[-1.], &[1.], &[1.], &[0.]);
+        let (k, eps) = sys.loop_shaping(1.05).unwrap();
+        let x = 2_f64.sqrt() - 1.;
+        let eps_max = 1. / (1. + x * x).sqrt();
+        assert_relative_eq!(eps_max / 1.05, eps, max_relative = 1e-9);
+        assert!(sys.closed_loop(&k).unwrap().is_stable());
+    }
+
+    #[test]
+    fn loop_shaping_stabilization() {
+        // Double integrator
+        let sys = Ss::new_from_slice(2, 1, 1, &[0., 1., 0., 0.], &[0., 1.], &[1., 0.], &[0.]);
+        let (k, eps) = sys.loop_shaping(1.1).unwrap();
+        assert_eq!(2, k.dim().states());
+        assert!(eps > 0. && eps < 1.);
+        assert!(sys.closed_loop(&k).unwrap().is_stable());
+        // Unstable plant with direct transmission
+        let sys = Ss::new_from_slice(1, 1, 1, &[2.], &[1.], &[3.], &[0.5]);
+        let (k, _) = sys.loop_shaping(1.1).unwrap();
+        assert!(sys.closed_loop(&k).unwrap().is_stable());
+    }
+
+    #[test]
+    fn loop_shaping_not_stabilizable() {
+        let sys = Ss::new_from_slice(2, 1, 1, &[1., 0., 0., -1.], &[0., 1.], &[1., 1.], &[0.]);
+        let res = sys.loop_shaping(1.1);
+        assert_eq!(ErrorKind::NoRiccatiSolution, res.unwrap_err().kind());
+    }
+
+    #[test]
+    fn linear_quadratic_gaussian() {
+        let sys = Ss::new_from_slice(2, 1, 1, &[0., 1., 2., -1.], &[0., 1.], &[1., 0.], &[0.]);
+        let i2 = DMatrix::identity(2, 2);
+        let i1 = DMatrix::identity(1, 1);
+        let comp = sys.lqg(&i2, &i1, &i2, &i1).unwrap();
+        assert_eq!(2, comp.dim().states());
+        assert_eq!(1, comp.dim().inputs());
+        assert_eq!(1, comp.dim().outputs());
+
+        // Separation principle: the closed loop poles are the union of the
+        // regulator and estimator poles.
+        let (k, _) = sys.lqr(&i2, &i1).unwrap();
+        let (l, _) = sys.lqe(&i2, &i1).unwrap();
+        let reg = Ss::new_from_matrices(
+            &sys.a - &sys.b * &k,
+            sys.b.clone(),
+            sys.c.clone(),
+            sys.d.clone(),
+        );
+        let est = Ss::new_from_matrices(
+            &sys.a - &l * &sys.c,
+            sys.b.clone(),
+            sys.c.clone(),
+            sys.d.clone(),
+        );
+        let mut expected: Vec<_> = reg.poles().into_iter().chain(est.poles()).collect();
+        let cl = sys.closed_loop(&comp).unwrap();
+        assert_eq!(4, cl.dim().states());
+        assert!(cl.is_stable());
+        let mut actual = cl.poles();
+        let key = |a: &num_complex::Complex<f64>, b: &num_complex::Complex<f64>| {
+            a.re.partial_cmp(&b.re)
+                .unwrap()
+                .then(a.im.partial_cmp(&b.im).unwrap())
+        };
+        expected.sort_by(key);
+        actual.sort_by(key);
+        for (e, a) in expected.iter().zip(&actual) {
+            assert_relative_eq!(e.re, a.re, max_relative = 1e-8);
+            assert_relative_eq!(e.im, a.im, epsilon = 1e-8);
+        }
+    }
+
     #[test]
     fn new_rk2() {
         let a = [-1., 1., -1., 0.25];
@@ -226,4 +1774,432 @@ mod tests {
         let iter = sys.radau(|_| vec![1.], &[0., 0.], Seconds(0.1), 30, 1e-5);
         assert_eq!(31, iter.count());
     }
+
+    #[test]
+    fn solvers_ramp_input() {
+        // Integrator driven by a ramp: x(t) = t^2 / 2
+        let sys = Ss::new_from_slice(1, 1, 1, &[0.], &[1.], &[1.], &[0.]);
+        let ramp = |t: Seconds<f64>| vec![t.0];
+        let expected = |t: Seconds<f64>| t.0 * t.0 / 2.;
+        for s in sys.rk2(ramp, &[0.], Seconds(0.1), 20) {
+            assert_relative_eq!(expected(s.time()), s.state()[0], epsilon = 1e-12);
+        }
+        for s in sys.rk4(ramp, &[0.], Seconds(0.1), 20) {
+            assert_relative_eq!(expected(s.time()), s.state()[0], epsilon = 1e-12);
+        }
+        for s in sys.rkf45(ramp, &[0.], Seconds(0.1), Seconds(2.), 1e-6) {
+            assert_relative_eq!(expected(s.time()), s.state()[0], epsilon = 1e-9);
+        }
+        for s in sys.radau(ramp, &[0.], Seconds(0.1), 20, 1e-8) {
+            assert_relative_eq!(expected(s.time()), s.state()[0], epsilon = 1e-9);
+        }
+    }
+
+    #[test]
+    fn solvers_sinusoidal_input() {
+        // x' = -x + sin(t): x(t) = (sin(t) - cos(t) + e^-t) / 2
+        let sys = Ss::new_from_slice(1, 1, 1, &[-1.], &[1.], &[1.], &[0.]);
+        let sine = |t: Seconds<f64>| vec![t.0.sin()];
+        let expected = |t: Seconds<f64>| (t.0.sin() - t.0.cos() + (-t.0).exp()) / 2.;
+        let last = sys.rk4(sine, &[0.], Seconds(0.05), 200).last().unwrap();
+        assert_relative_eq!(expected(last.time()), last.state()[0], epsilon = 1e-7);
+        let last = sys
+            .rkf45(sine, &[0.], Seconds(0.05), Seconds(10.), 1e-8)
+            .last()
+            .unwrap();
+        assert_relative_eq!(expected(last.time()), last.state()[0], epsilon = 1e-6);
+        let last = sys.rk2(sine, &[0.], Seconds(0.01), 1000).last().unwrap();
+        assert_relative_eq!(expected(last.time()), last.state()[0], epsilon = 1e-4);
+    }
+
+    #[test]
+    fn rkf45_step_bounds() {
+        let sys = Ss::new_from_slice(1, 1, 1, &[-1.], &[1.], &[1.], &[0.]);
+        let evo: Vec<_> = sys
+            .rkf45(|_| vec![1.], &[0.], Seconds(0.1), Seconds(2.), 1e-4)
+            .with_max_step(Seconds(0.05))
+            .collect();
+        assert!(evo
+            .windows(2)
+            .all(|s| s[1].time().0 - s[0].time().0 <= 0.05 + 1e-15));
+        assert_relative_eq!(2., evo.last().unwrap().time().0);
+
+        // The tolerance cannot be met, steps are accepted at the minimum
+        // interval.
+        let evo: Vec<_> = sys
+            .rkf45(|_| vec![1.], &[0.], Seconds(0.1), Seconds(0.1), 0.)
+            .with_min_step(Seconds(0.01))
+            .collect();
+        assert_eq!(11, evo.len());
+        assert_relative_eq!(0.1, evo.last().unwrap().time().0);
+
+        // The remaining time after a maximum step is shorter than the
+        // minimum interval, the last step is not stretched over the maximum.
+        let evo: Vec<_> = sys
+            .rkf45(|_| vec![1.], &[0.], Seconds(0.05), Seconds(0.105), 1.)
+            .with_min_step(Seconds(0.01))
+            .with_max_step(Seconds(0.05))
+            .collect();
+        assert!(evo
+            .windows(2)
+            .all(|s| s[1].time().0 - s[0].time().0 <= 0.05 + 1e-15));
+        assert_eq!(Seconds(0.105), evo.last().unwrap().time());
+    }
+
+    #[test]
+    fn rkf45_tolerance() {
+        let sys = Ss::new_from_slice(1, 1, 1, &[-1.], &[1.], &[1.], &[0.]);
+        let coarse = sys
+            .rkf45(|_| vec![1.], &[0.], Seconds(0.1), Seconds(2.), 1e-3)
+            .count();
+        let fine = sys
+            .rkf45(|_| vec![1.], &[0.], Seconds(0.1), Seconds(2.), 1e-9)
+            .count();
+        assert!(fine > coarse);
+    }
+
+    #[test]
+    fn rkf45_time_of_accepted_steps() {
+        // x' = -x + 1: x(t) = 1 - e^-t
+        let sys = Ss::new_from_slice(1, 1, 1, &[-1.], &[1.], &[1.], &[0.]);
+        let limit = 2.3;
+        let evo: Vec<_> = sys
+            .rkf45(|_| vec![1.], &[0.], Seconds(0.1), Seconds(limit), 1e-10)
+            .collect();
+        // The time of each step is the one of its state.
+        for s in &evo {
+            assert_relative_eq!(1. - f64::exp(-s.time().0), s.state()[0], epsilon = 1e-8);
+        }
+        // The time limit is reached and never exceeded.
+        assert!(evo.windows(2).all(|s| s[0].time() < s[1].time()));
+        assert_eq!(Seconds(limit), evo.last().unwrap().time());
+    }
+
+    #[test]
+    fn rkf45_dense_output() {
+        // x' = -x + sin(t): x(t) = (sin(t) - cos(t) + e^-t) / 2
+        let sys = Ss::new_from_slice(1, 1, 1, &[-1.], &[1.], &[2.], &[0.]);
+        let sine = |t: Seconds<f64>| vec![t.0.sin()];
+        let expected = |t: f64| (t.sin() - t.cos() + (-t).exp()) / 2.;
+        let evo: Vec<_> = sys
+            .rkf45(sine, &[0.], Seconds(0.1), Seconds(5.), 1e-8)
+            .dense_output(Seconds(0.05))
+            .collect();
+        assert_eq!(101, evo.len());
+        for (i, s) in evo.iter().enumerate() {
+            let t = 0.05 * i as f64;
+            assert_relative_eq!(t, s.time().0, max_relative = 1e-12);
+            assert_relative_eq!(expected(t), s.state()[0], epsilon = 1e-5);
+            assert_relative_eq!(2. * s.state()[0], s.output()[0]);
+        }
+        assert_relative_eq!(5., evo.last().unwrap().time().0);
+    }
+
+    #[test]
+    fn free_response() {
+        // Harmonic oscillator: x1 = cos(t), x2 = -sin(t)
+        let sys = Ss::new_from_slice(2, 1, 1, &[0., 1., -1., 0.], &[0., 1.], &[1., 0.], &[1.]);
+        let response = sys.initial_response(&[1., 0.], Seconds(0.1), 100);
+        assert_eq!(101, response.len());
+        for (k, s) in response.iter().enumerate() {
+            let t = 0.1 * k as f64;
+            assert_relative_eq!(t, s.time().0, max_relative = 1e-12);
+            assert_relative_eq!(t.cos(), s.state()[0], epsilon = 1e-12);
+            assert_relative_eq!(-t.sin(), s.state()[1], epsilon = 1e-12);
+            assert_relative_eq!(t.cos(), s.output()[0], epsilon = 1e-12);
+        }
+    }
+
+    #[test]
+    fn impulse_response() {
+        // G(s) = 1 / ((s + 1)(s + 2)), g(t) = e^-t - e^-2t
+        let tf = crate::Tf::new(crate::poly!(1.), crate::poly!(2., 3., 1.));
+        let sys = Ss::new_controllability_realization(&tf).unwrap();
+        let response = sys.impulse(Seconds(0.1), 50);
+        assert_eq!(1, response.len());
+        assert_eq!(51, response[0].len());
+        for s in &response[0] {
+            let t: f64 = s.time().0;
+            assert_relative_eq!((-t).exp() - (-2. * t).exp(), s.output()[0], epsilon = 1e-12);
+        }
+    }
+
+    #[test]
+    fn frequency_response() {
+        let sys = Ss::new_from_slice(
+            3,
+            2,
+            2,
+            &[-1., 2., 0.5, -3., -2., 1., 0.2, 4., -5.],
+            &[1., 0., 0.5, 1., -1., 2.],
+            &[1., 0., 2., 0., 1., -1.],
+            &[0.1, 0., 0., 0.2],
+        );
+        let freqs = [
+            RadiansPerSecond(0.),
+            RadiansPerSecond(0.3),
+            RadiansPerSecond(2.),
+            RadiansPerSecond(50.),
+        ];
+        let resp = sys.freq_response(&freqs);
+        assert_eq!(freqs.len(), resp.len());
+        let to_complex = |m: &DMatrix<f64>| m.map(|x| Complex::new(x, 0.));
+        for (w, g) in freqs.iter().zip(&resp) {
+            let jw_a =
+                DMatrix::from_diagonal_element(3, 3, Complex::new(0., w.0)) - to_complex(&sys.a);
+            let expected = to_complex(&sys.c) * jw_a.try_inverse().unwrap() * to_complex(&sys.b)
+                + to_complex(&sys.d);
+            assert_eq!((2, 2), g.shape());
+            for (e, a) in expected.iter().zip(g.iter()) {
+                assert_relative_eq!(e.re, a.re, epsilon = 1e-12);
+                assert_relative_eq!(e.im, a.im, epsilon = 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn frequency_response_siso() {
+        let tf = crate::Tf::new(crate::poly!(1., 2.), crate::poly!(2., 3., 1.));
+        let sys = Ss::new_observability_realization(&tf).unwrap();
+        for &w in &[0.1, 1., 10.] {
+            let g = sys.freq_response(&[RadiansPerSecond(w)]);
+            let expected = tf.eval(&Complex::new(0., w));
+            assert_relative_eq!(expected.re, g[0][(0, 0)].re, epsilon = 1e-12);
+            assert_relative_eq!(expected.im, g[0][(0, 0)].im, epsilon = 1e-12);
+        }
+    }
+
+    #[test]
+    fn singular_values_frequency_response() {
+        let sys = Ss::new_from_slice(
+            2,
+            2,
+            2,
+            &[-1., 0., 0., -10.],
+            &[1., 0., 0., 1.],
+            &[1., 0., 0., 1.],
+            &[0., 0., 0., 0.],
+        );
+        let s = sys.sigma(RadiansPerSecond(0.01), RadiansPerSecond(100.), 0.5);
+        assert_eq!(9, s.len());
+        for (w, sv) in &s {
+            assert_eq!(2, sv.len());
+            assert!(sv[0] >= sv[1]);
+            // Diagonal system: singular values are the magnitudes of the channels.
+            let w: f64 = w.0;
+            let g1 = 1. / (1. + w * w).sqrt();
+            let g2 = 1. / (100. + w * w).sqrt();
+            assert_relative_eq!(g1, sv[0], max_relative = 1e-12);
+            assert_relative_eq!(g2, sv[1], max_relative = 1e-12);
+        }
+        assert_relative_eq!(0.01, s[0].0 .0);
+        assert_relative_eq!(100., s[8].0 .0, max_relative = 1e-12);
+    }
+
+    #[test]
+    #[should_panic]
+    fn singular_values_frequency_response_fail() {
+        let sys = Ss::new_from_slice(1, 1, 1, &[-1.], &[1.], &[1.], &[0.]);
+        let _ = sys.sigma(RadiansPerSecond(10.), RadiansPerSecond(1.), 0.1);
+    }
+
+    #[test]
+    fn input_delay_pade() {
+        let sys = Ss::new_from_slice(
+            2,
+            2,
+            1,
+            &[-1., 0., 1., -2.],
+            &[1., 0., 0., 1.],
+            &[1., 1.],
+            &[0.5, 0.],
+        );
+        let tau = 0.3;
+        let delayed = sys.with_input_delay_pade(Seconds(tau), 2);
+        assert_eq!(6, delayed.dim().states());
+        assert_eq!(2, delayed.dim().inputs());
+        assert_eq!(1, delayed.dim().outputs());
+        // At low frequency the response matches the delayed system.
+        let w = 0.5;
+        let g = sys.freq_response(&[RadiansPerSecond(w)]);
+        let gd = delayed.freq_response(&[RadiansPerSecond(w)]);
+        let delay = Complex::from_polar(1., -w * tau);
+        for j in 0..2 {
+            let expected = g[0][(0, j)] * delay;
+            assert_relative_eq!(expected.re, gd[0][(0, j)].re, epsilon = 1e-5);
+            assert_relative_eq!(expected.im, gd[0][(0, j)].im, epsilon = 1e-5);
+        }
+        // Static gain is preserved.
+        let g0 = sys.freq_response(&[RadiansPerSecond(0.)]);
+        let gd0 = delayed.freq_response(&[RadiansPerSecond(0.)]);
+        assert_relative_eq!(g0[0][(0, 0)].re, gd0[0][(0, 0)].re, epsilon = 1e-12);
+        assert_relative_eq!(g0[0][(0, 1)].re, gd0[0][(0, 1)].re, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn input_delay_pade_all_pass() {
+        let sys = Ss::new_from_slice(0, 1, 1, &[], &[], &[], &[1.]);
+        let delayed = sys.with_input_delay_pade(Seconds(1.), 3);
+        for &w in &[0.1, 1., 10., 100.] {
+            let g = delayed.freq_response(&[RadiansPerSecond(w)]);
+            assert_relative_eq!(1., g[0][(0, 0)].norm(), max_relative = 1e-10);
+        }
+        let g = delayed.freq_response(&[RadiansPerSecond(0.2)]);
+        assert_relative_eq!(-0.2, g[0][(0, 0)].arg(), max_relative = 1e-6);
+
+        assert_eq!(sys, sys.with_input_delay_pade(Seconds(1.), 0));
+        assert_eq!(sys, sys.with_input_delay_pade(Seconds(0.), 2));
+    }
+
+    #[test]
+    #[should_panic]
+    fn input_delay_pade_negative() {
+        let sys = Ss::new_from_slice(1, 1, 1, &[-1.], &[1.], &[1.], &[0.]);
+        let _ = sys.with_input_delay_pade(Seconds(-1.), 2);
+    }
+
+    #[test]
+    fn h2_norm() {
+        // G(s) = wn^2 / (s^2 + 2*zeta*wn*s + wn^2), ||G||_2^2 = wn / (4*zeta)
+        let (wn, zeta) = (2., 0.1);
+        let tf = crate::Tf::new(
+            crate::poly!(wn * wn),
+            crate::poly!(wn * wn, 2. * zeta * wn, 1.),
+        );
+        let sys = Ss::new_observability_realization(&tf).unwrap();
+        let expected: f64 = wn / (4. * zeta);
+        assert_relative_eq!(
+            expected.sqrt(),
+            sys.norm_h2().unwrap(),
+            max_relative = 1e-10
+        );
+
+        let feedthrough = Ss::new_from_slice(1, 1, 1, &[-1.], &[1.], &[1.], &[1.]);
+        assert!(feedthrough.norm_h2().unwrap().is_infinite());
+
+        let unstable = Ss::new_from_slice(1, 1, 1, &[1.], &[1.], &[1.], &[0.]);
+        assert_eq!(
+            ErrorKind::UnstableSystem,
+            unstable.norm_h2().unwrap_err().kind()
+        );
+    }
+
+    #[test]
+    fn hinf_norm() {
+        // Resonance peak: 1 / (2*zeta*sqrt(1 - zeta^2))
+        let zeta: f64 = 0.05;
+        let tf = crate::Tf::new(crate::poly!(1.), crate::poly!(1., 2. * zeta, 1.));
+        let sys = Ss::new_observability_realization(&tf).unwrap();
+        let expected = 1. / (2. * zeta * (1. - zeta * zeta).sqrt());
+        assert_relative_eq!(expected, sys.norm_hinf(1e-10).unwrap(), max_relative = 1e-8);
+
+        // Static gain is the peak, with feedthrough.
+        let sys = Ss::new_from_slice(1, 1, 1, &[-1.], &[1.], &[2.], &[1.]);
+        assert_relative_eq!(3., sys.norm_hinf(1e-10).unwrap(), max_relative = 1e-8);
+
+        // Static MIMO system.
+        let sys = Ss::new_from_slice(0, 2, 2, &[], &[], &[], &[3., 0., 0., 4.]);
+        assert_relative_eq!(4., sys.norm_hinf(1e-10).unwrap());
+
+        let unstable = Ss::new_from_slice(1, 1, 1, &[1.], &[1.], &[1.], &[0.]);
+        assert!(unstable.norm_hinf(1e-6).is_err());
+    }
+
+    #[test]
+    fn hinf_norm_mimo() {
+        let sys = Ss::new_from_slice(
+            2,
+            2,
+            2,
+            &[-1., 0., 0., -10.],
+            &[1., 0., 0., 1.],
+            &[1., 0., 0., 100.],
+            &[0., 0., 0., 0.],
+        );
+        assert_relative_eq!(10., sys.norm_hinf(1e-10).unwrap(), max_relative = 1e-8);
+    }
+
+    #[test]
+    fn lsim_hold() {
+        // Integrator driven by a sampled ramp.
+        let sys = Ss::new_from_slice(1, 1, 1, &[0.], &[1.], &[1.], &[0.]);
+        let input: Vec<_> = [0., 0.1, 0.3, 0.4, 0.8, 1.]
+            .iter()
+            .map(|&t| (Seconds(t), vec![t]))
+            .collect();
+        let foh = sys.lsim(&input, &[0.], Interpolation::FirstOrderHold);
+        assert_eq!(input.len(), foh.len());
+        for s in &foh {
+            assert_relative_eq!(s.time().0 * s.time().0 / 2., s.output()[0], epsilon = 1e-14);
+        }
+        let zoh = sys.lsim(&input, &[0.], Interpolation::ZeroOrderHold);
+        let mut expected = 0.;
+        for (s, w) in zoh.iter().skip(1).zip(input.windows(2)) {
+            expected += w[0].1[0] * (w[1].0 .0 - w[0].0 .0);
+            assert_relative_eq!(expected, s.state()[0], epsilon = 1e-14);
+        }
+    }
+
+    #[test]
+    fn lsim_zoh_matches_discretization() {
+        let sys = Ss::new_from_slice(2, 1, 1, &[-3., 0., -4., -4.], &[0., 1.], &[1., 1.], &[0.5]);
+        let st = 0.1;
+        let input: Vec<_> = (0..30)
+            .map(|i| {
+                let t = st * f64::from(i);
+                (Seconds(t), vec![t.sin()])
+            })
+            .collect();
+        let response = sys.lsim(&input, &[1., -1.], Interpolation::ZeroOrderHold);
+        let disc_sys = sys.discretize_zoh(Seconds(st));
+        let evo = disc_sys.evolution_fn(29, |k| input[k].1.clone(), &[1., -1.]);
+        for (s, e) in response.iter().zip(evo) {
+            assert_relative_eq!(e.state()[0], s.state()[0], epsilon = 1e-12);
+            assert_relative_eq!(e.state()[1], s.state()[1], epsilon = 1e-12);
+            assert_relative_eq!(e.output()[0], s.output()[0], epsilon = 1e-12);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn lsim_unordered_samples() {
+        let sys = Ss::new_from_slice(1, 1, 1, &[0.], &[1.], &[1.], &[0.]);
+        let input = [(Seconds(0.), vec![1.]), (Seconds(0.), vec![1.])];
+        let _ = sys.lsim(&input, &[0.], Interpolation::ZeroOrderHold);
+    }
+
+    #[test]
+    fn solvers_single_precision() {
+        let a = [-1_f32, 1., -1., 0.25];
+        let b = [1., 0.25];
+        let c = [0., 1.];
+        let d = [0.];
+        let sys = Ss::new_from_slice(2, 1, 1, &a, &b, &c, &d);
+        let x_rk2 = sys.rk2(|_| vec![1.], &[0., 0.], Seconds(0.1), 200);
+        let x_rk4 = sys.rk4(|_| vec![1.], &[0., 0.], Seconds(0.1), 200);
+        let x_rkf45 = sys.rkf45(|_| vec![1.], &[0., 0.], Seconds(0.1), Seconds(20.), 1e-4);
+        let x_radau = sys.radau(|_| vec![1.], &[0., 0.], Seconds(0.1), 200, 1e-4);
+        let eq = sys.equilibrium(&[1.]).unwrap();
+        let expected = eq.y()[0];
+        assert_relative_eq!(
+            expected,
+            x_rk2.last().unwrap().output()[0],
+            max_relative = 1e-2
+        );
+        assert_relative_eq!(
+            expected,
+            x_rk4.last().unwrap().output()[0],
+            max_relative = 1e-2
+        );
+        assert_relative_eq!(
+            expected,
+            x_rkf45.last().unwrap().output()[0],
+            max_relative = 1e-2
+        );
+        assert_relative_eq!(
+            expected,
+            x_radau.last().unwrap().output()[0],
+            max_relative = 1e-2
+        );
+    }
 }
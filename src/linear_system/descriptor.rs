@@ -0,0 +1,500 @@
+//! # Descriptor linear system
+//!
+//! Continuous time linear system in descriptor (generalized state-space) form
+//! ```text
+//! E * xdot(t) = A * x(t) + B * u(t)
+//! y(t)        = C * x(t) + D * u(t)
+//! ```
+//!
+//! The descriptor matrix `E` may be singular, in this case part of the states
+//! are defined by algebraic equations, as it happens for semi-explicit
+//! differential algebraic equations obtained from physical modeling.
+//!
+//! This module contains:
+//! * poles calculation through generalized eigenvalues
+//! * conversion to the standard form for non singular descriptor matrices
+//! * simulation of index one systems
+
+use nalgebra::{ComplexField, DMatrix, DVector, RealField, Scalar};
+use num_complex::Complex;
+use num_traits::Float;
+
+use crate::{
+    enums::Interpolation,
+    error::{Error, ErrorKind},
+    linear_system::{continuous::Ss, eigenvalues, solver::Step, Dim},
+    units::Seconds,
+};
+
+/// Descriptor representation of a continuous time linear system
+///
+/// ```text
+/// E * xdot(t) = A * x(t) + B * u(t)
+/// y(t)        = C * x(t) + D * u(t)
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Dss<T: Scalar> {
+    /// E matrix
+    e: DMatrix<T>,
+    /// A matrix
+    a: DMatrix<T>,
+    /// B matrix
+    b: DMatrix<T>,
+    /// C matrix
+    c: DMatrix<T>,
+    /// D matrix
+    d: DMatrix<T>,
+    /// Dimensions
+    dim: Dim,
+}
+
+/// Implementation of the methods for the descriptor system
+impl<T: Scalar> Dss<T> {
+    /// Create a new descriptor system
+    ///
+    /// # Arguments
+    ///
+    /// * `states` - number of states (n)
+    /// * `inputs` - number of inputs (m)
+    /// * `outputs` - number of outputs (p)
+    /// * `e` - E matrix (nxn), row major matrix supplied as slice
+    /// * `a` - A matrix (nxn), row major matrix supplied as slice
+    /// * `b` - B matrix (nxm), row major matrix supplied as slice
+    /// * `c` - C matrix (pxn), row major matrix supplied as slice
+    /// * `d` - D matrix (pxm), row major matrix supplied as slice
+    ///
+    /// # Panics
+    ///
+    /// Panics if matrix dimensions do not match
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use au::Dss;
+    /// let sys = Dss::new_from_slice(
+    ///     2, 1, 1,
+    ///     &[1., 0., 0., 0.],
+    ///     &[-2., 1., 1., -1.],
+    ///     &[0., 1.],
+    ///     &[0., 1.],
+    ///     &[0.],
+    /// );
+    /// ```
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_from_slice(
+        states: usize,
+        inputs: usize,
+        outputs: usize,
+        e: &[T],
+        a: &[T],
+        b: &[T],
+        c: &[T],
+        d: &[T],
+    ) -> Self {
+        Self {
+            e: DMatrix::from_row_slice(states, states, e),
+            a: DMatrix::from_row_slice(states, states, a),
+            b: DMatrix::from_row_slice(states, inputs, b),
+            c: DMatrix::from_row_slice(outputs, states, c),
+            d: DMatrix::from_row_slice(outputs, inputs, d),
+            dim: Dim {
+                states,
+                inputs,
+                outputs,
+            },
+        }
+    }
+
+    /// Get the descriptor matrix (E) of the system.
+    #[must_use]
+    pub fn e(&self) -> &DMatrix<T> {
+        &self.e
+    }
+
+    /// Get the states matrix (A) of the system.
+    #[must_use]
+    pub fn a(&self) -> &DMatrix<T> {
+        &self.a
+    }
+
+    /// Get the input matrix (B) of the system.
+    #[must_use]
+    pub fn b(&self) -> &DMatrix<T> {
+        &self.b
+    }
+
+    /// Get the output matrix (C) of the system.
+    #[must_use]
+    pub fn c(&self) -> &DMatrix<T> {
+        &self.c
+    }
+
+    /// Get the direct transmission matrix (D) of the system.
+    #[must_use]
+    pub fn d(&self) -> &DMatrix<T> {
+        &self.d
+    }
+
+    /// Get the dimensions of the system (states, inputs, outputs).
+    #[must_use]
+    pub fn dim(&self) -> Dim {
+        self.dim
+    }
+}
+
+impl<T: ComplexField + Float + RealField> From<Ss<T>> for Dss<T> {
+    /// Descriptor form of a state-space system, with identity descriptor matrix.
+    fn from(sys: Ss<T>) -> Self {
+        Self {
+            e: DMatrix::identity(sys.dim.states, sys.dim.states),
+            a: sys.a,
+            b: sys.b,
+            c: sys.c,
+            d: sys.d,
+            dim: sys.dim,
+        }
+    }
+}
+
+/// Implementation of the methods for the descriptor system
+impl<T: ComplexField + Float + RealField> Dss<T> {
+    /// Calculate the finite poles of the system, the finite generalized
+    /// eigenvalues of the pencil `(A, E)`.
+    ///
+    /// The eigenvalues `mu` of `(A - s0*E)^-1 * E` are calculated for a shift
+    /// `s0` that is not a pole, the poles are `s0 + 1/mu` for the non zero
+    /// `mu`, while the zero ones correspond to infinite eigenvalues.
+    ///
+    /// The result is empty if the pencil is singular.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use au::Dss;
+    /// let sys = Dss::new_from_slice(
+    ///     2, 1, 1,
+    ///     &[1., 0., 0., 0.],
+    ///     &[-2., 1., 1., -1.],
+    ///     &[0., 1.],
+    ///     &[0., 1.],
+    ///     &[0.],
+    /// );
+    /// let poles = sys.poles();
+    /// assert_eq!(1, poles.len());
+    /// assert!((poles[0].re + 1_f64).abs() < 1e-12);
+    /// ```
+    #[must_use]
+    pub fn poles(&self) -> Vec<Complex<T>> {
+        // Shifts are chosen so that it is unlikely that they are poles.
+        let shifts = [0., 0.611_953, -1.324_718, 2.903_317];
+        let shifted_inverse = shifts.iter().find_map(|&s| {
+            let s0 = T::from(s).unwrap();
+            (&self.a - &self.e * s0)
+                .try_inverse()
+                .map(|inv| (s0, inv * &self.e))
+        });
+        let (s0, m) = match shifted_inverse {
+            Some(x) => x,
+            None => return Vec::new(),
+        };
+        let tol = Float::sqrt(T::epsilon()) * m.norm();
+        eigenvalues(&m)
+            .into_iter()
+            .filter(|mu| mu.norm() > tol)
+            .map(|mu| Complex::new(s0, T::zero()) + mu.inv())
+            .collect()
+    }
+
+    /// Convert the descriptor system into a state-space system with the same
+    /// states, when the descriptor matrix is not singular.
+    ///
+    /// ```text
+    /// xdot(t) = E^-1 * A * x(t) + E^-1 * B * u(t)
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// It returns an error if the descriptor matrix is singular.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use au::Dss;
+    /// let sys = Dss::new_from_slice(1, 1, 1, &[2.], &[-4.], &[2.], &[1.], &[0.]);
+    /// let ss = sys.to_ss().unwrap();
+    /// assert_eq!(-2., ss.a()[(0, 0)]);
+    /// assert_eq!(1., ss.b()[(0, 0)]);
+    /// ```
+    pub fn to_ss(&self) -> Result<Ss<T>, Error> {
+        let e_inv = self
+            .e
+            .clone()
+            .try_inverse()
+            .ok_or_else(|| Error::new_internal(ErrorKind::SingularDescriptorMatrix))?;
+        Ok(Ss::new_from_matrices(
+            &e_inv * &self.a,
+            &e_inv * &self.b,
+            self.c.clone(),
+            self.d.clone(),
+        ))
+    }
+
+    /// Simulate the time response of an index one system to a sampled input
+    /// signal.
+    ///
+    /// The algebraic part of the states is calculated from the differential
+    /// part and the input, the initial state is projected on the differential
+    /// part so that it is consistent with the algebraic constraints.
+    /// The simulation of the differential part follows `Ss::lsim`.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - Input samples as `(time, input vector)`, in increasing time order
+    /// * `x0` - Initial state at the time of the first sample (column vector)
+    /// * `hold` - Behavior of the input between samples
+    ///
+    /// # Errors
+    ///
+    /// It returns an error if the system is not of index one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the sample times are not increasing or the input and
+    /// initial state lengths do not match the system dimensions.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use au::{enums::Interpolation, Dss, Seconds};
+    /// let sys = Dss::new_from_slice(
+    ///     2, 1, 1,
+    ///     &[1., 0., 0., 0.],
+    ///     &[-2., 1., 1., -1.],
+    ///     &[0., 1.],
+    ///     &[0., 1.],
+    ///     &[0.],
+    /// );
+    /// let input: Vec<_> = (0..=10).map(|i| (Seconds(0.1 * f64::from(i)), vec![1.])).collect();
+    /// let response = sys.lsim(&input, &[0., 0.], Interpolation::ZeroOrderHold).unwrap();
+    /// let last = response.last().unwrap();
+    /// assert!((last.output()[0] - (2. - (-1_f64).exp())).abs() < 1e-12);
+    /// ```
+    pub fn lsim(
+        &self,
+        input: &[(Seconds<T>, Vec<T>)],
+        x0: &[T],
+        hold: Interpolation,
+    ) -> Result<Vec<Step<T>>, Error> {
+        assert_eq!(self.dim.states, x0.len(), "Wrong initial state length");
+        let reduced = self.reduce()?;
+        let z0 = reduced.projection.tr_mul(&DVector::from_column_slice(x0));
+        let response = reduced
+            .sys
+            .lsim(input, z0.as_slice(), hold)
+            .into_iter()
+            .zip(input)
+            .map(|(step, (_, u))| {
+                let z = DVector::from_column_slice(step.state());
+                let u = DVector::from_column_slice(u);
+                let x = &reduced.state_map * z + &reduced.input_map * u;
+                Step::new(step.time(), x.as_slice().to_vec(), step.output().clone())
+            })
+            .collect();
+        Ok(response)
+    }
+
+    /// Reduce an index one system to a state-space system for the
+    /// differential part of the states.
+    ///
+    /// Given the singular value decomposition `E = U*S*V'`, split in the parts
+    /// related to non zero (1) and zero (2) singular values, the states are
+    /// `x = V1*z1 + V2*z2`, where `z2` is obtained from the algebraic
+    /// equations, that can be solved if `U2'*A*V2` is not singular.
+    fn reduce(&self) -> Result<Reduction<T>, Error> {
+        let n = self.dim.states;
+        let svd = self.e.clone().svd(true, true);
+        let (u, v_t) = match (svd.u, svd.v_t) {
+            (Some(u), Some(v_t)) => (u, v_t),
+            _ => return Err(Error::new_internal(ErrorKind::NoIndexOneSystem)),
+        };
+        let sigma = svd.singular_values;
+        let sigma_max = sigma.iter().fold(T::zero(), |m, &s| Float::max(m, s));
+        let tol = T::from(n).unwrap() * T::epsilon() * sigma_max;
+        let (diff, alg): (Vec<_>, Vec<_>) = (0..n).partition(|&i| sigma[i] > tol);
+
+        let v = v_t.transpose();
+        let u1 = u.select_columns(&diff);
+        let u2 = u.select_columns(&alg);
+        let v1 = v.select_columns(&diff);
+        let v2 = v.select_columns(&alg);
+        let s1_inv = DMatrix::from_diagonal(&DVector::from_iterator(
+            diff.len(),
+            diff.iter().map(|&i| T::one() / sigma[i]),
+        ));
+
+        // Algebraic part: z2 = -A22^-1 * (A21*z1 + B2*u)
+        let a22_inv = u2
+            .tr_mul(&(&self.a * &v2))
+            .try_inverse()
+            .ok_or_else(|| Error::new_internal(ErrorKind::NoIndexOneSystem))?;
+        let z2_z1 = -&a22_inv * u2.tr_mul(&(&self.a * &v1));
+        let z2_u = -&a22_inv * u2.tr_mul(&self.b);
+
+        let state_map = &v1 + &v2 * &z2_z1;
+        let input_map = &v2 * &z2_u;
+        let a = &s1_inv * u1.tr_mul(&(&self.a * &state_map));
+        let b = &s1_inv * u1.tr_mul(&(&self.a * &input_map + &self.b));
+        let c = &self.c * &state_map;
+        let d = &self.c * &input_map + &self.d;
+
+        Ok(Reduction {
+            sys: Ss::new_from_matrices(a, b, c, d),
+            projection: v1,
+            state_map,
+            input_map,
+        })
+    }
+}
+
+/// Reduction of an index one descriptor system to its differential part
+struct Reduction<T: Scalar> {
+    /// State-space system of the differential part
+    sys: Ss<T>,
+    /// Projection of the states on the differential part
+    projection: DMatrix<T>,
+    /// States from the differential part
+    state_map: DMatrix<T>,
+    /// States from the input
+    input_map: DMatrix<T>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn index_one() -> Dss<f64> {
+        // xdot1 = -2*x1 + x2
+        // 0     = x1 - x2 + u
+        Dss::new_from_slice(
+            2,
+            1,
+            1,
+            &[1., 0., 0., 0.],
+            &[-2., 1., 1., -1.],
+            &[0., 1.],
+            &[0., 1.],
+            &[0.],
+        )
+    }
+
+    #[test]
+    fn descriptor_getters() {
+        let sys = index_one();
+        assert_eq!(&DMatrix::from_row_slice(2, 2, &[1., 0., 0., 0.]), sys.e());
+        assert_eq!((2, 2), sys.a().shape());
+        assert_eq!((2, 1), sys.b().shape());
+        assert_eq!((1, 2), sys.c().shape());
+        assert_eq!((1, 1), sys.d().shape());
+        assert_eq!(2, sys.dim().states());
+    }
+
+    #[test]
+    fn descriptor_from_ss() {
+        let ss = Ss::new_from_slice(2, 1, 1, &[-2., 0., 3., -7.], &[1., 3.], &[-1., 0.5], &[0.1]);
+        let sys = Dss::from(ss.clone());
+        assert_eq!(&DMatrix::identity(2, 2), sys.e());
+        assert_eq!(ss, sys.to_ss().unwrap());
+    }
+
+    #[test]
+    fn descriptor_poles_invertible() {
+        let sys = Dss::new_from_slice(
+            2,
+            1,
+            1,
+            &[2., 0., 0., 1.],
+            &[-2., 0., 0., -3.],
+            &[1., 1.],
+            &[1., 1.],
+            &[0.],
+        );
+        let mut poles: Vec<_> = sys.poles().iter().map(|p| p.re).collect();
+        poles.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_relative_eq!(-3., poles[0], max_relative = 1e-12);
+        assert_relative_eq!(-1., poles[1], max_relative = 1e-12);
+    }
+
+    #[test]
+    fn descriptor_poles_index_one() {
+        let poles = index_one().poles();
+        assert_eq!(1, poles.len());
+        assert_relative_eq!(-1., poles[0].re, max_relative = 1e-12);
+        assert_relative_eq!(0., poles[0].im);
+    }
+
+    #[test]
+    fn descriptor_poles_singular_pencil() {
+        let sys = Dss::new_from_slice(1, 1, 1, &[0.], &[0.], &[1.], &[1.], &[0.]);
+        assert!(sys.poles().is_empty());
+    }
+
+    #[test]
+    fn descriptor_to_ss() {
+        let sys = Dss::new_from_slice(
+            2,
+            1,
+            1,
+            &[2., 0., 0., 1.],
+            &[-2., 0., 0., -3.],
+            &[2., 1.],
+            &[1., 1.],
+            &[0.],
+        );
+        let ss = sys.to_ss().unwrap();
+        assert_eq!(&DMatrix::from_row_slice(2, 2, &[-1., 0., 0., -3.]), ss.a());
+        assert_eq!(&DMatrix::from_row_slice(2, 1, &[1., 1.]), ss.b());
+
+        let err = index_one().to_ss().unwrap_err();
+        assert_eq!(ErrorKind::SingularDescriptorMatrix, err.kind());
+    }
+
+    #[test]
+    fn descriptor_simulation() {
+        let sys = index_one();
+        let input: Vec<_> = (0..=20)
+            .map(|i| (Seconds(0.1 * f64::from(i)), vec![1.]))
+            .collect();
+        // Inconsistent initial algebraic state is corrected.
+        let response = sys
+            .lsim(&input, &[0., 5.], Interpolation::ZeroOrderHold)
+            .unwrap();
+        assert_eq!(input.len(), response.len());
+        for step in &response {
+            let t: f64 = step.time().0;
+            let x1 = 1. - (-t).exp();
+            assert_relative_eq!(x1, step.state()[0], epsilon = 1e-12);
+            assert_relative_eq!(x1 + 1., step.state()[1], epsilon = 1e-12);
+            assert_relative_eq!(x1 + 1., step.output()[0], epsilon = 1e-12);
+        }
+    }
+
+    #[test]
+    fn descriptor_simulation_not_index_one() {
+        // Nilpotent descriptor matrix, index two system.
+        let sys = Dss::new_from_slice(
+            2,
+            1,
+            1,
+            &[0., 1., 0., 0.],
+            &[1., 0., 0., 1.],
+            &[0., 1.],
+            &[1., 0.],
+            &[0.],
+        );
+        let input = [(Seconds(0.), vec![1.]), (Seconds(1.), vec![1.])];
+        let err = sys
+            .lsim(&input, &[0., 0.], Interpolation::ZeroOrderHold)
+            .unwrap_err();
+        assert_eq!(ErrorKind::NoIndexOneSystem, err.kind());
+    }
+}
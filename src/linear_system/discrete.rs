@@ -8,9 +8,12 @@
 //! * forward Euler method
 //! * backward Euler method
 //! * Tustin (trapezoidal) method
+//! * zero order hold (exact)
 
 use nalgebra::{ComplexField, DMatrix, DVector, RealField, Scalar};
-use num_traits::Float;
+use num_traits::{Float, Zero};
+#[cfg(feature = "rand")]
+use rand::Rng;
 
 use std::{
     marker::PhantomData,
@@ -19,12 +22,62 @@ use std::{
 
 use crate::{
     enums::{Discrete, Discretization},
-    linear_system::{continuous::Ss, Equilibrium, SsGen},
+    error::{Error, ErrorKind},
+    linear_system::{continuous::Ss, linalg, Equilibrium, SsGen},
+    units::{RadiansPerSecond, SampleTime, Seconds},
 };
 
+#[cfg(feature = "rand")]
+use crate::linear_system::random::{self, RandomPoles};
+
 /// State-space representation of discrete time linear system
 pub type Ssd<T> = SsGen<T, Discrete>;
 
+impl<T: Float + Scalar> Ssd<T> {
+    /// Set the sampling period of the system.
+    ///
+    /// The sampling period is set by the discretization of continuous time
+    /// systems and it is kept by the transformations of the system.
+    ///
+    /// # Arguments
+    ///
+    /// * `sample_time` - Sampling period
+    ///
+    /// # Panics
+    ///
+    /// Panics if the sampling period is not positive.
+    ///
+    /// # Example
+    /// ```
+    /// use au::{SampleTime, Ssd};
+    /// let sys = Ssd::new_from_slice(1, 1, 1, &[0.5], &[1.], &[1.], &[0.])
+    ///     .with_sample_time(SampleTime(0.1));
+    /// assert_eq!(Some(SampleTime(0.1)), sys.sample_time());
+    /// ```
+    #[must_use]
+    pub fn with_sample_time(self, sample_time: SampleTime<T>) -> Self {
+        assert!(
+            sample_time.0 > T::zero(),
+            "Sampling period must be positive"
+        );
+        self.with_sampling(Some(sample_time.0))
+    }
+
+    /// Get the sampling period of the system, `None` if it is not known.
+    ///
+    /// # Example
+    /// ```
+    /// use au::{Discretization, SampleTime, Ss};
+    /// let sys = Ss::new_from_slice(1, 1, 1, &[-1.], &[1.], &[1.], &[0.]);
+    /// let disc = sys.discretize(0.1, Discretization::Tustin).unwrap();
+    /// assert_eq!(Some(SampleTime(0.1)), disc.sample_time());
+    /// ```
+    #[must_use]
+    pub fn sample_time(&self) -> Option<SampleTime<T>> {
+        self.sample_time.map(SampleTime)
+    }
+}
+
 /// Implementation of the methods for the state-space
 impl<T: ComplexField> Ssd<T> {
     /// Calculate the equilibrium point for discrete time systems,
@@ -137,6 +190,125 @@ impl<T: Scalar> Ssd<T> {
             iter: iter.into_iter(),
         }
     }
+
+    /// Closed loop time evolution for a discrete linear system, where the
+    /// input is calculated at each step by a control law from the time and
+    /// the current output of the system.
+    ///
+    /// The output given to the control law is calculated with the input of
+    /// the previous step (zero at the first step), so that the loop is well
+    /// posed even if the system has a direct transmission (D matrix); for
+    /// strictly proper systems it is the current output.
+    ///
+    /// # Arguments
+    ///
+    /// * `steps` - simulation length
+    /// * `control` - control law, returns the input from time and output
+    /// * `x0` - initial state
+    ///
+    /// # Example
+    /// ```
+    /// use au::Ssd;
+    /// let sys = Ssd::new_from_slice(1, 1, 1, &[1.], &[1.], &[1.], &[0.]);
+    /// // Proportional controller with unitary reference.
+    /// let evo = sys.evolution_feedback(50, |_k, y: &[f64]| vec![0.5 * (1. - y[0])], &[0.]);
+    /// let last = evo.last().unwrap();
+    /// assert!((last.output()[0] - 1.).abs() < 1e-6);
+    /// ```
+    pub fn evolution_feedback<F>(
+        &self,
+        steps: usize,
+        control: F,
+        x0: &[T],
+    ) -> EvolutionFeedback<'_, F, T>
+    where
+        F: FnMut(usize, &[T]) -> Vec<T>,
+        T: Zero,
+    {
+        let state = DVector::from_column_slice(x0);
+        let input = DVector::from_element(self.dim.inputs(), T::zero());
+        EvolutionFeedback {
+            sys: self,
+            time: 0,
+            steps,
+            control,
+            state,
+            input,
+        }
+    }
+}
+
+/// Generation of random systems
+#[cfg(feature = "rand")]
+impl<T: ComplexField + Float + RealField> Ssd<T> {
+    /// Generate a random stable system, the modulus of the poles
+    /// is in the interval [0, 0.95].
+    ///
+    /// # Arguments
+    ///
+    /// * `states` - number of states
+    /// * `inputs` - number of inputs
+    /// * `outputs` - number of outputs
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use au::Ssd;
+    /// let sys = Ssd::<f64>::rand_stable(4, 2, 3);
+    /// assert!(sys.is_stable());
+    /// ```
+    #[must_use]
+    pub fn rand_stable(states: usize, inputs: usize, outputs: usize) -> Self {
+        Self::rand_stable_with(
+            states,
+            inputs,
+            outputs,
+            &RandomPoles::discrete(),
+            &mut rand::thread_rng(),
+        )
+    }
+
+    /// Generate a random stable system with the given distribution of the
+    /// poles and random number generator.
+    ///
+    /// The magnitude of the distribution is the modulus of the poles, the
+    /// damping of the distribution is not used.
+    ///
+    /// # Arguments
+    ///
+    /// * `states` - number of states
+    /// * `inputs` - number of inputs
+    /// * `outputs` - number of outputs
+    /// * `poles` - distribution of the poles
+    /// * `rng` - random number generator
+    ///
+    /// # Panics
+    ///
+    /// Panics if the maximum magnitude of the poles is not less than one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use au::{linear_system::random::RandomPoles, Ssd};
+    /// use rand::thread_rng;
+    /// let poles = RandomPoles::new(0.2, 0.5);
+    /// let sys = Ssd::<f64>::rand_stable_with(3, 1, 1, &poles, &mut thread_rng());
+    /// assert!(sys.poles().iter().all(|p| p.norm() <= 0.5 + 1e-9));
+    /// ```
+    pub fn rand_stable_with<R: Rng + ?Sized>(
+        states: usize,
+        inputs: usize,
+        outputs: usize,
+        poles: &RandomPoles,
+        rng: &mut R,
+    ) -> Self {
+        assert!(
+            poles.magnitude().1 < 1.,
+            "Maximum magnitude of the poles must be less than one"
+        );
+        let [a, b, c, d] = random::random_matrices((states, inputs, outputs), poles, true, rng);
+        Self::new_from_matrices(a, b, c, d)
+    }
 }
 
 impl<T: ComplexField + Float + RealField> Ssd<T> {
@@ -154,6 +326,81 @@ impl<T: ComplexField + Float + RealField> Ssd<T> {
     pub fn is_stable(&self) -> bool {
         self.poles().iter().all(|p| p.norm() < T::one())
     }
+
+    /// Stability margin of the system, the maximum magnitude of the A matrix
+    /// eigenvalues (poles). The system is stable if it is less than one.
+    ///
+    /// It is zero for systems without states.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use au::Ssd;
+    /// let sys = Ssd::new_from_slice(2, 1, 1, &[-0.2, 0., 3., 0.1], &[1., 3.], &[-1., 0.5], &[0.1]);
+    /// assert!((sys.stability_margin() - 0.2_f64).abs() < 1e-15);
+    /// ```
+    #[must_use]
+    pub fn stability_margin(&self) -> T {
+        self.poles()
+            .iter()
+            .fold(T::zero(), |m, p| Float::max(m, p.norm()))
+    }
+
+    /// Discrete time linear quadratic regulator.
+    ///
+    /// Calculate the state feedback gain `K` of the control law `u = -K*x`
+    /// that minimizes the cost function
+    /// ```text
+    /// J = Σ x(k)'*Q*x(k) + u(k)'*R*u(k)
+    /// ```
+    /// The gain is `K = (R + B'*P*B)^-1*B'*P*A`, where `P` is the stabilizing
+    /// solution of the discrete algebraic Riccati equation.
+    ///
+    /// The return value is: `(K, P)`
+    ///
+    /// # Arguments
+    ///
+    /// * `q` - State weight matrix (nxn), symmetric positive semi-definite
+    /// * `r` - Input weight matrix (mxm), symmetric positive definite
+    ///
+    /// # Errors
+    ///
+    /// It returns an error if the Riccati equation has no stabilizing
+    /// solution or `R` is not positive definite.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the weight matrices dimensions do not match the system ones.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use au::{nalgebra::DMatrix, Ssd};
+    /// let sys = Ssd::new_from_slice(1, 1, 1, &[1.], &[1.], &[1.], &[0.]);
+    /// let one = DMatrix::from_element(1, 1, 1.);
+    /// let (k, p) = sys.dlqr(&one, &one).unwrap();
+    /// assert!((k[(0, 0)] - (5_f64.sqrt() - 1.) / 2.).abs() < 1e-12);
+    /// ```
+    pub fn dlqr(&self, q: &DMatrix<T>, r: &DMatrix<T>) -> Result<(DMatrix<T>, DMatrix<T>), Error> {
+        let no_solution = || Error::new_internal(ErrorKind::NoRiccatiSolution);
+        let p = linalg::dare(&self.a, &self.b, q, r).ok_or_else(no_solution)?;
+        let btp = self.b.tr_mul(&p);
+        let k = (r + &btp * &self.b)
+            .lu()
+            .solve(&(btp * &self.a))
+            .ok_or_else(no_solution)?;
+        let closed_loop = Self::new_from_matrices(
+            &self.a - &self.b * &k,
+            self.b.clone(),
+            self.c.clone(),
+            self.d.clone(),
+        );
+        if closed_loop.is_stable() {
+            Ok((k, p))
+        } else {
+            Err(no_solution())
+        }
+    }
 }
 
 impl<T: ComplexField + Float> Ss<T> {
@@ -183,7 +430,92 @@ impl<T: ComplexField + Float> Ss<T> {
     }
 }
 
+impl<T: ComplexField + Float + RealField> Ss<T> {
+    /// Convert a linear system into a discrete system using a zero order
+    /// hold on the inputs. The discretization is exact, the state and input
+    /// matrices are obtained from the matrix exponential
+    /// ```text
+    /// ┌       ┐     ┌┌     ┐   ┐
+    /// │ Ad Bd │ = exp││ A B │*st│
+    /// │ 0  I  │     ││ 0 0 │   │
+    /// └       ┘     └└     ┘   ┘
+    /// ```
+    ///
+    /// # Arguments
+    ///
+    /// * `st` - sampling period in seconds
+    ///
+    /// # Example
+    /// ```
+    /// # #[macro_use] extern crate approx;
+    /// use au::{Seconds, Ss};
+    /// let sys = Ss::new_from_slice(1, 1, 1, &[-1.], &[1.], &[1.], &[0.]);
+    /// let disc_sys = sys.discretize_zoh(Seconds(0.1));
+    /// let evo = disc_sys.evolution_fn(1, |t| vec![1.], &[0.]);
+    /// let last = evo.last().unwrap();
+    /// assert_relative_eq!(1. - (-0.1_f64).exp(), last.state()[0], max_relative = 1e-12);
+    /// ```
+    #[must_use]
+    pub fn discretize_zoh(&self, st: Seconds<T>) -> Ssd<T> {
+        let n = self.dim.states;
+        let m = self.dim.inputs;
+        let mut aug = DMatrix::zeros(n + m, n + m);
+        aug.slice_mut((0, 0), (n, n)).copy_from(&(&self.a * st.0));
+        aug.slice_mut((0, n), (n, m)).copy_from(&(&self.b * st.0));
+        let exp = linalg::expm(&aug);
+        Ssd {
+            a: exp.slice((0, 0), (n, n)).into_owned(),
+            b: exp.slice((0, n), (n, m)).into_owned(),
+            c: self.c.clone(),
+            d: self.d.clone(),
+            dim: self.dim,
+            names: self.names.clone(),
+            time: PhantomData,
+            sample_time: Some(st.0),
+        }
+    }
+}
+
 impl<T: ComplexField + Float> Ss<T> {
+    /// Convert a linear system into a discrete system using Tustin method
+    /// with optional frequency pre-warping. When the pre-warping frequency
+    /// is given, the frequency response of the discrete system matches the
+    /// continuous one at that frequency.
+    ///
+    /// # Arguments
+    ///
+    /// * `ts` - Sampling period in seconds
+    /// * `prewarp` - Pre-warping frequency in radians per second
+    ///
+    /// # Example
+    /// ```
+    /// # #[macro_use] extern crate approx;
+    /// use au::{RadiansPerSecond, Seconds, Ss};
+    /// let sys = Ss::new_from_slice(2, 1, 1, &[-3., 0., -4., -4.], &[0., 1.], &[1., 1.], &[0.]);
+    /// let disc_sys = sys
+    ///     .discretize_tustin(Seconds(0.1), Some(RadiansPerSecond(2.)))
+    ///     .unwrap();
+    /// let evo = disc_sys.evolution_fn(20, |t| vec![1.], &[0., 0.]);
+    /// let last = evo.last().unwrap();
+    /// assert_relative_eq!(0.25, last.state()[1], max_relative = 0.01);
+    /// ```
+    pub fn discretize_tustin(
+        &self,
+        ts: Seconds<T>,
+        prewarp: Option<RadiansPerSecond<T>>,
+    ) -> Option<Ssd<T>> {
+        let sys = match prewarp {
+            // s = w/tan(w*ts/2)*(z-1)/(z+1) is Tustin method with an
+            // equivalent sampling period 2*tan(w*ts/2)/w.
+            Some(RadiansPerSecond(w)) if !w.is_zero() => {
+                let two = T::one() + T::one();
+                self.tustin(two * Float::tan(w * ts.0 / two) / w)
+            }
+            _ => self.tustin(ts.0),
+        };
+        sys.map(|s| s.with_sampling(Some(ts.0)))
+    }
+
     /// Discretization using forward Euler Method.
     ///
     /// # Arguments
@@ -198,7 +530,9 @@ impl<T: ComplexField + Float> Ss<T> {
             c: self.c.clone(),
             d: self.d.clone(),
             dim: self.dim,
+            names: self.names.clone(),
             time: PhantomData,
+            sample_time: Some(st),
         })
     }
 
@@ -217,7 +551,9 @@ impl<T: ComplexField + Float> Ss<T> {
             d: &self.d + &self.c * &a * &self.b * st,
             a,
             dim: self.dim,
+            names: self.names.io(),
             time: PhantomData,
+            sample_time: Some(st),
         })
     }
 
@@ -240,11 +576,68 @@ impl<T: ComplexField + Float> Ss<T> {
             d: &self.d + &self.c * &b * n_05,
             b,
             dim: self.dim,
+            names: self.names.io(),
             time: PhantomData,
+            sample_time: Some(st),
         })
     }
 }
 
+impl<T: ComplexField + Float> Ssd<T> {
+    /// Resample the system with a new sampling period, through the
+    /// continuous form given by the inverse Tustin transformation
+    /// ```text
+    /// A = 2/Ts * (I - 2*M),  B = 2/Ts * M * Bd,  C = 2 * Cd * M,
+    /// D = Dd - Cd * M * Bd,  M = (I + Ad)^-1
+    /// ```
+    /// that is discretized again with Tustin method. The resampling is
+    /// exact for systems discretized with Tustin method. The names of the
+    /// inputs and of the outputs are preserved.
+    ///
+    /// It returns `None` if the sampling period is not known or the system
+    /// has a pole at `-1`.
+    ///
+    /// # Arguments
+    ///
+    /// * `sample_time` - New sampling period
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new sampling period is not positive.
+    ///
+    /// # Example
+    /// ```
+    /// use au::{Discretization, SampleTime, Ss};
+    /// let sys = Ss::new_from_slice(1, 1, 1, &[-1.], &[1.], &[1.], &[0.]);
+    /// let disc = sys.discretize(0.1, Discretization::Tustin).unwrap();
+    /// let resampled = disc.resample(SampleTime(0.05)).unwrap();
+    /// assert_eq!(Some(SampleTime(0.05)), resampled.sample_time());
+    /// ```
+    #[must_use]
+    pub fn resample(&self, sample_time: SampleTime<T>) -> Option<Self> {
+        assert!(
+            sample_time.0 > T::zero(),
+            "Sampling period must be positive"
+        );
+        let ts = self.sample_time?;
+        let states = self.dim.states;
+        let identity = DMatrix::identity(states, states);
+        let two = T::one() + T::one();
+        let m = (&identity + &self.a).try_inverse()?;
+        let cont = Ss {
+            a: (&identity - &m * two) * (two / ts),
+            b: &m * &self.b * (two / ts),
+            c: &self.c * &m * two,
+            d: &self.d - &self.c * &m * &self.b,
+            dim: self.dim,
+            names: self.names.io(),
+            time: PhantomData,
+            sample_time: None,
+        };
+        cont.tustin(sample_time.0)
+    }
+}
+
 /// Struct to hold the iterator for the evolution of the discrete linear system.
 /// It uses function to supply inputs.
 #[derive(Debug)]
@@ -322,6 +715,50 @@ where
     }
 }
 
+/// Struct to hold the iterator for the closed loop evolution of the discrete
+/// linear system. It uses a control law to supply inputs.
+#[derive(Debug)]
+pub struct EvolutionFeedback<'a, F, T>
+where
+    F: FnMut(usize, &[T]) -> Vec<T>,
+    T: Scalar,
+{
+    sys: &'a Ssd<T>,
+    time: usize,
+    steps: usize,
+    control: F,
+    state: DVector<T>,
+    input: DVector<T>,
+}
+
+impl<'a, F, T> Iterator for EvolutionFeedback<'a, F, T>
+where
+    F: FnMut(usize, &[T]) -> Vec<T>,
+    T: AddAssign + Float + MulAssign + Scalar,
+{
+    type Item = TimeEvolution<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.time > self.steps {
+            None
+        } else {
+            let current_time = self.time;
+            // Measured output with the previous input.
+            let measure = &self.sys.c * &self.state + &self.sys.d * &self.input;
+            self.input = DVector::from_vec((self.control)(current_time, measure.as_slice()));
+            let output = &self.sys.c * &self.state + &self.sys.d * &self.input;
+            let state = self.state.as_slice().to_vec();
+            self.state = &self.sys.a * &self.state + &self.sys.b * &self.input;
+            self.time += 1;
+            Some(TimeEvolution {
+                time: current_time,
+                state,
+                output: output.as_slice().to_vec(),
+            })
+        }
+    }
+}
+
 /// Struct to hold the result of the discrete linear system evolution.
 #[derive(Debug)]
 pub struct TimeEvolution<T> {
@@ -354,6 +791,55 @@ impl<T> TimeEvolution<T> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn sample_time_propagation() {
+        use crate::Tfz;
+        let sys = Ss::new_from_slice(1, 1, 1, &[-1.], &[1.], &[1.], &[0.]);
+        assert_eq!(
+            Some(SampleTime(0.2)),
+            sys.discretize_zoh(Seconds(0.2)).sample_time()
+        );
+        let tustin = sys
+            .discretize_tustin(Seconds(0.2), Some(RadiansPerSecond(3.)))
+            .unwrap();
+        assert_eq!(Some(SampleTime(0.2)), tustin.sample_time());
+        assert_eq!(
+            Some(SampleTime(0.2)),
+            tustin.subsystem(&[0], &[0]).sample_time()
+        );
+        let tfz = Tfz::<f64>::new_from_siso(&tustin).unwrap();
+        assert_eq!(Some(SampleTime(0.2)), tfz.sample_time());
+        let back = Ssd::new_observability_realization(&tfz).unwrap();
+        assert_eq!(Some(SampleTime(0.2)), back.sample_time());
+        let ctrl = Ssd::new_from_slice(0, 1, 1, &[], &[], &[], &[-0.5]);
+        let cl = tustin.closed_loop(&ctrl).unwrap();
+        assert_eq!(Some(SampleTime(0.2)), cl.sample_time());
+    }
+
+    #[test]
+    #[should_panic]
+    fn closed_loop_different_sample_times() {
+        let sys = Ssd::new_from_slice(1, 1, 1, &[0.5], &[1.], &[1.], &[0.])
+            .with_sample_time(SampleTime(0.1));
+        let ctrl =
+            Ssd::new_from_slice(0, 1, 1, &[], &[], &[], &[-0.5]).with_sample_time(SampleTime(0.2));
+        let _ = sys.closed_loop(&ctrl);
+    }
+
+    #[test]
+    fn discretization_names() {
+        let sys = Ss::new_from_slice(1, 1, 1, &[-1.], &[1.], &[1.], &[0.])
+            .with_input_names(["u"])
+            .with_output_names(["y"])
+            .with_state_names(["x"]);
+        let zoh = sys.discretize_zoh(Seconds(0.1));
+        assert_eq!(sys.state_names(), zoh.state_names());
+        assert_eq!(sys.input_names(), zoh.input_names());
+        let tustin = sys.discretize(0.1, Discretization::Tustin).unwrap();
+        assert_eq!(None, tustin.state_names());
+        assert_eq!(sys.output_names(), tustin.output_names());
+    }
+
     #[allow(clippy::many_single_char_names)]
     #[test]
     fn equilibrium() {
@@ -375,6 +861,53 @@ mod tests {
         assert!(sys.equilibrium(&[0., 0., 0.]).is_none());
     }
 
+    #[test]
+    fn closed_loop_evolution() {
+        let sys = Ssd::new_from_slice(2, 1, 1, &[0.6, 0., 0., 0.4], &[1., 5.], &[1., 3.], &[0.]);
+        let mut inputs = Vec::new();
+        let evo: Vec<_> = sys
+            .evolution_feedback(
+                10,
+                |k, y| {
+                    let u = if k < 5 { 1. } else { -0.1 * y[0] };
+                    inputs.push(u);
+                    vec![u]
+                },
+                &[0., 0.],
+            )
+            .collect();
+        assert_eq!(11, evo.len());
+        // Same evolution of the open loop system with the recorded inputs.
+        let open_loop = sys.evolution_fn(10, |k| vec![inputs[k]], &[0., 0.]);
+        for (c, o) in evo.iter().zip(open_loop) {
+            assert_eq!(c.time(), o.time());
+            assert_relative_eq!(c.state()[0], o.state()[0]);
+            assert_relative_eq!(c.state()[1], o.state()[1]);
+            assert_relative_eq!(c.output()[0], o.output()[0]);
+        }
+    }
+
+    #[test]
+    fn closed_loop_evolution_feedthrough() {
+        let sys = Ssd::new_from_slice(1, 1, 1, &[0.5], &[1.], &[1.], &[2.]);
+        let mut measures = Vec::new();
+        let evo: Vec<_> = sys
+            .evolution_feedback(
+                2,
+                |_, y| {
+                    measures.push(y[0]);
+                    vec![1.]
+                },
+                &[1.],
+            )
+            .collect();
+        // The measure uses the previous input, zero at the first step.
+        assert_relative_eq!(1., measures[0]);
+        assert_relative_eq!(1.5 + 2., measures[1]);
+        assert_relative_eq!(3., evo[0].output()[0]);
+        assert_relative_eq!(3.5, evo[1].output()[0]);
+    }
+
     #[test]
     fn stability() {
         let a = &[0., 0.8, 0.4, 1., 0., 0., 0., 1., 0.7];
@@ -387,6 +920,63 @@ mod tests {
         assert!(!sys.is_stable());
     }
 
+    #[test]
+    fn stability_margin() {
+        let sys = Ssd::new_from_slice(
+            2,
+            1,
+            1,
+            &[0.5, 0.5, -0.5, 0.5],
+            &[1., 3.],
+            &[-1., 0.5],
+            &[0.1],
+        );
+        assert_relative_eq!(0.5_f64.sqrt(), sys.stability_margin(), max_relative = 1e-12);
+        assert!(sys.is_stable());
+
+        let sys = Ssd::new_from_slice(
+            3,
+            2,
+            1,
+            &[0., 0.8, 0.4, 1., 0., 0., 0., 1., 0.7],
+            &[0., 1., 0., 0., -1., 0.],
+            &[1., 1.8, 1.1],
+            &[-1., 1.],
+        );
+        assert!(sys.stability_margin() > 1.);
+        assert!(!sys.is_stable());
+
+        let sys = Ssd::new_from_slice(0, 1, 1, &[], &[], &[], &[1.]);
+        assert_eq!(0., sys.stability_margin());
+    }
+
+    #[test]
+    fn linear_quadratic_regulator() {
+        let sys = Ssd::new_from_slice(2, 1, 1, &[1., 0.1, 0., 1.], &[0.005, 0.1], &[1., 0.], &[0.]);
+        let q = DMatrix::from_row_slice(2, 2, &[1., 0., 0., 0.5]);
+        let r = DMatrix::from_element(1, 1, 2.);
+        let (k, p) = sys.dlqr(&q, &r).unwrap();
+        assert_eq!((1, 2), k.shape());
+        assert_eq!((2, 2), p.shape());
+        let a_cl = &sys.a - &sys.b * &k;
+        let cl = Ssd::new_from_matrices(a_cl, sys.b.clone(), sys.c.clone(), sys.d.clone());
+        assert!(cl.is_stable());
+        // Optimality: P = A'*P*(A - B*K) + Q
+        let res = sys.a.tr_mul(&p) * &cl.a + &q - &p;
+        assert_relative_eq!(0., res.norm(), epsilon = 1e-9);
+    }
+
+    #[test]
+    fn linear_quadratic_regulator_no_solution() {
+        let sys = Ssd::new_from_slice(2, 1, 1, &[2., 0., 0., 0.5], &[0., 1.], &[1., 0.], &[0.]);
+        let q = DMatrix::identity(2, 2);
+        let r = DMatrix::from_element(1, 1, 1.);
+        assert_eq!(
+            ErrorKind::NoRiccatiSolution,
+            sys.dlqr(&q, &r).unwrap_err().kind()
+        );
+    }
+
     #[test]
     fn time_evolution() {
         let disc_sys =
@@ -419,6 +1009,65 @@ mod tests {
         assert_relative_eq!(0.25, last.state()[1], max_relative = 0.01);
     }
 
+    #[test]
+    fn resample() {
+        let sys = Ss::new_from_slice(2, 1, 1, &[-3., 0., -4., -4.], &[0., 1.], &[1., 1.], &[0.5])
+            .with_input_names(["u"]);
+        let disc = sys.discretize(0.1, Discretization::Tustin).unwrap();
+        let resampled = disc.resample(SampleTime(0.02)).unwrap();
+        let expected = sys.discretize(0.02, Discretization::Tustin).unwrap();
+        assert_eq!(Some(SampleTime(0.02)), resampled.sample_time());
+        assert_eq!(sys.input_names(), resampled.input_names());
+        assert_relative_eq!(expected.a, resampled.a, max_relative = 1e-10);
+        assert_relative_eq!(expected.b, resampled.b, max_relative = 1e-10);
+        assert_relative_eq!(expected.c, resampled.c, max_relative = 1e-10);
+        assert_relative_eq!(expected.d, resampled.d, max_relative = 1e-10);
+
+        // Unknown sampling period.
+        let sys = Ssd::new_from_slice(1, 1, 1, &[0.5], &[1.], &[1.], &[0.]);
+        assert!(sys.resample(SampleTime(0.1)).is_none());
+        // Pole at -1.
+        let sys = Ssd::new_from_slice(1, 1, 1, &[-1.], &[1.], &[1.], &[0.])
+            .with_sample_time(SampleTime(0.1));
+        assert!(sys.resample(SampleTime(0.2)).is_none());
+    }
+
+    #[test]
+    fn discretization_tustin_prewarp() {
+        use crate::{Tf, Tfz};
+        use num_complex::Complex;
+        let sys = Ss::new_from_slice(2, 1, 1, &[0., 1., -4., -0.4], &[0., 1.], &[4., 0.], &[0.]);
+        let ts = 0.2;
+        let w = 2.;
+        let disc_sys = sys
+            .discretize_tustin(Seconds(ts), Some(RadiansPerSecond(w)))
+            .unwrap();
+        let tf = Tf::<f64>::new_from_siso(&sys).unwrap();
+        let tfz = Tfz::<f64>::new_from_siso(&disc_sys).unwrap();
+        let g = tf.eval(&Complex::new(0., w));
+        let gz = tfz.eval(&Complex::from_polar(1., w * ts));
+        assert_relative_eq!(g.re, gz.re, epsilon = 1e-10);
+        assert_relative_eq!(g.im, gz.im, max_relative = 1e-10);
+
+        let expected = tf
+            .discretize_with_warp(Seconds(ts), RadiansPerSecond(w))
+            .eval(&Complex::from_polar(1., 0.5));
+        let actual = tfz.eval(&Complex::from_polar(1., 0.5));
+        assert_relative_eq!(expected.re, actual.re, max_relative = 1e-10);
+        assert_relative_eq!(expected.im, actual.im, max_relative = 1e-10);
+    }
+
+    #[test]
+    fn discretization_tustin_no_prewarp() {
+        let sys = Ss::new_from_slice(2, 1, 1, &[-3., 0., -4., -4.], &[0., 1.], &[1., 1.], &[0.]);
+        let expected = sys.discretize(0.1, Discretization::Tustin).unwrap();
+        let disc_sys = sys.discretize_tustin(Seconds(0.1), None).unwrap();
+        assert_eq!(expected.a, disc_sys.a);
+        assert_eq!(expected.b, disc_sys.b);
+        assert_eq!(expected.c, disc_sys.c);
+        assert_eq!(expected.d, disc_sys.d);
+    }
+
     #[test]
     fn discretization_tustin_fail() {
         let sys = Ss::new_from_slice(2, 1, 1, &[-3., 5., 4., -4.], &[0., 1.], &[1., 1.], &[0.]);
@@ -451,4 +1100,32 @@ mod tests {
         let last = evo.last().unwrap();
         assert_relative_eq!(0.25, last.state()[1], max_relative = 0.01);
     }
+
+    #[test]
+    fn discretization_zero_order_hold() {
+        // Double integrator
+        let sys = Ss::new_from_slice(2, 1, 1, &[0., 1., 0., 0.], &[0., 1.], &[1., 0.], &[0.]);
+        let st = 0.5;
+        let disc_sys = sys.discretize_zoh(Seconds(st));
+        let a = DMatrix::from_row_slice(2, 2, &[1., st, 0., 1.]);
+        let b = DMatrix::from_row_slice(2, 1, &[st * st / 2., st]);
+        assert_relative_eq!(a, disc_sys.a, epsilon = 1e-14);
+        assert_relative_eq!(b, disc_sys.b, epsilon = 1e-14);
+        assert_eq!(sys.c, disc_sys.c);
+        assert_eq!(sys.d, disc_sys.d);
+    }
+
+    #[test]
+    fn discretization_zero_order_hold_steady_state() {
+        let sys = Ss::new_from_slice(2, 1, 1, &[-3., 0., -4., -4.], &[0., 1.], &[1., 1.], &[0.]);
+        let disc_sys = sys.discretize_zoh(Seconds(0.1));
+        let evo = disc_sys.evolution_fn(100, |_| vec![1.], &[0., 0.]);
+        let last = evo.last().unwrap();
+        assert_relative_eq!(0.25, last.state()[1], max_relative = 1e-3);
+        // Poles are mapped through z = e^(s*st).
+        let mut poles: Vec<_> = disc_sys.poles().iter().map(|p| p.re).collect();
+        poles.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_relative_eq!((-0.4_f64).exp(), poles[0], max_relative = 1e-12);
+        assert_relative_eq!((-0.3_f64).exp(), poles[1], max_relative = 1e-12);
+    }
 }
@@ -0,0 +1,504 @@
+//! # Fixed size state-space representation
+//!
+//! Continuous time linear system whose dimensions are known at compile time.
+//! The matrices are statically sized `nalgebra` matrices, whose dimensions
+//! are type level integers as `U1`, `U2`, `U3`, and the time evolution does
+//! not allocate memory, so small systems can be simulated on embedded targets
+//! and inside hot loops.
+//!
+//! The time evolution is performed with Runge-Kutta methods of order 2 and 4.
+
+use nalgebra::{
+    allocator::Allocator, storage::Owned, DMatrix, DefaultAllocator, DimName, Matrix, Scalar, U1,
+};
+use num_traits::Float;
+
+use std::ops::{AddAssign, MulAssign};
+
+use crate::{linear_system::continuous::Ss, units::Seconds};
+
+/// Matrix whose dimensions are known at compile time.
+pub type FixedMatrix<T, R, C> = Matrix<T, R, C, Owned<T, R, C>>;
+
+/// Allocator of the matrices and of the vectors of a fixed size system with
+/// `NX` states, `NU` inputs and `NY` outputs.
+///
+/// It is implemented by `nalgebra::DefaultAllocator` for all the type level
+/// dimensions.
+pub trait FixedAllocator<T: Scalar, NX: DimName, NU: DimName, NY: DimName>:
+    Allocator<T, NX, NX>
+    + Allocator<T, NX, NU>
+    + Allocator<T, NY, NX>
+    + Allocator<T, NY, NU>
+    + Allocator<T, NX>
+    + Allocator<T, NU>
+    + Allocator<T, NY>
+{
+}
+
+impl<T, NX, NU, NY> FixedAllocator<T, NX, NU, NY> for DefaultAllocator
+where
+    T: Scalar,
+    NX: DimName,
+    NU: DimName,
+    NY: DimName,
+    DefaultAllocator: Allocator<T, NX, NX>
+        + Allocator<T, NX, NU>
+        + Allocator<T, NY, NX>
+        + Allocator<T, NY, NU>
+        + Allocator<T, NX>
+        + Allocator<T, NU>
+        + Allocator<T, NY>,
+{
+}
+
+/// State-space representation of a continuous time linear system with `NX`
+/// states, `NU` inputs and `NY` outputs.
+///
+/// ```text
+/// xdot(t) = A * x(t) + B * u(t)
+/// y(t)    = C * x(t) + D * u(t)
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct SsN<T, NX, NU, NY>
+where
+    T: Scalar,
+    NX: DimName,
+    NU: DimName,
+    NY: DimName,
+    DefaultAllocator: FixedAllocator<T, NX, NU, NY>,
+{
+    /// A matrix
+    a: FixedMatrix<T, NX, NX>,
+    /// B matrix
+    b: FixedMatrix<T, NX, NU>,
+    /// C matrix
+    c: FixedMatrix<T, NY, NX>,
+    /// D matrix
+    d: FixedMatrix<T, NY, NU>,
+}
+
+impl<T, NX, NU, NY> SsN<T, NX, NU, NY>
+where
+    T: AddAssign + Float + MulAssign + Scalar,
+    NX: DimName,
+    NU: DimName,
+    NY: DimName,
+    DefaultAllocator: FixedAllocator<T, NX, NU, NY>,
+{
+    /// Create a new fixed size state-space representation
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - A matrix (NXxNX)
+    /// * `b` - B matrix (NXxNU)
+    /// * `c` - C matrix (NYxNX)
+    /// * `d` - D matrix (NYxNU)
+    ///
+    /// # Example
+    /// ```
+    /// use au::{
+    ///     linear_system::fixed::SsN,
+    ///     nalgebra::{Matrix1, Matrix2, RowVector2, Vector1, Vector2},
+    /// };
+    /// let sys = SsN::new(
+    ///     Matrix2::new(0., 1., -2., -3.),
+    ///     Vector2::new(0., 1.),
+    ///     RowVector2::new(1., 0.),
+    ///     Matrix1::new(0.),
+    /// );
+    /// let y = sys.output(&Vector2::new(1., 2.), &Vector1::new(0.));
+    /// assert_eq!(Vector1::new(1.), y);
+    /// ```
+    #[must_use]
+    pub fn new(
+        a: FixedMatrix<T, NX, NX>,
+        b: FixedMatrix<T, NX, NU>,
+        c: FixedMatrix<T, NY, NX>,
+        d: FixedMatrix<T, NY, NU>,
+    ) -> Self {
+        Self { a, b, c, d }
+    }
+
+    /// Create a fixed size state-space representation from a state-space
+    /// representation.
+    ///
+    /// Returns `None` if the dimensions of the system are different from the
+    /// fixed size ones.
+    ///
+    /// # Arguments
+    ///
+    /// * `sys` - state-space representation
+    ///
+    /// # Example
+    /// ```
+    /// use au::{linear_system::fixed::SsN, nalgebra::{U1, U2}, Ss};
+    /// let sys = Ss::new_from_slice(1, 1, 1, &[-1.], &[1.], &[1.], &[0.]);
+    /// assert!(SsN::<_, U1, U1, U1>::from_ss(&sys).is_some());
+    /// assert!(SsN::<_, U2, U1, U1>::from_ss(&sys).is_none());
+    /// ```
+    #[must_use]
+    pub fn from_ss(sys: &Ss<T>) -> Option<Self> {
+        let dim = sys.dim();
+        if (dim.states(), dim.inputs(), dim.outputs()) != (NX::dim(), NU::dim(), NY::dim()) {
+            return None;
+        }
+        Some(Self {
+            a: FixedMatrix::<T, NX, NX>::from_fn(|i, j| sys.a[(i, j)]),
+            b: FixedMatrix::<T, NX, NU>::from_fn(|i, j| sys.b[(i, j)]),
+            c: FixedMatrix::<T, NY, NX>::from_fn(|i, j| sys.c[(i, j)]),
+            d: FixedMatrix::<T, NY, NU>::from_fn(|i, j| sys.d[(i, j)]),
+        })
+    }
+
+    /// Convert the fixed size system into a state-space representation.
+    #[must_use]
+    pub fn to_ss(&self) -> Ss<T> {
+        let (n, m, p) = (NX::dim(), NU::dim(), NY::dim());
+        Ss::new_from_matrices(
+            DMatrix::from_column_slice(n, n, self.a.as_slice()),
+            DMatrix::from_column_slice(n, m, self.b.as_slice()),
+            DMatrix::from_column_slice(p, n, self.c.as_slice()),
+            DMatrix::from_column_slice(p, m, self.d.as_slice()),
+        )
+    }
+
+    /// Get the states matrix (A) of the system.
+    #[must_use]
+    pub fn a(&self) -> &FixedMatrix<T, NX, NX> {
+        &self.a
+    }
+
+    /// Get the input matrix (B) of the system.
+    #[must_use]
+    pub fn b(&self) -> &FixedMatrix<T, NX, NU> {
+        &self.b
+    }
+
+    /// Get the output matrix (C) of the system.
+    #[must_use]
+    pub fn c(&self) -> &FixedMatrix<T, NY, NX> {
+        &self.c
+    }
+
+    /// Get the direct transmission matrix (D) of the system.
+    #[must_use]
+    pub fn d(&self) -> &FixedMatrix<T, NY, NU> {
+        &self.d
+    }
+
+    /// Derivative of the state, `A*x + B*u`.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - state
+    /// * `u` - input
+    #[must_use]
+    pub fn derivative(
+        &self,
+        x: &FixedMatrix<T, NX, U1>,
+        u: &FixedMatrix<T, NU, U1>,
+    ) -> FixedMatrix<T, NX, U1> {
+        &self.a * x + &self.b * u
+    }
+
+    /// Output of the system, `C*x + D*u`.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - state
+    /// * `u` - input
+    #[must_use]
+    pub fn output(
+        &self,
+        x: &FixedMatrix<T, NX, U1>,
+        u: &FixedMatrix<T, NU, U1>,
+    ) -> FixedMatrix<T, NY, U1> {
+        &self.c * x + &self.d * u
+    }
+
+    /// Time evolution for the given input, using Runge-Kutta second order method
+    ///
+    /// # Arguments
+    ///
+    /// * `u` - input function of time
+    /// * `x0` - initial state
+    /// * `h` - integration time interval
+    /// * `n` - integration steps
+    pub fn rk2<F>(
+        &self,
+        u: F,
+        x0: FixedMatrix<T, NX, U1>,
+        h: Seconds<T>,
+        n: usize,
+    ) -> RkN<'_, F, T, NX, NU, NY>
+    where
+        F: Fn(Seconds<T>) -> FixedMatrix<T, NU, U1>,
+    {
+        RkN::new(self, u, x0, h, n, false)
+    }
+
+    /// Time evolution for the given input, using Runge-Kutta fourth order method
+    ///
+    /// # Arguments
+    ///
+    /// * `u` - input function of time
+    /// * `x0` - initial state
+    /// * `h` - integration time interval
+    /// * `n` - integration steps
+    ///
+    /// # Example
+    /// ```
+    /// use au::{linear_system::fixed::SsN, nalgebra::{Matrix1, Vector1}, Seconds};
+    /// let sys = SsN::new(Matrix1::new(-1.), Matrix1::new(1.), Matrix1::new(1.), Matrix1::new(0.));
+    /// let evo = sys.rk4(|_| Vector1::new(1.), Vector1::new(0.), Seconds(0.1), 100);
+    /// let last = evo.last().unwrap();
+    /// assert!((last.output()[0] - 1_f64).abs() < 1e-4);
+    /// ```
+    pub fn rk4<F>(
+        &self,
+        u: F,
+        x0: FixedMatrix<T, NX, U1>,
+        h: Seconds<T>,
+        n: usize,
+    ) -> RkN<'_, F, T, NX, NU, NY>
+    where
+        F: Fn(Seconds<T>) -> FixedMatrix<T, NU, U1>,
+    {
+        RkN::new(self, u, x0, h, n, true)
+    }
+}
+
+/// Struct for the time evolution of a fixed size linear system
+#[derive(Clone, Debug)]
+pub struct RkN<'a, F, T, NX, NU, NY>
+where
+    T: Float + Scalar,
+    NX: DimName,
+    NU: DimName,
+    NY: DimName,
+    DefaultAllocator: FixedAllocator<T, NX, NU, NY>,
+{
+    /// Linear system
+    sys: &'a SsN<T, NX, NU, NY>,
+    /// Input function
+    input: F,
+    /// State vector
+    state: FixedMatrix<T, NX, U1>,
+    /// Interval
+    h: Seconds<T>,
+    /// Number of steps
+    n: usize,
+    /// Index
+    index: usize,
+    /// Fourth order method if true, second order otherwise
+    rk4: bool,
+}
+
+impl<'a, F, T, NX, NU, NY> RkN<'a, F, T, NX, NU, NY>
+where
+    F: Fn(Seconds<T>) -> FixedMatrix<T, NU, U1>,
+    T: AddAssign + Float + MulAssign + Scalar,
+    NX: DimName,
+    NU: DimName,
+    NY: DimName,
+    DefaultAllocator: FixedAllocator<T, NX, NU, NY>,
+{
+    /// Create the solver for a Runge-Kutta method.
+    fn new(
+        sys: &'a SsN<T, NX, NU, NY>,
+        u: F,
+        x0: FixedMatrix<T, NX, U1>,
+        h: Seconds<T>,
+        n: usize,
+        rk4: bool,
+    ) -> Self {
+        Self {
+            sys,
+            input: u,
+            state: x0,
+            h,
+            n,
+            index: 0,
+            rk4,
+        }
+    }
+
+    /// Runge-Kutta order 2 method.
+    fn rk2_step(&self, t: T) -> (FixedMatrix<T, NX, U1>, FixedMatrix<T, NU, U1>) {
+        let h = self.h.0;
+        let half = T::from(0.5).unwrap();
+        let u = (self.input)(Seconds(t));
+        let uh = (self.input)(Seconds(t + h));
+        let k1 = self.sys.derivative(&self.state, &u);
+        let k2 = self.sys.derivative(&(&self.state + &k1 * h), &uh);
+        let x = &self.state + (k1 + k2) * (half * h);
+        (x, uh)
+    }
+
+    /// Runge-Kutta order 4 method.
+    #[allow(clippy::similar_names)]
+    fn rk4_step(&self, t: T) -> (FixedMatrix<T, NX, U1>, FixedMatrix<T, NU, U1>) {
+        let h = self.h.0;
+        let half_h = T::from(0.5).unwrap() * h;
+        let two = T::from(2.).unwrap();
+        let u = (self.input)(Seconds(t));
+        let u_mid = (self.input)(Seconds(t + half_h));
+        let u_end = (self.input)(Seconds(t + h));
+        let k1 = self.sys.derivative(&self.state, &u);
+        let k2 = self.sys.derivative(&(&self.state + &k1 * half_h), &u_mid);
+        let k3 = self.sys.derivative(&(&self.state + &k2 * half_h), &u_mid);
+        let k4 = self.sys.derivative(&(&self.state + &k3 * h), &u_end);
+        let k = k1 + k4 + (k2 + k3) * two;
+        let x = &self.state + k * (h / T::from(6.).unwrap());
+        (x, u_end)
+    }
+}
+
+/// Implementation of the Iterator trait for the `RkN` struct
+impl<'a, F, T, NX, NU, NY> Iterator for RkN<'a, F, T, NX, NU, NY>
+where
+    F: Fn(Seconds<T>) -> FixedMatrix<T, NU, U1>,
+    T: AddAssign + Float + MulAssign + Scalar,
+    NX: DimName,
+    NU: DimName,
+    NY: DimName,
+    DefaultAllocator: FixedAllocator<T, NX, NU, NY>,
+{
+    type Item = StepN<T, NX, NY>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index > self.n {
+            return None;
+        }
+        let time = T::from(self.index)? * self.h.0;
+        let u = if self.index == 0 {
+            (self.input)(Seconds(time))
+        } else {
+            let start = T::from(self.index - 1)? * self.h.0;
+            let (x, u) = if self.rk4 {
+                self.rk4_step(start)
+            } else {
+                self.rk2_step(start)
+            };
+            self.state = x;
+            u
+        };
+        self.index += 1;
+        Some(StepN {
+            time: Seconds(time),
+            output: self.sys.output(&self.state, &u),
+            state: self.state.clone(),
+        })
+    }
+}
+
+/// Struct to hold the data of the fixed size linear system time evolution
+#[derive(Clone, Debug, PartialEq)]
+pub struct StepN<T, NX, NY>
+where
+    T: Float + Scalar,
+    NX: DimName,
+    NY: DimName,
+    DefaultAllocator: Allocator<T, NX> + Allocator<T, NY>,
+{
+    /// Time of the current step
+    time: Seconds<T>,
+    /// Current state
+    state: FixedMatrix<T, NX, U1>,
+    /// Current output
+    output: FixedMatrix<T, NY, U1>,
+}
+
+impl<T, NX, NY> StepN<T, NX, NY>
+where
+    T: Float + Scalar,
+    NX: DimName,
+    NY: DimName,
+    DefaultAllocator: Allocator<T, NX> + Allocator<T, NY>,
+{
+    /// Get the time of the current step
+    #[must_use]
+    pub fn time(&self) -> Seconds<T> {
+        self.time
+    }
+
+    /// Get the current state of the system
+    #[must_use]
+    pub fn state(&self) -> &FixedMatrix<T, NX, U1> {
+        &self.state
+    }
+
+    /// Get the current output of the system
+    #[must_use]
+    pub fn output(&self) -> &FixedMatrix<T, NY, U1> {
+        &self.output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nalgebra::{Matrix1, Matrix2, RowVector2, Vector1, Vector2, U2};
+
+    #[test]
+    fn conversion() {
+        let sys = Ss::new_from_slice(
+            2,
+            1,
+            2,
+            &[0., 1., -2., -3.],
+            &[0., 1.],
+            &[1., 0., 0., 1.],
+            &[0., 0.5],
+        );
+        let fixed = SsN::<_, U2, U1, U2>::from_ss(&sys).unwrap();
+        assert_eq!(&Matrix2::new(0., 1., -2., -3.), fixed.a());
+        assert_eq!(&Vector2::new(0., 1.), fixed.b());
+        assert_eq!(&Matrix2::identity(), fixed.c());
+        assert_eq!(&Vector2::new(0., 0.5), fixed.d());
+        assert_eq!(sys, fixed.to_ss());
+        assert!(SsN::<_, U2, U2, U2>::from_ss(&sys).is_none());
+    }
+
+    #[test]
+    fn derivative_output() {
+        let sys = SsN::new(
+            Matrix2::new(0., 1., -2., -3.),
+            Vector2::new(0., 1.),
+            RowVector2::new(1., 1.),
+            Matrix1::new(2.),
+        );
+        let (x, u) = (Vector2::new(1., 2.), Vector1::new(1.));
+        assert_eq!(Vector2::new(2., -7.), sys.derivative(&x, &u));
+        assert_eq!(Vector1::new(5.), sys.output(&x, &u));
+    }
+
+    #[test]
+    fn evolution_matches_dynamic_solver() {
+        let sys = SsN::new(
+            Matrix2::new(0., 1., -2., -3.),
+            Vector2::new(0., 1.),
+            RowVector2::new(1., 0.),
+            Matrix1::new(0.1),
+        );
+        let dynamic = sys.to_ss();
+        let h = Seconds(0.05);
+        let x0 = Vector2::new(1., 0.);
+        let fixed_rk4 = sys.rk4(|t| Vector1::new(t.0.cos()), x0, h, 100);
+        let dyn_rk4 = dynamic.rk4(|t| vec![t.0.cos()], &[1., 0.], h, 100);
+        let mut count = 0;
+        for (f, d) in fixed_rk4.zip(dyn_rk4) {
+            assert_eq!(d.time(), f.time());
+            assert_relative_eq!(d.state()[0], f.state()[0], max_relative = 1e-12);
+            assert_relative_eq!(d.output()[0], f.output()[0], max_relative = 1e-12);
+            count += 1;
+        }
+        assert_eq!(101, count);
+        let fixed_rk2 = sys.rk2(|_| Vector1::new(1.), Vector2::zeros(), h, 50);
+        let dyn_rk2 = dynamic.rk2(|_| vec![1.], &[0., 0.], h, 50);
+        for (f, d) in fixed_rk2.zip(dyn_rk2) {
+            assert_relative_eq!(d.state()[1], f.state()[1], max_relative = 1e-12);
+        }
+    }
+}
@@ -0,0 +1,495 @@
+//! # Linear algebra
+//!
+//! Matrix equations used in the analysis and synthesis of linear systems
+//! * continuous time Lyapunov equation
+//! * continuous time algebraic Riccati equation
+//! * discrete time algebraic Riccati equation
+//! * matrix exponential
+
+use nalgebra::{Cholesky, ComplexField, DMatrix, DVector, RealField};
+use num_traits::Float;
+
+/// Solve the continuous time Lyapunov equation
+/// ```text
+/// A*X + X*A' + Q = 0
+/// ```
+///
+/// The equation is solved through the Kronecker product representation
+/// `(I⊗A + A⊗I) vec(X) = -vec(Q)`, it is intended for systems with a
+/// moderate number of states.
+///
+/// # Arguments
+///
+/// * `a` - A matrix (nxn)
+/// * `q` - Q matrix (nxn)
+///
+/// Returns `None` if the equation has no unique solution, i.e. `A` has two
+/// eigenvalues whose sum is zero.
+///
+/// # Panics
+///
+/// Panics if the matrices are not square or have different dimensions.
+///
+/// # Example
+/// ```
+/// use au::linear_system::linalg::lyap;
+/// use au::nalgebra::DMatrix;
+/// let a = DMatrix::from_row_slice(2, 2, &[-1., 0., 0., -2.]);
+/// let q = DMatrix::identity(2, 2);
+/// let x = lyap(&a, &q).unwrap();
+/// assert!((&a * &x + &x * a.transpose() + q).norm() < 1e-12);
+/// ```
+#[must_use]
+pub fn lyap<T: ComplexField + Float + RealField>(
+    a: &DMatrix<T>,
+    q: &DMatrix<T>,
+) -> Option<DMatrix<T>> {
+    assert!(a.is_square(), "A matrix must be square");
+    assert_eq!(
+        a.shape(),
+        q.shape(),
+        "A and Q must have the same dimensions"
+    );
+    let n = a.nrows();
+    let eye = DMatrix::<T>::identity(n, n);
+    let k = eye.kronecker(a) + a.kronecker(&eye);
+    let rhs = -DVector::from_column_slice(q.as_slice());
+    let x = k.lu().solve(&rhs)?;
+    let x = DMatrix::from_column_slice(n, n, x.as_slice());
+    // Enforce symmetry of the solution when Q is symmetric.
+    if q == &q.transpose() {
+        Some((&x + x.transpose()) * T::from(0.5_f32).unwrap())
+    } else {
+        Some(x)
+    }
+}
+
+/// Maximum number of iterations of the matrix sign function.
+const MAX_SIGN_ITERATIONS: usize = 100;
+
+/// Solve the continuous time algebraic Riccati equation
+/// ```text
+/// A'*X + X*A - X*B*R^-1*B'*X + Q = 0
+/// ```
+///
+/// The stabilizing solution is calculated from the stable invariant subspace
+/// of the Hamiltonian matrix, obtained through the matrix sign function
+/// (scaled Newton iteration).
+///
+/// # Arguments
+///
+/// * `a` - A matrix (nxn)
+/// * `b` - B matrix (nxm)
+/// * `q` - Q matrix (nxn), symmetric positive semi-definite
+/// * `r` - R matrix (mxm), symmetric positive definite
+///
+/// Returns `None` if `R` is not positive definite or the Hamiltonian matrix
+/// has eigenvalues on the imaginary axis, i.e. the stabilizing solution does
+/// not exist.
+///
+/// # Panics
+///
+/// Panics if the matrix dimensions do not match.
+///
+/// # Example
+/// ```
+/// use au::linear_system::linalg::care;
+/// use au::nalgebra::DMatrix;
+/// let a = DMatrix::from_element(1, 1, 0_f64);
+/// let one = DMatrix::from_element(1, 1, 1.);
+/// let x = care(&a, &one, &one, &one).unwrap();
+/// assert!((x[(0, 0)] - 1.).abs() < 1e-12);
+/// ```
+#[must_use]
+pub fn care<T: ComplexField + Float + RealField>(
+    a: &DMatrix<T>,
+    b: &DMatrix<T>,
+    q: &DMatrix<T>,
+    r: &DMatrix<T>,
+) -> Option<DMatrix<T>> {
+    assert!(a.is_square(), "A matrix must be square");
+    assert_eq!(a.nrows(), b.nrows(), "A and B must have the same rows");
+    assert_eq!(
+        a.shape(),
+        q.shape(),
+        "A and Q must have the same dimensions"
+    );
+    assert_eq!(
+        (b.ncols(), b.ncols()),
+        r.shape(),
+        "R must be square with the dimension of the inputs"
+    );
+    let n = a.nrows();
+    // G = B*R^-1*B'
+    let g = b * Cholesky::new(r.clone())?.solve(&b.transpose());
+    // Hamiltonian matrix
+    //     ┌         ┐
+    // H = │  A  -G  │
+    //     │ -Q  -A' │
+    //     └         ┘
+    let mut z = DMatrix::<T>::zeros(2 * n, 2 * n);
+    z.slice_mut((0, 0), (n, n)).copy_from(a);
+    z.slice_mut((0, n), (n, n)).copy_from(&-g);
+    z.slice_mut((n, 0), (n, n)).copy_from(&-q);
+    z.slice_mut((n, n), (n, n)).copy_from(&-a.transpose());
+
+    // Matrix sign function with determinant scaling.
+    let half = T::from(0.5_f32).unwrap();
+    let two_n = T::from(2 * n).unwrap_or_else(T::one);
+    let tol = Float::sqrt(T::epsilon());
+    let mut converged = false;
+    for _ in 0..MAX_SIGN_ITERATIONS {
+        let lu = z.clone().lu();
+        let log_det = lu
+            .u()
+            .diagonal()
+            .iter()
+            .fold(T::zero(), |acc, &d| acc + Float::ln(Float::abs(d)));
+        let z_inv = lu.try_inverse()?;
+        let c = if converged {
+            T::one()
+        } else {
+            Float::exp(-log_det / two_n)
+        };
+        let z_next = (&z * c + z_inv / c) * half;
+        let delta = (&z_next - &z).norm();
+        let scale = z_next.norm();
+        z = z_next;
+        if !scale.is_finite() {
+            return None;
+        }
+        if converged {
+            break;
+        }
+        // Perform a last unscaled iteration after convergence.
+        converged = delta <= tol * scale;
+    }
+    if !converged {
+        return None;
+    }
+
+    // The stable invariant subspace is the null space of (sign(H) + I):
+    // [W12; W22 + I] * X = -[W11 + I; W21]
+    let eye = DMatrix::<T>::identity(n, n);
+    let mut lhs = DMatrix::<T>::zeros(2 * n, n);
+    lhs.slice_mut((0, 0), (n, n))
+        .copy_from(&z.slice((0, n), (n, n)));
+    lhs.slice_mut((n, 0), (n, n))
+        .copy_from(&(z.slice((n, n), (n, n)) + &eye));
+    let mut rhs = DMatrix::<T>::zeros(2 * n, n);
+    rhs.slice_mut((0, 0), (n, n))
+        .copy_from(&-(z.slice((0, 0), (n, n)) + &eye));
+    rhs.slice_mut((n, 0), (n, n))
+        .copy_from(&-z.slice((n, 0), (n, n)));
+    let svd = lhs.svd(true, true);
+    let sv_max = svd.singular_values.max();
+    let sv_min = svd.singular_values.min();
+    // The stable subspace is not complementary to [0; I], the stabilizing
+    // solution does not exist.
+    if sv_min <= tol * sv_max {
+        return None;
+    }
+    let x = svd.solve(&rhs, T::epsilon()).ok()?;
+    // Enforce symmetry of the solution.
+    Some((&x + x.transpose()) * half)
+}
+
+/// Maximum number of iterations of the doubling algorithm.
+const MAX_DOUBLING_ITERATIONS: usize = 100;
+
+/// Solve the discrete time algebraic Riccati equation
+/// ```text
+/// X = A'*X*A - A'*X*B*(R + B'*X*B)^-1*B'*X*A + Q
+/// ```
+///
+/// The stabilizing solution is calculated with the structured doubling
+/// algorithm, that converges quadratically.
+///
+/// # Arguments
+///
+/// * `a` - A matrix (nxn)
+/// * `b` - B matrix (nxm)
+/// * `q` - Q matrix (nxn), symmetric positive semi-definite
+/// * `r` - R matrix (mxm), symmetric positive definite
+///
+/// Returns `None` if `R` is not positive definite or the algorithm does not
+/// converge, i.e. the stabilizing solution does not exist.
+///
+/// # Panics
+///
+/// Panics if the matrix dimensions do not match.
+///
+/// # Example
+/// ```
+/// use au::linear_system::linalg::dare;
+/// use au::nalgebra::DMatrix;
+/// let one = DMatrix::from_element(1, 1, 1.);
+/// let x = dare(&one, &one, &one, &one).unwrap();
+/// assert!((x[(0, 0)] - (1. + 5_f64.sqrt()) / 2.).abs() < 1e-12);
+/// ```
+#[must_use]
+pub fn dare<T: ComplexField + Float + RealField>(
+    a: &DMatrix<T>,
+    b: &DMatrix<T>,
+    q: &DMatrix<T>,
+    r: &DMatrix<T>,
+) -> Option<DMatrix<T>> {
+    assert!(a.is_square(), "A matrix must be square");
+    assert_eq!(a.nrows(), b.nrows(), "A and B must have the same rows");
+    assert_eq!(
+        a.shape(),
+        q.shape(),
+        "A and Q must have the same dimensions"
+    );
+    assert_eq!(
+        (b.ncols(), b.ncols()),
+        r.shape(),
+        "R must be square with the dimension of the inputs"
+    );
+    let n = a.nrows();
+    let eye = DMatrix::<T>::identity(n, n);
+    // G = B*R^-1*B'
+    let r_inv_bt = Cholesky::new(r.clone())?.solve(&b.transpose());
+    let mut ak = a.clone();
+    let mut gk = b * r_inv_bt;
+    let mut hk = q.clone();
+    let tol = T::epsilon() * T::from(100.).unwrap_or_else(T::one);
+    for _ in 0..MAX_DOUBLING_ITERATIONS {
+        // W = I + G*H
+        let w = (&eye + &gk * &hk).lu();
+        // W^-1*A, W^-1*G
+        let w_inv_a = w.solve(&ak)?;
+        let w_inv_g = w.solve(&gk)?;
+        let h_next = &hk + ak.tr_mul(&hk) * &w_inv_a;
+        let g_next = &gk + &ak * w_inv_g * ak.transpose();
+        let a_next = &ak * w_inv_a;
+        let delta = (&h_next - &hk).norm();
+        let scale = h_next.norm();
+        hk = h_next;
+        gk = g_next;
+        ak = a_next;
+        if !scale.is_finite() {
+            return None;
+        }
+        if delta <= tol * scale {
+            // Enforce symmetry of the solution.
+            return Some((&hk + hk.transpose()) * T::from(0.5_f32).unwrap());
+        }
+    }
+    None
+}
+
+/// Degree of the diagonal Padé approximant of the matrix exponential.
+const EXPM_PADE_DEGREE: usize = 6;
+
+/// Calculate the matrix exponential `e^A`.
+///
+/// The exponential is calculated with the scaling and squaring method: the
+/// matrix is scaled by a power of two so that its norm is not greater than
+/// one half,
+/// the exponential of the scaled matrix is approximated with a diagonal
+/// Padé approximant, and the result is squared back.
+///
+/// # Arguments
+///
+/// * `a` - A matrix (nxn)
+///
+/// # Panics
+///
+/// Panics if the matrix is not square or has elements that are not finite.
+///
+/// # Example
+/// ```
+/// use au::linear_system::linalg::expm;
+/// use au::nalgebra::DMatrix;
+/// let a = DMatrix::from_row_slice(2, 2, &[0., 1., 0., 0.]);
+/// let e = expm(&a);
+/// assert!((e - DMatrix::from_row_slice(2, 2, &[1., 1., 0., 1.])).norm() < 1e-12);
+/// ```
+#[must_use]
+pub fn expm<T: ComplexField + Float + RealField>(a: &DMatrix<T>) -> DMatrix<T> {
+    assert!(a.is_square(), "A matrix must be square");
+    assert!(
+        a.iter().all(|&x| Float::is_finite(x)),
+        "Matrix elements must be finite"
+    );
+    let n = a.nrows();
+    let eye = DMatrix::<T>::identity(n, n);
+    // Infinity norm of the matrix.
+    let norm = a
+        .row_iter()
+        .map(|r| r.iter().fold(T::zero(), |acc, &x| acc + Float::abs(x)))
+        .fold(T::zero(), Float::max);
+    if norm.is_zero() {
+        return eye;
+    }
+
+    // Scale the matrix so that its norm is not greater than 1/2.
+    let two = T::one() + T::one();
+    let squarings = Float::max(T::zero(), Float::floor(Float::log2(norm)) + two);
+    let squarings = squarings
+        .to_i32()
+        .expect("Number of squarings of a finite norm is representable");
+    let a_scaled = a / Float::powi(two, squarings);
+
+    // Padé approximant N(A)/D(A).
+    let q = EXPM_PADE_DEGREE;
+    let mut c = T::one() / two;
+    let mut x = a_scaled.clone();
+    let mut num = &eye + &a_scaled * c;
+    let mut den = &eye - &a_scaled * c;
+    for k in 2..=q {
+        c *= T::from(q - k + 1).unwrap() / T::from(k * (2 * q - k + 1)).unwrap();
+        x = &a_scaled * x;
+        num += &x * c;
+        if k % 2 == 0 {
+            den += &x * c;
+        } else {
+            den -= &x * c;
+        }
+    }
+    let mut e = den
+        .lu()
+        .solve(&num)
+        .expect("Padé denominator of a scaled matrix is invertible");
+
+    for _ in 0..squarings {
+        e = &e * &e;
+    }
+    e
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lyapunov_continuous() {
+        let a = DMatrix::from_row_slice(2, 2, &[0., 1., -2., -3.]);
+        let q = DMatrix::from_row_slice(2, 2, &[1., 0., 0., 2.]);
+        let x = lyap(&a, &q).unwrap();
+        let res = &a * &x + &x * a.transpose() + &q;
+        assert_relative_eq!(0., res.norm(), epsilon = 1e-12);
+        assert_eq!(x, x.transpose());
+    }
+
+    #[test]
+    fn lyapunov_continuous_non_symmetric() {
+        let a = DMatrix::from_row_slice(2, 2, &[-1., 2., 0., -3.]);
+        let q = DMatrix::from_row_slice(2, 2, &[1., 4., 0., 2.]);
+        let x = lyap(&a, &q).unwrap();
+        let res = &a * &x + &x * a.transpose() + &q;
+        assert_relative_eq!(0., res.norm(), epsilon = 1e-12);
+    }
+
+    #[test]
+    fn riccati_continuous() {
+        // Double integrator
+        let a = DMatrix::from_row_slice(2, 2, &[0., 1., 0., 0.]);
+        let b = DMatrix::from_row_slice(2, 1, &[0., 1.]);
+        let q = DMatrix::identity(2, 2);
+        let r = DMatrix::from_element(1, 1, 1.);
+        let x = care(&a, &b, &q, &r).unwrap();
+        let sq3 = 3_f64.sqrt();
+        let expected = DMatrix::from_row_slice(2, 2, &[sq3, 1., 1., sq3]);
+        assert_relative_eq!(expected, x, max_relative = 1e-10);
+    }
+
+    #[test]
+    fn riccati_continuous_residual() {
+        let a = DMatrix::from_row_slice(3, 3, &[-1., 2., 0., 0., 1., 1., 1., 0., -3.]);
+        let b = DMatrix::from_row_slice(3, 2, &[1., 0., 0., 1., 1., 1.]);
+        let q = DMatrix::from_row_slice(3, 3, &[2., 0., 0., 0., 1., 0., 0., 0., 1.]);
+        let r = DMatrix::from_row_slice(2, 2, &[1., 0., 0., 3.]);
+        let x = care(&a, &b, &q, &r).unwrap();
+        let g = &b * r.clone().try_inverse().unwrap() * b.transpose();
+        let res = a.tr_mul(&x) + &x * &a - &x * g * &x + &q;
+        assert_relative_eq!(0., res.norm(), epsilon = 1e-9);
+    }
+
+    #[test]
+    fn riccati_continuous_no_solution() {
+        // The unstable mode is not stabilizable.
+        let a = DMatrix::from_row_slice(2, 2, &[1., 0., 0., -1.]);
+        let b = DMatrix::from_row_slice(2, 1, &[0., 1.]);
+        let q = DMatrix::identity(2, 2);
+        let r = DMatrix::from_element(1, 1, 1.);
+        assert!(care(&a, &b, &q, &r).is_none());
+    }
+
+    #[test]
+    fn riccati_discrete() {
+        let a = DMatrix::from_row_slice(2, 2, &[1., 0.1, 0., 1.]);
+        let b = DMatrix::from_row_slice(2, 1, &[0.005, 0.1]);
+        let q = DMatrix::from_row_slice(2, 2, &[1., 0., 0., 0.5]);
+        let r = DMatrix::from_element(1, 1, 2.);
+        let x = dare(&a, &b, &q, &r).unwrap();
+        let btxb = &r + b.tr_mul(&x) * &b;
+        let res = a.tr_mul(&x) * &a
+            - a.tr_mul(&x) * &b * btxb.try_inverse().unwrap() * b.tr_mul(&x) * &a
+            + &q
+            - &x;
+        assert_relative_eq!(0., res.norm(), epsilon = 1e-9);
+        assert_eq!(x, x.transpose());
+    }
+
+    #[test]
+    fn riccati_discrete_no_solution() {
+        let a = DMatrix::from_row_slice(2, 2, &[2., 0., 0., 0.5]);
+        // The unstable mode is not stabilizable.
+        let b = DMatrix::from_row_slice(2, 1, &[0., 1.]);
+        let q = DMatrix::identity(2, 2);
+        let r = DMatrix::from_element(1, 1, 1.);
+        assert!(dare(&a, &b, &q, &r).is_none());
+
+        // R is not positive definite.
+        let b = DMatrix::from_row_slice(2, 1, &[1., 1.]);
+        let r = DMatrix::from_element(1, 1, -1.);
+        assert!(dare(&a, &b, &q, &r).is_none());
+    }
+
+    #[test]
+    fn matrix_exponential() {
+        let a = DMatrix::from_row_slice(2, 2, &[-1., 0., 0., 2.]);
+        let e = expm(&a);
+        let expected = DMatrix::from_row_slice(2, 2, &[(-1_f64).exp(), 0., 0., 2_f64.exp()]);
+        assert_relative_eq!(expected, e, max_relative = 1e-14);
+
+        let zero = DMatrix::<f64>::zeros(3, 3);
+        assert_eq!(DMatrix::identity(3, 3), expm(&zero));
+    }
+
+    #[test]
+    fn matrix_exponential_rotation() {
+        // Large norm requires scaling and squaring.
+        let w = 25.;
+        let a = DMatrix::from_row_slice(2, 2, &[0., w, -w, 0.]);
+        let e = expm(&a);
+        let (s, c) = f64::sin_cos(w);
+        let expected = DMatrix::from_row_slice(2, 2, &[c, s, -s, c]);
+        assert_relative_eq!(expected, e, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn matrix_exponential_nilpotent() {
+        let a = DMatrix::from_row_slice(3, 3, &[0., 1., 0., 0., 0., 1., 0., 0., 0.]);
+        let e = expm(&a);
+        let expected = DMatrix::from_row_slice(3, 3, &[1., 1., 0.5, 0., 1., 1., 0., 0., 1.]);
+        assert_relative_eq!(expected, e, epsilon = 1e-14);
+    }
+
+    #[test]
+    #[should_panic]
+    fn matrix_exponential_not_finite() {
+        let a = DMatrix::from_row_slice(2, 2, &[0., f64::INFINITY, 0., 0.]);
+        let _ = expm(&a);
+    }
+
+    #[test]
+    fn lyapunov_continuous_singular() {
+        // Eigenvalues 1 and -1 sum to zero.
+        let a = DMatrix::from_row_slice(2, 2, &[1., 0., 0., -1.]);
+        let q = DMatrix::identity(2, 2);
+        assert!(lyap(&a, &q).is_none());
+    }
+}
@@ -4,6 +4,8 @@
 //! * poles calculation
 //! * controllability matrix
 //! * observability matrix
+//! * minimal realization
+//! * pole placement
 //! * conversion from a generic transfer function
 //! * calculation the equilibrium point of the system.
 //! * system stability
@@ -14,12 +16,40 @@
 //! [discrete](discrete/index.html) module contains the specialized structs and
 //! methods for discrete systems.
 //!
+//! [descriptor](descriptor/index.html) module contains the descriptor
+//! representation of continuous systems.
+//!
 //! The [solver](solver/index.html) module contains the methods for the time
 //! evaluation of continuous systems.
+//!
+//! The [linalg](linalg/index.html) module contains the solvers of the matrix
+//! equations used in the analysis and synthesis of linear systems.
+//!
+//! The [random](random/index.html) module contains the distributions used to
+//! generate random stable systems, behind the `rand` feature.
+//!
+//! The [fixed](fixed/index.html) module contains the representation of
+//! continuous systems with dimensions known at compile time.
+//!
+//! The [sparse](sparse/index.html) module contains the representation of
+//! large continuous systems with a sparse state matrix.
+//!
+//! The [batch](batch/index.html) module contains the parallel simulation of
+//! batches of systems, it requires the `rayon` feature.
 
+#[cfg(feature = "rayon")]
+pub mod batch;
+mod canonical;
 pub mod continuous;
+pub mod descriptor;
 pub mod discrete;
+pub mod fixed;
+pub mod linalg;
+mod pole_placement;
+#[cfg(feature = "rand")]
+pub mod random;
 pub mod solver;
+pub mod sparse;
 
 use nalgebra::{ComplexField, DMatrix, DVector, RealField, Scalar};
 use num_complex::Complex;
@@ -32,12 +62,14 @@ use std::{
 };
 
 use crate::{
-    enums::Time,
+    enums::{Continuous, Discrete, Time},
     error::{Error, ErrorKind},
+    plots::pzmap::{Boundary, PzMap},
     polynomial,
     polynomial::Poly,
     polynomial_matrix::PolyMatrix,
     transfer_function::TfGen,
+    units::joint_sample_time,
 };
 
 /// State-space representation of a linear system
@@ -46,7 +78,21 @@ use crate::{
 /// xdot(t) = A * x(t) + B * u(t)
 /// y(t)    = C * x(t) + D * u(t)
 /// ```
-#[derive(Clone, Debug, PartialEq)]
+///
+/// Two systems are equal when they have the same matrices and sampling
+/// period, the names of the channels are not compared.
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde-serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(
+        bound(
+            serialize = "T: Scalar + serde::Serialize",
+            deserialize = "T: Scalar + serde::Deserialize<'de>"
+        ),
+        try_from = "SsData<T>"
+    )
+)]
 pub struct SsGen<T: Scalar, U: Time> {
     /// A matrix
     pub(super) a: DMatrix<T>,
@@ -58,12 +104,144 @@ pub struct SsGen<T: Scalar, U: Time> {
     pub(super) d: DMatrix<T>,
     /// Dimensions
     dim: Dim,
+    /// Names of the channels
+    #[cfg_attr(feature = "serde-serialize", serde(default))]
+    names: Names,
     /// Tag for continuous or discrete time
+    #[cfg_attr(feature = "serde-serialize", serde(skip))]
     time: PhantomData<U>,
+    /// Sampling period of discrete time systems, if known
+    pub(crate) sample_time: Option<T>,
+}
+
+impl<T: Scalar, U: Time> PartialEq for SsGen<T, U> {
+    fn eq(&self, other: &Self) -> bool {
+        self.dim == other.dim
+            && self.a == other.a
+            && self.b == other.b
+            && self.c == other.c
+            && self.d == other.d
+            && self.sample_time == other.sample_time
+    }
+}
+
+/// Serialized state-space representation, it is checked before the creation
+/// of the system.
+#[cfg(feature = "serde-serialize")]
+#[derive(serde::Deserialize)]
+#[serde(bound(deserialize = "T: Scalar + serde::Deserialize<'de>"))]
+struct SsData<T: Scalar> {
+    /// A matrix
+    a: DMatrix<T>,
+    /// B matrix
+    b: DMatrix<T>,
+    /// C matrix
+    c: DMatrix<T>,
+    /// D matrix
+    d: DMatrix<T>,
+    /// Dimensions
+    dim: Dim,
+    /// Names of the channels
+    #[serde(default)]
+    names: Names,
+    /// Sampling period
+    sample_time: Option<T>,
+}
+
+#[cfg(feature = "serde-serialize")]
+impl<T: Scalar, U: Time> std::convert::TryFrom<SsData<T>> for SsGen<T, U> {
+    type Error = Error;
+
+    /// Create the system from the serialized data.
+    ///
+    /// # Errors
+    ///
+    /// It returns an error if the dimensions of the matrices or the number
+    /// of names do not match the dimensions of the system.
+    fn try_from(data: SsData<T>) -> Result<Self, Self::Error> {
+        let Dim {
+            states,
+            inputs,
+            outputs,
+        } = data.dim;
+        let count = |names: &Option<Vec<String>>, n: usize| names.as_ref().map_or(n, Vec::len) == n;
+        if data.a.shape() != (states, states)
+            || data.b.shape() != (states, inputs)
+            || data.c.shape() != (outputs, states)
+            || data.d.shape() != (outputs, inputs)
+            || !count(&data.names.inputs, inputs)
+            || !count(&data.names.outputs, outputs)
+            || !count(&data.names.states, states)
+        {
+            return Err(Error::new_internal(ErrorKind::InvalidData));
+        }
+        Ok(Self::new_from_matrices(data.a, data.b, data.c, data.d)
+            .with_names(data.names)
+            .with_sampling(data.sample_time))
+    }
+}
+
+/// Names of the channels of a linear system, `None` when the channels are
+/// not named.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "serde-serialize",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub(crate) struct Names {
+    /// Names of the inputs
+    pub(crate) inputs: Option<Vec<String>>,
+    /// Names of the outputs
+    pub(crate) outputs: Option<Vec<String>>,
+    /// Names of the states
+    pub(crate) states: Option<Vec<String>>,
+}
+
+impl Names {
+    /// Names of the inputs and of the outputs only, used when the states of
+    /// the system are transformed.
+    pub(crate) fn io(&self) -> Self {
+        Self {
+            inputs: self.inputs.clone(),
+            outputs: self.outputs.clone(),
+            states: None,
+        }
+    }
+}
+
+/// Collect the names of `n` channels.
+///
+/// # Panics
+///
+/// Panics if the number of names is different from the number of channels.
+pub(crate) fn collect_names<I, S>(names: I, n: usize, channels: &str) -> Vec<String>
+where
+    I: IntoIterator<Item = S>,
+    S: ToString,
+{
+    let names: Vec<String> = names.into_iter().map(|s| s.to_string()).collect();
+    assert_eq!(
+        n,
+        names.len(),
+        "Number of names must be equal to the number of {}",
+        channels
+    );
+    names
+}
+
+/// Select the names of the channels with the given indexes.
+fn select_names(names: &Option<Vec<String>>, idx: &[usize]) -> Option<Vec<String>> {
+    names
+        .as_ref()
+        .map(|n| idx.iter().map(|&i| n[i].clone()).collect())
 }
 
 /// Dim of the linear system.
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "serde-serialize",
+    derive(serde::Serialize, serde::Deserialize)
+)]
 pub struct Dim {
     /// Number of states
     states: usize,
@@ -139,10 +317,146 @@ impl<T: Scalar, U: Time> SsGen<T, U> {
                 inputs,
                 outputs,
             },
+            names: Names::default(),
             time: PhantomData,
+            sample_time: None,
         }
     }
 
+    /// Set the names of the inputs of the system.
+    ///
+    /// # Arguments
+    ///
+    /// * `names` - Names of the inputs
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of names is different from the number of inputs.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use au::Ss;
+    /// let sys = Ss::new_from_slice(1, 1, 1, &[-1.], &[1.], &[1.], &[0.])
+    ///     .with_input_names(["force"]);
+    /// assert_eq!(Some(&["force".to_string()][..]), sys.input_names());
+    /// ```
+    #[must_use]
+    pub fn with_input_names<I, S>(mut self, names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: ToString,
+    {
+        self.names.inputs = Some(collect_names(names, self.dim.inputs, "inputs"));
+        self
+    }
+
+    /// Set the names of the outputs of the system.
+    ///
+    /// # Arguments
+    ///
+    /// * `names` - Names of the outputs
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of names is different from the number of outputs.
+    #[must_use]
+    pub fn with_output_names<I, S>(mut self, names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: ToString,
+    {
+        self.names.outputs = Some(collect_names(names, self.dim.outputs, "outputs"));
+        self
+    }
+
+    /// Set the names of the states of the system.
+    ///
+    /// # Arguments
+    ///
+    /// * `names` - Names of the states
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of names is different from the number of states.
+    #[must_use]
+    pub fn with_state_names<I, S>(mut self, names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: ToString,
+    {
+        self.names.states = Some(collect_names(names, self.dim.states, "states"));
+        self
+    }
+
+    /// Get the names of the inputs, `None` if the inputs are not named.
+    #[must_use]
+    pub fn input_names(&self) -> Option<&[String]> {
+        self.names.inputs.as_deref()
+    }
+
+    /// Get the names of the outputs, `None` if the outputs are not named.
+    #[must_use]
+    pub fn output_names(&self) -> Option<&[String]> {
+        self.names.outputs.as_deref()
+    }
+
+    /// Get the names of the states, `None` if the states are not named.
+    ///
+    /// State names are kept only by the transformations that preserve the
+    /// state coordinates of the system.
+    #[must_use]
+    pub fn state_names(&self) -> Option<&[String]> {
+        self.names.states.as_deref()
+    }
+
+    /// Set all the names of the channels of the system.
+    pub(crate) fn with_names(mut self, names: Names) -> Self {
+        self.names = names;
+        self
+    }
+
+    /// Set the sampling period of the system.
+    pub(crate) fn with_sampling(mut self, sample_time: Option<T>) -> Self {
+        self.sample_time = sample_time;
+        self
+    }
+
+    /// Get the states matrix (A) of the system.
+    #[must_use]
+    pub fn a(&self) -> &DMatrix<T> {
+        &self.a
+    }
+
+    /// Get the input matrix (B) of the system.
+    #[must_use]
+    pub fn b(&self) -> &DMatrix<T> {
+        &self.b
+    }
+
+    /// Get the output matrix (C) of the system.
+    #[must_use]
+    pub fn c(&self) -> &DMatrix<T> {
+        &self.c
+    }
+
+    /// Get the direct transmission matrix (D) of the system.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use au::Ssd;
+    /// let sys = Ssd::new_from_slice(2, 1, 1, &[-2., 0., 3., -7.], &[1., 3.], &[-1., 0.5], &[0.1]);
+    /// assert_eq!((2, 2), sys.a().shape());
+    /// assert_eq!((2, 1), sys.b().shape());
+    /// assert_eq!((1, 2), sys.c().shape());
+    /// assert_eq!(0.1, sys.d()[(0, 0)]);
+    /// ```
+    #[must_use]
+    pub fn d(&self) -> &DMatrix<T> {
+        &self.d
+    }
+
     /// Get the dimensions of the system (states, inputs, outputs).
     ///
     /// # Example
@@ -156,6 +470,91 @@ impl<T: Scalar, U: Time> SsGen<T, U> {
     pub fn dim(&self) -> Dim {
         self.dim
     }
+
+    /// Extract the subsystem from the selected input channels to the selected
+    /// output channels. The states of the system are preserved.
+    ///
+    /// Channels are taken in the given order, so the selection may also
+    /// permute or repeat them. The names of the channels are preserved.
+    ///
+    /// # Arguments
+    ///
+    /// * `inputs` - Indexes of the input channels
+    /// * `outputs` - Indexes of the output channels
+    ///
+    /// # Panics
+    ///
+    /// Panics if any index is out of the bounds of the system inputs or outputs.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use au::Ss;
+    /// let sys = Ss::new_from_slice(
+    ///     2, 2, 2,
+    ///     &[-2., 0., 3., -7.],
+    ///     &[1., 0., 0., 3.],
+    ///     &[-1., 0.5, 0., 1.],
+    ///     &[0.1, 0.2, 0.3, 0.4],
+    /// );
+    /// let sub = sys.subsystem(&[1], &[0]);
+    /// assert_eq!(1, sub.dim().inputs());
+    /// assert_eq!(1, sub.dim().outputs());
+    /// assert_eq!(0.2, sub.d()[(0, 0)]);
+    /// ```
+    #[must_use]
+    pub fn subsystem(&self, inputs: &[usize], outputs: &[usize]) -> Self {
+        let n = self.dim.states;
+        let b = DMatrix::from_fn(n, inputs.len(), |i, j| self.b[(i, inputs[j])].clone());
+        let c = DMatrix::from_fn(outputs.len(), n, |i, j| self.c[(outputs[i], j)].clone());
+        let d = DMatrix::from_fn(outputs.len(), inputs.len(), |i, j| {
+            self.d[(outputs[i], inputs[j])].clone()
+        });
+        let names = Names {
+            inputs: select_names(&self.names.inputs, inputs),
+            outputs: select_names(&self.names.outputs, outputs),
+            states: self.names.states.clone(),
+        };
+        Self::new_from_matrices(self.a.clone(), b, c, d)
+            .with_names(names)
+            .with_sampling(self.sample_time.clone())
+    }
+
+    /// Create a new state-space representation from its matrices.
+    /// Dimensions are inferred from the matrices.
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - A matrix (nxn)
+    /// * `b` - B matrix (nxm)
+    /// * `c` - C matrix (pxn)
+    /// * `d` - D matrix (pxm)
+    pub(crate) fn new_from_matrices(
+        a: DMatrix<T>,
+        b: DMatrix<T>,
+        c: DMatrix<T>,
+        d: DMatrix<T>,
+    ) -> Self {
+        debug_assert!(a.is_square());
+        debug_assert_eq!(a.nrows(), b.nrows());
+        debug_assert_eq!(a.ncols(), c.ncols());
+        debug_assert_eq!((c.nrows(), b.ncols()), d.shape());
+        let dim = Dim {
+            states: a.nrows(),
+            inputs: b.ncols(),
+            outputs: c.nrows(),
+        };
+        Self {
+            a,
+            b,
+            c,
+            d,
+            dim,
+            names: Names::default(),
+            time: PhantomData,
+            sample_time: None,
+        }
+    }
 }
 
 /// Implementation of the methods for the state-space
@@ -173,22 +572,32 @@ impl<T: ComplexField + Float + RealField, U: Time> SsGen<T, U> {
     /// ```
     #[must_use]
     pub fn poles(&self) -> Vec<Complex<T>> {
-        match self.dim().states() {
-            1 => vec![Complex::new(self.a[(0, 0)], T::zero())],
-            2 => {
-                let m00 = self.a[(0, 0)];
-                let m01 = self.a[(0, 1)];
-                let m10 = self.a[(1, 0)];
-                let m11 = self.a[(1, 1)];
-                let trace = m00 + m11;
-                let determinant = m00 * m11 - m01 * m10;
-
-                let (eig1, eig2) = polynomial::complex_quadratic_roots(-trace, determinant);
-
-                vec![eig1, eig2]
-            }
-            _ => self.a.complex_eigenvalues().as_slice().to_vec(),
+        eigenvalues(&self.a)
+    }
+}
+
+/// Eigenvalues of a square matrix.
+///
+/// # Arguments
+///
+/// * `a` - Square matrix
+fn eigenvalues<T: ComplexField + Float + RealField>(a: &DMatrix<T>) -> Vec<Complex<T>> {
+    match a.nrows() {
+        0 => Vec::new(),
+        1 => vec![Complex::new(a[(0, 0)], T::zero())],
+        2 => {
+            let m00 = a[(0, 0)];
+            let m01 = a[(0, 1)];
+            let m10 = a[(1, 0)];
+            let m11 = a[(1, 1)];
+            let trace = m00 + m11;
+            let determinant = m00 * m11 - m01 * m10;
+
+            let (eig1, eig2) = polynomial::complex_quadratic_roots(-trace, determinant);
+
+            vec![eig1, eig2]
         }
+        _ => a.complex_eigenvalues().as_slice().to_vec(),
     }
 }
 
@@ -306,6 +715,238 @@ impl<T: RealField + Scalar, U: Time> SsGen<T, U> {
     }
 }
 
+/// Orthonormal basis of the range of the given matrix, calculated through
+/// singular value decomposition. Singular values lower than `tol` times the
+/// largest singular value are considered null.
+///
+/// # Arguments
+///
+/// * `m` - Matrix
+/// * `tol` - Relative tolerance on singular values
+fn range_basis<T: ComplexField + Float + RealField>(m: &DMatrix<T>, tol: T) -> DMatrix<T> {
+    if m.is_empty() {
+        return DMatrix::zeros(m.nrows(), 0);
+    }
+    let svd = m.clone().svd(true, false);
+    let u = svd.u.expect("Left singular vectors have been requested");
+    let max_sv = svd
+        .singular_values
+        .iter()
+        .fold(T::zero(), |acc, &s| Float::max(acc, s));
+    let threshold = tol * max_sv;
+    let columns: Vec<_> = svd
+        .singular_values
+        .iter()
+        .enumerate()
+        .filter(|(_, &s)| s > threshold && s > T::zero())
+        .map(|(i, _)| u.column(i).into_owned())
+        .collect();
+    if columns.is_empty() {
+        DMatrix::zeros(m.nrows(), 0)
+    } else {
+        DMatrix::from_columns(&columns)
+    }
+}
+
+impl<T: ComplexField + Float + RealField, U: Time> SsGen<T, U> {
+    /// Minimal realization of the system. Uncontrollable and unobservable
+    /// states are removed through a Kalman decomposition, the input-output
+    /// behavior of the system is unchanged.
+    ///
+    /// The reachable subspace is the range of the controllability matrix,
+    /// the observable subspace is the range of the transposed observability
+    /// matrix, both are calculated through singular value decomposition.
+    ///
+    /// # Arguments
+    ///
+    /// * `tol` - Relative tolerance on singular values used to determine the
+    ///   rank of the controllability and observability matrices
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use au::Ss;
+    /// // The second state is not controllable.
+    /// let sys = Ss::new_from_slice(2, 1, 1, &[-1., 0., 0., -2.], &[1., 0.], &[1., 1.], &[0.]);
+    /// let min = sys.minreal(1e-10);
+    /// assert_eq!(1, min.dim().states());
+    /// ```
+    #[must_use]
+    pub fn minreal(&self, tol: T) -> Self {
+        // Controllable part.
+        let mr = controllability_impl(self.dim.states, self.dim.inputs, &self.a, &self.b);
+        let u = range_basis(&mr, tol);
+        let a = u.tr_mul(&self.a) * &u;
+        let b = u.tr_mul(&self.b);
+        let c = &self.c * &u;
+
+        // Observable part of the controllable subsystem.
+        let mo = observability_impl(u.ncols(), self.dim.outputs, &a, &c);
+        let v = range_basis(&mo, tol);
+        let a = v.tr_mul(&a) * &v;
+        let b = v.tr_mul(&b);
+        let c = c * &v;
+
+        Self::new_from_matrices(a, b, c, self.d.clone())
+            .with_names(self.names.io())
+            .with_sampling(self.sample_time)
+    }
+
+    /// Observer based compensator, composed by the state feedback `K` and the
+    /// state observer with gain `L`. The input of the compensator is the
+    /// output `y` of the system and its output is the control input `u`:
+    /// ```text
+    /// xhat' = (A - B*K - L*C + L*D*K) * xhat + L * y
+    /// u     = -K * xhat
+    /// ```
+    /// where `xhat'` is the derivative of the estimated state for continuous
+    /// systems and the estimated state at the next step for discrete systems.
+    ///
+    /// The compensator can be converted into a matrix of transfer functions
+    /// with `TfMatrix::from`, or into a transfer function with
+    /// `TfGen::new_from_siso` for single input single output systems.
+    ///
+    /// The inputs of the compensator have the names of the outputs of the
+    /// system, its outputs the names of the inputs of the system and its
+    /// states the names of the states of the system.
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - State feedback gain (mxn)
+    /// * `l` - Observer gain (nxp)
+    ///
+    /// # Panics
+    ///
+    /// Panics if the gains dimensions do not match the system ones.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use au::{nalgebra::DMatrix, Ss, Tf};
+    /// let sys = Ss::new_from_slice(1, 1, 1, &[0.], &[1.], &[1.], &[0.]);
+    /// let k = DMatrix::from_element(1, 1, 2.);
+    /// let l = DMatrix::from_element(1, 1, 3.);
+    /// let comp = sys.observer_compensator(&k, &l);
+    /// let tf = Tf::<f64>::new_from_siso(&comp).unwrap();
+    /// assert_eq!(Tf::new(au::poly!(-6.), au::poly!(5., 1.)), tf);
+    /// ```
+    #[must_use]
+    pub fn observer_compensator(&self, k: &DMatrix<T>, l: &DMatrix<T>) -> Self {
+        let n = self.dim.states;
+        assert_eq!(
+            (self.dim.inputs, n),
+            k.shape(),
+            "Wrong state feedback gain dimensions"
+        );
+        assert_eq!(
+            (n, self.dim.outputs),
+            l.shape(),
+            "Wrong observer gain dimensions"
+        );
+        let a = &self.a - &self.b * k - l * &self.c + l * &self.d * k;
+        let d = DMatrix::zeros(self.dim.inputs, self.dim.outputs);
+        let names = Names {
+            inputs: self.names.outputs.clone(),
+            outputs: self.names.inputs.clone(),
+            states: self.names.states.clone(),
+        };
+        Self::new_from_matrices(a, l.clone(), -k, d)
+            .with_sampling(self.sample_time)
+            .with_names(names)
+    }
+
+    /// Closed loop interconnection of the system with the given controller.
+    /// Discrete systems with known sampling periods shall have the same
+    /// sampling period.
+    /// The output of the system is the input of the controller and the
+    /// output of the controller is added to the external input `r`:
+    /// ```text
+    /// u = yc + r,   uc = y
+    /// ```
+    /// The feedback is positive, the sign shall be included in the
+    /// controller. The inputs of the closed loop system are the external
+    /// inputs `r`, the outputs are the outputs of the system `y` and the
+    /// states are the states of the system followed by the states of the
+    /// controller. The names of the inputs and of the outputs of the system
+    /// are preserved, the states are named if both the system and the
+    /// controller states are named.
+    ///
+    /// # Arguments
+    ///
+    /// * `controller` - Controller, whose inputs are the system outputs and
+    ///   whose outputs are the system inputs
+    ///
+    /// Returns `None` if the interconnection is not well posed, i.e.
+    /// `I - Dc*D` is singular.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the controller dimensions do not match the system ones or
+    /// the system and the controller have different sampling periods.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use au::Ss;
+    /// let sys = Ss::new_from_slice(1, 1, 1, &[1.], &[1.], &[1.], &[0.]);
+    /// // Static gain controller u = -2*y
+    /// let ctrl = Ss::new_from_slice(0, 1, 1, &[], &[], &[], &[-2.]);
+    /// let cl = sys.closed_loop(&ctrl).unwrap();
+    /// assert_eq!(-1., cl.poles()[0].re);
+    /// ```
+    #[must_use]
+    pub fn closed_loop(&self, controller: &Self) -> Option<Self> {
+        let n = self.dim.states;
+        let nc = controller.dim.states;
+        assert_eq!(
+            self.dim.outputs, controller.dim.inputs,
+            "Controller inputs must be equal to system outputs"
+        );
+        assert_eq!(
+            self.dim.inputs, controller.dim.outputs,
+            "Controller outputs must be equal to system inputs"
+        );
+        let m = self.dim.inputs;
+        // u = E*(Dc*C*x + Cc*xc + r), E = (I - Dc*D)^-1
+        let e = (DMatrix::identity(m, m) - &controller.d * &self.d).try_inverse()?;
+        let u_x = &e * &controller.d * &self.c;
+        let u_xc = &e * &controller.c;
+        let u_r = e;
+        // y = C*x + D*u
+        let y_x = &self.c + &self.d * &u_x;
+        let y_xc = &self.d * &u_xc;
+        let y_r = &self.d * &u_r;
+
+        let mut a = DMatrix::zeros(n + nc, n + nc);
+        a.slice_mut((0, 0), (n, n))
+            .copy_from(&(&self.a + &self.b * &u_x));
+        a.slice_mut((0, n), (n, nc)).copy_from(&(&self.b * &u_xc));
+        a.slice_mut((n, 0), (nc, n))
+            .copy_from(&(&controller.b * &y_x));
+        a.slice_mut((n, n), (nc, nc))
+            .copy_from(&(&controller.a + &controller.b * &y_xc));
+        let mut b = DMatrix::zeros(n + nc, m);
+        b.rows_mut(0, n).copy_from(&(&self.b * &u_r));
+        b.rows_mut(n, nc).copy_from(&(&controller.b * &y_r));
+        let mut c = DMatrix::zeros(self.dim.outputs, n + nc);
+        c.columns_mut(0, n).copy_from(&y_x);
+        c.columns_mut(n, nc).copy_from(&y_xc);
+        let states = match (&self.names.states, &controller.names.states) {
+            (Some(s), Some(sc)) => Some(s.iter().chain(sc).cloned().collect()),
+            _ => None,
+        };
+        let names = Names {
+            states,
+            ..self.names.io()
+        };
+        Some(
+            Self::new_from_matrices(a, b, c, y_r)
+                .with_names(names)
+                .with_sampling(joint_sample_time(self.sample_time, controller.sample_time)),
+        )
+    }
+}
+
 macro_rules! leverrier {
     ($ty:ty, $name:ident) => {
         /// Faddeev-LeVerrier algorithm
@@ -352,6 +993,60 @@ macro_rules! leverrier {
 leverrier!(f64, leverrier_f64);
 leverrier!(f32, leverrier_f32);
 
+macro_rules! pzmap_ss {
+    ($ty:ty) => {
+        impl SsGen<$ty, Continuous> {
+            /// Create a pole-zero map, the stability boundary is the imaginary
+            /// axis. The poles are the eigenvalues of the state matrix, the
+            /// zeros are the roots of the numerator of the transfer function.
+            ///
+            /// # Errors
+            ///
+            /// It returns an error if the linear system is not single input
+            /// single output.
+            ///
+            /// # Example
+            /// ```
+            /// use au::Ss;
+            /// let sys = Ss::new_from_slice(2, 1, 1, &[0_f64, 1., -2., -3.], &[0., 1.], &[1., 1.], &[0.]);
+            /// let map = sys.pzmap().unwrap();
+            /// assert_eq!(2, map.poles().count());
+            /// assert_eq!(1, map.zeros().count());
+            /// ```
+            pub fn pzmap(&self) -> Result<PzMap<$ty>, Error> {
+                let tf = TfGen::<$ty, Continuous>::new_from_siso(self)?;
+                Ok(PzMap::new(
+                    &self.poles(),
+                    &tf.complex_zeros(),
+                    Boundary::ImaginaryAxis,
+                ))
+            }
+        }
+
+        impl SsGen<$ty, Discrete> {
+            /// Create a pole-zero map, the stability boundary is the unit
+            /// circle. The poles are the eigenvalues of the state matrix, the
+            /// zeros are the roots of the numerator of the transfer function.
+            ///
+            /// # Errors
+            ///
+            /// It returns an error if the linear system is not single input
+            /// single output.
+            pub fn pzmap(&self) -> Result<PzMap<$ty>, Error> {
+                let tf = TfGen::<$ty, Discrete>::new_from_siso(self)?;
+                Ok(PzMap::new(
+                    &self.poles(),
+                    &tf.complex_zeros(),
+                    Boundary::UnitCircle,
+                ))
+            }
+        }
+    };
+}
+
+pzmap_ss!(f64);
+pzmap_ss!(f32);
+
 impl<T: ComplexField + Float + RealField, U: Time> SsGen<T, U> {
     /// Convert a transfer function representation into state space representation.
     /// Conversion is done using the observability canonical form.
@@ -380,7 +1075,8 @@ impl<T: ComplexField + Float + RealField, U: Time> SsGen<T, U> {
     ///
     /// # Errors
     ///
-    /// It returns an error if the transfer function has no poles.
+    /// It returns an error if the transfer function has no poles or it is
+    /// not proper.
     pub fn new_observability_realization(tf: &TfGen<T, U>) -> Result<Self, Error> {
         // Get the denominator in the monic form mantaining the original gain.
         let tf_norm = tf.normalize();
@@ -388,6 +1084,9 @@ impl<T: ComplexField + Float + RealField, U: Time> SsGen<T, U> {
             Some(d) => d,
             None => return Err(Error::new_internal(ErrorKind::ZeroPolynomialDenominator)),
         };
+        if !tf_norm.is_proper() {
+            return Err(Error::new_internal(ErrorKind::ImproperTransferFunction));
+        }
         let num = {
             // Extend the numerator coefficients with zeros to the length of the
             // denominator polynomial.
@@ -428,7 +1127,9 @@ impl<T: ComplexField + Float + RealField, U: Time> SsGen<T, U> {
                 inputs: 1,
                 outputs: 1,
             },
+            names: Names::default(),
             time: PhantomData,
+            sample_time: tf.sample_time,
         })
     }
 
@@ -460,7 +1161,8 @@ impl<T: ComplexField + Float + RealField, U: Time> SsGen<T, U> {
     ///
     /// # Errors
     ///
-    /// It returns an error if the transfer function has no poles.
+    /// It returns an error if the transfer function has no poles or it is
+    /// not proper.
     pub fn new_controllability_realization(tf: &TfGen<T, U>) -> Result<Self, Error> {
         // Get the denominator in the monic form mantaining the original gain.
         let tf_norm = tf.normalize();
@@ -468,6 +1170,9 @@ impl<T: ComplexField + Float + RealField, U: Time> SsGen<T, U> {
             Some(d) => d,
             None => return Err(Error::new_internal(ErrorKind::ZeroPolynomialDenominator)),
         };
+        if !tf_norm.is_proper() {
+            return Err(Error::new_internal(ErrorKind::ImproperTransferFunction));
+        }
         let num = {
             // Extend the numerator coefficients with zeros to the length of the
             // denominator polynomial.
@@ -508,7 +1213,9 @@ impl<T: ComplexField + Float + RealField, U: Time> SsGen<T, U> {
                 inputs: 1,
                 outputs: 1,
             },
+            names: Names::default(),
             time: PhantomData,
+            sample_time: tf.sample_time,
         })
     }
 }
@@ -578,7 +1285,18 @@ impl<T: Scalar + Display, U: Time> Display for SsGen<T, U> {
             f,
             "A: {}\nB: {}\nC: {}\nD: {}",
             self.a, self.b, self.c, self.d
-        )
+        )?;
+        let names = [
+            ("States", &self.names.states),
+            ("Inputs", &self.names.inputs),
+            ("Outputs", &self.names.outputs),
+        ];
+        for (channels, names) in &names {
+            if let Some(n) = names {
+                write!(f, "\n{}: {}", channels, n.join(", "))?;
+            }
+        }
+        Ok(())
     }
 }
 
@@ -589,6 +1307,8 @@ pub struct Equilibrium<T: Scalar> {
     x: DVector<T>,
     /// Output equilibrium
     y: DVector<T>,
+    /// Basis of the states that can be added to the equilibrium
+    null_space: DMatrix<T>,
 }
 
 /// Implement methods for equilibrium
@@ -600,7 +1320,20 @@ impl<T: Scalar> Equilibrium<T> {
     /// * `x` - State equilibrium
     /// * `y` - Output equilibrium
     fn new(x: DVector<T>, y: DVector<T>) -> Self {
-        Self { x, y }
+        let null_space = DMatrix::from_vec(x.len(), 0, Vec::new());
+        Self { x, y, null_space }
+    }
+
+    /// Create a new family of equilibrium points given a particular state,
+    /// the related output and the basis of the states that can be added to it
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - State equilibrium
+    /// * `y` - Output equilibrium
+    /// * `null_space` - Basis of the states that can be added (columns)
+    fn new_family(x: DVector<T>, y: DVector<T>, null_space: DMatrix<T>) -> Self {
+        Self { x, y, null_space }
     }
 
     /// Retrieve state coordinates for equilibrium
@@ -614,6 +1347,20 @@ impl<T: Scalar> Equilibrium<T> {
     pub fn y(&self) -> &[T] {
         self.y.as_slice()
     }
+
+    /// Retrieve the basis (columns) of the states that can be added to the
+    /// state equilibrium obtaining another equilibrium point.
+    /// It is empty when the equilibrium is unique.
+    #[must_use]
+    pub fn null_space(&self) -> &DMatrix<T> {
+        &self.null_space
+    }
+
+    /// Check if the equilibrium point is unique.
+    #[must_use]
+    pub fn is_unique(&self) -> bool {
+        self.null_space.ncols() == 0
+    }
 }
 
 /// Implementation of printing of equilibrium point
@@ -626,7 +1373,7 @@ impl<T: Display + Scalar> Display for Equilibrium<T> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{polynomial_matrix::MatrixOfPoly, Continuous, Discrete};
+    use crate::{poly, polynomial_matrix::MatrixOfPoly, Continuous, Discrete};
 
     use nalgebra::DMatrix;
     use proptest::prelude::*;
@@ -645,6 +1392,206 @@ mod tests {
         }
     }
 
+    #[test]
+    fn subsystem_channels() {
+        let sys = SsGen::<_, Continuous>::new_from_slice(
+            2,
+            3,
+            2,
+            &[-2., 0., 3., -7.],
+            &[1., 2., 3., 4., 5., 6.],
+            &[-1., 0.5, 7., 8.],
+            &[0.1, 0.2, 0.3, 0.4, 0.5, 0.6],
+        );
+        let sub = sys.subsystem(&[2, 0], &[1]);
+        assert_eq!(sys.a(), sub.a());
+        assert_eq!(&DMatrix::from_row_slice(2, 2, &[3., 1., 6., 4.]), sub.b());
+        assert_eq!(&DMatrix::from_row_slice(1, 2, &[7., 8.]), sub.c());
+        assert_eq!(&DMatrix::from_row_slice(1, 2, &[0.6, 0.4]), sub.d());
+        assert_eq!(
+            Dim {
+                states: 2,
+                inputs: 2,
+                outputs: 1
+            },
+            sub.dim()
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn subsystem_channels_fail() {
+        let sys = SsGen::<_, Continuous>::new_from_slice(1, 1, 1, &[0.], &[1.], &[1.], &[0.]);
+        let _ = sys.subsystem(&[1], &[0]);
+    }
+
+    #[test]
+    fn channel_names() {
+        let sys = SsGen::<_, Continuous>::new_from_slice(
+            2,
+            2,
+            1,
+            &[0., 1., -2., -3.],
+            &[0., 0., 1., 2.],
+            &[1., 0.],
+            &[0., 0.],
+        );
+        assert_eq!(None, sys.input_names());
+        let sys = sys
+            .with_input_names(vec!["force", "torque"])
+            .with_output_names(["position"])
+            .with_state_names(["x", "v"]);
+        assert_eq!(
+            Some(&["force".to_owned(), "torque".to_owned()][..]),
+            sys.input_names()
+        );
+        assert_eq!(Some(&["position".to_owned()][..]), sys.output_names());
+        assert_eq!(
+            Some(&["x".to_owned(), "v".to_owned()][..]),
+            sys.state_names()
+        );
+
+        let sub = sys.subsystem(&[1, 1], &[0]);
+        assert_eq!(
+            Some(&["torque".to_owned(), "torque".to_owned()][..]),
+            sub.input_names()
+        );
+        assert_eq!(sys.output_names(), sub.output_names());
+        assert_eq!(sys.state_names(), sub.state_names());
+
+        let min = sys.minreal(1e-10);
+        assert_eq!(sys.input_names(), min.input_names());
+        assert_eq!(sys.output_names(), min.output_names());
+        assert_eq!(None, min.state_names());
+
+        let display = format!("{}", sys);
+        assert!(display.ends_with("\nStates: x, v\nInputs: force, torque\nOutputs: position"));
+
+        let unnamed = SsGen::<_, Continuous>::new_from_slice(
+            2,
+            2,
+            1,
+            &[0., 1., -2., -3.],
+            &[0., 0., 1., 2.],
+            &[1., 0.],
+            &[0., 0.],
+        );
+        assert_eq!(unnamed, sys);
+    }
+
+    #[test]
+    fn channel_names_observer_compensator() {
+        let sys = SsGen::<_, Continuous>::new_from_slice(1, 1, 1, &[0.], &[1.], &[1.], &[0.])
+            .with_input_names(["u"])
+            .with_output_names(["y"])
+            .with_state_names(["x"]);
+        let k = DMatrix::from_element(1, 1, 2.);
+        let l = DMatrix::from_element(1, 1, 3.);
+        let comp = sys.observer_compensator(&k, &l);
+        assert_eq!(sys.output_names(), comp.input_names());
+        assert_eq!(sys.input_names(), comp.output_names());
+        assert_eq!(sys.state_names(), comp.state_names());
+    }
+
+    #[test]
+    fn channel_names_closed_loop() {
+        let sys = SsGen::<_, Continuous>::new_from_slice(1, 1, 1, &[1.], &[1.], &[1.], &[0.])
+            .with_input_names(["u"])
+            .with_output_names(["y"])
+            .with_state_names(["x"]);
+        let ctrl = SsGen::new_from_slice(1, 1, 1, &[-1.], &[1.], &[-1.], &[-2.]);
+        let cl = sys.closed_loop(&ctrl).unwrap();
+        assert_eq!(sys.input_names(), cl.input_names());
+        assert_eq!(sys.output_names(), cl.output_names());
+        assert_eq!(None, cl.state_names());
+        let cl = sys.closed_loop(&ctrl.with_state_names(["xc"])).unwrap();
+        assert_eq!(
+            Some(&["x".to_owned(), "xc".to_owned()][..]),
+            cl.state_names()
+        );
+    }
+
+    #[test]
+    fn pole_zero_map() {
+        // G(s) = (s + 3) / (s + 1)^2
+        let sys = SsGen::<_, Continuous>::new_from_slice(
+            2,
+            1,
+            1,
+            &[0_f64, 1., -1., -2.],
+            &[0., 1.],
+            &[3., 1.],
+            &[0.],
+        );
+        let map = sys.pzmap().unwrap();
+        assert_eq!(Boundary::ImaginaryAxis, map.boundary());
+        let pole = map.poles().next().unwrap();
+        assert_eq!(2, pole.multiplicity());
+        assert_relative_eq!(-1., pole.value().re, max_relative = 1e-6);
+        let zero = map.zeros().next().unwrap();
+        assert_relative_eq!(-3., zero.value().re, max_relative = 1e-12);
+
+        let sysd = SsGen::<_, Discrete>::new_from_slice(1, 1, 1, &[0.5_f32], &[1.], &[1.], &[1.]);
+        let map = sysd.pzmap().unwrap();
+        assert_eq!(Boundary::UnitCircle, map.boundary());
+        assert_relative_eq!(0.5, map.poles().next().unwrap().value().re);
+        assert_relative_eq!(-0.5, map.zeros().next().unwrap().value().re);
+
+        let mimo =
+            SsGen::<_, Continuous>::new_from_slice(1, 2, 1, &[-1_f64], &[1., 1.], &[1.], &[0., 0.]);
+        assert!(mimo.pzmap().is_err());
+    }
+
+    #[cfg(feature = "serde-serialize")]
+    #[test]
+    fn serde_round_trip() {
+        let sys = SsGen::<_, Continuous>::new_from_slice(
+            2,
+            1,
+            1,
+            &[-2., 0., 3., -7.],
+            &[1., 3.],
+            &[-1., 0.5],
+            &[0.1],
+        )
+        .with_input_names(["u"])
+        .with_state_names(["x1", "x2"]);
+        let json = serde_json::to_string(&sys).unwrap();
+        let back: SsGen<f64, Continuous> = serde_json::from_str(&json).unwrap();
+        assert_eq!(sys, back);
+        assert_eq!(sys.input_names(), back.input_names());
+        assert_eq!(sys.state_names(), back.state_names());
+
+        let sys = SsGen::<_, Discrete>::new_from_slice(1, 1, 1, &[0.5_f32], &[1.], &[1.], &[0.])
+            .with_sampling(Some(0.1));
+        let json = serde_json::to_string(&sys).unwrap();
+        let back: SsGen<f32, Discrete> = serde_json::from_str(&json).unwrap();
+        assert_eq!(sys, back);
+    }
+
+    #[cfg(feature = "serde-serialize")]
+    #[test]
+    fn serde_wrong_dimensions() {
+        let sys = SsGen::<_, Continuous>::new_from_slice(1, 1, 1, &[-1.], &[1.], &[1.], &[0.]);
+        let json = serde_json::to_string(&sys).unwrap();
+        let wrong = json.replace("\"states\":1", "\"states\":5");
+        assert_ne!(json, wrong);
+        assert!(serde_json::from_str::<SsGen<f64, Continuous>>(&wrong).is_err());
+
+        let sys = sys.with_output_names(["y"]);
+        let json = serde_json::to_string(&sys).unwrap();
+        let wrong = json.replace("[\"y\"]", "[\"y\",\"z\"]");
+        assert_ne!(json, wrong);
+        assert!(serde_json::from_str::<SsGen<f64, Continuous>>(&wrong).is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn channel_names_fail() {
+        let sys = SsGen::<_, Continuous>::new_from_slice(1, 1, 1, &[0.], &[1.], &[1.], &[0.]);
+        let _ = sys.with_output_names(["y1", "y2"]);
+    }
+
     #[test]
     fn system_dimensions() {
         let states = 2;
@@ -669,6 +1616,177 @@ mod tests {
         );
     }
 
+    #[test]
+    fn minimal_realization_uncontrollable() {
+        let sys = SsGen::<_, Continuous>::new_from_slice(
+            2,
+            1,
+            1,
+            &[-1., 0., 0., -2.],
+            &[1., 0.],
+            &[1., 1.],
+            &[0.5],
+        );
+        let min = sys.minreal(1e-10);
+        assert_eq!(1, min.dim().states());
+        assert_relative_eq!(-1., min.a[(0, 0)], max_relative = 1e-10);
+        assert_relative_eq!(1., (min.b[(0, 0)] * min.c[(0, 0)]), max_relative = 1e-10);
+        assert_eq!(sys.d, min.d);
+    }
+
+    #[test]
+    fn minimal_realization_unobservable() {
+        let sys = SsGen::<_, Discrete>::new_from_slice(
+            2,
+            1,
+            1,
+            &[0.5, 0., 0., 0.2],
+            &[1., 1.],
+            &[2., 0.],
+            &[0.],
+        );
+        let min = sys.minreal(1e-10);
+        assert_eq!(1, min.dim().states());
+        assert_relative_eq!(0.5, min.a[(0, 0)], max_relative = 1e-10);
+        assert_relative_eq!(2., (min.b[(0, 0)] * min.c[(0, 0)]), max_relative = 1e-10);
+    }
+
+    #[test]
+    fn minimal_realization_from_tf() {
+        use crate::transfer_function::continuous::Tf;
+        // (s + 1) / ((s + 1) * (s + 2))
+        let tf = Tf::new(poly!(1., 1.), poly!(2., 3., 1.));
+        let sys = SsGen::new_observability_realization(&tf).unwrap();
+        assert_eq!(2, sys.dim().states());
+        let min = sys.minreal(1e-8);
+        assert_eq!(1, min.dim().states());
+        assert_relative_eq!(-2., min.poles()[0].re, max_relative = 1e-8);
+        let tf_min = Tf::<f64>::new_from_siso(&min).unwrap().normalize();
+        assert_relative_eq!(1., tf_min.num()[0], max_relative = 1e-8);
+        assert_relative_eq!(2., tf_min.den()[0], max_relative = 1e-8);
+    }
+
+    #[test]
+    fn minimal_realization_keeps_minimal_system() {
+        let sys = SsGen::<_, Continuous>::new_from_slice(
+            2,
+            1,
+            1,
+            &[-2., 0., 3., -7.],
+            &[1., 3.],
+            &[-1., 0.5],
+            &[0.1],
+        );
+        let min = sys.minreal(1e-10);
+        assert_eq!(sys.dim(), min.dim());
+        let mut p1: Vec<_> = sys.poles().iter().map(|p| p.re).collect();
+        let mut p2: Vec<_> = min.poles().iter().map(|p| p.re).collect();
+        p1.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        p2.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        for (a, b) in p1.iter().zip(&p2) {
+            assert_relative_eq!(a, b, max_relative = 1e-10);
+        }
+    }
+
+    #[test]
+    fn closed_loop_static_controller() {
+        let sys = SsGen::<_, Continuous>::new_from_slice(
+            2,
+            1,
+            1,
+            &[0., 1., 0., 0.],
+            &[0., 1.],
+            &[1., 0.],
+            &[0.5],
+        );
+        let ctrl = SsGen::new_from_slice(0, 1, 1, &[], &[], &[], &[-1.]);
+        let cl = sys.closed_loop(&ctrl).unwrap();
+        // E = 1 / (1 + 0.5)
+        let e = 1. / 1.5;
+        assert_relative_eq!(
+            DMatrix::from_row_slice(2, 2, &[0., 1., -e, 0.]),
+            cl.a,
+            max_relative = 1e-12
+        );
+        assert_relative_eq!(
+            DMatrix::from_row_slice(2, 1, &[0., e]),
+            cl.b,
+            max_relative = 1e-12
+        );
+        assert_relative_eq!(
+            DMatrix::from_row_slice(1, 2, &[e, 0.]),
+            cl.c,
+            max_relative = 1e-12
+        );
+        assert_relative_eq!(0.5 * e, cl.d[(0, 0)], max_relative = 1e-12);
+    }
+
+    #[test]
+    fn closed_loop_dynamic_controller() {
+        // Integral controller 1/s on a first order system 1/(s+1).
+        let sys = SsGen::<_, Continuous>::new_from_slice(1, 1, 1, &[-1.], &[1.], &[1.], &[0.]);
+        let ctrl = SsGen::new_from_slice(1, 1, 1, &[0.], &[1.], &[-1.], &[0.]);
+        let cl = sys.closed_loop(&ctrl).unwrap();
+        assert_eq!(DMatrix::from_row_slice(2, 2, &[-1., -1., 1., 0.]), cl.a);
+        assert!(cl.is_stable());
+    }
+
+    #[test]
+    fn observer_based_compensator() {
+        let sys = SsGen::<_, Discrete>::new_from_slice(
+            2,
+            1,
+            1,
+            &[1., 0.1, 0., 1.],
+            &[0., 0.1],
+            &[1., 0.],
+            &[0.2],
+        );
+        let k = DMatrix::from_row_slice(1, 2, &[3., 4.]);
+        let l = DMatrix::from_row_slice(2, 1, &[0.5, 2.]);
+        let comp = sys.observer_compensator(&k, &l);
+        assert_eq!(
+            Dim {
+                states: 2,
+                inputs: 1,
+                outputs: 1
+            },
+            comp.dim()
+        );
+        let a = &sys.a - &sys.b * &k - &l * &sys.c + &l * &sys.d * &k;
+        assert_eq!(a, comp.a);
+        assert_eq!(l, comp.b);
+        assert_eq!(-k, comp.c);
+        assert_eq!(DMatrix::zeros(1, 1), comp.d);
+    }
+
+    #[test]
+    fn observer_based_compensator_tf_matrix() {
+        use crate::transfer_function::matrix::TfMatrix;
+        let sys = SsGen::<_, Continuous>::new_from_slice(1, 1, 1, &[0.], &[1.], &[1.], &[0.]);
+        let k = DMatrix::from_element(1, 1, 2.);
+        let l = DMatrix::from_element(1, 1, 3.);
+        let tfm = TfMatrix::from(sys.observer_compensator(&k, &l));
+        assert_eq!(poly!(5., 1.), tfm.den());
+        assert_eq!(poly!(-6.), tfm[[0, 0]]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn observer_based_compensator_fail() {
+        let sys = SsGen::<_, Continuous>::new_from_slice(1, 1, 1, &[0.], &[1.], &[1.], &[0.]);
+        let k = DMatrix::from_element(1, 2, 2.);
+        let l = DMatrix::from_element(1, 1, 3.);
+        let _ = sys.observer_compensator(&k, &l);
+    }
+
+    #[test]
+    fn closed_loop_ill_posed() {
+        let sys = SsGen::<_, Discrete>::new_from_slice(1, 1, 1, &[0.5], &[1.], &[1.], &[1.]);
+        let ctrl = SsGen::new_from_slice(0, 1, 1, &[], &[], &[], &[1.]);
+        assert!(sys.closed_loop(&ctrl).is_none());
+    }
+
     #[test]
     #[should_panic]
     fn poles_fail() {
@@ -900,6 +2018,19 @@ mod tests {
         assert!(ss.is_err());
     }
 
+    #[test]
+    fn improper_realization() {
+        use crate::transfer_function::continuous::Tf;
+        let tf = Tf::new(
+            Poly::new_from_coeffs(&[1., 1., 1.]),
+            Poly::new_from_coeffs(&[1., 1.]),
+        );
+        let ss = SsGen::new_observability_realization(&tf);
+        assert_eq!(ErrorKind::ImproperTransferFunction, ss.unwrap_err().kind());
+        let ss = SsGen::new_controllability_realization(&tf);
+        assert_eq!(ErrorKind::ImproperTransferFunction, ss.unwrap_err().kind());
+    }
+
     #[test]
     fn failed_controllability_canonical_form() {
         use crate::transfer_function::discrete::Tfz;
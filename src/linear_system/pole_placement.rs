@@ -0,0 +1,452 @@
+//! Pole placement through state feedback
+//!
+//! Robust eigenstructure assignment for multiple input systems, based on the
+//! Kautsky-Nichols-Van Dooren method.
+
+use nalgebra::{ComplexField, DMatrix, DVector, RealField};
+use num_complex::Complex;
+use num_traits::{Float, One, Zero};
+
+use crate::{
+    enums::Time,
+    error::{Error, ErrorKind},
+    linear_system::{controllability_impl, range_basis, SsGen},
+};
+
+/// Maximum number of sweeps of the eigenvectors update.
+const MAX_SWEEPS: usize = 100;
+
+impl<T: ComplexField + Float + RealField, U: Time> SsGen<T, U> {
+    /// Calculate the state feedback gain `K` that places the poles of the
+    /// closed loop system `A - B*K` in the given positions.
+    ///
+    /// When the system has more than one input the gain is not unique, the
+    /// closed loop eigenvectors are chosen to be as orthogonal as possible
+    /// (Kautsky-Nichols-Van Dooren method), so that the placed poles are
+    /// insensitive to perturbations of the system matrices.
+    ///
+    /// Complex poles shall be supplied as conjugate pairs, the conjugates are
+    /// matched with a relative tolerance. The multiplicity of each pole cannot
+    /// be greater than the number of inputs.
+    ///
+    /// # Arguments
+    ///
+    /// * `poles` - Desired poles of the closed loop system
+    ///
+    /// # Errors
+    ///
+    /// It returns an error if complex poles are not supplied as conjugate
+    /// pairs, if the multiplicity of a pole is greater than the number of
+    /// inputs, if the input matrix is not full column rank or the system is
+    /// not controllable.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of poles is not equal to the number of states.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use au::{num_complex::Complex, Ss};
+    /// let sys = Ss::<f64>::new_from_slice(2, 1, 1, &[0., 1., 0., 0.], &[0., 1.], &[1., 0.], &[0.]);
+    /// let k = sys.place(&[Complex::new(-1., 0.), Complex::new(-2., 0.)]).unwrap();
+    /// assert!((k[(0, 0)] - 2.).abs() < 1e-10);
+    /// assert!((k[(0, 1)] - 3.).abs() < 1e-10);
+    /// ```
+    pub fn place(&self, poles: &[Complex<T>]) -> Result<DMatrix<T>, Error> {
+        let n = self.dim.states;
+        let m = self.dim.inputs;
+        assert_eq!(n, poles.len(), "Wrong number of poles.");
+        let poles =
+            pair_conjugates(poles).ok_or_else(|| Error::new_internal(ErrorKind::InvalidData))?;
+
+        if m == 0 || m > n {
+            return Err(Error::new_internal(ErrorKind::NoFullRankInput));
+        }
+        if poles
+            .iter()
+            .any(|p| poles.iter().filter(|q| coincident(p, q)).count() > m)
+        {
+            return Err(Error::new_internal(ErrorKind::ExcessivePoleMultiplicity));
+        }
+
+        // Controllability check.
+        let mr = controllability_impl(n, m, &self.a, &self.b);
+        let eps = T::epsilon();
+        let tol = eps * T::from(n * m).unwrap_or_else(T::one);
+        if range_basis(&mr, tol).ncols() < n {
+            return Err(Error::new_internal(ErrorKind::NoControllableSystem));
+        }
+
+        // B = [U0 U1] * [Z 0]'
+        let mut b_ext = DMatrix::zeros(n, n);
+        b_ext.columns_mut(0, m).copy_from(&self.b);
+        let qr = b_ext.qr();
+        let q = qr.q();
+        let z = qr.r().slice((0, 0), (m, m)).into_owned();
+        let z_max = z
+            .diagonal()
+            .iter()
+            .fold(T::zero(), |acc, &d| Float::max(acc, Float::abs(d)));
+        if z.diagonal().iter().any(|&d| Float::abs(d) <= tol * z_max) {
+            return Err(Error::new_internal(ErrorKind::NoFullRankInput));
+        }
+        let u0 = q.columns(0, m).into_owned();
+        let u1 = q.columns(m, n - m).map(|x| Complex::new(x, T::zero()));
+
+        // Orthonormal basis S_j of the space where the eigenvector of each
+        // pole shall lie: null space of U1'*(A - λ_j*I).
+        let a_c = self.a.map(|x| Complex::new(x, T::zero()));
+        let s: Vec<_> = poles
+            .iter()
+            .map(|&p| {
+                let a_p = &a_c - DMatrix::from_diagonal_element(n, n, p);
+                null_space(&u1.tr_mul(&a_p), n, m)
+            })
+            .collect();
+
+        // Initial eigenvectors.
+        let mut x = DMatrix::from_fn(n, n, |_, _| Complex::zero());
+        for j in 0..n {
+            if poles[j].im.is_negative() {
+                continue;
+            }
+            let e = DVector::from_fn(n, |r, _| {
+                if r == j {
+                    Complex::one()
+                } else {
+                    Complex::zero()
+                }
+            });
+            let mut xj = &s[j] * (s[j].adjoint() * e);
+            if xj.norm() <= eps {
+                xj = s[j].column(0).into_owned();
+            }
+            set_eigenvector(&mut x, j, &xj.normalize(), &poles);
+        }
+
+        // Update each eigenvector to be as orthogonal as possible to the
+        // other ones.
+        let conv_tol = Float::sqrt(eps);
+        for _ in 0..MAX_SWEEPS {
+            let mut max_change = T::zero();
+            for j in 0..n {
+                if poles[j].im.is_negative() {
+                    continue;
+                }
+                let mut others = x.clone();
+                others.column_mut(j).fill(Complex::zero());
+                let y = least_singular_vector(others);
+                let xj = &s[j] * (s[j].adjoint() * y);
+                let norm = xj.norm();
+                if norm <= eps {
+                    continue;
+                }
+                let xj = xj / Complex::new(norm, T::zero());
+                let cos = x.column(j).dotc(&xj).norm();
+                max_change = Float::max(max_change, T::one() - cos);
+                set_eigenvector(&mut x, j, &xj, &poles);
+            }
+            if max_change < conv_tol {
+                break;
+            }
+        }
+
+        // A - B*K = X*Λ*X^-1  =>  Z*K = U0'*(A - X*Λ*X^-1)
+        let lambda = DMatrix::from_diagonal(&DVector::from_column_slice(&poles));
+        let x_inv = x
+            .clone()
+            .try_inverse()
+            .ok_or_else(|| Error::new_internal(ErrorKind::NoControllableSystem))?;
+        let closed_loop = (x * lambda * x_inv).map(|c| c.re);
+        let rhs = u0.tr_mul(&(&self.a - closed_loop));
+        z.solve_upper_triangular(&rhs)
+            .ok_or_else(|| Error::new_internal(ErrorKind::NoFullRankInput))
+    }
+}
+
+/// Check if two poles coincide up to a relative tolerance.
+fn coincident<T: Float>(p: &Complex<T>, q: &Complex<T>) -> bool {
+    (p - q).norm() <= Float::sqrt(T::epsilon()) * Float::max(p.norm(), q.norm())
+}
+
+/// Order the poles so that each complex pole with positive imaginary part is
+/// immediately followed by its conjugate. The conjugate is matched up to a
+/// relative tolerance and it is replaced by the exact conjugate.
+///
+/// It returns `None` if complex poles are not supplied as conjugate pairs.
+fn pair_conjugates<T: Float>(poles: &[Complex<T>]) -> Option<Vec<Complex<T>>> {
+    let mut negatives: Vec<_> = poles.iter().filter(|p| p.im < T::zero()).collect();
+    let mut paired = Vec::with_capacity(poles.len());
+    for p in poles.iter().filter(|p| p.im >= T::zero()) {
+        paired.push(*p);
+        if p.im > T::zero() {
+            let pos = negatives.iter().position(|&&q| coincident(&q, &p.conj()))?;
+            negatives.remove(pos);
+            paired.push(p.conj());
+        }
+    }
+    if negatives.is_empty() {
+        Some(paired)
+    } else {
+        None
+    }
+}
+
+/// Set the `j`-th column of the eigenvector matrix, if the corresponding pole
+/// is complex also the conjugate eigenvector is set in the following column.
+fn set_eigenvector<T: Float + RealField>(
+    x: &mut DMatrix<Complex<T>>,
+    j: usize,
+    xj: &DVector<Complex<T>>,
+    poles: &[Complex<T>],
+) {
+    x.set_column(j, xj);
+    if poles[j].im > T::zero() {
+        x.set_column(j + 1, &xj.map(|c| c.conj()));
+    }
+}
+
+/// Orthonormal basis of the null space of a (r x n) matrix, whose dimension
+/// is assumed to be `dim`.
+fn null_space<T: Float + RealField>(
+    mat: &DMatrix<Complex<T>>,
+    n: usize,
+    dim: usize,
+) -> DMatrix<Complex<T>> {
+    if mat.nrows() == 0 {
+        return DMatrix::identity(n, n);
+    }
+    // Extend the matrix to a square one to get the full set of right
+    // singular vectors.
+    let mut ext = DMatrix::from_fn(n, n, |_, _| Complex::zero());
+    ext.rows_mut(0, mat.nrows()).copy_from(mat);
+    let svd = ext.svd(false, true);
+    let sv = svd.singular_values;
+    let v_t = svd.v_t.expect("Right singular vectors have been requested");
+    let mut idx: Vec<usize> = (0..n).collect();
+    idx.sort_by(|&i, &j| {
+        sv[i]
+            .partial_cmp(&sv[j])
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    DMatrix::from_fn(n, dim, |r, c| v_t[(idx[c], r)].conj())
+}
+
+/// Left singular vector of a square matrix associated to the smallest
+/// singular value.
+fn least_singular_vector<T: Float + RealField>(mat: DMatrix<Complex<T>>) -> DVector<Complex<T>> {
+    let svd = mat.svd(true, false);
+    let u = svd.u.expect("Left singular vectors have been requested");
+    let (idx, _) = svd.singular_values.iter().enumerate().fold(
+        (0, T::infinity()),
+        |(i_min, s_min), (i, &s)| {
+            if s < s_min {
+                (i, s)
+            } else {
+                (i_min, s_min)
+            }
+        },
+    );
+    u.column(idx).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Continuous, Discrete};
+
+    /// Sort poles by real part and then by imaginary part.
+    fn sorted(mut p: Vec<Complex<f64>>) -> Vec<Complex<f64>> {
+        p.sort_by(|a, b| {
+            a.re.partial_cmp(&b.re)
+                .unwrap()
+                .then(a.im.partial_cmp(&b.im).unwrap())
+        });
+        p
+    }
+
+    fn closed_loop_poles<U: Time>(sys: &SsGen<f64, U>, k: &DMatrix<f64>) -> Vec<Complex<f64>> {
+        let a = &sys.a - &sys.b * k;
+        let cl = SsGen::<f64, U>::new_from_matrices(a, sys.b.clone(), sys.c.clone(), sys.d.clone());
+        sorted(cl.poles())
+    }
+
+    #[test]
+    fn place_siso() {
+        let sys = SsGen::<_, Continuous>::new_from_slice(
+            3,
+            1,
+            1,
+            &[0., 1., 0., 0., 0., 1., -1., -2., -3.],
+            &[0., 0., 1.],
+            &[1., 0., 0.],
+            &[0.],
+        );
+        let poles = vec![
+            Complex::new(-1., 0.),
+            Complex::new(-2., 1.),
+            Complex::new(-2., -1.),
+        ];
+        let k = sys.place(&poles).unwrap();
+        for (e, a) in sorted(poles).iter().zip(&closed_loop_poles(&sys, &k)) {
+            assert_relative_eq!(e.re, a.re, max_relative = 1e-8);
+            assert_relative_eq!(e.im, a.im, epsilon = 1e-8);
+        }
+    }
+
+    #[test]
+    fn place_mimo() {
+        let sys = SsGen::<_, Continuous>::new_from_slice(
+            4,
+            2,
+            1,
+            &[
+                0., 1., 0., 0., 2., -1., 1., 0., 0., 0., 0., 1., 1., 0., -3., 0.5,
+            ],
+            &[0., 0., 1., 0., 0., 0., 0., 1.],
+            &[1., 0., 0., 0.],
+            &[0., 0.],
+        );
+        let poles = vec![
+            Complex::new(-1., 2.),
+            Complex::new(-3., 0.),
+            Complex::new(-1., -2.),
+            Complex::new(-4., 0.),
+        ];
+        let k = sys.place(&poles).unwrap();
+        assert_eq!((2, 4), k.shape());
+        for (e, a) in sorted(poles).iter().zip(&closed_loop_poles(&sys, &k)) {
+            assert_relative_eq!(e.re, a.re, max_relative = 1e-8);
+            assert_relative_eq!(e.im, a.im, epsilon = 1e-8);
+        }
+    }
+
+    #[test]
+    fn place_repeated_poles() {
+        let sys = SsGen::<_, Discrete>::new_from_slice(
+            2,
+            2,
+            1,
+            &[1., 1., 0., 1.],
+            &[1., 0., 0., 1.],
+            &[1., 0.],
+            &[0., 0.],
+        );
+        let poles = vec![Complex::new(0.5, 0.), Complex::new(0.5, 0.)];
+        let k = sys.place(&poles).unwrap();
+        for a in closed_loop_poles(&sys, &k) {
+            assert_relative_eq!(0.5, a.re, max_relative = 1e-8);
+            assert_relative_eq!(0., a.im, epsilon = 1e-8);
+        }
+    }
+
+    #[test]
+    fn place_uncontrollable() {
+        let sys = SsGen::<_, Continuous>::new_from_slice(
+            2,
+            1,
+            1,
+            &[-1., 0., 0., -2.],
+            &[1., 0.],
+            &[1., 1.],
+            &[0.],
+        );
+        let poles = [Complex::new(-1., 0.), Complex::new(-3., 0.)];
+        assert_eq!(
+            ErrorKind::NoControllableSystem,
+            sys.place(&poles).unwrap_err().kind()
+        );
+    }
+
+    #[test]
+    fn place_rank_deficient_input() {
+        let sys = SsGen::<_, Continuous>::new_from_slice(
+            2,
+            2,
+            1,
+            &[0., 1., 0., 0.],
+            &[0., 0., 1., 1.],
+            &[1., 0.],
+            &[0., 0.],
+        );
+        let poles = [Complex::new(-1., 0.), Complex::new(-3., 0.)];
+        assert_eq!(
+            ErrorKind::NoFullRankInput,
+            sys.place(&poles).unwrap_err().kind()
+        );
+    }
+
+    #[test]
+    fn place_no_conjugate_poles() {
+        let sys = SsGen::<_, Continuous>::new_from_slice(
+            2,
+            1,
+            1,
+            &[0., 1., 0., 0.],
+            &[0., 1.],
+            &[1., 0.],
+            &[0.],
+        );
+        let res = sys.place(&[Complex::new(-1., 1.), Complex::new(-1., 2.)]);
+        assert_eq!(ErrorKind::InvalidData, res.unwrap_err().kind());
+        let res = sys.place(&[Complex::new(-1., 1.), Complex::new(-2., 0.)]);
+        assert_eq!(ErrorKind::InvalidData, res.unwrap_err().kind());
+    }
+
+    #[test]
+    fn place_rounded_conjugate_poles() {
+        let sys = SsGen::<_, Continuous>::new_from_slice(
+            2,
+            1,
+            1,
+            &[0., 1., 0., 0.],
+            &[0., 1.],
+            &[1., 0.],
+            &[0.],
+        );
+        let p = Complex::new(-0.1, 0.3) * 10.;
+        let q = Complex::new(-1., -3. * (1. + f64::EPSILON));
+        assert_ne!(p.conj(), q);
+        let k = sys.place(&[p, q]).unwrap();
+        // s^2 + 2s + 10
+        assert_relative_eq!(10., k[(0, 0)], max_relative = 1e-10);
+        assert_relative_eq!(2., k[(0, 1)], max_relative = 1e-10);
+    }
+
+    #[test]
+    fn place_excessive_multiplicity() {
+        let sys = SsGen::<_, Continuous>::new_from_slice(
+            2,
+            1,
+            1,
+            &[0., 1., 0., 0.],
+            &[0., 1.],
+            &[1., 0.],
+            &[0.],
+        );
+        let poles = [Complex::new(-1., 0.), Complex::new(-1., 0.)];
+        assert_eq!(
+            ErrorKind::ExcessivePoleMultiplicity,
+            sys.place(&poles).unwrap_err().kind()
+        );
+    }
+
+    #[test]
+    fn conjugate_pairing() {
+        let p = pair_conjugates(&[
+            Complex::new(-1., -1.),
+            Complex::new(-2., 0.),
+            Complex::new(-1., 1.),
+        ])
+        .unwrap();
+        assert_eq!(
+            vec![
+                Complex::new(-2., 0.),
+                Complex::new(-1., 1.),
+                Complex::new(-1., -1.)
+            ],
+            p
+        );
+    }
+}
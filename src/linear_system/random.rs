@@ -0,0 +1,277 @@
+//! # Random systems
+//!
+//! Generation of random stable state-space systems, useful to test
+//! algorithms on a large variety of systems.
+//!
+//! The location of the poles is controlled by a `RandomPoles` distribution.
+//! The state matrix is obtained with a random orthogonal similarity
+//! transformation of the block diagonal matrix of the poles, while the
+//! input, output and feedthrough matrices have entries uniformly distributed
+//! in the interval [-1, 1].
+
+use nalgebra::{DMatrix, RealField};
+use num_traits::Float;
+use rand::Rng;
+
+/// Distribution of the poles of a random system.
+///
+/// For continuous time systems the magnitude is the natural frequency of the
+/// poles, for discrete time systems it is the modulus of the poles.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RandomPoles {
+    /// Interval of the magnitude of the poles
+    magnitude: (f64, f64),
+    /// Interval of the damping of complex conjugate continuous poles
+    damping: (f64, f64),
+    /// Probability that a couple of poles is complex conjugate
+    complex_probability: f64,
+}
+
+impl RandomPoles {
+    /// Create a distribution of poles with magnitude in the given interval.
+    /// The damping of complex continuous poles is in the interval [0.1, 1]
+    /// and half of the couples of poles are complex conjugate.
+    ///
+    /// # Arguments
+    ///
+    /// * `min` - Minimum magnitude of the poles
+    /// * `max` - Maximum magnitude of the poles
+    ///
+    /// # Panics
+    ///
+    /// Panics if the minimum magnitude is negative or greater than the
+    /// maximum magnitude.
+    ///
+    /// # Example
+    /// ```
+    /// use au::linear_system::random::RandomPoles;
+    /// let p = RandomPoles::new(0.5, 2.);
+    /// assert_eq!((0.5, 2.), p.magnitude());
+    /// ```
+    #[must_use]
+    pub fn new(min: f64, max: f64) -> Self {
+        assert!(0. <= min && min <= max, "Invalid magnitude interval");
+        Self {
+            magnitude: (min, max),
+            damping: (0.1, 1.),
+            complex_probability: 0.5,
+        }
+    }
+
+    /// Default distribution for continuous time systems,
+    /// natural frequencies in the interval [0.1, 10].
+    #[must_use]
+    pub fn continuous() -> Self {
+        Self::new(0.1, 10.)
+    }
+
+    /// Default distribution for discrete time systems,
+    /// modulus of the poles in the interval [0, 0.95].
+    #[must_use]
+    pub fn discrete() -> Self {
+        Self::new(0., 0.95)
+    }
+
+    /// Set the interval of the damping of complex conjugate continuous poles.
+    ///
+    /// # Arguments
+    ///
+    /// * `min` - Minimum damping
+    /// * `max` - Maximum damping
+    ///
+    /// # Panics
+    ///
+    /// Panics if the interval is not included in (0, 1].
+    #[must_use]
+    pub fn with_damping(mut self, min: f64, max: f64) -> Self {
+        assert!(
+            0. < min && min <= max && max <= 1.,
+            "Invalid damping interval"
+        );
+        self.damping = (min, max);
+        self
+    }
+
+    /// Set the probability that a couple of poles is complex conjugate.
+    ///
+    /// # Arguments
+    ///
+    /// * `p` - Probability in the interval [0, 1]
+    ///
+    /// # Panics
+    ///
+    /// Panics if the probability is not in the interval [0, 1].
+    #[must_use]
+    pub fn with_complex_probability(mut self, p: f64) -> Self {
+        assert!((0. ..=1.).contains(&p), "Invalid probability");
+        self.complex_probability = p;
+        self
+    }
+
+    /// Get the interval of the magnitude of the poles
+    #[must_use]
+    pub fn magnitude(&self) -> (f64, f64) {
+        self.magnitude
+    }
+
+    /// Get the interval of the damping of complex conjugate continuous poles
+    #[must_use]
+    pub fn damping(&self) -> (f64, f64) {
+        self.damping
+    }
+
+    /// Get the probability that a couple of poles is complex conjugate
+    #[must_use]
+    pub fn complex_probability(&self) -> f64 {
+        self.complex_probability
+    }
+}
+
+/// Uniform random number in the closed interval [a, b].
+fn uniform<R: Rng + ?Sized>(rng: &mut R, (a, b): (f64, f64)) -> f64 {
+    a + (b - a) * rng.gen::<f64>()
+}
+
+/// Block diagonal matrix of the poles, 1x1 blocks for real poles
+/// and 2x2 blocks for complex conjugate poles.
+fn poles_matrix<R: Rng + ?Sized>(
+    n: usize,
+    dist: &RandomPoles,
+    discrete: bool,
+    rng: &mut R,
+) -> DMatrix<f64> {
+    let mut a = DMatrix::zeros(n, n);
+    let mut i = 0;
+    while i < n {
+        let m = uniform(rng, dist.magnitude);
+        if i + 1 < n && rng.gen::<f64>() < dist.complex_probability {
+            let (re, im) = if discrete {
+                let theta = std::f64::consts::PI * rng.gen::<f64>();
+                (m * theta.cos(), m * theta.sin())
+            } else {
+                let zeta = uniform(rng, dist.damping);
+                (-zeta * m, m * (1. - zeta * zeta).sqrt())
+            };
+            a[(i, i)] = re;
+            a[(i, i + 1)] = im;
+            a[(i + 1, i)] = -im;
+            a[(i + 1, i + 1)] = re;
+            i += 2;
+        } else {
+            a[(i, i)] = if discrete && rng.gen::<bool>() { m } else { -m };
+            i += 1;
+        }
+    }
+    a
+}
+
+/// Matrix with entries uniformly distributed in the interval [-1, 1].
+fn uniform_matrix<R: Rng + ?Sized>(rows: usize, cols: usize, rng: &mut R) -> DMatrix<f64> {
+    DMatrix::from_fn(rows, cols, |_, _| uniform(rng, (-1., 1.)))
+}
+
+/// Generate the matrices (A, B, C, D) of a random stable system.
+///
+/// # Arguments
+///
+/// * `states` - number of states
+/// * `inputs` - number of inputs
+/// * `outputs` - number of outputs
+/// * `dist` - distribution of the poles
+/// * `discrete` - true for discrete time systems
+/// * `rng` - random number generator
+pub(crate) fn random_matrices<T, R>(
+    (states, inputs, outputs): (usize, usize, usize),
+    dist: &RandomPoles,
+    discrete: bool,
+    rng: &mut R,
+) -> [DMatrix<T>; 4]
+where
+    T: Float + RealField,
+    R: Rng + ?Sized,
+{
+    let lambda = poles_matrix(states, dist, discrete, rng);
+    let q = uniform_matrix(states, states, rng).qr().q();
+    let a = &q * lambda * q.transpose();
+    let b = uniform_matrix(states, inputs, rng);
+    let c = uniform_matrix(outputs, states, rng);
+    let d = uniform_matrix(outputs, inputs, rng);
+    let conv = |m: DMatrix<f64>| m.map(|x| T::from(x).unwrap());
+    [conv(a), conv(b), conv(c), conv(d)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Ss, Ssd};
+    use proptest::prelude::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    proptest! {
+        #[test]
+        fn qc_rand_stable(seed: u64, n in 0_usize..8, m in 1_usize..4, p in 1_usize..4) {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let poles = RandomPoles::new(0.5, 5.);
+            let sys = Ss::<f64>::rand_stable_with(n, m, p, &poles, &mut rng);
+            assert_eq!((n, m, p), (sys.dim().states(), sys.dim().inputs(), sys.dim().outputs()));
+            for pole in sys.poles() {
+                assert!(pole.re < 0.);
+                assert!(pole.norm() > 0.5 - 1e-8 && pole.norm() < 5. + 1e-8);
+            }
+        }
+    }
+
+    #[test]
+    fn rand_stable_real_poles() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let poles = RandomPoles::new(1., 3.)
+            .with_complex_probability(0.)
+            .with_damping(0.5, 0.5);
+        let sys = Ss::<f64>::rand_stable_with(5, 2, 2, &poles, &mut rng);
+        for p in sys.poles() {
+            assert_abs_diff_eq!(0., p.im, epsilon = 1e-8);
+            assert!((-3. - 1e-8..=-1. + 1e-8).contains(&p.re));
+        }
+    }
+
+    #[test]
+    fn rand_stable_complex_poles() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let poles = RandomPoles::new(1., 3.)
+            .with_complex_probability(1.)
+            .with_damping(0.2, 0.2);
+        let sys = Ss::<f64>::rand_stable_with(4, 1, 1, &poles, &mut rng);
+        for p in sys.poles() {
+            assert_relative_eq!(0.2, -p.re / p.norm(), max_relative = 1e-6);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn rand_stable_zero_magnitude() {
+        let _ =
+            Ss::<f64>::rand_stable_with(2, 1, 1, &RandomPoles::discrete(), &mut rand::thread_rng());
+    }
+
+    #[test]
+    fn rand_stable_discrete() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let poles = RandomPoles::new(0.3, 0.8);
+        for n in 0..6 {
+            let sys = Ssd::<f64>::rand_stable_with(n, 2, 1, &poles, &mut rng);
+            assert_eq!(n, sys.dim().states());
+            assert!(sys.is_stable());
+            for p in sys.poles() {
+                assert!(p.norm() > 0.3 - 1e-8 && p.norm() < 0.8 + 1e-8);
+            }
+        }
+        assert!(Ssd::<f32>::rand_stable(3, 1, 1).is_stable());
+    }
+
+    #[test]
+    #[should_panic]
+    fn rand_stable_unstable_magnitude() {
+        let poles = RandomPoles::new(0.5, 1.5);
+        let _ = Ssd::<f64>::rand_stable_with(2, 1, 1, &poles, &mut rand::thread_rng());
+    }
+}
@@ -8,23 +8,39 @@
 //!
 //! `Rkf45` is an explicit Runge-Kutta-Fehlberg of order 4 and 5 with 6 steps
 //! and adaptive integration step, it is suitable for non stiff systems.
+//! Its dense output `Rkf45Dense` emits the time evolution on a uniform time
+//! grid.
 //!
 //! `Radau` is an implicit Runge-Kutta-Radau of order 3 with 2 steps, it is
 //! suitable for stiff systems.
+//!
+//! The solvers are generic over the floating point type of the system, both
+//! `f32` and `f64` systems can be simulated.
+//!
+//! The input is a function of time that is evaluated at the time of each stage
+//! of the method, so time-varying inputs (ramps, sinusoids, arbitrary
+//! excitations) are integrated and not held constant during the step.
 
 use approx::{AbsDiffEq, RelativeEq};
 use nalgebra::{ComplexField, DMatrix, DVector, Dynamic, Scalar, SimdPartialOrd, LU};
 use num_traits::{Float, Signed};
 
 use std::{
+    fmt::Display,
+    io::{self, Write},
+    iter::FromIterator,
     marker::Sized,
     ops::{AddAssign, MulAssign, SubAssign},
 };
 
-use crate::{linear_system::continuous::Ss, units::Seconds};
+use crate::{
+    enums::Time,
+    linear_system::{continuous::Ss, SsGen},
+    units::Seconds,
+};
 
 /// Define the order of the Runge-Kutta method.
-#[derive(Clone, Debug)]
+#[derive(Clone, Copy, Debug)]
 pub(super) enum Order {
     /// Runge-Kutta method of order 2.
     Rk2,
@@ -32,15 +48,40 @@ pub(super) enum Order {
     Rk4,
 }
 
+/// Linear system whose time evolution is calculated by the explicit
+/// Runge-Kutta solvers. The solvers only use the products of the system
+/// matrices with the state and input vectors, hence the state matrix may be
+/// stored in any format.
+pub trait LinearDynamics<T: Scalar> {
+    /// Derivative of the state, `A*x + B*u`
+    fn derivative(&self, x: &DVector<T>, u: &DVector<T>) -> DVector<T>;
+
+    /// Output of the system, `C*x + D*u`
+    fn output(&self, x: &DVector<T>, u: &DVector<T>) -> DVector<T>;
+}
+
+impl<T> LinearDynamics<T> for Ss<T>
+where
+    T: AddAssign + Float + MulAssign + Scalar,
+{
+    fn derivative(&self, x: &DVector<T>, u: &DVector<T>) -> DVector<T> {
+        &self.a * x + &self.b * u
+    }
+
+    fn output(&self, x: &DVector<T>, u: &DVector<T>) -> DVector<T> {
+        &self.c * x + &self.d * u
+    }
+}
+
 /// Struct for the time evolution of a linear system
 #[derive(Clone, Debug)]
-pub struct Rk<'a, F, T>
+pub struct Rk<'a, F, T, S = Ss<T>>
 where
     F: Fn(Seconds<T>) -> Vec<T>,
     T: Float + Scalar,
 {
     /// Linear system
-    sys: &'a Ss<T>,
+    sys: &'a S,
     /// Input function
     input: F,
     /// State vector.
@@ -57,10 +98,11 @@ where
     order: Order,
 }
 
-impl<'a, F, T> Rk<'a, F, T>
+impl<'a, F, T, S> Rk<'a, F, T, S>
 where
     F: Fn(Seconds<T>) -> Vec<T>,
     T: AddAssign + Float + MulAssign + RkConst + Scalar,
+    S: LinearDynamics<T>,
 {
     /// Create the solver for a Runge-Kutta method.
     ///
@@ -72,17 +114,10 @@ where
     /// * `h` - integration time interval
     /// * `n` - integration steps
     /// * `order` - order of the solver
-    pub(super) fn new(
-        sys: &'a Ss<T>,
-        u: F,
-        x0: &[T],
-        h: Seconds<T>,
-        n: usize,
-        order: Order,
-    ) -> Self {
+    pub(super) fn new(sys: &'a S, u: F, x0: &[T], h: Seconds<T>, n: usize, order: Order) -> Self {
         let start = DVector::from_vec(u(Seconds(T::zero())));
         let state = DVector::from_column_slice(x0);
-        let output = &sys.c * &state + &sys.d * &start;
+        let output = sys.output(&state, &start);
         Self {
             sys,
             input: u,
@@ -108,23 +143,21 @@ where
         })
     }
 
-    /// Runge-Kutta order 2 method.
-    #[allow(clippy::cast_precision_loss)]
-    fn main_iteration_rk2(&mut self) -> Option<Step<T>> {
-        // y_n+1 = y_n + 1/2(k1 + k2) + O(h^3)
-        // k1 = h*f(t_n, y_n)
-        // k2 = h*f(t_n + h, y_n + k1)
-        // Retrun None if conversion fails.
-        let init_time = Seconds(T::from(self.index - 1)? * self.h.0);
+    /// Runge-Kutta method of the given order.
+    fn main_iteration(&mut self) -> Option<Step<T>> {
+        // Return None if conversion fails.
+        let init_time = T::from(self.index - 1)? * self.h.0;
         let end_time = Seconds(T::from(self.index)? * self.h.0);
-        let u = DVector::from_vec((self.input)(init_time));
-        let uh = DVector::from_vec((self.input)(end_time));
-        let bu = &self.sys.b * &u;
-        let buh = &self.sys.b * &uh;
-        let k1 = (&self.sys.a * &self.state + &bu) * self.h.0;
-        let k2 = (&self.sys.a * (&self.state + &k1) + &buh) * self.h.0;
-        self.state += (k1 + k2) * T::_05;
-        self.output = &self.sys.c * &self.state + &self.sys.d * &uh;
+        let (sys, input) = (self.sys, &self.input);
+        self.state = rk_step(
+            |t, x| sys.derivative(x, &DVector::from_vec(input(Seconds(t)))),
+            init_time,
+            &self.state,
+            self.h.0,
+            self.order,
+        );
+        let u_end = DVector::from_vec((self.input)(end_time));
+        self.output = self.sys.output(&self.state, &u_end);
 
         self.index += 1;
         Some(Step {
@@ -134,39 +167,21 @@ where
         })
     }
 
-    /// Runge-Kutta order 4 method.
-    #[allow(clippy::cast_precision_loss, clippy::similar_names)]
-    fn main_iteration_rk4(&mut self) -> Option<Step<T>> {
-        // y_n+1 = y_n + h/6(k1 + 2*k2 + 2*k3 + k4) + O(h^4)
-        // k1 = f(t_n, y_n)
-        // k2 = f(t_n + h/2, y_n + h/2 * k1)
-        // k3 = f(t_n + h/2, y_n + h/2 * k2)
-        // k2 = f(t_n + h, y_n + h*k3)
-        // Return None if conversion fails
-        let init_time = Seconds(T::from(self.index - 1)? * self.h.0);
-        let mid_time = Seconds(init_time.0 + T::_05 * self.h.0);
-        let end_time = Seconds(T::from(self.index)? * self.h.0);
-        let u = DVector::from_vec((self.input)(init_time));
-        let u_mid = DVector::from_vec((self.input)(mid_time));
-        let u_end = DVector::from_vec((self.input)(end_time));
-        let bu = &self.sys.b * &u;
-        let bu_mid = &self.sys.b * &u_mid;
-        let bu_end = &self.sys.b * &u_end;
-        let k1 = &self.sys.a * &self.state + &bu;
-        let k2 = &self.sys.a * (&self.state + &k1 * (T::_05 * self.h.0)) + &bu_mid;
-        let k3 = &self.sys.a * (&self.state + &k2 * (T::_05 * self.h.0)) + &bu_mid;
-        let k4 = &self.sys.a * (&self.state + &k3 * self.h.0) + &bu_end;
-        let n_2 = T::A_RK[0];
-        let n_6 = T::A_RK[1];
-        self.state += (k1 + k2 * n_2 + k3 * n_2 + k4) * (self.h.0 / n_6);
-        self.output = &self.sys.c * &self.state + &self.sys.d * &u_end;
-
-        self.index += 1;
-        Some(Step {
-            time: end_time,
-            state: self.state.as_slice().to_vec(),
-            output: self.output.as_slice().to_vec(),
-        })
+    /// Write the time evolution in CSV format, see
+    /// [`TimeResponse::to_csv`](struct.TimeResponse.html#method.to_csv).
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - destination of the CSV data
+    ///
+    /// # Errors
+    ///
+    /// It returns an error if the writing fails.
+    pub fn to_csv<W: Write>(self, writer: W) -> io::Result<()>
+    where
+        T: Display,
+    {
+        self.collect::<TimeResponse<T>>().to_csv(writer)
     }
 }
 
@@ -195,11 +210,55 @@ impl_rk_const!(f32);
 impl_rk_const!(f64);
 //////
 
+/// Runge-Kutta step with fixed size, shared by the solvers of linear
+/// systems regardless of the storage of their matrices.
+///
+/// # Arguments
+///
+/// * `f` - derivative of the state as a function of time and state
+/// * `t` - initial time of the step
+/// * `x` - initial state of the step
+/// * `h` - integration interval
+/// * `order` - order of the method
+#[allow(clippy::similar_names)]
+pub(super) fn rk_step<T, F>(f: F, t: T, x: &DVector<T>, h: T, order: Order) -> DVector<T>
+where
+    T: AddAssign + Float + MulAssign + RkConst + Scalar,
+    F: Fn(T, &DVector<T>) -> DVector<T>,
+{
+    match order {
+        Order::Rk2 => {
+            // y_n+1 = y_n + 1/2(k1 + k2) + O(h^3)
+            // k1 = h*f(t_n, y_n)
+            // k2 = h*f(t_n + h, y_n + k1)
+            let k1 = f(t, x) * h;
+            let k2 = f(t + h, &(x + &k1)) * h;
+            x + (k1 + k2) * T::_05
+        }
+        Order::Rk4 => {
+            // y_n+1 = y_n + h/6(k1 + 2*k2 + 2*k3 + k4) + O(h^4)
+            // k1 = f(t_n, y_n)
+            // k2 = f(t_n + h/2, y_n + h/2 * k1)
+            // k3 = f(t_n + h/2, y_n + h/2 * k2)
+            // k4 = f(t_n + h, y_n + h*k3)
+            let half_h = T::_05 * h;
+            let k1 = f(t, x);
+            let k2 = f(t + half_h, &(x + &k1 * half_h));
+            let k3 = f(t + half_h, &(x + &k2 * half_h));
+            let k4 = f(t + h, &(x + &k3 * h));
+            let n_2 = T::A_RK[0];
+            let n_6 = T::A_RK[1];
+            x + (k1 + k2 * n_2 + k3 * n_2 + k4) * (h / n_6)
+        }
+    }
+}
+
 /// Implementation of the Iterator trait for the `Rk` struct
-impl<'a, F, T> Iterator for Rk<'a, F, T>
+impl<'a, F, T, S> Iterator for Rk<'a, F, T, S>
 where
     F: Fn(Seconds<T>) -> Vec<T>,
     T: AddAssign + Float + MulAssign + RkConst + Scalar,
+    S: LinearDynamics<T>,
 {
     type Item = Step<T>;
 
@@ -209,10 +268,7 @@ where
         } else if self.index == 0 {
             self.initial_step()
         } else {
-            match self.order {
-                Order::Rk2 => self.main_iteration_rk2(),
-                Order::Rk4 => self.main_iteration_rk4(),
-            }
+            self.main_iteration()
         }
     }
 }
@@ -229,6 +285,15 @@ pub struct Step<T: Float> {
 }
 
 impl<T: Float> Step<T> {
+    /// Create a new step of the time evolution.
+    pub(crate) fn new(time: Seconds<T>, state: Vec<T>, output: Vec<T>) -> Self {
+        Self {
+            time,
+            state,
+            output,
+        }
+    }
+
     /// Get the time of the current step
     pub fn time(&self) -> Seconds<T> {
         self.time
@@ -247,13 +312,13 @@ impl<T: Float> Step<T> {
 
 /// Struct for the time evolution of a linear system
 #[derive(Clone, Debug)]
-pub struct Rkf45<'a, F, T>
+pub struct Rkf45<'a, F, T, S = Ss<T>>
 where
     F: Fn(Seconds<T>) -> Vec<T>,
     T: Float + Scalar,
 {
     /// Linear system
-    sys: &'a Ss<T>,
+    sys: &'a S,
     /// Input function
     input: F,
     /// State vector.
@@ -268,14 +333,19 @@ where
     time: Seconds<T>,
     /// Tolerance
     tol: T,
+    /// Minimum integration interval
+    h_min: T,
+    /// Maximum integration interval
+    h_max: T,
     /// Is initial step
     initial_step: bool,
 }
 
-impl<'a, F, T> Rkf45<'a, F, T>
+impl<'a, F, T, S> Rkf45<'a, F, T, S>
 where
     F: Fn(Seconds<T>) -> Vec<T>,
     T: AddAssign + Float + MulAssign + Rkf45Const + Scalar + Signed + SimdPartialOrd + SubAssign,
+    S: LinearDynamics<T>,
 {
     /// Create a solver using Runge-Kutta-Fehlberg method
     ///
@@ -287,8 +357,11 @@ where
     /// * `h` - integration time interval
     /// * `limit` - time limit of the evaluation
     /// * `tol` - error tolerance
+    ///
+    /// The integration interval is bounded between the time limit multiplied
+    /// by the machine epsilon and the time limit.
     pub(super) fn new(
-        sys: &'a Ss<T>,
+        sys: &'a S,
         u: F,
         x0: &[T],
         h: Seconds<T>,
@@ -298,7 +371,7 @@ where
         let start = DVector::from_vec(u(Seconds(T::zero())));
         let state = DVector::from_column_slice(x0);
         // Calculate the output at time 0.
-        let output = &sys.c * &state + &sys.d * &start;
+        let output = sys.output(&state, &start);
         Self {
             sys,
             input: u,
@@ -308,10 +381,48 @@ where
             limit,
             time: Seconds(T::zero()),
             tol,
+            h_min: limit.0 * T::epsilon(),
+            h_max: limit.0,
             initial_step: true,
         }
     }
 
+    /// Set the minimum integration interval. When the error tolerance cannot
+    /// be met with the minimum interval, the step is accepted anyway and the
+    /// error is reported in the result.
+    ///
+    /// # Arguments
+    ///
+    /// * `h_min` - minimum integration interval
+    #[must_use]
+    pub fn with_min_step(mut self, h_min: Seconds<T>) -> Self {
+        self.h_min = h_min.0;
+        self
+    }
+
+    /// Set the maximum integration interval.
+    ///
+    /// # Arguments
+    ///
+    /// * `h_max` - maximum integration interval
+    ///
+    /// # Example
+    /// ```
+    /// use au::{Seconds, Ss};
+    /// let sys = Ss::new_from_slice(1, 1, 1, &[-1.], &[1.], &[1.], &[0.]);
+    /// let evo: Vec<_> = sys
+    ///     .rkf45(|_| vec![1.], &[0.], Seconds(0.1), Seconds(2.), 1e-8)
+    ///     .with_min_step(Seconds(1e-6))
+    ///     .with_max_step(Seconds(0.2))
+    ///     .collect();
+    /// assert!(evo.windows(2).all(|s| s[1].time().0 - s[0].time().0 <= 0.2));
+    /// ```
+    #[must_use]
+    pub fn with_max_step(mut self, h_max: Seconds<T>) -> Self {
+        self.h_max = h_max.0;
+        self
+    }
+
     /// Initial step (time 0) of the rkf45 solver.
     /// It contains the initial state and the calculated initial output
     /// at the constructor
@@ -327,66 +438,35 @@ where
 
     /// Runge-Kutta-Fehlberg order 4 and 5 method with adaptive step size
     fn main_iteration(&mut self) -> Option<StepWithError<T>> {
-        let mut error;
-        loop {
-            let u1 = DVector::from_vec((self.input)(self.time));
-            let u2 = DVector::from_vec((self.input)(Seconds(self.time.0 + self.h.0 * T::A[0])));
-            let u3 = DVector::from_vec((self.input)(Seconds(self.time.0 + self.h.0 * T::A[1])));
-            let u4 = DVector::from_vec((self.input)(Seconds(self.time.0 + self.h.0 * T::A[2])));
-            let u5 = DVector::from_vec((self.input)(Seconds(self.time.0 + self.h.0)));
-            let u6 = DVector::from_vec((self.input)(Seconds(self.time.0 + self.h.0 * T::A[3])));
-
-            let k1 = (&self.sys.a * &self.state + &self.sys.b * &u1) * self.h.0;
-            let k2 = (&self.sys.a * (&self.state + &k1 * T::B21) + &self.sys.b * &u2) * self.h.0;
-            let k3 = (&self.sys.a * (&self.state + &k1 * T::B3[0] + &k2 * T::B3[1])
-                + &self.sys.b * &u3)
-                * self.h.0;
-            let k4 = (&self.sys.a
-                * (&self.state + &k1 * T::B4[0] + &k2 * T::B4[1] + &k3 * T::B4[2])
-                + &self.sys.b * &u4)
-                * self.h.0;
-            let k5 = (&self.sys.a
-                * (&self.state
-                    + &k1 * T::B5[0]
-                    + &k2 * T::B5[1]
-                    + &k3 * T::B5[2]
-                    + &k4 * T::B5[3])
-                + &self.sys.b * &u5)
-                * self.h.0;
-            let k6 = (&self.sys.a
-                * (&self.state
-                    + &k1 * T::B6[0]
-                    + &k2 * T::B6[1]
-                    + &k3 * T::B6[2]
-                    + &k4 * T::B6[3]
-                    + &k5 * T::B6[4])
-                + &self.sys.b * &u6)
-                * self.h.0;
-
-            let xn1 = &self.state + &k1 * T::C[0] + &k3 * T::C[1] + &k4 * T::C[2] + &k5 * T::C[3];
-            let xn1_ = &self.state
-                + &k1 * T::D[0]
-                + &k3 * T::D[1]
-                + &k4 * T::D[2]
-                + &k5 * T::D[3]
-                + &k6 * T::D[4];
-
-            // Take the maximum absolute error between the states of the system.
-            error = (&xn1 - &xn1_).abs().max();
-            let error_ratio = self.tol / error;
-            if error < self.tol {
-                self.h.0 = T::SAFETY_FACTOR * self.h.0 * error_ratio.powf(T::EXP[0]);
-                self.state = xn1;
-                break;
-            }
-            self.h.0 = T::SAFETY_FACTOR * self.h.0 * error_ratio.powf(T::EXP[1]);
-        }
+        let (sys, input) = (self.sys, &self.input);
+        let derivative =
+            |t: T, x: &DVector<T>| sys.derivative(x, &DVector::from_vec(input(Seconds(t))));
+        let AdaptiveStep {
+            state,
+            step,
+            last,
+            error,
+        } = rkf45_step(
+            derivative,
+            self.time.0,
+            &self.state,
+            &mut self.h.0,
+            (self.h_min, self.h_max),
+            self.limit.0,
+            self.tol,
+        );
+        self.state = state;
 
-        // Update time before calculate the output.
-        self.time.0 += self.h.0;
+        // Update time with the size of the accepted step before calculate
+        // the output.
+        if last {
+            self.time = self.limit;
+        } else {
+            self.time.0 += step;
+        }
 
         let u = DVector::from_vec((self.input)(self.time));
-        self.output = &self.sys.c * &self.state + &self.sys.d * &u;
+        self.output = self.sys.output(&self.state, &u);
 
         Some(StepWithError {
             time: self.time,
@@ -398,24 +478,212 @@ where
 }
 
 /// Implementation of the Iterator trait for the `Rkf45` struct
-impl<'a, F, T> Iterator for Rkf45<'a, F, T>
+impl<'a, F, T, S> Iterator for Rkf45<'a, F, T, S>
 where
     F: Fn(Seconds<T>) -> Vec<T>,
     T: AddAssign + Float + MulAssign + Rkf45Const + Signed + Scalar + SimdPartialOrd + SubAssign,
+    S: LinearDynamics<T>,
 {
     type Item = StepWithError<T>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.time > self.limit {
-            None
-        } else if self.initial_step {
+        if self.initial_step {
             self.initial_step()
+        } else if self.time >= self.limit {
+            None
         } else {
             self.main_iteration()
         }
     }
 }
 
+impl<'a, F, T, S> Rkf45<'a, F, T, S>
+where
+    F: Fn(Seconds<T>) -> Vec<T>,
+    T: AddAssign + Float + MulAssign + Rkf45Const + Scalar + Signed + SimdPartialOrd + SubAssign,
+    S: LinearDynamics<T>,
+{
+    /// Emit the time evolution on a uniform time grid with the given
+    /// interval, while the integration step is still adapted internally.
+    /// The state between two integration steps is calculated through cubic
+    /// Hermite interpolation of the states and their derivatives.
+    ///
+    /// The grid starts from time zero and ends at the time limit of the
+    /// solver.
+    ///
+    /// # Arguments
+    ///
+    /// * `dt` - interval of the output time grid
+    ///
+    /// # Panics
+    ///
+    /// Panics if the interval is not positive.
+    ///
+    /// # Example
+    /// ```
+    /// use au::{Seconds, Ss};
+    /// let sys = Ss::new_from_slice(1, 1, 1, &[-1.], &[1.], &[1.], &[0.]);
+    /// let evo = sys
+    ///     .rkf45(|_| vec![1.], &[0.], Seconds(0.1), Seconds(2.), 1e-6)
+    ///     .dense_output(Seconds(0.5));
+    /// let times: Vec<_> = evo.map(|s| s.time().0).collect();
+    /// assert_eq!(vec![0., 0.5, 1., 1.5, 2.], times);
+    /// ```
+    pub fn dense_output(mut self, dt: Seconds<T>) -> Rkf45Dense<'a, F, T, S> {
+        assert!(dt.0 > T::zero(), "Output interval must be positive");
+        // The initial state is handled by the dense output.
+        self.initial_step = false;
+        let x = self.state.clone();
+        let f = self.derivative(self.time, &x);
+        Rkf45Dense {
+            t0: self.time.0,
+            x0: x.clone(),
+            f0: f.clone(),
+            t1: self.time.0,
+            x1: x,
+            f1: f,
+            solver: self,
+            dt,
+            index: 0,
+        }
+    }
+
+    /// Derivative of the state at the given time.
+    fn derivative(&self, time: Seconds<T>, x: &DVector<T>) -> DVector<T> {
+        let u = DVector::from_vec((self.input)(time));
+        self.sys.derivative(x, &u)
+    }
+
+    /// Write the time evolution in CSV format, see
+    /// [`TimeResponse::to_csv`](struct.TimeResponse.html#method.to_csv).
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - destination of the CSV data
+    ///
+    /// # Errors
+    ///
+    /// It returns an error if the writing fails.
+    pub fn to_csv<W: Write>(self, writer: W) -> io::Result<()>
+    where
+        T: Display,
+    {
+        self.collect::<TimeResponse<T>>().to_csv(writer)
+    }
+}
+
+/// Time evolution of a linear system on a uniform time grid using the
+/// adaptive Runge-Kutta-Fehlberg method.
+#[derive(Clone, Debug)]
+pub struct Rkf45Dense<'a, F, T, S = Ss<T>>
+where
+    F: Fn(Seconds<T>) -> Vec<T>,
+    T: Float + Scalar,
+{
+    /// Adaptive solver
+    solver: Rkf45<'a, F, T, S>,
+    /// Interval of the output grid
+    dt: Seconds<T>,
+    /// Index of the output grid
+    index: usize,
+    /// Time of the previous integration step
+    t0: T,
+    /// State at the previous integration step
+    x0: DVector<T>,
+    /// State derivative at the previous integration step
+    f0: DVector<T>,
+    /// Time of the last integration step
+    t1: T,
+    /// State at the last integration step
+    x1: DVector<T>,
+    /// State derivative at the last integration step
+    f1: DVector<T>,
+}
+
+impl<'a, F, T, S> Rkf45Dense<'a, F, T, S>
+where
+    F: Fn(Seconds<T>) -> Vec<T>,
+    T: AddAssign + Float + MulAssign + Rkf45Const + Scalar + Signed + SimdPartialOrd + SubAssign,
+    S: LinearDynamics<T>,
+{
+    /// Write the time evolution in CSV format, see
+    /// [`TimeResponse::to_csv`](struct.TimeResponse.html#method.to_csv).
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - destination of the CSV data
+    ///
+    /// # Errors
+    ///
+    /// It returns an error if the writing fails.
+    pub fn to_csv<W: Write>(self, writer: W) -> io::Result<()>
+    where
+        T: Display,
+    {
+        self.collect::<TimeResponse<T>>().to_csv(writer)
+    }
+}
+
+/// Implementation of the Iterator trait for the `Rkf45Dense` struct
+impl<'a, F, T, S> Iterator for Rkf45Dense<'a, F, T, S>
+where
+    F: Fn(Seconds<T>) -> Vec<T>,
+    T: AddAssign + Float + MulAssign + Rkf45Const + Scalar + Signed + SimdPartialOrd + SubAssign,
+    S: LinearDynamics<T>,
+{
+    type Item = Step<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let limit = self.solver.limit.0;
+        let time = T::from(self.index)? * self.dt.0;
+        // Tolerance on the last grid point to absorb rounding errors.
+        if time - limit > self.dt.0 * Float::sqrt(T::epsilon()) {
+            return None;
+        }
+        let time = Float::min(time, limit);
+
+        // Integrate until the grid time is reached.
+        while time > self.t1 && self.solver.time < self.solver.limit {
+            self.solver.main_iteration()?;
+            std::mem::swap(&mut self.x0, &mut self.x1);
+            std::mem::swap(&mut self.f0, &mut self.f1);
+            self.t0 = self.t1;
+            self.t1 = self.solver.time.0;
+            self.x1 = self.solver.state.clone();
+            self.f1 = self.solver.derivative(self.solver.time, &self.x1);
+        }
+        if time > self.t1 {
+            return None;
+        }
+
+        let h = self.t1 - self.t0;
+        let state = if h > T::zero() {
+            // Cubic Hermite basis functions.
+            let s = (time - self.t0) / h;
+            let s2 = s * s;
+            let s3 = s2 * s;
+            let two = T::one() + T::one();
+            let three = two + T::one();
+            let h00 = two * s3 - three * s2 + T::one();
+            let h10 = s3 - two * s2 + s;
+            let h01 = three * s2 - two * s3;
+            let h11 = s3 - s2;
+            &self.x0 * h00 + &self.f0 * (h10 * h) + &self.x1 * h01 + &self.f1 * (h11 * h)
+        } else {
+            self.x1.clone()
+        };
+        let u = DVector::from_vec((self.solver.input)(Seconds(time)));
+        let output = self.solver.sys.output(&state, &u);
+
+        self.index += 1;
+        Some(Step {
+            time: Seconds(time),
+            state: state.as_slice().to_vec(),
+            output: output.as_slice().to_vec(),
+        })
+    }
+}
+
 // Coefficients of the Butcher table of rkf45 method.
 /// Trait that defines the constants used in the Rkf45 solver.
 pub trait Rkf45Const
@@ -453,7 +721,7 @@ macro_rules! impl_rkf45_const {
             const B4: [Self; 3] = [1932. / 2197., -7200. / 2197., 7296. / 2197.];
             const B5: [Self; 4] = [439. / 216., -8., 3680. / 513., -845. / 4104.];
             const B6: [Self; 5] = [-8. / 27., 2., -3544. / 2565., 1859. / 4104., -11. / 40.];
-            const C: [Self; 4] = [25. / 216., 1408. / 2564., 2197. / 4101., -1. / 5.];
+            const C: [Self; 4] = [25. / 216., 1408. / 2565., 2197. / 4104., -1. / 5.];
             const D: [Self; 5] = [
                 16. / 135.,
                 6656. / 12_825.,
@@ -471,6 +739,107 @@ impl_rkf45_const!(f32);
 impl_rkf45_const!(f64);
 //////
 
+/// Accepted step of the Runge-Kutta-Fehlberg method.
+pub(crate) struct AdaptiveStep<T: Scalar> {
+    /// State at the end of the step.
+    pub(crate) state: DVector<T>,
+    /// Size of the step.
+    pub(crate) step: T,
+    /// The step reaches the time limit.
+    pub(crate) last: bool,
+    /// Estimated error of the step.
+    pub(crate) error: T,
+}
+
+/// Runge-Kutta-Fehlberg order 4 and 5 step with adaptive size, shared by
+/// the solvers of linear and nonlinear systems.
+///
+/// # Arguments
+///
+/// * `f` - derivative of the state as a function of time and state
+/// * `t` - initial time of the step
+/// * `x` - initial state of the step
+/// * `h` - integration interval, updated with the next proposed interval
+/// * `(h_min, h_max)` - bounds of the integration interval
+/// * `limit` - time limit of the evaluation
+/// * `tol` - error tolerance
+///
+/// When the error tolerance cannot be met with the minimum interval, the
+/// step is accepted anyway.
+pub(crate) fn rkf45_step<T, F>(
+    f: F,
+    t: T,
+    x: &DVector<T>,
+    h: &mut T,
+    (h_min, h_max): (T, T),
+    limit: T,
+    tol: T,
+) -> AdaptiveStep<T>
+where
+    T: AddAssign + Float + MulAssign + Rkf45Const + Scalar,
+    F: Fn(T, &DVector<T>) -> DVector<T>,
+{
+    let bound = |h: T| Float::min(Float::max(h, h_min), h_max);
+    loop {
+        *h = bound(*h);
+        // The interval cannot be reduced further.
+        let min_step = *h <= h_min;
+        // Do not step over the time limit, nor leave a final step
+        // shorter than the minimum interval. When the remaining time is
+        // longer than the maximum interval it is split in two steps.
+        let remaining = limit - t;
+        let short_tail = remaining - *h < h_min;
+        let last = short_tail && remaining <= h_max;
+        if last {
+            *h = remaining;
+        } else if short_tail {
+            *h = remaining / (T::one() + T::one());
+        }
+        let step = *h;
+        let k1 = f(t, x) * step;
+        let k2 = f(t + step * T::A[0], &(x + &k1 * T::B21)) * step;
+        let k3 = f(t + step * T::A[1], &(x + &k1 * T::B3[0] + &k2 * T::B3[1])) * step;
+        let k4 = f(
+            t + step * T::A[2],
+            &(x + &k1 * T::B4[0] + &k2 * T::B4[1] + &k3 * T::B4[2]),
+        ) * step;
+        let k5 = f(
+            t + step,
+            &(x + &k1 * T::B5[0] + &k2 * T::B5[1] + &k3 * T::B5[2] + &k4 * T::B5[3]),
+        ) * step;
+        let k6 = f(
+            t + step * T::A[3],
+            &(x + &k1 * T::B6[0]
+                + &k2 * T::B6[1]
+                + &k3 * T::B6[2]
+                + &k4 * T::B6[3]
+                + &k5 * T::B6[4]),
+        ) * step;
+
+        let xn1 = x + &k1 * T::C[0] + &k3 * T::C[1] + &k4 * T::C[2] + &k5 * T::C[3];
+        let xn1_ =
+            x + &k1 * T::D[0] + &k3 * T::D[1] + &k4 * T::D[2] + &k5 * T::D[3] + &k6 * T::D[4];
+
+        // Take the maximum absolute error between the states of the system.
+        let error = xn1
+            .iter()
+            .zip(xn1_.iter())
+            .fold(T::zero(), |m, (a, b)| Float::max(m, Float::abs(*a - *b)));
+        let error_ratio = tol / error;
+        // Accept the step also if the interval cannot be reduced further.
+        if error < tol || min_step {
+            *h = bound(T::SAFETY_FACTOR * step * error_ratio.powf(T::EXP[0]));
+            return AdaptiveStep {
+                state: xn1,
+                step,
+                last,
+                error,
+            };
+        }
+        *h = bound(T::SAFETY_FACTOR * step * error_ratio.powf(T::EXP[1]));
+    }
+}
+
 /// Struct to hold the data of the linear system time evolution
 #[derive(Clone, Debug)]
 pub struct StepWithError<T: Float> {
@@ -485,6 +854,16 @@ pub struct StepWithError<T: Float> {
 }
 
 impl<T: Float> StepWithError<T> {
+    /// Create a new step of the time evolution with its integration error.
+    pub(crate) fn new(time: Seconds<T>, state: Vec<T>, output: Vec<T>, error: T) -> Self {
+        Self {
+            time,
+            state,
+            output,
+            error,
+        }
+    }
+
     /// Get the time of the current step
     pub fn time(&self) -> Seconds<T> {
         self.time
@@ -506,6 +885,430 @@ impl<T: Float> StepWithError<T> {
     }
 }
 
+/// Time response of a linear system, collected from the steps of the time
+/// evolution.
+///
+/// States and outputs are stored as matrices with a row for each time
+/// sample and a column for each channel.
+///
+/// # Example
+/// ```
+/// use au::{linear_system::solver::TimeResponse, Seconds, Ss};
+/// let sys = Ss::new_from_slice(1, 1, 1, &[-1.], &[1.], &[2.], &[0.]);
+/// let resp: TimeResponse<_> = sys.rk4(|_| vec![1.], &[0.], Seconds(0.1), 10).collect();
+/// assert_eq!(11, resp.len());
+/// assert_eq!((11, 1), resp.states().shape());
+/// assert_eq!(11, resp.output(0).len());
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct TimeResponse<T: Float + Scalar> {
+    /// Time samples
+    time: Vec<Seconds<T>>,
+    /// States, one row for each time sample
+    states: DMatrix<T>,
+    /// Outputs, one row for each time sample
+    outputs: DMatrix<T>,
+    /// Names of the states
+    state_names: Option<Vec<String>>,
+    /// Names of the outputs
+    output_names: Option<Vec<String>>,
+}
+
+impl<T: Float + Scalar> TimeResponse<T> {
+    /// Build the time response from the time, state and output of each step.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the steps have a different number of states or outputs.
+    fn from_steps<I>(steps: I) -> Self
+    where
+        I: Iterator<Item = (Seconds<T>, Vec<T>, Vec<T>)>,
+    {
+        let mut time = Vec::new();
+        let mut states = Vec::new();
+        let mut outputs = Vec::new();
+        let (mut n_states, mut n_outputs) = (0, 0);
+        for (i, (t, x, y)) in steps.enumerate() {
+            if i == 0 {
+                n_states = x.len();
+                n_outputs = y.len();
+            }
+            assert_eq!(n_states, x.len(), "Inconsistent number of states");
+            assert_eq!(n_outputs, y.len(), "Inconsistent number of outputs");
+            time.push(t);
+            states.extend(x);
+            outputs.extend(y);
+        }
+        let samples = time.len();
+        Self {
+            time,
+            states: DMatrix::from_row_slice(samples, n_states, &states),
+            outputs: DMatrix::from_row_slice(samples, n_outputs, &outputs),
+            state_names: None,
+            output_names: None,
+        }
+    }
+
+    /// Use the names of the states and of the outputs of the simulated
+    /// system, they are used as header in the CSV export. Names that contain
+    /// commas, quotes or line breaks are quoted in the header.
+    ///
+    /// # Arguments
+    ///
+    /// * `sys` - simulated system
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of states or outputs of the system is different
+    /// from the one of the time response.
+    ///
+    /// # Example
+    /// ```
+    /// use au::{linear_system::solver::TimeResponse, Seconds, Ss};
+    /// let sys = Ss::new_from_slice(1, 1, 1, &[-1.], &[1.], &[2.], &[0.])
+    ///     .with_state_names(["v"])
+    ///     .with_output_names(["speed"]);
+    /// let resp: TimeResponse<_> = sys.rk2(|_| vec![1.], &[0.], Seconds(0.5), 1).collect();
+    /// let mut csv = Vec::new();
+    /// resp.with_names_of(&sys).to_csv(&mut csv).unwrap();
+    /// assert!(String::from_utf8(csv).unwrap().starts_with("time,v,speed\n"));
+    /// ```
+    #[must_use]
+    pub fn with_names_of<U: Time>(mut self, sys: &SsGen<T, U>) -> Self {
+        let dim = sys.dim();
+        assert_eq!(
+            dim.states(),
+            self.states.ncols(),
+            "Different number of states"
+        );
+        assert_eq!(
+            dim.outputs(),
+            self.outputs.ncols(),
+            "Different number of outputs"
+        );
+        self.state_names = sys.state_names().map(<[String]>::to_vec);
+        self.output_names = sys.output_names().map(<[String]>::to_vec);
+        self
+    }
+
+    /// Get the time samples
+    #[must_use]
+    pub fn time(&self) -> &[Seconds<T>] {
+        &self.time
+    }
+
+    /// Get the states, with a row for each time sample and a column for each
+    /// state
+    #[must_use]
+    pub fn states(&self) -> &DMatrix<T> {
+        &self.states
+    }
+
+    /// Get the outputs, with a row for each time sample and a column for each
+    /// output
+    #[must_use]
+    pub fn outputs(&self) -> &DMatrix<T> {
+        &self.outputs
+    }
+
+    /// Get the time evolution of the `i`-th state
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is greater than the number of states.
+    #[must_use]
+    pub fn state(&self, i: usize) -> &[T] {
+        column(&self.states, i)
+    }
+
+    /// Get the time evolution of the `i`-th output
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is greater than the number of outputs.
+    #[must_use]
+    pub fn output(&self, i: usize) -> &[T] {
+        column(&self.outputs, i)
+    }
+
+    /// Write the time response in CSV format. The first line is the header
+    /// with the columns names: `time`, the states `x1`, `x2`, ... and the
+    /// outputs `y1`, `y2`, ..., then a line for each time sample follows.
+    ///
+    /// States and outputs use the names of the system when they are set
+    /// with [`with_names_of`](struct.TimeResponse.html#method.with_names_of).
+    /// Names that contain commas, quotes or line breaks are enclosed in
+    /// double quotes and their quotes are doubled.
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - destination of the CSV data
+    ///
+    /// # Errors
+    ///
+    /// It returns an error if the writing fails.
+    ///
+    /// # Example
+    /// ```
+    /// use au::{linear_system::solver::TimeResponse, Seconds, Ss};
+    /// let sys = Ss::new_from_slice(1, 1, 1, &[-1.], &[1.], &[2.], &[0.]);
+    /// let resp: TimeResponse<_> = sys.rk2(|_| vec![1.], &[0.], Seconds(0.5), 1).collect();
+    /// let mut csv = Vec::new();
+    /// resp.to_csv(&mut csv).unwrap();
+    /// assert_eq!("time,x1,y1\n0,0,0\n0.5,0.375,0.75\n", String::from_utf8(csv).unwrap());
+    /// ```
+    pub fn to_csv<W: Write>(&self, mut writer: W) -> io::Result<()>
+    where
+        T: Display,
+    {
+        write!(writer, "time")?;
+        match &self.state_names {
+            Some(names) => names
+                .iter()
+                .try_for_each(|n| write!(writer, ",{}", csv_field(n)))?,
+            None => (1..=self.states.ncols()).try_for_each(|i| write!(writer, ",x{}", i))?,
+        }
+        match &self.output_names {
+            Some(names) => names
+                .iter()
+                .try_for_each(|n| write!(writer, ",{}", csv_field(n)))?,
+            None => (1..=self.outputs.ncols()).try_for_each(|i| write!(writer, ",y{}", i))?,
+        }
+        writeln!(writer)?;
+        for (k, t) in self.time.iter().enumerate() {
+            write!(writer, "{}", t.0)?;
+            for x in self.states.row(k).iter() {
+                write!(writer, ",{}", x)?;
+            }
+            for y in self.outputs.row(k).iter() {
+                write!(writer, ",{}", y)?;
+            }
+            writeln!(writer)?;
+        }
+        Ok(())
+    }
+
+    /// Number of time samples
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.time.len()
+    }
+
+    /// Check if the time response has no samples
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.time.is_empty()
+    }
+
+    /// Step response metrics of each output channel.
+    ///
+    /// The initial value of each output is its first sample and the steady
+    /// state value is its last sample, so the simulation shall be long
+    /// enough for the output to settle.
+    ///
+    /// # Arguments
+    ///
+    /// * `settling` - Settling threshold as fraction of the output change
+    ///   (e.g. 0.02 for 2%)
+    ///
+    /// # Example
+    /// ```
+    /// use au::{linear_system::solver::TimeResponse, Seconds, Ss};
+    /// let sys = Ss::new_from_slice(1, 1, 1, &[-1.], &[1.], &[1.], &[0.]);
+    /// let resp: TimeResponse<_> = sys.rk4(|_| vec![1.], &[0.], Seconds(0.01), 1000).collect();
+    /// let info = resp.step_info(0.02);
+    /// let rise = info[0].rise_time().unwrap();
+    /// assert!((rise.0 - 9_f64.ln()).abs() < 1e-3);
+    /// assert_eq!(0., info[0].overshoot());
+    /// ```
+    #[must_use]
+    pub fn step_info(&self, settling: T) -> Vec<StepInfo<T>> {
+        if self.is_empty() {
+            return Vec::new();
+        }
+        (0..self.outputs.ncols())
+            .map(|i| StepInfo::new(&self.time, self.output(i), settling))
+            .collect()
+    }
+}
+
+/// Step response metrics of an output channel
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StepInfo<T: Float> {
+    /// Time to go from 10% to 90% of the output change
+    rise_time: Option<Seconds<T>>,
+    /// Time after which the output stays within the settling threshold
+    settling_time: Option<Seconds<T>>,
+    /// Overshoot, percentage of the output change
+    overshoot: T,
+    /// Undershoot, percentage of the output change
+    undershoot: T,
+    /// Peak value of the output
+    peak: T,
+    /// Time of the peak value of the output
+    peak_time: Seconds<T>,
+    /// Steady state value of the output
+    steady_state: T,
+}
+
+impl<T: Float> StepInfo<T> {
+    /// Calculate the metrics of the output samples.
+    ///
+    /// # Arguments
+    ///
+    /// * `time` - Time samples
+    /// * `y` - Output samples
+    /// * `settling` - Settling threshold as fraction of the output change
+    fn new(time: &[Seconds<T>], y: &[T], settling: T) -> Self {
+        let y0 = y[0];
+        let yf = y[y.len() - 1];
+        let delta = yf - y0;
+        let hundred = T::from(100.).unwrap();
+        // Normalized output, it goes from 0 to 1.
+        let norm = |v: T| (v - y0) / delta;
+
+        let (peak_idx, &peak) = y.iter().enumerate().fold((0, &y0), |(i, p), (j, v)| {
+            if Float::abs(*v - y0) > Float::abs(*p - y0) {
+                (j, v)
+            } else {
+                (i, p)
+            }
+        });
+
+        if delta.is_zero() {
+            return Self {
+                rise_time: None,
+                settling_time: None,
+                overshoot: T::zero(),
+                undershoot: T::zero(),
+                peak,
+                peak_time: time[peak_idx],
+                steady_state: yf,
+            };
+        }
+
+        // The overshoot and the undershoot are measured from the maximum and
+        // the minimum of the normalized output, independently of the peak.
+        let overshoot = y
+            .iter()
+            .fold(T::zero(), |o, &v| Float::max(o, norm(v) - T::one()))
+            * hundred;
+        let undershoot = y.iter().fold(T::zero(), |u, &v| Float::max(u, -norm(v))) * hundred;
+
+        let crossing = |level: T| {
+            let k = y.iter().position(|&v| norm(v) >= level)?;
+            if k == 0 {
+                return Some(time[0].0);
+            }
+            // Linear interpolation between the samples.
+            let (n0, n1) = (norm(y[k - 1]), norm(y[k]));
+            let (t0, t1) = (time[k - 1].0, time[k].0);
+            Some(t0 + (t1 - t0) * (level - n0) / (n1 - n0))
+        };
+        let t10 = crossing(T::from(0.1).unwrap());
+        let t90 = crossing(T::from(0.9).unwrap());
+        let rise_time = match (t10, t90) {
+            (Some(t10), Some(t90)) => Some(Seconds(t90 - t10)),
+            _ => None,
+        };
+
+        let settling_time = match y
+            .iter()
+            .rposition(|&v| Float::abs(norm(v) - T::one()) > settling)
+        {
+            Some(k) if k + 1 < time.len() => Some(time[k + 1]),
+            Some(_) => None,
+            None => Some(time[0]),
+        };
+
+        Self {
+            rise_time,
+            settling_time,
+            overshoot,
+            undershoot,
+            peak,
+            peak_time: time[peak_idx],
+            steady_state: yf,
+        }
+    }
+
+    /// Get the rise time, the time to go from 10% to 90% of the output change.
+    /// It is `None` if the output does not change.
+    #[must_use]
+    pub fn rise_time(&self) -> Option<Seconds<T>> {
+        self.rise_time
+    }
+
+    /// Get the settling time, the time after which the output stays within
+    /// the settling threshold around the steady state value.
+    /// It is `None` if the output does not change.
+    #[must_use]
+    pub fn settling_time(&self) -> Option<Seconds<T>> {
+        self.settling_time
+    }
+
+    /// Get the overshoot, the maximum excursion of the output beyond the
+    /// steady state value, as percentage of the output change
+    #[must_use]
+    pub fn overshoot(&self) -> T {
+        self.overshoot
+    }
+
+    /// Get the undershoot, the maximum excursion of the output in the opposite
+    /// direction of the output change, as percentage of the output change
+    #[must_use]
+    pub fn undershoot(&self) -> T {
+        self.undershoot
+    }
+
+    /// Get the peak value, the output value farthest from the initial one
+    #[must_use]
+    pub fn peak(&self) -> T {
+        self.peak
+    }
+
+    /// Get the time of the peak value
+    #[must_use]
+    pub fn peak_time(&self) -> Seconds<T> {
+        self.peak_time
+    }
+
+    /// Get the steady state value, the last sample of the output
+    #[must_use]
+    pub fn steady_state(&self) -> T {
+        self.steady_state
+    }
+}
+
+/// Slice of the `i`-th column of a matrix, stored in column major order.
+fn column<T: Scalar>(m: &DMatrix<T>, i: usize) -> &[T] {
+    assert!(i < m.ncols(), "Channel index out of bounds");
+    let rows = m.nrows();
+    &m.as_slice()[i * rows..(i + 1) * rows]
+}
+
+/// Field of a CSV file, quoted if it contains separators, quotes or line
+/// breaks.
+fn csv_field(field: &str) -> String {
+    if field.contains(&[',', '"', '\n', '\r'][..]) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+impl<T: Float + Scalar> FromIterator<Step<T>> for TimeResponse<T> {
+    fn from_iter<I: IntoIterator<Item = Step<T>>>(iter: I) -> Self {
+        Self::from_steps(iter.into_iter().map(|s| (s.time, s.state, s.output)))
+    }
+}
+
+impl<T: Float + Scalar> FromIterator<StepWithError<T>> for TimeResponse<T> {
+    fn from_iter<I: IntoIterator<Item = StepWithError<T>>>(iter: I) -> Self {
+        Self::from_steps(iter.into_iter().map(|s| (s.time, s.state, s.output)))
+    }
+}
+
 /// Struct for the time evolution of the linear system using the implicit
 /// Radau method of order 3 with 2 steps
 #[derive(Clone, Debug)]
@@ -664,6 +1467,23 @@ where
             output: self.output.as_slice().to_vec(),
         })
     }
+
+    /// Write the time evolution in CSV format, see
+    /// [`TimeResponse::to_csv`](struct.TimeResponse.html#method.to_csv).
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - destination of the CSV data
+    ///
+    /// # Errors
+    ///
+    /// It returns an error if the writing fails.
+    pub fn to_csv<W: Write>(self, writer: W) -> io::Result<()>
+    where
+        T: Display,
+    {
+        self.collect::<TimeResponse<T>>().to_csv(writer)
+    }
 }
 
 // Constants for Radau method.
@@ -756,6 +1576,231 @@ mod tests {
         assert_eq!(e, rkf.error());
     }
 
+    #[test]
+    fn rkf45_butcher_table() {
+        let nodes = [0., f64::A[1], f64::A[2], 1.];
+        let sum = |w: &[f64]| w.iter().sum::<f64>();
+        let first_moment = |w: &[f64]| w.iter().zip(&nodes).map(|(w, c)| w * c).sum::<f64>();
+        // Fourth order weights of the stages 1, 3, 4 and 5.
+        assert_relative_eq!(1., sum(&f64::C), max_relative = 1e-15);
+        assert_relative_eq!(0.5, first_moment(&f64::C), max_relative = 1e-15);
+        // Fifth order weights of the stages 1, 3, 4, 5 and 6.
+        assert_relative_eq!(1., sum(&f64::D), max_relative = 1e-15);
+        assert_relative_eq!(
+            0.5,
+            first_moment(&f64::D[..4]) + f64::D[4] * f64::A[3],
+            max_relative = 1e-15
+        );
+    }
+
+    #[test]
+    fn time_response() {
+        let steps = vec![
+            Step::new(Seconds(0.), vec![1., 2.], vec![3.]),
+            Step::new(Seconds(0.5), vec![4., 5.], vec![6.]),
+            Step::new(Seconds(1.), vec![7., 8.], vec![9.]),
+        ];
+        let resp: TimeResponse<_> = steps.into_iter().collect();
+        assert_eq!(3, resp.len());
+        assert!(!resp.is_empty());
+        assert_eq!(&[Seconds(0.), Seconds(0.5), Seconds(1.)], resp.time());
+        assert_eq!(
+            &DMatrix::from_row_slice(3, 2, &[1., 2., 4., 5., 7., 8.]),
+            resp.states()
+        );
+        assert_eq!(
+            &DMatrix::from_row_slice(3, 1, &[3., 6., 9.]),
+            resp.outputs()
+        );
+        assert_eq!(&[2., 5., 8.], resp.state(1));
+        assert_eq!(&[3., 6., 9.], resp.output(0));
+    }
+
+    #[test]
+    fn step_info_second_order() {
+        // Damping 0.5, natural frequency 1.
+        let sys = Ss::new_from_slice(
+            2,
+            1,
+            2,
+            &[0., 1., -1., -1.],
+            &[0., 1.],
+            &[1., 0., 0., 1.],
+            &[0., 0.],
+        );
+        let resp: TimeResponse<_> = sys
+            .rk4(|_| vec![1.], &[0., 0.], Seconds(0.001), 30_000)
+            .collect();
+        let info = resp.step_info(0.02);
+        assert_eq!(2, info.len());
+        let position = info[0];
+        let zeta: f64 = 0.5;
+        let expected_overshoot =
+            (-zeta * std::f64::consts::PI / (1. - zeta * zeta).sqrt()).exp() * 100.;
+        assert_relative_eq!(
+            expected_overshoot,
+            position.overshoot(),
+            max_relative = 1e-3
+        );
+        assert_relative_eq!(
+            1. + expected_overshoot / 100.,
+            position.peak(),
+            max_relative = 1e-4
+        );
+        let peak_time = std::f64::consts::PI / (1. - zeta * zeta).sqrt();
+        assert_relative_eq!(peak_time, position.peak_time().0, max_relative = 1e-3);
+        assert_relative_eq!(1., position.steady_state(), max_relative = 1e-6);
+        assert_eq!(0., position.undershoot());
+        let rise = position.rise_time().unwrap().0;
+        assert!(rise > 1.5 && rise < 1.7);
+        let settling = position.settling_time().unwrap().0;
+        assert!(settling > 7. && settling < 8.5);
+        assert_abs_diff_eq!(0., info[1].steady_state(), epsilon = 1e-6);
+    }
+
+    #[test]
+    fn step_info_non_minimum_phase() {
+        // (1 - 6s) / (s^2 + s + 1), the undershoot is larger than the overshoot.
+        let sys = Ss::new_from_slice(2, 1, 1, &[0., 1., -1., -1.], &[0., 1.], &[1., -6.], &[0.]);
+        let resp: TimeResponse<_> = sys
+            .rk4(|_| vec![1.], &[0., 0.], Seconds(0.001), 30_000)
+            .collect();
+        let info = resp.step_info(0.02)[0];
+        let output = resp.output(0);
+        let max = output.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let min = output.iter().copied().fold(f64::INFINITY, f64::min);
+        assert!(-min > max - 1.);
+        assert_relative_eq!((max - 1.) * 100., info.overshoot(), max_relative = 1e-5);
+        assert_relative_eq!(-min * 100., info.undershoot(), max_relative = 1e-5);
+        assert!(info.overshoot() > 60.);
+        // The peak is the undershoot.
+        assert_relative_eq!(min, info.peak());
+        assert!(info.peak_time().0 < 2.);
+    }
+
+    #[test]
+    fn step_info_samples() {
+        let steps = vec![
+            Step::new(Seconds(0.), vec![], vec![1., 0.]),
+            Step::new(Seconds(1.), vec![], vec![0.5, 1.]),
+            Step::new(Seconds(2.), vec![], vec![2., 0.]),
+            Step::new(Seconds(3.), vec![], vec![3.5, 0.]),
+            Step::new(Seconds(4.), vec![], vec![3., 0.]),
+        ];
+        let resp: TimeResponse<_> = steps.into_iter().collect();
+        let info = resp.step_info(0.1);
+        // Output change 2, 10% at 1+7/15, 90% at 2+8/15
+        assert_relative_eq!(16. / 15., info[0].rise_time().unwrap().0);
+        assert_relative_eq!(25., info[0].overshoot());
+        assert_relative_eq!(25., info[0].undershoot());
+        assert_relative_eq!(3.5, info[0].peak());
+        assert_eq!(Seconds(3.), info[0].peak_time());
+        assert_eq!(Some(Seconds(4.)), info[0].settling_time());
+        assert_eq!(3., info[0].steady_state());
+        // No output change.
+        assert_eq!(None, info[1].rise_time());
+        assert_eq!(None, info[1].settling_time());
+        assert_eq!(0., info[1].overshoot());
+        assert_eq!(1., info[1].peak());
+
+        let empty: TimeResponse<f64> = Vec::<Step<f64>>::new().into_iter().collect();
+        assert!(empty.step_info(0.02).is_empty());
+    }
+
+    #[test]
+    fn time_response_with_error() {
+        let steps = vec![
+            StepWithError {
+                time: Seconds(0.),
+                state: vec![1.],
+                output: vec![2., 3.],
+                error: 0.,
+            },
+            StepWithError {
+                time: Seconds(0.2),
+                state: vec![4.],
+                output: vec![5., 6.],
+                error: 1e-5,
+            },
+        ];
+        let resp: TimeResponse<_> = steps.into_iter().collect();
+        assert_eq!(&[1., 4.], resp.state(0));
+        assert_eq!(&[3., 6.], resp.output(1));
+
+        let empty: TimeResponse<f64> = Vec::<Step<f64>>::new().into_iter().collect();
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn time_response_csv() {
+        let steps = vec![
+            Step::new(Seconds(0.), vec![1., 2.], vec![3.]),
+            Step::new(Seconds(0.5), vec![4., 5.], vec![6.5]),
+        ];
+        let resp: TimeResponse<_> = steps.into_iter().collect();
+        let mut csv = Vec::new();
+        resp.to_csv(&mut csv).unwrap();
+        assert_eq!(
+            "time,x1,x2,y1\n0,1,2,3\n0.5,4,5,6.5\n",
+            String::from_utf8(csv).unwrap()
+        );
+    }
+
+    #[test]
+    fn time_response_csv_quoted_names() {
+        let sys = Ss::new_from_slice(2, 1, 1, &[0., 1., 0., 0.], &[0., 1.], &[1., 0.], &[0.])
+            .with_state_names(["x", "v, m/s"])
+            .with_output_names(["\"y\""]);
+        let mut csv = Vec::new();
+        sys.rk2(|_| vec![1.], &[0., 0.], Seconds(0.5), 1)
+            .collect::<TimeResponse<_>>()
+            .with_names_of(&sys)
+            .to_csv(&mut csv)
+            .unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        assert_eq!(Some("time,x,\"v, m/s\",\"\"\"y\"\"\""), csv.lines().next());
+    }
+
+    #[test]
+    fn solver_csv() {
+        let sys = Ss::new_from_slice(1, 1, 1, &[-1.], &[1.], &[1.], &[0.]);
+        let mut csv = Vec::new();
+        sys.rk4(|_| vec![1.], &[0.], Seconds(0.1), 10)
+            .to_csv(&mut csv)
+            .unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        assert_eq!(12, csv.lines().count());
+        assert_eq!(Some("time,x1,y1"), csv.lines().next());
+
+        let mut csv = Vec::new();
+        sys.rkf45(|_| vec![1.], &[0.], Seconds(0.1), Seconds(1.), 1e-6)
+            .dense_output(Seconds(0.25))
+            .to_csv(&mut csv)
+            .unwrap();
+        assert_eq!(6, String::from_utf8(csv).unwrap().lines().count());
+
+        let mut csv = Vec::new();
+        sys.radau(|_| vec![1.], &[0.], Seconds(0.1), 5, 1e-6)
+            .to_csv(&mut csv)
+            .unwrap();
+        assert_eq!(7, String::from_utf8(csv).unwrap().lines().count());
+
+        let mut csv = Vec::new();
+        sys.rkf45(|_| vec![1.], &[0.], Seconds(0.1), Seconds(1.), 1e-6)
+            .to_csv(&mut csv)
+            .unwrap();
+        assert!(String::from_utf8(csv).unwrap().lines().count() > 2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn time_response_channel_out_of_bounds() {
+        let resp: TimeResponse<_> = vec![Step::new(Seconds(0.), vec![1.], vec![2.])]
+            .into_iter()
+            .collect();
+        let _ = resp.output(1);
+    }
+
     #[test]
     fn radau_struct() {
         let t = Seconds(12.);
@@ -0,0 +1,512 @@
+//! # Sparse state-space representation
+//!
+//! Continuous time linear system whose state matrix (A) is stored in
+//! compressed sparse row (CSR) format, while the input, output and direct
+//! transmission matrices are dense.
+//!
+//! Large plants, such as the spatial discretization of partial differential
+//! equations, have thousands of states with few non-zero coefficients of the
+//! state matrix. The time evolution of the sparse representation costs a
+//! number of operations proportional to the non-zero coefficients, instead of
+//! the square of the number of states.
+//!
+//! The time evolution is performed with the explicit solvers of the
+//! [solver](../solver/index.html) module, Runge-Kutta methods of order 2 and 4
+//! and the adaptive Runge-Kutta-Fehlberg method. The implicit Radau method
+//! factorizes the state matrix and it is available only for the dense
+//! representation.
+
+use nalgebra::{DMatrix, DVector, Scalar, SimdPartialOrd};
+use num_traits::{Float, Signed};
+
+use std::ops::{AddAssign, MulAssign, SubAssign};
+
+use crate::{
+    linear_system::{
+        continuous::Ss,
+        solver::{LinearDynamics, Order, Rk, RkConst, Rkf45, Rkf45Const},
+        Dim,
+    },
+    units::Seconds,
+};
+
+/// Sparse matrix in compressed sparse row (CSR) format
+#[derive(Clone, Debug, PartialEq)]
+pub struct CsrMatrix<T: Scalar> {
+    /// Number of rows
+    nrows: usize,
+    /// Number of columns
+    ncols: usize,
+    /// Offsets of the first element of each row, `nrows + 1` elements
+    row_offsets: Vec<usize>,
+    /// Column indexes of the non-zero elements
+    col_indices: Vec<usize>,
+    /// Values of the non-zero elements
+    values: Vec<T>,
+}
+
+impl<T: Float + Scalar> CsrMatrix<T> {
+    /// Create a sparse matrix from a list of (row, column, value) triplets.
+    /// Values with the same row and column are summed, zero values are
+    /// discarded.
+    ///
+    /// # Arguments
+    ///
+    /// * `nrows` - Number of rows
+    /// * `ncols` - Number of columns
+    /// * `triplets` - Non-zero elements of the matrix
+    ///
+    /// # Panics
+    ///
+    /// Panics if any index is out of the bounds of the matrix.
+    ///
+    /// # Example
+    /// ```
+    /// use au::linear_system::sparse::CsrMatrix;
+    /// let m = CsrMatrix::from_triplets(2, 2, &[(0, 1, 2.), (1, 0, -1.), (0, 1, 1.)]);
+    /// assert_eq!(2, m.nnz());
+    /// assert_eq!(3., m.to_dense()[(0, 1)]);
+    /// ```
+    #[must_use]
+    pub fn from_triplets(nrows: usize, ncols: usize, triplets: &[(usize, usize, T)]) -> Self {
+        let mut sorted = triplets.to_vec();
+        for &(i, j, _) in &sorted {
+            assert!(i < nrows && j < ncols, "Index out of the matrix bounds");
+        }
+        sorted.sort_by_key(|&(i, j, _)| (i, j));
+        let mut row_offsets = vec![0; nrows + 1];
+        let mut col_indices: Vec<usize> = Vec::with_capacity(sorted.len());
+        let mut values: Vec<T> = Vec::with_capacity(sorted.len());
+        let mut last = None;
+        for (i, j, v) in sorted {
+            if last == Some((i, j)) {
+                if let Some(l) = values.last_mut() {
+                    *l = *l + v;
+                }
+            } else {
+                row_offsets[i + 1] += 1;
+                col_indices.push(j);
+                values.push(v);
+                last = Some((i, j));
+            }
+        }
+        for i in 0..nrows {
+            row_offsets[i + 1] += row_offsets[i];
+        }
+        let mut m = Self {
+            nrows,
+            ncols,
+            row_offsets,
+            col_indices,
+            values,
+        };
+        m.remove_zeros();
+        m
+    }
+
+    /// Create a sparse matrix from the non-zero elements of a dense matrix.
+    ///
+    /// # Arguments
+    ///
+    /// * `m` - Dense matrix
+    #[must_use]
+    pub fn from_dense(m: &DMatrix<T>) -> Self {
+        let mut row_offsets = Vec::with_capacity(m.nrows() + 1);
+        let mut col_indices = Vec::new();
+        let mut values = Vec::new();
+        row_offsets.push(0);
+        for row in m.row_iter() {
+            for (j, &v) in row.iter().enumerate() {
+                if !v.is_zero() {
+                    col_indices.push(j);
+                    values.push(v);
+                }
+            }
+            row_offsets.push(values.len());
+        }
+        Self {
+            nrows: m.nrows(),
+            ncols: m.ncols(),
+            row_offsets,
+            col_indices,
+            values,
+        }
+    }
+
+    /// Remove the zero elements from the storage.
+    fn remove_zeros(&mut self) {
+        let mut k = 0;
+        for i in 0..self.nrows {
+            let (start, end) = (self.row_offsets[i], self.row_offsets[i + 1]);
+            self.row_offsets[i] = k;
+            for idx in start..end {
+                if !self.values[idx].is_zero() {
+                    self.col_indices[k] = self.col_indices[idx];
+                    self.values[k] = self.values[idx];
+                    k += 1;
+                }
+            }
+        }
+        self.row_offsets[self.nrows] = k;
+        self.col_indices.truncate(k);
+        self.values.truncate(k);
+    }
+
+    /// Get the number of rows
+    #[must_use]
+    pub fn nrows(&self) -> usize {
+        self.nrows
+    }
+
+    /// Get the number of columns
+    #[must_use]
+    pub fn ncols(&self) -> usize {
+        self.ncols
+    }
+
+    /// Get the number of stored non-zero elements
+    #[must_use]
+    pub fn nnz(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Convert the sparse matrix into a dense matrix.
+    #[must_use]
+    pub fn to_dense(&self) -> DMatrix<T> {
+        let mut m = DMatrix::zeros(self.nrows, self.ncols);
+        for i in 0..self.nrows {
+            for idx in self.row_offsets[i]..self.row_offsets[i + 1] {
+                m[(i, self.col_indices[idx])] = self.values[idx];
+            }
+        }
+        m
+    }
+
+    /// Multiplication of the matrix by a vector.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - Vector
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of the vector is different from the number of
+    /// columns.
+    #[must_use]
+    pub fn mul_vec(&self, x: &DVector<T>) -> DVector<T> {
+        assert_eq!(self.ncols, x.len(), "Vector length must match the columns");
+        DVector::from_fn(self.nrows, |i, _| {
+            (self.row_offsets[i]..self.row_offsets[i + 1]).fold(T::zero(), |acc, idx| {
+                acc + self.values[idx] * x[self.col_indices[idx]]
+            })
+        })
+    }
+}
+
+/// State-space representation of a continuous time linear system with a
+/// sparse state matrix.
+///
+/// ```text
+/// xdot(t) = A * x(t) + B * u(t)
+/// y(t)    = C * x(t) + D * u(t)
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct SsSparse<T: Scalar> {
+    /// A matrix, sparse
+    a: CsrMatrix<T>,
+    /// B matrix
+    b: DMatrix<T>,
+    /// C matrix
+    c: DMatrix<T>,
+    /// D matrix
+    d: DMatrix<T>,
+    /// Dimensions
+    dim: Dim,
+}
+
+impl<T: Float + Scalar> SsSparse<T> {
+    /// Create a new sparse state-space representation.
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - A matrix (nxn), sparse
+    /// * `b` - B matrix (nxm)
+    /// * `c` - C matrix (pxn)
+    /// * `d` - D matrix (pxm)
+    ///
+    /// # Panics
+    ///
+    /// Panics if matrix dimensions do not match.
+    ///
+    /// # Example
+    /// ```
+    /// use au::{linear_system::sparse::{CsrMatrix, SsSparse}, nalgebra::DMatrix};
+    /// let a = CsrMatrix::from_triplets(2, 2, &[(0, 0, -1.), (1, 1, -2.)]);
+    /// let b = DMatrix::from_row_slice(2, 1, &[1., 1.]);
+    /// let c = DMatrix::from_row_slice(1, 2, &[1., 1.]);
+    /// let d = DMatrix::zeros(1, 1);
+    /// let sys = SsSparse::new(a, b, c, d);
+    /// assert_eq!(2, sys.dim().states());
+    /// ```
+    #[must_use]
+    pub fn new(a: CsrMatrix<T>, b: DMatrix<T>, c: DMatrix<T>, d: DMatrix<T>) -> Self {
+        assert_eq!(a.nrows(), a.ncols(), "A matrix must be square");
+        assert_eq!(a.nrows(), b.nrows(), "B matrix rows must match the states");
+        assert_eq!(
+            a.ncols(),
+            c.ncols(),
+            "C matrix columns must match the states"
+        );
+        assert_eq!(
+            (c.nrows(), b.ncols()),
+            d.shape(),
+            "D matrix must match inputs and outputs"
+        );
+        let dim = Dim {
+            states: a.nrows(),
+            inputs: b.ncols(),
+            outputs: c.nrows(),
+        };
+        Self { a, b, c, d, dim }
+    }
+
+    /// Get the sparse states matrix (A) of the system.
+    #[must_use]
+    pub fn a(&self) -> &CsrMatrix<T> {
+        &self.a
+    }
+
+    /// Get the input matrix (B) of the system.
+    #[must_use]
+    pub fn b(&self) -> &DMatrix<T> {
+        &self.b
+    }
+
+    /// Get the output matrix (C) of the system.
+    #[must_use]
+    pub fn c(&self) -> &DMatrix<T> {
+        &self.c
+    }
+
+    /// Get the direct transmission matrix (D) of the system.
+    #[must_use]
+    pub fn d(&self) -> &DMatrix<T> {
+        &self.d
+    }
+
+    /// Get the dimensions of the system (states, inputs, outputs).
+    #[must_use]
+    pub fn dim(&self) -> Dim {
+        self.dim
+    }
+
+    /// Convert the system into a state-space representation with dense
+    /// matrices.
+    #[must_use]
+    pub fn to_ss(&self) -> Ss<T> {
+        Ss::new_from_matrices(
+            self.a.to_dense(),
+            self.b.clone(),
+            self.c.clone(),
+            self.d.clone(),
+        )
+    }
+
+    /// Time evolution for the given input, using Runge-Kutta second order method
+    ///
+    /// # Arguments
+    ///
+    /// * `u` - input function returning a vector (column vector)
+    /// * `x0` - initial state (column vector)
+    /// * `h` - integration time interval
+    /// * `n` - integration steps
+    pub fn rk2<F>(&self, u: F, x0: &[T], h: Seconds<T>, n: usize) -> Rk<'_, F, T, Self>
+    where
+        F: Fn(Seconds<T>) -> Vec<T>,
+        T: AddAssign + MulAssign + RkConst,
+    {
+        Rk::new(self, u, x0, h, n, Order::Rk2)
+    }
+
+    /// Time evolution for the given input, using Runge-Kutta fourth order method
+    ///
+    /// # Arguments
+    ///
+    /// * `u` - input function returning a vector (column vector)
+    /// * `x0` - initial state (column vector)
+    /// * `h` - integration time interval
+    /// * `n` - integration steps
+    ///
+    /// # Example
+    /// ```
+    /// use au::{linear_system::sparse::SsSparse, Seconds, Ss};
+    /// let sys = SsSparse::from(&Ss::new_from_slice(1, 1, 1, &[-1.], &[1.], &[1.], &[0.]));
+    /// let last = sys.rk4(|_| vec![1.], &[0.], Seconds(0.1), 100).last().unwrap();
+    /// assert!((last.output()[0] - 1_f64).abs() < 1e-4);
+    /// ```
+    pub fn rk4<F>(&self, u: F, x0: &[T], h: Seconds<T>, n: usize) -> Rk<'_, F, T, Self>
+    where
+        F: Fn(Seconds<T>) -> Vec<T>,
+        T: AddAssign + MulAssign + RkConst,
+    {
+        Rk::new(self, u, x0, h, n, Order::Rk4)
+    }
+
+    /// Runge-Kutta-Fehlberg 45 with adaptive step for time evolution.
+    ///
+    /// # Arguments
+    ///
+    /// * `u` - input function returning a vector (column vector)
+    /// * `x0` - initial state (column vector)
+    /// * `h` - integration time interval
+    /// * `limit` - time evaluation limit
+    /// * `tol` - error tolerance
+    ///
+    /// # Example
+    /// ```
+    /// use au::{linear_system::sparse::SsSparse, Seconds, Ss};
+    /// let sys = SsSparse::from(&Ss::new_from_slice(1, 1, 1, &[-1.], &[1.], &[1.], &[0.]));
+    /// let last = sys
+    ///     .rkf45(|_| vec![1.], &[0.], Seconds(0.1), Seconds(10.), 1e-6)
+    ///     .last()
+    ///     .unwrap();
+    /// assert!((last.output()[0] - 1_f64).abs() < 1e-4);
+    /// ```
+    pub fn rkf45<F>(
+        &self,
+        u: F,
+        x0: &[T],
+        h: Seconds<T>,
+        limit: Seconds<T>,
+        tol: T,
+    ) -> Rkf45<'_, F, T, Self>
+    where
+        F: Fn(Seconds<T>) -> Vec<T>,
+        T: AddAssign + MulAssign + Rkf45Const + Signed + SimdPartialOrd + SubAssign,
+    {
+        Rkf45::new(self, u, x0, h, limit, tol)
+    }
+}
+
+impl<T> LinearDynamics<T> for SsSparse<T>
+where
+    T: AddAssign + Float + MulAssign + Scalar,
+{
+    /// Derivative of the state, the product of the state matrix and the
+    /// state costs a number of operations proportional to the non-zero
+    /// elements.
+    fn derivative(&self, x: &DVector<T>, u: &DVector<T>) -> DVector<T> {
+        self.a.mul_vec(x) + &self.b * u
+    }
+
+    fn output(&self, x: &DVector<T>, u: &DVector<T>) -> DVector<T> {
+        &self.c * x + &self.d * u
+    }
+}
+
+impl<T: Float + Scalar> From<&Ss<T>> for SsSparse<T> {
+    /// Convert a state-space representation into a sparse one, keeping
+    /// the non-zero elements of the state matrix.
+    fn from(sys: &Ss<T>) -> Self {
+        Self {
+            a: CsrMatrix::from_dense(&sys.a),
+            b: sys.b.clone(),
+            c: sys.c.clone(),
+            d: sys.d.clone(),
+            dim: sys.dim(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn csr_matrix() {
+        let m = CsrMatrix::from_triplets(
+            3,
+            4,
+            &[(2, 3, 5.), (0, 1, 1.), (2, 0, 4.), (0, 1, 2.), (1, 2, 0.)],
+        );
+        assert_eq!((3, 4), (m.nrows(), m.ncols()));
+        assert_eq!(3, m.nnz());
+        let dense =
+            DMatrix::from_row_slice(3, 4, &[0., 3., 0., 0., 0., 0., 0., 0., 4., 0., 0., 5.]);
+        assert_eq!(dense, m.to_dense());
+        assert_eq!(m, CsrMatrix::from_dense(&dense));
+        let x = DVector::from_vec(vec![1., 2., 3., 4.]);
+        assert_eq!(&dense * &x, m.mul_vec(&x));
+    }
+
+    #[test]
+    fn csr_cancellation() {
+        let m = CsrMatrix::from_triplets(2, 2, &[(0, 0, 1.), (0, 0, -1.), (1, 1, 2.)]);
+        assert_eq!(1, m.nnz());
+        assert_eq!(
+            DMatrix::from_row_slice(2, 2, &[0., 0., 0., 2.]),
+            m.to_dense()
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn csr_out_of_bounds() {
+        let _ = CsrMatrix::from_triplets(2, 2, &[(2, 0, 1.)]);
+    }
+
+    #[test]
+    fn sparse_dense_conversion() {
+        let sys = Ss::new_from_slice(2, 1, 1, &[-2., 0., 3., -7.], &[1., 3.], &[-1., 0.5], &[0.1]);
+        let sparse = SsSparse::from(&sys);
+        assert_eq!(3, sparse.a().nnz());
+        assert_eq!(sys.dim(), sparse.dim());
+        assert_eq!(sys, sparse.to_ss());
+    }
+
+    #[test]
+    fn sparse_evolution() {
+        // Discretized heat equation.
+        let n = 50;
+        let mut triplets = Vec::new();
+        for i in 0..n {
+            triplets.push((i, i, -2.));
+            if i > 0 {
+                triplets.push((i, i - 1, 1.));
+            }
+            if i + 1 < n {
+                triplets.push((i, i + 1, 1.));
+            }
+        }
+        let a = CsrMatrix::from_triplets(n, n, &triplets);
+        let b = DMatrix::from_fn(n, 1, |i, _| if i == 0 { 1. } else { 0. });
+        let c = DMatrix::from_fn(1, n, |_, j| if j == n - 1 { 1. } else { 0. });
+        let sparse = SsSparse::new(a, b, c, DMatrix::zeros(1, 1));
+        let dense = sparse.to_ss();
+        let x0 = vec![0.; n];
+        let input = |t: Seconds<f64>| vec![t.0.sin()];
+        for (s, d) in
+            sparse
+                .rk4(input, &x0, Seconds(0.1), 100)
+                .zip(dense.rk4(input, &x0, Seconds(0.1), 100))
+        {
+            assert_eq!(d.time(), s.time());
+            assert_relative_eq!(d.output()[0], s.output()[0], max_relative = 1e-10);
+        }
+        for (s, d) in
+            sparse
+                .rk2(input, &x0, Seconds(0.1), 50)
+                .zip(dense.rk2(input, &x0, Seconds(0.1), 50))
+        {
+            assert_relative_eq!(d.state()[0], s.state()[0], max_relative = 1e-10);
+        }
+        assert_eq!(101, sparse.rk4(input, &x0, Seconds(0.1), 100).count());
+        for (s, d) in sparse
+            .rkf45(input, &x0, Seconds(0.1), Seconds(5.), 1e-6)
+            .zip(dense.rkf45(input, &x0, Seconds(0.1), Seconds(5.), 1e-6))
+        {
+            assert_relative_eq!(d.time().0, s.time().0, max_relative = 1e-10);
+            assert_relative_eq!(d.output()[0], s.output()[0], max_relative = 1e-10);
+        }
+    }
+}
@@ -0,0 +1,594 @@
+//! # Nonlinear systems
+//!
+//! Time evolution of nonlinear systems described by arbitrary functions
+//! ```text
+//! xdot(t) = f(t, x(t), u(t))
+//! y(t)    = g(t, x(t), u(t))
+//! ```
+//!
+//! The ODE solvers use the same methods and coefficients of the
+//! [solvers](../linear_system/solver/index.html) of linear systems:
+//! * `Rk` - explicit Runge-Kutta of order 2 or 4 with fixed step
+//! * `Rkf45` - explicit Runge-Kutta-Fehlberg of order 4 and 5 with adaptive
+//!   step
+//!
+//! The system can be linearized around an operating point, so that the
+//! nonlinear plant can be compared with its linear approximation.
+
+use nalgebra::{DMatrix, DVector, Scalar};
+use num_traits::Float;
+
+use std::ops::{AddAssign, MulAssign};
+
+use crate::{
+    linear_system::{
+        continuous::Ss,
+        solver::{rkf45_step, AdaptiveStep, RkConst, Rkf45Const, Step, StepWithError},
+    },
+    units::Seconds,
+};
+
+/// Nonlinear system
+///
+/// ```text
+/// xdot(t) = f(t, x(t), u(t))
+/// y(t)    = g(t, x(t), u(t))
+/// ```
+#[derive(Clone, Debug)]
+pub struct NonlinearSystem<F, G> {
+    /// State function
+    f: F,
+    /// Output function
+    g: G,
+}
+
+impl<F, G> NonlinearSystem<F, G> {
+    /// Create a nonlinear system from its state and output functions.
+    ///
+    /// Both functions take the time, the state and the input and return a
+    /// vector.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - state function, returns the derivative of the state
+    /// * `g` - output function, returns the output
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use au::{nonlinear::NonlinearSystem, Seconds};
+    /// // Damped pendulum.
+    /// let pendulum = NonlinearSystem::new(
+    ///     |_t: Seconds<f64>, x: &[f64], u: &[f64]| vec![x[1], -x[0].sin() - 0.1 * x[1] + u[0]],
+    ///     |_t: Seconds<f64>, x: &[f64], _u: &[f64]| vec![x[0]],
+    /// );
+    /// ```
+    pub fn new(f: F, g: G) -> Self {
+        Self { f, g }
+    }
+
+    /// Time evolution for the nonlinear system using Runge-Kutta
+    /// second order method
+    ///
+    /// # Arguments
+    ///
+    /// * `u` - input function returning a vector (column vector)
+    /// * `x0` - initial state (column vector)
+    /// * `h` - integration time interval
+    /// * `n` - integration steps
+    pub fn rk2<T, U>(&self, u: U, x0: &[T], h: Seconds<T>, n: usize) -> Rk<'_, F, G, U, T>
+    where
+        F: Fn(Seconds<T>, &[T], &[T]) -> Vec<T>,
+        G: Fn(Seconds<T>, &[T], &[T]) -> Vec<T>,
+        U: Fn(Seconds<T>) -> Vec<T>,
+        T: AddAssign + Float + MulAssign + RkConst + Scalar,
+    {
+        Rk::new(self, u, x0, h, n, Order::Rk2)
+    }
+
+    /// Time evolution for the nonlinear system using Runge-Kutta
+    /// fourth order method
+    ///
+    /// # Arguments
+    ///
+    /// * `u` - input function returning a vector (column vector)
+    /// * `x0` - initial state (column vector)
+    /// * `h` - integration time interval
+    /// * `n` - integration steps
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use au::{nonlinear::NonlinearSystem, Seconds};
+    /// let sys = NonlinearSystem::new(
+    ///     |_t: Seconds<f64>, x: &[f64], u: &[f64]| vec![-x[0] * x[0] + u[0]],
+    ///     |_t: Seconds<f64>, x: &[f64], _u: &[f64]| vec![x[0]],
+    /// );
+    /// let last = sys.rk4(|_| vec![1.], &[0.], Seconds(0.1), 100).last().unwrap();
+    /// assert!((last.output()[0] - 1.).abs() < 1e-6);
+    /// ```
+    pub fn rk4<T, U>(&self, u: U, x0: &[T], h: Seconds<T>, n: usize) -> Rk<'_, F, G, U, T>
+    where
+        F: Fn(Seconds<T>, &[T], &[T]) -> Vec<T>,
+        G: Fn(Seconds<T>, &[T], &[T]) -> Vec<T>,
+        U: Fn(Seconds<T>) -> Vec<T>,
+        T: AddAssign + Float + MulAssign + RkConst + Scalar,
+    {
+        Rk::new(self, u, x0, h, n, Order::Rk4)
+    }
+
+    /// Time evolution for the nonlinear system using Runge-Kutta-Fehlberg
+    /// method with adaptive step
+    ///
+    /// # Arguments
+    ///
+    /// * `u` - input function returning a vector (column vector)
+    /// * `x0` - initial state (column vector)
+    /// * `h` - initial integration time interval
+    /// * `limit` - time limit of the evaluation
+    /// * `tol` - error tolerance
+    pub fn rkf45<T, U>(
+        &self,
+        u: U,
+        x0: &[T],
+        h: Seconds<T>,
+        limit: Seconds<T>,
+        tol: T,
+    ) -> Rkf45<'_, F, G, U, T>
+    where
+        F: Fn(Seconds<T>, &[T], &[T]) -> Vec<T>,
+        G: Fn(Seconds<T>, &[T], &[T]) -> Vec<T>,
+        U: Fn(Seconds<T>) -> Vec<T>,
+        T: AddAssign + Float + MulAssign + Rkf45Const + Scalar,
+    {
+        Rkf45::new(self, u, x0, h, limit, tol)
+    }
+
+    /// Linearize the system around an operating point, the Jacobians of the
+    /// state and output functions are calculated through central finite
+    /// differences.
+    ///
+    /// ```text
+    /// A = df/dx, B = df/du, C = dg/dx, D = dg/du
+    /// ```
+    ///
+    /// The linear system describes the deviations from the operating point,
+    /// which needs not to be an equilibrium.
+    ///
+    /// # Arguments
+    ///
+    /// * `t` - time of the operating point
+    /// * `x0` - state of the operating point
+    /// * `u0` - input of the operating point
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use au::{nonlinear::NonlinearSystem, Seconds};
+    /// let pendulum = NonlinearSystem::new(
+    ///     |_t: Seconds<f64>, x: &[f64], u: &[f64]| vec![x[1], -x[0].sin() - 0.1 * x[1] + u[0]],
+    ///     |_t: Seconds<f64>, x: &[f64], _u: &[f64]| vec![x[0]],
+    /// );
+    /// let sys = pendulum.linearize(Seconds(0.), &[0., 0.], &[0.]);
+    /// assert!((sys.a()[(1, 0)] + 1.).abs() < 1e-9);
+    /// ```
+    pub fn linearize<T>(&self, t: Seconds<T>, x0: &[T], u0: &[T]) -> Ss<T>
+    where
+        F: Fn(Seconds<T>, &[T], &[T]) -> Vec<T>,
+        G: Fn(Seconds<T>, &[T], &[T]) -> Vec<T>,
+        T: Float + Scalar,
+    {
+        let (a, c) = self.jacobians(t, x0, u0, true);
+        let (b, d) = self.jacobians(t, x0, u0, false);
+        Ss::new_from_matrices(a, b, c, d)
+    }
+
+    /// Jacobians of the state and output functions with respect to the
+    /// state or the input, calculated with central finite differences.
+    ///
+    /// # Arguments
+    ///
+    /// * `t` - time of the operating point
+    /// * `x0` - state of the operating point
+    /// * `u0` - input of the operating point
+    /// * `wrt_state` - derive with respect to the state, otherwise the input
+    fn jacobians<T>(
+        &self,
+        t: Seconds<T>,
+        x0: &[T],
+        u0: &[T],
+        wrt_state: bool,
+    ) -> (DMatrix<T>, DMatrix<T>)
+    where
+        F: Fn(Seconds<T>, &[T], &[T]) -> Vec<T>,
+        G: Fn(Seconds<T>, &[T], &[T]) -> Vec<T>,
+        T: Float + Scalar,
+    {
+        let n = (self.f)(t, x0, u0).len();
+        let p = (self.g)(t, x0, u0).len();
+        let cols = if wrt_state { x0.len() } else { u0.len() };
+        let mut jf = DMatrix::from_element(n, cols, T::zero());
+        let mut jg = DMatrix::from_element(p, cols, T::zero());
+        let two = T::one() + T::one();
+        for j in 0..cols {
+            let (mut x_p, mut x_m) = (x0.to_vec(), x0.to_vec());
+            let (mut u_p, mut u_m) = (u0.to_vec(), u0.to_vec());
+            let (v_p, v_m) = if wrt_state {
+                (&mut x_p[j], &mut x_m[j])
+            } else {
+                (&mut u_p[j], &mut u_m[j])
+            };
+            // Step size that balances truncation and rounding errors.
+            let delta = Float::cbrt(T::epsilon()) * Float::max(T::one(), Float::abs(*v_p));
+            *v_p = *v_p + delta;
+            *v_m = *v_m - delta;
+            let f_p = (self.f)(t, &x_p, &u_p);
+            let f_m = (self.f)(t, &x_m, &u_m);
+            let g_p = (self.g)(t, &x_p, &u_p);
+            let g_m = (self.g)(t, &x_m, &u_m);
+            for i in 0..n {
+                jf[(i, j)] = (f_p[i] - f_m[i]) / (two * delta);
+            }
+            for i in 0..p {
+                jg[(i, j)] = (g_p[i] - g_m[i]) / (two * delta);
+            }
+        }
+        (jf, jg)
+    }
+}
+
+/// Define the order of the Runge-Kutta method.
+#[derive(Clone, Copy, Debug)]
+enum Order {
+    /// Runge-Kutta method of order 2.
+    Rk2,
+    /// Runge-Kutta method of order 4.
+    Rk4,
+}
+
+/// Struct for the time evolution of a nonlinear system using Runge-Kutta
+/// methods with fixed step
+#[derive(Debug)]
+pub struct Rk<'a, F, G, U, T>
+where
+    T: Float + Scalar,
+{
+    /// Nonlinear system
+    sys: &'a NonlinearSystem<F, G>,
+    /// Input function
+    input: U,
+    /// State vector.
+    state: DVector<T>,
+    /// Interval.
+    h: Seconds<T>,
+    /// Number of steps.
+    n: usize,
+    /// Index.
+    index: usize,
+    /// Order of the solver.
+    order: Order,
+}
+
+impl<'a, F, G, U, T> Rk<'a, F, G, U, T>
+where
+    F: Fn(Seconds<T>, &[T], &[T]) -> Vec<T>,
+    G: Fn(Seconds<T>, &[T], &[T]) -> Vec<T>,
+    U: Fn(Seconds<T>) -> Vec<T>,
+    T: AddAssign + Float + MulAssign + RkConst + Scalar,
+{
+    /// Create the solver for a Runge-Kutta method.
+    ///
+    /// # Arguments
+    ///
+    /// * `sys` - nonlinear system
+    /// * `u` - input function that returns a vector (column vector)
+    /// * `x0` - initial state (column vector)
+    /// * `h` - integration time interval
+    /// * `n` - integration steps
+    /// * `order` - order of the solver
+    fn new(
+        sys: &'a NonlinearSystem<F, G>,
+        u: U,
+        x0: &[T],
+        h: Seconds<T>,
+        n: usize,
+        order: Order,
+    ) -> Self {
+        Self {
+            sys,
+            input: u,
+            state: DVector::from_column_slice(x0),
+            h,
+            n,
+            index: 0,
+            order,
+        }
+    }
+
+    /// Derivative of the state.
+    fn derivative(&self, time: T, x: &DVector<T>) -> DVector<T> {
+        let u = (self.input)(Seconds(time));
+        DVector::from_vec((self.sys.f)(Seconds(time), x.as_slice(), &u))
+    }
+
+    /// Step with the current state at the given time.
+    fn step(&self, time: T) -> Step<T> {
+        let u = (self.input)(Seconds(time));
+        let output = (self.sys.g)(Seconds(time), self.state.as_slice(), &u);
+        Step::new(Seconds(time), self.state.as_slice().to_vec(), output)
+    }
+}
+
+/// Implementation of the Iterator trait for the `Rk` struct
+impl<'a, F, G, U, T> Iterator for Rk<'a, F, G, U, T>
+where
+    F: Fn(Seconds<T>, &[T], &[T]) -> Vec<T>,
+    G: Fn(Seconds<T>, &[T], &[T]) -> Vec<T>,
+    U: Fn(Seconds<T>) -> Vec<T>,
+    T: AddAssign + Float + MulAssign + RkConst + Scalar,
+{
+    type Item = Step<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index > self.n {
+            return None;
+        }
+        if self.index > 0 {
+            // Return None if conversion fails.
+            let t = T::from(self.index - 1)? * self.h.0;
+            let h = self.h.0;
+            match self.order {
+                Order::Rk2 => {
+                    // y_n+1 = y_n + 1/2(k1 + k2)
+                    let k1 = self.derivative(t, &self.state) * h;
+                    let k2 = self.derivative(t + h, &(&self.state + &k1)) * h;
+                    self.state += (k1 + k2) * T::_05;
+                }
+                Order::Rk4 => {
+                    // y_n+1 = y_n + h/6(k1 + 2*k2 + 2*k3 + k4)
+                    let h_2 = T::_05 * h;
+                    let k1 = self.derivative(t, &self.state);
+                    let k2 = self.derivative(t + h_2, &(&self.state + &k1 * h_2));
+                    let k3 = self.derivative(t + h_2, &(&self.state + &k2 * h_2));
+                    let k4 = self.derivative(t + h, &(&self.state + &k3 * h));
+                    let n_2 = T::A_RK[0];
+                    let n_6 = T::A_RK[1];
+                    self.state += (k1 + k2 * n_2 + k3 * n_2 + k4) * (h / n_6);
+                }
+            }
+        }
+        let time = T::from(self.index)? * self.h.0;
+        self.index += 1;
+        Some(self.step(time))
+    }
+}
+
+/// Struct for the time evolution of a nonlinear system using the
+/// Runge-Kutta-Fehlberg method with adaptive step
+#[derive(Debug)]
+pub struct Rkf45<'a, F, G, U, T>
+where
+    T: Float + Scalar,
+{
+    /// Nonlinear system
+    sys: &'a NonlinearSystem<F, G>,
+    /// Input function
+    input: U,
+    /// State vector.
+    state: DVector<T>,
+    /// Interval.
+    h: Seconds<T>,
+    /// Time limit of the evaluation
+    limit: Seconds<T>,
+    /// Time
+    time: Seconds<T>,
+    /// Tolerance
+    tol: T,
+    /// Minimum integration interval
+    h_min: T,
+    /// Is initial step
+    initial_step: bool,
+}
+
+impl<'a, F, G, U, T> Rkf45<'a, F, G, U, T>
+where
+    F: Fn(Seconds<T>, &[T], &[T]) -> Vec<T>,
+    G: Fn(Seconds<T>, &[T], &[T]) -> Vec<T>,
+    U: Fn(Seconds<T>) -> Vec<T>,
+    T: AddAssign + Float + MulAssign + Rkf45Const + Scalar,
+{
+    /// Create a solver using Runge-Kutta-Fehlberg method
+    ///
+    /// # Arguments
+    ///
+    /// * `sys` - nonlinear system
+    /// * `u` - input function (column vector)
+    /// * `x0` - initial state (column vector)
+    /// * `h` - integration time interval
+    /// * `limit` - time limit of the evaluation
+    /// * `tol` - error tolerance
+    fn new(
+        sys: &'a NonlinearSystem<F, G>,
+        u: U,
+        x0: &[T],
+        h: Seconds<T>,
+        limit: Seconds<T>,
+        tol: T,
+    ) -> Self {
+        Self {
+            sys,
+            input: u,
+            state: DVector::from_column_slice(x0),
+            h,
+            limit,
+            time: Seconds(T::zero()),
+            tol,
+            h_min: limit.0 * T::epsilon(),
+            initial_step: true,
+        }
+    }
+
+    /// Derivative of the state.
+    fn derivative(&self, time: T, x: &DVector<T>) -> DVector<T> {
+        let u = (self.input)(Seconds(time));
+        DVector::from_vec((self.sys.f)(Seconds(time), x.as_slice(), &u))
+    }
+
+    /// Runge-Kutta-Fehlberg order 4 and 5 method with adaptive step size
+    fn main_iteration(&mut self) -> StepWithError<T> {
+        let t = self.time.0;
+        let mut h = self.h.0;
+        let AdaptiveStep {
+            state,
+            step,
+            last,
+            error,
+        } = rkf45_step(
+            |time, x| self.derivative(time, x),
+            t,
+            &self.state,
+            &mut h,
+            (self.h_min, self.limit.0),
+            self.limit.0,
+            self.tol,
+        );
+        self.h.0 = h;
+        self.state = state;
+
+        self.time = if last { self.limit } else { Seconds(t + step) };
+        let u = (self.input)(self.time);
+        let output = (self.sys.g)(self.time, self.state.as_slice(), &u);
+        StepWithError::new(self.time, self.state.as_slice().to_vec(), output, error)
+    }
+}
+
+/// Implementation of the Iterator trait for the `Rkf45` struct
+impl<'a, F, G, U, T> Iterator for Rkf45<'a, F, G, U, T>
+where
+    F: Fn(Seconds<T>, &[T], &[T]) -> Vec<T>,
+    G: Fn(Seconds<T>, &[T], &[T]) -> Vec<T>,
+    U: Fn(Seconds<T>) -> Vec<T>,
+    T: AddAssign + Float + MulAssign + Rkf45Const + Scalar,
+{
+    type Item = StepWithError<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.initial_step {
+            self.initial_step = false;
+            let u = (self.input)(self.time);
+            let output = (self.sys.g)(self.time, self.state.as_slice(), &u);
+            Some(StepWithError::new(
+                self.time,
+                self.state.as_slice().to_vec(),
+                output,
+                T::zero(),
+            ))
+        } else if self.time >= self.limit {
+            None
+        } else {
+            Some(self.main_iteration())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn linear_system_comparison() {
+        let ss = Ss::new_from_slice(2, 1, 1, &[0., 1., -2., -0.5], &[0., 1.], &[1., 0.], &[0.5]);
+        let sys = NonlinearSystem::new(
+            |_t: Seconds<f64>, x: &[f64], u: &[f64]| vec![x[1], -2. * x[0] - 0.5 * x[1] + u[0]],
+            |_t: Seconds<f64>, x: &[f64], u: &[f64]| vec![x[0] + 0.5 * u[0]],
+        );
+        let input = |t: Seconds<f64>| vec![t.0.sin()];
+        let x0 = [1., 0.];
+        let linear = ss.rk4(input, &x0, Seconds(0.1), 50);
+        let nonlinear = sys.rk4(input, &x0, Seconds(0.1), 50);
+        for (l, n) in linear.zip(nonlinear) {
+            assert_relative_eq!(l.time().0, n.time().0);
+            assert_relative_eq!(l.state()[0], n.state()[0], max_relative = 1e-12);
+            assert_relative_eq!(l.state()[1], n.state()[1], max_relative = 1e-12);
+            assert_relative_eq!(l.output()[0], n.output()[0], max_relative = 1e-12);
+        }
+
+        let linear = ss.rk2(input, &x0, Seconds(0.1), 50);
+        let nonlinear = sys.rk2(input, &x0, Seconds(0.1), 50);
+        assert_eq!(linear.count(), nonlinear.count());
+    }
+
+    #[test]
+    fn rk2_exponential_decay() {
+        let sys = NonlinearSystem::new(
+            |_t: Seconds<f64>, x: &[f64], _u: &[f64]| vec![-x[0]],
+            |_t: Seconds<f64>, x: &[f64], _u: &[f64]| vec![x[0]],
+        );
+        let last = sys
+            .rk2(|_| vec![], &[1.], Seconds(0.001), 1000)
+            .last()
+            .unwrap();
+        assert_relative_eq!(1., last.time().0);
+        assert_relative_eq!((-1_f64).exp(), last.output()[0], max_relative = 1e-6);
+    }
+
+    #[test]
+    fn rkf45_riccati_equation() {
+        // xdot = -x^2, x(0) = 1 -> x(t) = 1 / (1 + t)
+        let sys = NonlinearSystem::new(
+            |_t: Seconds<f64>, x: &[f64], _u: &[f64]| vec![-x[0] * x[0]],
+            |_t: Seconds<f64>, x: &[f64], _u: &[f64]| vec![x[0]],
+        );
+        let evo: Vec<_> = sys
+            .rkf45(|_| vec![], &[1.], Seconds(0.1), Seconds(5.), 1e-8)
+            .collect();
+        assert_relative_eq!(0., evo[0].time().0);
+        assert_relative_eq!(1., evo[0].output()[0]);
+        let last = evo.last().unwrap();
+        assert_relative_eq!(5., last.time().0);
+        for s in &evo {
+            assert_relative_eq!(1. / (1. + s.time().0), s.state()[0], max_relative = 1e-6);
+        }
+    }
+
+    #[test]
+    fn pendulum_linearization() {
+        let pendulum = NonlinearSystem::new(
+            |_t: Seconds<f64>, x: &[f64], u: &[f64]| vec![x[1], -x[0].sin() - 0.1 * x[1] + u[0]],
+            |_t: Seconds<f64>, x: &[f64], u: &[f64]| vec![x[0], x[1] + 2. * u[0]],
+        );
+        let theta = std::f64::consts::FRAC_PI_4;
+        let sys = pendulum.linearize(Seconds(0.), &[theta, 0.], &[theta.sin()]);
+        let expected_a = DMatrix::from_row_slice(2, 2, &[0., 1., -theta.cos(), -0.1]);
+        assert_relative_eq!(expected_a, sys.a().clone(), max_relative = 1e-8);
+        assert_relative_eq!(
+            DMatrix::from_row_slice(2, 1, &[0., 1.]),
+            sys.b().clone(),
+            epsilon = 1e-8
+        );
+        assert_relative_eq!(
+            DMatrix::from_row_slice(2, 2, &[1., 0., 0., 1.]),
+            sys.c().clone(),
+            epsilon = 1e-8
+        );
+        assert_relative_eq!(
+            DMatrix::from_row_slice(2, 1, &[0., 2.]),
+            sys.d().clone(),
+            epsilon = 1e-8
+        );
+
+        // Small deviations from the operating point follow the linearization.
+        let x0 = [theta + 0.001, 0.];
+        let u = |_| vec![theta.sin()];
+        let nonlinear = pendulum.rk4(u, &x0, Seconds(0.01), 200).last().unwrap();
+        let linear = sys
+            .rk4(|_| vec![0.], &[0.001, 0.], Seconds(0.01), 200)
+            .last()
+            .unwrap();
+        assert_relative_eq!(
+            nonlinear.state()[0] - theta,
+            linear.state()[0],
+            epsilon = 1e-6
+        );
+    }
+}
@@ -3,13 +3,18 @@
 //! Bode plot returns the angular frequency, the magnitude and the phase.
 //!
 //! Functions use angular frequencies as default inputs and output, being the
-//! inverse of the poles and zeros time constants.
+//! inverse of the poles and zeros time constants. The frequencies of the
+//! output can be converted into Hertz with
+//! [into_hz](struct.IntoIter.html#method.into_hz).
 
+use num_complex::Complex;
 use num_traits::{Float, FloatConst, MulAdd, Num};
 
+use std::collections::VecDeque;
+
 use crate::{
-    plots::Plotter,
-    units::{Hertz, RadiansPerSecond, ToDecibel},
+    plots::{exp10, frequency_grid, FrequencyPoint, Grid, Plotter, Refinement},
+    units::{Degrees, Hertz, NormalizedFrequency, Radians, RadiansPerSecond, Seconds, ToDecibel},
 };
 
 /// Struct for the calculation of Bode plots
@@ -23,6 +28,13 @@ pub struct Bode<T: Num, U: Plotter<T>> {
     max_freq: RadiansPerSecond<T>,
     /// Step between frequencies
     step: T,
+    /// Adaptive refinement of the frequency grid
+    refinement: Option<Refinement<T>>,
+    /// Sampling time of discrete systems, the frequency response is evaluated
+    /// at the product of the angular frequency and the sampling time
+    sample_time: T,
+    /// Exponents of the angular frequencies of an explicit grid
+    grid: Option<Vec<T>>,
 }
 
 impl<T: Float, U: Plotter<T>> Bode<T, U> {
@@ -55,8 +67,66 @@ impl<T: Float, U: Plotter<T>> Bode<T, U> {
             min_freq,
             max_freq,
             step,
+            refinement: None,
+            sample_time: T::one(),
+            grid: None,
         }
     }
+
+    /// Create a `Bode` plot struct evaluated at the given angular
+    /// frequencies, for example the frequencies of a measured frequency
+    /// response.
+    ///
+    /// # Arguments
+    ///
+    /// * `tf` - Transfer function to plot
+    /// * `frequencies` - Angular frequencies of the plot
+    ///
+    /// # Panics
+    ///
+    /// Panics if the frequencies are not strictly positive and strictly
+    /// increasing.
+    ///
+    /// # Example
+    /// ```
+    /// use au::{plots::bode::Bode, poly, RadiansPerSecond, Tf};
+    /// let tf = Tf::new(poly!(1.), poly!(1., 1.));
+    /// let measured = [0.3, 1.7, 42.];
+    /// let bode = Bode::new_from_frequencies(tf, measured.iter().map(|&w| RadiansPerSecond(w)));
+    /// let freqs: Vec<_> = bode.into_iter().map(|d| d.angular_frequency().0).collect();
+    /// assert_eq!(3, freqs.len());
+    /// assert!((freqs[1] - 1.7_f64).abs() < 1e-12);
+    /// ```
+    pub fn new_from_frequencies<I>(tf: U, frequencies: I) -> Self
+    where
+        I: IntoIterator<Item = RadiansPerSecond<T>>,
+    {
+        let (grid, step) = frequency_grid(frequencies);
+        let min = grid.first().map_or(T::one(), |&e| exp10(e));
+        let max = grid.last().map_or(T::one(), |&e| exp10(e));
+
+        Self {
+            tf,
+            min_freq: RadiansPerSecond(min),
+            max_freq: RadiansPerSecond(max),
+            step,
+            refinement: None,
+            sample_time: T::one(),
+            grid: Some(grid),
+        }
+    }
+
+    /// Refine the frequency grid where the frequency response changes fast,
+    /// the step becomes the coarsest step between frequencies.
+    ///
+    /// # Arguments
+    ///
+    /// * `refinement` - Criteria for the refinement of the grid
+    #[must_use]
+    pub fn with_refinement(mut self, refinement: Refinement<T>) -> Self {
+        self.refinement = Some(refinement);
+        self
+    }
 }
 
 impl<T: Float + FloatConst, U: Plotter<T>> Bode<T, U> {
@@ -65,7 +135,7 @@ impl<T: Float + FloatConst, U: Plotter<T>> Bode<T, U> {
     /// # Arguments
     ///
     /// * `tf` - Transfer function to plot
-    /// * `min_freq` - Minimum angular frequency of the plot
+    /// * `min_freq` - Minimum normalized frequency of the plot
     /// * `step` - Step between frequencies
     ///
     /// `step` shall be in logarithmic scale. Use 0.1 to have 10 point per decade
@@ -74,16 +144,77 @@ impl<T: Float + FloatConst, U: Plotter<T>> Bode<T, U> {
     ///
     /// Panics if the step is not strictly positive and the minimum frequency
     /// is not lower than pi.
-    pub fn new_discrete(tf: U, min_freq: RadiansPerSecond<T>, step: T) -> Self {
+    pub fn new_discrete(tf: U, min_freq: NormalizedFrequency<T>, step: T) -> Self {
         let pi = RadiansPerSecond(T::PI());
         assert!(step > T::zero());
-        assert!(min_freq < pi);
+        assert!(min_freq.0 < pi.0);
 
         Self {
             tf,
-            min_freq,
+            min_freq: RadiansPerSecond(min_freq.0),
             max_freq: pi,
             step,
+            refinement: None,
+            sample_time: T::one(),
+            grid: None,
+        }
+    }
+
+    /// Create a `Bode` plot struct for discrete time systems with the given
+    /// sampling time. The frequency response is evaluated on the unit circle
+    /// at `e^(j*omega*Ts)` up to the Nyquist frequency `pi/Ts`, the
+    /// frequencies are in radians per second.
+    ///
+    /// # Arguments
+    ///
+    /// * `tf` - Transfer function to plot
+    /// * `sample_time` - Sampling time
+    /// * `min_freq` - Minimum angular frequency of the plot
+    /// * `step` - Step between frequencies
+    ///
+    /// `step` shall be in logarithmic scale. Use 0.1 to have 10 point per decade
+    ///
+    /// # Panics
+    ///
+    /// Panics if the step or the sampling time are not strictly positive, if
+    /// the minimum frequency is not lower than the Nyquist frequency or if the
+    /// transfer function has a different sampling time.
+    ///
+    /// # Example
+    /// ```
+    /// use au::{plots::bode::Bode, poly, RadiansPerSecond, Seconds, Tfz};
+    /// let tfz = Tfz::new(poly!(0.5), poly!(-0.5, 1.));
+    /// let bode = Bode::new_sampled(tfz, Seconds(0.1), RadiansPerSecond(0.1), 0.1);
+    /// let data: Vec<_> = bode.into_iter().collect();
+    /// let nyquist = 10. * std::f64::consts::PI;
+    /// assert!(data.iter().all(|d| d.angular_frequency().0 <= nyquist));
+    /// assert!((data[0].magnitude() - 1.).abs() < 1e-3);
+    /// ```
+    pub fn new_sampled(
+        tf: U,
+        sample_time: Seconds<T>,
+        min_freq: RadiansPerSecond<T>,
+        step: T,
+    ) -> Self {
+        assert!(sample_time.0 > T::zero());
+        if let Some(st) = tf.sample_time() {
+            assert!(
+                crate::units::same_sample_time(st, sample_time.0),
+                "Sampling time different from the one of the transfer function"
+            );
+        }
+        let nyquist = RadiansPerSecond(T::PI() / sample_time.0);
+        assert!(step > T::zero());
+        assert!(min_freq < nyquist);
+
+        Self {
+            tf,
+            min_freq,
+            max_freq: nyquist,
+            step,
+            refinement: None,
+            sample_time: sample_time.0,
+            grid: None,
         }
     }
 }
@@ -95,13 +226,23 @@ impl<T: Float + MulAdd<Output = T>, U: Plotter<T>> IntoIterator for Bode<T, U> {
     fn into_iter(self) -> Self::IntoIter {
         let min = self.min_freq.0.log10();
         let max = self.max_freq.0.log10();
-        let intervals = ((max - min) / self.step).floor();
+        let intervals = match &self.grid {
+            Some(grid) => T::from(grid.len()).unwrap() - T::one(),
+            None => ((max - min) / self.step).floor(),
+        };
         Self::IntoIter {
             tf: self.tf,
             intervals,
             step: self.step,
             base_freq: RadiansPerSecond(min),
             index: T::zero(),
+            refinement: self.refinement,
+            sample_time: self.sample_time,
+            grid: self.grid,
+            last: None,
+            pending: Vec::new(),
+            unwrap: false,
+            previous_phase: None,
         }
     }
 }
@@ -119,6 +260,93 @@ pub struct IntoIter<T: Float, U: Plotter<T>> {
     base_freq: RadiansPerSecond<T>,
     /// Current data index
     index: T,
+    /// Adaptive refinement of the frequency grid
+    refinement: Option<Refinement<T>>,
+    /// Sampling time of discrete systems
+    sample_time: T,
+    /// Exponents of the angular frequencies of an explicit grid
+    grid: Option<Vec<T>>,
+    /// Last point of the refined grid
+    last: Option<(T, Complex<T>)>,
+    /// Points of the refined grid not yet returned
+    pending: Vec<(T, Complex<T>)>,
+    /// Accumulate the phase across frequencies
+    unwrap: bool,
+    /// Phase of the previous data point
+    previous_phase: Option<T>,
+}
+
+impl<T: Float, U: Plotter<T>> IntoIter<T, U> {
+    /// Return a continuous phase, without the jumps of 2*pi due to the
+    /// principal value of the argument of the frequency response.
+    ///
+    /// The phase of the first point is the principal value, the following
+    /// ones differ from the previous phase by less than pi.
+    ///
+    /// # Example
+    /// ```
+    /// use au::{plots::bode::Bode, Poly, RadiansPerSecond, Tf};
+    /// let tf = Tf::new(Poly::new_from_coeffs(&[1.]), Poly::new_from_roots(&[-1., -1., -1.]));
+    /// let bode = Bode::new(tf, RadiansPerSecond(0.1), RadiansPerSecond(100.), 0.1);
+    /// let last = bode.into_iter().into_unwrapped().last().unwrap();
+    /// assert!(last.phase() < -std::f64::consts::PI);
+    /// ```
+    #[must_use]
+    pub fn into_unwrapped(self) -> Self {
+        Self {
+            unwrap: true,
+            ..self
+        }
+    }
+}
+
+impl<T: Float + MulAdd<Output = T>, U: Plotter<T>> IntoIter<T, U> {
+    /// Collect the data points into the parallel arrays of the angular
+    /// frequencies (rad/s), the magnitudes and the phases (rad).
+    ///
+    /// # Example
+    /// ```
+    /// use au::{plots::bode::Bode, poly, RadiansPerSecond, Tf};
+    /// let tf = Tf::new(poly!(1.), poly!(0., 1.));
+    /// let bode = Bode::new(tf, RadiansPerSecond(1.), RadiansPerSecond(100.), 1.);
+    /// let (freq, mag, phase) = bode.into_iter().into_vecs();
+    /// assert_eq!(vec![1., 10., 100.], freq);
+    /// assert_eq!(vec![1., 0.1, 0.01], mag);
+    /// assert_eq!(3, phase.len());
+    /// ```
+    #[must_use]
+    pub fn into_vecs(self) -> (Vec<T>, Vec<T>, Vec<T>) {
+        let mut arrays = (Vec::new(), Vec::new(), Vec::new());
+        for d in self {
+            arrays.0.push(d.angular_frequency.0);
+            arrays.1.push(d.magnitude);
+            arrays.2.push(d.phase);
+        }
+        arrays
+    }
+}
+
+impl<T: Float + FloatConst + MulAdd<Output = T>, U: Plotter<T>> IntoIter<T, U> {
+    /// Convert the frequencies of `Bode` into Hertz.
+    ///
+    /// The data points in decibels and degrees are converted mapping them
+    /// with `HertzData::from`.
+    ///
+    /// # Example
+    /// ```
+    /// use au::{plots::bode::{Bode, HertzData}, poly, RadiansPerSecond, Tf};
+    /// let tf = Tf::new(poly!(1.), poly!(1., 1.));
+    /// let bode = Bode::new(tf, RadiansPerSecond(1.), RadiansPerSecond(10.), 1.);
+    /// let hz: Vec<_> = bode.clone().into_iter().into_hz().map(|d| d.frequency().0).collect();
+    /// let tau = 2. * std::f64::consts::PI;
+    /// assert!((hz[0] - 1. / tau).abs() < 1e-12 && (hz[1] - 10. / tau).abs() < 1e-12);
+    ///
+    /// let first = bode.into_iter().into_db_deg().map(HertzData::from).next().unwrap();
+    /// assert!((first.magnitude() + 3.0103).abs() < 1e-4);
+    /// ```
+    pub fn into_hz(self) -> impl Iterator<Item = HertzData<T>> {
+        self.map(HertzData::from)
+    }
 }
 
 impl<T: Float + MulAdd<Output = T> + ToDecibel, U: Plotter<T>> IntoIter<T, U> {
@@ -127,6 +355,7 @@ impl<T: Float + MulAdd<Output = T> + ToDecibel, U: Plotter<T>> IntoIter<T, U> {
         self.map(|g| Data {
             magnitude: g.magnitude.to_db(),
             phase: g.phase.to_degrees(),
+            db_deg: true,
             ..g
         })
     }
@@ -141,6 +370,12 @@ pub struct Data<T: Num> {
     magnitude: T,
     /// Phase (rad or degrees)
     phase: T,
+    /// Group delay
+    group_delay: T,
+    /// Value of the frequency response
+    output: Complex<T>,
+    /// Magnitude in decibels and phase in degrees
+    db_deg: bool,
 }
 
 impl<T: Float + FloatConst> Data<T> {
@@ -159,10 +394,165 @@ impl<T: Float + FloatConst> Data<T> {
         self.magnitude
     }
 
-    /// Get the phase
+    /// Get the phase, in radians or in degrees if the data have been
+    /// converted with `into_db_deg`
+    pub fn phase(&self) -> T {
+        self.phase
+    }
+
+    /// Get the phase in radians, independently from the conversion of the
+    /// data
+    ///
+    /// # Example
+    /// ```
+    /// use au::{plots::bode::Bode, poly, RadiansPerSecond, Tf};
+    /// let tf = Tf::new(poly!(1.), poly!(0., 1.));
+    /// let bode = Bode::new(tf, RadiansPerSecond(1.), RadiansPerSecond(10.), 1.);
+    /// let first = bode.into_iter().into_db_deg().next().unwrap();
+    /// assert_eq!(-90., first.phase_deg().0);
+    /// assert!((first.phase_rad().0 + std::f64::consts::FRAC_PI_2).abs() < 1e-12);
+    /// ```
+    #[must_use]
+    pub fn phase_rad(&self) -> Radians<T> {
+        if self.db_deg {
+            Degrees(self.phase).into()
+        } else {
+            Radians(self.phase)
+        }
+    }
+
+    /// Get the phase in degrees, independently from the conversion of the
+    /// data
+    #[must_use]
+    pub fn phase_deg(&self) -> Degrees<T> {
+        if self.db_deg {
+            Degrees(self.phase)
+        } else {
+            Radians(self.phase).into()
+        }
+    }
+
+    /// Get the group delay, the opposite of the derivative of the phase with
+    /// respect to the angular frequency.
+    ///
+    /// It is in seconds for continuous systems and for discrete systems with
+    /// a sampling time, in samples for discrete systems with normalized
+    /// frequencies.
+    ///
+    /// # Example
+    /// ```
+    /// use au::{plots::bode::Bode, poly, RadiansPerSecond, Tf};
+    /// // Delay approximation G(s) = (1 - s/2) / (1 + s/2), 1 s at low frequency.
+    /// let tf = Tf::new(poly!(1., -0.5), poly!(1., 0.5));
+    /// let bode = Bode::new(tf, RadiansPerSecond(0.001), RadiansPerSecond(0.01), 1.);
+    /// let first = bode.into_iter().next().unwrap();
+    /// assert!((first.group_delay() - 1_f64).abs() < 1e-5);
+    /// ```
+    #[must_use]
+    pub fn group_delay(&self) -> T {
+        self.group_delay
+    }
+}
+
+impl<T: Float> FrequencyPoint<T> for Data<T> {
+    fn angular_frequency(&self) -> RadiansPerSecond<T> {
+        self.angular_frequency
+    }
+
+    fn value(&self) -> Complex<T> {
+        self.output
+    }
+}
+
+/// Struct to hold the data of the Bode plot with the frequency in Hertz
+#[derive(Debug, PartialEq)]
+pub struct HertzData<T: Num> {
+    /// Frequency (Hz)
+    frequency: Hertz<T>,
+    /// Magnitude (absolute value or dB)
+    magnitude: T,
+    /// Phase (rad or degrees)
+    phase: T,
+    /// Group delay
+    group_delay: T,
+    /// Value of the frequency response
+    output: Complex<T>,
+    /// Magnitude in decibels and phase in degrees
+    db_deg: bool,
+}
+
+impl<T: Float> HertzData<T> {
+    /// Get the frequency
+    #[must_use]
+    pub fn frequency(&self) -> Hertz<T> {
+        self.frequency
+    }
+
+    /// Get the magnitude
+    #[must_use]
+    pub fn magnitude(&self) -> T {
+        self.magnitude
+    }
+
+    /// Get the phase, in radians or in degrees if the data have been
+    /// converted with `into_db_deg`
+    #[must_use]
     pub fn phase(&self) -> T {
         self.phase
     }
+
+    /// Get the phase in radians, independently from the conversion of the
+    /// data
+    #[must_use]
+    pub fn phase_rad(&self) -> Radians<T> {
+        if self.db_deg {
+            Degrees(self.phase).into()
+        } else {
+            Radians(self.phase)
+        }
+    }
+
+    /// Get the phase in degrees, independently from the conversion of the
+    /// data
+    #[must_use]
+    pub fn phase_deg(&self) -> Degrees<T> {
+        if self.db_deg {
+            Degrees(self.phase)
+        } else {
+            Radians(self.phase).into()
+        }
+    }
+
+    /// Get the group delay, the opposite of the derivative of the phase with
+    /// respect to the angular frequency.
+    #[must_use]
+    pub fn group_delay(&self) -> T {
+        self.group_delay
+    }
+}
+
+impl<T: Float + FloatConst> FrequencyPoint<T> for HertzData<T> {
+    fn angular_frequency(&self) -> RadiansPerSecond<T> {
+        self.frequency.into()
+    }
+
+    fn value(&self) -> Complex<T> {
+        self.output
+    }
+}
+
+impl<T: Float + FloatConst> From<Data<T>> for HertzData<T> {
+    /// Convert the angular frequency of the Bode data into Hertz.
+    fn from(data: Data<T>) -> Self {
+        Self {
+            frequency: data.angular_frequency.into(),
+            magnitude: data.magnitude,
+            phase: data.phase,
+            group_delay: data.group_delay,
+            output: data.output,
+            db_deg: data.db_deg,
+        }
+    }
 }
 
 /// Implementation of the Iterator trait for `Bode` struct
@@ -170,21 +560,263 @@ impl<T: Float + MulAdd<Output = T>, U: Plotter<T>> Iterator for IntoIter<T, U> {
     type Item = Data<T>;
 
     fn next(&mut self) -> Option<Self::Item> {
+        let (freq_exponent, g) = self.next_point()?;
+        let phase = match (self.unwrap, self.previous_phase) {
+            (true, Some(previous)) => {
+                let tau = T::from(2. * std::f64::consts::PI).unwrap();
+                let turns = ((previous - g.arg()) / tau).round();
+                g.arg() + turns * tau
+            }
+            _ => g.arg(),
+        };
+        self.previous_phase = Some(phase);
+        let omega = exp10(freq_exponent);
+        let group_delay = -self.sample_time * self.tf.phase_derivative(omega * self.sample_time);
+        Some(Data {
+            angular_frequency: RadiansPerSecond(omega),
+            magnitude: g.norm(),
+            phase,
+            group_delay,
+            output: g,
+            db_deg: false,
+        })
+    }
+}
+
+impl<T: Float + MulAdd<Output = T>, U: Plotter<T>> IntoIter<T, U> {
+    /// Get the exponent of the next angular frequency and the value of the
+    /// frequency response.
+    fn next_point(&mut self) -> Option<(T, Complex<T>)> {
+        let point = match (self.refinement, self.last) {
+            (Some(refinement), Some(last)) => {
+                let step = self.step;
+                let mut pending = std::mem::take(&mut self.pending);
+                let point = refinement.next_point(self, step, &last, &mut pending);
+                self.pending = pending;
+                point?
+            }
+            _ => self.coarse_point()?,
+        };
+        if self.refinement.is_some() {
+            self.last = Some(point);
+        }
+        Some(point)
+    }
+}
+
+impl<T: Float + MulAdd<Output = T>, U: Plotter<T>> Grid<T, Complex<T>> for IntoIter<T, U> {
+    /// Get the next point of the coarse frequency grid.
+    fn coarse_point(&mut self) -> Option<(T, Complex<T>)> {
         if self.index > self.intervals {
             None
         } else {
-            let freq_exponent = MulAdd::mul_add(self.step, self.index, self.base_freq.0);
-            // Casting is safe for both f32 and f64, representation is exact.
-            let omega = T::from(10.0_f32).unwrap().powf(freq_exponent);
-            let g = self.tf.eval_point(omega);
+            let freq_exponent = match &self.grid {
+                Some(grid) => grid[self.index.to_usize().unwrap()],
+                None => MulAdd::mul_add(self.step, self.index, self.base_freq.0),
+            };
             self.index = self.index + T::one();
-            Some(Data {
-                angular_frequency: RadiansPerSecond(omega),
-                magnitude: g.norm(),
-                phase: g.arg(),
-            })
+            Some((freq_exponent, self.eval(freq_exponent)))
+        }
+    }
+
+    /// Evaluate the frequency response at the exponent of the angular
+    /// frequency.
+    fn eval(&self, freq_exponent: T) -> Complex<T> {
+        self.tf.eval_point(exp10(freq_exponent) * self.sample_time)
+    }
+}
+
+/// Items of the Bode iterator with crossover detection
+#[derive(Debug, PartialEq)]
+pub enum Event<T: Num> {
+    /// Data point of the Bode plot
+    Point(Data<T>),
+    /// The magnitude crosses 1 (0 dB) at the given angular frequency
+    GainCrossover(RadiansPerSecond<T>),
+    /// The phase crosses -pi (-180°), or an odd multiple of pi, at the given
+    /// angular frequency
+    PhaseCrossover(RadiansPerSecond<T>),
+}
+
+impl<T: Float + MulAdd<Output = T>, U: Plotter<T>> IntoIter<T, U> {
+    /// Detect the gain and phase crossovers while iterating.
+    ///
+    /// The crossover frequencies are linearly interpolated between
+    /// consecutive data points, the magnitude in logarithmic scale and the
+    /// phase after unwrapping, in the logarithmic frequency scale.
+    /// Crossovers are emitted before the data point that follows them.
+    ///
+    /// # Example
+    /// ```
+    /// use au::{plots::bode::{Bode, Event}, Poly, RadiansPerSecond, Tf};
+    /// let tf = Tf::new(Poly::new_from_coeffs(&[8.]), Poly::new_from_roots(&[-1., -1., -1.]));
+    /// let bode = Bode::new(tf, RadiansPerSecond(0.1), RadiansPerSecond(10.), 0.01);
+    /// let phase_crossover = bode
+    ///     .into_iter()
+    ///     .into_crossovers()
+    ///     .find_map(|e| match e {
+    ///         Event::PhaseCrossover(w) => Some(w),
+    ///         _ => None,
+    ///     })
+    ///     .unwrap();
+    /// assert!((phase_crossover.0 - 3_f64.sqrt()).abs() < 1e-3);
+    /// ```
+    #[must_use]
+    pub fn into_crossovers(self) -> Crossovers<T, U> {
+        Crossovers {
+            iter: self,
+            previous: None,
+            queue: VecDeque::new(),
+        }
+    }
+}
+
+/// Bode iterator with crossover detection.
+#[derive(Debug)]
+pub struct Crossovers<T: Float, U: Plotter<T>> {
+    /// Bode iterator
+    iter: IntoIter<T, U>,
+    /// Logarithm of the frequency, logarithm of the magnitude and unwrapped
+    /// phase of the previous data point
+    previous: Option<(T, T, T)>,
+    /// Items ready to be emitted
+    queue: VecDeque<Event<T>>,
+}
+
+/// Interpolate the abscissa where the segment crosses the given level.
+fn crossing<T: Float>((x0, y0): (T, T), (x1, y1): (T, T), level: T) -> T {
+    x0 + (x1 - x0) * (level - y0) / (y1 - y0)
+}
+
+impl<T: Float + MulAdd<Output = T>, U: Plotter<T>> Iterator for Crossovers<T, U> {
+    type Item = Event<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(event) = self.queue.pop_front() {
+            return Some(event);
+        }
+        let data = self.iter.next()?;
+        let log_freq = data.angular_frequency.0.log10();
+        let log_mag = data.magnitude.log10();
+        let pi = T::from(std::f64::consts::PI).unwrap();
+        let tau = pi + pi;
+        let phase = match self.previous {
+            Some((_, _, p)) => data.phase + ((p - data.phase) / tau).round() * tau,
+            None => data.phase,
+        };
+        if let Some((f0, m0, p0)) = self.previous {
+            let mut events = Vec::new();
+            if (m0 < T::zero()) != (log_mag < T::zero()) {
+                let f = crossing((f0, m0), (log_freq, log_mag), T::zero());
+                events.push((f, true));
+            }
+            // Odd multiples of pi between the two phases.
+            let (low, high) = (p0.min(phase), p0.max(phase));
+            let mut k = ((low + pi) / tau).ceil();
+            while k * tau - pi <= high {
+                let level = k * tau - pi;
+                if level != p0 {
+                    let f = crossing((f0, p0), (log_freq, phase), level);
+                    events.push((f, false));
+                }
+                k = k + T::one();
+            }
+            events.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+            for (f, gain) in events {
+                let w = RadiansPerSecond(exp10(f));
+                self.queue.push_back(if gain {
+                    Event::GainCrossover(w)
+                } else {
+                    Event::PhaseCrossover(w)
+                });
+            }
         }
+        self.previous = Some((log_freq, log_mag, phase));
+        self.queue.push_back(Event::Point(data));
+        self.queue.pop_front()
+    }
+}
+
+/// Resonance peak of the magnitude of the frequency response
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Peak<T: Num> {
+    /// Resonant angular frequency
+    angular_frequency: RadiansPerSecond<T>,
+    /// Peak magnitude (absolute value)
+    magnitude: T,
+}
+
+impl<T: Float> Peak<T> {
+    /// Get the resonant angular frequency
+    #[must_use]
+    pub fn angular_frequency(&self) -> RadiansPerSecond<T> {
+        self.angular_frequency
+    }
+
+    /// Get the peak magnitude (absolute value)
+    #[must_use]
+    pub fn magnitude(&self) -> T {
+        self.magnitude
+    }
+}
+
+impl<T: Float + MulAdd<Output = T>, U: Plotter<T>> IntoIter<T, U> {
+    /// Consume the iterator and locate the peaks of the magnitude, the local
+    /// maxima between the first and the last frequency.
+    ///
+    /// The resonant frequency and the peak gain are interpolated with the
+    /// parabola through the sampled maximum and its neighbours, in
+    /// logarithmic scale for both the frequency and the magnitude.
+    ///
+    /// # Example
+    /// ```
+    /// use au::{plots::bode::Bode, poly, RadiansPerSecond, Tf};
+    /// // Lightly damped mode, natural frequency 10 rad/s and damping 0.05.
+    /// let tf = Tf::new(poly!(100.), poly!(100., 1., 1.));
+    /// let bode = Bode::new(tf, RadiansPerSecond(1.), RadiansPerSecond(100.), 0.01);
+    /// let peak = bode.into_iter().resonance_peaks()[0];
+    /// let z = 0.05_f64;
+    /// let wr = 10. * (1. - 2. * z * z).sqrt();
+    /// let mr = 1. / (2. * z * (1. - z * z).sqrt());
+    /// assert!((peak.angular_frequency().0 - wr).abs() < 1e-3 * wr);
+    /// assert!((peak.magnitude() - mr).abs() < 1e-3 * mr);
+    /// ```
+    #[must_use]
+    pub fn resonance_peaks(self) -> Vec<Peak<T>> {
+        let points: Vec<_> = self
+            .map(|d| (d.angular_frequency.0.log10(), d.magnitude.log10()))
+            .collect();
+        points
+            .windows(3)
+            .filter(|w| w[1].1 > w[0].1 && w[1].1 >= w[2].1)
+            .map(|w| {
+                let (x, y) = parabola_vertex(w[0], w[1], w[2]).unwrap_or(w[1]);
+                Peak {
+                    angular_frequency: RadiansPerSecond(exp10(x)),
+                    magnitude: exp10(y),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Vertex of the parabola through three points, `None` if the parabola is
+/// not concave or the vertex lies outside the interval of the points.
+fn parabola_vertex<T: Float>(p0: (T, T), p1: (T, T), p2: (T, T)) -> Option<(T, T)> {
+    // Divided differences of the Newton form y = y0 + d1 (x - x0) + d2 (x - x0) (x - x1).
+    let d01 = (p1.1 - p0.1) / (p1.0 - p0.0);
+    let d12 = (p2.1 - p1.1) / (p2.0 - p1.0);
+    let d2 = (d12 - d01) / (p2.0 - p0.0);
+    if d2.is_nan() || d2 >= T::zero() {
+        return None;
+    }
+    let two = T::one() + T::one();
+    let x = (p0.0 + p1.0) / two - d01 / (two * d2);
+    if x < p0.0 || x > p2.0 {
+        return None;
     }
+    let y = p0.1 + (x - p0.0) * (d01 + d2 * (x - p1.0));
+    Some((x, y))
 }
 
 #[cfg(test)]
@@ -204,13 +836,162 @@ mod tests {
         assert_relative_eq!(0., iter.index);
     }
 
+    #[test]
+    fn explicit_frequencies() {
+        let tf = Tf::new(poly!(1.), poly!(1., 1.));
+        let freqs = [0.01, 0.5, 0.7, 300.];
+        let grid = freqs.iter().map(|&w| RadiansPerSecond(w));
+        let iter = Bode::new_from_frequencies(tf.clone(), grid).into_iter();
+        assert_relative_eq!(3., iter.intervals);
+        let data: Vec<_> = iter.collect();
+        assert_eq!(4, data.len());
+        for (d, &w) in data.iter().zip(&freqs) {
+            assert_relative_eq!(w, d.angular_frequency().0, max_relative = 1e-12);
+            assert_relative_eq!(tf.eval_point(w).norm(), d.magnitude(), max_relative = 1e-12);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn explicit_frequencies_not_positive() {
+        let tf = Tf::new(poly!(1.), poly!(1., 1.));
+        let _ = Bode::new_from_frequencies(tf, vec![RadiansPerSecond(0.), RadiansPerSecond(1.)]);
+    }
+
+    #[test]
+    fn hertz_output() {
+        let tf = Tf::new(poly!(1., -0.5), poly!(1., 0.5));
+        let bode = Bode::new(tf, RadiansPerSecond(0.1), RadiansPerSecond(10.), 0.5);
+        for (w, f) in bode.clone().into_iter().zip(bode.into_iter().into_hz()) {
+            assert_relative_eq!(w.frequency().0, f.frequency().0);
+            assert_relative_eq!(w.magnitude(), f.magnitude());
+            assert_relative_eq!(w.phase(), f.phase());
+            assert_relative_eq!(w.group_delay(), f.group_delay());
+        }
+    }
+
+    #[test]
+    fn vertex_of_parabola() {
+        // y = 2 - (x - 1)^2
+        let (x, y) = parabola_vertex((0., 1.), (0.5, 1.75), (3., -2.)).unwrap();
+        assert_relative_eq!(1., x, max_relative = 1e-12);
+        assert_relative_eq!(2., y, max_relative = 1e-12);
+        assert_eq!(None, parabola_vertex((0., 0.), (1., 1.), (2., 2.)));
+    }
+
+    #[test]
+    fn two_resonances() {
+        let first = Tf::new(poly!(4.), poly!(4., 0.2, 1.));
+        let second = Tf::new(poly!(900.), poly!(900., 3., 1.));
+        let bode = Bode::new(
+            first * second,
+            RadiansPerSecond(0.1),
+            RadiansPerSecond(100.),
+            0.02,
+        );
+        let peaks = bode.into_iter().resonance_peaks();
+        assert_eq!(2, peaks.len());
+        assert_relative_eq!(2., peaks[0].angular_frequency().0, max_relative = 0.01);
+        assert_relative_eq!(30., peaks[1].angular_frequency().0, max_relative = 0.01);
+    }
+
+    #[test]
+    fn no_resonance() {
+        let tf = Tf::new(poly!(1.), poly!(1., 1.));
+        let bode = Bode::new(tf, RadiansPerSecond(0.1), RadiansPerSecond(10.), 0.1);
+        assert!(bode.into_iter().resonance_peaks().is_empty());
+    }
+
+    #[test]
+    fn frequency_point() {
+        let tf = Tf::new(poly!(1.), poly!(1., 1.));
+        let bode = Bode::new(
+            tf.clone(),
+            RadiansPerSecond(0.1),
+            RadiansPerSecond(10.),
+            0.5,
+        );
+        for d in bode.into_iter().into_db_deg() {
+            let w = FrequencyPoint::angular_frequency(&d);
+            assert_eq!(d.angular_frequency(), w);
+            assert_relative_eq!(tf.eval_point(w.0).re, d.value().re, max_relative = 1e-12);
+        }
+    }
+
     #[test]
     fn create_discrete() {
         let tf = Tfz::new(poly!(2., 3.), poly!(1., 1., 1.));
-        let iter = Bode::new_discrete(tf, RadiansPerSecond(0.01), 0.1).into_iter();
+        let iter = Bode::new_discrete(tf, NormalizedFrequency(0.01), 0.1).into_iter();
         assert!(iter.last().unwrap().angular_frequency().0 < std::f32::consts::PI);
     }
 
+    #[test]
+    fn sampled_discrete() {
+        let tf = Tfz::new(poly!(2., 3.), poly!(1., 1., 1.));
+        let normalized: Vec<_> = Bode::new_discrete(tf.clone(), NormalizedFrequency(0.01), 0.1)
+            .into_iter()
+            .collect();
+        let sampled: Vec<_> = Bode::new_sampled(tf, Seconds(0.5), RadiansPerSecond(0.02), 0.1)
+            .into_iter()
+            .collect();
+        assert_eq!(normalized.len(), sampled.len());
+        for (n, s) in normalized.iter().zip(&sampled) {
+            assert_relative_eq!(
+                n.angular_frequency().0 * 2.,
+                s.angular_frequency().0,
+                max_relative = 1e-12
+            );
+            assert_relative_eq!(n.magnitude(), s.magnitude(), max_relative = 1e-12);
+            assert_relative_eq!(n.phase(), s.phase(), max_relative = 1e-12);
+        }
+        assert!(sampled.last().unwrap().angular_frequency().0 <= 2. * std::f64::consts::PI);
+    }
+
+    #[test]
+    fn sampled_discrete_refined() {
+        // Resonant discrete system, poles at 0.99*exp(+-0.5j).
+        let tf = Tfz::new(poly!(1.), poly!(0.9801, -1.98 * 0.5_f64.cos(), 1.));
+        let refined: Vec<_> = Bode::new_sampled(tf, Seconds(0.01), RadiansPerSecond(1.), 0.5)
+            .with_refinement(Refinement::new(1., 0.1, 10))
+            .into_iter()
+            .collect();
+        let peak = refined
+            .iter()
+            .max_by(|a, b| a.magnitude().partial_cmp(&b.magnitude()).unwrap())
+            .unwrap();
+        // Resonance at 0.5 rad per sample, 50 rad/s.
+        assert_relative_eq!(50., peak.angular_frequency().0, max_relative = 0.02);
+    }
+
+    #[test]
+    fn group_delay_continuous() {
+        // G(s) = 1 / (s + 1), group delay 1 / (1 + w^2).
+        let tf = Tf::new(poly!(1.), poly!(1., 1.));
+        for d in Bode::new(tf, RadiansPerSecond(0.1), RadiansPerSecond(10.), 0.1) {
+            let w = d.angular_frequency().0;
+            assert_relative_eq!(1. / (1. + w * w), d.group_delay(), max_relative = 1e-10);
+        }
+    }
+
+    #[test]
+    fn group_delay_discrete() {
+        // G(z) = 1 / z^2, delay of two samples.
+        let tf = Tfz::new(poly!(1.), poly!(0., 0., 1.));
+        for d in Bode::new_discrete(tf.clone(), NormalizedFrequency(0.01), 0.1) {
+            assert_relative_eq!(2., d.group_delay(), max_relative = 1e-10);
+        }
+        for d in Bode::new_sampled(tf, Seconds(0.1), RadiansPerSecond(0.1), 0.1) {
+            assert_relative_eq!(0.2, d.group_delay(), max_relative = 1e-10);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn sampled_discrete_fail() {
+        let tf = Tfz::new(poly!(2., 3.), poly!(1., 1., 1.));
+        let _ = Bode::new_sampled(tf, Seconds(0.1), RadiansPerSecond(40.), 0.1);
+    }
+
     #[test]
     fn create_iterator_db_deg() {
         let tf = Tf::new(poly!(2., 3.), poly!(1., 1., 1.));
@@ -219,6 +1000,12 @@ mod tests {
         let res = iter2.last().unwrap();
         assert_eq!(RadiansPerSecond(1000.), res.angular_frequency());
         assert_relative_eq!(-90.0, res.phase(), max_relative = 0.001);
+        assert_relative_eq!(-90.0, res.phase_deg().0, max_relative = 0.001);
+        assert_relative_eq!(
+            -std::f64::consts::FRAC_PI_2,
+            res.phase_rad().0,
+            max_relative = 0.001
+        );
     }
 
     #[test]
@@ -230,11 +1017,168 @@ mod tests {
             angular_frequency: f,
             magnitude: mag,
             phase: ph,
+            group_delay: 0.5,
+            output: Complex::from_polar(mag, ph),
+            db_deg: false,
         };
         assert_eq!(f, p.angular_frequency());
         assert_relative_eq!(19.0986, p.frequency().0, max_relative = 0.00001);
         assert_relative_eq!(mag, p.magnitude());
         assert_relative_eq!(ph, p.phase());
+        assert_relative_eq!(180., p.phase_deg().0);
+        assert_relative_eq!(0.5, p.group_delay());
+    }
+
+    #[test]
+    fn unwrapped_phase() {
+        let tf = Tf::new(poly!(1.), poly!(1., 3., 3., 1.));
+        let bode = Bode::new(tf, RadiansPerSecond(0.1), RadiansPerSecond(100.), 0.1);
+        let wrapped: Vec<_> = bode.clone().into_iter().map(|d| d.phase()).collect();
+        let unwrapped: Vec<_> = bode
+            .into_iter()
+            .into_unwrapped()
+            .map(|d| d.phase())
+            .collect();
+        assert!(wrapped.windows(2).any(|w| w[1] > w[0]));
+        assert!(unwrapped.windows(2).all(|w| w[1] < w[0]));
+        let last = unwrapped.last().unwrap();
+        assert_relative_eq!(-1.5 * std::f64::consts::PI, *last, max_relative = 0.01);
+        for (w, u) in wrapped.iter().zip(&unwrapped) {
+            assert_relative_eq!(w.sin(), u.sin(), epsilon = 1e-12);
+        }
+    }
+
+    #[test]
+    fn unwrapped_phase_db_deg() {
+        let tf = Tf::new(poly!(1.), poly!(1., 3., 3., 1.));
+        let last = Bode::new(tf, RadiansPerSecond(0.1), RadiansPerSecond(100.), 0.1)
+            .into_iter()
+            .into_unwrapped()
+            .into_db_deg()
+            .last()
+            .unwrap();
+        assert_relative_eq!(-270., last.phase(), max_relative = 0.01);
+    }
+
+    #[test]
+    fn refined_grid() {
+        // Resonance at 10 rad/s with damping 0.01.
+        let tf = Tf::new(poly!(100.), poly!(100., 0.2, 1.));
+        let bode = Bode::new(tf, RadiansPerSecond(1.), RadiansPerSecond(100.), 0.7);
+        let coarse: Vec<_> = bode.clone().into_iter().collect();
+        let refined: Vec<_> = bode
+            .with_refinement(Refinement::new(1., 0.1, 12))
+            .into_iter()
+            .collect();
+        assert_eq!(3, coarse.len());
+        assert!(refined.len() > coarse.len());
+        assert!(refined
+            .windows(2)
+            .all(|w| w[0].angular_frequency() < w[1].angular_frequency()));
+        for c in &coarse {
+            assert!(refined.contains(c));
+        }
+        let coarse_peak = coarse.iter().map(Data::magnitude).fold(0., f64::max);
+        let refined_peak = refined.iter().map(Data::magnitude).fold(0., f64::max);
+        // The peak magnitude is 50, the refined grid misses it by less than 1 dB.
+        assert!(coarse_peak < 2.);
+        assert!(refined_peak > 44.5 && refined_peak <= 50.);
+    }
+
+    #[test]
+    fn refined_flat_grid() {
+        let tf = Tf::new(poly!(1.), poly!(1.));
+        let bode = Bode::new(tf, RadiansPerSecond(1.), RadiansPerSecond(100.), 0.5)
+            .with_refinement(Refinement::new(1., 0.1, 12));
+        assert_eq!(5, bode.into_iter().count());
+    }
+
+    #[test]
+    fn coarsened_grid() {
+        // Flat below 1 rad/s and above 1000 rad/s, resonance at 10 rad/s.
+        let tf = Tf::new(poly!(100.), poly!(100., 0.2, 1.));
+        let bode = Bode::new(tf, RadiansPerSecond(1e-3), RadiansPerSecond(1e3), 0.25);
+        let refinement = Refinement::new(1., 0.1, 12);
+        let refined: Vec<_> = bode
+            .clone()
+            .with_refinement(refinement)
+            .into_iter()
+            .collect();
+        let coarsened: Vec<_> = bode
+            .with_refinement(refinement.with_coarsening(3))
+            .into_iter()
+            .collect();
+        assert!(coarsened.len() < refined.len());
+        assert_eq!(refined.first(), coarsened.first());
+        assert_eq!(refined.last(), coarsened.last());
+        assert!(coarsened.windows(2).all(|w| {
+            let ratio = w[1].angular_frequency().0 / w[0].angular_frequency().0;
+            ratio < 10_f64.powf(2.) * (1. + 1e-12)
+        }));
+        let peak = coarsened.iter().map(Data::magnitude).fold(0., f64::max);
+        assert!(peak > 44.5 && peak <= 50.);
+    }
+
+    #[test]
+    fn crossovers() {
+        // L(s) = 4 / (s + 1)^3, gain crossover at sqrt(4^(2/3) - 1),
+        // phase crossover at sqrt(3).
+        let tf = Tf::new(poly!(4.), poly!(1., 3., 3., 1.));
+        let events: Vec<_> = Bode::new(tf, RadiansPerSecond(0.1), RadiansPerSecond(10.), 0.01)
+            .into_iter()
+            .into_crossovers()
+            .collect();
+        let gain: Vec<_> = events
+            .iter()
+            .filter_map(|e| match e {
+                Event::GainCrossover(w) => Some(w.0),
+                _ => None,
+            })
+            .collect();
+        let phase: Vec<_> = events
+            .iter()
+            .filter_map(|e| match e {
+                Event::PhaseCrossover(w) => Some(w.0),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(1, gain.len());
+        assert_relative_eq!(
+            (4_f64.powf(2. / 3.) - 1.).sqrt(),
+            gain[0],
+            max_relative = 1e-3
+        );
+        assert_eq!(1, phase.len());
+        assert_relative_eq!(3_f64.sqrt(), phase[0], max_relative = 1e-3);
+        assert_eq!(
+            201,
+            events
+                .iter()
+                .filter(|e| matches!(e, Event::Point(_)))
+                .count()
+        );
+
+        // Crossovers are placed between the data points that surround them.
+        let index = events
+            .iter()
+            .position(|e| matches!(e, Event::PhaseCrossover(_)))
+            .unwrap();
+        match (&events[index - 1], &events[index + 1]) {
+            (Event::Point(before), Event::Point(after)) => {
+                assert!(before.angular_frequency().0 < phase[0]);
+                assert!(after.angular_frequency().0 > phase[0]);
+            }
+            _ => panic!("Crossover not surrounded by data points"),
+        }
+    }
+
+    #[test]
+    fn no_crossovers() {
+        let tf = Tf::new(poly!(0.5), poly!(1., 1.));
+        let mut events = Bode::new(tf, RadiansPerSecond(0.1), RadiansPerSecond(10.), 0.1)
+            .into_iter()
+            .into_crossovers();
+        assert!(events.all(|e| matches!(e, Event::Point(_))));
     }
 
     #[test]
@@ -0,0 +1,223 @@
+//! # Decimation
+//!
+//! Reduction of long time series, as the ones produced by the
+//! [solvers](../../linear_system/solver/index.html) or by the plot iterators,
+//! to the resolution of the plot before they are exported.
+//!
+//! The min-max decimation splits the data points in buckets of consecutive
+//! points and keeps, for each bucket, the points with the minimum and the
+//! maximum value, in their original order, so that the peaks of the signal
+//! are not lost.
+
+use std::{collections::VecDeque, fmt};
+
+/// Decimation of iterators
+pub trait Decimation: Iterator + Sized {
+    /// Keep the points with the minimum and the maximum value of each bucket
+    /// of consecutive points. One or two points are returned for each
+    /// bucket, in the original order; the first occurrence is kept when the
+    /// value is repeated and undefined values are ignored. A bucket made
+    /// only of undefined values returns its first point.
+    ///
+    /// # Arguments
+    ///
+    /// * `bucket` - Number of consecutive points of each bucket
+    /// * `value` - Value of the point used for the comparison
+    ///
+    /// # Panics
+    ///
+    /// Panics if the bucket size is zero.
+    ///
+    /// # Example
+    /// ```
+    /// use au::plots::decimation::Decimation;
+    /// let signal = (0..1_000).map(|i| if i == 567 { 10. } else { (i % 7) as f64 });
+    /// let decimated: Vec<_> = signal.min_max(100, |&x| x).collect();
+    /// assert!(decimated.len() <= 20);
+    /// assert!(decimated.contains(&10.));
+    /// ```
+    fn min_max<F, T>(self, bucket: usize, value: F) -> MinMax<Self, F>
+    where
+        F: FnMut(&Self::Item) -> T,
+        T: PartialOrd,
+    {
+        assert!(bucket > 0, "Bucket size must be strictly positive");
+        MinMax {
+            iter: self,
+            bucket,
+            value,
+            pending: VecDeque::with_capacity(2),
+        }
+    }
+}
+
+impl<I: Iterator> Decimation for I {}
+
+/// Iterator of the min-max decimation
+#[derive(Clone)]
+pub struct MinMax<I: Iterator, F> {
+    /// Decimated iterator
+    iter: I,
+    /// Number of consecutive points of each bucket
+    bucket: usize,
+    /// Value of the point used for the comparison
+    value: F,
+    /// Points of the last bucket not yet returned
+    pending: VecDeque<I::Item>,
+}
+
+impl<I: Iterator, F> fmt::Debug for MinMax<I, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MinMax")
+            .field("bucket", &self.bucket)
+            .finish()
+    }
+}
+
+impl<I, F, T> Iterator for MinMax<I, F>
+where
+    I: Iterator,
+    F: FnMut(&I::Item) -> T,
+    T: PartialOrd,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(point) = self.pending.pop_front() {
+            return Some(point);
+        }
+        // The maximum is `None` while the minimum is also the maximum.
+        let mut min: Option<(usize, T, I::Item)> = None;
+        let mut max: Option<(usize, T, I::Item)> = None;
+        let mut undefined = None;
+        for (i, point) in self.iter.by_ref().take(self.bucket).enumerate() {
+            let v = (self.value)(&point);
+            if v.partial_cmp(&v).is_none() {
+                if undefined.is_none() {
+                    undefined = Some(point);
+                }
+                continue;
+            }
+            match min.take() {
+                None => min = Some((i, v, point)),
+                Some(m) => {
+                    let above = max.as_ref().map_or(&m.1, |x| &x.1);
+                    if v < m.1 {
+                        if max.is_none() {
+                            max = Some(m);
+                        }
+                        min = Some((i, v, point));
+                    } else {
+                        if v > *above {
+                            max = Some((i, v, point));
+                        }
+                        min = Some(m);
+                    }
+                }
+            }
+        }
+        let (i_min, _, p_min) = match min {
+            Some(m) => m,
+            None => return undefined,
+        };
+        match max {
+            Some((i_max, _, p_max)) if i_max < i_min => {
+                self.pending.push_back(p_min);
+                Some(p_max)
+            }
+            Some((_, _, p_max)) => {
+                self.pending.push_back(p_max);
+                Some(p_min)
+            }
+            None => Some(p_min),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (low, high) = self.iter.size_hint();
+        let buckets = |n: usize| n.saturating_add(self.bucket - 1) / self.bucket;
+        let pending = self.pending.len();
+        (
+            buckets(low) + pending,
+            high.map(|h| 2 * buckets(h) + pending),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{linear_system::continuous::Ss, plots::time::TimePlot, units::Seconds};
+
+    #[test]
+    fn keeps_order_and_extremes() {
+        let signal = vec![3, 1, 4, 1, 5, 9, 2, 6, 5, 3, 5];
+        let decimated: Vec<_> = signal.into_iter().min_max(4, |&x| x).collect();
+        // Buckets [3, 1, 4, 1], [5, 9, 2, 6], [5, 3, 5].
+        assert_eq!(vec![1, 4, 9, 2, 5, 3], decimated);
+    }
+
+    #[test]
+    fn maximum_before_minimum() {
+        let decimated: Vec<_> = vec![2, 8, 0, 5].into_iter().min_max(4, |&x| x).collect();
+        assert_eq!(vec![8, 0], decimated);
+    }
+
+    #[test]
+    fn constant_bucket() {
+        let decimated: Vec<_> = vec![7, 7, 7].into_iter().min_max(2, |&x| x).collect();
+        assert_eq!(vec![7, 7], decimated);
+    }
+
+    #[test]
+    fn unit_bucket() {
+        let signal = vec![1., f64::NAN, 3.];
+        let decimated: Vec<_> = signal.into_iter().min_max(1, |&x| x).collect();
+        assert_eq!(3, decimated.len());
+    }
+
+    #[test]
+    fn undefined_first_point() {
+        let signal = vec![f64::NAN, 2., 1., f64::NAN, 3., f64::NAN, f64::NAN];
+        let decimated: Vec<_> = signal.into_iter().min_max(5, |&x| x).collect();
+        // Buckets [NaN, 2, 1, NaN, 3], [NaN, NaN].
+        assert_eq!(3, decimated.len());
+        assert_relative_eq!(1., decimated[0]);
+        assert_relative_eq!(3., decimated[1]);
+        assert!(decimated[2].is_nan());
+    }
+
+    #[test]
+    fn bounds_of_size_hint() {
+        let iter = (0..10).min_max(3, |&x| x);
+        assert_eq!((4, Some(8)), iter.size_hint());
+        assert_eq!(7, iter.count());
+    }
+
+    #[test]
+    #[should_panic]
+    fn empty_bucket() {
+        let _ = (0..10).min_max(0, |&x| x);
+    }
+
+    #[test]
+    fn time_response_peak() {
+        // Lightly damped second order system, overshoot of the step response.
+        let sys = Ss::new_from_slice(2, 1, 1, &[0., 1., -1., -0.2], &[0., 1.], &[1., 0.], &[0.]);
+        let peak = |steps: &[crate::linear_system::solver::Step<f64>]| {
+            steps.iter().map(|s| s.output()[0]).fold(0., f64::max)
+        };
+        let full: Vec<_> = TimePlot::step(&sys, 0, Seconds(0.001), 20_000)
+            .into_iter()
+            .collect();
+        let decimated: Vec<_> = TimePlot::step(&sys, 0, Seconds(0.001), 20_000)
+            .into_iter()
+            .min_max(500, |s| s.output()[0])
+            .collect();
+        assert!(decimated.len() <= 82);
+        assert_relative_eq!(peak(&full), peak(&decimated));
+        for (a, b) in decimated.iter().zip(&decimated[1..]) {
+            assert!(a.time() < b.time());
+        }
+    }
+}
@@ -0,0 +1,440 @@
+//! # Export of plots
+//!
+//! The data points of the plots can be written in CSV or JSON format with
+//! labeled columns, directly consuming the plot iterators:
+//! * Bode plot: `angular_frequency`, `magnitude`, `phase`, or `frequency`,
+//!   `magnitude`, `phase` when the frequencies are in Hertz
+//! * Polar plot: `angular_frequency`, `real`, `imag`
+//! * Root locus: `k`, then `re1`, `im1`, `re2`, `im2`, ... for each root
+//! * Singular value plot: `angular_frequency`, then `sv1`, `sv2`, ... in
+//!   decreasing order
+//! * Nichols plot: `angular_frequency`, `phase`, `magnitude`
+//! * Nichols grid contour: `phase`, `magnitude`
+//! * Kharitonov value set: `angular_frequency`, then `re1`, `im1`, ... `re4`,
+//!   `im4` for the vertices of the rectangle
+//! * Complex numbers, as the points of M and N circles: `real`, `imag`
+//!
+//! The samples of grouped plots (see [multi](../multi/index.html)) are
+//! written in a single row, the names of the columns have the suffix `_1`,
+//! `_2`, ... for each plot, excluding the first column which is shared.
+//!
+//! JSON output is an array of objects, one for each data point, that have
+//! the names of the columns as keys. Non finite values are written as
+//! `null`.
+
+use num_complex::Complex;
+use num_traits::{Float, FloatConst};
+
+use std::{
+    fmt::Display,
+    io::{self, Write},
+};
+
+use crate::plots::{bode, kharitonov, nichols, polar, root_locus, sigma};
+
+/// Data point of a plot that can be written as a row of a table.
+pub trait Record<T> {
+    /// Names of the columns of the row
+    fn columns(&self) -> Vec<String>;
+
+    /// Values of the columns of the row
+    fn values(&self) -> Vec<T>;
+}
+
+impl<T: Float + FloatConst> Record<T> for bode::Data<T> {
+    fn columns(&self) -> Vec<String> {
+        labels(&["angular_frequency", "magnitude", "phase"])
+    }
+
+    fn values(&self) -> Vec<T> {
+        vec![self.angular_frequency().0, self.magnitude(), self.phase()]
+    }
+}
+
+impl<T: Float> Record<T> for bode::HertzData<T> {
+    fn columns(&self) -> Vec<String> {
+        labels(&["frequency", "magnitude", "phase"])
+    }
+
+    fn values(&self) -> Vec<T> {
+        vec![self.frequency().0, self.magnitude(), self.phase()]
+    }
+}
+
+impl<T: Float> Record<T> for polar::Data<T> {
+    fn columns(&self) -> Vec<String> {
+        labels(&["angular_frequency", "real", "imag"])
+    }
+
+    fn values(&self) -> Vec<T> {
+        vec![self.freq(), self.real(), self.imag()]
+    }
+}
+
+impl<T: Float> Record<T> for root_locus::Data<T> {
+    fn columns(&self) -> Vec<String> {
+        let roots =
+            (1..=self.output().len()).flat_map(|i| vec![format!("re{}", i), format!("im{}", i)]);
+        std::iter::once("k".to_owned()).chain(roots).collect()
+    }
+
+    fn values(&self) -> Vec<T> {
+        let roots = self.output().iter().flat_map(|r| vec![r.re, r.im]);
+        std::iter::once(self.k()).chain(roots).collect()
+    }
+}
+
+impl<T: Float> Record<T> for sigma::Data<T> {
+    fn columns(&self) -> Vec<String> {
+        let values = (1..=self.singular_values().len()).map(|i| format!("sv{}", i));
+        std::iter::once("angular_frequency".to_owned())
+            .chain(values)
+            .collect()
+    }
+
+    fn values(&self) -> Vec<T> {
+        std::iter::once(self.angular_frequency().0)
+            .chain(self.singular_values().iter().copied())
+            .collect()
+    }
+}
+
+impl<T: Float> Record<T> for kharitonov::ValueSet<T> {
+    fn columns(&self) -> Vec<String> {
+        let vertices = (1..=4).flat_map(|i| vec![format!("re{}", i), format!("im{}", i)]);
+        std::iter::once("angular_frequency".to_owned())
+            .chain(vertices)
+            .collect()
+    }
+
+    fn values(&self) -> Vec<T> {
+        let vertices = self.vertices().iter().flat_map(|v| vec![v.re, v.im]);
+        std::iter::once(self.angular_frequency().0)
+            .chain(vertices)
+            .collect()
+    }
+}
+
+impl<T: Copy> Record<T> for Complex<T> {
+    fn columns(&self) -> Vec<String> {
+        labels(&["real", "imag"])
+    }
+
+    fn values(&self) -> Vec<T> {
+        vec![self.re, self.im]
+    }
+}
+
+impl<T: Float> Record<T> for nichols::Data<T> {
+    fn columns(&self) -> Vec<String> {
+        labels(&["angular_frequency", "phase", "magnitude"])
+    }
+
+    fn values(&self) -> Vec<T> {
+        vec![self.angular_frequency().0, self.phase(), self.magnitude()]
+    }
+}
+
+impl<T: Copy> Record<T> for nichols::Point<T> {
+    fn columns(&self) -> Vec<String> {
+        labels(&["phase", "magnitude"])
+    }
+
+    fn values(&self) -> Vec<T> {
+        vec![self.phase(), self.magnitude()]
+    }
+}
+
+impl<T, R: Record<T>> Record<T> for Vec<R> {
+    fn columns(&self) -> Vec<String> {
+        let mut columns = Vec::new();
+        for (i, record) in self.iter().enumerate() {
+            let names = record.columns().into_iter().enumerate();
+            if i == 0 {
+                columns.extend(names.map(|(j, c)| if j == 0 { c } else { format!("{}_1", c) }));
+            } else {
+                columns.extend(names.skip(1).map(|(_, c)| format!("{}_{}", c, i + 1)));
+            }
+        }
+        columns
+    }
+
+    fn values(&self) -> Vec<T> {
+        let mut values = Vec::new();
+        for (i, record) in self.iter().enumerate() {
+            values.extend(record.values().into_iter().skip(if i == 0 { 0 } else { 1 }));
+        }
+        values
+    }
+}
+
+/// Names of the columns from string slices
+fn labels(names: &[&str]) -> Vec<String> {
+    names.iter().map(|&n| n.to_owned()).collect()
+}
+
+/// Write a number in JSON format, `null` if it is not finite.
+fn write_json_number<T: Display + Float, W: Write>(writer: &mut W, x: T) -> io::Result<()> {
+    if x.is_finite() {
+        write!(writer, "{}", x)
+    } else {
+        write!(writer, "null")
+    }
+}
+
+/// Export of the data points of plot iterators.
+pub trait Export<T>: Iterator + Sized
+where
+    Self::Item: Record<T>,
+    T: Display + Float,
+{
+    /// Write the data points in CSV format. The first line is the header with
+    /// the names of the columns, then a line for each data point follows.
+    /// Nothing is written if there are no data points.
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - destination of the CSV data
+    ///
+    /// # Errors
+    ///
+    /// It returns an error if the writing fails.
+    ///
+    /// # Example
+    /// ```
+    /// use au::{plots::{bode::Bode, export::Export}, poly, RadiansPerSecond, Tf};
+    /// let tf = Tf::new(poly!(1.), poly!(1., 1.));
+    /// let bode = Bode::new(tf, RadiansPerSecond(1.), RadiansPerSecond(10.), 1.);
+    /// let mut csv = Vec::new();
+    /// bode.into_iter().into_db_deg().to_csv(&mut csv).unwrap();
+    /// let csv = String::from_utf8(csv).unwrap();
+    /// assert!(csv.starts_with("angular_frequency,magnitude,phase\n1,"));
+    /// assert_eq!(3, csv.lines().count());
+    /// ```
+    fn to_csv<W: Write>(self, mut writer: W) -> io::Result<()> {
+        let mut header = true;
+        for record in self {
+            if header {
+                writeln!(writer, "{}", record.columns().join(","))?;
+                header = false;
+            }
+            let values: Vec<_> = record.values().iter().map(ToString::to_string).collect();
+            writeln!(writer, "{}", values.join(","))?;
+        }
+        Ok(())
+    }
+
+    /// Collect the data points into parallel arrays, one for each column of
+    /// the table, in the order of the names of the columns.
+    ///
+    /// # Example
+    /// ```
+    /// use au::{plots::{bode::Bode, export::Export}, poly, RadiansPerSecond, Tf};
+    /// let tf = Tf::new(poly!(1.), poly!(0., 1.));
+    /// let bode = Bode::new(tf, RadiansPerSecond(1.), RadiansPerSecond(10.), 1.);
+    /// let arrays = bode.into_iter().into_db_deg().collect_arrays();
+    /// assert_eq!(3, arrays.len());
+    /// assert_eq!(vec![0., -20.], arrays[1]);
+    /// assert_eq!(vec![-90., -90.], arrays[2]);
+    /// ```
+    fn collect_arrays(self) -> Vec<Vec<T>> {
+        crate::plots::transpose(self.map(|record| record.values()).collect())
+    }
+
+    /// Write the data points in JSON format, as an array of objects that
+    /// have the names of the columns as keys.
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - destination of the JSON data
+    ///
+    /// # Errors
+    ///
+    /// It returns an error if the writing fails.
+    ///
+    /// # Example
+    /// ```
+    /// use au::{plots::export::Export, poly, Tf};
+    /// let tf = Tf::new(poly!(1.), poly!(1., 1.));
+    /// let mut json = Vec::new();
+    /// tf.root_locus_plot(1., 2., 1.).into_iter().to_json(&mut json).unwrap();
+    /// assert_eq!(
+    ///     r#"[{"k":1,"re1":-2,"im1":0},{"k":2,"re1":-3,"im1":0}]"#,
+    ///     String::from_utf8(json).unwrap()
+    /// );
+    /// ```
+    fn to_json<W: Write>(self, mut writer: W) -> io::Result<()> {
+        write!(writer, "[")?;
+        for (i, record) in self.enumerate() {
+            if i > 0 {
+                write!(writer, ",")?;
+            }
+            write!(writer, "{{")?;
+            for (j, (c, v)) in record.columns().iter().zip(record.values()).enumerate() {
+                if j > 0 {
+                    write!(writer, ",")?;
+                }
+                write!(writer, "\"{}\":", c)?;
+                write_json_number(&mut writer, v)?;
+            }
+            write!(writer, "}}")?;
+        }
+        write!(writer, "]")
+    }
+}
+
+impl<T, I> Export<T> for I
+where
+    I: Iterator,
+    I::Item: Record<T>,
+    T: Display + Float,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        plots::{bode::Bode, multi, polar::Polar, sigma::Sigma},
+        poly,
+        transfer_function::continuous::Tf,
+        units::RadiansPerSecond,
+        Ss,
+    };
+
+    #[test]
+    fn bode_csv() {
+        let tf = Tf::new(poly!(1.), poly!(0., 1.));
+        let bode = Bode::new(tf, RadiansPerSecond(1.), RadiansPerSecond(10.), 1.);
+        let mut csv = Vec::new();
+        bode.into_iter().to_csv(&mut csv).unwrap();
+        let phase = -std::f64::consts::FRAC_PI_2;
+        assert_eq!(
+            format!(
+                "angular_frequency,magnitude,phase\n1,1,{}\n10,0.1,{}\n",
+                phase, phase
+            ),
+            String::from_utf8(csv).unwrap()
+        );
+    }
+
+    #[test]
+    fn bode_hz_csv() {
+        let tf = Tf::new(poly!(3.), poly!(1.));
+        let bode = Bode::new(tf, RadiansPerSecond(1.), RadiansPerSecond(10.), 1.);
+        let mut csv = Vec::new();
+        bode.into_iter().into_hz().to_csv(&mut csv).unwrap();
+        let hz = 1. / (2. * std::f64::consts::PI);
+        assert_eq!(
+            format!("frequency,magnitude,phase\n{},3,0\n{},3,0\n", hz, 10. * hz),
+            String::from_utf8(csv).unwrap()
+        );
+    }
+
+    #[test]
+    fn polar_json() {
+        let tf = Tf::new(poly!(2.), poly!(1.));
+        let polar = Polar::new(tf, RadiansPerSecond(1.), RadiansPerSecond(10.), 1.);
+        let mut json = Vec::new();
+        polar.into_iter().to_json(&mut json).unwrap();
+        assert_eq!(
+            r#"[{"angular_frequency":1,"real":2,"imag":0},{"angular_frequency":10,"real":2,"imag":0}]"#,
+            String::from_utf8(json).unwrap()
+        );
+    }
+
+    #[test]
+    fn non_finite_json() {
+        let tf = Tf::new(poly!(1.), poly!(1., 0., 1.));
+        let bode = Bode::new(tf, RadiansPerSecond(1.), RadiansPerSecond(10.), 1.);
+        let mut json = Vec::new();
+        bode.into_iter().to_json(&mut json).unwrap();
+        let json = String::from_utf8(json).unwrap();
+        assert!(json.starts_with(r#"[{"angular_frequency":1,"magnitude":null,"#));
+    }
+
+    #[test]
+    fn empty_plot() {
+        let mut csv = Vec::new();
+        Vec::<bode::Data<f64>>::new()
+            .into_iter()
+            .to_csv(&mut csv)
+            .unwrap();
+        assert!(csv.is_empty());
+        let mut json = Vec::new();
+        Vec::<bode::Data<f64>>::new()
+            .into_iter()
+            .to_json(&mut json)
+            .unwrap();
+        assert_eq!(b"[]".to_vec(), json);
+    }
+
+    #[test]
+    fn grouped_arrays() {
+        let systems = vec![Tf::new(poly!(1.), poly!(1.)), Tf::new(poly!(2.), poly!(1.))];
+        let table = multi::polar(systems, RadiansPerSecond(1.), RadiansPerSecond(10.), 1.);
+        let arrays = table.collect_arrays();
+        assert_eq!(5, arrays.len());
+        assert_eq!(vec![1., 10.], arrays[0]);
+        assert_eq!(vec![2., 2.], arrays[3]);
+        assert!(Vec::<bode::Data<f64>>::new()
+            .into_iter()
+            .collect_arrays()
+            .is_empty());
+    }
+
+    #[test]
+    fn grouped_csv() {
+        let systems = vec![Tf::new(poly!(1.), poly!(1.)), Tf::new(poly!(2.), poly!(1.))];
+        let table = multi::polar(systems, RadiansPerSecond(1.), RadiansPerSecond(10.), 1.);
+        let mut csv = Vec::new();
+        table.to_csv(&mut csv).unwrap();
+        assert_eq!(
+            "angular_frequency,real_1,imag_1,real_2,imag_2\n1,1,0,2,0\n10,1,0,2,0\n",
+            String::from_utf8(csv).unwrap()
+        );
+    }
+
+    #[test]
+    fn value_set_csv() {
+        let family = kharitonov::Kharitonov::new(&[1., 1.], &[2., 3.]);
+        let plot = family.value_sets(RadiansPerSecond(1.), RadiansPerSecond(10.), 1.);
+        let mut csv = Vec::new();
+        plot.into_iter().to_csv(&mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        assert!(csv
+            .starts_with("angular_frequency,re1,im1,re2,im2,re3,im3,re4,im4\n1,1,1,2,1,2,3,1,3\n"));
+    }
+
+    #[test]
+    fn circle_csv() {
+        let circle = polar::Circle::constant_magnitude(3.).unwrap();
+        let mut csv = Vec::new();
+        circle.points(2).into_iter().to_csv(&mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        assert!(csv.starts_with("real,imag\n-0.75,0\n"));
+        assert_eq!(4, csv.lines().count());
+    }
+
+    #[test]
+    fn nichols_contour_csv() {
+        let contour = nichols::Contour::phase(-90., 4);
+        let mut csv = Vec::new();
+        contour.points().iter().copied().to_csv(&mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        assert!(csv.starts_with("phase,magnitude\n-120,"));
+        assert_eq!(2, csv.lines().count());
+    }
+
+    #[test]
+    fn sigma_csv() {
+        let sys = Ss::new_from_slice(1, 2, 2, &[-1.], &[0., 0.], &[0., 0.], &[2., 0., 0., 1.]);
+        let sigma = Sigma::new(sys, RadiansPerSecond(1.), RadiansPerSecond(10.), 1.);
+        let mut csv = Vec::new();
+        sigma.into_iter().to_csv(&mut csv).unwrap();
+        assert_eq!(
+            "angular_frequency,sv1,sv2\n1,2,1\n10,2,1\n",
+            String::from_utf8(csv).unwrap()
+        );
+    }
+}
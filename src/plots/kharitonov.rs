@@ -0,0 +1,374 @@
+//! # Kharitonov value sets
+//!
+//! Robust stability of interval polynomials, polynomials whose coefficients
+//! are only known to lie between a lower and an upper bound.
+//!
+//! The interval polynomial is Hurwitz stable for all the values of the
+//! coefficients if and only if the four Kharitonov polynomials are stable.
+//!
+//! At each angular frequency the values of the interval polynomial at `jω`
+//! fill a rectangle of the complex plane (value set), whose vertices are the
+//! values of the Kharitonov polynomials. By the zero exclusion principle the
+//! family is stable if one member is stable and no value set contains the
+//! origin.
+
+use nalgebra::RealField;
+use num_complex::Complex;
+use num_traits::{Float, MulAdd, Num};
+
+use crate::{plots::exp10, polynomial::Poly, units::RadiansPerSecond};
+
+/// Interval polynomial and its Kharitonov polynomials
+#[derive(Clone, Debug, PartialEq)]
+pub struct Kharitonov<T> {
+    /// Lower bounds of the coefficients, from the lowest power
+    lower: Vec<T>,
+    /// Upper bounds of the coefficients, from the lowest power
+    upper: Vec<T>,
+}
+
+/// Pattern of the bounds of the Kharitonov polynomials, repeated every four
+/// coefficients; `true` selects the upper bound.
+const PATTERNS: [[bool; 4]; 4] = [
+    [false, false, true, true],
+    [true, true, false, false],
+    [false, true, true, false],
+    [true, false, false, true],
+];
+
+impl<T: Float> Kharitonov<T> {
+    /// Create an interval polynomial
+    ///
+    /// # Arguments
+    ///
+    /// * `lower` - Lower bounds of the coefficients, from the lowest power
+    /// * `upper` - Upper bounds of the coefficients, from the lowest power
+    ///
+    /// # Panics
+    ///
+    /// Panics if the bounds have different lengths or a lower bound is
+    /// greater than the upper bound.
+    ///
+    /// # Example
+    /// ```
+    /// use au::plots::kharitonov::Kharitonov;
+    /// let k = Kharitonov::new(&[1., 2., 1.], &[2., 3., 1.5]);
+    /// assert_eq!(4, k.polynomials().len());
+    /// ```
+    #[must_use]
+    pub fn new(lower: &[T], upper: &[T]) -> Self {
+        assert_eq!(lower.len(), upper.len(), "Bounds must have the same length");
+        assert!(
+            lower.iter().zip(upper).all(|(l, u)| l <= u),
+            "Lower bounds must not be greater than upper bounds"
+        );
+        Self {
+            lower: lower.to_vec(),
+            upper: upper.to_vec(),
+        }
+    }
+
+    /// Get the lower bounds of the coefficients
+    #[must_use]
+    pub fn lower(&self) -> &[T] {
+        &self.lower
+    }
+
+    /// Get the upper bounds of the coefficients
+    #[must_use]
+    pub fn upper(&self) -> &[T] {
+        &self.upper
+    }
+
+    /// Kharitonov polynomials, the bounds of their coefficients follow the
+    /// patterns (lower, lower, upper, upper), (upper, upper, lower, lower),
+    /// (lower, upper, upper, lower) and (upper, lower, lower, upper) from
+    /// the lowest power.
+    #[must_use]
+    pub fn polynomials(&self) -> [Poly<T>; 4] {
+        let poly = |pattern: &[bool; 4]| {
+            Poly::new_from_coeffs_iter(self.lower.iter().zip(&self.upper).enumerate().map(
+                |(i, (&l, &u))| {
+                    if pattern[i % 4] {
+                        u
+                    } else {
+                        l
+                    }
+                },
+            ))
+        };
+        [
+            poly(&PATTERNS[0]),
+            poly(&PATTERNS[1]),
+            poly(&PATTERNS[2]),
+            poly(&PATTERNS[3]),
+        ]
+    }
+
+    /// Value set of the interval polynomial at the given angular frequency
+    ///
+    /// # Arguments
+    ///
+    /// * `omega` - Angular frequency
+    #[must_use]
+    pub fn value_set(&self, omega: RadiansPerSecond<T>) -> ValueSet<T> {
+        let s = Complex::new(T::zero(), omega.0);
+        let [k1, k2, k3, k4] = self.polynomials();
+        ValueSet {
+            angular_frequency: omega,
+            vertices: [
+                k1.eval_by_val(s),
+                k4.eval_by_val(s),
+                k2.eval_by_val(s),
+                k3.eval_by_val(s),
+            ],
+        }
+    }
+
+    /// Create the plot of the value sets
+    ///
+    /// # Arguments
+    ///
+    /// * `min_freq` - Minimum angular frequency of the plot
+    /// * `max_freq` - Maximum angular frequency of the plot
+    /// * `step` - Step between frequencies
+    ///
+    /// `step` shall be in logarithmic scale. Use 0.1 to have 10 point per decade
+    ///
+    /// # Panics
+    ///
+    /// Panics if the step is not strictly positive of the minimum frequency
+    /// is not lower than the maximum frequency.
+    ///
+    /// # Example
+    /// ```
+    /// use au::{plots::kharitonov::Kharitonov, RadiansPerSecond};
+    /// let k = Kharitonov::new(&[1., 2., 1.], &[2., 3., 1.5]);
+    /// let plot = k.value_sets(RadiansPerSecond(0.1), RadiansPerSecond(10.), 0.1);
+    /// assert!(plot.into_iter().all(|v| !v.contains_zero()));
+    /// ```
+    #[must_use]
+    pub fn value_sets(
+        &self,
+        min_freq: RadiansPerSecond<T>,
+        max_freq: RadiansPerSecond<T>,
+        step: T,
+    ) -> ValueSetPlot<T> {
+        assert!(step > T::zero());
+        assert!(min_freq < max_freq);
+        ValueSetPlot {
+            family: self.clone(),
+            min_freq,
+            max_freq,
+            step,
+        }
+    }
+
+    /// Zero exclusion check, no value set in the frequency range contains
+    /// the origin.
+    ///
+    /// # Arguments
+    ///
+    /// * `min_freq` - Minimum angular frequency of the check
+    /// * `max_freq` - Maximum angular frequency of the check
+    /// * `step` - Step between frequencies
+    ///
+    /// `step` shall be in logarithmic scale. Use 0.1 to have 10 point per decade
+    ///
+    /// # Panics
+    ///
+    /// Panics if the step is not strictly positive of the minimum frequency
+    /// is not lower than the maximum frequency.
+    #[must_use]
+    pub fn zero_exclusion(
+        &self,
+        min_freq: RadiansPerSecond<T>,
+        max_freq: RadiansPerSecond<T>,
+        step: T,
+    ) -> bool
+    where
+        T: MulAdd<Output = T>,
+    {
+        self.value_sets(min_freq, max_freq, step)
+            .into_iter()
+            .all(|v| !v.contains_zero())
+    }
+}
+
+impl<T: Float + RealField> Kharitonov<T> {
+    /// Robust stability of the interval polynomial, the degree does not
+    /// change within the bounds and the four Kharitonov polynomials are
+    /// Hurwitz stable.
+    ///
+    /// # Example
+    /// ```
+    /// use au::plots::kharitonov::Kharitonov;
+    /// let stable = Kharitonov::new(&[1., 2., 1.], &[2., 3., 1.5]);
+    /// assert!(stable.is_robustly_stable());
+    /// let unstable = Kharitonov::new(&[-1., 2., 1.], &[2., 3., 1.5]);
+    /// assert!(!unstable.is_robustly_stable());
+    /// ```
+    #[must_use]
+    pub fn is_robustly_stable(&self) -> bool {
+        let leading = match (self.lower.last(), self.upper.last()) {
+            (Some(&l), Some(&u)) => l > T::zero() || u < T::zero(),
+            _ => false,
+        };
+        leading
+            && self
+                .polynomials()
+                .iter()
+                .all(|p| p.complex_roots().iter().all(|r| r.re < T::zero()))
+    }
+}
+
+/// Value set of an interval polynomial at an angular frequency
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ValueSet<T: Num> {
+    /// Angular frequency
+    angular_frequency: RadiansPerSecond<T>,
+    /// Vertices of the rectangle, counterclockwise
+    vertices: [Complex<T>; 4],
+}
+
+impl<T: Float> ValueSet<T> {
+    /// Get the angular frequency
+    #[must_use]
+    pub fn angular_frequency(&self) -> RadiansPerSecond<T> {
+        self.angular_frequency
+    }
+
+    /// Get the vertices of the rectangle, counterclockwise from the vertex
+    /// with the lowest real and imaginary parts. They are the values of the
+    /// first, fourth, second and third Kharitonov polynomials.
+    #[must_use]
+    pub fn vertices(&self) -> &[Complex<T>; 4] {
+        &self.vertices
+    }
+
+    /// Check if the value set contains the origin
+    #[must_use]
+    pub fn contains_zero(&self) -> bool {
+        let [low, _, high, _] = self.vertices;
+        low.re <= T::zero() && T::zero() <= high.re && low.im <= T::zero() && T::zero() <= high.im
+    }
+}
+
+/// Struct for the plot of the value sets of an interval polynomial
+#[derive(Clone, Debug)]
+pub struct ValueSetPlot<T: Num> {
+    /// Interval polynomial
+    family: Kharitonov<T>,
+    /// Minimum angular frequency of the plot
+    min_freq: RadiansPerSecond<T>,
+    /// Maximum angular frequency of the plot
+    max_freq: RadiansPerSecond<T>,
+    /// Step between frequencies
+    step: T,
+}
+
+impl<T: Float + MulAdd<Output = T>> IntoIterator for ValueSetPlot<T> {
+    type Item = ValueSet<T>;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let min = self.min_freq.0.log10();
+        let max = self.max_freq.0.log10();
+        let intervals = ((max - min) / self.step).floor();
+        Self::IntoIter {
+            family: self.family,
+            intervals,
+            step: self.step,
+            base_freq_exp: min,
+            index: T::zero(),
+        }
+    }
+}
+
+/// Struct for the value set plot data point iteration.
+#[derive(Clone, Debug)]
+pub struct IntoIter<T> {
+    /// Interval polynomial
+    family: Kharitonov<T>,
+    /// Number of intervals of the plot
+    intervals: T,
+    /// Step between frequencies
+    step: T,
+    /// Start frequency exponent
+    base_freq_exp: T,
+    /// Current data index
+    index: T,
+}
+
+impl<T: Float + MulAdd<Output = T>> Iterator for IntoIter<T> {
+    type Item = ValueSet<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index > self.intervals {
+            None
+        } else {
+            let freq_exponent = MulAdd::mul_add(self.step, self.index, self.base_freq_exp);
+            self.index = self.index + T::one();
+            Some(
+                self.family
+                    .value_set(RadiansPerSecond(exp10(freq_exponent))),
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn kharitonov_polynomials() {
+        let k = Kharitonov::new(&[1., 2., 3., 4., 5.], &[6., 7., 8., 9., 10.]);
+        let [k1, k2, k3, k4] = k.polynomials();
+        assert_eq!(&[1., 2., 8., 9., 5.], k1.as_slice());
+        assert_eq!(&[6., 7., 3., 4., 10.], k2.as_slice());
+        assert_eq!(&[1., 7., 8., 4., 5.], k3.as_slice());
+        assert_eq!(&[6., 2., 3., 9., 10.], k4.as_slice());
+    }
+
+    #[test]
+    fn value_set_is_rectangle() {
+        let k = Kharitonov::new(&[1., 2., 3., 4.], &[2., 3., 5., 6.]);
+        let v = k.value_set(RadiansPerSecond(0.7));
+        let [a, b, c, d] = *v.vertices();
+        assert_relative_eq!(a.im, b.im);
+        assert_relative_eq!(b.re, c.re);
+        assert_relative_eq!(c.im, d.im);
+        assert_relative_eq!(d.re, a.re);
+        assert!(a.re < c.re && a.im < c.im);
+
+        // Every member of the family lies in the rectangle.
+        let p = Poly::new_from_coeffs(&[1.5, 2.2, 4.1, 5.]);
+        let x = p.eval_by_val(Complex::new(0., 0.7));
+        assert!(a.re <= x.re && x.re <= c.re && a.im <= x.im && x.im <= c.im);
+    }
+
+    #[test]
+    fn zero_exclusion() {
+        // s^3 + a2 s^2 + a1 s + a0, stable for a2 a1 > a0.
+        let stable = Kharitonov::new(&[1., 2., 2., 1.], &[1.5, 3., 3., 1.]);
+        assert!(stable.is_robustly_stable());
+        assert!(stable.zero_exclusion(RadiansPerSecond(0.01), RadiansPerSecond(100.), 0.001));
+
+        let unstable = Kharitonov::new(&[1., 0.5, 0.5, 1.], &[5., 3., 3., 1.]);
+        assert!(!unstable.is_robustly_stable());
+        assert!(!unstable.zero_exclusion(RadiansPerSecond(0.01), RadiansPerSecond(100.), 0.001));
+    }
+
+    #[test]
+    fn varying_degree() {
+        let k = Kharitonov::new(&[1., 1., -1.], &[2., 2., 1.]);
+        assert!(!k.is_robustly_stable());
+    }
+
+    #[test]
+    #[should_panic]
+    fn wrong_bounds() {
+        let _ = Kharitonov::new(&[1., 3.], &[2., 2.]);
+    }
+}
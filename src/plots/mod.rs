@@ -6,13 +6,59 @@
 //!
 //! [Root locus](root_locus/index.html)
 //!
-//! Plots are implemented as iterators.
+//! [Pole-zero map](pzmap/index.html)
+//!
+//! [Singular value plot](sigma/index.html)
+//!
+//! [Nichols plot and grid](nichols/index.html) of constant closed loop magnitude and
+//! phase
+//!
+//! [Kharitonov value sets](kharitonov/index.html) for the robust stability of
+//! interval polynomials
+//!
+//! [Comparative plots](multi/index.html) of several systems
+//!
+//! [Time response plots](time/index.html): step, impulse and ramp responses
+//!
+//! [Export](export/index.html) of the plots in CSV and JSON format
+//!
+//! [Decimation](decimation/index.html) of long time series to the resolution
+//! of the plot
+//!
+//! [Rendering](render/index.html) of the plots to PNG and SVG images, it
+//! requires the `plotters` feature
+//!
+//! Plots are implemented as iterators. The data points can be collected
+//! into parallel arrays, one for each quantity, with the `into_vecs` method
+//! of the iterators.
+//!
+//! The data points of the frequency response plots (Bode, polar, Nyquist
+//! and Nichols) share the [FrequencyPoint](trait.FrequencyPoint.html)
+//! accessors, the iterators over them are [FrequencyPlot](trait.FrequencyPlot.html)s.
+//!
+//! The frequency grid of Bode and polar plots can be refined where the
+//! frequency response changes fast, see [Refinement](struct.Refinement.html).
+//! Bode and polar plots can also be evaluated on an explicit list of
+//! frequencies, for example the ones of a measured frequency response.
 
 pub mod bode;
+pub mod decimation;
+pub mod export;
+pub mod kharitonov;
+pub mod multi;
+pub mod nichols;
 pub mod polar;
+pub mod pzmap;
+#[cfg(feature = "plotters")]
+pub mod render;
 pub mod root_locus;
+pub mod sigma;
+pub mod time;
 
 use num_complex::Complex;
+use num_traits::{Float, Num};
+
+use crate::units::RadiansPerSecond;
 
 /// Determine how the transfer function is evaluated in plots.
 pub trait Plotter<T> {
@@ -22,4 +68,422 @@ pub trait Plotter<T> {
     ///
     /// * `x` - value at which the function is evaluated
     fn eval_point(&self, x: T) -> Complex<T>;
+
+    /// Derivative of the phase of the transfer function with respect to the
+    /// evaluation value.
+    ///
+    /// The default implementation uses the central finite difference of
+    /// `eval_point`.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - value at which the derivative is evaluated
+    fn phase_derivative(&self, x: T) -> T
+    where
+        T: Float,
+    {
+        let h = T::epsilon().cbrt() * x.abs().max(T::one());
+        (self.eval_point(x + h) / self.eval_point(x - h)).arg() / (h + h)
+    }
+
+    /// Sampling period of discrete time systems, if known.
+    ///
+    /// The default implementation returns `None`.
+    fn sample_time(&self) -> Option<T> {
+        None
+    }
+}
+
+/// Data point of a frequency response plot.
+pub trait FrequencyPoint<T: Num> {
+    /// Angular frequency of the data point
+    fn angular_frequency(&self) -> RadiansPerSecond<T>;
+
+    /// Value of the frequency response at the angular frequency
+    fn value(&self) -> Complex<T>;
+}
+
+/// Frequency response plot, iterator over frequency response data points.
+///
+/// It is implemented by the iterators of Bode, polar, Nyquist and Nichols
+/// plots, generic code over all the frequency response plots can be written
+/// using the [FrequencyPoint](trait.FrequencyPoint.html) accessors of the
+/// items.
+pub trait FrequencyPlot<T: Num>: Iterator + Sized
+where
+    Self::Item: FrequencyPoint<T>,
+{
+    /// Collect the data points into the parallel arrays of the angular
+    /// frequencies and of the values of the frequency response.
+    ///
+    /// # Example
+    /// ```
+    /// use au::{
+    ///     plots::{bode::Bode, polar::Polar, FrequencyPlot},
+    ///     poly, RadiansPerSecond, Tf,
+    /// };
+    /// let tf = Tf::new(poly!(1.), poly!(1., 1.));
+    /// let (w, range) = (RadiansPerSecond(1.), RadiansPerSecond(10.));
+    /// let (_, bode) = Bode::new(tf.clone(), w, range, 0.5).into_iter().into_frequency_response();
+    /// let (_, polar) = Polar::new(tf, w, range, 0.5).into_iter().into_frequency_response();
+    /// for (b, p) in bode.iter().zip(&polar) {
+    ///     assert!((b - p).norm() < 1e-12);
+    /// }
+    /// ```
+    fn into_frequency_response(self) -> (Vec<T>, Vec<Complex<T>>) {
+        self.map(|d| (d.angular_frequency().0, d.value())).unzip()
+    }
+}
+
+impl<T: Num, I> FrequencyPlot<T> for I
+where
+    I: Iterator,
+    I::Item: FrequencyPoint<T>,
+{
+}
+
+/// Criteria for the adaptive refinement of the frequency grid of plots.
+///
+/// The interval between two consecutive frequencies is halved as long as the
+/// magnitude or the phase of the frequency response change more than the
+/// given thresholds, up to the maximum depth.
+///
+/// In flat regions the points of the frequency grid can also be skipped, as
+/// long as the frequency response changes less than the thresholds, see
+/// [with_coarsening](struct.Refinement.html#method.with_coarsening).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Refinement<T> {
+    /// Maximum magnitude change between consecutive frequencies (dB)
+    magnitude: T,
+    /// Maximum phase change between consecutive frequencies (rad)
+    phase: T,
+    /// Maximum number of halvings of the step
+    depth: u32,
+    /// Maximum number of doublings of the step
+    coarsening: u32,
+}
+
+impl<T: Float> Refinement<T> {
+    /// Create the refinement criteria.
+    ///
+    /// # Arguments
+    ///
+    /// * `magnitude` - Maximum magnitude change between consecutive frequencies (dB)
+    /// * `phase` - Maximum phase change between consecutive frequencies (rad)
+    /// * `depth` - Maximum number of halvings of the step
+    ///
+    /// # Panics
+    ///
+    /// Panics if the thresholds are not strictly positive.
+    ///
+    /// # Example
+    /// ```
+    /// use au::{plots::{bode::Bode, Refinement}, Poly, RadiansPerSecond, Tf};
+    /// // Lightly damped resonance at 10 rad/s.
+    /// let tf = Tf::new(Poly::new_from_coeffs(&[100.]), Poly::new_from_coeffs(&[100., 0.2, 1.]));
+    /// let bode = Bode::new(tf, RadiansPerSecond(1.), RadiansPerSecond(100.), 0.5)
+    ///     .with_refinement(Refinement::new(1., 0.1, 10));
+    /// let peak = bode.into_iter().map(|d| d.magnitude()).fold(0., f64::max);
+    /// assert!(peak > 45.);
+    /// ```
+    #[must_use]
+    pub fn new(magnitude: T, phase: T, depth: u32) -> Self {
+        assert!(
+            magnitude > T::zero(),
+            "Magnitude threshold must be positive"
+        );
+        assert!(phase > T::zero(), "Phase threshold must be positive");
+        Self {
+            magnitude,
+            phase,
+            depth,
+            coarsening: 0,
+        }
+    }
+
+    /// Skip the points of the frequency grid where the frequency response
+    /// changes less than the thresholds, the step between consecutive
+    /// frequencies is at most doubled the given number of times.
+    ///
+    /// # Arguments
+    ///
+    /// * `coarsening` - Maximum number of doublings of the step
+    ///
+    /// # Example
+    /// ```
+    /// use au::{plots::{bode::Bode, Refinement}, Poly, RadiansPerSecond, Tf};
+    /// let tf = Tf::new(Poly::new_from_coeffs(&[1.]), Poly::new_from_coeffs(&[1., 1.]));
+    /// let bode = Bode::new(tf, RadiansPerSecond(1e-4), RadiansPerSecond(1e-1), 0.1);
+    /// let refinement = Refinement::new(1., 0.1, 4).with_coarsening(3);
+    /// let coarse = bode.clone().into_iter().count();
+    /// let adaptive = bode.with_refinement(refinement).into_iter().count();
+    /// assert!(adaptive < coarse);
+    /// ```
+    #[must_use]
+    pub fn with_coarsening(self, coarsening: u32) -> Self {
+        Self { coarsening, ..self }
+    }
+
+    /// Get the maximum magnitude change between consecutive frequencies (dB)
+    #[must_use]
+    pub fn magnitude(&self) -> T {
+        self.magnitude
+    }
+
+    /// Get the maximum phase change between consecutive frequencies (rad)
+    #[must_use]
+    pub fn phase(&self) -> T {
+        self.phase
+    }
+
+    /// Get the maximum number of halvings of the step
+    #[must_use]
+    pub fn depth(&self) -> u32 {
+        self.depth
+    }
+
+    /// Get the maximum number of doublings of the step
+    #[must_use]
+    pub fn coarsening(&self) -> u32 {
+        self.coarsening
+    }
+
+    /// Check if the frequency response changes too much between two points.
+    fn exceeded(&self, g1: Complex<T>, g2: Complex<T>) -> bool {
+        let twenty = T::from(20.).unwrap();
+        let db = twenty * (g2.norm() / g1.norm()).log10();
+        let phase = (g2 / g1).arg();
+        // NaN comparisons are false, the negation refines undefined changes.
+        !(db.abs() <= self.magnitude && phase.abs() <= self.phase)
+    }
+
+    /// Get the next point of the adaptive frequency grid.
+    ///
+    /// Points are represented by the exponent of the angular frequency
+    /// and the value of the frequency response.
+    ///
+    /// # Arguments
+    ///
+    /// * `grid` - Frequency grid
+    /// * `step` - Step of the regular grid
+    /// * `last` - Last returned point
+    /// * `pending` - Stack of points not yet returned, the nearest on top
+    pub(crate) fn next_point<G: Grid<T, Complex<T>>>(
+        &self,
+        grid: &mut G,
+        step: T,
+        last: &(T, Complex<T>),
+        pending: &mut Vec<(T, Complex<T>)>,
+    ) -> Option<(T, Complex<T>)> {
+        let steps = (
+            step / T::from(2_u32.pow(self.depth.min(31))).unwrap(),
+            step * T::from(2_u32.pow(self.coarsening.min(31))).unwrap(),
+        );
+        adaptive_point(grid, |a, b| self.exceeded(*a, *b), steps, last, pending)
+    }
+}
+
+/// Grid of points of a plot, whose regular points can be refined or skipped.
+pub(crate) trait Grid<T, V> {
+    /// Get the next point of the regular grid, its position and its value.
+    fn coarse_point(&mut self) -> Option<(T, V)>;
+
+    /// Evaluate the value at the given position of the grid.
+    fn eval(&self, x: T) -> V;
+}
+
+/// Get the next point of an adaptive grid, after the last returned point.
+///
+/// The points of the regular grid are skipped as long as the value does not
+/// change too much from the last point and the step does not exceed the
+/// maximum one. The interval between the last point and the nearest pending
+/// one is halved as long as the value changes too much, up to the minimum
+/// step.
+///
+/// # Arguments
+///
+/// * `grid` - Grid of points
+/// * `exceeded` - Check if the value changes too much between two points
+/// * `steps` - Minimum and maximum step between points
+/// * `last` - Last returned point
+/// * `pending` - Stack of points not yet returned, the nearest on top
+pub(crate) fn adaptive_point<T, V, G, F>(
+    grid: &mut G,
+    exceeded: F,
+    steps: (T, T),
+    last: &(T, V),
+    pending: &mut Vec<(T, V)>,
+) -> Option<(T, V)>
+where
+    T: Float,
+    G: Grid<T, V>,
+    F: Fn(&V, &V) -> bool,
+{
+    let (min_step, max_step) = steps;
+    if pending.is_empty() {
+        pending.push(grid.coarse_point()?);
+    }
+    // The only pending point belongs to the regular grid.
+    while pending.len() == 1 && !exceeded(&last.1, &pending[0].1) {
+        match grid.coarse_point() {
+            Some(next) if next.0 - last.0 <= max_step && !exceeded(&last.1, &next.1) => {
+                pending[0] = next;
+            }
+            Some(next) => {
+                pending.insert(0, next);
+                break;
+            }
+            None => break,
+        }
+    }
+    let two = T::one() + T::one();
+    loop {
+        let right = pending.last()?;
+        let width = right.0 - last.0;
+        if width / two < min_step || !exceeded(&last.1, &right.1) {
+            return pending.pop();
+        }
+        let mid = (last.0 + right.0) / two;
+        let value = grid.eval(mid);
+        pending.push((mid, value));
+    }
+}
+
+/// Power of 10 of the exponent.
+pub(crate) fn exp10<T: Float>(exponent: T) -> T {
+    // Casting is safe for both f32 and f64, representation is exact.
+    T::from(10.0_f32).unwrap().powf(exponent)
+}
+
+/// Transpose the rows of a table into its columns, rows shorter than the
+/// longest one leave the missing values out of the columns.
+pub(crate) fn transpose<X>(rows: Vec<Vec<X>>) -> Vec<Vec<X>> {
+    let mut columns: Vec<Vec<X>> = Vec::new();
+    for row in rows {
+        for (i, x) in row.into_iter().enumerate() {
+            if i == columns.len() {
+                columns.push(Vec::new());
+            }
+            columns[i].push(x);
+        }
+    }
+    columns
+}
+
+/// Explicit frequency grid of a plot.
+///
+/// It returns the exponents of the angular frequencies and the largest step
+/// between consecutive exponents, that is used as coarsest step by the
+/// refinement of the grid.
+///
+/// # Arguments
+///
+/// * `frequencies` - Angular frequencies of the grid
+///
+/// # Panics
+///
+/// Panics if the frequencies are not strictly positive and strictly
+/// increasing.
+pub(crate) fn frequency_grid<T, I>(frequencies: I) -> (Vec<T>, T)
+where
+    T: Float,
+    I: IntoIterator<Item = RadiansPerSecond<T>>,
+{
+    let grid: Vec<_> = frequencies
+        .into_iter()
+        .map(|w| {
+            assert!(w.0 > T::zero(), "Frequencies must be strictly positive");
+            w.0.log10()
+        })
+        .collect();
+    let step = grid.windows(2).fold(T::zero(), |step, w| {
+        assert!(w[0] < w[1], "Frequencies must be strictly increasing");
+        step.max(w[1] - w[0])
+    });
+    (grid, if step > T::zero() { step } else { T::one() })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Transfer function that only defines the evaluation of the plot.
+    struct Evaluation(crate::transfer_function::continuous::Tf<f64>);
+
+    impl Plotter<f64> for Evaluation {
+        fn eval_point(&self, x: f64) -> Complex<f64> {
+            self.0.eval_point(x)
+        }
+    }
+
+    #[test]
+    fn default_phase_derivative() {
+        let tf = crate::transfer_function::continuous::Tf::new(
+            crate::poly!(1., -0.5),
+            crate::poly!(1., 0.5, 0.2),
+        );
+        let finite = Evaluation(tf.clone());
+        for &w in &[0.1, 1., 2.2, 10.] {
+            assert_relative_eq!(
+                tf.phase_derivative(w),
+                finite.phase_derivative(w),
+                max_relative = 1e-8
+            );
+        }
+    }
+
+    #[test]
+    fn explicit_frequency_grid() {
+        let (grid, step) = frequency_grid(vec![
+            RadiansPerSecond(0.1),
+            RadiansPerSecond(1.),
+            RadiansPerSecond(1000.),
+        ]);
+        assert_eq!(3, grid.len());
+        assert_relative_eq!(-1., grid[0]);
+        assert_relative_eq!(3., grid[2]);
+        assert_relative_eq!(3., step);
+
+        let (grid, step) = frequency_grid(vec![RadiansPerSecond(1.)]);
+        assert_eq!(vec![0.], grid);
+        assert_relative_eq!(1., step);
+    }
+
+    #[test]
+    #[should_panic]
+    fn unsorted_frequency_grid() {
+        let _ = frequency_grid(vec![RadiansPerSecond(2.), RadiansPerSecond(1.)]);
+    }
+
+    #[test]
+    fn transpose_rows() {
+        let columns = transpose(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        assert_eq!(vec![vec![1, 4], vec![2, 5], vec![3, 6]], columns);
+        assert!(transpose(Vec::<Vec<i32>>::new()).is_empty());
+    }
+
+    #[test]
+    fn refinement_getters() {
+        let r = Refinement::new(2., 0.5, 4);
+        assert_relative_eq!(2., r.magnitude());
+        assert_relative_eq!(0.5, r.phase());
+        assert_eq!(4, r.depth());
+        assert_eq!(0, r.coarsening());
+        assert_eq!(2, r.with_coarsening(2).coarsening());
+    }
+
+    #[test]
+    fn refinement_exceeded() {
+        let r = Refinement::new(1., 0.1, 4);
+        assert!(!r.exceeded(Complex::new(1., 0.), Complex::new(1.1, 0.05)));
+        assert!(r.exceeded(Complex::new(1., 0.), Complex::new(2., 0.)));
+        assert!(r.exceeded(Complex::new(1., 0.), Complex::new(0., 1.)));
+        assert!(r.exceeded(Complex::new(0., 0.), Complex::new(1., 0.)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn refinement_fail() {
+        let _ = Refinement::new(0., 0.5, 4);
+    }
 }
@@ -0,0 +1,218 @@
+//! # Comparative plots
+//!
+//! Iteration over the plots of several systems at the same time, each item
+//! groups the samples of all the systems at the same frequency, so that the
+//! responses can be compared or exported in a single aligned table.
+//!
+//! The plots shall have the same frequency grid, the constructors of this
+//! module guarantee it creating all the plots with the same parameters.
+//! Plots with adaptive refinement of the frequency grid have different
+//! grids and shall not be grouped.
+
+use num_traits::{Float, MulAdd};
+
+use crate::{
+    plots::{bode::Bode, polar::Polar, Plotter},
+    units::RadiansPerSecond,
+};
+
+/// Iterator over the grouped samples of several plots.
+#[derive(Clone, Debug)]
+pub struct MultiPlot<I> {
+    /// Iterators of the plots
+    iters: Vec<I>,
+}
+
+impl<I: Iterator> MultiPlot<I> {
+    /// Group the samples of the given plots.
+    ///
+    /// The iteration stops when the shortest plot ends.
+    ///
+    /// # Arguments
+    ///
+    /// * `plots` - Plots to group
+    pub fn new<P, J>(plots: P) -> Self
+    where
+        P: IntoIterator<Item = J>,
+        J: IntoIterator<IntoIter = I, Item = I::Item>,
+    {
+        Self {
+            iters: plots.into_iter().map(IntoIterator::into_iter).collect(),
+        }
+    }
+
+    /// Number of grouped plots
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.iters.len()
+    }
+
+    /// Check if there are no grouped plots
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.iters.is_empty()
+    }
+}
+
+impl<I: Iterator> Iterator for MultiPlot<I> {
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.iters.is_empty() {
+            return None;
+        }
+        self.iters.iter_mut().map(Iterator::next).collect()
+    }
+}
+
+/// Bode plots of several systems on the same frequency grid.
+///
+/// # Arguments
+///
+/// * `systems` - Systems to plot
+/// * `min_freq` - Minimum angular frequency of the plot
+/// * `max_freq` - Maximum angular frequency of the plot
+/// * `step` - Step between frequencies
+///
+/// `step` shall be in logarithmic scale. Use 0.1 to have 10 point per decade
+///
+/// # Panics
+///
+/// Panics if the step is not strictly positive and the minimum frequency
+/// is not lower than the maximum frequency
+///
+/// # Example
+/// ```
+/// use au::{plots::multi, poly, Poly, RadiansPerSecond, Tf};
+/// let plant = Tf::new(poly!(1.), Poly::new_from_roots(&[-1., -2.]));
+/// let controller = Tf::new(poly!(1., 1.), poly!(0., 1.));
+/// let compensated = &plant * &controller;
+/// let table = multi::bode(
+///     vec![plant, compensated],
+///     RadiansPerSecond(0.1),
+///     RadiansPerSecond(10.),
+///     0.1,
+/// );
+/// for row in table {
+///     assert_eq!(2, row.len());
+///     assert_eq!(row[0].angular_frequency(), row[1].angular_frequency());
+/// }
+/// ```
+pub fn bode<T, U, S>(
+    systems: S,
+    min_freq: RadiansPerSecond<T>,
+    max_freq: RadiansPerSecond<T>,
+    step: T,
+) -> MultiPlot<crate::plots::bode::IntoIter<T, U>>
+where
+    T: Float + MulAdd<Output = T>,
+    U: Plotter<T>,
+    S: IntoIterator<Item = U>,
+{
+    MultiPlot::new(
+        systems
+            .into_iter()
+            .map(|sys| Bode::new(sys, min_freq, max_freq, step))
+            .collect::<Vec<_>>(),
+    )
+}
+
+/// Polar plots of several systems on the same frequency grid.
+///
+/// # Arguments
+///
+/// * `systems` - Systems to plot
+/// * `min_freq` - Minimum angular frequency of the plot
+/// * `max_freq` - Maximum angular frequency of the plot
+/// * `step` - Step between frequencies
+///
+/// `step` shall be in logarithmic scale. Use 0.1 to have 10 point per decade
+///
+/// # Panics
+///
+/// Panics if the step is not strictly positive and the minimum frequency
+/// is not lower than the maximum frequency
+pub fn polar<T, U, S>(
+    systems: S,
+    min_freq: RadiansPerSecond<T>,
+    max_freq: RadiansPerSecond<T>,
+    step: T,
+) -> MultiPlot<crate::plots::polar::IntoIter<T, U>>
+where
+    T: Float + MulAdd<Output = T>,
+    U: Plotter<T>,
+    S: IntoIterator<Item = U>,
+{
+    MultiPlot::new(
+        systems
+            .into_iter()
+            .map(|sys| Polar::new(sys, min_freq, max_freq, step))
+            .collect::<Vec<_>>(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{poly, transfer_function::continuous::Tf};
+
+    #[test]
+    fn grouped_bode() {
+        let systems = vec![
+            Tf::new(poly!(1.), poly!(1., 1.)),
+            Tf::new(poly!(2.), poly!(1., 1.)),
+            Tf::new(poly!(1.), poly!(1., 0.1)),
+        ];
+        let table = multi_bode(systems);
+        assert_eq!(3, table.len());
+        assert!(!table.is_empty());
+        let rows: Vec<_> = table.collect();
+        assert_eq!(21, rows.len());
+        for row in &rows {
+            assert_eq!(3, row.len());
+            assert_eq!(row[0].angular_frequency(), row[1].angular_frequency());
+            assert_eq!(row[0].angular_frequency(), row[2].angular_frequency());
+            assert_relative_eq!(2. * row[0].magnitude(), row[1].magnitude());
+        }
+    }
+
+    fn multi_bode(systems: Vec<Tf<f64>>) -> MultiPlot<crate::plots::bode::IntoIter<f64, Tf<f64>>> {
+        bode(systems, RadiansPerSecond(0.1), RadiansPerSecond(10.), 0.1)
+    }
+
+    #[test]
+    fn grouped_polar() {
+        let systems = vec![
+            Tf::new(poly!(1.), poly!(1., 1.)),
+            Tf::new(poly!(-1.), poly!(1., 1.)),
+        ];
+        for row in polar(systems, RadiansPerSecond(0.1), RadiansPerSecond(10.), 0.5) {
+            assert_eq!(row[0].freq(), row[1].freq());
+            assert_relative_eq!(row[0].real(), -row[1].real());
+        }
+    }
+
+    #[test]
+    fn grouped_shortest() {
+        let short = Bode::new(
+            Tf::new(poly!(1.), poly!(1., 1.)),
+            RadiansPerSecond(0.1),
+            RadiansPerSecond(1.),
+            0.1,
+        );
+        let long = Bode::new(
+            Tf::new(poly!(1.), poly!(1., 1.)),
+            RadiansPerSecond(0.1),
+            RadiansPerSecond(10.),
+            0.1,
+        );
+        assert_eq!(11, MultiPlot::new(vec![short, long]).count());
+    }
+
+    #[test]
+    fn empty_group() {
+        let mut table = multi_bode(Vec::new());
+        assert!(table.is_empty());
+        assert!(table.next().is_none());
+    }
+}
@@ -0,0 +1,512 @@
+//! # Nichols plot
+//!
+//! The Nichols plot is the open loop frequency response drawn in the Nichols
+//! plane, with the phase in the interval (-360°, 0°].
+//!
+//! Contours of constant closed loop magnitude (M) and phase (N) in the
+//! Nichols plane, the open loop phase in degrees on the horizontal axis and
+//! the open loop magnitude in decibels on the vertical axis.
+//!
+//! The closed loop is the unity feedback `T = L / (1 + L)` of the open loop
+//! transfer function `L`. The contours span the open loop phases between
+//! -360° and 0°, the Bode plot converted into decibels and degrees and with
+//! the phase shifted in the same interval can be drawn on the grid.
+//!
+//! The points of the plot and of the contours can be
+//! [exported](../export/index.html).
+
+use num_complex::Complex;
+use num_traits::{Float, FloatConst, MulAdd};
+
+use crate::{
+    plots::{exp10, polar, polar::Polar, FrequencyPoint, Plotter},
+    units::{RadiansPerSecond, Seconds},
+    utils::linspace,
+};
+
+/// Struct for the calculation of Nichols plots
+#[derive(Clone, Debug)]
+pub struct Nichols<T: Float + MulAdd<Output = T>, U: Plotter<T>> {
+    /// Polar plot of the open loop transfer function
+    polar: Polar<T, U>,
+}
+
+impl<T: Float + MulAdd<Output = T>, U: Plotter<T>> Nichols<T, U> {
+    /// Create a `Nichols` plot struct
+    ///
+    /// # Arguments
+    ///
+    /// * `tf` - Open loop transfer function to plot
+    /// * `min_freq` - Minimum angular frequency of the plot
+    /// * `max_freq` - Maximum angular frequency of the plot
+    /// * `step` - Step between frequencies
+    ///
+    /// `step` shall be in logarithmic scale. Use 0.1 to have 10 point per decade
+    ///
+    /// # Panics
+    ///
+    /// Panics if the step is not strictly positive of the minimum frequency
+    /// is not lower than the maximum frequency.
+    ///
+    /// # Example
+    /// ```
+    /// use au::{plots::nichols::Nichols, poly, RadiansPerSecond, Tf};
+    /// let tf = Tf::new(poly!(1.), poly!(0., 1., 1.));
+    /// let nichols = Nichols::new(tf, RadiansPerSecond(0.1), RadiansPerSecond(10.), 0.1);
+    /// assert!(nichols.into_iter().all(|d| d.phase() > -180. && d.phase() < -90.));
+    /// ```
+    pub fn new(
+        tf: U,
+        min_freq: RadiansPerSecond<T>,
+        max_freq: RadiansPerSecond<T>,
+        step: T,
+    ) -> Self {
+        Self {
+            polar: Polar::new(tf, min_freq, max_freq, step),
+        }
+    }
+}
+
+impl<T: Float + MulAdd<Output = T>, U: Plotter<T>> From<Polar<T, U>> for Nichols<T, U> {
+    fn from(polar: Polar<T, U>) -> Self {
+        Self { polar }
+    }
+}
+
+impl<T: Float + MulAdd<Output = T>, U: Plotter<T>> IntoIterator for Nichols<T, U> {
+    type Item = Data<T>;
+    type IntoIter = IntoIter<T, U>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        Self::IntoIter {
+            iter: self.polar.into_iter(),
+        }
+    }
+}
+
+/// Struct for the Nichols plot data point iteration.
+#[derive(Clone, Debug)]
+pub struct IntoIter<T: Float + MulAdd<Output = T>, U: Plotter<T>> {
+    /// Iterator of the polar plot of the open loop transfer function
+    iter: polar::IntoIter<T, U>,
+}
+
+impl<T: Float + MulAdd<Output = T>, U: Plotter<T>> Iterator for IntoIter<T, U> {
+    type Item = Data<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|d| Data {
+            angular_frequency: RadiansPerSecond(d.freq()),
+            output: d.output(),
+        })
+    }
+}
+
+/// Struct to hold the data returned by the Nichols iterator.
+#[derive(Clone, Copy, Debug)]
+pub struct Data<T: Float> {
+    /// Angular frequency (rad)
+    angular_frequency: RadiansPerSecond<T>,
+    /// Value of the open loop frequency response
+    output: Complex<T>,
+}
+
+impl<T: Float> Data<T> {
+    /// Get the angular frequency
+    #[must_use]
+    pub fn angular_frequency(&self) -> RadiansPerSecond<T> {
+        self.angular_frequency
+    }
+
+    /// Get the open loop phase (degrees), in the interval (-360°, 0°]
+    #[must_use]
+    pub fn phase(&self) -> T {
+        let phase = self.output.arg().to_degrees();
+        if phase > T::zero() {
+            phase - T::from(360.).unwrap()
+        } else {
+            phase
+        }
+    }
+
+    /// Get the open loop magnitude (dB)
+    #[must_use]
+    pub fn magnitude(&self) -> T {
+        T::from(20.).unwrap() * self.output.norm().log10()
+    }
+
+    /// Get the point of the Nichols plane
+    #[must_use]
+    pub fn point(&self) -> Point<T> {
+        Point {
+            phase: self.phase(),
+            magnitude: self.magnitude(),
+        }
+    }
+}
+
+impl<T: Float> FrequencyPoint<T> for Data<T> {
+    fn angular_frequency(&self) -> RadiansPerSecond<T> {
+        self.angular_frequency
+    }
+
+    fn value(&self) -> Complex<T> {
+        self.output
+    }
+}
+
+/// Point of the Nichols plane
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Point<T> {
+    /// Open loop phase (degrees)
+    phase: T,
+    /// Open loop magnitude (dB)
+    magnitude: T,
+}
+
+impl<T: Copy> Point<T> {
+    /// Get the open loop phase (degrees)
+    #[must_use]
+    pub fn phase(&self) -> T {
+        self.phase
+    }
+
+    /// Get the open loop magnitude (dB)
+    #[must_use]
+    pub fn magnitude(&self) -> T {
+        self.magnitude
+    }
+}
+
+/// Contour of constant closed loop magnitude or phase
+#[derive(Clone, Debug, PartialEq)]
+pub struct Contour<T> {
+    /// Closed loop magnitude (dB) or phase (degrees) of the contour
+    value: T,
+    /// Points of the contour
+    points: Vec<Point<T>>,
+}
+
+impl<T: Copy> Contour<T> {
+    /// Get the closed loop magnitude (dB) or phase (degrees) of the contour
+    #[must_use]
+    pub fn value(&self) -> T {
+        self.value
+    }
+
+    /// Get the points of the contour, ordered along the curve
+    #[must_use]
+    pub fn points(&self) -> &[Point<T>] {
+        &self.points
+    }
+}
+
+impl<T: Float + FloatConst> Contour<T> {
+    /// Contour of constant closed loop magnitude (M contour).
+    ///
+    /// Contours with positive magnitude are closed curves around the point
+    /// (-180°, 0 dB), the other ones span all the open loop phases.
+    ///
+    /// # Arguments
+    ///
+    /// * `magnitude` - Closed loop magnitude (dB)
+    /// * `points` - Number of open loop phases at which the contour is sampled
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of points is less than two.
+    ///
+    /// # Example
+    /// ```
+    /// use au::{plots::nichols::Contour, units::ToDecibel};
+    /// let m = Contour::magnitude(3., 101);
+    /// // Closed contour, at -180° the open loop magnitude is 20*log10(M/(M-1))
+    /// // on the upper branch.
+    /// let top = m.points().iter().map(|p| p.magnitude()).fold(f64::MIN, f64::max);
+    /// let m_abs = 3_f64.from_db();
+    /// assert!((top - 20. * (m_abs / (m_abs - 1.)).log10()).abs() < 1e-2);
+    /// ```
+    #[must_use]
+    pub fn magnitude(magnitude: T, points: usize) -> Self {
+        assert!(points >= 2, "At least two points are required");
+        let m2 = exp10(magnitude / T::from(10.).unwrap());
+        let one_m2 = T::one() - m2;
+        let twenty = T::from(20.).unwrap();
+        let point = |phase: T, r: T| Point {
+            phase,
+            magnitude: twenty * r.log10(),
+        };
+
+        let points = if m2 > T::one() {
+            // Open loop phases where the open loop magnitude has two solutions.
+            let limit = (-((m2 - T::one()) / m2).sqrt()).acos().to_degrees();
+            let full = T::from(360.).unwrap();
+            let (mut upper, mut lower): (Vec<_>, Vec<_>) =
+                linspace(Seconds(limit - full), Seconds(-limit), points)
+                    .map(|Seconds(phase)| {
+                        let cos = phase.to_radians().cos();
+                        let disc = (m2 * m2 * cos * cos + m2 * one_m2).max(T::zero()).sqrt();
+                        let r1 = (m2 * cos + disc) / one_m2;
+                        let r2 = (m2 * cos - disc) / one_m2;
+                        (point(phase, r1.max(r2)), point(phase, r1.min(r2)))
+                    })
+                    .unzip();
+            lower.reverse();
+            upper.extend(lower);
+            upper
+        } else {
+            linspace(Seconds(T::from(-360.).unwrap()), Seconds(T::zero()), points)
+                .filter_map(|Seconds(phase)| {
+                    let cos = phase.to_radians().cos();
+                    let r = if one_m2 == T::zero() {
+                        -(cos + cos).recip()
+                    } else {
+                        let disc = (m2 * m2 * cos * cos + m2 * one_m2).sqrt();
+                        (m2 * cos + disc) / one_m2
+                    };
+                    if r.is_finite() && r > T::zero() {
+                        Some(point(phase, r))
+                    } else {
+                        None
+                    }
+                })
+                .collect()
+        };
+        Self {
+            value: magnitude,
+            points,
+        }
+    }
+
+    /// Contour of constant closed loop phase (N contour).
+    ///
+    /// The contour is empty if the phase is a multiple of 180°.
+    ///
+    /// # Arguments
+    ///
+    /// * `phase` - Closed loop phase (degrees)
+    /// * `points` - Number of open loop phases at which the contour is sampled
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of points is less than two.
+    ///
+    /// # Example
+    /// ```
+    /// use au::plots::nichols::Contour;
+    /// let n = Contour::phase(-90., 50);
+    /// // Open loop phases between -180° and -90°.
+    /// assert!(n.points().iter().all(|p| p.phase() >= -180. && p.phase() < -90.));
+    /// ```
+    #[must_use]
+    pub fn phase(phase: T, points: usize) -> Self {
+        assert!(points >= 2, "At least two points are required");
+        let psi = phase.to_radians();
+        let tau = T::TAU();
+        let twenty = T::from(20.).unwrap();
+        let points = linspace(Seconds(T::from(-360.).unwrap()), Seconds(T::zero()), points)
+            .filter_map(|Seconds(theta)| {
+                let theta_rad = theta.to_radians();
+                let r = (theta_rad - psi).sin() / psi.sin();
+                if !r.is_finite() || r <= T::zero() {
+                    return None;
+                }
+                let l = Complex::from_polar(r, theta_rad);
+                let closed = l / (l + T::one());
+                let diff = (closed.arg() - psi) / tau;
+                if (diff - diff.round()).abs() > T::epsilon().sqrt() {
+                    return None;
+                }
+                Some(Point {
+                    phase: theta,
+                    magnitude: twenty * r.log10(),
+                })
+            })
+            .collect();
+        Self {
+            value: phase,
+            points,
+        }
+    }
+}
+
+/// Nichols grid, contours of constant closed loop magnitude and phase
+#[derive(Clone, Debug, PartialEq)]
+pub struct NicholsGrid<T> {
+    /// Contours of constant closed loop magnitude
+    magnitude_contours: Vec<Contour<T>>,
+    /// Contours of constant closed loop phase
+    phase_contours: Vec<Contour<T>>,
+}
+
+impl<T: Float + FloatConst> NicholsGrid<T> {
+    /// Create a Nichols grid
+    ///
+    /// # Arguments
+    ///
+    /// * `magnitudes` - Closed loop magnitudes of the M contours (dB)
+    /// * `phases` - Closed loop phases of the N contours (degrees)
+    /// * `points` - Number of open loop phases at which the contours are sampled
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of points is less than two.
+    #[must_use]
+    pub fn new(magnitudes: &[T], phases: &[T], points: usize) -> Self {
+        Self {
+            magnitude_contours: magnitudes
+                .iter()
+                .map(|&m| Contour::magnitude(m, points))
+                .collect(),
+            phase_contours: phases.iter().map(|&n| Contour::phase(n, points)).collect(),
+        }
+    }
+
+    /// Create the standard Nichols grid, with the M contours at
+    /// 6, 3, 1, 0.5, 0.25, 0, -1, -3, -6, -12, -20 and -40 dB and the N
+    /// contours from -1° to -359°.
+    ///
+    /// # Arguments
+    ///
+    /// * `points` - Number of open loop phases at which the contours are sampled
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of points is less than two.
+    ///
+    /// # Example
+    /// ```
+    /// use au::plots::nichols::NicholsGrid;
+    /// let grid = NicholsGrid::<f64>::standard(200);
+    /// assert_eq!(12, grid.magnitude_contours().len());
+    /// assert!(grid.phase_contours().iter().all(|c| !c.points().is_empty()));
+    /// ```
+    #[must_use]
+    pub fn standard(points: usize) -> Self {
+        let magnitudes = [6., 3., 1., 0.5, 0.25, 0., -1., -3., -6., -12., -20., -40.];
+        let phases = [
+            -1., -5., -10., -20., -30., -45., -60., -90., -120., -150., -210., -240., -270., -300.,
+            -315., -330., -340., -350., -355., -359.,
+        ];
+        let cast = |v: &[f64]| -> Vec<T> { v.iter().map(|&x| T::from(x).unwrap()).collect() };
+        Self::new(&cast(&magnitudes), &cast(&phases), points)
+    }
+}
+
+impl<T> NicholsGrid<T> {
+    /// Get the contours of constant closed loop magnitude
+    #[must_use]
+    pub fn magnitude_contours(&self) -> &[Contour<T>] {
+        &self.magnitude_contours
+    }
+
+    /// Get the contours of constant closed loop phase
+    #[must_use]
+    pub fn phase_contours(&self) -> &[Contour<T>] {
+        &self.phase_contours
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{plots::FrequencyPlot, poly, units::ToDecibel, Tf};
+
+    #[test]
+    fn nichols_plot() {
+        let tf = Tf::new(poly!(4.), poly!(1., 2., 3., 1.));
+        let nichols = Nichols::new(
+            tf.clone(),
+            RadiansPerSecond(0.1),
+            RadiansPerSecond(10.),
+            0.1,
+        );
+        let data: Vec<_> = nichols.into_iter().collect();
+        assert_eq!(21, data.len());
+        for d in &data {
+            assert!(d.phase() > -360. && d.phase() <= 0.);
+            let g = tf.eval(&Complex::new(0., d.angular_frequency().0));
+            assert_relative_eq!(20. * g.norm().log10(), d.point().magnitude());
+        }
+        // Phase of a third order system goes below -180°.
+        assert!(data.last().unwrap().phase() < -180.);
+    }
+
+    #[test]
+    fn nichols_frequency_response() {
+        let tf = Tf::new(poly!(1.), poly!(1., 1.));
+        let polar = Polar::new(tf, RadiansPerSecond(1.), RadiansPerSecond(10.), 0.5);
+        let (w, values) = Nichols::from(polar.clone())
+            .into_iter()
+            .into_frequency_response();
+        let (w_polar, values_polar) = polar.into_iter().into_frequency_response();
+        assert_eq!(w_polar, w);
+        assert_eq!(values_polar, values);
+    }
+
+    /// Closed loop frequency response at the point of the Nichols plane.
+    fn closed_loop(p: &Point<f64>) -> Complex<f64> {
+        let l = Complex::from_polar(p.magnitude().from_db(), p.phase().to_radians());
+        l / (l + 1.)
+    }
+
+    #[test]
+    fn magnitude_contours() {
+        for &m in &[6., 1., 0., -3., -20.] {
+            let c = Contour::magnitude(m, 101);
+            assert_relative_eq!(m, c.value());
+            assert!(!c.points().is_empty());
+            for p in c.points() {
+                let db = 20. * closed_loop(p).norm().log10();
+                assert_relative_eq!(m, db, epsilon = 1e-8);
+            }
+        }
+    }
+
+    #[test]
+    fn closed_magnitude_contour() {
+        let c = Contour::magnitude(3., 51);
+        assert_eq!(102, c.points().len());
+        let first = c.points()[0];
+        let last = c.points()[101];
+        assert_relative_eq!(first.phase(), last.phase());
+        assert_relative_eq!(first.magnitude(), last.magnitude(), epsilon = 1e-6);
+        assert!(c
+            .points()
+            .iter()
+            .all(|p| p.phase() > -360. && p.phase() < 0.));
+    }
+
+    #[test]
+    fn phase_contours() {
+        for &n in &[-5., -90., -150., -210., -355.] {
+            let c = Contour::phase(n, 181);
+            assert!(!c.points().is_empty());
+            for p in c.points() {
+                let phase = closed_loop(p).arg().to_degrees();
+                let diff = (phase - n) / 360.;
+                assert_relative_eq!(0., diff - diff.round(), epsilon = 1e-8);
+            }
+        }
+    }
+
+    #[test]
+    fn degenerate_phase_contour() {
+        assert!(Contour::phase(-180., 100).points().is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn too_few_points() {
+        let _ = Contour::magnitude(1., 1);
+    }
+
+    #[test]
+    fn grid() {
+        let grid = NicholsGrid::new(&[3., -3.], &[-30.], 10);
+        assert_eq!(2, grid.magnitude_contours().len());
+        assert_eq!(1, grid.phase_contours().len());
+        assert_relative_eq!(-30., grid.phase_contours()[0].value());
+    }
+}
@@ -3,12 +3,22 @@
 //! Polar plot returns the iterator providing the complex numbers at the given
 //! angular frequencies.
 //!
+//! The inverse Nyquist plot, the polar plot of `1 / G(jw)`, is obtained
+//! plotting the [inverse](struct.Inverse.html) of the transfer function.
+//!
+//! The [circles](struct.Circle.html) of constant closed loop magnitude (M
+//! circles) and phase (N circles) can be overlaid to the polar plot of the
+//! open loop transfer function.
+//!
 //! Functions use angular frequencies as default inputs.
 
 use num_complex::Complex;
 use num_traits::{Float, FloatConst, MulAdd, Num};
 
-use crate::{plots::Plotter, units::RadiansPerSecond};
+use crate::{
+    plots::{exp10, frequency_grid, FrequencyPoint, Grid, Plotter, Refinement},
+    units::{Degrees, NormalizedFrequency, Radians, RadiansPerSecond},
+};
 
 /// Struct representing a Polar plot.
 #[derive(Clone, Debug)]
@@ -21,6 +31,10 @@ pub struct Polar<T: Num, U: Plotter<T>> {
     max_freq: RadiansPerSecond<T>,
     /// Step between frequencies
     step: T,
+    /// Adaptive refinement of the frequency grid
+    refinement: Option<Refinement<T>>,
+    /// Exponents of the angular frequencies of an explicit grid
+    grid: Option<Vec<T>>,
 }
 
 impl<T: Float + MulAdd<Output = T>, U: Plotter<T>> Polar<T, U> {
@@ -53,8 +67,63 @@ impl<T: Float + MulAdd<Output = T>, U: Plotter<T>> Polar<T, U> {
             min_freq,
             max_freq,
             step,
+            refinement: None,
+            grid: None,
         }
     }
+
+    /// Create a `Polar` plot struct evaluated at the given angular
+    /// frequencies, for example the frequencies of a measured frequency
+    /// response.
+    ///
+    /// # Arguments
+    ///
+    /// * `tf` - Transfer function to plot
+    /// * `frequencies` - Angular frequencies of the plot
+    ///
+    /// # Panics
+    ///
+    /// Panics if the frequencies are not strictly positive and strictly
+    /// increasing.
+    ///
+    /// # Example
+    /// ```
+    /// use au::{plots::polar::Polar, poly, RadiansPerSecond, Tf};
+    /// let tf = Tf::new(poly!(1.), poly!(1., 1.));
+    /// let freqs = vec![RadiansPerSecond(1.), RadiansPerSecond(2.5)];
+    /// let data: Vec<_> = Polar::new_from_frequencies(tf, freqs).into_iter().collect();
+    /// assert_eq!(2, data.len());
+    /// assert!((data[0].imag() + 0.5_f64).abs() < 1e-12);
+    /// ```
+    pub fn new_from_frequencies<I>(tf: U, frequencies: I) -> Self
+    where
+        I: IntoIterator<Item = RadiansPerSecond<T>>,
+    {
+        let (grid, step) = frequency_grid(frequencies);
+        let min = grid.first().map_or(T::one(), |&e| exp10(e));
+        let max = grid.last().map_or(T::one(), |&e| exp10(e));
+
+        Self {
+            tf,
+            min_freq: RadiansPerSecond(min),
+            max_freq: RadiansPerSecond(max),
+            step,
+            refinement: None,
+            grid: Some(grid),
+        }
+    }
+
+    /// Refine the frequency grid where the frequency response changes fast,
+    /// the step becomes the coarsest step between frequencies.
+    ///
+    /// # Arguments
+    ///
+    /// * `refinement` - Criteria for the refinement of the grid
+    #[must_use]
+    pub fn with_refinement(mut self, refinement: Refinement<T>) -> Self {
+        self.refinement = Some(refinement);
+        self
+    }
 }
 
 impl<T: Float + FloatConst + MulAdd<Output = T>, U: Plotter<T>> Polar<T, U> {
@@ -63,7 +132,7 @@ impl<T: Float + FloatConst + MulAdd<Output = T>, U: Plotter<T>> Polar<T, U> {
     /// # Arguments
     ///
     /// * `tf` - Transfer function to plot
-    /// * `min_freq` - Minimum angular frequency of the plot
+    /// * `min_freq` - Minimum normalized frequency of the plot
     /// * `step` - Step between frequencies
     ///
     /// `step` shall be in logarithmic scale. Use 0.1 to have 10 point per decade
@@ -72,20 +141,111 @@ impl<T: Float + FloatConst + MulAdd<Output = T>, U: Plotter<T>> Polar<T, U> {
     ///
     /// Panics if the step is not strictly positive of the minimum frequency
     /// is not lower than pi.
-    pub fn new_discrete(tf: U, min_freq: RadiansPerSecond<T>, step: T) -> Self {
+    pub fn new_discrete(tf: U, min_freq: NormalizedFrequency<T>, step: T) -> Self {
         let pi = RadiansPerSecond(T::PI());
         assert!(step > T::zero());
-        assert!(min_freq < pi);
+        assert!(min_freq.0 < pi.0);
 
         Self {
             tf,
-            min_freq,
+            min_freq: RadiansPerSecond(min_freq.0),
             max_freq: pi,
             step,
+            refinement: None,
+            grid: None,
         }
     }
 }
 
+/// Transfer function evaluated as its inverse, `1 / G`, used for inverse
+/// Nyquist plots.
+#[derive(Clone, Debug)]
+pub struct Inverse<U>(U);
+
+impl<U> Inverse<U> {
+    /// Create the inverse of the transfer function
+    ///
+    /// # Arguments
+    ///
+    /// * `tf` - Transfer function
+    pub fn new(tf: U) -> Self {
+        Self(tf)
+    }
+
+    /// Get the transfer function that is inverted
+    pub fn inner(&self) -> &U {
+        &self.0
+    }
+}
+
+impl<T: Float, U: Plotter<T>> Plotter<T> for Inverse<U> {
+    fn eval_point(&self, x: T) -> Complex<T> {
+        self.0.eval_point(x).inv()
+    }
+
+    fn phase_derivative(&self, x: T) -> T {
+        -self.0.phase_derivative(x)
+    }
+}
+
+impl<T: Float + MulAdd<Output = T>, U: Plotter<T>> Polar<T, Inverse<U>> {
+    /// Create an inverse Nyquist plot, the polar plot of `1 / G(jw)`
+    ///
+    /// # Arguments
+    ///
+    /// * `tf` - Transfer function to plot
+    /// * `min_freq` - Minimum angular frequency of the plot
+    /// * `max_freq` - Maximum angular frequency of the plot
+    /// * `step` - Step between frequencies
+    ///
+    /// `step` shall be in logarithmic scale. Use 0.1 to have 10 point per decade
+    ///
+    /// # Panics
+    ///
+    /// Panics if the step is not strictly positive of the minimum frequency
+    /// is not lower than the maximum frequency.
+    ///
+    /// # Example
+    /// ```
+    /// use au::{plots::polar::Polar, poly, RadiansPerSecond, Tf};
+    /// let tf = Tf::new(poly!(1.), poly!(0., 1., 1.));
+    /// let inverse = Polar::new_inverse(tf, RadiansPerSecond(1.), RadiansPerSecond(10.), 1.);
+    /// let last = inverse.into_iter().last().unwrap();
+    /// // 1 / G(10j) = -100 + 10j
+    /// assert!((last.real() + 100_f64).abs() < 1e-10);
+    /// assert!((last.imag() - 10_f64).abs() < 1e-10);
+    /// ```
+    pub fn new_inverse(
+        tf: U,
+        min_freq: RadiansPerSecond<T>,
+        max_freq: RadiansPerSecond<T>,
+        step: T,
+    ) -> Self {
+        Self::new(Inverse(tf), min_freq, max_freq, step)
+    }
+}
+
+impl<T: Float + FloatConst + MulAdd<Output = T>, U: Plotter<T>> Polar<T, Inverse<U>> {
+    /// Create an inverse Nyquist plot of a discrete system, the polar plot of
+    /// `1 / G(e^(jw))`
+    ///
+    /// # Arguments
+    ///
+    /// * `tf` - Transfer function to plot
+    /// * `min_freq` - Minimum normalized frequency of the plot
+    /// * `step` - Step between frequencies
+    ///
+    /// `step` shall be in logarithmic scale. Use 0.1 to have 10 point per decade
+    ///
+    /// # Panics
+    ///
+    /// Panics if the step is not strictly positive of the minimum frequency
+    /// is not lower than pi.
+    pub fn new_inverse_discrete(tf: U, min_freq: NormalizedFrequency<T>, step: T) -> Self {
+        Self::new_discrete(Inverse(tf), min_freq, step)
+    }
+}
+
 impl<T: Float + MulAdd<Output = T>, U: Plotter<T>> IntoIterator for Polar<T, U> {
     type Item = Data<T>;
     type IntoIter = IntoIter<T, U>;
@@ -93,13 +253,20 @@ impl<T: Float + MulAdd<Output = T>, U: Plotter<T>> IntoIterator for Polar<T, U>
     fn into_iter(self) -> Self::IntoIter {
         let min = self.min_freq.0.log10();
         let max = self.max_freq.0.log10();
-        let intervals = ((max - min) / self.step).floor();
+        let intervals = match &self.grid {
+            Some(grid) => T::from(grid.len()).unwrap() - T::one(),
+            None => ((max - min) / self.step).floor(),
+        };
         Self::IntoIter {
             tf: self.tf,
             intervals,
             step: self.step,
             base_freq_exp: min,
             index: T::zero(),
+            refinement: self.refinement,
+            grid: self.grid,
+            last: None,
+            pending: Vec::new(),
         }
     }
 }
@@ -117,6 +284,14 @@ pub struct IntoIter<T: Float + MulAdd<Output = T>, U: Plotter<T>> {
     base_freq_exp: T,
     /// Current data index
     index: T,
+    /// Adaptive refinement of the frequency grid
+    refinement: Option<Refinement<T>>,
+    /// Exponents of the angular frequencies of an explicit grid
+    grid: Option<Vec<T>>,
+    /// Last point of the refined grid
+    last: Option<(T, Complex<T>)>,
+    /// Points of the refined grid not yet returned
+    pending: Vec<(T, Complex<T>)>,
 }
 
 /// Struct to hold the data returned by the Polar iterator.
@@ -154,10 +329,49 @@ impl<T: Float> Data<T> {
         self.output.norm()
     }
 
-    /// Get the phase
+    /// Get the phase (rad)
     pub fn phase(&self) -> T {
         self.output.arg()
     }
+
+    /// Get the phase in radians
+    #[must_use]
+    pub fn phase_rad(&self) -> Radians<T> {
+        Radians(self.output.arg())
+    }
+
+    /// Get the phase in degrees
+    #[must_use]
+    pub fn phase_deg(&self) -> Degrees<T> {
+        Radians(self.output.arg()).into()
+    }
+
+    /// Get the distance of the output from the critical point -1,
+    /// `|1 + G(jw)|`.
+    ///
+    /// When the output is the loop transfer function, its minimum over the
+    /// frequencies is the vector margin.
+    #[must_use]
+    pub fn critical_distance(&self) -> T {
+        (self.output + T::one()).norm()
+    }
+
+    /// Get the magnitude of the sensitivity function, `1 / |1 + G(jw)|`,
+    /// the inverse of the distance from the critical point.
+    #[must_use]
+    pub fn sensitivity(&self) -> T {
+        self.critical_distance().recip()
+    }
+}
+
+impl<T: Float> FrequencyPoint<T> for Data<T> {
+    fn angular_frequency(&self) -> RadiansPerSecond<T> {
+        RadiansPerSecond(self.freq)
+    }
+
+    fn value(&self) -> Complex<T> {
+        self.output
+    }
 }
 
 /// Implementation of the Iterator trait for `Polar` struct
@@ -166,18 +380,219 @@ impl<T: Float + MulAdd<Output = T>, U: Plotter<T>> Iterator for IntoIter<T, U> {
     type Item = Data<T>;
 
     fn next(&mut self) -> Option<Self::Item> {
+        let (freq_exponent, output) = self.next_point()?;
+        Some(Data {
+            freq: exp10(freq_exponent),
+            output,
+        })
+    }
+}
+
+impl<T: Float + MulAdd<Output = T>, U: Plotter<T>> IntoIter<T, U> {
+    /// Consume the iterator and return the data point nearest to the
+    /// critical point -1, its distance from the critical point is the vector
+    /// margin of the loop transfer function on the frequency grid.
+    ///
+    /// It returns `None` if the plot has no data points.
+    ///
+    /// # Example
+    /// ```
+    /// use au::{plots::polar::Polar, poly, Poly, RadiansPerSecond, Tf};
+    /// let l = Tf::new(poly!(4.), Poly::new_from_roots(&[-1., -1., -1.]));
+    /// let polar = Polar::new(l, RadiansPerSecond(0.1), RadiansPerSecond(10.), 0.01);
+    /// let nearest = polar.into_iter().vector_margin().unwrap();
+    /// assert!(nearest.critical_distance() < 0.6);
+    /// assert!(nearest.sensitivity() > 1.);
+    /// ```
+    pub fn vector_margin(self) -> Option<Data<T>> {
+        self.fold(None, |nearest: Option<Data<T>>, d| match nearest {
+            Some(n) if n.critical_distance() <= d.critical_distance() => Some(n),
+            _ => Some(d),
+        })
+    }
+
+    /// Collect the data points into the parallel arrays of the angular
+    /// frequencies, the real parts and the imaginary parts.
+    ///
+    /// # Example
+    /// ```
+    /// use au::{plots::polar::Polar, poly, RadiansPerSecond, Tf};
+    /// let tf = Tf::new(poly!(1.), poly!(0., 1.));
+    /// let polar = Polar::new(tf, RadiansPerSecond(1.), RadiansPerSecond(10.), 1.);
+    /// let (freq, real, imag) = polar.into_iter().into_vecs();
+    /// assert_eq!(vec![1., 10.], freq);
+    /// assert_eq!(vec![0., 0.], real);
+    /// assert_eq!(vec![-1., -0.1], imag);
+    /// ```
+    #[must_use]
+    pub fn into_vecs(self) -> (Vec<T>, Vec<T>, Vec<T>) {
+        let mut arrays = (Vec::new(), Vec::new(), Vec::new());
+        for d in self {
+            arrays.0.push(d.freq);
+            arrays.1.push(d.output.re);
+            arrays.2.push(d.output.im);
+        }
+        arrays
+    }
+
+    /// Get the exponent of the next angular frequency and the value of the
+    /// frequency response.
+    fn next_point(&mut self) -> Option<(T, Complex<T>)> {
+        let point = match (self.refinement, self.last) {
+            (Some(refinement), Some(last)) => {
+                let step = self.step;
+                let mut pending = std::mem::take(&mut self.pending);
+                let point = refinement.next_point(self, step, &last, &mut pending);
+                self.pending = pending;
+                point?
+            }
+            _ => self.coarse_point()?,
+        };
+        if self.refinement.is_some() {
+            self.last = Some(point);
+        }
+        Some(point)
+    }
+}
+
+impl<T: Float + MulAdd<Output = T>, U: Plotter<T>> Grid<T, Complex<T>> for IntoIter<T, U> {
+    /// Get the next point of the coarse frequency grid.
+    fn coarse_point(&mut self) -> Option<(T, Complex<T>)> {
         if self.index > self.intervals {
             None
         } else {
-            let freq_exponent = MulAdd::mul_add(self.step, self.index, self.base_freq_exp);
-            // Casting is safe for both f32 and f64, representation is exact.
-            let omega = T::from(10.0_f32).unwrap().powf(freq_exponent);
+            let freq_exponent = match &self.grid {
+                Some(grid) => grid[self.index.to_usize().unwrap()],
+                None => MulAdd::mul_add(self.step, self.index, self.base_freq_exp),
+            };
             self.index = self.index + T::one();
-            Some(Data {
-                freq: omega,
-                output: self.tf.eval_point(omega),
-            })
+            Some((freq_exponent, self.eval(freq_exponent)))
+        }
+    }
+
+    /// Evaluate the frequency response at the exponent of the angular
+    /// frequency.
+    fn eval(&self, freq_exponent: T) -> Complex<T> {
+        self.tf.eval_point(exp10(freq_exponent))
+    }
+}
+
+/// Circle of the complex plane, locus of constant closed loop magnitude or
+/// phase of the unity feedback `T = L / (1 + L)` of the open loop `L`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Circle<T> {
+    /// Center of the circle
+    center: Complex<T>,
+    /// Radius of the circle
+    radius: T,
+}
+
+impl<T: Float + FloatConst> Circle<T> {
+    /// Circle of constant closed loop magnitude (M circle).
+    ///
+    /// The closed loop bandwidth is the frequency at which the polar plot of
+    /// the open loop crosses the circle with `M = 1 / sqrt(2)`.
+    ///
+    /// It returns `None` if the magnitude is 1, the locus is the line with
+    /// real part -1/2.
+    ///
+    /// # Arguments
+    ///
+    /// * `magnitude` - Closed loop magnitude (absolute value)
+    ///
+    /// # Panics
+    ///
+    /// Panics if the magnitude is not strictly positive.
+    ///
+    /// # Example
+    /// ```
+    /// use au::{num_complex::Complex, plots::polar::Circle};
+    /// let m = Circle::constant_magnitude(2.).unwrap();
+    /// assert_eq!(Complex::new(-4. / 3., 0.), m.center());
+    /// assert_eq!(2. / 3., m.radius());
+    /// ```
+    #[must_use]
+    pub fn constant_magnitude(magnitude: T) -> Option<Self> {
+        assert!(magnitude > T::zero(), "Magnitude must be strictly positive");
+        let m2 = magnitude * magnitude;
+        let den = m2 - T::one();
+        if den == T::zero() {
+            return None;
         }
+        Some(Self {
+            center: Complex::new(-m2 / den, T::zero()),
+            radius: (magnitude / den).abs(),
+        })
+    }
+
+    /// Circle of constant closed loop phase (N circle).
+    ///
+    /// The circle is the locus of the closed loop phases `phase` and
+    /// `phase + pi`, it passes through the origin and the critical point -1.
+    ///
+    /// It returns `None` if the phase is a multiple of pi, the locus is the
+    /// real axis.
+    ///
+    /// # Arguments
+    ///
+    /// * `phase` - Closed loop phase (rad)
+    ///
+    /// # Example
+    /// ```
+    /// use au::{num_complex::Complex, plots::polar::Circle};
+    /// let n = Circle::constant_phase(-std::f64::consts::FRAC_PI_4).unwrap();
+    /// assert!((n.center() - Complex::new(-0.5, -0.5)).norm() < 1e-12);
+    /// assert!((n.radius() - 0.5_f64.sqrt()).abs() < 1e-12);
+    /// ```
+    #[must_use]
+    pub fn constant_phase(phase: T) -> Option<Self> {
+        let n = phase.tan();
+        if n.abs() <= T::epsilon() {
+            return None;
+        }
+        let two = T::one() + T::one();
+        let half = T::one() / two;
+        Some(Self {
+            center: Complex::new(-half, (two * n).recip()),
+            radius: (half * half + (two * n).powi(2).recip()).sqrt(),
+        })
+    }
+
+    /// Get the center of the circle
+    #[must_use]
+    pub fn center(&self) -> Complex<T> {
+        self.center
+    }
+
+    /// Get the radius of the circle
+    #[must_use]
+    pub fn radius(&self) -> T {
+        self.radius
+    }
+
+    /// Points of the circle, counterclockwise from the point with the
+    /// largest real part. The first point is repeated at the end to close
+    /// the curve.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - Number of points, excluding the closing one
+    ///
+    /// # Example
+    /// ```
+    /// use au::plots::polar::Circle;
+    /// let m = Circle::constant_magnitude(0.5).unwrap();
+    /// let points = m.points(100);
+    /// assert_eq!(101, points.len());
+    /// assert_eq!(points[0], points[100]);
+    /// ```
+    #[must_use]
+    pub fn points(&self, n: usize) -> Vec<Complex<T>> {
+        let step = T::TAU() / T::from(n.max(1)).unwrap();
+        (0..n)
+            .map(|i| self.center + Complex::from_polar(self.radius, step * T::from(i).unwrap()))
+            .chain(std::iter::once(self.center + self.radius))
+            .collect()
     }
 }
 
@@ -201,7 +616,7 @@ mod tests {
     #[test]
     fn create_discrete() {
         let tf = Tfz::new(poly!(2., 3.), poly!(1., 1., 1.));
-        let iter = Polar::new_discrete(tf, RadiansPerSecond(0.01), 0.1).into_iter();
+        let iter = Polar::new_discrete(tf, NormalizedFrequency(0.01), 0.1).into_iter();
         assert!(iter.last().unwrap().freq() <= std::f32::consts::PI);
     }
 
@@ -218,6 +633,156 @@ mod tests {
         assert_relative_eq!(4., p.imag());
         assert_relative_eq!(5., p.magnitude());
         assert_relative_eq!(0.9273, p.phase(), max_relative = 0.00001);
+        assert_relative_eq!(53.1301, p.phase_deg().0, max_relative = 0.00001);
+        assert_eq!(Radians(p.phase()), p.phase_rad());
+        assert_relative_eq!(32_f64.sqrt(), p.critical_distance());
+        assert_relative_eq!(1. / 32_f64.sqrt(), p.sensitivity());
+    }
+
+    #[test]
+    fn explicit_frequencies() {
+        let tf = Tf::new(poly!(1.), poly!(1., 1.));
+        let freqs = [0.05, 0.2, 3., 70.];
+        let polar =
+            Polar::new_from_frequencies(tf.clone(), freqs.iter().map(|&w| RadiansPerSecond(w)));
+        let data: Vec<_> = polar.into_iter().collect();
+        assert_eq!(4, data.len());
+        for (d, &w) in data.iter().zip(&freqs) {
+            assert_relative_eq!(w, d.freq(), max_relative = 1e-12);
+            assert_relative_eq!(tf.eval_point(w).re, d.real(), max_relative = 1e-12);
+        }
+
+        let empty = Polar::new_from_frequencies(tf, Vec::new());
+        assert_eq!(0, empty.into_iter().count());
+    }
+
+    #[test]
+    fn explicit_frequencies_refined() {
+        let tf = Tf::new(poly!(1.), poly!(1., 1.));
+        let freqs = vec![RadiansPerSecond(0.1), RadiansPerSecond(10.)];
+        let polar =
+            Polar::new_from_frequencies(tf, freqs).with_refinement(Refinement::new(1., 0.1, 5));
+        let data: Vec<_> = polar.into_iter().collect();
+        assert!(data.len() > 2);
+        assert_relative_eq!(0.1, data[0].freq(), max_relative = 1e-12);
+        assert_relative_eq!(10., data.last().unwrap().freq(), max_relative = 1e-12);
+    }
+
+    /// Closed loop frequency response of the unity feedback.
+    fn closed_loop(l: Complex<f64>) -> Complex<f64> {
+        l / (l + 1.)
+    }
+
+    #[test]
+    fn constant_magnitude_circles() {
+        for &m in &[0.1, 0.5, std::f64::consts::FRAC_1_SQRT_2, 1.3, 4.] {
+            let circle = Circle::constant_magnitude(m).unwrap();
+            for p in circle.points(36) {
+                assert_relative_eq!(m, closed_loop(p).norm(), max_relative = 1e-10);
+            }
+        }
+        assert_eq!(None, Circle::constant_magnitude(1.));
+    }
+
+    #[test]
+    #[should_panic]
+    fn constant_magnitude_fail() {
+        let _ = Circle::constant_magnitude(0.);
+    }
+
+    #[test]
+    fn constant_phase_circles() {
+        for &deg in &[-10_f64, -45., -90., -150., 30.] {
+            let phase = deg.to_radians();
+            let circle = Circle::constant_phase(phase).unwrap();
+            for p in circle.points(24).into_iter().skip(1).take(22) {
+                if (p + 1.).norm() < 1e-6 || p.norm() < 1e-6 {
+                    continue;
+                }
+                let diff = (closed_loop(p).arg() - phase) / std::f64::consts::PI;
+                assert_relative_eq!(0., diff - diff.round(), epsilon = 1e-10);
+            }
+        }
+        assert_eq!(None, Circle::constant_phase(0.));
+    }
+
+    #[test]
+    fn bandwidth_from_m_circle() {
+        // L(s) = 1 / s, closed loop 1 / (s + 1), bandwidth 1 rad/s.
+        let l = Tf::new(poly!(1.), poly!(0., 1.));
+        let circle = Circle::constant_magnitude(std::f64::consts::FRAC_1_SQRT_2).unwrap();
+        let polar = Polar::new(l, RadiansPerSecond(0.1), RadiansPerSecond(10.), 0.01);
+        let crossing = polar
+            .into_iter()
+            .find(|d| (d.output() - circle.center()).norm() <= circle.radius())
+            .unwrap();
+        assert_relative_eq!(1., crossing.freq(), max_relative = 0.03);
+    }
+
+    #[test]
+    fn inverse_nyquist() {
+        let tf = Tf::new(poly!(2., 1.), poly!(1., 3., 1.));
+        let direct = Polar::new(
+            tf.clone(),
+            RadiansPerSecond(0.1),
+            RadiansPerSecond(10.),
+            0.1,
+        );
+        let inverse = Polar::new_inverse(tf, RadiansPerSecond(0.1), RadiansPerSecond(10.), 0.1);
+        let mut count = 0;
+        for (d, i) in direct.into_iter().zip(inverse) {
+            assert_relative_eq!(d.freq(), i.freq());
+            assert_relative_eq!(1., (d.output() * i.output()).re, max_relative = 1e-12);
+            assert_relative_eq!(0., (d.output() * i.output()).im, epsilon = 1e-12);
+            count += 1;
+        }
+        assert_eq!(21, count);
+    }
+
+    #[test]
+    fn inverse_nyquist_discrete() {
+        let tf = Tfz::new(poly!(1.), poly!(-0.5, 1.));
+        let inverse = Polar::new_inverse_discrete(tf.clone(), NormalizedFrequency(0.01), 0.1);
+        assert_relative_eq!(
+            -tf.phase_derivative(0.3),
+            Inverse::new(tf).phase_derivative(0.3)
+        );
+        for i in inverse {
+            // 1 / G(z) = z - 0.5
+            let z = Complex::from_polar(1., i.freq());
+            assert_relative_eq!((z - 0.5).re, i.real(), epsilon = 1e-12);
+            assert_relative_eq!((z - 0.5).im, i.imag(), epsilon = 1e-12);
+        }
+    }
+
+    #[test]
+    fn vector_margin() {
+        // L(s) = 2 / (s + 1)^2, |1 + L|^2 = (u^2 - 2u + 9) / (1 + u)^2 with
+        // u = w^2, it is minimum for u = 5 where it is equal to 2/3.
+        let l = Tf::new(poly!(2.), poly!(1., 2., 1.));
+        let polar = Polar::new(l, RadiansPerSecond(0.1), RadiansPerSecond(10.), 0.001);
+        let nearest = polar.into_iter().vector_margin().unwrap();
+        assert_relative_eq!(5_f64.sqrt(), nearest.freq(), max_relative = 1e-2);
+        let margin = (2. / 3_f64).sqrt();
+        assert_relative_eq!(margin, nearest.critical_distance(), max_relative = 1e-4);
+    }
+
+    #[test]
+    fn refined_grid() {
+        let tf = Tf::new(poly!(100.), poly!(100., 0.2, 1.));
+        let polar = Polar::new(tf, RadiansPerSecond(1.), RadiansPerSecond(100.), 0.5)
+            .with_refinement(Refinement::new(1., 0.1, 12));
+        let refined: Vec<_> = polar.into_iter().collect();
+        assert!(refined.len() > 5);
+        assert!(refined.windows(2).all(|w| w[0].freq() < w[1].freq()));
+        assert_relative_eq!(1., refined[0].freq());
+        assert_relative_eq!(100., refined.last().unwrap().freq());
+        for w in refined.windows(2) {
+            let ratio = w[1].magnitude() / w[0].magnitude();
+            let phase = (w[1].output() / w[0].output()).arg();
+            let min_step = w[1].freq() / w[0].freq() < 1.001;
+            assert!(min_step || (20. * ratio.log10()).abs() <= 1. && phase.abs() <= 0.1);
+        }
     }
 
     #[test]
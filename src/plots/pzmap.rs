@@ -0,0 +1,251 @@
+//! # Pole-zero map
+//!
+//! Location of the poles and of the zeros of a system in the complex plane,
+//! together with the boundary of the stability region, the imaginary axis for
+//! continuous systems and the unit circle for discrete systems.
+//!
+//! Coincident roots are merged into a single point with their multiplicity.
+
+use num_complex::Complex;
+use num_traits::{Float, FloatConst};
+
+/// Kind of a point of the pole-zero map
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Kind {
+    /// Pole of the system
+    Pole,
+    /// Zero of the system
+    Zero,
+}
+
+/// Boundary of the stability region
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Boundary {
+    /// Imaginary axis, stability boundary of continuous systems
+    ImaginaryAxis,
+    /// Unit circle, stability boundary of discrete systems
+    UnitCircle,
+}
+
+/// Point of the pole-zero map
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Point<T> {
+    /// Kind of the point
+    kind: Kind,
+    /// Location in the complex plane
+    value: Complex<T>,
+    /// Number of coincident roots
+    multiplicity: usize,
+}
+
+impl<T: Copy> Point<T> {
+    /// Get the kind of the point
+    #[must_use]
+    pub fn kind(&self) -> Kind {
+        self.kind
+    }
+
+    /// Get the location of the point in the complex plane
+    #[must_use]
+    pub fn value(&self) -> Complex<T> {
+        self.value
+    }
+
+    /// Get the number of coincident roots
+    #[must_use]
+    pub fn multiplicity(&self) -> usize {
+        self.multiplicity
+    }
+}
+
+/// Struct for the pole-zero map
+#[derive(Clone, Debug, PartialEq)]
+pub struct PzMap<T> {
+    /// Poles and zeros
+    points: Vec<Point<T>>,
+    /// Boundary of the stability region
+    boundary: Boundary,
+}
+
+impl<T: Float> PzMap<T> {
+    /// Create a pole-zero map.
+    ///
+    /// Roots closer than the cubic root of the machine epsilon, relative to
+    /// their magnitude, are considered coincident.
+    ///
+    /// # Arguments
+    ///
+    /// * `poles` - Poles of the system
+    /// * `zeros` - Zeros of the system
+    /// * `boundary` - Boundary of the stability region
+    pub(crate) fn new(poles: &[Complex<T>], zeros: &[Complex<T>], boundary: Boundary) -> Self {
+        let mut points = group(poles, Kind::Pole);
+        points.extend(group(zeros, Kind::Zero));
+        Self { points, boundary }
+    }
+
+    /// Get the poles and the zeros
+    #[must_use]
+    pub fn points(&self) -> &[Point<T>] {
+        &self.points
+    }
+
+    /// Iterator over the poles
+    pub fn poles(&self) -> impl Iterator<Item = &Point<T>> {
+        self.points.iter().filter(|p| p.kind == Kind::Pole)
+    }
+
+    /// Iterator over the zeros
+    pub fn zeros(&self) -> impl Iterator<Item = &Point<T>> {
+        self.points.iter().filter(|p| p.kind == Kind::Zero)
+    }
+
+    /// Get the boundary of the stability region
+    #[must_use]
+    pub fn boundary(&self) -> Boundary {
+        self.boundary
+    }
+}
+
+impl<T: Float + FloatConst> PzMap<T> {
+    /// Sample the boundary of the stability region with `n` equally spaced
+    /// points.
+    ///
+    /// The unit circle is a closed line, the first and the last point
+    /// coincide. The imaginary axis is sampled symmetrically in the interval
+    /// that contains the imaginary part of all the points of the map,
+    /// enlarged by 10%, and at least in the interval [-1, 1].
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - Number of points
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of points is less than two.
+    ///
+    /// # Example
+    /// ```
+    /// use au::{poly, Poly, Tfz};
+    /// let tfz = Tfz::new(poly!(1.), Poly::new_from_roots(&[0.5, 0.2]));
+    /// let circle = tfz.pzmap().boundary_points(5);
+    /// assert_eq!(5, circle.len());
+    /// assert!((circle[2].re + 1_f64).abs() < 1e-15);
+    /// ```
+    #[must_use]
+    pub fn boundary_points(&self, n: usize) -> Vec<Complex<T>> {
+        assert!(n > 1, "At least two points are required");
+        let last = T::from(n - 1).unwrap();
+        match self.boundary {
+            Boundary::ImaginaryAxis => {
+                let extent = self
+                    .points
+                    .iter()
+                    .map(|p| p.value.im.abs())
+                    .fold(T::zero(), Float::max);
+                let r = Float::max(T::one(), extent * T::from(1.1).unwrap());
+                (0..n)
+                    .map(|i| {
+                        let t = T::from(i).unwrap() / last;
+                        Complex::new(T::zero(), r * (t + t - T::one()))
+                    })
+                    .collect()
+            }
+            Boundary::UnitCircle => (0..n)
+                .map(|i| {
+                    let theta = T::TAU() * T::from(i).unwrap() / last;
+                    Complex::from_polar(T::one(), theta)
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Merge coincident roots into points with multiplicity.
+///
+/// The location of a point is the mean of the merged roots.
+fn group<T: Float>(roots: &[Complex<T>], kind: Kind) -> Vec<Point<T>> {
+    let tol = T::epsilon().cbrt();
+    let mut clusters: Vec<(Complex<T>, Vec<Complex<T>>)> = Vec::new();
+    for &r in roots {
+        let close = clusters
+            .iter_mut()
+            .find(|(first, _)| (*first - r).norm() <= tol * Float::max(T::one(), r.norm()));
+        match close {
+            Some((_, members)) => members.push(r),
+            None => clusters.push((r, vec![r])),
+        }
+    }
+    clusters
+        .into_iter()
+        .map(|(_, members)| {
+            let m = T::from(members.len()).unwrap();
+            let sum = members
+                .iter()
+                .fold(Complex::new(T::zero(), T::zero()), |acc, &x| acc + x);
+            Point {
+                kind,
+                value: sum / m,
+                multiplicity: members.len(),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn group_coincident_roots() {
+        let roots = [
+            Complex::new(-1., 0.),
+            Complex::new(-2., 1.),
+            Complex::new(-1. + 1e-9, 0.),
+            Complex::new(-2., -1.),
+        ];
+        let map = PzMap::new(&roots, &[Complex::new(3., 0.)], Boundary::ImaginaryAxis);
+        assert_eq!(4, map.points().len());
+        assert_eq!(3, map.poles().count());
+        assert_eq!(1, map.zeros().count());
+        let double = map.poles().next().unwrap();
+        assert_eq!(2, double.multiplicity());
+        assert_eq!(Kind::Pole, double.kind());
+        assert_relative_eq!(-1. + 0.5e-9, double.value().re);
+        let zero = map.zeros().next().unwrap();
+        assert_eq!(1, zero.multiplicity());
+        assert_eq!(Complex::new(3., 0.), zero.value());
+    }
+
+    #[test]
+    fn imaginary_axis_boundary() {
+        let map = PzMap::new(&[Complex::new(-1., 5.)], &[], Boundary::ImaginaryAxis);
+        assert_eq!(Boundary::ImaginaryAxis, map.boundary());
+        let axis = map.boundary_points(3);
+        assert_relative_eq!(-5.5, axis[0].im);
+        assert_relative_eq!(0., axis[1].im);
+        assert_relative_eq!(5.5, axis[2].im);
+        assert!(axis.iter().all(|p| p.re == 0.));
+
+        let map = PzMap::<f32>::new(&[], &[], Boundary::ImaginaryAxis);
+        let axis = map.boundary_points(2);
+        assert_relative_eq!(-1., axis[0].im);
+        assert_relative_eq!(1., axis[1].im);
+    }
+
+    #[test]
+    fn unit_circle_boundary() {
+        let map = PzMap::<f64>::new(&[], &[], Boundary::UnitCircle);
+        let circle = map.boundary_points(9);
+        assert!(circle.iter().all(|p| (p.norm() - 1.).abs() < 1e-15));
+        assert_relative_eq!(circle[0].re, circle[8].re);
+        assert_relative_eq!(circle[0].im, circle[8].im, epsilon = 1e-15);
+    }
+
+    #[test]
+    #[should_panic]
+    fn boundary_points_fail() {
+        let map = PzMap::<f64>::new(&[], &[], Boundary::UnitCircle);
+        let _ = map.boundary_points(1);
+    }
+}
@@ -0,0 +1,552 @@
+//! # Rendering of plots
+//!
+//! Rendering of Bode, Nyquist, pole-zero map and step response figures to
+//! image files using the `plotters` crate, it requires the `plotters` feature.
+//!
+//! The format of the image is chosen from the extension of the file: `png`
+//! for bitmap images and `svg` for vector images.
+
+use nalgebra::Scalar;
+use num_traits::{Float, FloatConst, MulAdd};
+use plotters::{coord::Shift, prelude::*};
+
+use std::{ffi::OsStr, ops::Range, path::Path};
+
+use crate::{
+    error::{Error, ErrorKind},
+    linear_system::solver::TimeResponse,
+    plots::{bode::Bode, polar::Polar, pzmap::PzMap, Plotter},
+};
+
+/// Result of the drawing of a figure on a backend
+type DrawResult<DB> = Result<(), DrawingAreaErrorKind<<DB as DrawingBackend>::ErrorType>>;
+
+/// Font of the captions of the figures
+const CAPTION_FONT: (&str, u32) = ("sans-serif", 20);
+
+/// Number of points used to draw the stability boundary of pole-zero maps
+const BOUNDARY_POINTS: usize = 200;
+
+/// Figure that can be drawn on any backend.
+trait Figure {
+    /// Draw the figure on the given drawing area.
+    ///
+    /// # Arguments
+    ///
+    /// * `root` - drawing area that contains the whole figure
+    fn draw<DB: DrawingBackend>(&self, root: &DrawingArea<DB, Shift>) -> DrawResult<DB>;
+}
+
+/// Render a Bode plot, with the magnitude in decibels and the unwrapped
+/// phase in degrees.
+///
+/// # Arguments
+///
+/// * `plot` - Bode plot
+/// * `path` - Image file, `png` or `svg`
+/// * `size` - Width and height of the image in pixels
+///
+/// # Errors
+///
+/// It returns an error if the format of the file is not supported or if
+/// the rendering fails.
+///
+/// # Example
+/// ```
+/// use au::{plots::{bode::Bode, render}, poly, RadiansPerSecond, Tf};
+/// let tf = Tf::new(poly!(1.), poly!(1., 1.));
+/// let bode = Bode::new(tf, RadiansPerSecond(0.1), RadiansPerSecond(10.), 0.1);
+/// let path = std::env::temp_dir().join("au_bode_example.svg");
+/// render::bode(bode, &path, (640, 480)).unwrap();
+/// assert!(path.exists());
+/// # std::fs::remove_file(&path).unwrap();
+/// ```
+pub fn bode<T, U, P>(plot: Bode<T, U>, path: P, size: (u32, u32)) -> Result<(), Error>
+where
+    T: Float + FloatConst + MulAdd<Output = T>,
+    U: Plotter<T>,
+    P: AsRef<Path>,
+{
+    let points = plot
+        .into_iter()
+        .into_unwrapped()
+        .map(|d| {
+            (
+                to_f64(d.angular_frequency().0),
+                20. * to_f64(d.magnitude()).log10(),
+                to_f64(d.phase()).to_degrees(),
+            )
+        })
+        .collect();
+    render(&BodeFigure { points }, path.as_ref(), size)
+}
+
+/// Render a Nyquist plot, the polar plot for positive frequencies together
+/// with its mirror image for negative frequencies. The critical point -1 is
+/// marked.
+///
+/// # Arguments
+///
+/// * `plot` - Polar plot
+/// * `path` - Image file, `png` or `svg`
+/// * `size` - Width and height of the image in pixels
+///
+/// # Errors
+///
+/// It returns an error if the format of the file is not supported or if
+/// the rendering fails.
+///
+/// # Example
+/// ```
+/// use au::{plots::{polar::Polar, render}, poly, Poly, RadiansPerSecond, Tf};
+/// let tf = Tf::new(poly!(1.), Poly::new_from_roots(&[-1., -2.]));
+/// let polar = Polar::new(tf, RadiansPerSecond(0.01), RadiansPerSecond(100.), 0.05);
+/// let path = std::env::temp_dir().join("au_nyquist_example.svg");
+/// render::nyquist(polar, &path, (640, 480)).unwrap();
+/// assert!(path.exists());
+/// # std::fs::remove_file(&path).unwrap();
+/// ```
+pub fn nyquist<T, U, P>(plot: Polar<T, U>, path: P, size: (u32, u32)) -> Result<(), Error>
+where
+    T: Float + MulAdd<Output = T>,
+    U: Plotter<T>,
+    P: AsRef<Path>,
+{
+    let points = plot
+        .into_iter()
+        .map(|d| (to_f64(d.real()), to_f64(d.imag())))
+        .collect();
+    render(&NyquistFigure { points }, path.as_ref(), size)
+}
+
+/// Render a pole-zero map, the poles are marked with crosses and the zeros
+/// with circles, the multiplicity of coincident roots is written next to
+/// them. The boundary of the stability region is drawn.
+///
+/// # Arguments
+///
+/// * `map` - Pole-zero map
+/// * `path` - Image file, `png` or `svg`
+/// * `size` - Width and height of the image in pixels
+///
+/// # Errors
+///
+/// It returns an error if the format of the file is not supported or if
+/// the rendering fails.
+///
+/// # Example
+/// ```
+/// use au::{plots::render, poly, Poly, Tf};
+/// let tf = Tf::new(poly!(1., 1.), Poly::new_from_roots(&[-2., -3.]));
+/// let path = std::env::temp_dir().join("au_pzmap_example.svg");
+/// render::pzmap(&tf.pzmap(), &path, (480, 480)).unwrap();
+/// assert!(path.exists());
+/// # std::fs::remove_file(&path).unwrap();
+/// ```
+pub fn pzmap<T, P>(map: &PzMap<T>, path: P, size: (u32, u32)) -> Result<(), Error>
+where
+    T: Float + FloatConst,
+    P: AsRef<Path>,
+{
+    let complex = |p: &crate::plots::pzmap::Point<T>| {
+        (
+            (to_f64(p.value().re), to_f64(p.value().im)),
+            p.multiplicity(),
+        )
+    };
+    let figure = PzMapFigure {
+        poles: map.poles().map(complex).collect(),
+        zeros: map.zeros().map(complex).collect(),
+        boundary: map
+            .boundary_points(BOUNDARY_POINTS)
+            .iter()
+            .map(|c| (to_f64(c.re), to_f64(c.im)))
+            .collect(),
+    };
+    render(&figure, path.as_ref(), size)
+}
+
+/// Render a step response, or any other time response, with a line for each
+/// output of the system.
+///
+/// # Arguments
+///
+/// * `response` - Time response
+/// * `path` - Image file, `png` or `svg`
+/// * `size` - Width and height of the image in pixels
+///
+/// # Errors
+///
+/// It returns an error if the format of the file is not supported or if
+/// the rendering fails.
+///
+/// # Example
+/// ```
+/// use au::{linear_system::solver::TimeResponse, plots::render, Seconds, Ss};
+/// let sys = Ss::new_from_slice(1, 1, 1, &[-1.], &[1.], &[1.], &[0.]);
+/// let resp: TimeResponse<_> = sys.rk4(|_| vec![1.], &[0.], Seconds(0.01), 500).collect();
+/// let path = std::env::temp_dir().join("au_step_example.svg");
+/// render::step(&resp, &path, (640, 480)).unwrap();
+/// assert!(path.exists());
+/// # std::fs::remove_file(&path).unwrap();
+/// ```
+pub fn step<T, P>(response: &TimeResponse<T>, path: P, size: (u32, u32)) -> Result<(), Error>
+where
+    T: Float + Scalar,
+    P: AsRef<Path>,
+{
+    let time: Vec<_> = response.time().iter().map(|t| to_f64(t.0)).collect();
+    let outputs = (0..response.outputs().ncols())
+        .map(|i| {
+            time.iter()
+                .zip(response.output(i))
+                .map(|(&t, &y)| (t, to_f64(y)))
+                .collect()
+        })
+        .collect();
+    render(&StepFigure { outputs }, path.as_ref(), size)
+}
+
+/// Render the figure to the image file, choosing the backend from the
+/// extension of the file.
+///
+/// # Arguments
+///
+/// * `figure` - Figure to render
+/// * `path` - Image file, `png` or `svg`
+/// * `size` - Width and height of the image in pixels
+fn render<F: Figure>(figure: &F, path: &Path, size: (u32, u32)) -> Result<(), Error> {
+    let extension = path
+        .extension()
+        .and_then(OsStr::to_str)
+        .map(str::to_ascii_lowercase);
+    match extension.as_deref() {
+        Some("png") => {
+            let root = BitMapBackend::new(path, size).into_drawing_area();
+            figure
+                .draw(&root)
+                .and_then(|_| root.present())
+                .map_err(Error::new_rendering)
+        }
+        Some("svg") => {
+            let root = SVGBackend::new(path, size).into_drawing_area();
+            figure
+                .draw(&root)
+                .and_then(|_| root.present())
+                .map_err(Error::new_rendering)
+        }
+        _ => Err(Error::new_internal(ErrorKind::UnsupportedFileFormat)),
+    }
+}
+
+/// Bode plot data: angular frequency, magnitude (dB) and phase (degrees)
+struct BodeFigure {
+    points: Vec<(f64, f64, f64)>,
+}
+
+impl Figure for BodeFigure {
+    fn draw<DB: DrawingBackend>(&self, root: &DrawingArea<DB, Shift>) -> DrawResult<DB> {
+        root.fill(&WHITE)?;
+        let root = root.titled("Bode plot", CAPTION_FONT)?;
+        let (upper, lower) = root.split_vertically(root.dim_in_pixel().1 / 2);
+        let freq = log_bounds(self.points.iter().map(|p| p.0));
+
+        let mut magnitude = ChartBuilder::on(&upper)
+            .margin(10)
+            .x_label_area_size(30)
+            .y_label_area_size(60)
+            .build_cartesian_2d(
+                freq.clone().log_scale(),
+                bounds(self.points.iter().map(|p| p.1)),
+            )?;
+        magnitude.configure_mesh().y_desc("Magnitude (dB)").draw()?;
+        magnitude.draw_series(LineSeries::new(
+            finite(self.points.iter().map(|p| (p.0, p.1))),
+            BLUE,
+        ))?;
+
+        let mut phase = ChartBuilder::on(&lower)
+            .margin(10)
+            .x_label_area_size(40)
+            .y_label_area_size(60)
+            .build_cartesian_2d(freq.log_scale(), bounds(self.points.iter().map(|p| p.2)))?;
+        phase
+            .configure_mesh()
+            .x_desc("Angular frequency (rad/s)")
+            .y_desc("Phase (deg)")
+            .draw()?;
+        phase.draw_series(LineSeries::new(
+            finite(self.points.iter().map(|p| (p.0, p.2))),
+            BLUE,
+        ))?;
+        Ok(())
+    }
+}
+
+/// Nyquist plot data: real and imaginary part for positive frequencies
+struct NyquistFigure {
+    points: Vec<(f64, f64)>,
+}
+
+impl Figure for NyquistFigure {
+    fn draw<DB: DrawingBackend>(&self, root: &DrawingArea<DB, Shift>) -> DrawResult<DB> {
+        root.fill(&WHITE)?;
+        let re = self.points.iter().map(|p| p.0).chain(Some(-1.));
+        let im = self
+            .points
+            .iter()
+            .flat_map(|p| vec![p.1, -p.1])
+            .chain(Some(0.));
+        let mut chart = ChartBuilder::on(root)
+            .caption("Nyquist plot", CAPTION_FONT)
+            .margin(10)
+            .x_label_area_size(40)
+            .y_label_area_size(60)
+            .build_cartesian_2d(bounds(re), bounds(im))?;
+        chart
+            .configure_mesh()
+            .x_desc("Real")
+            .y_desc("Imaginary")
+            .draw()?;
+        chart.draw_series(LineSeries::new(finite(self.points.iter().copied()), BLUE))?;
+        chart.draw_series(LineSeries::new(
+            finite(self.points.iter().map(|p| (p.0, -p.1))),
+            BLUE.mix(0.4),
+        ))?;
+        chart.draw_series(Some(Cross::new((-1., 0.), 6, RED)))?;
+        Ok(())
+    }
+}
+
+/// Pole-zero map data: location and multiplicity of poles and zeros and
+/// the points of the stability boundary
+struct PzMapFigure {
+    poles: Vec<((f64, f64), usize)>,
+    zeros: Vec<((f64, f64), usize)>,
+    boundary: Vec<(f64, f64)>,
+}
+
+impl Figure for PzMapFigure {
+    fn draw<DB: DrawingBackend>(&self, root: &DrawingArea<DB, Shift>) -> DrawResult<DB> {
+        root.fill(&WHITE)?;
+        let points = || {
+            self.poles
+                .iter()
+                .chain(&self.zeros)
+                .map(|p| p.0)
+                .chain(self.boundary.iter().copied())
+        };
+        let mut chart = ChartBuilder::on(root)
+            .caption("Pole-zero map", CAPTION_FONT)
+            .margin(10)
+            .x_label_area_size(40)
+            .y_label_area_size(60)
+            .build_cartesian_2d(bounds(points().map(|p| p.0)), bounds(points().map(|p| p.1)))?;
+        chart
+            .configure_mesh()
+            .x_desc("Real")
+            .y_desc("Imaginary")
+            .draw()?;
+        chart.draw_series(LineSeries::new(
+            finite(self.boundary.iter().copied()),
+            BLACK.mix(0.5),
+        ))?;
+        chart.draw_series(self.poles.iter().map(|p| Cross::new(p.0, 6, BLUE)))?;
+        chart.draw_series(self.zeros.iter().map(|p| Circle::new(p.0, 6, RED)))?;
+        chart.draw_series(
+            self.poles
+                .iter()
+                .chain(&self.zeros)
+                .filter(|p| p.1 > 1)
+                .map(|p| Text::new(p.1.to_string(), p.0, ("sans-serif", 14))),
+        )?;
+        Ok(())
+    }
+}
+
+/// Time response data: time and value of each output
+struct StepFigure {
+    outputs: Vec<Vec<(f64, f64)>>,
+}
+
+impl Figure for StepFigure {
+    fn draw<DB: DrawingBackend>(&self, root: &DrawingArea<DB, Shift>) -> DrawResult<DB> {
+        root.fill(&WHITE)?;
+        let points = || self.outputs.iter().flatten();
+        let mut chart = ChartBuilder::on(root)
+            .caption("Step response", CAPTION_FONT)
+            .margin(10)
+            .x_label_area_size(40)
+            .y_label_area_size(60)
+            .build_cartesian_2d(bounds(points().map(|p| p.0)), bounds(points().map(|p| p.1)))?;
+        chart
+            .configure_mesh()
+            .x_desc("Time (s)")
+            .y_desc("Output")
+            .draw()?;
+        for (i, output) in self.outputs.iter().enumerate() {
+            let color = Palette99::pick(i).to_rgba();
+            chart
+                .draw_series(LineSeries::new(finite(output.iter().copied()), color))?
+                .label(format!("y{}", i + 1))
+                .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], color));
+        }
+        if self.outputs.len() > 1 {
+            chart
+                .configure_series_labels()
+                .background_style(WHITE.mix(0.8))
+                .border_style(BLACK)
+                .draw()?;
+        }
+        Ok(())
+    }
+}
+
+/// Convert a number to `f64`, `NaN` if it cannot be represented.
+fn to_f64<T: Float>(x: T) -> f64 {
+    x.to_f64().unwrap_or(f64::NAN)
+}
+
+/// Remove the points with non finite coordinates.
+fn finite(points: impl Iterator<Item = (f64, f64)>) -> impl Iterator<Item = (f64, f64)> {
+    points.filter(|p| p.0.is_finite() && p.1.is_finite())
+}
+
+/// Range of the axis that contains all the finite values, enlarged by 5% on
+/// both sides. It is [-1, 1] if there are no finite values.
+fn bounds(values: impl Iterator<Item = f64>) -> Range<f64> {
+    let (min, max) = values
+        .filter(|v| v.is_finite())
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), v| {
+            (min.min(v), max.max(v))
+        });
+    if min > max {
+        -1.0..1.0
+    } else if (max - min) <= f64::EPSILON * max.abs().max(1.) {
+        let delta = max.abs().max(1.) * 0.1;
+        min - delta..max + delta
+    } else {
+        let delta = (max - min) * 0.05;
+        min - delta..max + delta
+    }
+}
+
+/// Range of a logarithmic axis that contains all the finite positive values.
+/// It is [0.1, 10] if there are no such values.
+fn log_bounds(values: impl Iterator<Item = f64>) -> Range<f64> {
+    let (min, max) = values
+        .filter(|v| v.is_finite() && *v > 0.)
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), v| {
+            (min.min(v), max.max(v))
+        });
+    if min > max {
+        0.1..10.
+    } else if min < max {
+        min..max
+    } else {
+        min / 10.0..max * 10.
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        poly, polynomial::Poly, transfer_function::continuous::Tf, units::RadiansPerSecond,
+        units::Seconds, Ss,
+    };
+
+    use std::{fs, path::PathBuf};
+
+    /// Image file in the temporary directory, removed when dropped.
+    struct TempFile(PathBuf);
+
+    impl TempFile {
+        fn new(name: &str) -> Self {
+            Self(std::env::temp_dir().join(name))
+        }
+    }
+
+    impl Drop for TempFile {
+        fn drop(&mut self) {
+            // The file does not exist if the rendering failed.
+            let _ = fs::remove_file(&self.0);
+        }
+    }
+
+    fn tf() -> Tf<f64> {
+        Tf::new(poly!(1.), Poly::new_from_roots(&[-1., -2.]))
+    }
+
+    #[test]
+    fn bode_png() {
+        let file = TempFile::new("au_render_test_bode.png");
+        let path = &file.0;
+        let plot = Bode::new(tf(), RadiansPerSecond(0.1), RadiansPerSecond(10.), 0.1);
+        bode(plot, path, (320, 240)).unwrap();
+        let png = fs::read(path).unwrap();
+        assert_eq!(b"\x89PNG".to_vec(), png[..4].to_vec());
+    }
+
+    #[test]
+    fn nyquist_svg() {
+        let file = TempFile::new("au_render_test_nyquist.svg");
+        let path = &file.0;
+        let plot = Polar::new(tf(), RadiansPerSecond(0.1), RadiansPerSecond(10.), 0.1);
+        nyquist(plot, path, (320, 240)).unwrap();
+        let svg = fs::read_to_string(path).unwrap();
+        assert!(svg.contains("<svg"));
+        assert!(svg.contains("Nyquist plot"));
+    }
+
+    #[test]
+    fn pzmap_svg() {
+        let file = TempFile::new("au_render_test_pzmap.svg");
+        let path = &file.0;
+        let tf = Tf::new(poly!(1., 1.), Poly::new_from_roots(&[-2., -2.]));
+        pzmap(&tf.pzmap(), path, (240, 240)).unwrap();
+        let svg = fs::read_to_string(path).unwrap();
+        assert!(svg.contains("Pole-zero map"));
+    }
+
+    #[test]
+    fn step_svg() {
+        let file = TempFile::new("au_render_test_step.svg");
+        let path = &file.0;
+        let sys = Ss::new_from_slice(1, 1, 2, &[-1.], &[1.], &[1., 2.], &[0., 0.]);
+        let resp: TimeResponse<_> = sys.rk4(|_| vec![1.], &[0.], Seconds(0.1), 50).collect();
+        step(&resp, path, (320, 240)).unwrap();
+        let svg = fs::read_to_string(path).unwrap();
+        assert!(svg.contains("y2"));
+    }
+
+    #[test]
+    fn unsupported_format() {
+        let plot = Bode::new(tf(), RadiansPerSecond(0.1), RadiansPerSecond(10.), 0.1);
+        let file = TempFile::new("au_render_test.txt");
+        let err = bode(plot, &file.0, (320, 240)).unwrap_err();
+        assert_eq!(ErrorKind::UnsupportedFileFormat, err.kind());
+    }
+
+    #[test]
+    fn rendering_failure() {
+        let path = std::env::temp_dir()
+            .join("au_render_test_missing_dir")
+            .join("bode.svg");
+        let plot = Bode::new(tf(), RadiansPerSecond(0.1), RadiansPerSecond(10.), 0.1);
+        let err = bode(plot, path, (320, 240)).unwrap_err();
+        assert_eq!(ErrorKind::RenderingFailure, err.kind());
+        assert!(std::error::Error::source(&err).is_some());
+    }
+
+    #[test]
+    fn axis_bounds() {
+        assert_eq!(-1.0..1.0, bounds(vec![f64::NAN].into_iter()));
+        assert_eq!(0.9..1.1, bounds(vec![1., 1.].into_iter()));
+        let r = bounds(vec![0., f64::INFINITY, 10.].into_iter());
+        assert_relative_eq!(-0.5, r.start);
+        assert_relative_eq!(10.5, r.end);
+        assert_eq!(0.1..10., log_bounds(vec![0., -1.].into_iter()));
+        assert_eq!(0.2..20., log_bounds(vec![2.].into_iter()));
+    }
+}
@@ -2,27 +2,52 @@
 //!
 //! Trajectories of the poles when the system is put in feedback with a pure
 //! constant controller
+//!
+//! The transfer constants can be spaced linearly or logarithmically, and the
+//! step can be refined where the roots move quickly, see
+//! [with_adaptive_step](struct.RootLocus.html#method.with_adaptive_step).
+//!
+//! The qualitative shape of the locus for positive transfer constants is
+//! given by its [geometry](struct.Geometry.html): asymptotes, real axis
+//! segments, break points and imaginary axis crossings.
+//!
+//! The root locus of discrete systems lies in the z-plane, the roots that
+//! left the unit circle are reported for each transfer constant, see
+//! [outside_unit_circle](struct.Data.html#method.outside_unit_circle), and the
+//! critical gains are the [crossings](struct.Crossing.html) of the unit
+//! circle.
 
 use nalgebra::RealField;
 use num_complex::Complex;
-use num_traits::{Float, MulAdd};
+use num_traits::{Float, FloatConst, MulAdd, Num};
 
-use crate::transfer_function::continuous::Tf;
+use crate::{
+    enums::{Continuous, Time},
+    plots::{adaptive_point, Grid},
+    polynomial::Poly,
+    transfer_function::{continuous::Tf, TfGen},
+    units::RadiansPerSecond,
+};
 
 /// Struct for root locus plot
 #[derive(Clone, Debug)]
-pub struct RootLocus<T: Float> {
+pub struct RootLocus<T: Float, U: Time = Continuous> {
     /// Transfer function
-    tf: Tf<T>,
+    tf: TfGen<T, U>,
     /// Minimum transfer constant of the plot
     min_k: T,
     /// Maximum transfer constant of the plot
     max_k: T,
     /// Step size
     step: T,
+    /// Logarithmic spacing of the transfer constants
+    log: bool,
+    /// Maximum displacement of the roots and maximum number of halvings of
+    /// the step
+    adaptive: Option<(T, u32)>,
 }
 
-impl<T: Float> RootLocus<T> {
+impl<T: Float, U: Time> RootLocus<T, U> {
     /// Create a `RootLocus` plot struct
     ///
     /// # Arguments
@@ -38,7 +63,7 @@ impl<T: Float> RootLocus<T> {
     ///
     /// Panics if the step is not strictly positive of the minimum transfer constant
     /// is not lower than the maximum transfer constant.
-    pub(crate) fn new(tf: Tf<T>, min_k: T, max_k: T, step: T) -> Self {
+    pub(crate) fn new(tf: TfGen<T, U>, min_k: T, max_k: T, step: T) -> Self {
         assert!(step > T::zero(), "Step value must be strictly positive.");
         assert!(
             min_k < max_k,
@@ -50,15 +75,85 @@ impl<T: Float> RootLocus<T> {
             min_k,
             max_k,
             step,
+            log: false,
+            adaptive: None,
+        }
+    }
+
+    /// Create a `RootLocus` plot struct with logarithmic spacing of the
+    /// transfer constants
+    ///
+    /// # Arguments
+    ///
+    /// * `tf` - Transfer function to plot
+    /// * `min_k` - Minimum transfer constant of the plot
+    /// * `max_k` - Maximum transfer constant of the plot
+    /// * `step` - Step between each transfer constant
+    ///
+    /// `step` shall be in logarithmic scale. Use 0.1 to have 10 point per decade
+    ///
+    /// # Panics
+    ///
+    /// Panics if the step or the minimum transfer constant are not strictly
+    /// positive or the minimum transfer constant is not lower than the maximum
+    /// transfer constant.
+    pub(crate) fn new_log(tf: TfGen<T, U>, min_k: T, max_k: T, step: T) -> Self {
+        assert!(
+            min_k > T::zero(),
+            "Minimum transfer constant must be strictly positive."
+        );
+        let mut locus = Self::new(tf, min_k.log10(), max_k.log10(), step);
+        locus.log = true;
+        locus
+    }
+
+    /// Refine the step between transfer constants where the roots move
+    /// quickly.
+    ///
+    /// The interval between two consecutive transfer constants is halved as
+    /// long as a root moves more than the given displacement, up to the
+    /// maximum depth.
+    ///
+    /// # Arguments
+    ///
+    /// * `displacement` - Maximum displacement of the roots between
+    ///   consecutive transfer constants
+    /// * `depth` - Maximum number of halvings of the step
+    ///
+    /// # Panics
+    ///
+    /// Panics if the displacement is not strictly positive.
+    ///
+    /// # Example
+    /// ```
+    /// use au::{poly, Poly, Tf};
+    /// let l = Tf::new(poly!(1.), Poly::new_from_roots(&[-1., -2.]));
+    /// let coarse = l.clone().root_locus_plot(0., 1., 0.5).into_iter().count();
+    /// let refined = l
+    ///     .root_locus_plot(0., 1., 0.5)
+    ///     .with_adaptive_step(0.1, 6)
+    ///     .into_iter()
+    ///     .count();
+    /// assert!(refined > coarse);
+    /// ```
+    #[must_use]
+    pub fn with_adaptive_step(self, displacement: T, depth: u32) -> Self {
+        assert!(
+            displacement > T::zero(),
+            "Displacement must be strictly positive."
+        );
+        Self {
+            adaptive: Some((displacement, depth)),
+            ..self
         }
     }
 }
 
 /// Struct for root locus plot
 #[derive(Clone, Debug)]
-pub struct IntoIter<T: Float> {
+pub struct IntoIter<T: Float, U: Time = Continuous> {
     /// Transfer function
-    tf: Tf<T>,
+    tf: TfGen<T, U>,
     /// Minimum transfer constant
     min_k: T,
     /// Step size
@@ -67,11 +162,20 @@ pub struct IntoIter<T: Float> {
     intervals: T,
     /// Current index of iterator
     index: T,
+    /// Logarithmic spacing of the transfer constants
+    log: bool,
+    /// Maximum displacement of the roots and maximum number of halvings of
+    /// the step
+    adaptive: Option<(T, u32)>,
+    /// Last point of the refined grid
+    last: Option<(T, Vec<Complex<T>>)>,
+    /// Points of the refined grid not yet returned, the nearest on top
+    pending: Vec<(T, Vec<Complex<T>>)>,
 }
 
-impl<T: Float + MulAdd<Output = T> + RealField> IntoIterator for RootLocus<T> {
+impl<T: Float + MulAdd<Output = T> + RealField, U: Time> IntoIterator for RootLocus<T, U> {
     type Item = Data<T>;
-    type IntoIter = IntoIter<T>;
+    type IntoIter = IntoIter<T, U>;
 
     fn into_iter(self) -> Self::IntoIter {
         let intervals = num_traits::Float::floor((self.max_k - self.min_k) / self.step);
@@ -81,6 +185,10 @@ impl<T: Float + MulAdd<Output = T> + RealField> IntoIterator for RootLocus<T> {
             step: self.step,
             intervals,
             index: T::zero(),
+            log: self.log,
+            adaptive: self.adaptive,
+            last: None,
+            pending: Vec::new(),
         }
     }
 }
@@ -106,29 +214,500 @@ impl<T: Copy> Data<T> {
     }
 }
 
-impl<T: Float + MulAdd<Output = T> + RealField> Iterator for IntoIter<T> {
+impl<T: Float> Data<T> {
+    /// Get the indices of the roots outside the unit circle, the unstable
+    /// closed loop poles of discrete systems.
+    ///
+    /// # Example
+    /// ```
+    /// use au::{poly, Tfz};
+    /// let l = Tfz::new(poly!(1.), poly!(-0.5, 1.));
+    /// let data: Vec<_> = l.root_locus_plot(0., 2., 1.).into_iter().collect();
+    /// assert!(data[0].outside_unit_circle().is_empty());
+    /// assert_eq!(vec![0], data[2].outside_unit_circle());
+    /// ```
+    #[must_use]
+    pub fn outside_unit_circle(&self) -> Vec<usize> {
+        self.output
+            .iter()
+            .enumerate()
+            .filter(|(_, r)| r.norm() > T::one())
+            .map(|(i, _)| i)
+            .collect()
+    }
+}
+
+impl<T: Float + MulAdd<Output = T> + RealField, U: Time> Iterator for IntoIter<T, U> {
     type Item = Data<T>;
 
     fn next(&mut self) -> Option<Self::Item> {
+        let (x, output) = match self.adaptive {
+            None => self.coarse_point()?,
+            Some((displacement, depth)) => self.refined_point(displacement, depth)?,
+        };
+        Some(Self::Item {
+            k: self.gain(x),
+            output,
+        })
+    }
+}
+
+impl<T: Float + MulAdd<Output = T> + RealField, U: Time> IntoIter<T, U> {
+    /// Roots of the closed loop at the given transfer constant
+    fn roots(&self, k: T) -> Vec<Complex<T>> {
+        (&(self.tf.num() * k) + self.tf.den()).complex_roots()
+    }
+
+    /// Transfer constant at the given position of the grid
+    fn gain(&self, x: T) -> T {
+        if self.log {
+            crate::plots::exp10(x)
+        } else {
+            x
+        }
+    }
+
+    /// Next point of the refined grid, between the last returned point and
+    /// the nearest pending point of the regular grid.
+    fn refined_point(&mut self, displacement: T, depth: u32) -> Option<(T, Vec<Complex<T>>)> {
+        let last = match self.last.take() {
+            Some(last) => last,
+            None => {
+                let first = self.coarse_point()?;
+                self.last = Some(first.clone());
+                return Some(first);
+            }
+        };
+        let steps = (
+            self.step / T::from(2_u32.pow(depth.min(31))).unwrap(),
+            self.step,
+        );
+        let exceeded = |a: &Vec<_>, b: &Vec<_>| max_displacement(a, b) > displacement;
+        let mut pending = std::mem::take(&mut self.pending);
+        let point = adaptive_point(self, exceeded, steps, &last, &mut pending);
+        self.pending = pending;
+        self.last = Some(match &point {
+            Some(p) => p.clone(),
+            None => last,
+        });
+        point
+    }
+}
+
+impl<T: Float + MulAdd<Output = T> + RealField, U: Time> Grid<T, Vec<Complex<T>>>
+    for IntoIter<T, U>
+{
+    /// Next point of the regular grid, position and roots.
+    fn coarse_point(&mut self) -> Option<(T, Vec<Complex<T>>)> {
         if self.index > self.intervals {
             None
         } else {
-            // k = step * index + min_k, is used to avoid loss of precision
-            // of k += step, due to floating point addition
-            let k = MulAdd::mul_add(self.step, self.index, self.min_k);
+            // x = step * index + min_k, is used to avoid loss of precision
+            // of x += step, due to floating point addition
+            let x = MulAdd::mul_add(self.step, self.index, self.min_k);
             self.index += T::one();
-            Some(Self::Item {
+            Some((x, self.eval(x)))
+        }
+    }
+
+    /// Roots of the closed loop at the given position of the grid.
+    fn eval(&self, x: T) -> Vec<Complex<T>> {
+        self.roots(self.gain(x))
+    }
+}
+
+impl<T: Float + MulAdd<Output = T> + RealField, U: Time> IntoIter<T, U> {
+    /// Collect the data points into the parallel arrays of the transfer
+    /// constants and of the roots, an array for each root.
+    ///
+    /// # Example
+    /// ```
+    /// use au::{num_complex::Complex, poly, Tf};
+    /// let l = Tf::new(poly!(1.), poly!(1., 1.));
+    /// let (k, roots) = l.root_locus_plot(0., 1., 1.).into_iter().into_vecs();
+    /// assert_eq!(vec![0., 1.], k);
+    /// assert_eq!(vec![vec![Complex::new(-1., 0.), Complex::new(-2., 0.)]], roots);
+    /// ```
+    #[must_use]
+    pub fn into_vecs(self) -> (Vec<T>, Vec<Vec<Complex<T>>>) {
+        let (k, rows) = self.map(|d| (d.k, d.output)).unzip();
+        (k, crate::plots::transpose(rows))
+    }
+}
+
+/// Maximum distance of a root of `a` from the nearest root of `b`.
+fn max_displacement<T: Float>(a: &[Complex<T>], b: &[Complex<T>]) -> T {
+    a.iter()
+        .map(|ra| {
+            b.iter()
+                .map(|rb| (ra - rb).norm())
+                .fold(T::infinity(), Float::min)
+        })
+        .fold(T::zero(), Float::max)
+}
+
+/// Geometry of the root locus for positive transfer constants `k`, the
+/// closed loop poles are the roots of `den(s) + k * num(s)`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Geometry<T: Num> {
+    /// Intersection of the asymptotes with the real axis
+    centroid: Option<T>,
+    /// Angles of the asymptotes (rad)
+    asymptote_angles: Vec<T>,
+    /// Segments of the real axis that belong to the locus
+    real_axis_segments: Vec<(T, T)>,
+    /// Breakaway and break-in points on the real axis
+    break_points: Vec<BreakPoint<T>>,
+    /// Crossings of the imaginary axis
+    imaginary_axis_crossings: Vec<Crossing<T>>,
+}
+
+/// Point of the real axis where branches of the locus meet
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BreakPoint<T> {
+    /// Location on the real axis
+    point: T,
+    /// Transfer constant at the point
+    k: T,
+    /// The branches leave the real axis when `k` increases
+    breakaway: bool,
+}
+
+impl<T: Copy> BreakPoint<T> {
+    /// Get the location on the real axis
+    #[must_use]
+    pub fn point(&self) -> T {
+        self.point
+    }
+
+    /// Get the transfer constant at the point
+    #[must_use]
+    pub fn k(&self) -> T {
+        self.k
+    }
+
+    /// Check if the branches leave the real axis when `k` increases
+    /// (breakaway point), otherwise they enter it (break-in point)
+    #[must_use]
+    pub fn is_breakaway(&self) -> bool {
+        self.breakaway
+    }
+}
+
+/// Crossing of the stability boundary by the locus, the imaginary axis for
+/// continuous systems and the unit circle for discrete systems
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Crossing<T: Num> {
+    /// Transfer constant at the crossing
+    k: T,
+    /// Angular frequency of the crossing, the roots are `±jω` or `e^(±jω)`
+    omega: RadiansPerSecond<T>,
+}
+
+impl<T: Copy + Num> Crossing<T> {
+    /// Get the transfer constant at the crossing
+    #[must_use]
+    pub fn k(&self) -> T {
+        self.k
+    }
+
+    /// Get the angular frequency of the crossing, the roots are `±jω` for
+    /// continuous systems and `e^(±jω)` for discrete systems, where the
+    /// frequency is in radians per sample.
+    #[must_use]
+    pub fn omega(&self) -> RadiansPerSecond<T> {
+        self.omega
+    }
+}
+
+impl<T: Float + FloatConst + RealField> Geometry<T> {
+    /// Calculate the geometry of the root locus of the transfer function.
+    ///
+    /// # Arguments
+    ///
+    /// * `tf` - Open loop transfer function
+    pub(crate) fn new(tf: &Tf<T>) -> Self {
+        let (num, den) = (tf.num(), tf.den());
+        let (m, n) = match (num.degree(), den.degree()) {
+            (Some(m), Some(n)) => (m, n),
+            _ => {
+                return Self {
+                    centroid: None,
+                    asymptote_angles: Vec::new(),
+                    real_axis_segments: Vec::new(),
+                    break_points: Vec::new(),
+                    imaginary_axis_crossings: Vec::new(),
+                }
+            }
+        };
+        let (centroid, asymptote_angles) = asymptotes(num, den, m, n);
+        Self {
+            centroid,
+            asymptote_angles,
+            real_axis_segments: real_axis_segments(num, den),
+            break_points: break_points(num, den),
+            imaginary_axis_crossings: imaginary_axis_crossings(num, den),
+        }
+    }
+}
+
+impl<T: Copy + Num> Geometry<T> {
+    /// Get the intersection of the asymptotes with the real axis, `None` if
+    /// there are no asymptotes.
+    #[must_use]
+    pub fn centroid(&self) -> Option<T> {
+        self.centroid
+    }
+
+    /// Get the angles of the asymptotes (rad), in the interval [0, 2π).
+    /// There is an asymptote for each pole in excess of the zeros.
+    #[must_use]
+    pub fn asymptote_angles(&self) -> &[T] {
+        &self.asymptote_angles
+    }
+
+    /// Get the segments of the real axis that belong to the locus, ordered
+    /// from left to right. Unbounded segments have infinite extremes.
+    #[must_use]
+    pub fn real_axis_segments(&self) -> &[(T, T)] {
+        &self.real_axis_segments
+    }
+
+    /// Get the breakaway and break-in points on the real axis, ordered from
+    /// left to right.
+    #[must_use]
+    pub fn break_points(&self) -> &[BreakPoint<T>] {
+        &self.break_points
+    }
+
+    /// Get the crossings of the imaginary axis, ordered by increasing
+    /// transfer constant.
+    #[must_use]
+    pub fn imaginary_axis_crossings(&self) -> &[Crossing<T>] {
+        &self.imaginary_axis_crossings
+    }
+}
+
+/// Tolerance used to decide if a computed root is real
+fn tolerance<T: Float>() -> T {
+    T::epsilon().cbrt()
+}
+
+/// Real roots of the polynomial, in increasing order.
+fn sorted_real_roots<T: Float + RealField>(p: &Poly<T>) -> Vec<T> {
+    let mut roots: Vec<_> = p
+        .complex_roots()
+        .iter()
+        .filter(|r| Float::abs(r.im) <= tolerance::<T>() * Float::max(T::one(), r.norm()))
+        .map(|r| r.re)
+        .collect();
+    roots.sort_unstable_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    roots
+}
+
+/// Transfer constant that places a closed loop pole at `x`, `-den(x) / num(x)`.
+fn gain<T: Float>(num: &Poly<T>, den: &Poly<T>, x: T) -> T {
+    -den.eval_by_val(x) / num.eval_by_val(x)
+}
+
+/// Centroid and angles of the asymptotes.
+///
+/// The sum of the roots is taken from the coefficient of the second highest
+/// power of the polynomials.
+fn asymptotes<T: Float + FloatConst>(
+    num: &Poly<T>,
+    den: &Poly<T>,
+    m: usize,
+    n: usize,
+) -> (Option<T>, Vec<T>) {
+    if n <= m {
+        return (None, Vec::new());
+    }
+    let sum_of_roots = |p: &Poly<T>, d: usize| {
+        if d == 0 {
+            T::zero()
+        } else {
+            -p.as_slice()[d - 1] / p.as_slice()[d]
+        }
+    };
+    let excess = T::from(n - m).unwrap();
+    let centroid = (sum_of_roots(den, n) - sum_of_roots(num, m)) / excess;
+    // With a negative high frequency gain the angles are even multiples of pi.
+    let offset = if num.leading_coeff() / den.leading_coeff() > T::zero() {
+        T::one()
+    } else {
+        T::zero()
+    };
+    let angles = (0..n - m)
+        .map(|q| (T::from(2 * q).unwrap() + offset) * T::PI() / excess)
+        .collect();
+    (Some(centroid), angles)
+}
+
+/// Segments of the real axis where `-den(x) / num(x)` is positive.
+fn real_axis_segments<T: Float + RealField>(num: &Poly<T>, den: &Poly<T>) -> Vec<(T, T)> {
+    let mut bounds = sorted_real_roots(num);
+    bounds.extend(sorted_real_roots(den));
+    bounds.sort_unstable_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    bounds.dedup();
+
+    let mut edges = vec![T::neg_infinity()];
+    edges.extend(bounds);
+    edges.push(T::infinity());
+
+    let mut segments: Vec<(T, T)> = Vec::new();
+    for w in edges.windows(2) {
+        let (a, b) = (w[0], w[1]);
+        let x = if a.is_infinite() && b.is_infinite() {
+            T::zero()
+        } else if a.is_infinite() {
+            b - T::one()
+        } else if b.is_infinite() {
+            a + T::one()
+        } else {
+            (a + b) / (T::one() + T::one())
+        };
+        if den.eval_by_val(x) * num.eval_by_val(x) < T::zero() {
+            match segments.last_mut() {
+                Some(last) if last.1 == a => last.1 = b,
+                _ => segments.push((a, b)),
+            }
+        }
+    }
+    segments
+}
+
+/// Real points where the transfer constant is stationary along the real
+/// axis, roots of `num'(x) * den(x) - num(x) * den'(x)`.
+fn break_points<T: Float + RealField>(num: &Poly<T>, den: &Poly<T>) -> Vec<BreakPoint<T>> {
+    let stationary = &(&num.derive() * den) - &(num * &den.derive());
+    // Sign of the second derivative of the transfer constant.
+    let curvature = &(&den.derive().derive() * num) - &(den * &num.derive().derive());
+
+    let mut points: Vec<BreakPoint<T>> = Vec::new();
+    for x in sorted_real_roots(&stationary) {
+        let k = gain(num, den, x);
+        if !k.is_finite() || k < T::zero() {
+            continue;
+        }
+        let close = |p: &BreakPoint<T>| {
+            Float::abs(p.point - x) <= tolerance::<T>() * Float::max(T::one(), Float::abs(x))
+        };
+        if !matches!(points.last(), Some(p) if close(p)) {
+            points.push(BreakPoint {
+                point: x,
+                k: Float::max(k, T::zero()),
+                breakaway: curvature.eval_by_val(x) > T::zero(),
+            });
+        }
+    }
+    points
+}
+
+/// Split the polynomial evaluated at `jω` into its real and imaginary parts,
+/// as polynomials in `ω`.
+fn split_imaginary<T: Float>(p: &Poly<T>) -> (Poly<T>, Poly<T>) {
+    let mut re = vec![T::zero(); p.as_slice().len()];
+    let mut im = vec![T::zero(); p.as_slice().len()];
+    for (i, &c) in p.as_slice().iter().enumerate() {
+        match i % 4 {
+            0 => re[i] = c,
+            1 => im[i] = c,
+            2 => re[i] = -c,
+            _ => im[i] = -c,
+        }
+    }
+    (Poly::new_from_coeffs(&re), Poly::new_from_coeffs(&im))
+}
+
+/// Crossings of the imaginary axis.
+///
+/// The characteristic equation `den(jω) + k * num(jω) = 0` has a real
+/// solution `k` when `den(jω) * conj(num(jω))` is real, it is the same
+/// condition that makes a row of the Routh table vanish.
+fn imaginary_axis_crossings<T: Float + RealField>(
+    num: &Poly<T>,
+    den: &Poly<T>,
+) -> Vec<Crossing<T>> {
+    let (num_re, num_im) = split_imaginary(num);
+    let (den_re, den_im) = split_imaginary(den);
+    let condition = &(&den_im * &num_re) - &(&den_re * &num_im);
+
+    let mut crossings: Vec<Crossing<T>> = Vec::new();
+    for omega in sorted_real_roots(&condition) {
+        let omega = Float::abs(omega);
+        let s = Complex::new(T::zero(), omega);
+        let (n, d) = (num.eval_by_val(s), den.eval_by_val(s));
+        if n.norm() <= T::epsilon() * Float::max(T::one(), d.norm()) {
+            continue;
+        }
+        let k = -(d * n.conj()).re / n.norm_sqr();
+        let residual = (d + n * k).norm();
+        if k < T::zero() || residual > tolerance::<T>() * (d.norm() + k * n.norm()) {
+            continue;
+        }
+        let duplicate = crossings.iter().any(|c| {
+            Float::abs(c.omega.0 - omega) <= tolerance::<T>() * Float::max(T::one(), omega)
+        });
+        if !duplicate {
+            crossings.push(Crossing {
                 k,
-                output: self.tf.root_locus(k),
-            })
+                omega: RadiansPerSecond(omega),
+            });
         }
     }
+    crossings.sort_unstable_by(|a, b| a.k.partial_cmp(&b.k).unwrap_or(std::cmp::Ordering::Equal));
+    crossings
+}
+
+/// Crossings of the unit circle, ordered by increasing transfer constant.
+///
+/// The bilinear transformation `z = (1 + w) / (1 - w)` maps the unit circle
+/// into the imaginary axis, where the crossings are found; the point `z = -1`
+/// is mapped to infinity and it is checked separately.
+pub(crate) fn unit_circle_crossings<T: Float + RealField>(
+    num: &Poly<T>,
+    den: &Poly<T>,
+) -> Vec<Crossing<T>> {
+    let n = match (num.degree(), den.degree()) {
+        (Some(m), Some(n)) => m.max(n),
+        _ => return Vec::new(),
+    };
+    let bilinear = |p: &Poly<T>| {
+        let plus = Poly::new_from_coeffs(&[T::one(), T::one()]);
+        let minus = Poly::new_from_coeffs(&[T::one(), -T::one()]);
+        p.as_slice()
+            .iter()
+            .enumerate()
+            .fold(Poly::new_from_coeffs(&[T::zero()]), |acc, (i, &c)| {
+                &acc + &(&(plus.powi(i as u32) * minus.powi((n - i) as u32)) * c)
+            })
+    };
+    let two = T::one() + T::one();
+    let mut crossings: Vec<_> = imaginary_axis_crossings(&bilinear(num), &bilinear(den))
+        .into_iter()
+        .map(|c| Crossing {
+            k: c.k,
+            omega: RadiansPerSecond(two * Float::atan(c.omega.0)),
+        })
+        .collect();
+
+    let minus_one = -T::one();
+    let (n_1, d_1) = (num.eval_by_val(minus_one), den.eval_by_val(minus_one));
+    if n_1 != T::zero() && -d_1 / n_1 >= T::zero() {
+        crossings.push(Crossing {
+            k: -d_1 / n_1,
+            omega: RadiansPerSecond(T::pi()),
+        });
+    }
+    crossings.sort_unstable_by(|a, b| a.k.partial_cmp(&b.k).unwrap_or(std::cmp::Ordering::Equal));
+    crossings
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::poly;
+    use crate::{poly, polynomial::Poly, transfer_function::discrete::Tfz};
 
     #[test]
     #[should_panic]
@@ -143,4 +722,157 @@ mod tests {
         let tf = Tf::new(poly!(1.), poly!(0., 1.));
         RootLocus::new(tf, 0.9, 0.2, 0.1);
     }
+
+    #[test]
+    fn geometry_three_poles() {
+        // G(s) = 1 / (s (s + 1) (s + 2))
+        let tf = Tf::new(poly!(1.), Poly::new_from_roots(&[0., -1., -2.]));
+        let g = Geometry::new(&tf);
+        assert_relative_eq!(-1., g.centroid().unwrap());
+        let pi = std::f64::consts::PI;
+        assert_eq!(3, g.asymptote_angles().len());
+        assert_relative_eq!(pi / 3., g.asymptote_angles()[0]);
+        assert_relative_eq!(pi, g.asymptote_angles()[1]);
+        assert_relative_eq!(5. * pi / 3., g.asymptote_angles()[2]);
+
+        let segments = g.real_axis_segments();
+        assert_eq!(2, segments.len());
+        assert_eq!(f64::NEG_INFINITY, segments[0].0);
+        assert_relative_eq!(-2., segments[0].1, max_relative = 1e-10);
+        assert_relative_eq!(-1., segments[1].0, max_relative = 1e-10);
+        assert_relative_eq!(0., segments[1].1, epsilon = 1e-10);
+
+        assert_eq!(1, g.break_points().len());
+        let b = g.break_points()[0];
+        assert_relative_eq!(-1. + 1. / 3_f64.sqrt(), b.point(), max_relative = 1e-10);
+        assert_relative_eq!(2. / (3. * 3_f64.sqrt()), b.k(), max_relative = 1e-10);
+        assert!(b.is_breakaway());
+
+        let c = g.imaginary_axis_crossings().last().unwrap();
+        assert_relative_eq!(6., c.k(), max_relative = 1e-10);
+        assert_relative_eq!(2_f64.sqrt(), c.omega().0, max_relative = 1e-10);
+    }
+
+    #[test]
+    fn geometry_break_in() {
+        // G(s) = (s + 3) / (s (s + 1))
+        let tf = Tf::new(poly!(3., 1.), Poly::new_from_roots(&[0., -1.]));
+        let g = Geometry::new(&tf);
+        assert_relative_eq!(2., g.centroid().unwrap());
+        assert_eq!(1, g.asymptote_angles().len());
+        let points = g.break_points();
+        assert_eq!(2, points.len());
+        let s6 = 6_f64.sqrt();
+        assert_relative_eq!(-3. - s6, points[0].point(), max_relative = 1e-10);
+        assert!(!points[0].is_breakaway());
+        assert_relative_eq!(-3. + s6, points[1].point(), max_relative = 1e-10);
+        assert!(points[1].is_breakaway());
+        assert!(points[0].k() > points[1].k());
+    }
+
+    #[test]
+    fn geometry_negative_high_frequency_gain() {
+        // G(s) = -1 / (s + 1)^2
+        let tf = Tf::new(poly!(-1.), Poly::new_from_roots(&[-1., -1.]));
+        let g = Geometry::new(&tf);
+        assert_relative_eq!(0., g.asymptote_angles()[0]);
+        assert_relative_eq!(std::f64::consts::PI, g.asymptote_angles()[1]);
+        assert_eq!(1, g.real_axis_segments().len());
+        let crossing = g.imaginary_axis_crossings()[0];
+        assert_relative_eq!(1., crossing.k(), max_relative = 1e-10);
+        assert_relative_eq!(0., crossing.omega().0, epsilon = 1e-10);
+    }
+
+    #[test]
+    fn geometry_without_asymptotes() {
+        let tf = Tf::new(poly!(1., 1.), poly!(2., 1.));
+        let g = Geometry::new(&tf);
+        assert_eq!(None, g.centroid());
+        assert!(g.asymptote_angles().is_empty());
+        assert_eq!(1, g.real_axis_segments().len());
+        assert!(g.imaginary_axis_crossings().is_empty());
+    }
+
+    #[test]
+    fn unit_circle_complex_crossing() {
+        // Closed loop poles z^2 - 0.7z + 0.1 + k = 0.
+        let c = unit_circle_crossings(&poly!(1.), &poly!(0.1, -0.7, 1.));
+        assert_eq!(1, c.len());
+        assert_relative_eq!(0.9, c[0].k(), max_relative = 1e-10);
+        assert_relative_eq!(0.35_f64.acos(), c[0].omega().0, max_relative = 1e-10);
+    }
+
+    #[test]
+    fn unit_circle_real_crossings() {
+        // Closed loop pole z = 0.5 + k crosses in z = 1.
+        let c = unit_circle_crossings(&poly!(-1.), &poly!(-0.5, 1.));
+        assert_eq!(1, c.len());
+        assert_relative_eq!(0.5, c[0].k(), max_relative = 1e-10);
+        assert_relative_eq!(0., c[0].omega().0, epsilon = 1e-10);
+
+        // Closed loop pole z = 0.5 - k crosses in z = -1.
+        let c = unit_circle_crossings(&poly!(1.), &poly!(-0.5, 1.));
+        assert_eq!(1, c.len());
+        assert_relative_eq!(1.5, c[0].k(), max_relative = 1e-10);
+        assert_relative_eq!(std::f64::consts::PI, c[0].omega().0);
+    }
+
+    #[test]
+    fn discrete_outside_unit_circle() {
+        let tf = Tfz::new(poly!(1.), poly!(0.1, -0.7, 1.));
+        for d in RootLocus::new(tf, 0., 2., 0.25) {
+            let expected = if d.k() > 0.9 { 2 } else { 0 };
+            assert_eq!(expected, d.outside_unit_circle().len());
+        }
+    }
+
+    #[test]
+    fn log_spacing() {
+        let tf = Tf::new(poly!(1.), Poly::new_from_roots(&[-1., -2.]));
+        let k: Vec<_> = RootLocus::new_log(tf, 0.1, 10., 0.5)
+            .into_iter()
+            .map(|d| d.k())
+            .collect();
+        assert_eq!(5, k.len());
+        for (a, b) in k.iter().zip(&k[1..]) {
+            assert_relative_eq!(10_f64.sqrt(), b / a, max_relative = 1e-12);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn log_spacing_fail() {
+        let tf = Tf::new(poly!(1.), poly!(0., 1.));
+        RootLocus::new_log(tf, 0., 1., 0.1);
+    }
+
+    #[test]
+    fn adaptive_step_near_breakaway() {
+        // Breakaway point at k = 0.25, the roots move quickly nearby.
+        let tf = Tf::new(poly!(1.), Poly::new_from_roots(&[-1., -2.]));
+        let data: Vec<_> = RootLocus::new(tf, 0., 1., 0.125)
+            .with_adaptive_step(0.05, 8)
+            .into_iter()
+            .collect();
+        assert_relative_eq!(0., data[0].k());
+        assert_relative_eq!(1., data[data.len() - 1].k());
+        for (a, b) in data.iter().zip(&data[1..]) {
+            assert!(a.k() < b.k());
+            let width = b.k() - a.k();
+            let moved = max_displacement(a.output(), b.output());
+            assert!(moved <= 0.05 || width <= 0.125 / 256. * 2.);
+        }
+        let near: Vec<_> = data
+            .iter()
+            .filter(|d| (d.k() - 0.25).abs() < 0.01)
+            .collect();
+        assert!(near.len() > 2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn adaptive_step_fail() {
+        let tf = Tf::new(poly!(1.), poly!(0., 1.));
+        let _ = RootLocus::new(tf, 0., 1., 0.1).with_adaptive_step(0., 4);
+    }
 }
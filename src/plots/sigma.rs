@@ -0,0 +1,270 @@
+//! # Singular value plot
+//!
+//! Singular value plot returns the angular frequency and the singular values
+//! of the frequency response matrix of a multiple input multiple output
+//! system, the multivariable generalization of the Bode magnitude plot.
+//!
+//! The maximum and the minimum singular values bound the gain of the system
+//! for any direction of the input.
+//!
+//! Functions use angular frequencies as default inputs and output.
+
+use nalgebra::{DMatrix, RealField};
+use num_complex::Complex;
+use num_traits::{Float, Num};
+
+use crate::{
+    plots::{exp10, transpose},
+    units::RadiansPerSecond,
+};
+
+/// Determine how the frequency response matrix is evaluated in singular value
+/// plots.
+pub trait SigmaPlot<T: Num> {
+    /// Evaluate the frequency response matrix at the given angular
+    /// frequencies.
+    ///
+    /// All the frequencies of the plot are evaluated at once, so that the
+    /// calculations that do not depend on the frequency are done only once.
+    ///
+    /// # Arguments
+    ///
+    /// * `freqs` - angular frequencies at which the response is evaluated
+    fn eval_matrices(&self, freqs: &[RadiansPerSecond<T>]) -> Vec<DMatrix<Complex<T>>>;
+}
+
+impl<T: Num, U: SigmaPlot<T>> SigmaPlot<T> for &U {
+    fn eval_matrices(&self, freqs: &[RadiansPerSecond<T>]) -> Vec<DMatrix<Complex<T>>> {
+        (**self).eval_matrices(freqs)
+    }
+}
+
+/// Struct for the calculation of singular value plots
+#[derive(Clone, Debug)]
+pub struct Sigma<T: Num, U: SigmaPlot<T>> {
+    /// System
+    sys: U,
+    /// Minimum angular frequency of the plot
+    min_freq: RadiansPerSecond<T>,
+    /// Maximum angular frequency of the plot
+    max_freq: RadiansPerSecond<T>,
+    /// Step between frequencies
+    step: T,
+}
+
+impl<T: Float, U: SigmaPlot<T>> Sigma<T, U> {
+    /// Create a `Sigma` plot struct
+    ///
+    /// # Arguments
+    ///
+    /// * `sys` - System to plot
+    /// * `min_freq` - Minimum angular frequency of the plot
+    /// * `max_freq` - Maximum angular frequency of the plot
+    /// * `step` - Step between frequencies
+    ///
+    /// `step` shall be in logarithmic scale. Use 0.1 to have 10 point per decade
+    ///
+    /// # Panics
+    ///
+    /// Panics if the step is not strictly positive and the minimum frequency
+    /// is not lower than the maximum frequency
+    ///
+    /// # Example
+    /// ```
+    /// use au::{plots::sigma::Sigma, RadiansPerSecond, Ss};
+    /// let sys = Ss::new_from_slice(1, 2, 2, &[-1.], &[1., 2.], &[1., 0.], &[0.; 4]);
+    /// let sigma = Sigma::new(sys, RadiansPerSecond(0.1), RadiansPerSecond(10.), 1.);
+    /// let data: Vec<_> = sigma.into_iter().collect();
+    /// assert_eq!(3, data.len());
+    /// assert!(data[0].max() > data[2].max());
+    /// assert!(data[0].min() < 1e-12);
+    /// ```
+    pub fn new(
+        sys: U,
+        min_freq: RadiansPerSecond<T>,
+        max_freq: RadiansPerSecond<T>,
+        step: T,
+    ) -> Self {
+        assert!(step > T::zero());
+        assert!(min_freq < max_freq);
+
+        Self {
+            sys,
+            min_freq,
+            max_freq,
+            step,
+        }
+    }
+}
+
+impl<T: Float + RealField, U: SigmaPlot<T>> IntoIterator for Sigma<T, U> {
+    type Item = Data<T>;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let min = Float::log10(self.min_freq.0);
+        let max = Float::log10(self.max_freq.0);
+        let intervals = Float::floor((max - min) / self.step)
+            .to_usize()
+            .unwrap_or(0);
+        let freqs: Vec<_> = (0..=intervals)
+            .map(|i| RadiansPerSecond(exp10(min + self.step * T::from(i).unwrap())))
+            .collect();
+        let responses = self.sys.eval_matrices(&freqs);
+        Self::IntoIter {
+            responses: freqs
+                .into_iter()
+                .zip(responses)
+                .collect::<Vec<_>>()
+                .into_iter(),
+        }
+    }
+}
+
+/// Struct for the singular value plot data point iteration.
+#[derive(Clone, Debug)]
+pub struct IntoIter<T: Float> {
+    /// Angular frequencies and frequency response matrices
+    responses: std::vec::IntoIter<(RadiansPerSecond<T>, DMatrix<Complex<T>>)>,
+}
+
+/// Struct to hold the data returned by the singular value plot iterator
+#[derive(Clone, Debug, PartialEq)]
+pub struct Data<T: Num> {
+    /// Angular frequency (rad/s)
+    angular_frequency: RadiansPerSecond<T>,
+    /// Singular values in decreasing order
+    singular_values: Vec<T>,
+}
+
+impl<T: Float> Data<T> {
+    /// Get the angular frequency
+    #[must_use]
+    pub fn angular_frequency(&self) -> RadiansPerSecond<T> {
+        self.angular_frequency
+    }
+
+    /// Get the singular values in decreasing order
+    #[must_use]
+    pub fn singular_values(&self) -> &[T] {
+        &self.singular_values
+    }
+
+    /// Get the maximum singular value, zero for systems without inputs or
+    /// outputs
+    #[must_use]
+    pub fn max(&self) -> T {
+        self.singular_values
+            .first()
+            .copied()
+            .unwrap_or_else(T::zero)
+    }
+
+    /// Get the minimum singular value, zero for systems without inputs or
+    /// outputs
+    #[must_use]
+    pub fn min(&self) -> T {
+        self.singular_values.last().copied().unwrap_or_else(T::zero)
+    }
+}
+
+/// Implementation of the Iterator trait for `Sigma` struct
+impl<T: Float + RealField> Iterator for IntoIter<T> {
+    type Item = Data<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (angular_frequency, g) = self.responses.next()?;
+        let mut singular_values: Vec<_> = if g.is_empty() {
+            Vec::new()
+        } else {
+            g.singular_values().iter().copied().collect()
+        };
+        singular_values
+            .sort_unstable_by(|a, b| b.partial_cmp(a).unwrap_or(std::cmp::Ordering::Equal));
+        Some(Data {
+            angular_frequency,
+            singular_values,
+        })
+    }
+}
+
+impl<T: Float + RealField> IntoIter<T> {
+    /// Collect the data points into the parallel arrays of the angular
+    /// frequencies and of the singular values, an array for each singular
+    /// value in decreasing order.
+    ///
+    /// # Example
+    /// ```
+    /// use au::{plots::sigma::Sigma, RadiansPerSecond, Ss};
+    /// let sys = Ss::new_from_slice(1, 2, 2, &[-1.], &[0., 0.], &[0., 0.], &[2., 0., 0., 1.]);
+    /// let sigma = Sigma::new(sys, RadiansPerSecond(1.), RadiansPerSecond(10.), 1.);
+    /// let (freq, sv) = sigma.into_iter().into_vecs();
+    /// assert_eq!(2, freq.len());
+    /// assert_eq!(vec![vec![2., 2.], vec![1., 1.]], sv);
+    /// ```
+    #[must_use]
+    pub fn into_vecs(self) -> (Vec<T>, Vec<Vec<T>>) {
+        let (freq, rows) = self
+            .map(|d| (d.angular_frequency.0, d.singular_values))
+            .unzip();
+        (freq, transpose(rows))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{linear_system::continuous::Ss, transfer_function::matrix::TfMatrix};
+
+    fn diagonal() -> Ss<f64> {
+        // G(s) = diag(1/(s+1), 10/(s+10))
+        Ss::new_from_slice(
+            2,
+            2,
+            2,
+            &[-1., 0., 0., -10.],
+            &[1., 0., 0., 10.],
+            &[1., 0., 0., 1.],
+            &[0.; 4],
+        )
+    }
+
+    #[test]
+    fn singular_values_of_diagonal_system() {
+        let sigma = Sigma::new(
+            diagonal(),
+            RadiansPerSecond(0.01),
+            RadiansPerSecond(100.),
+            1.,
+        );
+        let data: Vec<_> = sigma.into_iter().collect();
+        assert_eq!(5, data.len());
+        for d in &data {
+            let w = d.angular_frequency().0;
+            let g1 = 1. / (1. + w * w).sqrt();
+            let g2 = 10. / (100. + w * w).sqrt();
+            assert_relative_eq!(g1.max(g2), d.max(), max_relative = 1e-10);
+            assert_relative_eq!(g1.min(g2), d.min(), max_relative = 1e-10);
+            assert_eq!(2, d.singular_values().len());
+        }
+    }
+
+    #[test]
+    fn transfer_matrix_and_state_space_agree() {
+        let sys = diagonal();
+        let tfm = TfMatrix::from(sys.clone());
+        let from_ss = Sigma::new(sys, RadiansPerSecond(0.1), RadiansPerSecond(10.), 0.5);
+        let from_tf = Sigma::new(tfm, RadiansPerSecond(0.1), RadiansPerSecond(10.), 0.5);
+        for (s, t) in from_ss.into_iter().zip(from_tf) {
+            assert_eq!(s.angular_frequency(), t.angular_frequency());
+            assert_relative_eq!(s.max(), t.max(), max_relative = 1e-10);
+            assert_relative_eq!(s.min(), t.min(), max_relative = 1e-10);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn sigma_fail() {
+        let _ = Sigma::new(diagonal(), RadiansPerSecond(10.), RadiansPerSecond(1.), 0.1);
+    }
+}
@@ -0,0 +1,292 @@
+//! # Time response plots
+//!
+//! Step, impulse and ramp responses of continuous systems, computed
+//! integrating the state-space representation with the Runge-Kutta method of
+//! order 4.
+//!
+//! The unit signal is applied to one input channel, the other inputs are
+//! zero, and the initial state is zero. The impulse response is the free
+//! evolution from the state given by the column of the input matrix of the
+//! channel, the impulse in the output due to the direct transmission matrix
+//! is not included.
+//!
+//! The items of the iteration are the [steps](../../linear_system/solver/struct.Step.html)
+//! of the solver, they can be collected into a
+//! [TimeResponse](../../linear_system/solver/struct.TimeResponse.html).
+
+use nalgebra::Scalar;
+use num_traits::Float;
+
+use std::{
+    fmt,
+    ops::{AddAssign, MulAssign},
+};
+
+use crate::{
+    linear_system::{
+        continuous::Ss,
+        solver::{Rk, RkConst, Step},
+    },
+    units::Seconds,
+};
+
+/// Input signal of the time response
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Response {
+    /// Unit step, `u(t) = 1`
+    Step,
+    /// Unit impulse, `u(t) = δ(t)`
+    Impulse,
+    /// Unit ramp, `u(t) = t`
+    Ramp,
+}
+
+/// Struct for the calculation of time response plots
+#[derive(Clone, Debug)]
+pub struct TimePlot<'a, T: Float + Scalar> {
+    /// System
+    sys: &'a Ss<T>,
+    /// Input signal
+    response: Response,
+    /// Input channel
+    input: usize,
+    /// Time step
+    time_step: Seconds<T>,
+    /// Number of time steps
+    n: usize,
+}
+
+impl<'a, T: Float + Scalar> TimePlot<'a, T> {
+    /// Create a `TimePlot` struct
+    ///
+    /// # Arguments
+    ///
+    /// * `sys` - System to simulate
+    /// * `response` - Input signal
+    /// * `input` - Input channel to which the signal is applied
+    /// * `time_step` - Time step of the simulation
+    /// * `n` - Number of time steps
+    ///
+    /// # Panics
+    ///
+    /// Panics if the input channel does not exist or the time step is not
+    /// strictly positive.
+    ///
+    /// # Example
+    /// ```
+    /// use au::{plots::time::{Response, TimePlot}, Seconds, Ss};
+    /// let sys = Ss::new_from_slice(1, 1, 1, &[-1.], &[1.], &[1.], &[0.]);
+    /// let plot = TimePlot::new(&sys, Response::Ramp, 0, Seconds(0.1), 10);
+    /// assert_eq!(11, plot.into_iter().count());
+    /// ```
+    pub fn new(
+        sys: &'a Ss<T>,
+        response: Response,
+        input: usize,
+        time_step: Seconds<T>,
+        n: usize,
+    ) -> Self {
+        assert!(input < sys.dim().inputs(), "Input channel is out of range");
+        assert!(
+            time_step.0 > T::zero(),
+            "Time step must be strictly positive"
+        );
+        Self {
+            sys,
+            response,
+            input,
+            time_step,
+            n,
+        }
+    }
+
+    /// Create the step response plot
+    ///
+    /// # Arguments
+    ///
+    /// * `sys` - System to simulate
+    /// * `input` - Input channel to which the step is applied
+    /// * `time_step` - Time step of the simulation
+    /// * `n` - Number of time steps
+    ///
+    /// # Panics
+    ///
+    /// Panics if the input channel does not exist or the time step is not
+    /// strictly positive.
+    ///
+    /// # Example
+    /// ```
+    /// use au::{plots::time::TimePlot, Seconds, Ss};
+    /// let sys = Ss::new_from_slice(1, 1, 1, &[-1.], &[1.], &[2.], &[0.]);
+    /// let last = TimePlot::step(&sys, 0, Seconds(0.01), 1000).into_iter().last().unwrap();
+    /// assert!((last.output()[0] - 2_f64).abs() < 1e-3);
+    /// ```
+    pub fn step(sys: &'a Ss<T>, input: usize, time_step: Seconds<T>, n: usize) -> Self {
+        Self::new(sys, Response::Step, input, time_step, n)
+    }
+
+    /// Create the impulse response plot
+    ///
+    /// # Arguments
+    ///
+    /// * `sys` - System to simulate
+    /// * `input` - Input channel to which the impulse is applied
+    /// * `time_step` - Time step of the simulation
+    /// * `n` - Number of time steps
+    ///
+    /// # Panics
+    ///
+    /// Panics if the input channel does not exist or the time step is not
+    /// strictly positive.
+    ///
+    /// # Example
+    /// ```
+    /// use au::{plots::time::TimePlot, Seconds, Ss};
+    /// let sys = Ss::new_from_slice(1, 1, 1, &[-1.], &[1.], &[2.], &[0.]);
+    /// let first = TimePlot::impulse(&sys, 0, Seconds(0.01), 100).into_iter().next().unwrap();
+    /// assert_eq!(2., first.output()[0]);
+    /// ```
+    pub fn impulse(sys: &'a Ss<T>, input: usize, time_step: Seconds<T>, n: usize) -> Self {
+        Self::new(sys, Response::Impulse, input, time_step, n)
+    }
+
+    /// Create the ramp response plot
+    ///
+    /// # Arguments
+    ///
+    /// * `sys` - System to simulate
+    /// * `input` - Input channel to which the ramp is applied
+    /// * `time_step` - Time step of the simulation
+    /// * `n` - Number of time steps
+    ///
+    /// # Panics
+    ///
+    /// Panics if the input channel does not exist or the time step is not
+    /// strictly positive.
+    pub fn ramp(sys: &'a Ss<T>, input: usize, time_step: Seconds<T>, n: usize) -> Self {
+        Self::new(sys, Response::Ramp, input, time_step, n)
+    }
+}
+
+/// Input function of the solver
+type InputFn<T> = Box<dyn Fn(Seconds<T>) -> Vec<T>>;
+
+impl<'a, T> IntoIterator for TimePlot<'a, T>
+where
+    T: AddAssign + Float + MulAssign + RkConst + Scalar,
+{
+    type Item = Step<T>;
+    type IntoIter = IntoIter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let (inputs, input) = (self.sys.dim().inputs(), self.input);
+        let unit = move |value: T| {
+            let mut u = vec![T::zero(); inputs];
+            u[input] = value;
+            u
+        };
+        let (u, x0): (InputFn<T>, Vec<T>) = match self.response {
+            Response::Step => (
+                Box::new(move |_| unit(T::one())),
+                vec![T::zero(); self.sys.dim().states()],
+            ),
+            Response::Impulse => (
+                Box::new(move |_| vec![T::zero(); inputs]),
+                self.sys.b().column(input).iter().copied().collect(),
+            ),
+            Response::Ramp => (
+                Box::new(move |t: Seconds<T>| unit(t.0)),
+                vec![T::zero(); self.sys.dim().states()],
+            ),
+        };
+        IntoIter {
+            solver: self.sys.rk4(u, &x0, self.time_step, self.n),
+        }
+    }
+}
+
+/// Struct for the time response plot data point iteration.
+pub struct IntoIter<'a, T: Float + Scalar> {
+    /// Solver of the time evolution
+    solver: Rk<'a, InputFn<T>, T>,
+}
+
+impl<'a, T: Float + Scalar> fmt::Debug for IntoIter<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IntoIter").finish()
+    }
+}
+
+impl<'a, T> Iterator for IntoIter<'a, T>
+where
+    T: AddAssign + Float + MulAssign + RkConst + Scalar,
+{
+    type Item = Step<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.solver.next()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linear_system::solver::TimeResponse;
+
+    fn first_order() -> Ss<f64> {
+        // G(s) = 1 / (s + 1)
+        Ss::new_from_slice(1, 1, 1, &[-1.], &[1.], &[1.], &[0.])
+    }
+
+    #[test]
+    fn step_response() {
+        let sys = first_order();
+        for s in TimePlot::step(&sys, 0, Seconds(0.01), 200) {
+            let expected = 1. - (-s.time().0).exp();
+            assert_relative_eq!(expected, s.output()[0], epsilon = 1e-8);
+        }
+    }
+
+    #[test]
+    fn impulse_response() {
+        let sys = first_order();
+        for s in TimePlot::impulse(&sys, 0, Seconds(0.01), 200) {
+            let expected = (-s.time().0).exp();
+            assert_relative_eq!(expected, s.output()[0], epsilon = 1e-8);
+        }
+    }
+
+    #[test]
+    fn ramp_response() {
+        let sys = first_order();
+        for s in TimePlot::ramp(&sys, 0, Seconds(0.01), 200) {
+            let t = s.time().0;
+            let expected = t - 1. + (-t).exp();
+            assert_relative_eq!(expected, s.output()[0], epsilon = 1e-8);
+        }
+    }
+
+    #[test]
+    fn second_input_channel() {
+        let sys = Ss::new_from_slice(1, 2, 1, &[-1.], &[1., 3.], &[1.], &[0., 0.]);
+        let resp: TimeResponse<_> = TimePlot::step(&sys, 1, Seconds(0.01), 1000)
+            .into_iter()
+            .collect();
+        assert_eq!(1001, resp.len());
+        assert_relative_eq!(3., resp.output(0)[1000], max_relative = 1e-3);
+    }
+
+    #[test]
+    #[should_panic]
+    fn input_out_of_range() {
+        let sys = first_order();
+        let _ = TimePlot::step(&sys, 1, Seconds(0.1), 10);
+    }
+
+    #[test]
+    #[should_panic]
+    fn non_positive_time_step() {
+        let sys = first_order();
+        let _ = TimePlot::ramp(&sys, 0, Seconds(0.), 10);
+    }
+}
@@ -864,7 +864,7 @@ mod tests {
     fn poly_sub_panic() {
         let p = poly!(1, 2, 3) - 3_u32;
         // The assert is used only to avoid code optimization in release mode.
-        assert_eq!(p.coeffs, vec![]);
+        assert_eq!(p.coeffs, Vec::<u32>::new());
     }
 
     #[test]
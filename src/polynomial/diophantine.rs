@@ -0,0 +1,127 @@
+//! Solution of the polynomial Diophantine equation through the Sylvester
+//! matrix of the polynomials.
+
+use nalgebra::{ComplexField, DMatrix, DVector, RealField};
+use num_traits::Float;
+
+use crate::polynomial::Poly;
+
+impl<T: ComplexField + Float + RealField> Poly<T> {
+    /// Solve the Diophantine equation
+    /// ```text
+    /// a*x + b*y = c
+    /// ```
+    /// The solution is the one with the minimum degree of `y`, lower than
+    /// the degree of `a`.
+    ///
+    /// The equation is transformed into the linear system whose matrix is
+    /// the Sylvester matrix of `a` and `b`, the solution is unique if `a`
+    /// and `b` are coprime. Returns `None` if `a` is a constant or `a` and
+    /// `b` have a common factor.
+    ///
+    /// The return value is `(x, y)`.
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - Polynomial that multiplies `x`
+    /// * `b` - Polynomial that multiplies `y`
+    /// * `c` - Right hand side polynomial
+    ///
+    /// # Example
+    /// ```
+    /// use au::poly;
+    /// use au::polynomial::Poly;
+    /// let a = poly!(-1., 1.);
+    /// let b = poly!(2.);
+    /// let (x, y) = Poly::diophantine(&a, &b, &poly!(0.5, -1.5, 1.)).unwrap();
+    /// // (z - 1)*(z - 0.5) + 2*0 = z^2 - 1.5z + 0.5
+    /// assert_eq!(poly!(-0.5, 1.), x.roundoff(&1e-12));
+    /// assert_eq!(poly!(0.), y.roundoff(&1e-12));
+    /// ```
+    #[must_use]
+    pub fn diophantine(a: &Self, b: &Self, c: &Self) -> Option<(Self, Self)> {
+        let n = a.degree().filter(|&n| n > 0)?;
+        let m = b.degree().unwrap_or(0);
+        let dc = c.degree().unwrap_or(0);
+        // Degree of a*x
+        let big_n = dc.max(m + n - 1).max(n);
+        let nx = big_n - n + 1;
+        let size = big_n + 1;
+
+        // Columns of the shifted coefficients of a for x, of b for y.
+        let mut sylvester = DMatrix::zeros(size, size);
+        for j in 0..nx {
+            for (i, &ca) in a.coeffs.iter().enumerate() {
+                sylvester[(i + j, j)] = ca;
+            }
+        }
+        for j in 0..n {
+            for (i, &cb) in b.coeffs.iter().enumerate() {
+                sylvester[(i + j, nx + j)] = cb;
+            }
+        }
+
+        let svd = sylvester.clone().svd(false, false);
+        let max = svd.singular_values.max();
+        let min = svd.singular_values.min();
+        let tol = Float::sqrt(T::epsilon()) * max;
+        if min <= tol {
+            return None;
+        }
+
+        let mut rhs = DVector::zeros(size);
+        for (i, &cc) in c.coeffs.iter().enumerate() {
+            rhs[i] = cc;
+        }
+        let sol = sylvester.lu().solve(&rhs)?;
+        let x = Self::new_from_coeffs(&sol.as_slice()[..nx]);
+        let y = Self::new_from_coeffs(&sol.as_slice()[nx..]);
+        Some((x, y))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::poly;
+
+    fn check(a: &Poly<f64>, b: &Poly<f64>, c: &Poly<f64>) -> (Poly<f64>, Poly<f64>) {
+        let (x, y) = Poly::diophantine(a, b, c).unwrap();
+        assert!(y.degree().unwrap_or(0) < a.degree().unwrap());
+        let lhs = a * &x + b * &y;
+        let lhs = lhs.coeffs();
+        let rhs = c.coeffs();
+        for i in 0..lhs.len().max(rhs.len()) {
+            let l = lhs.get(i).copied().unwrap_or(0.);
+            let r = rhs.get(i).copied().unwrap_or(0.);
+            assert_relative_eq!(r, l, epsilon = 1e-10);
+        }
+        (x, y)
+    }
+
+    #[test]
+    fn coprime_polynomials() {
+        let a = Poly::new_from_roots(&[0.5, 0.8]);
+        let b = poly!(0.3, 1.);
+        let (x, y) = check(&a, &b, &Poly::new_from_roots(&[0.1, 0.2, 0.3]));
+        assert_eq!(Some(1), x.degree());
+        assert_eq!(Some(1), y.degree());
+        check(&a, &b, &poly!(1.));
+        check(&a, &poly!(2.), &Poly::new_from_roots(&[0.1, 0.2, 0.3, 0.4]));
+    }
+
+    #[test]
+    fn common_factor() {
+        let a = Poly::new_from_roots(&[0.5, 0.8]);
+        let b = Poly::new_from_roots(&[0.5]);
+        assert_eq!(None, Poly::diophantine(&a, &b, &poly!(1., 1., 1.)));
+    }
+
+    #[test]
+    fn constant_a() {
+        assert_eq!(
+            None,
+            Poly::diophantine(&poly!(2.), &poly!(1., 1.), &poly!(1.))
+        );
+    }
+}
@@ -14,9 +14,11 @@
 //! * evaluation using real or complex numbers
 //! * coefficient indexing
 //! * zero and unit polynomials
+//! * solution of the Diophantine equation
 
 pub mod arithmetic;
 mod convex_hull;
+mod diophantine;
 mod fft;
 mod roots;
 
@@ -36,10 +38,47 @@ use crate::iterator;
 ///
 /// `p(x) = c0 + c1*x + c2*x^2 + ...`
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(
+    feature = "serde-serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(
+        bound(deserialize = "T: Clone + PartialEq + Zero + serde::Deserialize<'de>"),
+        try_from = "PolyData<T>"
+    )
+)]
 pub struct Poly<T> {
     coeffs: Vec<T>,
 }
 
+/// Serialized polynomial, the coefficients are checked before the creation
+/// of the polynomial.
+#[cfg(feature = "serde-serialize")]
+#[derive(serde::Deserialize)]
+struct PolyData<T> {
+    /// Coefficients from the lowest to the highest degree
+    coeffs: Vec<T>,
+}
+
+#[cfg(feature = "serde-serialize")]
+impl<T: Clone + PartialEq + Zero> std::convert::TryFrom<PolyData<T>> for Poly<T> {
+    type Error = crate::error::Error;
+
+    /// Create the polynomial from the serialized coefficients, the zeros of
+    /// the high degree terms are trimmed.
+    ///
+    /// # Errors
+    ///
+    /// It returns an error if there are no coefficients.
+    fn try_from(data: PolyData<T>) -> Result<Self, Self::Error> {
+        if data.coeffs.is_empty() {
+            return Err(crate::error::Error::new_internal(
+                crate::error::ErrorKind::InvalidData,
+            ));
+        }
+        Ok(Self::new_from_coeffs_iter(data.coeffs))
+    }
+}
+
 /// Macro shortcut to crate a polynomial from its coefficients.
 ///
 /// # Example
@@ -752,6 +791,18 @@ mod tests {
     use num_complex::Complex;
     use proptest::prelude::*;
 
+    #[cfg(feature = "serde-serialize")]
+    #[test]
+    fn serde_round_trip() {
+        let p = poly!(1., -2., 3.);
+        let json = serde_json::to_string(&p).unwrap();
+        assert_eq!(p, serde_json::from_str(&json).unwrap());
+
+        let p: Poly<f64> = serde_json::from_str(r#"{"coeffs":[1.0,2.0,0.0]}"#).unwrap();
+        assert_eq!(Some(1), p.degree());
+        assert!(serde_json::from_str::<Poly<f64>>(r#"{"coeffs":[]}"#).is_err());
+    }
+
     #[test]
     fn poly_formatting() {
         assert_eq!("0", format!("{}", Poly::<i16>::zero()));
@@ -128,6 +128,26 @@ impl<T: Clone + Mul<Output = T> + PartialEq + Zero> Add<&T> for Rf<T> {
     }
 }
 
+/// Implementation of rational function and polynomial addition
+impl<T: Clone + Mul<Output = T> + PartialEq + Zero> Add<Poly<T>> for Rf<T> {
+    type Output = Self;
+
+    fn add(mut self, rhs: Poly<T>) -> Self {
+        self.num = self.num + &self.den * &rhs;
+        self
+    }
+}
+
+/// Implementation of rational function and polynomial addition
+impl<T: Clone + Mul<Output = T> + PartialEq + Zero> Add<&Poly<T>> for Rf<T> {
+    type Output = Self;
+
+    fn add(mut self, rhs: &Poly<T>) -> Self {
+        self.num = self.num + &self.den * rhs;
+        self
+    }
+}
+
 /// Implementation of rational function subtraction
 #[allow(clippy::suspicious_arithmetic_impl)]
 impl<T: Clone + Neg<Output = T> + PartialEq + Sub<Output = T> + Zero + One> Sub for &Rf<T> {
@@ -218,6 +238,64 @@ impl<T: Clone + One + PartialEq + Zero> Mul<&Rf<T>> for Rf<T> {
     }
 }
 
+// The multiplication by a scalar is implemented for concrete types only,
+// a generic `Mul<T> for Rf<T>` would make the type of the left operand of
+// `Rf::zero() * rf` ambiguous.
+macro_rules! impl_mul_scalar_for_rf {
+    (
+        $(#[$meta:meta])*
+            $f:ty
+    ) => {
+        $(#[$meta])*
+        impl Mul<$f> for Rf<$f> {
+            type Output = Self;
+
+            fn mul(mut self, rhs: $f) -> Self {
+                self.num = self.num * rhs;
+                self
+            }
+        }
+        $(#[$meta])*
+        impl Mul<&$f> for Rf<$f> {
+            type Output = Self;
+
+            fn mul(mut self, rhs: &$f) -> Self {
+                self.num = self.num * rhs;
+                self
+            }
+        }
+    };
+}
+
+impl_mul_scalar_for_rf!(
+    /// Implementation of rational function and f32 multiplication
+    f32
+);
+impl_mul_scalar_for_rf!(
+    /// Implementation of rational function and f64 multiplication
+    f64
+);
+
+/// Implementation of rational function and polynomial multiplication
+impl<T: Clone + Mul<Output = T> + PartialEq + Zero> Mul<Poly<T>> for Rf<T> {
+    type Output = Self;
+
+    fn mul(mut self, rhs: Poly<T>) -> Self {
+        self.num = self.num * rhs;
+        self
+    }
+}
+
+/// Implementation of rational function and polynomial multiplication
+impl<T: Clone + Mul<Output = T> + PartialEq + Zero> Mul<&Poly<T>> for Rf<T> {
+    type Output = Self;
+
+    fn mul(mut self, rhs: &Poly<T>) -> Self {
+        self.num = self.num * rhs;
+        self
+    }
+}
+
 /// Implementation of rational function division
 #[allow(clippy::suspicious_arithmetic_impl)]
 impl<T: Clone + One + PartialEq + Zero> Div for &Rf<T> {
@@ -347,6 +425,14 @@ mod tests {
         assert_eq!(expected, actual);
     }
 
+    #[test]
+    fn add_polynomial() {
+        let rf = Rf::new(poly!(1., 2.), poly!(1., 5.));
+        let expected = Rf::new(poly!(2., 8., 5.), poly!(1., 5.));
+        assert_eq!(expected, rf.clone() + poly!(1., 1.));
+        assert_eq!(expected, rf + &poly!(1., 1.));
+    }
+
     #[test]
     fn sub_references() {
         let rf1 = Rf::new(poly!(-1., 9.), poly!(4., -1.));
@@ -415,6 +501,23 @@ mod tests {
         assert_eq!(Rf::zero(), Rf::zero() * &expected);
     }
 
+    #[test]
+    #[allow(clippy::op_ref)]
+    fn mul_scalar() {
+        let rf = Rf::new(poly!(1., 2.), poly!(1., 5.));
+        let expected = Rf::new(poly!(3., 6.), poly!(1., 5.));
+        assert_eq!(expected, rf.clone() * 3.);
+        assert_eq!(expected, rf * &3.);
+    }
+
+    #[test]
+    fn mul_polynomial() {
+        let rf = Rf::new(poly!(1., 2.), poly!(1., 5.));
+        let expected = Rf::new(poly!(0., 1., 2.), poly!(1., 5.));
+        assert_eq!(expected, rf.clone() * poly!(0., 1.));
+        assert_eq!(expected, rf * &poly!(0., 1.));
+    }
+
     #[test]
     fn div_values() {
         let rf1 = Rf::new(poly!(1., 2., 3.), poly!(1., 5.));
@@ -8,7 +8,7 @@
 //!        a_m*x^m + a_(m-1)*x^(m-1) + ... + a_1*x + a_0
 //! ```
 
-use nalgebra::RealField;
+use nalgebra::{DMatrix, DVector, RealField};
 use num_complex::Complex;
 use num_traits::{Float, One, Zero};
 
@@ -24,6 +24,11 @@ mod arithmetic;
 
 /// Rational function
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde-serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound(deserialize = "T: Clone + PartialEq + Zero + serde::Deserialize<'de>"))
+)]
 pub struct Rf<T> {
     /// Rational function numerator
     num: Poly<T>,
@@ -99,6 +104,83 @@ impl<T: Clone + PartialEq + Zero> Rf<T> {
             _ => 0,
         }
     }
+
+    /// Check if the rational function is proper, i.e. the degree of the
+    /// numerator is not greater than the degree of the denominator.
+    ///
+    /// # Example
+    /// ```
+    /// use au::{poly, Rf};
+    /// let rf = Rf::new(poly!(1., 2.), poly!(-4., 6., -2.));
+    /// assert!(rf.is_proper());
+    /// let rf = Rf::new(poly!(1., 2., 3.), poly!(-4., 6.));
+    /// assert!(!rf.is_proper());
+    /// ```
+    #[must_use]
+    pub fn is_proper(&self) -> bool {
+        self.relative_degree() >= 0
+    }
+
+    /// Check if the rational function is strictly proper, i.e. the degree of
+    /// the numerator is lower than the degree of the denominator.
+    ///
+    /// # Example
+    /// ```
+    /// use au::{poly, Rf};
+    /// let rf = Rf::new(poly!(1., 2.), poly!(-4., 6., -2.));
+    /// assert!(rf.is_strictly_proper());
+    /// let rf = Rf::new(poly!(1., 2.), poly!(-4., 6.));
+    /// assert!(!rf.is_strictly_proper());
+    /// ```
+    #[must_use]
+    pub fn is_strictly_proper(&self) -> bool {
+        self.relative_degree() > 0
+    }
+
+    /// Check if the rational function is biproper, i.e. the degree of the
+    /// numerator is equal to the degree of the denominator. A biproper
+    /// function has a proper inverse.
+    ///
+    /// # Example
+    /// ```
+    /// use au::{poly, Rf};
+    /// let rf = Rf::new(poly!(1., 2.), poly!(-4., 6.));
+    /// assert!(rf.is_biproper());
+    /// ```
+    #[must_use]
+    pub fn is_biproper(&self) -> bool {
+        self.relative_degree() == 0
+    }
+}
+
+impl<T: Float> Rf<T> {
+    /// Split the rational function into a polynomial part and a strictly
+    /// proper rational function.
+    ///
+    /// ```text
+    /// f(x) = q(x) + r(x) / a(x)
+    /// ```
+    ///
+    /// The polynomial part is zero if the rational function is strictly proper.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the denominator is zero.
+    ///
+    /// # Example
+    /// ```
+    /// use au::{poly, Rf};
+    /// let rf = Rf::new(poly!(1., 0., 1.), poly!(1., 1.));
+    /// let (q, r) = rf.make_proper();
+    /// assert_eq!(poly!(-1., 1.), q);
+    /// assert_eq!(Rf::new(poly!(2.), poly!(1., 1.)), r);
+    /// ```
+    #[must_use]
+    pub fn make_proper(&self) -> (Poly<T>, Self) {
+        let q = &self.num / &self.den;
+        let r = &self.num % &self.den;
+        (q, Self::new(r, self.den.clone()))
+    }
 }
 
 impl<T: Float + RealField> Rf<T> {
@@ -125,6 +207,66 @@ impl<T: Float + RealField> Rf<T> {
     pub fn complex_zeros(&self) -> Vec<Complex<T>> {
         self.num.complex_roots()
     }
+
+    /// Build the [m/n] Padé approximant of a function given the coefficients
+    /// of its truncated Taylor series around zero.
+    ///
+    /// ```text
+    /// f(x) = c_0 + c_1*x + c_2*x^2 + ...
+    ///
+    ///        p_0 + p_1*x + ... + p_m*x^m
+    /// R(x) = ---------------------------
+    ///         1 + q_1*x + ... + q_n*x^n
+    /// ```
+    ///
+    /// The denominator is normalized so that its constant term is one.
+    ///
+    /// # Arguments
+    ///
+    /// * `coeffs` - Taylor series coefficients, in ascending order of power,
+    ///   at least `m + n + 1` are required
+    /// * `m` - degree of the numerator
+    /// * `n` - degree of the denominator
+    ///
+    /// Returns `None` if there are not enough coefficients or if the
+    /// approximant does not exist (the linear system is singular).
+    ///
+    /// # Example
+    /// ```
+    /// use au::{poly, Rf};
+    /// // Exponential function: 1 + x + x^2/2
+    /// let rf = Rf::pade_from_series(&[1., 1., 0.5], 1, 1).unwrap();
+    /// assert_eq!(&poly!(1., 0.5), rf.num());
+    /// assert_eq!(&poly!(1., -0.5), rf.den());
+    /// ```
+    #[must_use]
+    pub fn pade_from_series(coeffs: &[T], m: usize, n: usize) -> Option<Self> {
+        if coeffs.len() < m + n + 1 {
+            return None;
+        }
+        // Coefficient c_(i-j), zero for negative indices.
+        let c = |i: usize, j: usize| if i >= j { coeffs[i - j] } else { T::zero() };
+
+        // Denominator coefficients q_1..q_n are the solution of the system
+        // sum_(j=1..n) q_j*c_(k-j) = -c_k, for k = m+1, ..., m+n
+        let mut q = vec![T::one()];
+        if n > 0 {
+            let a = DMatrix::from_fn(n, n, |r, j| c(m + 1 + r, j + 1));
+            let b = DVector::from_fn(n, |r, _| -coeffs[m + 1 + r]);
+            let sol = a.lu().solve(&b)?;
+            q.extend(sol.iter());
+        }
+
+        // Numerator coefficients p_k = sum_(j=0..min(k,n)) q_j*c_(k-j)
+        let p: Vec<_> = (0..=m)
+            .map(|k| (0..=k.min(n)).fold(T::zero(), |acc, j| acc + q[j] * coeffs[k - j]))
+            .collect();
+
+        Some(Self::new(
+            Poly::new_from_coeffs(&p),
+            Poly::new_from_coeffs(&q),
+        ))
+    }
 }
 
 impl<T: Clone + Div<Output = T> + One + PartialEq + Zero> Rf<T> {
@@ -292,6 +434,68 @@ mod tests {
         );
     }
 
+    #[test]
+    fn properness() {
+        let strictly = Rf::new(poly!(1., 2.), poly!(-4., 6., -2.));
+        assert!(strictly.is_proper());
+        assert!(strictly.is_strictly_proper());
+        assert!(!strictly.is_biproper());
+
+        let bi = Rf::new(poly!(1., 2.), poly!(-4., 6.));
+        assert!(bi.is_proper());
+        assert!(!bi.is_strictly_proper());
+        assert!(bi.is_biproper());
+
+        let improper = Rf::new(poly!(1., 2., 3.), poly!(-4., 6.));
+        assert!(!improper.is_proper());
+        assert!(!improper.is_strictly_proper());
+        assert!(!improper.is_biproper());
+
+        assert!(Rf::new(Poly::zero(), poly!(1., 1.)).is_strictly_proper());
+    }
+
+    #[test]
+    fn pade_exponential() {
+        let series = [1., 1., 1. / 2., 1. / 6., 1. / 24.];
+        let rf = Rf::pade_from_series(&series, 2, 2).unwrap();
+        let num = [1., 0.5, 1. / 12.];
+        let den = [1., -0.5, 1. / 12.];
+        for (a, e) in rf.num().as_slice().iter().zip(&num) {
+            assert_relative_eq!(*e, *a, max_relative = 1e-12);
+        }
+        for (a, e) in rf.den().as_slice().iter().zip(&den) {
+            assert_relative_eq!(*e, *a, max_relative = 1e-12);
+        }
+        assert_relative_eq!(0.1.exp(), rf.eval(&0.1), max_relative = 1e-6);
+    }
+
+    #[test]
+    fn pade_polynomial() {
+        let rf = Rf::pade_from_series(&[1., 2., 3.], 2, 0).unwrap();
+        assert_eq!(Rf::new(poly!(1., 2., 3.), poly!(1.)), rf);
+    }
+
+    #[test]
+    fn pade_failure() {
+        assert!(Rf::pade_from_series(&[1., 2.], 1, 1).is_none());
+        // Singular system: c_1 = 0 for [1/1].
+        assert!(Rf::<f64>::pade_from_series(&[1., 0., 1.], 1, 1).is_none());
+    }
+
+    #[test]
+    fn make_proper() {
+        let rf = Rf::new(poly!(1., 3., 3., 1.), poly!(2., 1.));
+        let (q, r) = rf.make_proper();
+        assert!(r.is_strictly_proper());
+        let x = 1.7;
+        assert_relative_eq!(rf.eval(&x), q.eval(&x) + r.eval(&x), max_relative = 1e-12);
+
+        let strictly = Rf::new(poly!(1.), poly!(2., 1.));
+        let (q, r) = strictly.make_proper();
+        assert!(q.is_zero());
+        assert_eq!(strictly, r);
+    }
+
     #[test]
     fn evaluation() {
         let rf = Rf::new(poly!(-0.75, 0.25), poly!(0.75, 0.75, 1.));
@@ -0,0 +1,116 @@
+//! # Serialization of floating point values
+//!
+//! Serialization of floating point values that may be infinite or not a
+//! number, as the unbounded limits of the controllers or the missing integral
+//! action of a PID. Formats as JSON have no representation of these values,
+//! so they are written as the strings `"inf"`, `"-inf"` and `"nan"`.
+//!
+//! The functions of this module are used with the `serde(with = ...)`
+//! attribute.
+
+use num_traits::Float;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+/// Floating point value that may be infinite or not a number
+struct Value<T>(T);
+
+impl<T: Float> Serialize for Value<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let x = self.0;
+        if x.is_finite() {
+            serializer.serialize_f64(x.to_f64().unwrap())
+        } else if x.is_nan() {
+            serializer.serialize_str("nan")
+        } else if x > T::zero() {
+            serializer.serialize_str("inf")
+        } else {
+            serializer.serialize_str("-inf")
+        }
+    }
+}
+
+/// Serialized representation of the value
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Repr {
+    /// Finite value
+    Number(f64),
+    /// Infinite value or not a number
+    Text(String),
+}
+
+impl<'de, T: Float> Deserialize<'de> for Value<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let x = match Repr::deserialize(deserializer)? {
+            Repr::Number(x) => x,
+            Repr::Text(s) => match s.as_str() {
+                "inf" => f64::INFINITY,
+                "-inf" => f64::NEG_INFINITY,
+                "nan" => f64::NAN,
+                _ => {
+                    return Err(de::Error::invalid_value(
+                        de::Unexpected::Str(&s),
+                        &"a number, \"inf\", \"-inf\" or \"nan\"",
+                    ))
+                }
+            },
+        };
+        T::from(x)
+            .map(Value)
+            .ok_or_else(|| de::Error::custom("value out of the range of the type"))
+    }
+}
+
+/// Serialize a floating point value.
+pub(crate) fn serialize<T: Float, S: Serializer>(x: &T, serializer: S) -> Result<S::Ok, S::Error> {
+    Value(*x).serialize(serializer)
+}
+
+/// Deserialize a floating point value.
+pub(crate) fn deserialize<'de, T: Float, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<T, D::Error> {
+    Value::deserialize(deserializer).map(|v| v.0)
+}
+
+/// Serialization of pairs of floating point values, as limits.
+pub(crate) mod pair {
+    use super::*;
+
+    /// Serialize a pair of floating point values.
+    pub(crate) fn serialize<T: Float, S: Serializer>(
+        x: &(T, T),
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        (Value(x.0), Value(x.1)).serialize(serializer)
+    }
+
+    /// Deserialize a pair of floating point values.
+    pub(crate) fn deserialize<'de, T: Float, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<(T, T), D::Error> {
+        <(Value<T>, Value<T>)>::deserialize(deserializer).map(|(a, b)| (a.0, b.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn non_finite_values() {
+        for &x in &[1.5, -2., f64::INFINITY, f64::NEG_INFINITY] {
+            let json = serde_json::to_string(&Value(x)).unwrap();
+            let back: Value<f64> = serde_json::from_str(&json).unwrap();
+            assert_eq!(x, back.0);
+        }
+        assert_eq!(
+            "\"-inf\"",
+            serde_json::to_string(&Value(f32::NEG_INFINITY)).unwrap()
+        );
+        let nan: Value<f32> = serde_json::from_str("\"nan\"").unwrap();
+        assert!(nan.0.is_nan());
+        assert!(serde_json::from_str::<Value<f64>>("\"infinity\"").is_err());
+        assert!(serde_json::from_str::<Value<f64>>("null").is_err());
+    }
+}
@@ -2,7 +2,15 @@
 
 pub mod continuous {
     //! Collection of continuous signals.
-    use crate::units::{RadiansPerSecond, Seconds};
+    //!
+    //! The signals are functions of time that return the input vector, as
+    //! required by the time evolution of continuous systems; they can be
+    //! sampled with [`sample`](fn.sample.html) for the simulation of sampled
+    //! inputs.
+    use crate::{
+        enums::Sweep,
+        units::{RadiansPerSecond, Seconds},
+    };
     use num_traits::Float;
 
     /// Zero input function
@@ -58,6 +66,488 @@ pub mod continuous {
         move |t| vec![a * T::sin(omega.0 * t.0 - phi)]
     }
 
+    /// Sine function
+    ///
+    /// `a * sin(omega*t - phi)`
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - Sine amplitude
+    /// * `omega` - Sine pulse in radians per second
+    /// * `phi` - Sine phase in radians
+    /// * `size` - Output size
+    pub fn sine<T: Float>(
+        a: T,
+        omega: RadiansPerSecond<T>,
+        phi: T,
+        size: usize,
+    ) -> impl Fn(Seconds<T>) -> Vec<T> {
+        move |t| vec![a * T::sin(omega.0 * t.0 - phi); size]
+    }
+
+    /// Ramp function
+    ///
+    /// `k * t`
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - Ramp slope
+    /// * `size` - Output size
+    pub fn ramp<T: Float>(k: T, size: usize) -> impl Fn(Seconds<T>) -> Vec<T> {
+        move |t| vec![k * t.0; size]
+    }
+
+    /// Chirp function, sine whose frequency changes with time from `omega0`
+    /// at the initial time to `omega1` at the `duration` time.
+    ///
+    /// After the `duration` time the frequency continues to change with the
+    /// same law.
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - Sine amplitude
+    /// * `omega0` - Initial pulse in radians per second
+    /// * `omega1` - Final pulse in radians per second
+    /// * `duration` - Duration of the sweep
+    /// * `sweep` - Law of the frequency change
+    /// * `size` - Output size
+    ///
+    /// # Panics
+    ///
+    /// Panics if the duration is not positive or the pulses are not positive
+    /// for the logarithmic sweep.
+    ///
+    /// # Example
+    /// ```
+    /// use au::{enums::Sweep, signals::continuous::chirp, RadiansPerSecond, Seconds};
+    /// let c = chirp(1., RadiansPerSecond(1.), RadiansPerSecond(10.), Seconds(10.), Sweep::Linear, 1);
+    /// assert_eq!(0., c(Seconds(0.))[0]);
+    /// ```
+    pub fn chirp<T: Float>(
+        a: T,
+        omega0: RadiansPerSecond<T>,
+        omega1: RadiansPerSecond<T>,
+        duration: Seconds<T>,
+        sweep: Sweep,
+        size: usize,
+    ) -> impl Fn(Seconds<T>) -> Vec<T> {
+        assert!(duration.0 > T::zero(), "Duration must be positive");
+        let (w0, w1, d) = (omega0.0, omega1.0, duration.0);
+        if sweep == Sweep::Logarithmic {
+            assert!(
+                w0 > T::zero() && w1 > T::zero(),
+                "Pulses must be positive for the logarithmic sweep"
+            );
+        }
+        move |t| {
+            let phase = match sweep {
+                Sweep::Linear => w0 * t.0 + (w1 - w0) * t.0 * t.0 / (d + d),
+                Sweep::Logarithmic => {
+                    let ln_k = (w1 / w0).ln();
+                    if ln_k == T::zero() {
+                        w0 * t.0
+                    } else {
+                        w0 * d / ln_k * ((ln_k * t.0 / d).exp() - T::one())
+                    }
+                }
+            };
+            vec![a * phase.sin(); size]
+        }
+    }
+
+    /// Square wave, equal to `a` for the first fraction `duty` of each period
+    /// and to `-a` for the rest of the period.
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - Amplitude
+    /// * `period` - Period of the wave
+    /// * `duty` - Fraction of the period at the high value, between 0 and 1
+    /// * `size` - Output size
+    ///
+    /// # Panics
+    ///
+    /// Panics if the period is not positive or the duty is not between 0 and 1.
+    pub fn square<T: Float>(
+        a: T,
+        period: Seconds<T>,
+        duty: T,
+        size: usize,
+    ) -> impl Fn(Seconds<T>) -> Vec<T> {
+        assert!(period.0 > T::zero(), "Period must be positive");
+        assert!(
+            duty >= T::zero() && duty <= T::one(),
+            "Duty must be between 0 and 1"
+        );
+        move |t| {
+            let v = if phase(t, period) < duty { a } else { -a };
+            vec![v; size]
+        }
+    }
+
+    /// Sawtooth wave, rising linearly from `-a` to `a` in each period.
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - Amplitude
+    /// * `period` - Period of the wave
+    /// * `size` - Output size
+    ///
+    /// # Panics
+    ///
+    /// Panics if the period is not positive.
+    pub fn sawtooth<T: Float>(
+        a: T,
+        period: Seconds<T>,
+        size: usize,
+    ) -> impl Fn(Seconds<T>) -> Vec<T> {
+        assert!(period.0 > T::zero(), "Period must be positive");
+        move |t| {
+            let two = T::one() + T::one();
+            vec![a * (two * phase(t, period) - T::one()); size]
+        }
+    }
+
+    /// Fraction of the period elapsed at the given time, in [0, 1).
+    fn phase<T: Float>(t: Seconds<T>, period: Seconds<T>) -> T {
+        let x = t.0 / period.0;
+        x - x.floor()
+    }
+
+    /// Band limited white noise, with gaussian distribution and zero mean.
+    ///
+    /// The noise is constant for each interval of `ts` length and its value
+    /// depends only on the time and on the seed, so the function can be
+    /// evaluated multiple times at the same time by the solvers.
+    /// Each output has independent values.
+    ///
+    /// # Arguments
+    ///
+    /// * `sigma` - Standard deviation of the noise
+    /// * `ts` - Sample time of the noise
+    /// * `seed` - Seed of the random number generator
+    /// * `size` - Output size
+    ///
+    /// # Panics
+    ///
+    /// Panics if the sample time is not positive.
+    ///
+    /// # Example
+    /// ```
+    /// use au::{signals::continuous::white_noise, Seconds};
+    /// let noise = white_noise(0.5, Seconds(0.1), 42, 2);
+    /// assert_eq!(noise(Seconds(1.02)), noise(Seconds(1.07)));
+    /// ```
+    pub fn white_noise<T: Float>(
+        sigma: T,
+        ts: Seconds<T>,
+        seed: u64,
+        size: usize,
+    ) -> impl Fn(Seconds<T>) -> Vec<T> {
+        assert!(ts.0 > T::zero(), "Sample time must be positive");
+        move |t| {
+            #[allow(clippy::cast_sign_loss)]
+            let k = (t.0 / ts.0).floor().to_i64().unwrap_or_default() as u64;
+            let mut state = seed ^ k.wrapping_mul(0x9E37_79B9_7F4A_7C15);
+            (0..size)
+                .map(|_| {
+                    // Box-Muller transform.
+                    let u1 = 1. - uniform(&mut state);
+                    let u2 = uniform(&mut state);
+                    let n = (-2. * u1.ln()).sqrt() * (2. * std::f64::consts::PI * u2).cos();
+                    sigma * T::from(n).unwrap()
+                })
+                .collect()
+        }
+    }
+
+    /// Pseudo random number uniformly distributed in the interval [0, 1),
+    /// generated with the SplitMix64 algorithm.
+    ///
+    /// # Arguments
+    ///
+    /// * `state` - State of the generator, updated at each call
+    fn uniform(state: &mut u64) -> f64 {
+        *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = *state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+        // The 53 most significant bits fit exactly the mantissa of f64.
+        #[allow(clippy::cast_precision_loss)]
+        let x = (z >> 11) as f64;
+        x / 9_007_199_254_740_992.
+    }
+
+    /// Pseudo random binary sequence, equal to `a` or `-a`.
+    ///
+    /// The sequence is generated by a linear feedback shift register of
+    /// `bits` length and has the maximum length `2^bits - 1`, then it
+    /// repeats. Each value is held for the `ts` time. All outputs are equal.
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - Amplitude
+    /// * `ts` - Time of each value of the sequence
+    /// * `bits` - Length of the shift register, between 2 and 16
+    /// * `size` - Output size
+    ///
+    /// # Panics
+    ///
+    /// Panics if the time is not positive or the length of the register is
+    /// not between 2 and 16.
+    ///
+    /// # Example
+    /// ```
+    /// use au::{signals::continuous::prbs, Seconds};
+    /// let p = prbs(1., Seconds(0.5), 3, 1);
+    /// let seq: Vec<_> = (0..7).map(|i| p(Seconds(0.5 * f64::from(i)))[0]).collect();
+    /// // The maximum length sequence has one more high value than low values.
+    /// assert_eq!(1., seq.iter().sum::<f64>());
+    /// assert_eq!(seq[0], p(Seconds(3.5))[0]);
+    /// ```
+    pub fn prbs<T: Float>(
+        a: T,
+        ts: Seconds<T>,
+        bits: u32,
+        size: usize,
+    ) -> impl Fn(Seconds<T>) -> Vec<T> {
+        assert!(ts.0 > T::zero(), "Time of each value must be positive");
+        let sequence = lfsr_sequence(bits);
+        move |t| {
+            let len = T::from(sequence.len()).unwrap();
+            let k = (t.0 / ts.0).floor();
+            let k = (k - (k / len).floor() * len).to_usize().unwrap_or_default();
+            let v = if sequence[k] { a } else { -a };
+            vec![v; size]
+        }
+    }
+
+    /// Maximum length sequence of a Fibonacci linear feedback shift register.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of the register is not between 2 and 16.
+    fn lfsr_sequence(bits: u32) -> Vec<bool> {
+        // Taps of the maximum length registers, counted from 1.
+        let taps: &[u32] = match bits {
+            2 => &[2, 1],
+            3 => &[3, 2],
+            4 => &[4, 3],
+            5 => &[5, 3],
+            6 => &[6, 5],
+            7 => &[7, 6],
+            8 => &[8, 6, 5, 4],
+            9 => &[9, 5],
+            10 => &[10, 7],
+            11 => &[11, 9],
+            12 => &[12, 11, 10, 4],
+            13 => &[13, 12, 11, 8],
+            14 => &[14, 13, 12, 2],
+            15 => &[15, 14],
+            16 => &[16, 15, 13, 4],
+            _ => panic!("Register length must be between 2 and 16"),
+        };
+        let mut state = 1_u32;
+        (0..(1_u32 << bits) - 1)
+            .map(|_| {
+                let out = state & 1 == 1;
+                let bit = taps.iter().fold(0, |b, &t| b ^ (state >> (bits - t)) & 1);
+                state = (state >> 1) | (bit << (bits - 1));
+                out
+            })
+            .collect()
+    }
+
+    /// Sum of two signals
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - First signal
+    /// * `b` - Second signal
+    ///
+    /// # Panics
+    ///
+    /// The returned function panics if the signals have different sizes.
+    ///
+    /// # Example
+    /// ```
+    /// use au::{signals::continuous::{delay, step, sum}, Seconds};
+    /// // Step at t = 1 plus a disturbance at t = 5.
+    /// let input = sum(delay(step(1., 1), Seconds(1.)), delay(step(-0.2, 1), Seconds(5.)));
+    /// assert_eq!(0., input(Seconds(0.5))[0]);
+    /// assert_eq!(1., input(Seconds(2.))[0]);
+    /// assert_eq!(0.8, input(Seconds(6.))[0]);
+    /// ```
+    pub fn sum<T, A, B>(a: A, b: B) -> impl Fn(Seconds<T>) -> Vec<T>
+    where
+        T: Float,
+        A: Fn(Seconds<T>) -> Vec<T>,
+        B: Fn(Seconds<T>) -> Vec<T>,
+    {
+        move |t| {
+            let (va, vb) = (a(t), b(t));
+            assert_eq!(va.len(), vb.len(), "Signals must have the same size");
+            va.iter().zip(&vb).map(|(&x, &y)| x + y).collect()
+        }
+    }
+
+    /// Signal multiplied by a constant
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - Scaling factor
+    /// * `signal` - Signal
+    pub fn scale<T, F>(k: T, signal: F) -> impl Fn(Seconds<T>) -> Vec<T>
+    where
+        T: Float,
+        F: Fn(Seconds<T>) -> Vec<T>,
+    {
+        move |t| signal(t).into_iter().map(|x| k * x).collect()
+    }
+
+    /// Signal shifted in time, `signal(t - shift)`
+    ///
+    /// # Arguments
+    ///
+    /// * `signal` - Signal
+    /// * `shift` - Time shift, positive to the future
+    pub fn shift<T, F>(signal: F, shift: Seconds<T>) -> impl Fn(Seconds<T>) -> Vec<T>
+    where
+        T: Float,
+        F: Fn(Seconds<T>) -> Vec<T>,
+    {
+        move |t| signal(Seconds(t.0 - shift.0))
+    }
+
+    /// Delayed signal, null before the delay time and equal to
+    /// `signal(t - delay)` after it.
+    ///
+    /// # Arguments
+    ///
+    /// * `signal` - Signal
+    /// * `delay` - Delay time
+    pub fn delay<T, F>(signal: F, delay: Seconds<T>) -> impl Fn(Seconds<T>) -> Vec<T>
+    where
+        T: Float,
+        F: Fn(Seconds<T>) -> Vec<T>,
+    {
+        move |t| {
+            let v = signal(Seconds(t.0 - delay.0));
+            if t.0 < delay.0 {
+                vec![T::zero(); v.len()]
+            } else {
+                v
+            }
+        }
+    }
+
+    /// Signal limited to a time window, null outside of the interval
+    /// `[start, end)`.
+    ///
+    /// # Arguments
+    ///
+    /// * `signal` - Signal
+    /// * `start` - Start of the window
+    /// * `end` - End of the window
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start is after the end of the window.
+    ///
+    /// # Example
+    /// ```
+    /// use au::{signals::continuous::{step, window}, Seconds};
+    /// let pulse = window(step(2., 1), Seconds(1.), Seconds(3.));
+    /// assert_eq!(vec![0., 2., 0.], [0., 1., 3.].iter().map(|&t| pulse(Seconds(t))[0]).collect::<Vec<_>>());
+    /// ```
+    pub fn window<T, F>(
+        signal: F,
+        start: Seconds<T>,
+        end: Seconds<T>,
+    ) -> impl Fn(Seconds<T>) -> Vec<T>
+    where
+        T: Float,
+        F: Fn(Seconds<T>) -> Vec<T>,
+    {
+        assert!(
+            start <= end,
+            "Start must not be after the end of the window"
+        );
+        move |t| {
+            let v = signal(t);
+            if t < start || t >= end {
+                vec![T::zero(); v.len()]
+            } else {
+                v
+            }
+        }
+    }
+
+    /// Concatenation of two segments: the first signal before the switching
+    /// time, the second signal after it. The second signal starts from its
+    /// own zero time at the switching time, `second(t - at)`.
+    ///
+    /// Longer sequences are built concatenating the result with further
+    /// segments, the switching times of the outer concatenations are absolute.
+    ///
+    /// # Arguments
+    ///
+    /// * `first` - First segment
+    /// * `at` - Switching time
+    /// * `second` - Second segment
+    ///
+    /// # Example
+    /// ```
+    /// use au::{signals::continuous::{concat, ramp, step}, Seconds};
+    /// // Ramp up to 2 in 2 seconds, then hold.
+    /// let input = concat(ramp(1., 1), Seconds(2.), step(2., 1));
+    /// assert_eq!(1., input(Seconds(1.))[0]);
+    /// assert_eq!(2., input(Seconds(5.))[0]);
+    /// ```
+    pub fn concat<T, A, B>(first: A, at: Seconds<T>, second: B) -> impl Fn(Seconds<T>) -> Vec<T>
+    where
+        T: Float,
+        A: Fn(Seconds<T>) -> Vec<T>,
+        B: Fn(Seconds<T>) -> Vec<T>,
+    {
+        move |t| {
+            if t < at {
+                first(t)
+            } else {
+                second(Seconds(t.0 - at.0))
+            }
+        }
+    }
+
+    /// Sample a signal at equally spaced times, starting from zero, for the
+    /// simulation of sampled inputs.
+    ///
+    /// # Arguments
+    ///
+    /// * `signal` - Signal to be sampled
+    /// * `ts` - Sample time
+    /// * `n` - Number of samples
+    ///
+    /// # Example
+    /// ```
+    /// use au::{signals::continuous::{ramp, sample}, Seconds};
+    /// let samples = sample(ramp(2., 1), Seconds(0.5), 3);
+    /// assert_eq!(vec![(Seconds(0.), vec![0.]), (Seconds(0.5), vec![1.]), (Seconds(1.), vec![2.])], samples);
+    /// ```
+    pub fn sample<T, F>(signal: F, ts: Seconds<T>, n: usize) -> Vec<(Seconds<T>, Vec<T>)>
+    where
+        T: Float,
+        F: Fn(Seconds<T>) -> Vec<T>,
+    {
+        (0..n)
+            .map(|k| {
+                let t = Seconds(T::from(k).unwrap() * ts.0);
+                (t, signal(t))
+            })
+            .collect()
+    }
+
     #[cfg(test)]
     mod tests {
         use super::*;
@@ -104,6 +594,170 @@ pub mod continuous {
             let traslated_sine = sin_siso(1., RadiansPerSecond(0.5), PI)(Seconds(t))[0];
             assert_relative_eq!(sine, -traslated_sine, max_relative = 1e-9);
         }
+
+        #[test]
+        fn sine_and_ramp() {
+            let s = sine(2., RadiansPerSecond(PI), 0., 2)(Seconds(0.5));
+            assert_eq!(2, s.len());
+            assert_relative_eq!(2., s[1]);
+            assert_relative_eq!(-1.5, ramp(0.5, 1)(Seconds(-3.))[0]);
+        }
+
+        #[test]
+        fn chirp_frequency() {
+            // Instantaneous frequency from the zero crossings of the signal.
+            let crossings = |c: &dyn Fn(Seconds<f64>) -> Vec<f64>| -> Vec<f64> {
+                let y: Vec<_> = (0..=100_000)
+                    .map(|i| c(Seconds(f64::from(i) * 1e-4))[0])
+                    .collect();
+                y.windows(2)
+                    .enumerate()
+                    .filter(|(_, w)| w[0] < 0. && w[1] >= 0.)
+                    .map(|(i, _)| i as f64 * 1e-4)
+                    .collect()
+            };
+            let lin = chirp(
+                1.,
+                RadiansPerSecond(2. * PI),
+                RadiansPerSecond(20. * PI),
+                Seconds(10.),
+                Sweep::Linear,
+                1,
+            );
+            let z = crossings(&lin);
+            // Phase 2*pi*(t + 0.45*t^2): 55 cycles in 10 seconds.
+            assert!((54..=55).contains(&z.len()));
+            // First cycle at t + 0.45*t^2 = 1.
+            assert_relative_eq!((2.8_f64.sqrt() - 1.) / 0.9, z[0], max_relative = 1e-3);
+            let log = chirp(
+                1.,
+                RadiansPerSecond(2. * PI),
+                RadiansPerSecond(20. * PI),
+                Seconds(10.),
+                Sweep::Logarithmic,
+                1,
+            );
+            let z = crossings(&log);
+            // Phase 2*pi*10/ln(10)*(10^(t/10) - 1): about 39 cycles.
+            assert_eq!(39, z.len());
+            let flat = chirp(
+                1.,
+                RadiansPerSecond(1.),
+                RadiansPerSecond(1.),
+                Seconds(1.),
+                Sweep::Logarithmic,
+                1,
+            );
+            assert_relative_eq!(1_f64.sin(), flat(Seconds(1.))[0]);
+        }
+
+        #[test]
+        fn square_and_sawtooth() {
+            let sq = square(2., Seconds(1.), 0.25, 1);
+            let v: Vec<_> = [0., 0.2, 0.3, 0.9, 1.1, -0.9]
+                .iter()
+                .map(|&t| sq(Seconds(t))[0])
+                .collect();
+            assert_eq!(vec![2., 2., -2., -2., 2., 2.], v);
+            let saw = sawtooth(1., Seconds(2.), 1);
+            assert_relative_eq!(-1., saw(Seconds(0.))[0]);
+            assert_relative_eq!(0., saw(Seconds(1.))[0]);
+            assert_relative_eq!(0.5, saw(Seconds(-0.5))[0]);
+        }
+
+        #[test]
+        #[should_panic]
+        fn square_wrong_duty() {
+            let _ = square(1., Seconds(1.), 1.5, 1);
+        }
+
+        #[test]
+        fn white_noise_statistics() {
+            let noise = white_noise(2., Seconds(0.01), 7, 2);
+            let samples: Vec<_> = (0..20_000)
+                .map(|i| noise(Seconds(f64::from(i) * 0.01 + 0.005)))
+                .collect();
+            let n = samples.len() as f64;
+            let mean = samples.iter().map(|s| s[0]).sum::<f64>() / n;
+            let var = samples.iter().map(|s| (s[0] - mean).powi(2)).sum::<f64>() / n;
+            assert!(mean.abs() < 0.05);
+            assert_relative_eq!(4., var, max_relative = 0.05);
+            // Independent outputs.
+            assert!(samples.iter().all(|s| s[0] != s[1]));
+            // Repeatable for the same seed, different for another seed.
+            assert_eq!(
+                noise(Seconds(3.)),
+                white_noise(2., Seconds(0.01), 7, 2)(Seconds(3.))
+            );
+            assert_ne!(
+                noise(Seconds(3.)),
+                white_noise(2., Seconds(0.01), 8, 2)(Seconds(3.))
+            );
+        }
+
+        #[test]
+        fn prbs_maximum_length() {
+            for bits in 2..=16 {
+                let seq = lfsr_sequence(bits);
+                assert_eq!((1 << bits) - 1, seq.len());
+                let ones = seq.iter().filter(|&&b| b).count();
+                assert_eq!(1 << (bits - 1), ones);
+            }
+            let p = prbs(0.5, Seconds(0.1), 4, 1);
+            assert_eq!(p(Seconds(0.05)), p(Seconds(1.55)));
+            assert_eq!(p(Seconds(-0.05)), p(Seconds(1.45)));
+        }
+
+        #[test]
+        #[should_panic]
+        fn prbs_wrong_length() {
+            let _ = prbs(1., Seconds(1.), 17, 1);
+        }
+
+        #[test]
+        fn combinators() {
+            let s = sum(step(1., 2), scale(2., ramp(1., 2)));
+            assert_eq!(vec![7., 7.], s(Seconds(3.)));
+            let sh = shift(ramp(1., 1), Seconds(2.));
+            assert_relative_eq!(-2., sh(Seconds(0.))[0]);
+            let d = delay(ramp(1., 1), Seconds(2.));
+            assert_relative_eq!(0., d(Seconds(0.))[0]);
+            assert_relative_eq!(1., d(Seconds(3.))[0]);
+            let w = window(step(1., 1), Seconds(1.), Seconds(2.));
+            let v: Vec<_> = [0.5, 1., 1.5, 2.]
+                .iter()
+                .map(|&t| w(Seconds(t))[0])
+                .collect();
+            assert_eq!(vec![0., 1., 1., 0.], v);
+        }
+
+        #[test]
+        fn concatenation() {
+            let seg = concat(
+                concat(step(1., 1), Seconds(1.), ramp(1., 1)),
+                Seconds(3.),
+                zero(1),
+            );
+            let v: Vec<_> = [0., 1., 2., 3., 4.]
+                .iter()
+                .map(|&t| seg(Seconds(t))[0])
+                .collect();
+            assert_eq!(vec![1., 0., 1., 0., 0.], v);
+        }
+
+        #[test]
+        #[should_panic]
+        fn sum_wrong_sizes() {
+            let _ = sum(step(1., 2), step(1., 1))(Seconds(0.));
+        }
+
+        #[test]
+        fn sampled_signal() {
+            let samples = sample(step(1., 2), Seconds(0.1), 11);
+            assert_eq!(11, samples.len());
+            assert_relative_eq!(1., samples[10].0 .0);
+            assert_eq!(vec![1., 1.], samples[10].1);
+        }
     }
 }
 
@@ -6,6 +6,9 @@
 //! * sensitivity function
 //! * complementary sensitivity function
 //! * control sensitivity function
+//! * gang of four
+//! * natural frequency and damping of the poles
+//! * gain and phase margins
 //! * root locus plot
 //! * bode plot
 //! * polar plot
@@ -13,22 +16,56 @@
 
 use nalgebra::RealField;
 use num_complex::Complex;
-use num_traits::Float;
+use num_traits::{Float, FloatConst};
 
 use std::{cmp::Ordering, marker::PhantomData, ops::Div};
 
 use crate::{
+    complex,
     enums::Continuous,
-    plots::{root_locus::RootLocus, Plotter},
+    plots::{
+        pzmap::{Boundary, PzMap},
+        root_locus::{Geometry, RootLocus},
+        Plotter,
+    },
+    polynomial::Poly,
     rational_function::Rf,
     transfer_function::TfGen,
-    units::Seconds,
+    units::{DampingRatio, Degrees, NaturalFrequency, RadiansPerSecond, Seconds},
 };
 
 /// Continuous transfer function
 pub type Tf<T> = TfGen<T, Continuous>;
 
 impl<T: Float> Tf<T> {
+    /// Create a second order transfer function with unitary static gain
+    /// ```text
+    ///                wn^2
+    /// G(s) = ---------------------
+    ///        s^2 + 2*zeta*wn*s + wn^2
+    /// ```
+    ///
+    /// # Arguments
+    ///
+    /// * `wn` - Natural frequency
+    /// * `zeta` - Damping ratio
+    ///
+    /// # Example
+    /// ```
+    /// use au::{poly, DampingRatio, NaturalFrequency, Tf};
+    /// let tf = Tf::new_second_order(NaturalFrequency(2.), DampingRatio(0.5));
+    /// assert_eq!(Tf::new(poly!(4.), poly!(4., 2., 1.)), tf);
+    /// ```
+    #[must_use]
+    pub fn new_second_order(wn: NaturalFrequency<T>, zeta: DampingRatio<T>) -> Self {
+        let (wn, zeta) = (wn.0, zeta.0);
+        let wn2 = wn * wn;
+        Self::new(
+            Poly::new_from_coeffs(&[wn2]),
+            Poly::new_from_coeffs(&[wn2, (zeta + zeta) * wn, T::one()]),
+        )
+    }
+
     /// Time delay for continuous time transfer function.
     /// `y(t) = u(t - tau)`
     /// `G(s) = e^(-tau * s)
@@ -113,6 +150,7 @@ impl<T: Float> Tf<T> {
         Self {
             rf: Rf::new(d.clone(), n + d),
             time: PhantomData,
+            sample_time: None,
         }
     }
 
@@ -168,8 +206,162 @@ impl<T: Float> Tf<T> {
                 r.num() * self.num() + r.den() * self.den(),
             ),
             time: PhantomData,
+            sample_time: None,
+        }
+    }
+
+    /// Gang of four for the given controller `r`. The four transfer functions
+    /// share the same denominator, that is calculated only once.
+    /// ```text
+    ///              1                 G(s)*R(s)
+    /// S(s) = -------------   T(s) = -------------
+    ///        1 + G(s)*R(s)          1 + G(s)*R(s)
+    ///
+    ///             G(s)                     R(s)
+    /// GS(s) = -------------   RS(s) = -------------
+    ///         1 + G(s)*R(s)           1 + G(s)*R(s)
+    /// ```
+    ///
+    /// # Arguments
+    ///
+    /// * `r` - Controller
+    ///
+    /// # Example
+    /// ```
+    /// use au::{poly, Tf};
+    /// let g = Tf::new(poly!(1.), poly!(0., 1.));
+    /// let r = Tf::new(poly!(4.), poly!(1., 1.));
+    /// let gang = g.gang_of_four(&r);
+    /// assert_eq!(&g.sensitivity(&r), gang.s());
+    /// assert_eq!(&g.compl_sensitivity(&r), gang.t());
+    /// ```
+    #[must_use]
+    pub fn gang_of_four(&self, r: &Self) -> GangOfFour<T> {
+        let dd = self.den() * r.den();
+        let nn = self.num() * r.num();
+        let den = &dd + &nn;
+        let new_tf = |num| Self {
+            rf: Rf::new(num, den.clone()),
+            time: PhantomData,
+            sample_time: None,
+        };
+        GangOfFour {
+            s: new_tf(dd),
+            t: new_tf(nn),
+            gs: new_tf(self.num() * r.den()),
+            rs: new_tf(r.num() * self.den()),
+        }
+    }
+}
+
+/// Real and imaginary parts of `p(jw)` as polynomials in `w`.
+fn split_jw<T: Float>(p: &Poly<T>) -> (Poly<T>, Poly<T>) {
+    let coeffs = p.coeffs();
+    let mut re = vec![T::zero(); coeffs.len()];
+    let mut im = vec![T::zero(); coeffs.len()];
+    for (k, &c) in coeffs.iter().enumerate() {
+        // j^k = 1, j, -1, -j
+        match k % 4 {
+            0 => re[k] = c,
+            1 => im[k] = c,
+            2 => re[k] = -c,
+            _ => im[k] = -c,
         }
     }
+    (Poly::new_from_coeffs(&re), Poly::new_from_coeffs(&im))
+}
+
+/// Strictly positive real roots of the polynomial, sorted.
+fn positive_real_roots<T: Float + FloatConst>(p: &Poly<T>) -> Vec<T> {
+    let tol = Float::sqrt(T::epsilon());
+    let mut roots: Vec<_> = p
+        .iterative_roots()
+        .into_iter()
+        .filter(|r| r.re > T::zero() && Float::abs(r.im) <= tol * Float::max(r.norm(), T::one()))
+        .map(|r| r.re)
+        .collect();
+    roots.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+    roots
+}
+
+/// Gain and phase margins of a loop transfer function
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde-serialize",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub struct Margins<T: Float> {
+    /// Gain margin (absolute value) and phase crossover frequency
+    gain_margin: Option<(T, RadiansPerSecond<T>)>,
+    /// Phase margin and gain crossover frequency
+    phase_margin: Option<(Degrees<T>, RadiansPerSecond<T>)>,
+}
+
+impl<T: Float> Margins<T> {
+    /// Get the gain margin (absolute value), `None` if the phase never
+    /// crosses -180°
+    #[must_use]
+    pub fn gain_margin(&self) -> Option<T> {
+        self.gain_margin.map(|m| m.0)
+    }
+
+    /// Get the phase crossover frequency of the gain margin
+    #[must_use]
+    pub fn phase_crossover(&self) -> Option<RadiansPerSecond<T>> {
+        self.gain_margin.map(|m| m.1)
+    }
+
+    /// Get the phase margin, `None` if the magnitude never crosses 1 (0 dB)
+    #[must_use]
+    pub fn phase_margin(&self) -> Option<Degrees<T>> {
+        self.phase_margin.map(|m| m.0)
+    }
+
+    /// Get the gain crossover frequency of the phase margin
+    #[must_use]
+    pub fn gain_crossover(&self) -> Option<RadiansPerSecond<T>> {
+        self.phase_margin.map(|m| m.1)
+    }
+}
+
+/// Gang of four transfer functions of a feedback loop with plant `G(s)`
+/// and controller `R(s)`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GangOfFour<T> {
+    /// Sensitivity function
+    s: Tf<T>,
+    /// Complementary sensitivity function
+    t: Tf<T>,
+    /// Load disturbance sensitivity function
+    gs: Tf<T>,
+    /// Noise sensitivity function (control sensitivity)
+    rs: Tf<T>,
+}
+
+impl<T> GangOfFour<T> {
+    /// Sensitivity function `1 / (1 + GR)`
+    #[must_use]
+    pub fn s(&self) -> &Tf<T> {
+        &self.s
+    }
+
+    /// Complementary sensitivity function `GR / (1 + GR)`
+    #[must_use]
+    pub fn t(&self) -> &Tf<T> {
+        &self.t
+    }
+
+    /// Load disturbance sensitivity function `G / (1 + GR)`
+    #[must_use]
+    pub fn gs(&self) -> &Tf<T> {
+        &self.gs
+    }
+
+    /// Noise sensitivity function `R / (1 + GR)`
+    #[must_use]
+    pub fn rs(&self) -> &Tf<T> {
+        &self.rs
+    }
 }
 
 impl<T: Float + RealField> Tf<T> {
@@ -187,6 +379,49 @@ impl<T: Float + RealField> Tf<T> {
         self.complex_poles().iter().all(|p| p.re.is_negative())
     }
 
+    /// Create a pole-zero map, the stability boundary is the imaginary axis.
+    ///
+    /// # Example
+    /// ```
+    /// use au::{plots::pzmap::Kind, Poly, Tf};
+    /// let tf = Tf::new(Poly::new_from_roots(&[1.]), Poly::new_from_roots(&[-1., -2.]));
+    /// let map = tf.pzmap();
+    /// assert_eq!(3, map.points().len());
+    /// assert_eq!(Kind::Zero, map.points()[2].kind());
+    /// ```
+    #[must_use]
+    pub fn pzmap(&self) -> PzMap<T> {
+        PzMap::new(
+            &self.complex_poles(),
+            &self.complex_zeros(),
+            Boundary::ImaginaryAxis,
+        )
+    }
+
+    /// Natural frequency and damping ratio of each pole, sorted by
+    /// increasing natural frequency. Complex conjugate poles appear twice.
+    ///
+    /// # Example
+    /// ```
+    /// use au::{poly, Tf};
+    /// let tf = Tf::new(poly!(1.), poly!(4., 0.4, 1.) * poly!(1., 1.));
+    /// let modes = tf.damp();
+    /// assert_eq!(3, modes.len());
+    /// assert!((modes[0].0 .0 - 1_f64).abs() < 1e-12);
+    /// assert!((modes[2].0 .0 - 2_f64).abs() < 1e-12);
+    /// assert!((modes[2].1 .0 - 0.1_f64).abs() < 1e-12);
+    /// ```
+    #[must_use]
+    pub fn damp(&self) -> Vec<(NaturalFrequency<T>, DampingRatio<T>)> {
+        let mut modes: Vec<_> = self
+            .complex_poles()
+            .into_iter()
+            .map(|p| (NaturalFrequency(p.norm()), DampingRatio(complex::damp(p))))
+            .collect();
+        modes.sort_by(|a, b| a.0 .0.partial_cmp(&b.0 .0).unwrap_or(Ordering::Equal));
+        modes
+    }
+
     /// Root locus for the given coefficient `k`
     ///
     /// # Arguments
@@ -230,6 +465,112 @@ impl<T: Float + RealField> Tf<T> {
     pub fn root_locus_plot(self, min_k: T, max_k: T, step: T) -> RootLocus<T> {
         RootLocus::new(self, min_k, max_k, step)
     }
+
+    /// Create a `RootLocus` plot with logarithmic spacing of the transfer
+    /// constants
+    ///
+    /// # Arguments
+    ///
+    /// * `min_k` - Minimum transfer constant of the plot
+    /// * `max_k` - Maximum transfer constant of the plot
+    /// * `step` - Step between each transfer constant
+    ///
+    /// `step` shall be in logarithmic scale. Use 0.1 to have 10 point per decade
+    ///
+    /// # Panics
+    ///
+    /// Panics if the step or the minimum transfer constant are not strictly
+    /// positive or the minimum transfer constant is not lower than the maximum
+    /// transfer constant.
+    ///
+    /// # Example
+    /// ```
+    /// use au::{poly, Poly, Tf};
+    /// let l = Tf::new(poly!(1.), Poly::new_from_roots(&[-1., -2.]));
+    /// let k: Vec<_> = l.root_locus_log_plot(0.01, 100., 1.).into_iter().map(|d| d.k()).collect();
+    /// assert_eq!(5, k.len());
+    /// assert!((k[4] - 100_f64).abs() < 1e-10);
+    /// ```
+    pub fn root_locus_log_plot(self, min_k: T, max_k: T, step: T) -> RootLocus<T> {
+        RootLocus::new_log(self, min_k, max_k, step)
+    }
+
+    /// Geometry of the root locus for positive transfer constants:
+    /// asymptotes, real axis segments, break points and imaginary axis
+    /// crossings.
+    ///
+    /// # Example
+    /// ```
+    /// use au::{poly, Poly, Tf};
+    /// let l = Tf::new(poly!(1.), Poly::new_from_roots(&[0., -1., -2.]));
+    /// let geometry = l.root_locus_geometry();
+    /// assert_eq!(Some(-1.), geometry.centroid());
+    /// assert_eq!(3, geometry.asymptote_angles().len());
+    /// let crossing = geometry.imaginary_axis_crossings().last().unwrap();
+    /// assert!((crossing.k() - 6_f64).abs() < 1e-10);
+    /// ```
+    #[must_use]
+    pub fn root_locus_geometry(&self) -> Geometry<T>
+    where
+        T: FloatConst,
+    {
+        Geometry::new(self)
+    }
+}
+
+impl<T: Float + FloatConst> Tf<T> {
+    /// Gain and phase margins of the transfer function, considered as the
+    /// loop transfer function of a negative feedback.
+    ///
+    /// The crossover frequencies are the positive real roots of the
+    /// polynomials `|N(jw)|^2 - |D(jw)|^2` (gain crossover) and
+    /// `Im(N(jw) * D(-jw))` (phase crossover, where the phase is an odd
+    /// multiple of -180°). With several crossovers the smallest margins are
+    /// returned.
+    ///
+    /// # Example
+    /// ```
+    /// use au::{Poly, Tf};
+    /// let l = Tf::new(Poly::new_from_coeffs(&[4.]), Poly::new_from_roots(&[-1., -1., -1.]));
+    /// let margins = l.margins();
+    /// assert!((margins.gain_margin().unwrap() - 2_f64).abs() < 1e-10);
+    /// assert!((margins.phase_crossover().unwrap().0 - 3_f64.sqrt()).abs() < 1e-10);
+    /// assert!(margins.phase_margin().unwrap().0 > 0.);
+    /// ```
+    #[must_use]
+    pub fn margins(&self) -> Margins<T> {
+        let (n_re, n_im) = split_jw(self.num());
+        let (d_re, d_im) = split_jw(self.den());
+        let gain_poly =
+            &(&(&n_re * &n_re) + &(&n_im * &n_im)) - &(&(&d_re * &d_re) + &(&d_im * &d_im));
+        let phase_poly = &(&n_im * &d_re) - &(&n_re * &d_im);
+        let eval = |w: T| self.eval(&Complex::new(T::zero(), w));
+
+        let pi = T::from(180.).unwrap();
+        let phase_margin = positive_real_roots(&gain_poly)
+            .into_iter()
+            .map(|w| {
+                let pm = eval(w).arg().to_degrees() + pi;
+                let pm = if pm > pi { pm - pi - pi } else { pm };
+                (Degrees(pm), RadiansPerSecond(w))
+            })
+            .min_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal));
+        let gain_margin = positive_real_roots(&phase_poly)
+            .into_iter()
+            .filter_map(|w| {
+                let l = eval(w);
+                if l.re < T::zero() {
+                    Some((T::one() / l.norm(), RadiansPerSecond(w)))
+                } else {
+                    None
+                }
+            })
+            .min_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal));
+        Margins {
+            gain_margin,
+            phase_margin,
+        }
+    }
 }
 
 impl<T> Tf<T> {
@@ -262,6 +603,19 @@ impl<T: Float> Plotter<T> for Tf<T> {
     fn eval_point(&self, s: T) -> Complex<T> {
         self.eval(&Complex::new(T::zero(), s))
     }
+
+    /// Derivative of the phase with respect to the angular frequency,
+    /// `Re(G'(jw) / G(jw))`, from the derivatives of the numerator and of
+    /// the denominator.
+    ///
+    /// # Arguments
+    ///
+    /// * `w` - angular frequency at which the derivative is evaluated
+    fn phase_derivative(&self, w: T) -> T {
+        let s = Complex::new(T::zero(), w);
+        let log_derivative = |p: &Poly<T>| p.derive().eval_by_val(s) / p.eval_by_val(s);
+        (log_derivative(self.num()) - log_derivative(self.den())).re
+    }
 }
 
 #[cfg(test)]
@@ -286,6 +640,18 @@ mod tests {
         assert_relative_eq!(-1., d(Complex::new(0., 0.5)).arg());
     }
 
+    #[test]
+    fn second_order() {
+        let tf = Tf::new_second_order(NaturalFrequency(3.), DampingRatio(0.2));
+        assert_relative_eq!(1., tf.static_gain());
+        let modes = tf.damp();
+        assert_eq!(2, modes.len());
+        for (wn, zeta) in modes {
+            assert_relative_eq!(3., wn.0, max_relative = 1e-12);
+            assert_relative_eq!(0.2, zeta.0, max_relative = 1e-12);
+        }
+    }
+
     proptest! {
     #[test]
         fn qc_static_gain(g: f32) {
@@ -369,6 +735,19 @@ mod tests {
         assert_eq!(Tf::new(poly!(0., 4.), poly!(4., 1., 1.)), q);
     }
 
+    #[test]
+    fn gang_of_four() {
+        let g = Tf::new(poly!(1.), poly!(0., 1., 2.));
+        let r = Tf::new(poly!(4., 1.), poly!(1., 1.));
+        let gang = g.gang_of_four(&r);
+        let den = poly!(4., 2., 3., 2.);
+        assert_eq!(&Tf::new(poly!(0., 1., 3., 2.), den.clone()), gang.s());
+        assert_eq!(&Tf::new(poly!(4., 1.), den.clone()), gang.t());
+        assert_eq!(&Tf::new(poly!(1., 1.), den.clone()), gang.gs());
+        assert_eq!(&Tf::new(poly!(0., 4., 9., 2.), den), gang.rs());
+        assert_eq!(&g.control_sensitivity(&r), gang.rs());
+    }
+
     #[test]
     fn root_locus() {
         let l = Tf::new(poly!(1.), Poly::new_from_roots(&[-1., -2.]));
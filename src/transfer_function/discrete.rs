@@ -4,6 +4,9 @@
 //! * time delay
 //! * initial value
 //! * static gain
+//! * stability (Schur-Cohn test)
+//! * sampling period and frequency response in radians per second or in
+//!   radians per sample
 //! * ARMA (autoregressive moving average) time evaluation method
 //!
 //! This module contains the discretization struct of a continuous time
@@ -14,7 +17,7 @@
 
 use nalgebra::RealField;
 use num_complex::Complex;
-use num_traits::{Float, Zero};
+use num_traits::{Float, MulAdd, Zero};
 
 use std::{
     cmp::Ordering,
@@ -24,12 +27,159 @@ use std::{
     ops::{Add, Div, Mul},
 };
 
-use crate::{enums::Discrete, plots::Plotter, transfer_function::TfGen};
+use crate::{
+    enums::{Discrete, Stability},
+    plots::{
+        pzmap::{Boundary, PzMap},
+        root_locus::{self, Crossing, RootLocus},
+        Plotter,
+    },
+    polynomial::Poly,
+    transfer_function::TfGen,
+    units::{NormalizedFrequency, RadiansPerSecond, SampleTime},
+};
 
 /// Discrete transfer function
 pub type Tfz<T> = TfGen<T, Discrete>;
 
 impl<T: Float> Tfz<T> {
+    /// Set the sampling period of the transfer function.
+    ///
+    /// The sampling period is set by the discretization of continuous time
+    /// transfer functions and it is kept by the operations between transfer
+    /// functions.
+    ///
+    /// # Arguments
+    ///
+    /// * `sample_time` - Sampling period
+    ///
+    /// # Panics
+    ///
+    /// Panics if the sampling period is not positive.
+    ///
+    /// # Example
+    /// ```
+    /// use au::{poly, SampleTime, Tfz};
+    /// let tfz = Tfz::new(poly!(1.), poly!(-0.5, 1.)).with_sample_time(SampleTime(0.1));
+    /// assert_eq!(Some(SampleTime(0.1)), tfz.sample_time());
+    /// ```
+    #[must_use]
+    pub fn with_sample_time(mut self, sample_time: SampleTime<T>) -> Self {
+        assert!(
+            sample_time.0 > T::zero(),
+            "Sampling period must be positive"
+        );
+        self.sample_time = Some(sample_time.0);
+        self
+    }
+
+    /// Get the sampling period of the transfer function, `None` if it is not
+    /// known.
+    #[must_use]
+    pub fn sample_time(&self) -> Option<SampleTime<T>> {
+        self.sample_time.map(SampleTime)
+    }
+
+    /// Resample the transfer function with a new sampling period, through
+    /// the continuous form given by the inverse Tustin transformation.
+    /// With `r = Ts_old / Ts_new` the substitution is
+    /// ```text
+    ///      (1 + r)*z + (1 - r)
+    /// z -> -------------------
+    ///      (1 - r)*z + (1 + r)
+    /// ```
+    /// The resampling is exact for transfer functions discretized with
+    /// Tustin method.
+    ///
+    /// It returns `None` if the sampling period is not known.
+    ///
+    /// # Arguments
+    ///
+    /// * `sample_time` - New sampling period
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new sampling period is not positive.
+    ///
+    /// # Example
+    /// ```
+    /// use au::{poly, Discretization, SampleTime, Seconds, Tf};
+    /// let tf = Tf::new(poly!(1.), poly!(1., 1.));
+    /// let tfz = tf.discretize(Seconds(0.1_f64), Discretization::Tustin);
+    /// let resampled = tfz.resample(SampleTime(0.05)).unwrap();
+    /// assert_eq!(Some(SampleTime(0.05)), resampled.sample_time());
+    /// let expected = tf.discretize(Seconds(0.05), Discretization::Tustin);
+    /// assert!((resampled.static_gain() - expected.static_gain()).abs() < 1e-12);
+    /// ```
+    #[must_use]
+    pub fn resample(&self, sample_time: SampleTime<T>) -> Option<Self> {
+        assert!(
+            sample_time.0 > T::zero(),
+            "Sampling period must be positive"
+        );
+        let r = self.sample_time? / sample_time.0;
+        let z_num = Poly::new_from_coeffs(&[T::one() - r, T::one() + r]);
+        let z_den = Poly::new_from_coeffs(&[T::one() + r, T::one() - r]);
+        // Both polynomials are multiplied by z_den^n, with n the maximum
+        // degree, so that the result is a ratio of polynomials.
+        let n = self.num().degree().max(self.den().degree()).unwrap_or(0);
+        let substitute = |p: &Poly<T>| {
+            p.as_slice()
+                .iter()
+                .enumerate()
+                .fold(Poly::zero(), |acc, (i, &c)| {
+                    #[allow(clippy::cast_possible_truncation)]
+                    let (i, n) = (i as u32, n as u32);
+                    acc + z_num.powi(i) * z_den.powi(n - i) * c
+                })
+        };
+        Some(
+            Self::new(substitute(self.num()), substitute(self.den())).with_sample_time(sample_time),
+        )
+    }
+
+    /// Frequency response at the given angular frequency, evaluated at
+    /// `z = e^(j*omega*Ts)` with the sampling period of the transfer function.
+    ///
+    /// It returns `None` if the sampling period is not known.
+    ///
+    /// # Arguments
+    ///
+    /// * `omega` - Angular frequency
+    ///
+    /// # Example
+    /// ```
+    /// use au::{poly, Discretization, RadiansPerSecond, Seconds, Tf};
+    /// let tf = Tf::new(poly!(1.), poly!(1., 1.));
+    /// let tfz = tf.discretize(Seconds(0.01), Discretization::Tustin);
+    /// let g = tfz.frequency_response(RadiansPerSecond(1.)).unwrap();
+    /// assert!((g.norm() - 0.5_f64.sqrt()).abs() < 1e-4);
+    /// ```
+    #[must_use]
+    pub fn frequency_response(&self, omega: RadiansPerSecond<T>) -> Option<Complex<T>> {
+        self.sample_time
+            .map(|ts| self.normalized_frequency_response(NormalizedFrequency(omega.0 * ts)))
+    }
+
+    /// Frequency response at the given normalized frequency, evaluated at
+    /// `z = e^(j*theta)`. It does not require the sampling period.
+    ///
+    /// # Arguments
+    ///
+    /// * `theta` - Normalized angular frequency
+    ///
+    /// # Example
+    /// ```
+    /// use au::{poly, units::NormalizedFrequency, Tfz};
+    /// let tfz = Tfz::new(poly!(0., 1.), poly!(1.));
+    /// let g = tfz.normalized_frequency_response(NormalizedFrequency::from_cycles_per_sample(0.5));
+    /// assert!((g.re + 1_f64).abs() < 1e-12);
+    /// ```
+    #[must_use]
+    pub fn normalized_frequency_response(&self, theta: NormalizedFrequency<T>) -> Complex<T> {
+        self.eval(&Complex::from_polar(T::one(), theta.0))
+    }
+
     /// Time delay for discrete time transfer function.
     /// `y(k) = u(k - h)`
     /// `G(z) = z^(-h)
@@ -97,6 +247,99 @@ impl<'a, T: 'a + Add<&'a T, Output = T> + Div<Output = T> + Zero> Tfz<T> {
     }
 }
 
+impl<T: Float> Tfz<T> {
+    /// System stability through the Schur-Cohn test. The test does not compute
+    /// the poles, it uses the reflection coefficients of the denominator.
+    ///
+    /// When the absolute value of a reflection coefficient lies within `tol`
+    /// from one the recursion is singular. If the reduced polynomial is
+    /// self-inversive (within `tol`) its poles are on the unit circle or in
+    /// pairs symmetric with respect to it (`p` and `1/p`): the test continues
+    /// on its derivative and the system is marginally stable if all the
+    /// roots of the derivative lie in the closed unit disk (Cohn's theorem),
+    /// unstable otherwise. If the reduced polynomial is not self-inversive
+    /// the system is unstable.
+    ///
+    /// A transfer function with zero denominator is unstable.
+    ///
+    /// # Arguments
+    ///
+    /// * `tol` - tolerance band around the unit circle
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use au::{enums::Stability, Poly, Tfz};
+    /// let tfz = Tfz::new(Poly::new_from_coeffs(&[1.]), Poly::new_from_roots(&[0.5, 1.]));
+    /// assert_eq!(Stability::MarginallyStable, tfz.is_stable_with(1e-10));
+    /// ```
+    #[must_use]
+    pub fn is_stable_with(&self, tol: T) -> Stability {
+        let den = self.den();
+        let degree = match den.degree() {
+            Some(d) => d,
+            None => return Stability::Unstable,
+        };
+        schur_cohn(den.as_slice()[..=degree].to_vec(), tol.abs())
+    }
+}
+
+/// Schur-Cohn step-down recursion on the coefficients of a polynomial.
+///
+/// # Arguments
+///
+/// * `a` - coefficients of the polynomial, the leading one is not zero
+/// * `tol` - tolerance band around the unit circle
+fn schur_cohn<T: Float>(mut a: Vec<T>, tol: T) -> Stability {
+    // At every step the polynomial degree is reduced by one,
+    // with a_i' = a_i - k * a_(n-i), k = a_0 / a_n.
+    while a.len() > 1 {
+        let n = a.len() - 1;
+        let k = a[0] / a[n];
+        let k_abs = k.abs();
+        if k_abs > T::one() + tol {
+            return Stability::Unstable;
+        }
+        let reduced: Vec<T> = (1..=n).map(|i| a[i] - k * a[n - i]).collect();
+        if k_abs >= T::one() - tol {
+            return schur_cohn_singular(&a, &reduced, tol);
+        }
+        a = reduced;
+    }
+    Stability::Stable
+}
+
+/// Stability when the Schur-Cohn recursion is singular, the product of the
+/// magnitudes of the roots of the polynomial `a` is one.
+///
+/// If the reduced polynomial vanishes, `a` is self-inversive: its roots are
+/// on the unit circle or in pairs `p` and `1/p`, and they all lie on the unit
+/// circle if and only if the roots of its derivative lie in the closed unit
+/// disk (Cohn's theorem). Otherwise `a` has a root outside the unit circle.
+///
+/// # Arguments
+///
+/// * `a` - coefficients of the polynomial
+/// * `reduced` - coefficients of the reduced polynomial
+/// * `tol` - tolerance band around the unit circle
+fn schur_cohn_singular<T: Float>(a: &[T], reduced: &[T], tol: T) -> Stability {
+    let scale = a.iter().fold(T::zero(), |m, c| m.max(c.abs()));
+    if reduced.iter().all(|c| c.abs() <= tol * scale) {
+        let derivative = a
+            .iter()
+            .enumerate()
+            .skip(1)
+            .map(|(i, &c)| T::from(i).unwrap() * c)
+            .collect();
+        match schur_cohn(derivative, tol) {
+            Stability::Unstable => Stability::Unstable,
+            _ => Stability::MarginallyStable,
+        }
+    } else {
+        Stability::Unstable
+    }
+}
+
 impl<T: Float + RealField> Tfz<T> {
     /// System stability. Checks if all poles are inside the unit circle.
     ///
@@ -111,6 +354,132 @@ impl<T: Float + RealField> Tfz<T> {
     pub fn is_stable(&self) -> bool {
         self.complex_poles().iter().all(|p| p.norm() < T::one())
     }
+
+    /// Create a pole-zero map, the stability boundary is the unit circle.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use au::{plots::pzmap::Boundary, Poly, Tfz};
+    /// let tfz = Tfz::new(Poly::new_from_roots(&[0.2]), Poly::new_from_roots(&[0.5, 0.5]));
+    /// let map = tfz.pzmap();
+    /// assert_eq!(Boundary::UnitCircle, map.boundary());
+    /// assert_eq!(2, map.poles().next().unwrap().multiplicity());
+    /// ```
+    #[must_use]
+    pub fn pzmap(&self) -> PzMap<T> {
+        PzMap::new(
+            &self.complex_poles(),
+            &self.complex_zeros(),
+            Boundary::UnitCircle,
+        )
+    }
+
+    /// Root locus for the given feedback gain.
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - Transfer function constant
+    ///
+    /// # Example
+    /// ```
+    /// use au::{num_complex::Complex, poly, Tfz};
+    /// let l = Tfz::new(poly!(1.), poly!(-0.5, 1.));
+    /// assert_eq!(Complex::new(-0.5, 0.), l.root_locus(1.)[0]);
+    /// ```
+    pub fn root_locus(&self, k: T) -> Vec<Complex<T>> {
+        let p = &(self.num() * k) + self.den();
+        p.complex_roots()
+    }
+
+    /// Crossings of the unit circle by the root locus for positive transfer
+    /// constants, ordered by increasing transfer constant. The frequencies of
+    /// the crossings are in radians per sample.
+    ///
+    /// # Example
+    /// ```
+    /// use au::{poly, Tfz};
+    /// // Closed loop poles z^2 - 0.7z + 0.1 + k = 0, |z|^2 = 0.1 + k.
+    /// let l = Tfz::new(poly!(1.), poly!(0.1, -0.7, 1.));
+    /// let crossing = l.root_locus_crossings()[0];
+    /// assert!((crossing.k() - 0.9_f64).abs() < 1e-10);
+    /// assert!((crossing.omega().0 - 0.35_f64.acos()).abs() < 1e-10);
+    /// ```
+    #[must_use]
+    pub fn root_locus_crossings(&self) -> Vec<Crossing<T>> {
+        root_locus::unit_circle_crossings(self.num(), self.den())
+    }
+
+    /// Critical gain, the smallest strictly positive transfer constant that
+    /// places a closed loop pole on the unit circle. For a stable open loop
+    /// system it is the gain margin of the closed loop.
+    ///
+    /// # Example
+    /// ```
+    /// use au::{poly, Tfz};
+    /// // Closed loop pole z = 0.5 - k, it crosses the unit circle in z = -1.
+    /// let l = Tfz::new(poly!(1.), poly!(-0.5, 1.));
+    /// assert_eq!(Some(1.5), l.critical_gain());
+    /// ```
+    #[must_use]
+    pub fn critical_gain(&self) -> Option<T> {
+        self.root_locus_crossings()
+            .iter()
+            .map(Crossing::k)
+            .find(|&k| k > T::zero())
+    }
+}
+
+impl<T: Float + MulAdd<Output = T> + RealField> Tfz<T> {
+    /// Create a `RootLocus` plot in the z-plane
+    ///
+    /// # Arguments
+    ///
+    /// * `min_k` - Minimum transfer constant of the plot
+    /// * `max_k` - Maximum transfer constant of the plot
+    /// * `step` - Step between each transfer constant
+    ///
+    /// `step` is linear.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the step is not strictly positive of the minimum transfer constant
+    /// is not lower than the maximum transfer constant.
+    ///
+    /// # Example
+    /// ```
+    /// use au::{poly, Tfz};
+    /// let l = Tfz::new(poly!(1.), poly!(-0.5, 1.));
+    /// let unstable = l
+    ///     .root_locus_plot(0., 3., 0.5)
+    ///     .into_iter()
+    ///     .filter(|d| !d.outside_unit_circle().is_empty())
+    ///     .count();
+    /// assert_eq!(3, unstable);
+    /// ```
+    pub fn root_locus_plot(self, min_k: T, max_k: T, step: T) -> RootLocus<T, Discrete> {
+        RootLocus::new(self, min_k, max_k, step)
+    }
+
+    /// Create a `RootLocus` plot in the z-plane with logarithmic spacing of
+    /// the transfer constants
+    ///
+    /// # Arguments
+    ///
+    /// * `min_k` - Minimum transfer constant of the plot
+    /// * `max_k` - Maximum transfer constant of the plot
+    /// * `step` - Step between each transfer constant
+    ///
+    /// `step` shall be in logarithmic scale. Use 0.1 to have 10 point per decade
+    ///
+    /// # Panics
+    ///
+    /// Panics if the step or the minimum transfer constant are not strictly
+    /// positive or the minimum transfer constant is not lower than the maximum
+    /// transfer constant.
+    pub fn root_locus_log_plot(self, min_k: T, max_k: T, step: T) -> RootLocus<T, Discrete> {
+        RootLocus::new_log(self, min_k, max_k, step)
+    }
 }
 
 /// Macro defining the common behaviour when creating the arma iterator.
@@ -372,6 +741,24 @@ impl<T: Float> Plotter<T> for Tfz<T> {
     fn eval_point(&self, theta: T) -> Complex<T> {
         self.eval(&Complex::from_polar(T::one(), theta))
     }
+
+    /// Derivative of the phase with respect to the angle,
+    /// `Re(z * G'(z) / G(z))` with `z = e^(i*theta)`, from the derivatives of
+    /// the numerator and of the denominator.
+    ///
+    /// # Arguments
+    ///
+    /// * `theta` - angle at which the derivative is evaluated
+    fn phase_derivative(&self, theta: T) -> T {
+        let z = Complex::from_polar(T::one(), theta);
+        let log_derivative = |p: &Poly<T>| p.derive().eval_by_val(z) / p.eval_by_val(z);
+        (z * (log_derivative(self.num()) - log_derivative(self.den()))).re
+    }
+
+    /// Sampling period of the transfer function, if known.
+    fn sample_time(&self) -> Option<T> {
+        self.sample_time
+    }
 }
 
 #[cfg(test)]
@@ -379,6 +766,29 @@ mod tests {
     use super::*;
     use crate::{poly, polynomial::Poly, signals::discrete, units::ToDecibel};
     use num_complex::Complex64;
+    use proptest::prelude::*;
+
+    #[test]
+    fn discrete_root_locus() {
+        let l = Tfz::new(poly!(1.), poly!(0.1, -0.7, 1.));
+        assert_relative_eq!(0.9, l.critical_gain().unwrap(), max_relative = 1e-10);
+        let roots = l.root_locus(0.9);
+        assert_relative_eq!(1., roots[0].norm(), max_relative = 1e-10);
+        let data: Vec<_> = l.root_locus_log_plot(0.01, 100., 1.).into_iter().collect();
+        assert_eq!(5, data.len());
+        assert_eq!(2, data[4].outside_unit_circle().len());
+    }
+
+    #[test]
+    fn critical_gain_unstable_open_loop() {
+        // Closed loop pole z = 2 - k, stable for 1 < k < 3.
+        let l = Tfz::new(poly!(1.), poly!(-2., 1.));
+        let crossings = l.root_locus_crossings();
+        assert_eq!(2, crossings.len());
+        assert_relative_eq!(1., crossings[0].k(), max_relative = 1e-10);
+        assert_relative_eq!(3., crossings[1].k(), max_relative = 1e-10);
+        assert_relative_eq!(1., l.critical_gain().unwrap(), max_relative = 1e-10);
+    }
 
     #[test]
     fn tfz() {
@@ -420,6 +830,77 @@ mod tests {
         assert!(!unstable_tf.is_stable());
     }
 
+    #[test]
+    fn schur_cohn_stability() {
+        let tol = 1e-10;
+        let tf = Tfz::new(poly!(1., 2.), Poly::new_from_roots(&[-0.3, 0.5]));
+        assert_eq!(Stability::Stable, tf.is_stable_with(tol));
+
+        let tf = Tfz::new(poly!(1., 2.), Poly::new_from_roots(&[2., 0.25]));
+        assert_eq!(Stability::Unstable, tf.is_stable_with(tol));
+
+        let tf = Tfz::new(poly!(1.), poly!(1.5, 0., 1.));
+        assert_eq!(Stability::Unstable, tf.is_stable_with(tol));
+
+        let tf = Tfz::new(poly!(1.), poly!(1., 0., 1.));
+        assert_eq!(Stability::MarginallyStable, tf.is_stable_with(tol));
+
+        let tf = Tfz::new(poly!(1.), Poly::new_from_roots(&[0.5, 0.999]));
+        assert_eq!(Stability::Stable, tf.is_stable_with(tol));
+        assert_eq!(Stability::MarginallyStable, tf.is_stable_with(0.01));
+
+        let tf = Tfz::new(poly!(1.), poly!(3.));
+        assert_eq!(Stability::Stable, tf.is_stable_with(tol));
+
+        let tf = Tfz::new(poly!(1.), Poly::zero());
+        assert_eq!(Stability::Unstable, tf.is_stable_with(tol));
+    }
+
+    #[test]
+    fn schur_cohn_reciprocal_poles() {
+        // Poles symmetric with respect to the unit circle make the recursion
+        // singular without poles on the unit circle.
+        let tf = Tfz::new(poly!(1.), Poly::new_from_roots(&[2., 0.5]));
+        assert!(!tf.is_stable());
+        assert_eq!(Stability::Unstable, tf.is_stable_with(1e-10));
+
+        let tf = Tfz::new(poly!(1.), Poly::new_from_roots(&[-4., -0.25, 0.3]));
+        assert_eq!(Stability::Unstable, tf.is_stable_with(1e-10));
+
+        let tf = Tfz::new(poly!(1.), Poly::new_from_roots(&[1., -1.]));
+        assert_eq!(Stability::MarginallyStable, tf.is_stable_with(1e-10));
+
+        // Poles on the unit circle together with a reciprocal pair.
+        let tf = Tfz::new(
+            poly!(1.),
+            poly!(1., 0., 1.) * Poly::new_from_roots(&[3., 1. / 3.]),
+        );
+        assert_eq!(Stability::Unstable, tf.is_stable_with(1e-10));
+
+        // Double pole on the unit circle.
+        let tf = Tfz::new(poly!(1.), poly!(1., 0., 1.) * poly!(1., 0., 1.));
+        assert_eq!(Stability::MarginallyStable, tf.is_stable_with(1e-10));
+
+        // Unit product of the magnitudes of the poles, not self-inversive.
+        let tf = Tfz::new(poly!(1.), poly!(-1., 0.3, 1.));
+        assert!(!tf.is_stable());
+        assert_eq!(Stability::Unstable, tf.is_stable_with(1e-10));
+    }
+
+    proptest! {
+        #[test]
+        fn qc_schur_cohn_agrees_with_roots(r1 in -1.9_f64..1.9, r2 in -1.9_f64..1.9) {
+            prop_assume!((r1.abs() - 1.).abs() > 1e-3 && (r2.abs() - 1.).abs() > 1e-3);
+            let tf = Tfz::new(poly!(1.), Poly::new_from_roots(&[r1, r2]));
+            let expected = if tf.is_stable() {
+                Stability::Stable
+            } else {
+                Stability::Unstable
+            };
+            assert_eq!(expected, tf.is_stable_with(1e-6));
+        }
+    }
+
     #[test]
     fn eval() {
         let tf = Tfz::new(
@@ -458,4 +939,82 @@ mod tests {
         assert_eq!(Some(0.03125), iter.next());
         assert_eq!(None, iter.next());
     }
+
+    #[test]
+    fn sample_time_propagation() {
+        use crate::{enums::Discretization, plots::bode::Bode, Seconds, Tf};
+        let tfz =
+            Tf::new(poly!(1.), poly!(1., 1.)).discretize(Seconds(0.1), Discretization::Tustin);
+        assert_eq!(Some(SampleTime(0.1)), tfz.sample_time());
+        let plain = Tfz::new(poly!(2.), poly!(1.));
+        assert_eq!(None, plain.sample_time());
+        assert_eq!(None, plain.frequency_response(RadiansPerSecond(1.)));
+        assert_eq!(Some(SampleTime(0.1)), (&tfz * &plain).sample_time());
+        assert_eq!(Some(SampleTime(0.1)), (plain + tfz.clone()).sample_time());
+        assert_eq!(Some(SampleTime(0.1)), tfz.feedback_n().sample_time());
+        let g = tfz.frequency_response(RadiansPerSecond(2.)).unwrap();
+        assert_eq!(tfz.eval(&Complex64::from_polar(1., 0.2)), g);
+        let bode = Bode::new_sampled(tfz, Seconds(0.1), RadiansPerSecond(1.), 0.1);
+        assert!(bode.into_iter().next().is_some());
+    }
+
+    #[test]
+    fn resample() {
+        use crate::{enums::Discretization, Seconds, Tf};
+        let tf = Tf::new(poly!(1., 0.5), poly!(2., 3., 1.));
+        let tfz = tf.discretize(Seconds(0.1), Discretization::Tustin);
+        let resampled = tfz.resample(SampleTime(0.025)).unwrap();
+        assert_eq!(Some(SampleTime(0.025)), resampled.sample_time());
+        let expected = tf.discretize(Seconds(0.025), Discretization::Tustin);
+        for &z in &[
+            Complex64::new(0.3, 0.4),
+            Complex64::new(-2., 1.),
+            Complex64::i(),
+        ] {
+            let (e, r) = (expected.eval(&z), resampled.eval(&z));
+            assert_relative_eq!(0., (e - r).norm() / e.norm(), epsilon = 1e-10);
+        }
+
+        assert_eq!(
+            None,
+            Tfz::new(poly!(1.), poly!(-0.5, 1.)).resample(SampleTime(0.1))
+        );
+    }
+
+    #[test]
+    fn normalized_frequency_response() {
+        let tfz = Tfz::new(poly!(0.5), poly!(-0.5, 1.)).with_sample_time(SampleTime(0.2));
+        let theta = NormalizedFrequency(0.4);
+        let g = tfz.normalized_frequency_response(theta);
+        assert_eq!(tfz.eval(&Complex64::from_polar(1., 0.4)), g);
+        let omega = theta.to_radians_per_second(tfz.sample_time().unwrap());
+        assert_eq!(Some(g), tfz.frequency_response(omega));
+    }
+
+    #[test]
+    #[should_panic]
+    fn different_sample_times() {
+        let a = Tfz::new(poly!(1.), poly!(1.)).with_sample_time(SampleTime(0.1));
+        let b = Tfz::new(poly!(1.), poly!(1.)).with_sample_time(SampleTime(0.2));
+        let _ = a * b;
+    }
+
+    #[test]
+    fn rounded_sample_times() {
+        use crate::{plots::bode::Bode, Seconds};
+        let a = Tfz::new(poly!(1.), poly!(1.)).with_sample_time(SampleTime(0.1 + 0.2));
+        let b = Tfz::new(poly!(1.), poly!(2.)).with_sample_time(SampleTime(0.3));
+        let c = &a / &b;
+        assert_relative_eq!(0.3, c.sample_time().unwrap().0);
+        let bode = Bode::new_sampled(c, Seconds(0.3), RadiansPerSecond(1.), 0.1);
+        assert!(bode.into_iter().next().is_some());
+    }
+
+    #[test]
+    #[should_panic]
+    fn bode_different_sample_time() {
+        use crate::{plots::bode::Bode, Seconds};
+        let tfz = Tfz::new(poly!(1.), poly!(1.)).with_sample_time(SampleTime(0.1));
+        let _ = Bode::new_sampled(tfz, Seconds(0.2), RadiansPerSecond(1.), 0.1);
+    }
 }
@@ -103,7 +103,7 @@ impl<T: Float> Tf<T> {
     /// assert_eq!(0.1 / 1.1, tfz.real_poles().unwrap()[0]);
     /// ```
     pub fn discretize(&self, ts: Seconds<T>, method: Discretization) -> Tfz<T> {
-        match method {
+        let tfz = match method {
             Discretization::ForwardEuler => {
                 let t = ts.0.recip();
                 let s = Poly::new_from_coeffs(&[-t, t]);
@@ -122,7 +122,8 @@ impl<T: Float> Tf<T> {
                 let s_den = Poly::new_from_coeffs(&[T::one(), T::one()]);
                 discr_impl(self, &s_num, &s_den)
             }
-        }
+        };
+        tfz.with_sample_time(ts.into())
     }
 
     /// Convert a continuous time transfer function into a discrete time
@@ -147,7 +148,7 @@ impl<T: Float> Tf<T> {
         let k = warp_freq.0 / (warp_freq.0 * ts.0 / two).tan();
         let s_num = Poly::new_from_coeffs(&[-T::one(), T::one()]) * k;
         let s_den = Poly::new_from_coeffs(&[T::one(), T::one()]);
-        discr_impl(self, &s_num, &s_den)
+        discr_impl(self, &s_num, &s_den).with_sample_time(ts.into())
     }
 }
 
@@ -163,7 +164,7 @@ fn discr_impl<T: Float>(tf: &Tf<T>, s_num: &Poly<T>, s_den: &Poly<T>) -> Tfz<T>
             Tfz::new(num, den)
         }
         g if g < 0 => {
-            let den = den * s_num.powi(-g as u32);
+            let den = den * s_den.powi(-g as u32);
             Tfz::new(num, den)
         }
         _ => Tfz::new(num, den),
@@ -214,7 +215,10 @@ impl<T: Float> TfDiscretization<T> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{polynomial::Poly, units::ToDecibel};
+    use crate::{
+        polynomial::Poly,
+        units::{SampleTime, ToDecibel},
+    };
     use num_complex::Complex64;
 
     #[test]
@@ -296,7 +300,8 @@ mod tests {
         let expected = Tfz::new(
             Poly::new_from_coeffs(&[-180., 200.]),
             Poly::new_from_coeffs(&[9., 1.]),
-        );
+        )
+        .with_sample_time(SampleTime(1.));
         assert_eq!(expected, tfz);
     }
 
@@ -312,7 +317,8 @@ mod tests {
         let expected = Tfz::new(
             Poly::new_from_coeffs(&[5.62, -11.6, 6.]),
             Poly::new_from_coeffs(&[-0.92, 1.]),
-        );
+        )
+        .with_sample_time(SampleTime(0.1));
         assert_eq!(expected, tfz);
     }
 
@@ -328,7 +334,8 @@ mod tests {
         let expected = Tfz::new(
             Poly::new_from_coeffs(&[-0.46, 0.5]),
             Poly::new_from_coeffs(&[0.83, -1.8, 1.]),
-        );
+        )
+        .with_sample_time(SampleTime(0.1));
         assert_eq!(expected, tfz);
     }
 
@@ -345,7 +352,8 @@ mod tests {
         let expected = Tfz::new(
             Poly::new_from_coeffs(&[-20. / 1.1, 20.]),
             Poly::new_from_coeffs(&[-0.1 / 1.1, 1.]),
-        );
+        )
+        .with_sample_time(SampleTime(1.));
         assert_eq!(expected, tfz);
     }
 
@@ -362,7 +370,8 @@ mod tests {
         let expected = Tfz::new(
             Poly::new_from_coeffs(&[-38. / 1.2, 35.]),
             Poly::new_from_coeffs(&[0.8 / 1.2, 1.]),
-        );
+        )
+        .with_sample_time(SampleTime(1.));
         assert_eq!(expected, tfz);
     }
 
@@ -379,7 +388,8 @@ mod tests {
         let expected = Tfz::new(
             Poly::new_from_coeffs(&[-31.643_282, 34.977_077]),
             Poly::new_from_coeffs(&[0.666_898_2, 1.]),
-        );
+        )
+        .with_sample_time(SampleTime(1.));
         assert_eq!(expected, tfz);
     }
 }
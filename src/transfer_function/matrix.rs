@@ -5,7 +5,9 @@
 //! since it is equal for every transfer function.
 //! * evaluation of a vector of inputs
 //! * conversion from a generic state space representation
+//! * optional names of the input and output channels
 
+use nalgebra::{DMatrix, Scalar};
 use ndarray::{Array2, Axis, Zip};
 use num_complex::Complex;
 use num_traits::{Float, MulAdd, One, Signed, Zero};
@@ -20,8 +22,10 @@ use crate::{
     complex,
     enums::Time,
     linear_system::{self, SsGen},
+    plots::sigma::SigmaPlot,
     polynomial::Poly,
     polynomial_matrix::{MatrixOfPoly, PolyMatrix},
+    units::RadiansPerSecond,
 };
 
 /// Matrix of transfer functions
@@ -31,6 +35,10 @@ pub struct TfMatrix<T> {
     num: MatrixOfPoly<T>,
     /// Common polynomial denominator
     den: Poly<T>,
+    /// Names of the inputs
+    input_names: Option<Vec<String>>,
+    /// Names of the outputs
+    output_names: Option<Vec<String>>,
 }
 
 /// Implementation of transfer function matrix
@@ -42,7 +50,71 @@ impl<T> TfMatrix<T> {
     /// * `num` - Polynomial matrix
     /// * `den` - Characteristic polynomial of the system
     fn new(num: MatrixOfPoly<T>, den: Poly<T>) -> Self {
-        Self { num, den }
+        Self {
+            num,
+            den,
+            input_names: None,
+            output_names: None,
+        }
+    }
+
+    /// Set the names of the inputs of the transfer function matrix.
+    ///
+    /// # Arguments
+    ///
+    /// * `names` - Names of the inputs
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of names is different from the number of inputs.
+    #[must_use]
+    pub fn with_input_names<I, S>(mut self, names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: ToString,
+    {
+        let inputs = self.num.matrix().ncols();
+        self.input_names = Some(linear_system::collect_names(names, inputs, "inputs"));
+        self
+    }
+
+    /// Set the names of the outputs of the transfer function matrix.
+    ///
+    /// # Arguments
+    ///
+    /// * `names` - Names of the outputs
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of names is different from the number of outputs.
+    #[must_use]
+    pub fn with_output_names<I, S>(mut self, names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: ToString,
+    {
+        let outputs = self.num.matrix().nrows();
+        self.output_names = Some(linear_system::collect_names(names, outputs, "outputs"));
+        self
+    }
+
+    /// Get the names of the inputs, `None` if the inputs are not named.
+    #[must_use]
+    pub fn input_names(&self) -> Option<&[String]> {
+        self.input_names.as_deref()
+    }
+
+    /// Get the names of the outputs, `None` if the outputs are not named.
+    #[must_use]
+    pub fn output_names(&self) -> Option<&[String]> {
+        self.output_names.as_deref()
+    }
+
+    /// Copy the names of the channels of a state-space system.
+    fn with_names_of<U: Time, N: Scalar>(mut self, ss: &SsGen<N, U>) -> Self {
+        self.input_names = ss.input_names().map(<[String]>::to_vec);
+        self.output_names = ss.output_names().map(<[String]>::to_vec);
+        self
     }
 }
 
@@ -95,8 +167,31 @@ impl<T: Float + MulAdd<Output = T>> TfMatrix<T> {
     }
 }
 
+impl<T: Float + MulAdd<Output = T> + Scalar> SigmaPlot<T> for TfMatrix<T> {
+    /// Evaluate the matrix of transfer functions at the given angular
+    /// frequencies.
+    ///
+    /// # Arguments
+    ///
+    /// * `freqs` - angular frequencies at which the matrix is evaluated
+    fn eval_matrices(&self, freqs: &[RadiansPerSecond<T>]) -> Vec<DMatrix<Complex<T>>> {
+        let (rows, cols) = self.num.matrix().dim();
+        freqs
+            .iter()
+            .map(|w| {
+                let s = Complex::new(T::zero(), w.0);
+                let den = self.den.eval(&s);
+                DMatrix::from_fn(rows, cols, |i, j| {
+                    complex::compdiv(self.num.matrix()[[i, j]].eval(&s), den)
+                })
+            })
+            .collect()
+    }
+}
+
 impl<T: Time> From<SsGen<f64, T>> for TfMatrix<f64> {
-    /// Convert a state-space representation into a matrix of transfer functions
+    /// Convert a state-space representation into a matrix of transfer functions.
+    /// The names of the inputs and of the outputs are preserved.
     ///
     /// # Arguments
     ///
@@ -106,12 +201,13 @@ impl<T: Time> From<SsGen<f64, T>> for TfMatrix<f64> {
         let g = a_inv.left_mul(&ss.c).right_mul(&ss.b);
         let rest = PolyMatrix::multiply(&pc, &ss.d);
         let tf = g + rest;
-        Self::new(MatrixOfPoly::from(tf), pc)
+        Self::new(MatrixOfPoly::from(tf), pc).with_names_of(&ss)
     }
 }
 
 impl<T: Time> From<SsGen<f32, T>> for TfMatrix<f32> {
-    /// Convert a state-space representation into a matrix of transfer functions
+    /// Convert a state-space representation into a matrix of transfer functions.
+    /// The names of the inputs and of the outputs are preserved.
     ///
     /// # Arguments
     ///
@@ -121,7 +217,7 @@ impl<T: Time> From<SsGen<f32, T>> for TfMatrix<f32> {
         let g = a_inv.left_mul(&ss.c).right_mul(&ss.b);
         let rest = PolyMatrix::multiply(&pc, &ss.d);
         let tf = g + rest;
-        Self::new(MatrixOfPoly::from(tf), pc)
+        Self::new(MatrixOfPoly::from(tf), pc).with_names_of(&ss)
     }
 }
 
@@ -162,7 +258,14 @@ where
         let length = s_den.len();
         let dash = "\u{2500}".repeat(length);
 
-        write!(f, "{}\n{}\n{}", s_num, dash, s_den)
+        write!(f, "{}\n{}\n{}", s_num, dash, s_den)?;
+        if let Some(n) = &self.input_names {
+            write!(f, "\nInputs: {}", n.join(", "))?;
+        }
+        if let Some(n) = &self.output_names {
+            write!(f, "\nOutputs: {}", n.join(", "))?;
+        }
+        Ok(())
     }
 }
 
@@ -171,6 +274,23 @@ mod tests {
     use super::*;
     use crate::{poly, Ss, Ssd};
 
+    #[test]
+    fn tf_matrix_names() {
+        let sys = Ss::new_from_slice(1, 2, 1, &[-1.], &[1., 2.], &[1.], &[0., 0.])
+            .with_input_names(["u1", "u2"])
+            .with_output_names(["y"]);
+        let tfm = TfMatrix::<f64>::from(sys);
+        assert_eq!(
+            Some(&["u1".to_owned(), "u2".to_owned()][..]),
+            tfm.input_names()
+        );
+        assert_eq!(Some(&["y".to_owned()][..]), tfm.output_names());
+        assert!(format!("{}", tfm).ends_with("\nInputs: u1, u2\nOutputs: y"));
+
+        let tfm = tfm.with_output_names(["z"]);
+        assert_eq!(Some(&["z".to_owned()][..]), tfm.output_names());
+    }
+
     #[test]
     fn tf_matrix_new() {
         let sys = Ssd::new_from_slice(
@@ -39,15 +39,24 @@ use crate::{
     polynomial::Poly,
     polynomial_matrix::{MatrixOfPoly, PolyMatrix},
     rational_function::Rf,
+    units::joint_sample_time,
 };
 
 /// Transfer function representation of a linear system
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde-serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound(deserialize = "T: Clone + PartialEq + Zero + serde::Deserialize<'de>"))
+)]
 pub struct TfGen<T, U: Time> {
     /// Rational function
     rf: Rf<T>,
     /// Tag to disambiguate continuous and discrete
+    #[cfg_attr(feature = "serde-serialize", serde(skip))]
     time: PhantomData<U>,
+    /// Sampling period of discrete time transfer functions, if known
+    pub(crate) sample_time: Option<T>,
 }
 
 impl<T: Float, U: Time> TfGen<T, U> {
@@ -68,6 +77,7 @@ impl<T: Float, U: Time> TfGen<T, U> {
         Self {
             rf: Rf::new(num, den),
             time: PhantomData::<U>,
+            sample_time: None,
         }
     }
 
@@ -85,6 +95,77 @@ impl<T: Float, U: Time> TfGen<T, U> {
     pub fn relative_degree(&self) -> i32 {
         self.rf.relative_degree()
     }
+
+    /// Check if the transfer function is proper, i.e. the degree of the
+    /// numerator is not greater than the degree of the denominator.
+    ///
+    /// # Example
+    /// ```
+    /// use au::{num_traits::Inv, poly, Tf};
+    /// let tf = Tf::new(poly!(1., 2.), poly!(-4., 6., -2.));
+    /// assert!(tf.is_proper());
+    /// assert!(!tf.inv().is_proper());
+    /// ```
+    #[must_use]
+    pub fn is_proper(&self) -> bool {
+        self.rf.is_proper()
+    }
+
+    /// Check if the transfer function is strictly proper, i.e. the degree of
+    /// the numerator is lower than the degree of the denominator.
+    ///
+    /// # Example
+    /// ```
+    /// use au::{poly, Tf};
+    /// let tf = Tf::new(poly!(1., 2.), poly!(-4., 6., -2.));
+    /// assert!(tf.is_strictly_proper());
+    /// ```
+    #[must_use]
+    pub fn is_strictly_proper(&self) -> bool {
+        self.rf.is_strictly_proper()
+    }
+
+    /// Check if the transfer function is biproper, i.e. the degree of the
+    /// numerator is equal to the degree of the denominator.
+    ///
+    /// # Example
+    /// ```
+    /// use au::{poly, Tf};
+    /// let tf = Tf::new(poly!(1., 2.), poly!(-4., 6.));
+    /// assert!(tf.is_biproper());
+    /// ```
+    #[must_use]
+    pub fn is_biproper(&self) -> bool {
+        self.rf.is_biproper()
+    }
+
+    /// Split the transfer function into a polynomial part and a strictly
+    /// proper transfer function.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the denominator is zero.
+    ///
+    /// # Example
+    /// ```
+    /// use au::{poly, Tf};
+    /// let tf = Tf::new(poly!(1., 0., 1.), poly!(1., 1.));
+    /// let (q, r) = tf.make_proper();
+    /// assert_eq!(poly!(-1., 1.), q);
+    /// assert_eq!(Tf::new(poly!(2.), poly!(1., 1.)), r);
+    /// ```
+    #[must_use]
+    pub fn make_proper(&self) -> (Poly<T>, Self) {
+        let (q, r) = self.rf.make_proper();
+        (
+            q,
+            Self {
+                rf: r,
+                time: PhantomData,
+                sample_time: self.sample_time,
+            },
+        )
+    }
 }
 
 impl<T, U: Time> TfGen<T, U> {
@@ -132,6 +213,7 @@ impl<T: Clone, U: Time> Inv for &TfGen<T, U> {
         Self::Output {
             rf: Inv::inv(&self.rf),
             time: PhantomData,
+            sample_time: self.sample_time.clone(),
         }
     }
 }
@@ -186,6 +268,7 @@ impl<T: Float, U: Time> TfGen<T, U> {
         Self {
             rf: Rf::new(self.rf.num().clone(), self.den() + self.num()),
             time: PhantomData,
+            sample_time: self.sample_time,
         }
     }
 
@@ -202,6 +285,7 @@ impl<T: Float, U: Time> TfGen<T, U> {
         Self {
             rf: Rf::new(self.rf.num().clone(), self.den() - self.num()),
             time: PhantomData,
+            sample_time: self.sample_time,
         }
     }
 
@@ -233,6 +317,7 @@ impl<T: Float, U: Time> TfGen<T, U> {
         Self {
             rf: self.rf.normalize(),
             time: PhantomData,
+            sample_time: self.sample_time,
         }
     }
 
@@ -286,11 +371,51 @@ macro_rules! from_ss_to_tr {
                 let rest = PolyMatrix::multiply(&pc, &ss.d);
                 let tf = g + rest;
                 if let Some(num) = MatrixOfPoly::from(tf).single() {
-                    Ok(Self::new(num.clone(), pc))
+                    let mut tf = Self::new(num.clone(), pc);
+                    tf.sample_time = ss.sample_time;
+                    Ok(tf)
                 } else {
                     Err(Error::new_internal(ErrorKind::NoSisoSystem))
                 }
             }
+
+            /// Extract the transfer function between an input and an output
+            /// of a multiple input multiple output system.
+            ///
+            /// # Arguments
+            ///
+            /// * `ss` - state space linear system
+            /// * `input` - index of the input channel
+            /// * `output` - index of the output channel
+            ///
+            /// # Errors
+            ///
+            /// It returns an error if the input or the output channel does not
+            /// exist.
+            ///
+            /// # Example
+            /// ```
+            /// use au::{Ss, Tf};
+            /// let sys = Ss::new_from_slice(
+            ///     2, 2, 2,
+            ///     &[-1., 0., 0., -2.],
+            ///     &[1., 0., 0., 1.],
+            ///     &[1., 0., 0., 1.],
+            ///     &[0., 0., 0., 0.],
+            /// );
+            /// let tf = Tf::<f64>::new_from_siso_channel(&sys, 1, 1).unwrap();
+            /// assert_eq!(0.5, tf.eval(&0.));
+            /// ```
+            pub fn new_from_siso_channel(
+                ss: &SsGen<$ty, U>,
+                input: usize,
+                output: usize,
+            ) -> Result<Self, Error> {
+                if input >= ss.dim().inputs() || output >= ss.dim().outputs() {
+                    return Err(Error::new_internal(ErrorKind::ChannelOutOfRange));
+                }
+                Self::new_from_siso(&ss.subsystem(&[input], &[output]))
+            }
         }
     };
 }
@@ -307,6 +432,7 @@ impl<T: Float, U: Time> Neg for &TfGen<T, U> {
         Self::Output {
             rf: Neg::neg(&self.rf),
             time: PhantomData,
+            sample_time: self.sample_time,
         }
     }
 }
@@ -327,10 +453,14 @@ impl<T: Float, U: Time> Neg for TfGen<T, U> {
 impl<T: Float, U: Time> Add for &TfGen<T, U> {
     type Output = TfGen<T, U>;
 
+    /// # Panics
+    ///
+    /// Panics if the transfer functions have different sampling periods.
     fn add(self, rhs: Self) -> Self::Output {
         Self::Output {
             rf: Add::add(&self.rf, &rhs.rf),
             time: PhantomData,
+            sample_time: joint_sample_time(self.sample_time, rhs.sample_time),
         }
     }
 }
@@ -339,7 +469,11 @@ impl<T: Float, U: Time> Add for &TfGen<T, U> {
 impl<T: Float, U: Time> Add for TfGen<T, U> {
     type Output = Self;
 
+    /// # Panics
+    ///
+    /// Panics if the transfer functions have different sampling periods.
     fn add(mut self, rhs: Self) -> Self {
+        self.sample_time = joint_sample_time(self.sample_time, rhs.sample_time);
         self.rf = Add::add(self.rf, rhs.rf);
         self
     }
@@ -365,14 +499,56 @@ impl<T: Float, U: Time> Add<&T> for TfGen<T, U> {
     }
 }
 
+/// Implementation of transfer function and polynomial addition
+impl<T: Float, U: Time> Add<Poly<T>> for TfGen<T, U> {
+    type Output = Self;
+
+    fn add(mut self, rhs: Poly<T>) -> Self {
+        self.rf = Add::add(self.rf, rhs);
+        self
+    }
+}
+
+/// Implementation of transfer function and polynomial addition
+impl<T: Float, U: Time> Add<&Poly<T>> for TfGen<T, U> {
+    type Output = Self;
+
+    fn add(mut self, rhs: &Poly<T>) -> Self {
+        self.rf = Add::add(self.rf, rhs);
+        self
+    }
+}
+
+/// Implementation of polynomial and transfer function addition
+impl<T: Float, U: Time> Add<TfGen<T, U>> for Poly<T> {
+    type Output = TfGen<T, U>;
+
+    fn add(self, rhs: TfGen<T, U>) -> TfGen<T, U> {
+        rhs + self
+    }
+}
+
+/// Implementation of polynomial and transfer function addition
+impl<T: Float, U: Time> Add<TfGen<T, U>> for &Poly<T> {
+    type Output = TfGen<T, U>;
+
+    fn add(self, rhs: TfGen<T, U>) -> TfGen<T, U> {
+        rhs + self
+    }
+}
+
 /// Implementation of transfer function subtraction
 impl<T: Float, U: Time> Sub for &TfGen<T, U> {
     type Output = TfGen<T, U>;
 
+    /// # Panics
+    ///
+    /// Panics if the transfer functions have different sampling periods.
     fn sub(self, rhs: Self) -> Self::Output {
         Self::Output {
             rf: Sub::sub(&self.rf, &rhs.rf),
             time: PhantomData,
+            sample_time: joint_sample_time(self.sample_time, rhs.sample_time),
         }
     }
 }
@@ -381,7 +557,11 @@ impl<T: Float, U: Time> Sub for &TfGen<T, U> {
 impl<T: Float, U: Time> Sub for TfGen<T, U> {
     type Output = Self;
 
+    /// # Panics
+    ///
+    /// Panics if the transfer functions have different sampling periods.
     fn sub(mut self, rhs: Self) -> Self {
+        self.sample_time = joint_sample_time(self.sample_time, rhs.sample_time);
         self.rf = Sub::sub(self.rf, rhs.rf);
         self
     }
@@ -391,10 +571,14 @@ impl<T: Float, U: Time> Sub for TfGen<T, U> {
 impl<T: Float, U: Time> Mul for &TfGen<T, U> {
     type Output = TfGen<T, U>;
 
+    /// # Panics
+    ///
+    /// Panics if the transfer functions have different sampling periods.
     fn mul(self, rhs: Self) -> Self::Output {
         Self::Output {
             rf: Mul::mul(&self.rf, &rhs.rf),
             time: PhantomData,
+            sample_time: joint_sample_time(self.sample_time, rhs.sample_time),
         }
     }
 }
@@ -403,7 +587,11 @@ impl<T: Float, U: Time> Mul for &TfGen<T, U> {
 impl<T: Float, U: Time> Mul for TfGen<T, U> {
     type Output = Self;
 
+    /// # Panics
+    ///
+    /// Panics if the transfer functions have different sampling periods.
     fn mul(mut self, rhs: Self) -> Self {
+        self.sample_time = joint_sample_time(self.sample_time, rhs.sample_time);
         self.rf = Mul::mul(self.rf, rhs.rf);
         self
     }
@@ -413,20 +601,119 @@ impl<T: Float, U: Time> Mul for TfGen<T, U> {
 impl<T: Float, U: Time> Mul<&TfGen<T, U>> for TfGen<T, U> {
     type Output = Self;
 
+    /// # Panics
+    ///
+    /// Panics if the transfer functions have different sampling periods.
     fn mul(mut self, rhs: &TfGen<T, U>) -> Self {
+        self.sample_time = joint_sample_time(self.sample_time, rhs.sample_time);
         self.rf = Mul::mul(self.rf, &rhs.rf);
         self
     }
 }
 
+/// Implementation of transfer function and scalar multiplication
+impl<T: Float, U: Time> Mul<T> for TfGen<T, U> {
+    type Output = Self;
+
+    fn mul(mut self, rhs: T) -> Self {
+        self.rf = Rf::new(self.rf.num() * rhs, self.rf.den().clone());
+        self
+    }
+}
+
+/// Implementation of transfer function and scalar multiplication
+impl<T: Float, U: Time> Mul<&T> for TfGen<T, U> {
+    type Output = Self;
+
+    fn mul(mut self, rhs: &T) -> Self {
+        self.rf = Rf::new(self.rf.num() * rhs, self.rf.den().clone());
+        self
+    }
+}
+
+/// Implementation of transfer function and polynomial multiplication
+impl<T: Float, U: Time> Mul<Poly<T>> for TfGen<T, U> {
+    type Output = Self;
+
+    fn mul(mut self, rhs: Poly<T>) -> Self {
+        self.rf = Mul::mul(self.rf, rhs);
+        self
+    }
+}
+
+/// Implementation of transfer function and polynomial multiplication
+impl<T: Float, U: Time> Mul<&Poly<T>> for TfGen<T, U> {
+    type Output = Self;
+
+    fn mul(mut self, rhs: &Poly<T>) -> Self {
+        self.rf = Mul::mul(self.rf, rhs);
+        self
+    }
+}
+
+/// Implementation of polynomial and transfer function multiplication
+impl<T: Float, U: Time> Mul<TfGen<T, U>> for Poly<T> {
+    type Output = TfGen<T, U>;
+
+    fn mul(self, rhs: TfGen<T, U>) -> TfGen<T, U> {
+        rhs * self
+    }
+}
+
+/// Implementation of polynomial and transfer function multiplication
+impl<T: Float, U: Time> Mul<TfGen<T, U>> for &Poly<T> {
+    type Output = TfGen<T, U>;
+
+    fn mul(self, rhs: TfGen<T, U>) -> TfGen<T, U> {
+        rhs * self
+    }
+}
+
+macro_rules! impl_scalar_ops_for_tf {
+    (
+        $(#[$meta:meta])*
+            $f:ty
+    ) => {
+        $(#[$meta])*
+        impl<U: Time> Add<TfGen<$f, U>> for $f {
+            type Output = TfGen<Self, U>;
+
+            fn add(self, rhs: TfGen<Self, U>) -> TfGen<Self, U> {
+                rhs + self
+            }
+        }
+        $(#[$meta])*
+        impl<U: Time> Mul<TfGen<$f, U>> for $f {
+            type Output = TfGen<Self, U>;
+
+            fn mul(self, rhs: TfGen<Self, U>) -> TfGen<Self, U> {
+                rhs * self
+            }
+        }
+    };
+}
+
+impl_scalar_ops_for_tf!(
+    /// Implementation of f32 and transfer function addition and multiplication
+    f32
+);
+impl_scalar_ops_for_tf!(
+    /// Implementation of f64 and transfer function addition and multiplication
+    f64
+);
+
 /// Implementation of transfer function division
 impl<T: Float, U: Time> Div for &TfGen<T, U> {
     type Output = TfGen<T, U>;
 
+    /// # Panics
+    ///
+    /// Panics if the transfer functions have different sampling periods.
     fn div(self, rhs: Self) -> Self::Output {
         Self::Output {
             rf: Div::div(&self.rf, &rhs.rf),
             time: PhantomData,
+            sample_time: joint_sample_time(self.sample_time, rhs.sample_time),
         }
     }
 }
@@ -435,7 +722,11 @@ impl<T: Float, U: Time> Div for &TfGen<T, U> {
 impl<T: Float, U: Time> Div for TfGen<T, U> {
     type Output = Self;
 
+    /// # Panics
+    ///
+    /// Panics if the transfer functions have different sampling periods.
     fn div(mut self, rhs: Self) -> Self {
+        self.sample_time = joint_sample_time(self.sample_time, rhs.sample_time);
         self.rf = Div::div(self.rf, rhs.rf);
         self
     }
@@ -446,6 +737,7 @@ impl<T: Float, U: Time> Zero for TfGen<T, U> {
         Self {
             rf: Rf::zero(),
             time: PhantomData,
+            sample_time: None,
         }
     }
 
@@ -528,6 +820,18 @@ mod tests {
         assert_eq!(&den, tf.den());
     }
 
+    #[test]
+    fn properness() {
+        let tf = TfGen::<_, Continuous>::new(poly!(1., 0., 2.), poly!(3., 1.));
+        assert!(!tf.is_proper());
+        let (q, r) = tf.make_proper();
+        assert!(r.is_strictly_proper());
+        assert_eq!(poly!(-6., 2.), q);
+        assert_eq!(poly!(19.), r.num().clone());
+        assert!(tf.inv().is_strictly_proper());
+        assert!(TfGen::<_, Continuous>::new(poly!(1., 1.), poly!(1., 2.)).is_biproper());
+    }
+
     #[test]
     fn relative_degree() {
         let tfz = TfGen::<_, Continuous>::new(poly!(1., 2.), poly!(-4., 6., -2.));
@@ -690,6 +994,26 @@ mod tests {
         assert_eq!(expected, actual);
     }
 
+    #[test]
+    fn add_polynomial() {
+        let tf = TfGen::<_, Continuous>::new(poly!(1., 2.), poly!(3., -4.));
+        let expected = TfGen::new(poly!(4., 1., -4.), poly!(3., -4.));
+        assert_eq!(expected, tf.clone() + poly!(1., 1.));
+        assert_eq!(expected, tf.clone() + &poly!(1., 1.));
+        assert_eq!(expected, poly!(1., 1.) + tf.clone());
+        assert_eq!(expected, &poly!(1., 1.) + tf);
+    }
+
+    #[test]
+    fn add_scalar_left() {
+        let tf = TfGen::<_, Discrete>::new(poly!(1., 2.), poly!(3., -4.));
+        let expected = TfGen::new(poly!(4., -2.), poly!(3., -4.));
+        assert_eq!(expected, 1. + tf);
+        let tf = TfGen::<_, Discrete>::new(poly!(1_f32, 2.), poly!(3., -4.));
+        let expected = TfGen::new(poly!(4., -2.), poly!(3., -4.));
+        assert_eq!(expected, 1. + tf);
+    }
+
     #[test]
     fn sub_references() {
         let tf1 = TfGen::<_, Continuous>::new(poly!(-1., 9.), poly!(4., -1.));
@@ -747,6 +1071,36 @@ mod tests {
         assert_eq!(TfGen::zero(), TfGen::zero() * expected);
     }
 
+    #[test]
+    #[allow(clippy::op_ref)]
+    fn mul_scalar() {
+        let tf = TfGen::<_, Continuous>::new(poly!(1., 2.), poly!(3., -4.));
+        let expected = TfGen::new(poly!(2., 4.), poly!(3., -4.));
+        assert_eq!(expected, tf.clone() * 2.);
+        assert_eq!(expected, tf.clone() * &2.);
+        assert_eq!(expected, 2. * tf);
+        let tf = TfGen::<_, Continuous>::new(poly!(1_f32, 2.), poly!(3., -4.));
+        assert_eq!(TfGen::new(poly!(2., 4.), poly!(3., -4.)), 2. * tf);
+    }
+
+    #[test]
+    fn mul_polynomial() {
+        let tf = TfGen::<_, Continuous>::new(poly!(1., 2.), poly!(3., -4.));
+        let expected = TfGen::new(poly!(0., 1., 2.), poly!(3., -4.));
+        assert_eq!(expected, tf.clone() * poly!(0., 1.));
+        assert_eq!(expected, tf.clone() * &poly!(0., 1.));
+        assert_eq!(expected, poly!(0., 1.) * tf.clone());
+        assert_eq!(expected, &poly!(0., 1.) * tf);
+    }
+
+    #[test]
+    fn mixed_expression() {
+        let g = TfGen::<_, Continuous>::new(poly!(1.), poly!(1., 1.));
+        let actual = 1. + 2. * g;
+        let expected = TfGen::new(poly!(3., 1.), poly!(1., 1.));
+        assert_eq!(expected, actual);
+    }
+
     #[test]
     fn mul_value_reference() {
         let tf1 = TfGen::<_, Continuous>::new(poly!(1., 2., 3.), poly!(1., 5.));
@@ -845,6 +1199,36 @@ mod tests {
         assert!(res.is_err());
     }
 
+    #[test]
+    fn conversion_from_ss_channel() {
+        let ss = crate::Ss::new_from_slice(
+            2,
+            2,
+            2,
+            &[-1., 0., 1., -2.],
+            &[1., 0., 0., 1.],
+            &[1., 0., 0., 1.],
+            &[0., 0.5, 0., 0.],
+        );
+        let tf = TfGen::<f64, Continuous>::new_from_siso_channel(&ss, 1, 0)
+            .unwrap()
+            .normalize();
+        // G01(s) = 0.5
+        assert_relative_eq!(0.5, tf.eval(&0.));
+        assert_relative_eq!(0.5, tf.eval(&3.));
+        let tf = TfGen::<f64, Continuous>::new_from_siso_channel(&ss, 0, 1)
+            .unwrap()
+            .normalize();
+        // G10(s) = 1 / ((s + 1)(s + 2))
+        assert_relative_eq!(0.5, tf.eval(&0.));
+        assert_relative_eq!(1. / 6., tf.eval(&1.));
+
+        let err = TfGen::<f64, Continuous>::new_from_siso_channel(&ss, 2, 0).unwrap_err();
+        assert_eq!(ErrorKind::ChannelOutOfRange, err.kind());
+        let err = TfGen::<f64, Continuous>::new_from_siso_channel(&ss, 0, 2).unwrap_err();
+        assert_eq!(ErrorKind::ChannelOutOfRange, err.kind());
+    }
+
     #[test]
     fn eval_trasfer_function() {
         let s_num = Poly::new_from_coeffs(&[-1., 1.]);
@@ -855,4 +1239,17 @@ mod tests {
         let expected = TfGen::<f64, Continuous>::new(poly!(3., -8., 6.), poly!(0., 0., 1.));
         assert_eq!(expected, r);
     }
+
+    #[cfg(feature = "serde-serialize")]
+    #[test]
+    fn serde_round_trip() {
+        let tf = TfGen::<f64, Continuous>::new(poly!(1., 2.), poly!(3., 4., 5.));
+        let json = serde_json::to_string(&tf).unwrap();
+        assert_eq!(tf, serde_json::from_str(&json).unwrap());
+
+        let mut tfz = TfGen::<f32, Discrete>::new(poly!(1.), poly!(-0.5, 1.));
+        tfz.sample_time = Some(0.1);
+        let json = serde_json::to_string(&tfz).unwrap();
+        assert_eq!(tfz, serde_json::from_str(&json).unwrap());
+    }
 }
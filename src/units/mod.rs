@@ -6,16 +6,36 @@
 //! * seconds
 //! * Hertz
 //! * radians per second
+//! * radians
+//! * degrees
+//! * sampling period
+//! * natural frequency
+//! * damping ratio
+//! * normalized frequency of discrete time systems
 //!
 //! Conversion between units are available.
+//!
+//! Frequencies can be compared and shifted by decades and octaves.
+//! ```
+//! use au::RadiansPerSecond;
+//! let w = RadiansPerSecond(2.);
+//! assert!((w.decades_to(RadiansPerSecond(200.)) - 2_f64).abs() < 1e-12);
+//! assert!((w.shift_octaves(-1.).0 - 1_f64).abs() < 1e-12);
+//! ```
+//!
+//! With the `serde-serialize` feature the units are serialized as their
+//! inner value.
 
 use std::{
-    convert::From,
+    convert::{From, TryFrom},
     fmt::{Display, Formatter, LowerExp, UpperExp},
+    time::Duration,
 };
 
 use num_traits::{Float, FloatConst, Inv, Num};
 
+use crate::error::{Error, ErrorKind};
+
 /// Macro to implement Display trait for units. It passes the formatter options
 /// to the unit inner type.
 ///
@@ -48,10 +68,50 @@ macro_rules! impl_display {
     };
 }
 
-/// Trait for the conversion to decibels.
+/// Trait for the conversion to and from decibels.
 pub trait ToDecibel {
-    /// Convert to decibels
+    /// Convert an amplitude ratio to decibels, `20*log10(x)`
     fn to_db(&self) -> Self;
+
+    /// Convert a power ratio to decibels, `10*log10(x)`
+    fn to_db_power(&self) -> Self
+    where
+        Self: Float,
+    {
+        ten::<Self>() * self.log10()
+    }
+
+    /// Convert decibels to an amplitude ratio, `10^(x/20)`
+    ///
+    /// # Example
+    /// ```
+    /// use au::units::ToDecibel;
+    /// assert!((6_f64.from_db() - 1.995).abs() < 1e-3);
+    /// ```
+    #[allow(clippy::wrong_self_convention)]
+    fn from_db(&self) -> Self
+    where
+        Self: Float,
+    {
+        let ten = ten::<Self>();
+        ten.powf(*self / (ten + ten))
+    }
+
+    /// Convert decibels to a power ratio, `10^(x/10)`
+    #[allow(clippy::wrong_self_convention)]
+    fn from_db_power(&self) -> Self
+    where
+        Self: Float,
+    {
+        let ten = ten::<Self>();
+        ten.powf(*self / ten)
+    }
+}
+
+/// Ten in the given floating point type.
+fn ten<T: Float>() -> T {
+    // Casting is safe for both f32 and f64, representation is exact.
+    T::from(10_u8).unwrap()
 }
 
 /// Implementation of the Decibels for f64
@@ -71,25 +131,166 @@ impl ToDecibel for f32 {
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+#[cfg_attr(
+    feature = "serde-serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
 /// Unit of measurement: deciBel \[dB\]
 pub struct Decibel<T: Num>(pub T);
 
 /// Unit of measurement: seconds \[s\]
 #[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+#[cfg_attr(
+    feature = "serde-serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
 pub struct Seconds<T: Num>(pub T);
 
 /// Unit of measurement: Hertz \[Hz\]
 #[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+#[cfg_attr(
+    feature = "serde-serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
 pub struct Hertz<T: Num>(pub T);
 
 /// Unit of measurement: Radians per seconds \[rad/s\]
 #[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+#[cfg_attr(
+    feature = "serde-serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
 pub struct RadiansPerSecond<T: Num>(pub T);
 
+/// Unit of measurement: Radians \[rad\]
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+#[cfg_attr(
+    feature = "serde-serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct Radians<T: Num>(pub T);
+
+/// Unit of measurement: Degrees \[°\]
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+#[cfg_attr(
+    feature = "serde-serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct Degrees<T: Num>(pub T);
+
+/// Unit of measurement: sampling period of discrete time systems \[s\]
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+#[cfg_attr(
+    feature = "serde-serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct SampleTime<T: Num>(pub T);
+
+/// Unit of measurement: natural frequency of a mode \[rad/s\]
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+#[cfg_attr(
+    feature = "serde-serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct NaturalFrequency<T: Num>(pub T);
+
+/// Damping ratio of a mode \[-\]
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+#[cfg_attr(
+    feature = "serde-serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct DampingRatio<T: Num>(pub T);
+
+/// Unit of measurement: normalized angular frequency of discrete time systems
+/// \[rad/sample\]
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+#[cfg_attr(
+    feature = "serde-serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct NormalizedFrequency<T: Num>(pub T);
+
+/// Macro to implement the logarithmic ratios between frequencies, in decades
+/// and octaves.
+///
+/// # Examples
+/// ```ignore
+/// impl_frequency_ratios!(Hertz);
+/// ```
+macro_rules! impl_frequency_ratios {
+    ($name:ident) => {
+        impl<T: Float> $name<T> {
+            /// Number of decades from this frequency to `other`, negative if
+            /// `other` is lower.
+            ///
+            /// # Arguments
+            ///
+            /// * `other` - Final frequency
+            #[must_use]
+            pub fn decades_to(self, other: Self) -> T {
+                (other.0 / self.0).log10()
+            }
+
+            /// Number of octaves from this frequency to `other`, negative if
+            /// `other` is lower.
+            ///
+            /// # Arguments
+            ///
+            /// * `other` - Final frequency
+            #[must_use]
+            pub fn octaves_to(self, other: Self) -> T {
+                (other.0 / self.0).log2()
+            }
+
+            /// Frequency shifted by `n` decades, towards lower frequencies if
+            /// `n` is negative.
+            ///
+            /// # Arguments
+            ///
+            /// * `n` - Number of decades
+            #[must_use]
+            pub fn shift_decades(self, n: T) -> Self {
+                Self(self.0 * T::from(10.).unwrap().powf(n))
+            }
+
+            /// Frequency shifted by `n` octaves, towards lower frequencies if
+            /// `n` is negative.
+            ///
+            /// # Arguments
+            ///
+            /// * `n` - Number of octaves
+            #[must_use]
+            pub fn shift_octaves(self, n: T) -> Self {
+                Self(self.0 * n.exp2())
+            }
+        }
+    };
+}
+
 impl_display!(Decibel);
 impl_display!(Seconds);
 impl_display!(Hertz);
 impl_display!(RadiansPerSecond);
+impl_display!(Radians);
+impl_display!(Degrees);
+impl_display!(SampleTime);
+impl_display!(NaturalFrequency);
+impl_display!(DampingRatio);
+impl_display!(NormalizedFrequency);
+
+impl_frequency_ratios!(Hertz);
+impl_frequency_ratios!(RadiansPerSecond);
 
 impl<T: Num + FloatConst> From<Hertz<T>> for RadiansPerSecond<T> {
     /// Convert Hertz into radians per second.
@@ -105,6 +306,189 @@ impl<T: Num + FloatConst> From<RadiansPerSecond<T>> for Hertz<T> {
     }
 }
 
+impl<T: Float> From<Radians<T>> for Degrees<T> {
+    /// Convert radians into degrees.
+    fn from(rad: Radians<T>) -> Self {
+        Self(rad.0.to_degrees())
+    }
+}
+
+impl<T: Float> From<Degrees<T>> for Radians<T> {
+    /// Convert degrees into radians.
+    fn from(deg: Degrees<T>) -> Self {
+        Self(deg.0.to_radians())
+    }
+}
+
+impl<T: Num> From<RadiansPerSecond<T>> for NaturalFrequency<T> {
+    /// Convert radians per second into a natural frequency.
+    fn from(rps: RadiansPerSecond<T>) -> Self {
+        Self(rps.0)
+    }
+}
+
+impl<T: Num> From<NaturalFrequency<T>> for RadiansPerSecond<T> {
+    /// Convert a natural frequency into radians per second.
+    fn from(wn: NaturalFrequency<T>) -> Self {
+        Self(wn.0)
+    }
+}
+
+impl<T: Num> From<Seconds<T>> for SampleTime<T> {
+    /// Convert seconds into a sampling period.
+    fn from(s: Seconds<T>) -> Self {
+        Self(s.0)
+    }
+}
+
+impl<T: Num> From<SampleTime<T>> for Seconds<T> {
+    /// Convert a sampling period into seconds.
+    fn from(st: SampleTime<T>) -> Self {
+        Self(st.0)
+    }
+}
+
+impl From<Duration> for Seconds<f64> {
+    /// Convert a duration into seconds.
+    fn from(d: Duration) -> Self {
+        Self(d.as_secs_f64())
+    }
+}
+
+impl<T: Float> TryFrom<Seconds<T>> for Duration {
+    type Error = Error;
+
+    /// Convert seconds into a duration, with nanosecond resolution.
+    ///
+    /// # Errors
+    ///
+    /// It returns an error if the time is negative, not finite or it exceeds
+    /// the maximum duration.
+    fn try_from(s: Seconds<T>) -> Result<Self, Self::Error> {
+        match s.0.to_f64() {
+            #[allow(clippy::cast_precision_loss)]
+            Some(secs) if secs >= 0. && secs < u64::MAX as f64 => Ok(Duration::from_secs_f64(secs)),
+            _ => Err(Error::new_internal(ErrorKind::InvalidDuration)),
+        }
+    }
+}
+
+impl<T: Float + FloatConst> SampleTime<T> {
+    /// Nyquist angular frequency `pi/Ts` of the sampling period
+    ///
+    /// # Example
+    /// ```
+    /// use au::{units::SampleTime, RadiansPerSecond};
+    /// assert_eq!(RadiansPerSecond(std::f64::consts::PI), SampleTime(1.).nyquist());
+    /// ```
+    #[must_use]
+    pub fn nyquist(&self) -> RadiansPerSecond<T> {
+        RadiansPerSecond(T::PI() / self.0)
+    }
+}
+
+impl<T: Float + FloatConst> NormalizedFrequency<T> {
+    /// Create a normalized frequency from cycles per sample.
+    ///
+    /// # Arguments
+    ///
+    /// * `cycles` - Frequency in cycles per sample
+    ///
+    /// # Example
+    /// ```
+    /// use au::units::NormalizedFrequency;
+    /// let nyquist = NormalizedFrequency::from_cycles_per_sample(0.5);
+    /// assert_eq!(NormalizedFrequency(std::f64::consts::PI), nyquist);
+    /// ```
+    #[must_use]
+    pub fn from_cycles_per_sample(cycles: T) -> Self {
+        Self(T::TAU() * cycles)
+    }
+
+    /// Get the frequency in cycles per sample
+    #[must_use]
+    pub fn cycles_per_sample(self) -> T {
+        self.0 / T::TAU()
+    }
+
+    /// Normalize an angular frequency with the sampling period, `omega*Ts`.
+    ///
+    /// # Arguments
+    ///
+    /// * `omega` - Angular frequency
+    /// * `sample_time` - Sampling period
+    ///
+    /// # Example
+    /// ```
+    /// use au::{units::NormalizedFrequency, RadiansPerSecond, SampleTime};
+    /// let theta = NormalizedFrequency::from_radians_per_second(RadiansPerSecond(5.), SampleTime(0.1));
+    /// assert_eq!(NormalizedFrequency(0.5), theta);
+    /// ```
+    #[must_use]
+    pub fn from_radians_per_second(omega: RadiansPerSecond<T>, sample_time: SampleTime<T>) -> Self {
+        Self(omega.0 * sample_time.0)
+    }
+
+    /// Normalize a frequency with the sampling period, `2*pi*f*Ts`.
+    ///
+    /// # Arguments
+    ///
+    /// * `freq` - Frequency
+    /// * `sample_time` - Sampling period
+    #[must_use]
+    pub fn from_hertz(freq: Hertz<T>, sample_time: SampleTime<T>) -> Self {
+        Self::from_radians_per_second(freq.into(), sample_time)
+    }
+
+    /// Angular frequency corresponding to the normalized frequency with the
+    /// given sampling period, `theta/Ts`.
+    ///
+    /// # Arguments
+    ///
+    /// * `sample_time` - Sampling period
+    #[must_use]
+    pub fn to_radians_per_second(self, sample_time: SampleTime<T>) -> RadiansPerSecond<T> {
+        RadiansPerSecond(self.0 / sample_time.0)
+    }
+
+    /// Frequency corresponding to the normalized frequency with the given
+    /// sampling period, `theta/(2*pi*Ts)`.
+    ///
+    /// # Arguments
+    ///
+    /// * `sample_time` - Sampling period
+    #[must_use]
+    pub fn to_hertz(self, sample_time: SampleTime<T>) -> Hertz<T> {
+        self.to_radians_per_second(sample_time).into()
+    }
+}
+
+/// Check if two sampling periods are equal, within a relative tolerance that
+/// absorbs the rounding errors of their calculation.
+pub(crate) fn same_sample_time<T: Float>(a: T, b: T) -> bool {
+    (a - b).abs() <= T::epsilon().sqrt() * a.abs().max(b.abs())
+}
+
+/// Sampling period of the interconnection of two systems, the systems shall
+/// have the same sampling period when both of them are known.
+///
+/// # Panics
+///
+/// Panics if the sampling periods are different.
+pub(crate) fn joint_sample_time<T: Float>(a: Option<T>, b: Option<T>) -> Option<T> {
+    match (a, b) {
+        (Some(x), Some(y)) => {
+            assert!(
+                same_sample_time(x, y),
+                "Systems must have the same sampling period"
+            );
+            Some(x)
+        }
+        (x, None) => x,
+        (None, y) => y,
+    }
+}
+
 impl<T: Inv<Output = T> + Num> Inv for Seconds<T> {
     type Output = Hertz<T>;
 
@@ -134,6 +518,36 @@ mod tests {
         assert_relative_eq!(-3.0103, 2_f64.inv().sqrt().to_db(), max_relative = 1e5);
 
         assert_abs_diff_eq!(0., 1_f32.to_db(), epsilon = 0.);
+        assert_relative_eq!(20., 100_f64.to_db_power());
+        assert_relative_eq!(-10., 0.1_f32.to_db_power());
+    }
+
+    #[test]
+    fn from_decibel() {
+        assert_relative_eq!(100., 40_f64.from_db());
+        assert_relative_eq!(0.1, (-10_f64).from_db_power());
+        assert_relative_eq!(1., 0_f32.from_db());
+        assert_relative_eq!(1000., 30_f32.from_db_power(), max_relative = 1e-6);
+    }
+
+    #[test]
+    fn decibel_only_required_method() {
+        // Implementations of the trait outside of the crate only define `to_db`.
+        struct Ratio(f64);
+        impl ToDecibel for Ratio {
+            fn to_db(&self) -> Self {
+                Self(self.0.to_db())
+            }
+        }
+        assert_relative_eq!(20., Ratio(10.).to_db().0);
+    }
+
+    proptest! {
+        #[test]
+        fn qc_decibel_round_trip(x in (1e-6..1e6_f64)) {
+            assert_relative_eq!(x, x.to_db().from_db(), max_relative = 1e-12);
+            assert_relative_eq!(x, x.to_db_power().from_db_power(), max_relative = 1e-12);
+        }
     }
 
     #[test]
@@ -148,6 +562,100 @@ mod tests {
         assert_eq!(rps, RadiansPerSecond::from(Hertz::from(rps)));
     }
 
+    #[test]
+    fn sample_time() {
+        let st = SampleTime::from(Seconds(0.5));
+        assert_eq!(Seconds(0.5), st.into());
+        assert_relative_eq!(2. * std::f64::consts::PI, st.nyquist().0);
+        assert_eq!(Some(0.5), joint_sample_time(Some(0.5), None));
+        assert_eq!(Some(0.5), joint_sample_time(None, Some(0.5)));
+        assert_eq!(None, joint_sample_time::<f64>(None, None));
+    }
+
+    #[test]
+    fn rounded_sample_times() {
+        let st = joint_sample_time(Some(0.1 + 0.2), Some(0.3)).unwrap();
+        assert_relative_eq!(0.3, st);
+        assert!(same_sample_time(0.1_f32 * 3., 0.3));
+        assert!(!same_sample_time(0.1, 0.1001));
+    }
+
+    #[test]
+    #[should_panic]
+    fn different_sample_times() {
+        let _ = joint_sample_time(Some(0.1), Some(0.2));
+    }
+
+    #[test]
+    fn duration() {
+        let s: Seconds<f64> = Duration::from_millis(1500).into();
+        assert_eq!(Seconds(1.5), s);
+        assert_eq!(
+            Duration::from_micros(2250),
+            Duration::try_from(Seconds(0.00225)).unwrap()
+        );
+        assert_eq!(
+            Duration::from_millis(250),
+            Duration::try_from(Seconds(0.25_f32)).unwrap()
+        );
+        for &t in &[-1., f64::NAN, f64::INFINITY, 1e20] {
+            let err = Duration::try_from(Seconds(t)).unwrap_err();
+            assert_eq!(ErrorKind::InvalidDuration, err.kind());
+        }
+    }
+
+    #[test]
+    fn frequency_ratios() {
+        let w = RadiansPerSecond(0.5);
+        assert_relative_eq!(3., w.decades_to(RadiansPerSecond(500.)));
+        assert_relative_eq!(-1., w.octaves_to(RadiansPerSecond(0.25)));
+        assert_relative_eq!(50., w.shift_decades(2.).0);
+        assert_relative_eq!(4., w.shift_octaves(3.).0);
+        let f = Hertz(10.);
+        assert_relative_eq!(0.5, f.decades_to(f.shift_decades(0.5)));
+        assert_relative_eq!(-2.5, f.octaves_to(f.shift_octaves(-2.5)));
+        // The ratios do not depend on the unit.
+        let rps: RadiansPerSecond<f64> = f.into();
+        assert_relative_eq!(f.decades_to(Hertz(1.)), rps.decades_to(Hertz(1.).into()));
+    }
+
+    #[test]
+    fn normalized_frequency() {
+        let st = SampleTime(0.01);
+        let theta = NormalizedFrequency::from_hertz(Hertz(25.), st);
+        assert_relative_eq!(std::f64::consts::FRAC_PI_2, theta.0);
+        assert_relative_eq!(0.25, theta.cycles_per_sample());
+        assert_relative_eq!(25., theta.to_hertz(st).0);
+        assert_relative_eq!(
+            50. * std::f64::consts::PI,
+            theta.to_radians_per_second(st).0
+        );
+        assert_eq!(
+            st.nyquist(),
+            NormalizedFrequency::from_cycles_per_sample(0.5).to_radians_per_second(st)
+        );
+    }
+
+    #[test]
+    fn natural_frequency() {
+        let wn = NaturalFrequency::from(RadiansPerSecond(3.));
+        assert_eq!(NaturalFrequency(3.), wn);
+        assert_eq!(RadiansPerSecond(3.), wn.into());
+        assert_eq!("0.70".to_owned(), format!("{:.2}", DampingRatio(0.7)));
+    }
+
+    #[test]
+    fn angle_conversion() {
+        assert_relative_eq!(180., Degrees::from(Radians(std::f64::consts::PI)).0);
+        assert_relative_eq!(
+            -std::f32::consts::FRAC_PI_2,
+            Radians::from(Degrees(-90_f32)).0
+        );
+        let deg = Degrees(30.);
+        assert_relative_eq!(deg.0, Degrees::from(Radians::from(deg)).0);
+        assert_eq!("45.0".to_owned(), format!("{:.1}", Degrees(45.)));
+    }
+
     proptest! {
         #[test]
         fn qc_conversion_hertz(hz in (0.0..1e12)) {
@@ -0,0 +1,153 @@
+//! # Utilities
+//!
+//! Generation of the grids of times and frequencies used by simulations and
+//! plots
+//! * linearly spaced times
+//! * logarithmically spaced angular frequencies
+
+use num_traits::Float;
+
+use crate::units::{RadiansPerSecond, Seconds};
+
+/// Linearly spaced times between `start` and `end`, both included.
+///
+/// # Arguments
+///
+/// * `start` - First time
+/// * `end` - Last time
+/// * `n` - Number of points
+///
+/// A single point is the start time, no points give an empty iterator.
+///
+/// # Example
+/// ```
+/// use au::{utils::linspace, Seconds};
+/// let t: Vec<_> = linspace(Seconds(0.), Seconds(1.), 5).collect();
+/// assert_eq!(vec![Seconds(0.), Seconds(0.25), Seconds(0.5), Seconds(0.75), Seconds(1.)], t);
+/// ```
+pub fn linspace<T: Float>(
+    start: Seconds<T>,
+    end: Seconds<T>,
+    n: usize,
+) -> impl Iterator<Item = Seconds<T>> {
+    let intervals = T::from(n.saturating_sub(1).max(1)).unwrap();
+    let step = (end.0 - start.0) / intervals;
+    (0..n).map(move |i| {
+        if i + 1 == n && n > 1 {
+            // Avoid the rounding error on the last point.
+            end
+        } else {
+            Seconds(start.0 + T::from(i).unwrap() * step)
+        }
+    })
+}
+
+/// Logarithmically spaced angular frequencies from `10^decade_start` to
+/// `10^decade_end`, both included.
+///
+/// The number of intervals is the number of decades times the points per
+/// decade, rounded to the nearest integer (at least one when the decades
+/// differ), and the frequencies are evenly spaced on the logarithmic scale.
+///
+/// # Arguments
+///
+/// * `decade_start` - Exponent of the first angular frequency
+/// * `decade_end` - Exponent of the last angular frequency
+/// * `points_per_decade` - Number of points in each decade
+///
+/// # Panics
+///
+/// Panics if the number of points per decade is zero or the last decade is
+/// lower than the first one.
+///
+/// # Example
+/// ```
+/// use au::{plots::bode::Bode, poly, utils::logspace, RadiansPerSecond, Tf};
+/// let w: Vec<_> = logspace(-1., 2., 1).collect();
+/// assert_eq!(4, w.len());
+/// assert_eq!(RadiansPerSecond(100.), w[3]);
+///
+/// let tf = Tf::new(poly!(1.), poly!(1., 1.));
+/// let bode = Bode::new_from_frequencies(tf, logspace(-1., 1., 10));
+/// assert_eq!(21, bode.into_iter().count());
+/// ```
+pub fn logspace<T: Float>(
+    decade_start: T,
+    decade_end: T,
+    points_per_decade: usize,
+) -> impl Iterator<Item = RadiansPerSecond<T>> {
+    assert!(points_per_decade > 0, "Points per decade must be positive");
+    assert!(
+        decade_end >= decade_start,
+        "Last decade must not be lower than the first one"
+    );
+    let ppd = T::from(points_per_decade).unwrap();
+    let span = decade_end - decade_start;
+    let intervals = (span * ppd).round().to_usize().unwrap();
+    let intervals = if span > T::zero() {
+        intervals.max(1)
+    } else {
+        intervals
+    };
+    let step = span / T::from(intervals.max(1)).unwrap();
+    let ten = T::from(10.).unwrap();
+    (0..=intervals).map(move |i| {
+        let exponent = if i == intervals {
+            decade_end
+        } else {
+            decade_start + T::from(i).unwrap() * step
+        };
+        RadiansPerSecond(ten.powf(exponent))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn linear_times() {
+        let t: Vec<_> = linspace(Seconds(1.), Seconds(3.), 3).collect();
+        assert_eq!(vec![Seconds(1.), Seconds(2.), Seconds(3.)], t);
+        assert_eq!(
+            vec![Seconds(2.)],
+            linspace(Seconds(2.), Seconds(5.), 1).collect::<Vec<_>>()
+        );
+        assert_eq!(0, linspace(Seconds(0.), Seconds(1.), 0).count());
+        let t: Vec<_> = linspace(Seconds(0.), Seconds(0.3), 4).collect();
+        assert_eq!(Seconds(0.3), t[3]);
+    }
+
+    #[test]
+    fn logarithmic_frequencies() {
+        let w: Vec<_> = logspace(0., 2., 2).collect();
+        assert_eq!(5, w.len());
+        assert_relative_eq!(1., w[0].0);
+        assert_relative_eq!(10_f64.sqrt(), w[1].0);
+        assert_relative_eq!(10., w[2].0);
+        assert_eq!(RadiansPerSecond(100.), w[4]);
+        assert_eq!(1, logspace(1., 1., 10).count());
+    }
+
+    #[test]
+    fn logarithmic_frequencies_uneven_decades() {
+        // 2.6 intervals are rounded to 3 evenly spaced ones.
+        let w: Vec<_> = logspace(0., 1.3, 2).collect();
+        assert_eq!(4, w.len());
+        let ratios: Vec<_> = w.windows(2).map(|w| w[1].0 / w[0].0).collect();
+        for r in &ratios {
+            assert_relative_eq!(10_f64.powf(1.3 / 3.), r, max_relative = 1e-12);
+        }
+        assert_eq!(RadiansPerSecond(10_f64.powf(1.3)), w[3]);
+
+        // Less than one interval.
+        let w: Vec<_> = logspace(0., 0.1, 1).collect();
+        assert_eq!(2, w.len());
+    }
+
+    #[test]
+    #[should_panic]
+    fn logspace_wrong_decades() {
+        let _ = logspace(2., 1., 10);
+    }
+}
@@ -1,7 +1,7 @@
 #[macro_use]
 extern crate approx;
 
-use au::{poly, signals::continuous, Poly, Seconds, Ss, Tf};
+use au::{error::ErrorKind, poly, signals::continuous, Poly, Seconds, Ss, Tf};
 
 /// TC3.1
 #[test]
@@ -42,7 +42,7 @@ fn no_equilibrium() {
     let d = [0., 0.];
     let sys = Ss::new_from_slice(2, 2, 1, &a, &b, &c, &d);
     let no_eq = sys.equilibrium(&[1., 1.]);
-    assert!(no_eq.is_none());
+    assert_eq!(ErrorKind::NoEquilibrium, no_eq.unwrap_err().kind());
 }
 
 /// TC3.4